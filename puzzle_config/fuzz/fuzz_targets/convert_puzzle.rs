@@ -0,0 +1,28 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use puzzle_config::{JsonLoader, create_json_loader};
+
+thread_local! {
+    static LOADER: JsonLoader = create_json_loader(
+        include_str!("../../../puzzled/resources/predefined.json"),
+        "1.2.1",
+    )
+    .unwrap();
+}
+
+// The input is used as the puzzle and the custom tiles and boards of an otherwise valid
+// collection, so most inputs get past the collection fields and reach the converter.
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    let (puzzle, custom) = input.split_once('\n').unwrap_or((input, "{}"));
+    let json_str = format!(
+        r#"{{"format": 2, "name": "Fuzz", "author": "Fuzz", "id": "de.til7701.Fuzz",
+            "custom_tiles": {custom}, "custom_boards": {custom}, "puzzles": [{puzzle}]}}"#
+    );
+    LOADER.with(|loader| {
+        let _ = loader.load_puzzle_collection(&json_str);
+    });
+});
@@ -0,0 +1,21 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use puzzle_config::{JsonLoader, create_json_loader};
+
+thread_local! {
+    static LOADER: JsonLoader = create_json_loader(
+        include_str!("../../../puzzled/resources/predefined.json"),
+        "1.2.1",
+    )
+    .unwrap();
+}
+
+// Loading any input must either succeed or return a `ReadError`.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(json_str) = std::str::from_utf8(data) {
+        LOADER.with(|loader| {
+            let _ = loader.load_puzzle_collection(json_str);
+        });
+    }
+});
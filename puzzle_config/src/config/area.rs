@@ -1,7 +1,9 @@
+use serde::Serialize;
+
 /// Metadata for an area on the board.
 /// Includes the name and the formatter for the area values.
 /// This is used by the target selection UI.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
 pub struct AreaConfig {
     name: String,
     formatter: AreaValueFormatter,
@@ -35,7 +37,8 @@ impl AreaConfig {
 }
 
 /// Formatter for a value for an area to display on the target selection button.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum AreaValueFormatter {
     /// Displays the value as is.
     Plain,
@@ -43,4 +46,35 @@ pub enum AreaValueFormatter {
     Nth,
     /// Formats the value with a prefix and suffix.
     PrefixSuffix { prefix: String, suffix: String },
+    /// Formats a number as Roman numerals (1 -> I, 4 -> IV, 12 -> XII, etc.).
+    /// Values, which are not a number between 1 and 3999, are displayed as is.
+    Roman,
+    /// Formats a month as its full name.
+    /// The value can be the number of the month (1-12) or its short English name ("Jan", "Feb",
+    /// etc.). The names contain one entry per month starting with January. Values, which are not
+    /// a month, are displayed as is.
+    LocalizedMonth { names: Vec<String> },
+    /// Pads a number with leading zeros to the given width (7 -> 07 for a width of 2).
+    /// Values, which are not a number, are displayed as is.
+    ZeroPadded { width: usize },
 }
+
+/// The largest width of the zero padded formatter.
+/// The formatted numbers have at most 20 digits, so wider padding is never needed.
+pub const MAX_PADDED_WIDTH: usize = 20;
+
+/// The full English names of the months starting with January.
+pub(crate) const ENGLISH_MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
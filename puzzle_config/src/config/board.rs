@@ -1,23 +1,36 @@
 use crate::config::area::AreaConfig;
-use crate::{Target, TargetIndex, TargetTemplate};
-use ndarray::Array2;
+use crate::config::serialize;
+use crate::{AreaTarget, Target, TargetIndex, TargetTemplate};
+use ndarray::{Array2, Axis};
 use puzzled_common::Shape;
 use puzzled_common::ShapeType::Square;
+use rand::{Rng, RngExt};
+use serde::Serialize;
 use std::hash::{Hash, Hasher};
 
 /// Configuration for the board layout and areas.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 pub enum BoardConfig {
     Simple {
+        #[serde(serialize_with = "serialize::shape")]
         layout: Shape,
     },
     Area {
+        #[serde(serialize_with = "serialize::shape")]
         layout: Box<Shape>,
+        #[serde(serialize_with = "serialize::array")]
         area_indices: Box<Array2<i32>>,
+        #[serde(serialize_with = "serialize::array")]
         display_values: Box<Array2<String>>,
+        #[serde(serialize_with = "serialize::array")]
         value_order: Box<Array2<i32>>,
         area_configs: Vec<AreaConfig>,
         target_template: TargetTemplate,
+        /// Whether the names of the areas are shown beside the board.
+        show_legend: bool,
+        /// Whether thicker lines are drawn between cells of different areas.
+        show_area_boundaries: bool,
     },
 }
 
@@ -39,7 +52,7 @@ impl BoardConfig {
                         display_values,
                         area_indices,
                     ) {
-                        indices.push(target_index);
+                        indices.push(AreaTarget::Index(target_index));
                     }
                 }
                 Some(Target { indices })
@@ -47,6 +60,90 @@ impl BoardConfig {
         }
     }
 
+    /// Returns a random target, which contains one randomly chosen value for each area.
+    ///
+    /// The target is not checked for solvability. Returns `None` for simple boards or if any
+    /// area does not contain a value.
+    ///
+    /// # Arguments
+    ///
+    /// * `rng`: The random number generator to use.
+    ///
+    /// returns: Option<Target>
+    pub fn random_target(&self, rng: &mut dyn Rng) -> Option<Target> {
+        if let BoardConfig::Simple { .. } = self {
+            return None;
+        }
+        let mut indices = Vec::new();
+        for area_index in 0..self.area_count() {
+            let mut values = self.get_display_values_for_area(area_index as i32);
+            if values.is_empty() {
+                return None;
+            }
+            let (_, target_index) = values.swap_remove(rng.random_range(0..values.len()));
+            indices.push(AreaTarget::Index(target_index));
+        }
+        Some(Target { indices })
+    }
+
+    /// Returns this board rotated clockwise by 90 degrees as it is shown in the UI.
+    /// The areas and display values keep their cells, so the same target can be expressed on
+    /// the rotated board using [Self::rotate_target_clockwise].
+    pub fn rotated_clockwise(&self) -> BoardConfig {
+        match self {
+            BoardConfig::Simple { layout } => {
+                let mut layout = layout.clone();
+                // The layout is drawn transposed, so counterclockwise is clockwise in the UI.
+                layout.rotate_counterclockwise();
+                BoardConfig::Simple { layout }
+            }
+            BoardConfig::Area {
+                layout,
+                area_indices,
+                display_values,
+                value_order,
+                area_configs,
+                target_template,
+                show_legend,
+                show_area_boundaries,
+            } => {
+                let mut layout = layout.clone();
+                layout.rotate_counterclockwise();
+                BoardConfig::Area {
+                    layout,
+                    area_indices: Box::new(rotate_array_clockwise(area_indices)),
+                    display_values: Box::new(rotate_array_clockwise(display_values)),
+                    value_order: Box::new(rotate_array_clockwise(value_order)),
+                    area_configs: area_configs.clone(),
+                    target_template: target_template.clone(),
+                    show_legend: *show_legend,
+                    show_area_boundaries: *show_area_boundaries,
+                }
+            }
+        }
+    }
+
+    /// Returns the index on the board returned by [Self::rotated_clockwise] of the cell with the
+    /// given index on this board.
+    pub fn rotate_index_clockwise(&self, index: &TargetIndex) -> TargetIndex {
+        let height = self.layout().dim().1;
+        TargetIndex(height - 1 - index.1, index.0)
+    }
+
+    /// Returns the given target of this board for the board returned by
+    /// [Self::rotated_clockwise].
+    pub fn rotate_target_clockwise(&self, target: &Target) -> Target {
+        let indices = target
+            .indices
+            .iter()
+            .map(|area_target| match area_target {
+                AreaTarget::Index(index) => AreaTarget::Index(self.rotate_index_clockwise(index)),
+                AreaTarget::Any => AreaTarget::Any,
+            })
+            .collect();
+        Target { indices }
+    }
+
     fn find_index_for_value_in_area(
         board_value: &str,
         area_index: i32,
@@ -161,18 +258,28 @@ impl Hash for BoardConfig {
     }
 }
 
+fn rotate_array_clockwise<T: Clone>(array: &Array2<T>) -> Array2<T> {
+    let mut rotated = array.clone().reversed_axes();
+    rotated.invert_axis(Axis(0));
+    rotated
+}
+
 pub fn from_predefined_board(name: &str) -> Option<BoardConfig> {
-    let dim: Option<(i32, i32)> = name
-        .split("x")
-        .filter_map(|part| part.parse::<i32>().ok())
-        .collect::<Vec<i32>>()
-        .get(0..2)
-        .map(|dims| (dims[0], dims[1]));
-    dim.map(|(rows, cols)| BoardConfig::Simple {
-        layout: Shape::from_elem((rows as usize, cols as usize), Square, true),
+    predefined_board_size(name).map(|(rows, cols)| BoardConfig::Simple {
+        layout: Shape::from_elem((rows, cols), Square, true),
     })
 }
 
+/// Returns the size of the predefined board with the given name like `5x4`, without creating
+/// the board.
+pub fn predefined_board_size(name: &str) -> Option<(usize, usize)> {
+    name.split("x")
+        .filter_map(|part| part.parse::<usize>().ok())
+        .collect::<Vec<usize>>()
+        .get(0..2)
+        .map(|dims| (dims[0], dims[1]))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -180,9 +287,10 @@ mod tests {
     use crate::config::target::{TargetIndex, TargetTemplate};
     use ndarray::arr2;
     use puzzled_common::shape::shape_square;
+    use rand::rngs::Xoshiro256PlusPlus;
+    use rand::SeedableRng;
 
-    #[test]
-    fn test_puzzle_config_get_display_values_for_area() {
+    fn create_area_board_config() -> BoardConfig {
         let board_layout =
             shape_square(&[[true, true, false], [true, true, true], [false, true, true]]);
         let area_indices = arr2(&[[0, 0, -1], [0, 1, 1], [-1, 1, 1]]);
@@ -205,14 +313,21 @@ mod tests {
             ),
         ];
 
-        let board_config = BoardConfig::Area {
+        BoardConfig::Area {
             layout: Box::new(board_layout),
             area_indices: Box::new(area_indices),
             display_values: Box::new(display_values),
             value_order: Box::new(value_order),
             area_configs,
             target_template: TargetTemplate::new("{0}, {1}, {2}"),
-        };
+            show_legend: false,
+            show_area_boundaries: false,
+        }
+    }
+
+    #[test]
+    fn test_puzzle_config_get_display_values_for_area() {
+        let board_config = create_area_board_config();
 
         let area_0_values = board_config.get_display_values_for_area(0);
         assert_eq!(
@@ -235,4 +350,79 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_random_target() {
+        let board_config = create_area_board_config();
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(42);
+
+        for _ in 0..20 {
+            let target = board_config.random_target(&mut rng).unwrap();
+            assert_eq!(target.indices.len(), 2);
+            let area_0_values = board_config.get_display_values_for_area(0);
+            let area_1_values = board_config.get_display_values_for_area(1);
+            assert!(area_0_values
+                .iter()
+                .any(|(_, i)| Some(i) == target.indices[0].index()));
+            assert!(area_1_values
+                .iter()
+                .any(|(_, i)| Some(i) == target.indices[1].index()));
+        }
+    }
+
+    #[test]
+    fn test_random_target_simple_board() {
+        let board_config = from_predefined_board("3x3").unwrap();
+        let mut rng = Xoshiro256PlusPlus::seed_from_u64(42);
+
+        assert_eq!(board_config.random_target(&mut rng), None);
+    }
+
+    #[test]
+    fn test_rotated_clockwise() {
+        let board_config = create_area_board_config();
+
+        let rotated = board_config.rotated_clockwise();
+
+        assert_eq!(
+            rotated.layout(),
+            &shape_square(&[[false, true, true], [true, true, true], [true, true, false]])
+        );
+        let BoardConfig::Area { display_values, .. } = &board_config else {
+            panic!("Expected an area board");
+        };
+        let BoardConfig::Area {
+            display_values: rotated_display_values,
+            ..
+        } = &rotated
+        else {
+            panic!("Expected an area board");
+        };
+        for ((x, y), value) in display_values.indexed_iter() {
+            let TargetIndex(rotated_x, rotated_y) =
+                board_config.rotate_index_clockwise(&TargetIndex(x, y));
+            assert_eq!(&rotated_display_values[[rotated_x, rotated_y]], value);
+        }
+        assert_eq!(
+            rotated
+                .rotated_clockwise()
+                .rotated_clockwise()
+                .rotated_clockwise(),
+            board_config
+        );
+    }
+
+    #[test]
+    fn test_rotate_target_clockwise() {
+        let board_config = create_area_board_config();
+        let target = board_config.default_target().unwrap();
+
+        let rotated = board_config.rotated_clockwise();
+        let rotated_target = board_config.rotate_target_clockwise(&target);
+
+        assert_eq!(
+            rotated.format_target(&rotated_target),
+            board_config.format_target(&target)
+        );
+    }
 }
@@ -1,8 +1,12 @@
+use crate::config::color::ColorConfig;
+use crate::config::difficulty;
+use crate::config::integrity::IntegrityConfig;
 use crate::config::preview::PreviewConfig;
 use crate::config::progression::ProgressionConfig;
 use crate::{PuzzleConfig, PuzzleDifficultyConfig};
+use serde::Serialize;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PuzzleConfigCollection {
     name: String,
     description: Option<String>,
@@ -11,7 +15,11 @@ pub struct PuzzleConfigCollection {
     version: Option<String>,
     progression: ProgressionConfig,
     preview: PreviewConfig,
+    palette: Vec<ColorConfig>,
+    allow_board_rotation: bool,
+    music: Option<String>,
     puzzles: Vec<PuzzleConfig>,
+    integrity: IntegrityConfig,
 }
 
 impl PuzzleConfigCollection {
@@ -24,6 +32,9 @@ impl PuzzleConfigCollection {
         version: Option<String>,
         progression: ProgressionConfig,
         preview: PreviewConfig,
+        palette: Vec<ColorConfig>,
+        allow_board_rotation: bool,
+        music: Option<String>,
         puzzles: Vec<PuzzleConfig>,
     ) -> PuzzleConfigCollection {
         PuzzleConfigCollection {
@@ -34,7 +45,11 @@ impl PuzzleConfigCollection {
             version,
             progression,
             preview,
+            palette,
+            allow_board_rotation,
+            music,
             puzzles,
+            integrity: IntegrityConfig::default(),
         }
     }
 
@@ -66,27 +81,39 @@ impl PuzzleConfigCollection {
         &self.preview
     }
 
-    pub fn average_difficulty(&self) -> Option<PuzzleDifficultyConfig> {
-        let puzzles_with_difficulty: Vec<&PuzzleDifficultyConfig> = self
-            .puzzles
-            .iter()
-            .flat_map(|puzzle| puzzle.difficulty())
-            .collect();
-
-        if puzzles_with_difficulty.is_empty() {
-            return None;
-        }
+    /// The colors used for tiles without an explicit color.
+    /// Empty if the collection does not define a palette and the default colors are used.
+    pub fn palette(&self) -> &[ColorConfig] {
+        &self.palette
+    }
 
-        let total_difficulty: u32 = puzzles_with_difficulty
-            .iter()
-            .map(|difficulty| **difficulty as u32)
-            .sum();
+    /// Whether the boards of this collection may be rotated.
+    /// If true, the player may also rotate the board while solving a puzzle.
+    pub fn allow_board_rotation(&self) -> bool {
+        self.allow_board_rotation
+    }
 
-        let average_difficulty = total_difficulty as f32 / puzzles_with_difficulty.len() as f32;
-        Some(average_difficulty.into())
+    /// The URI of the background music played while a puzzle of this collection is shown.
+    /// Only `https` and `resource` URIs are allowed, so the music is either downloaded or bundled
+    /// with the app.
+    pub fn music(&self) -> &Option<String> {
+        &self.music
+    }
+
+    pub fn average_difficulty(&self) -> Option<PuzzleDifficultyConfig> {
+        difficulty::average(self.puzzles.iter().flat_map(|puzzle| puzzle.difficulty()))
     }
 
     pub fn puzzles(&self) -> &Vec<PuzzleConfig> {
         &self.puzzles
     }
+
+    /// How far the content of the collection could be verified when it was loaded.
+    pub fn integrity(&self) -> &IntegrityConfig {
+        &self.integrity
+    }
+
+    pub(crate) fn set_integrity(&mut self, integrity: IntegrityConfig) {
+        self.integrity = integrity;
+    }
 }
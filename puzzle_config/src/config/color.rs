@@ -1,3 +1,5 @@
+use serde::{Serialize, Serializer};
+
 const COLORS: [ColorConfig; 35] = [
     ColorConfig::from_rgb_hex(0x1c71d8), // Blue 4
     ColorConfig::from_rgb_hex(0x2ec27e), // Green 4
@@ -36,7 +38,7 @@ const COLORS: [ColorConfig; 35] = [
     ColorConfig::from_rgb_hex(0xf6d32d), // Yellow 3
 ];
 
-#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
 pub struct ColorConfig {
     red: u8,
     green: u8,
@@ -106,6 +108,58 @@ impl ColorConfig {
     pub const fn alpha(&self) -> u8 {
         self.alpha
     }
+
+    /// Calculates the perceived difference between this color and the given one as the CIE76
+    /// Delta-E. A difference of about 2.3 is just noticeable. The alpha channel is ignored.
+    ///
+    /// # Arguments
+    ///
+    /// * `other`: The color to compare to.
+    ///
+    /// returns: f32 The Delta-E, which is 0 for identical colors.
+    pub fn delta_e(&self, other: &ColorConfig) -> f32 {
+        let (l1, a1, b1) = self.to_lab();
+        let (l2, a2, b2) = other.to_lab();
+        ((l1 - l2).powi(2) + (a1 - a2).powi(2) + (b1 - b2).powi(2)).sqrt()
+    }
+
+    /// Converts the sRGB color to the CIELAB color space with the D65 white point.
+    fn to_lab(self) -> (f32, f32, f32) {
+        fn linear(channel: u8) -> f32 {
+            let channel = channel as f32 / 255.0;
+            if channel <= 0.04045 {
+                channel / 12.92
+            } else {
+                ((channel + 0.055) / 1.055).powf(2.4)
+            }
+        }
+        fn lab_f(t: f32) -> f32 {
+            if t > 0.008856 {
+                t.cbrt()
+            } else {
+                7.787 * t + 16.0 / 116.0
+            }
+        }
+
+        let (r, g, b) = (linear(self.red), linear(self.green), linear(self.blue));
+        let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+        let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+        let (fx, fy, fz) = (lab_f(x), lab_f(y), lab_f(z));
+        (116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz))
+    }
+}
+
+/// Serializes the color as a hex string like in the collection format.
+/// The alpha component is only included, if the color is not fully opaque.
+impl Serialize for ColorConfig {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut hex = format!("#{:02x}{:02x}{:02x}", self.red, self.green, self.blue);
+        if self.alpha != 255 {
+            hex.push_str(&format!("{:02x}", self.alpha));
+        }
+        serializer.serialize_str(&hex)
+    }
 }
 
 impl TryFrom<String> for ColorConfig {
@@ -173,4 +227,16 @@ mod tests {
         let invalid_hex_length = ColorConfig::try_from("#1c71d".to_string());
         assert!(invalid_hex_length.is_err());
     }
+
+    #[test]
+    fn test_delta_e() {
+        let blue = ColorConfig::from_rgb_hex(0x1c71d8);
+        let similar_blue = ColorConfig::from_rgb_hex(0x1d72d8);
+        let white = ColorConfig::from_rgb_hex(0xffffff);
+        let black = ColorConfig::from_rgb_hex(0x000000);
+
+        assert_eq!(blue.delta_e(&blue), 0.0);
+        assert!(blue.delta_e(&similar_blue) < 1.0);
+        assert!((white.delta_e(&black) - 100.0).abs() < 0.1);
+    }
 }
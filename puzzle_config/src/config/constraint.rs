@@ -0,0 +1,103 @@
+use crate::config::board::BoardConfig;
+use crate::config::serialize;
+use crate::TargetIndex;
+use puzzle_solver::board::Board;
+use puzzle_solver::tile::Tile;
+use puzzled_common::Shape;
+use serde::Serialize;
+
+/// A cell of the board, which has to be covered by a specific tile.
+///
+/// If the puzzle contains multiple identical tiles, any of them may cover the cell.
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize)]
+pub struct TileConstraint {
+    /// The position of the cell on the board.
+    cell: (usize, usize),
+    /// The name the tile was referred to with in the config file.
+    tile_name: String,
+    /// The base of the tile.
+    #[serde(serialize_with = "serialize::shape")]
+    base: Shape,
+}
+
+impl TileConstraint {
+    /// Creates a new TileConstraint.
+    ///
+    /// # Arguments
+    ///
+    /// * `cell`: The position of the cell on the board.
+    /// * `tile_name`: The name of the tile, which has to cover the cell.
+    /// * `base`: The base of the tile.
+    ///
+    /// returns: TileConstraint
+    pub fn new(cell: (usize, usize), tile_name: String, base: Shape) -> TileConstraint {
+        TileConstraint {
+            cell,
+            tile_name,
+            base,
+        }
+    }
+
+    pub fn cell(&self) -> (usize, usize) {
+        self.cell
+    }
+
+    pub fn tile_name(&self) -> &str {
+        &self.tile_name
+    }
+
+    pub fn base(&self) -> &Shape {
+        &self.base
+    }
+
+    /// Returns this constraint for the board returned by [BoardConfig::rotated_clockwise].
+    ///
+    /// # Arguments
+    ///
+    /// * `board_config`: The board this constraint belongs to before the rotation.
+    ///
+    /// returns: TileConstraint
+    pub fn rotated_clockwise(&self, board_config: &BoardConfig) -> TileConstraint {
+        let TargetIndex(x, y) =
+            board_config.rotate_index_clockwise(&TargetIndex(self.cell.0, self.cell.1));
+        TileConstraint {
+            cell: (x, y),
+            ..self.clone()
+        }
+    }
+
+    /// Returns this constraint for the board transposed by
+    /// [Shape::rotate_to_landscape](puzzled_common::Shape::rotate_to_landscape).
+    pub(crate) fn transposed(&self) -> TileConstraint {
+        TileConstraint {
+            cell: (self.cell.1, self.cell.0),
+            ..self.clone()
+        }
+    }
+
+    /// Reserves the cell for the tile on the board given to the solver.
+    /// The positions of the board have to match the positions of the board config.
+    pub fn apply(&self, board: &mut Board) {
+        board.reserve_cell([self.cell.0, self.cell.1], &Tile::new(self.base.clone()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use puzzled_common::shape::shape_square;
+
+    #[test]
+    fn test_rotated_clockwise() {
+        let board_config = BoardConfig::Simple {
+            layout: shape_square(&[[true, true, true], [true, true, true]]),
+        };
+        let constraint = TileConstraint::new((1, 0), "I2".to_string(), shape_square(&[[true]]));
+
+        let rotated = constraint.rotated_clockwise(&board_config);
+
+        assert_eq!(rotated.cell(), (2, 1));
+        assert_eq!(rotated.tile_name(), "I2");
+        assert_eq!(constraint.transposed().cell(), (0, 1));
+    }
+}
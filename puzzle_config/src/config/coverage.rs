@@ -0,0 +1,360 @@
+use crate::{BoardConfig, PuzzleConfig, Target};
+use puzzle_solver::area::{quarter_count, QUARTERS_PER_CELL};
+use puzzled_common::Shape;
+use std::collections::BTreeSet;
+
+/// Compares the area covered by the tiles of a puzzle with the area of the board, which has to
+/// be covered for a target.
+///
+/// A puzzle can only be solved, if both are equal. Half cells count as half of a cell.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct AreaCoverage {
+    tile_quarters: usize,
+    board_quarters: usize,
+}
+
+impl AreaCoverage {
+    /// Computes the coverage for the given puzzle and target.
+    ///
+    /// # Arguments
+    ///
+    /// * `puzzle_config`: The puzzle to compute the coverage for.
+    /// * `target`: The selected target. The cells of the target stay uncovered. For each area set
+    ///   to [crate::AreaTarget::Any], one cell stays uncovered.
+    ///
+    /// returns: AreaCoverage
+    pub fn new(puzzle_config: &PuzzleConfig, target: Option<&Target>) -> AreaCoverage {
+        let tile_quarters = puzzle_config
+            .tiles()
+            .iter()
+            .map(|tile| quarter_count(tile.base()))
+            .sum();
+
+        let layout = puzzle_config.board_config().layout();
+        let mut board_quarters = quarter_count(layout);
+        if let Some(target) = target {
+            let target_quarters: usize = target
+                .fixed_indices()
+                .map(|index| layout.quarters((index.0, index.1)).count_ones() as usize)
+                .sum();
+            let any_quarters = target.any_areas().len() * QUARTERS_PER_CELL;
+            board_quarters = board_quarters.saturating_sub(target_quarters + any_quarters);
+        }
+
+        AreaCoverage {
+            tile_quarters,
+            board_quarters,
+        }
+    }
+
+    /// Returns the number of cells covered by all tiles.
+    pub fn tile_cells(&self) -> f32 {
+        self.tile_quarters as f32 / QUARTERS_PER_CELL as f32
+    }
+
+    /// Returns the number of cells on the board, which have to be covered.
+    pub fn board_cells(&self) -> f32 {
+        self.board_quarters as f32 / QUARTERS_PER_CELL as f32
+    }
+
+    /// Returns true, if the tiles cover exactly the cells, which have to be covered.
+    pub fn is_plausible(&self) -> bool {
+        self.tile_quarters == self.board_quarters
+    }
+}
+
+/// Compares the area covered by the tiles of a puzzle with the area of the board, which has to
+/// be covered for each of its targets.
+///
+/// Unlike [AreaCoverage], no target has to be selected, so this can be checked while a puzzle is
+/// edited. On area boards, each target leaves one cell of each area uncovered, so the area to
+/// cover depends on the half cells among the target cells.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InventoryCheck {
+    tile_quarters: usize,
+    /// The quarters to cover for any target, sorted ascending.
+    board_quarters: Vec<usize>,
+}
+
+impl InventoryCheck {
+    /// Computes the check for the given board and tiles.
+    ///
+    /// # Arguments
+    ///
+    /// * `board_config`: The board of the puzzle. Areas without any cell are ignored.
+    /// * `tiles`: The base shapes of all tiles of the puzzle.
+    ///
+    /// returns: InventoryCheck
+    pub fn new(board_config: &BoardConfig, tiles: &[Shape]) -> InventoryCheck {
+        let tile_quarters = tiles.iter().map(quarter_count).sum();
+
+        let layout = board_config.layout();
+        let mut target_quarters = BTreeSet::from([0]);
+        for area_index in 0..board_config.area_count() {
+            let mut cell_quarters: BTreeSet<usize> = board_config
+                .get_display_values_for_area(area_index as i32)
+                .iter()
+                .map(|(_, index)| layout.quarters((index.0, index.1)).count_ones() as usize)
+                .collect();
+            if cell_quarters.is_empty() {
+                continue;
+            }
+            // Like in AreaCoverage, an area set to any value leaves a full cell uncovered.
+            cell_quarters.insert(QUARTERS_PER_CELL);
+            target_quarters = target_quarters
+                .iter()
+                .flat_map(|sum| cell_quarters.iter().map(move |quarters| sum + quarters))
+                .collect();
+        }
+        let layout_quarters = quarter_count(layout);
+        let board_quarters = target_quarters
+            .iter()
+            .rev()
+            .map(|quarters| layout_quarters.saturating_sub(*quarters))
+            .collect();
+
+        InventoryCheck {
+            tile_quarters,
+            board_quarters,
+        }
+    }
+
+    /// Returns the number of cells covered by all tiles.
+    pub fn tile_cells(&self) -> f32 {
+        self.tile_quarters as f32 / QUARTERS_PER_CELL as f32
+    }
+
+    /// Returns the number of cells to cover for the target closest to the area of the tiles.
+    pub fn closest_board_cells(&self) -> f32 {
+        let closest = self
+            .board_quarters
+            .iter()
+            .min_by_key(|quarters| quarters.abs_diff(self.tile_quarters))
+            .copied()
+            .unwrap_or(0);
+        closest as f32 / QUARTERS_PER_CELL as f32
+    }
+
+    /// Returns true, if the tiles cover exactly the cells to cover for at least one target.
+    pub fn matches_any_target(&self) -> bool {
+        self.board_quarters
+            .binary_search(&self.tile_quarters)
+            .is_ok()
+    }
+}
+
+/// Checks, whether the empty cells of a partially solved puzzle can still be covered by the
+/// tiles, which are not placed yet.
+///
+/// This is a quick check of the area only, so the empty cells may still not be coverable, even
+/// if it passes. Not all tiles need to be placed, so any selection of the remaining tiles is
+/// considered.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct RemainingCoverage {
+    empty_quarters: usize,
+    coverable: bool,
+}
+
+impl RemainingCoverage {
+    /// Computes the remaining coverage.
+    ///
+    /// # Arguments
+    ///
+    /// * `empty_quarters`: The quarters of the board, which are not covered by a tile yet.
+    /// * `uncovered_cells`: The number of empty cells, which stay uncovered, like one cell for
+    ///   each area set to [crate::AreaTarget::Any].
+    /// * `remaining_tiles`: The tiles, which are not placed yet.
+    ///
+    /// returns: RemainingCoverage
+    pub fn new(
+        empty_quarters: usize,
+        uncovered_cells: usize,
+        remaining_tiles: &[Shape],
+    ) -> RemainingCoverage {
+        let coverable = match empty_quarters.checked_sub(uncovered_cells * QUARTERS_PER_CELL) {
+            None => false,
+            Some(quarters_to_cover) => {
+                let tile_quarters: Vec<usize> = remaining_tiles.iter().map(quarter_count).collect();
+                is_subset_sum(&tile_quarters, quarters_to_cover)
+            }
+        };
+        RemainingCoverage {
+            empty_quarters,
+            coverable,
+        }
+    }
+
+    /// Returns the number of cells on the board, which are not covered by a tile yet.
+    pub fn empty_cells(&self) -> f32 {
+        self.empty_quarters as f32 / QUARTERS_PER_CELL as f32
+    }
+
+    /// Returns false, if no selection of the remaining tiles covers the cells to cover.
+    pub fn is_coverable(&self) -> bool {
+        self.coverable
+    }
+}
+
+/// Returns true, if some of the given sizes add up to exactly the given sum.
+fn is_subset_sum(sizes: &[usize], sum: usize) -> bool {
+    let mut reachable = vec![false; sum + 1];
+    reachable[0] = true;
+    for &size in sizes {
+        for reached in (size..=sum).rev() {
+            if reachable[reached - size] {
+                reachable[reached] = true;
+            }
+        }
+    }
+    reachable[sum]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        AreaConfig, AreaTarget, AreaValueFormatter, BoardConfig, ColorConfig, HintConfig,
+        TargetIndex, TargetTemplate, TileConfig,
+    };
+    use ndarray::arr2;
+    use puzzled_common::shape::cell::CellShape;
+    use puzzled_common::shape::shape_square;
+
+    fn create_puzzle(tiles: Vec<Shape>, layout: Shape) -> PuzzleConfig {
+        let tiles = tiles
+            .into_iter()
+            .enumerate()
+            .map(|(i, base)| TileConfig::new(base, ColorConfig::default_with_index(i), None))
+            .collect();
+        PuzzleConfig::new(
+            0,
+            "0".to_string(),
+            "Test".to_string(),
+            None,
+            None,
+            false,
+            tiles,
+            BoardConfig::Simple { layout },
+            None,
+            None,
+            HintConfig::default(),
+        )
+    }
+
+    #[test]
+    fn test_coverage_with_half_cells() {
+        let mut tile = shape_square(&[[true, true]]);
+        tile.set_cell_shape((0, 1), CellShape::TopLeft);
+        let puzzle = create_puzzle(
+            vec![tile, shape_square(&[[true, true]])],
+            shape_square(&[[true, true], [true, true]]),
+        );
+
+        let coverage = AreaCoverage::new(&puzzle, None);
+
+        assert_eq!(coverage.tile_cells(), 3.5);
+        assert_eq!(coverage.board_cells(), 4.0);
+        assert!(!coverage.is_plausible());
+    }
+
+    #[test]
+    fn test_coverage_with_target() {
+        let puzzle = create_puzzle(
+            vec![shape_square(&[[true, true]])],
+            shape_square(&[[true, true], [true, true]]),
+        );
+        let target = Target {
+            indices: vec![AreaTarget::Index(TargetIndex(0, 0)), AreaTarget::Any],
+        };
+
+        let coverage = AreaCoverage::new(&puzzle, Some(&target));
+
+        assert_eq!(coverage.tile_cells(), 2.0);
+        assert_eq!(coverage.board_cells(), 2.0);
+        assert!(coverage.is_plausible());
+    }
+
+    #[test]
+    fn test_inventory_check_simple_board() {
+        let board = BoardConfig::Simple {
+            layout: shape_square(&[[true, true], [true, true]]),
+        };
+        let tiles = vec![shape_square(&[[true, true]])];
+
+        let check = InventoryCheck::new(&board, &tiles);
+
+        assert_eq!(check.tile_cells(), 2.0);
+        assert_eq!(check.closest_board_cells(), 4.0);
+        assert!(!check.matches_any_target());
+        assert!(
+            InventoryCheck::new(&board, &[tiles[0].clone(), tiles[0].clone()]).matches_any_target()
+        );
+    }
+
+    #[test]
+    fn test_inventory_check_area_board() {
+        let mut layout = shape_square(&[[true, true], [true, true], [true, true]]);
+        layout.set_cell_shape((0, 1), CellShape::TopLeft);
+        let area_config = |name: &str| {
+            AreaConfig::new(name.to_string(), AreaValueFormatter::Plain, "1".to_string())
+        };
+        let board = BoardConfig::Area {
+            layout: Box::new(layout),
+            area_indices: Box::new(arr2(&[[0, 0], [1, 1], [-1, -1]])),
+            display_values: Box::new(arr2(&[
+                ["1".to_string(), "2".to_string()],
+                ["1".to_string(), "2".to_string()],
+                [String::new(), String::new()],
+            ])),
+            value_order: Box::new(arr2(&[[0, 1], [0, 1], [0, 0]])),
+            area_configs: vec![area_config("First"), area_config("Second")],
+            target_template: TargetTemplate::new("{0} {1}"),
+            show_legend: false,
+            show_area_boundaries: false,
+        };
+        let domino = shape_square(&[[true, true]]);
+        let mut half_domino = domino.clone();
+        half_domino.set_cell_shape((0, 1), CellShape::TopLeft);
+
+        // The board has 5.5 cells. Depending on the target, 4 or 3.5 cells have to be covered.
+        let matching_full = InventoryCheck::new(&board, &[domino.clone(), domino.clone()]);
+        let matching_half = InventoryCheck::new(&board, &[domino, half_domino.clone()]);
+        let mismatch = InventoryCheck::new(&board, &[half_domino.clone(), half_domino]);
+
+        assert!(matching_full.matches_any_target());
+        assert!(matching_half.matches_any_target());
+        assert!(!mismatch.matches_any_target());
+        assert_eq!(mismatch.tile_cells(), 3.0);
+        assert_eq!(mismatch.closest_board_cells(), 3.5);
+    }
+
+    #[test]
+    fn test_remaining_coverage() {
+        let tiles = vec![
+            shape_square(&[[true, true, true]]),
+            shape_square(&[[true, true, true, true, true]]),
+        ];
+
+        let coverable = RemainingCoverage::new(5 * QUARTERS_PER_CELL, 0, &tiles);
+        let not_coverable = RemainingCoverage::new(4 * QUARTERS_PER_CELL, 0, &tiles);
+        let with_uncovered_cell = RemainingCoverage::new(4 * QUARTERS_PER_CELL, 1, &tiles);
+
+        assert_eq!(coverable.empty_cells(), 5.0);
+        assert!(coverable.is_coverable());
+        assert!(!not_coverable.is_coverable());
+        assert!(with_uncovered_cell.is_coverable());
+        assert!(!RemainingCoverage::new(0, 1, &tiles).is_coverable());
+        assert!(RemainingCoverage::new(0, 0, &[]).is_coverable());
+    }
+
+    #[test]
+    fn test_remaining_coverage_with_half_cells() {
+        let mut tile = shape_square(&[[true, true]]);
+        tile.set_cell_shape((0, 1), CellShape::TopLeft);
+
+        let coverage = RemainingCoverage::new(6, 0, &[tile]);
+
+        assert_eq!(coverage.empty_cells(), 1.5);
+        assert!(coverage.is_coverable());
+    }
+}
@@ -1,4 +1,7 @@
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+use serde::Serialize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum PuzzleDifficultyConfig {
     Easy = 1,
     Medium = 2,
@@ -30,3 +33,16 @@ impl From<f32> for PuzzleDifficultyConfig {
         }
     }
 }
+
+/// Returns the average of the given difficulties, or `None`, if there are none.
+pub(crate) fn average<'a>(
+    difficulties: impl Iterator<Item = &'a PuzzleDifficultyConfig>,
+) -> Option<PuzzleDifficultyConfig> {
+    let (total, count) = difficulties.fold((0, 0), |(total, count), difficulty| {
+        (total + *difficulty as u32, count + 1)
+    });
+    if count == 0 {
+        return None;
+    }
+    Some((total as f32 / count as f32).into())
+}
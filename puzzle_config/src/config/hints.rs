@@ -0,0 +1,82 @@
+use crate::config::serialize;
+use serde::Serialize;
+use std::time::Duration;
+
+/// Restricts the hints, which can be requested while solving a puzzle.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct HintConfig {
+    enabled: bool,
+    max_hints: Option<u32>,
+    #[serde(serialize_with = "serialize::seconds")]
+    cooldown: Option<Duration>,
+}
+
+impl HintConfig {
+    /// Creates a new HintConfig.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled`: Whether hints can be requested at all.
+    /// * `max_hints`: How many hints can be requested per puzzle. None means unlimited.
+    /// * `cooldown`: How long to wait after a hint, before the next one can be requested.
+    ///
+    /// returns: HintConfig
+    pub fn new(enabled: bool, max_hints: Option<u32>, cooldown: Option<Duration>) -> HintConfig {
+        HintConfig {
+            enabled,
+            max_hints,
+            cooldown,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn max_hints(&self) -> Option<u32> {
+        self.max_hints
+    }
+
+    pub fn cooldown(&self) -> Option<Duration> {
+        self.cooldown
+    }
+
+    /// Returns how many more hints can be requested after the given number of hints were used.
+    /// None means unlimited.
+    pub fn remaining_hints(&self, used_hints: u32) -> Option<u32> {
+        if !self.enabled {
+            return Some(0);
+        }
+        self.max_hints
+            .map(|max_hints| max_hints.saturating_sub(used_hints))
+    }
+}
+
+impl Default for HintConfig {
+    /// Hints are enabled without any restrictions.
+    fn default() -> Self {
+        HintConfig::new(true, None, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remaining_hints() {
+        assert_eq!(HintConfig::default().remaining_hints(10), None);
+        assert_eq!(
+            HintConfig::new(true, Some(3), None).remaining_hints(1),
+            Some(2)
+        );
+        assert_eq!(
+            HintConfig::new(true, Some(3), None).remaining_hints(5),
+            Some(0)
+        );
+        assert_eq!(
+            HintConfig::new(false, None, None).remaining_hints(0),
+            Some(0)
+        );
+    }
+}
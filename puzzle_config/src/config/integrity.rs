@@ -0,0 +1,21 @@
+use serde::Serialize;
+
+/// Describes how far the content of a collection could be verified when it was loaded.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum IntegrityConfig {
+    /// The collection neither contains a checksum nor a signature of a known key.
+    #[default]
+    Unchecked,
+    /// The checksum in the collection matches its content.
+    Checksum,
+    /// The signature in the collection matches the known key with the given id.
+    VerifiedAuthor { key_id: String },
+}
+
+impl IntegrityConfig {
+    /// Returns true, if the author of the collection has been verified.
+    pub fn is_verified_author(&self) -> bool {
+        matches!(self, IntegrityConfig::VerifiedAuthor { .. })
+    }
+}
@@ -2,9 +2,16 @@ pub mod area;
 pub mod board;
 pub mod collection;
 pub mod color;
+pub mod constraint;
+pub mod coverage;
 pub mod difficulty;
+pub mod hints;
+pub mod integrity;
 pub mod preview;
 pub mod progression;
 pub mod puzzle;
+pub(crate) mod serialize;
+pub mod statistics;
+pub mod summary;
 pub mod target;
 pub mod tile;
@@ -1,4 +1,7 @@
-#[derive(Debug, Clone)]
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum ProgressionConfig {
     /// Puzzles can be completed in any order.
     Any,
@@ -1,6 +1,10 @@
 use crate::config::board::BoardConfig;
+use crate::config::constraint::TileConstraint;
 use crate::config::difficulty::PuzzleDifficultyConfig;
-use crate::TileConfig;
+use crate::config::statistics::SolutionStatistics;
+use crate::config::tile;
+use crate::{HintConfig, TileConfig};
+use serde::Serialize;
 use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 
@@ -9,7 +13,7 @@ pub type PuzzleId = String;
 /// Configuration for a puzzle. It describes the board layout and tiles.
 /// It additionally contains configuration for the areas to show in the target selection.
 /// The solution statistics are optional since they might not be known yet for all puzzles.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct PuzzleConfig {
     /// The index of the puzzle in the collection.
     index: usize,
@@ -25,7 +29,12 @@ pub struct PuzzleConfig {
     tiles: Vec<TileConfig>,
     /// Configuration of the board layout and areas.
     board_config: BoardConfig,
+    solution_statistics: Option<SolutionStatistics>,
     additional_info: Option<HashMap<String, String>>,
+    /// Restrictions for the hints, which can be requested while solving.
+    hint_config: HintConfig,
+    /// Cells of the board, which have to be covered by a specific tile.
+    tile_constraints: Vec<TileConstraint>,
 }
 
 impl PuzzleConfig {
@@ -39,8 +48,10 @@ impl PuzzleConfig {
     /// * `display_values`: An array where each cell contains the display value for that cell.
     /// * `value_order`: An array where each cell contains the order value for that cell in the area it belongs to.
     /// * `area_configs`: Configuration for each area on the board.
-    /// * `tiles`: The tiles that can be placed on the board.
+    /// * `tiles`: The tiles that can be placed on the board. Their ids are made unique.
     /// * `solution_statistics`: Optional statistics about the solutions for this puzzle.
+    /// * `additional_info`: Optional metadata to show in the puzzle info.
+    /// * `hint_config`: Restrictions for the hints, which can be requested while solving.
     /// * `default_target`: Optional default target for the puzzle.
     /// * `target_template`: Template for formatting targets to show in the UI.
     ///
@@ -55,8 +66,12 @@ impl PuzzleConfig {
         unsolvable: bool,
         tiles: Vec<TileConfig>,
         board_config: BoardConfig,
+        solution_statistics: Option<SolutionStatistics>,
         additional_info: Option<HashMap<String, String>>,
+        hint_config: HintConfig,
     ) -> PuzzleConfig {
+        let mut tiles = tiles;
+        tile::make_ids_unique(&mut tiles);
         PuzzleConfig {
             index,
             id,
@@ -66,10 +81,19 @@ impl PuzzleConfig {
             unsolvable,
             board_config,
             tiles,
+            solution_statistics,
             additional_info,
+            hint_config,
+            tile_constraints: Vec::new(),
         }
     }
 
+    /// Returns this puzzle with the given cells, which have to be covered by a specific tile.
+    pub fn with_tile_constraints(mut self, tile_constraints: Vec<TileConstraint>) -> PuzzleConfig {
+        self.tile_constraints = tile_constraints;
+        self
+    }
+
     pub fn index(&self) -> usize {
         self.index
     }
@@ -102,14 +126,27 @@ impl PuzzleConfig {
         &self.board_config
     }
 
+    pub fn solution_statistics(&self) -> &Option<SolutionStatistics> {
+        &self.solution_statistics
+    }
+
     pub fn additional_info(&self) -> &Option<HashMap<String, String>> {
         &self.additional_info
     }
+
+    pub fn hint_config(&self) -> &HintConfig {
+        &self.hint_config
+    }
+
+    pub fn tile_constraints(&self) -> &[TileConstraint] {
+        &self.tile_constraints
+    }
 }
 
 impl Hash for PuzzleConfig {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.board_config.hash(state);
         TileConfig::hash_slice(&self.tiles, state);
+        self.tile_constraints.hash(state);
     }
 }
@@ -0,0 +1,141 @@
+//! Helpers to serialize the fields of the configs, whose types do not implement [Serialize].
+//!
+//! The serialized configs are fully resolved. They are meant for external tools and debugging
+//! and are not the format collections are written in.
+
+use ndarray::Array2;
+use puzzled_common::{Shape, ShapeType};
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
+use std::borrow::Borrow;
+use std::time::Duration;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+/// Serializes the shape with its type and its cells as rows like in the collection format.
+/// The quarters of the cells are only included, if the shape has half cells.
+pub(crate) fn shape<S: Serializer, B: Borrow<Shape>>(
+    shape: &B,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let shape = shape.borrow();
+    let (width, height) = shape.dim();
+    let cells: Vec<Vec<bool>> = (0..height)
+        .map(|y| (0..width).map(|x| shape[(x, y)]).collect())
+        .collect();
+
+    let mut state = serializer.serialize_struct("Shape", 3)?;
+    state.serialize_field(
+        "type",
+        match shape.shape_type() {
+            ShapeType::Square => "square",
+            ShapeType::Triangle => "triangle",
+            ShapeType::Hexagon => "hexagon",
+        },
+    )?;
+    state.serialize_field("cells", &cells)?;
+    if shape.has_half_cells() {
+        let quarters: Vec<Vec<u8>> = (0..height)
+            .map(|y| (0..width).map(|x| shape.quarters((x, y))).collect())
+            .collect();
+        state.serialize_field("quarters", &quarters)?;
+    } else {
+        state.skip_field("quarters")?;
+    }
+    state.end()
+}
+
+/// Serializes the array indexed by `(x, y)` as rows like in the collection format.
+pub(crate) fn array<S: Serializer, T: Serialize, B: Borrow<Array2<T>>>(
+    array: &B,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let array = array.borrow();
+    let (width, height) = array.dim();
+    let rows: Vec<Vec<&T>> = (0..height)
+        .map(|y| (0..width).map(|x| &array[(x, y)]).collect())
+        .collect();
+    rows.serialize(serializer)
+}
+
+/// Serializes the duration in whole seconds like in the collection format.
+pub(crate) fn seconds<S: Serializer>(
+    duration: &Option<Duration>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    duration
+        .map(|duration| duration.as_secs())
+        .serialize(serializer)
+}
+
+/// Serializes the date in the RFC 3339 format.
+pub(crate) fn rfc3339<S: Serializer>(
+    date: &Option<OffsetDateTime>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let date = date
+        .map(|date| date.format(&Rfc3339))
+        .transpose()
+        .map_err(serde::ser::Error::custom)?;
+    date.serialize(serializer)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::create_json_loader;
+    use serde_json::json;
+
+    #[test]
+    fn test_serialize_resolved_collection() {
+        let predefined_json_str = r#"
+        {
+            "tiles": {
+                "L3": [
+                    [1, 0],
+                    [1, 1]
+                ]
+            },
+            "boards": {}
+        }
+        "#;
+        let json_loader = create_json_loader(predefined_json_str, "0.1.0").unwrap();
+        let json_str = r##"
+        {
+          "puzzled": "0.1.0",
+          "name": "Test Collection",
+          "author": "Test Author",
+          "id": "de.til7701.Puzzled.test-collection",
+          "palette": ["#1c71d8"],
+          "puzzles": [
+            {
+              "name": "Puzzle",
+              "tiles": ["L3"],
+              "board": {
+                "layout": [
+                  [0, 0],
+                  [0, 1]
+                ]
+              }
+            }
+          ]
+        }
+        "##;
+        let collection = json_loader.load_puzzle_collection(json_str).unwrap();
+
+        let value = serde_json::to_value(&collection).unwrap();
+
+        assert_eq!(value["name"], json!("Test Collection"));
+        assert_eq!(value["palette"], json!(["#1c71d8"]));
+        let puzzle = &value["puzzles"][0];
+        assert_eq!(
+            puzzle["tiles"][0]["base"],
+            json!({"type": "square", "cells": [[true, false], [true, true]]})
+        );
+        assert_eq!(puzzle["tiles"][0]["color"], json!("#1c71d8"));
+        assert_eq!(puzzle["tiles"][0]["name"], json!("L3"));
+        assert_eq!(
+            puzzle["board_config"]["layout"]["cells"],
+            json!([[true, true], [true, false]])
+        );
+    }
+}
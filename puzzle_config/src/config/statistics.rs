@@ -0,0 +1,127 @@
+use crate::config::serialize;
+use serde::Serialize;
+use time::OffsetDateTime;
+
+/// Statistics about the number of solutions of a puzzle.
+///
+/// For puzzles with areas, the number of solutions is calculated for each possible target.
+/// Simple puzzles only have a single target.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SolutionStatistics {
+    min: u64,
+    max: u64,
+    median: u64,
+    total: u64,
+    target_count: u64,
+    /// When the statistics were computed. This is `None` for statistics which were written by
+    /// hand.
+    #[serde(serialize_with = "serialize::rfc3339")]
+    computed_on: Option<OffsetDateTime>,
+}
+
+impl SolutionStatistics {
+    pub fn new(
+        min: u64,
+        max: u64,
+        median: u64,
+        total: u64,
+        target_count: u64,
+        computed_on: Option<OffsetDateTime>,
+    ) -> SolutionStatistics {
+        SolutionStatistics {
+            min,
+            max,
+            median,
+            total,
+            target_count,
+            computed_on,
+        }
+    }
+
+    /// Creates the statistics from the number of solutions for each target.
+    ///
+    /// # Arguments
+    ///
+    /// * `solution_counts`: The number of solutions for each target.
+    /// * `computed_on`: When the solution counts were computed.
+    ///
+    /// returns: SolutionStatistics
+    pub fn from_solution_counts(
+        solution_counts: &[u64],
+        computed_on: Option<OffsetDateTime>,
+    ) -> SolutionStatistics {
+        let mut sorted = solution_counts.to_vec();
+        sorted.sort_unstable();
+        SolutionStatistics {
+            min: sorted.first().copied().unwrap_or(0),
+            max: sorted.last().copied().unwrap_or(0),
+            median: sorted.get(sorted.len() / 2).copied().unwrap_or(0),
+            total: sorted.iter().sum(),
+            target_count: sorted.len() as u64,
+            computed_on,
+        }
+    }
+
+    /// The smallest number of solutions of any target.
+    pub fn min(&self) -> u64 {
+        self.min
+    }
+
+    /// The largest number of solutions of any target.
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+
+    pub fn median(&self) -> u64 {
+        self.median
+    }
+
+    /// The sum of the solutions of all targets.
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// The number of targets the statistics were computed for.
+    pub fn target_count(&self) -> u64 {
+        self.target_count
+    }
+
+    /// The average number of solutions per target.
+    pub fn average(&self) -> f64 {
+        if self.target_count == 0 {
+            0.0
+        } else {
+            self.total as f64 / self.target_count as f64
+        }
+    }
+
+    pub fn computed_on(&self) -> Option<OffsetDateTime> {
+        self.computed_on
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_solution_counts() {
+        let statistics = SolutionStatistics::from_solution_counts(&[4, 1, 7, 0, 3], None);
+
+        assert_eq!(statistics.min(), 0);
+        assert_eq!(statistics.max(), 7);
+        assert_eq!(statistics.median(), 3);
+        assert_eq!(statistics.total(), 15);
+        assert_eq!(statistics.target_count(), 5);
+        assert_eq!(statistics.average(), 3.0);
+    }
+
+    #[test]
+    fn test_from_solution_counts_empty() {
+        let statistics = SolutionStatistics::from_solution_counts(&[], None);
+
+        assert_eq!(statistics.total(), 0);
+        assert_eq!(statistics.target_count(), 0);
+        assert_eq!(statistics.average(), 0.0);
+    }
+}
@@ -0,0 +1,137 @@
+use crate::config::difficulty;
+use crate::{IntegrityConfig, PuzzleConfigCollection, PuzzleDifficultyConfig, PuzzleId};
+
+/// The metadata of a collection, which is needed to list it without loading its puzzles.
+///
+/// It is read by [JsonLoader::load_collection_summary](crate::JsonLoader::load_collection_summary)
+/// without converting the tiles and boards, which makes it much cheaper than loading the whole
+/// collection.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectionSummary {
+    name: String,
+    description: Option<String>,
+    author: String,
+    id: String,
+    version: Option<String>,
+    integrity: IntegrityConfig,
+    puzzles: Vec<PuzzleSummary>,
+}
+
+/// The metadata of a puzzle in a [CollectionSummary].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PuzzleSummary {
+    id: PuzzleId,
+    name: String,
+    difficulty: Option<PuzzleDifficultyConfig>,
+    unsolvable: bool,
+}
+
+impl CollectionSummary {
+    pub(crate) fn new(
+        name: String,
+        description: Option<String>,
+        author: String,
+        id: String,
+        version: Option<String>,
+        integrity: IntegrityConfig,
+        puzzles: Vec<PuzzleSummary>,
+    ) -> CollectionSummary {
+        CollectionSummary {
+            name,
+            description,
+            author,
+            id,
+            version,
+            integrity,
+            puzzles,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn description(&self) -> &Option<String> {
+        &self.description
+    }
+
+    pub fn author(&self) -> &str {
+        &self.author
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn version(&self) -> &Option<String> {
+        &self.version
+    }
+
+    /// How far the content of the collection could be verified when it was loaded.
+    pub fn integrity(&self) -> &IntegrityConfig {
+        &self.integrity
+    }
+
+    pub fn puzzles(&self) -> &[PuzzleSummary] {
+        &self.puzzles
+    }
+
+    pub fn average_difficulty(&self) -> Option<PuzzleDifficultyConfig> {
+        difficulty::average(self.puzzles.iter().flat_map(|puzzle| &puzzle.difficulty))
+    }
+}
+
+impl From<&PuzzleConfigCollection> for CollectionSummary {
+    fn from(collection: &PuzzleConfigCollection) -> Self {
+        CollectionSummary {
+            name: collection.name().to_string(),
+            description: collection.description().clone(),
+            author: collection.author().to_string(),
+            id: collection.id().to_string(),
+            version: collection.version().clone(),
+            integrity: collection.integrity().clone(),
+            puzzles: collection
+                .puzzles()
+                .iter()
+                .map(|puzzle| PuzzleSummary {
+                    id: puzzle.id().clone(),
+                    name: puzzle.name().to_string(),
+                    difficulty: *puzzle.difficulty(),
+                    unsolvable: puzzle.is_unsolvable(),
+                })
+                .collect(),
+        }
+    }
+}
+
+impl PuzzleSummary {
+    pub(crate) fn new(
+        id: PuzzleId,
+        name: String,
+        difficulty: Option<PuzzleDifficultyConfig>,
+        unsolvable: bool,
+    ) -> PuzzleSummary {
+        PuzzleSummary {
+            id,
+            name,
+            difficulty,
+            unsolvable,
+        }
+    }
+
+    pub fn id(&self) -> &PuzzleId {
+        &self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn difficulty(&self) -> &Option<PuzzleDifficultyConfig> {
+        &self.difficulty
+    }
+
+    pub fn is_unsolvable(&self) -> bool {
+        self.unsolvable
+    }
+}
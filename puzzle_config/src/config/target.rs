@@ -1,13 +1,17 @@
-use crate::config::area::{AreaConfig, AreaValueFormatter};
+use crate::config::area::{AreaConfig, AreaValueFormatter, ENGLISH_MONTH_NAMES};
 use ndarray::Array2;
+use serde::Serialize;
 use std::fmt::{Display, Formatter};
 
+/// The value shown in place of an area, which is set to [AreaTarget::Any].
+pub const ANY_DISPLAY_VALUE: &str = "Any";
+
 /// Template for formatting targets to show in the UI.
 ///
 /// The placeholders {0}, {1}, {2}, etc. will be replaced with the display values
 /// of the corresponding target indices.
 /// The area formatter will be applied to each value before inserting it into the template.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
 pub struct TargetTemplate(String);
 
 impl TargetTemplate {
@@ -34,24 +38,26 @@ impl TargetTemplate {
         let values: Vec<String> = target
             .indices
             .iter()
-            .map(|TargetIndex(x, y)| {
-                board_values
-                    .get((*x, *y))
-                    .cloned()
-                    .unwrap_or_else(|| "???".to_string())
+            .enumerate()
+            .map(|(i, area_target)| match area_target {
+                AreaTarget::Index(TargetIndex(x, y)) => {
+                    let value = board_values
+                        .get((*x, *y))
+                        .cloned()
+                        .unwrap_or_else(|| "???".to_string());
+                    if let Some(area_config) = area_configs.get(i) {
+                        self.format_value(&value, area_config)
+                    } else {
+                        value
+                    }
+                }
+                AreaTarget::Any => ANY_DISPLAY_VALUE.to_string(),
             })
             .collect();
 
         let mut result = self.0.clone();
         for (i, value) in values.iter().enumerate() {
             let placeholder = format!("{{{}}}", i);
-            let value = {
-                if let Some(area_config) = area_configs.get(i) {
-                    &self.format_value(value, area_config)
-                } else {
-                    value
-                }
-            };
             result = result.replace(&placeholder, value.as_str());
         }
         result
@@ -82,17 +88,122 @@ impl TargetTemplate {
             AreaValueFormatter::PrefixSuffix { prefix, suffix } => {
                 format!("{}{}{}", prefix, value, suffix)
             }
+            AreaValueFormatter::Roman => value
+                .parse::<u16>()
+                .ok()
+                .and_then(to_roman)
+                .unwrap_or_else(|| value.to_string()),
+            AreaValueFormatter::LocalizedMonth { names } => month_index(value)
+                .and_then(|index| names.get(index))
+                .cloned()
+                .unwrap_or_else(|| value.to_string()),
+            AreaValueFormatter::ZeroPadded { width } => match value.parse::<u64>() {
+                Ok(number) => format!("{:0width$}", number, width = width),
+                Err(_) => value.to_string(),
+            },
+        }
+    }
+}
+
+/// Converts a number to Roman numerals.
+///
+/// returns: Option<String> `None`, if the number is not between 1 and 3999
+fn to_roman(mut number: u16) -> Option<String> {
+    const NUMERALS: [(u16, &str); 13] = [
+        (1000, "M"),
+        (900, "CM"),
+        (500, "D"),
+        (400, "CD"),
+        (100, "C"),
+        (90, "XC"),
+        (50, "L"),
+        (40, "XL"),
+        (10, "X"),
+        (9, "IX"),
+        (5, "V"),
+        (4, "IV"),
+        (1, "I"),
+    ];
+    if !(1..4000).contains(&number) {
+        return None;
+    }
+    let mut result = String::new();
+    for (value, numeral) in NUMERALS {
+        while number >= value {
+            result.push_str(numeral);
+            number -= value;
         }
     }
+    Some(result)
+}
+
+/// Returns the index of the month starting with 0 for January.
+/// The value can be the number of the month or the start of its English name.
+fn month_index(value: &str) -> Option<usize> {
+    if let Ok(number) = value.parse::<usize>() {
+        return (1..=ENGLISH_MONTH_NAMES.len())
+            .contains(&number)
+            .then(|| number - 1);
+    }
+    if value.len() < 3 {
+        return None;
+    }
+    ENGLISH_MONTH_NAMES.iter().position(|name| {
+        name.get(..value.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(value))
+    })
 }
 
 /// A target for the puzzle.
 ///
-/// It consists of a list of target indices, each representing a cell on the board.
-/// It should have one index per area defined in the puzzle.
+/// It consists of a list of area targets, each representing a cell on the board or a wildcard
+/// for the whole area.
+/// It should have one entry per area defined in the puzzle.
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub struct Target {
-    pub indices: Vec<TargetIndex>,
+    pub indices: Vec<AreaTarget>,
+}
+
+impl Target {
+    /// Returns the indices of all cells, which have to remain uncovered.
+    pub fn fixed_indices(&self) -> impl Iterator<Item = &TargetIndex> {
+        self.indices.iter().filter_map(AreaTarget::index)
+    }
+
+    /// Returns the indices of all areas, which are set to [AreaTarget::Any].
+    pub fn any_areas(&self) -> Vec<usize> {
+        self.indices
+            .iter()
+            .enumerate()
+            .filter(|(_, area_target)| **area_target == AreaTarget::Any)
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+/// The target for a single area of the board.
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
+pub enum AreaTarget {
+    /// The cell at the given index has to remain uncovered.
+    Index(TargetIndex),
+    /// Any one cell of the area may remain uncovered.
+    Any,
+}
+
+impl AreaTarget {
+    /// Returns the target index, if this is not a wildcard.
+    pub fn index(&self) -> Option<&TargetIndex> {
+        match self {
+            AreaTarget::Index(target_index) => Some(target_index),
+            AreaTarget::Any => None,
+        }
+    }
+}
+
+impl From<TargetIndex> for AreaTarget {
+    fn from(target_index: TargetIndex) -> Self {
+        AreaTarget::Index(target_index)
+    }
 }
 
 /// Represents the index of a target cell on the board.
@@ -110,3 +221,125 @@ impl Display for TargetIndex {
         write!(f, "({}, {})", self.0, self.1)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr2;
+
+    fn create_area_configs() -> Vec<AreaConfig> {
+        vec![
+            AreaConfig::new(
+                "Month".to_string(),
+                AreaValueFormatter::Plain,
+                "".to_string(),
+            ),
+            AreaConfig::new("Day".to_string(), AreaValueFormatter::Nth, "".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_format_target() {
+        let board_values = arr2(&[
+            ["Jan".to_string(), "Feb".to_string()],
+            ["1".to_string(), "2".to_string()],
+        ]);
+        let template = TargetTemplate::new("{1} of {0}");
+        let target = Target {
+            indices: vec![TargetIndex(0, 1).into(), TargetIndex(1, 0).into()],
+        };
+
+        let result = template.format(&target, &board_values, &create_area_configs());
+
+        assert_eq!(result, "1st of Feb");
+    }
+
+    #[test]
+    fn test_format_target_any() {
+        let board_values = arr2(&[
+            ["Jan".to_string(), "Feb".to_string()],
+            ["1".to_string(), "2".to_string()],
+        ]);
+        let template = TargetTemplate::new("{1} of {0}");
+        let target = Target {
+            indices: vec![TargetIndex(0, 0).into(), AreaTarget::Any],
+        };
+
+        let result = template.format(&target, &board_values, &create_area_configs());
+
+        assert_eq!(result, "Any of Jan");
+    }
+
+    #[test]
+    fn test_format_value_roman() {
+        let template = TargetTemplate::new("{0}");
+        let area_config = AreaConfig::new(
+            "Year".to_string(),
+            AreaValueFormatter::Roman,
+            "".to_string(),
+        );
+
+        assert_eq!(template.format_value("4", &area_config), "IV");
+        assert_eq!(template.format_value("12", &area_config), "XII");
+        assert_eq!(template.format_value("1999", &area_config), "MCMXCIX");
+        assert_eq!(template.format_value("0", &area_config), "0");
+        assert_eq!(template.format_value("Jan", &area_config), "Jan");
+    }
+
+    #[test]
+    fn test_format_value_localized_month() {
+        let template = TargetTemplate::new("{0}");
+        let english = AreaConfig::new(
+            "Month".to_string(),
+            AreaValueFormatter::LocalizedMonth {
+                names: ENGLISH_MONTH_NAMES.map(str::to_string).to_vec(),
+            },
+            "".to_string(),
+        );
+        let german = AreaConfig::new(
+            "Monat".to_string(),
+            AreaValueFormatter::LocalizedMonth {
+                names: vec![
+                    "Januar".to_string(),
+                    "Februar".to_string(),
+                    "März".to_string(),
+                ],
+            },
+            "".to_string(),
+        );
+
+        assert_eq!(template.format_value("Feb", &english), "February");
+        assert_eq!(template.format_value("sep", &english), "September");
+        assert_eq!(template.format_value("12", &english), "December");
+        assert_eq!(template.format_value("13", &english), "13");
+        assert_eq!(template.format_value("Ju", &english), "Ju");
+        assert_eq!(template.format_value("Mar", &german), "März");
+        assert_eq!(template.format_value("Apr", &german), "Apr");
+    }
+
+    #[test]
+    fn test_format_value_zero_padded() {
+        let template = TargetTemplate::new("{0}");
+        let area_config = AreaConfig::new(
+            "Day".to_string(),
+            AreaValueFormatter::ZeroPadded { width: 2 },
+            "".to_string(),
+        );
+
+        assert_eq!(template.format_value("7", &area_config), "07");
+        assert_eq!(template.format_value("31", &area_config), "31");
+        assert_eq!(template.format_value("123", &area_config), "123");
+        assert_eq!(template.format_value("Mon", &area_config), "Mon");
+    }
+
+    #[test]
+    fn test_fixed_indices_and_any_areas() {
+        let target = Target {
+            indices: vec![AreaTarget::Any, TargetIndex(1, 2).into(), AreaTarget::Any],
+        };
+
+        let fixed: Vec<&TargetIndex> = target.fixed_indices().collect();
+        assert_eq!(fixed, vec![&TargetIndex(1, 2)]);
+        assert_eq!(target.any_areas(), vec![0, 2]);
+    }
+}
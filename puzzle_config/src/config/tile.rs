@@ -1,13 +1,19 @@
 use crate::config::color::ColorConfig;
+use crate::config::serialize;
 use puzzled_common::Shape;
+use serde::Serialize;
+use std::collections::HashSet;
 use std::hash::{DefaultHasher, Hash, Hasher};
 
 /// Configuration for a tile that can be placed on the board.
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize)]
 pub struct TileConfig {
+    #[serde(serialize_with = "serialize::shape")]
     base: Shape,
     color: ColorConfig,
     name: Option<String>,
+    /// Identifies the tile within its puzzle, even if the tiles are reordered.
+    id: String,
 }
 
 impl TileConfig {
@@ -17,9 +23,24 @@ impl TileConfig {
     ///
     /// * `base`: Base shape of the tile as a 2D boolean array.
     ///
+    /// The id is derived from the name or, if there is no name, from the base. Identical tiles
+    /// get the same id, until [crate::PuzzleConfig::new] makes the ids of a puzzle unique.
+    ///
     /// returns: TileConfig
     pub fn new(base: Shape, color: ColorConfig, name: Option<String>) -> TileConfig {
-        TileConfig { base, color, name }
+        let id = name.clone().unwrap_or_else(|| layout_id(&base));
+        TileConfig {
+            base,
+            color,
+            name,
+            id,
+        }
+    }
+
+    /// Returns this tile with the given id instead of the derived one.
+    pub fn with_id(mut self, id: String) -> TileConfig {
+        self.id = id;
+        self
     }
 
     /// Base shape of the tile as a 2D boolean array.
@@ -37,6 +58,46 @@ impl TileConfig {
     pub fn name(&self) -> &Option<String> {
         &self.name
     }
+
+    /// The id of the tile, which is unique within its puzzle.
+    ///
+    /// It is given by the author or derived from the name or shape of the tile, so it stays the
+    /// same, if the tiles of the puzzle are reordered. It should be used instead of the index of
+    /// the tile to store information about it.
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+}
+
+/// Derives an id from the layout of a tile.
+/// The quarters of each cell are written as a hex digit and the columns are separated by dots.
+fn layout_id(base: &Shape) -> String {
+    let (width, height) = base.dim();
+    (0..width)
+        .map(|x| {
+            (0..height)
+                .map(|y| format!("{:x}", base.quarters((x, y))))
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join(".")
+}
+
+/// Makes the ids of the given tiles unique.
+/// The first tile with an id keeps it. The following tiles with the same id get the number of
+/// the occurrence appended, like `O4#2`, so the ids stay the same, if copies are added at the end.
+pub(crate) fn make_ids_unique(tiles: &mut [TileConfig]) {
+    let mut used_ids: HashSet<String> = HashSet::new();
+    for tile in tiles.iter_mut() {
+        let mut id = tile.id.clone();
+        let mut occurrence = 1;
+        while used_ids.contains(&id) {
+            occurrence += 1;
+            id = format!("{}#{}", tile.id, occurrence);
+        }
+        used_ids.insert(id.clone());
+        tile.id = id;
+    }
 }
 
 impl Hash for TileConfig {
@@ -105,6 +166,42 @@ mod tests {
         assert_ne!(hash1, hash3);
     }
 
+    #[test]
+    fn test_id() {
+        let named = TileConfig::new(
+            shape_square(&[[true, false], [true, true]]),
+            ColorConfig::default_with_index(0),
+            Some("L3".to_string()),
+        );
+        let unnamed = TileConfig::new(
+            shape_square(&[[true, false], [true, true]]),
+            ColorConfig::default_with_index(0),
+            None,
+        );
+
+        assert_eq!(named.id(), "L3");
+        assert_eq!(unnamed.id(), "f0.ff");
+        assert_eq!(unnamed.with_id("corner".to_string()).id(), "corner");
+    }
+
+    #[test]
+    fn test_make_ids_unique() {
+        let tile = |id: &str| {
+            TileConfig::new(
+                shape_square(&[[true]]),
+                ColorConfig::default_with_index(0),
+                None,
+            )
+            .with_id(id.to_string())
+        };
+        let mut tiles = vec![tile("a"), tile("b"), tile("a"), tile("a#2"), tile("a")];
+
+        make_ids_unique(&mut tiles);
+
+        let ids: Vec<&str> = tiles.iter().map(TileConfig::id).collect();
+        assert_eq!(ids, vec!["a", "b", "a#2", "a#2#2", "a#3"]);
+    }
+
     #[test]
     fn test_hash_slice_any_order() {
         let tile1 = TileConfig::new(
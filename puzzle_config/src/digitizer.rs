@@ -0,0 +1,324 @@
+//! Experimental import of the board of a physical puzzle from a photo.
+//!
+//! The photo should show the board from above, roughly aligned with the image borders. The grid
+//! lines are found by projecting the edges of the image onto both axes. Cells, which are much
+//! darker than the others, are considered blocked. The result is only a draft, which has to be
+//! refined in the editor.
+
+use crate::BoardConfig;
+use image::imageops::FilterType;
+use image::GrayImage;
+use imageproc::contrast::otsu_level;
+use imageproc::edges::canny;
+use puzzled_common::Shape;
+use puzzled_common::ShapeType::Square;
+
+/// The longest side of the image the grid is searched in. Larger photos are scaled down.
+const WORKING_SIZE: u32 = 800;
+/// The thresholds of the gradient for the edge detection.
+const EDGE_LOW_THRESHOLD: f32 = 50.0;
+const EDGE_HIGH_THRESHOLD: f32 = 100.0;
+/// The most cells per side of the board, which can be detected. Edges closer than half the
+/// smallest cell are merged into one line.
+const MAX_CELLS_PER_SIDE: usize = 40;
+/// The share of the strongest line a row or column of edges needs to be a grid line.
+const LINE_THRESHOLD: f32 = 0.5;
+/// The share of each side of a cell, which is ignored when measuring its brightness, to skip
+/// the grid lines.
+const CELL_INSET: f32 = 0.25;
+/// The minimum difference in brightness between the darkest and the brightest cell to consider
+/// any cell blocked.
+const MIN_CELL_CONTRAST: u8 = 48;
+
+/// An error while creating a board from a photo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DigitizeError {
+    /// The image could not be decoded.
+    InvalidImage(String),
+    /// No grid with at least one cell was found in the image.
+    GridNotFound,
+}
+
+/// Creates a draft board from a photo of a grid-based puzzle board.
+///
+/// The photo can be in any format supported by the `image` crate, which is enabled for this
+/// crate, currently PNG and JPEG.
+///
+/// # Arguments
+///
+/// * `image_bytes`: The encoded photo.
+///
+/// returns: Result<BoardConfig, DigitizeError>
+pub fn digitize_board(image_bytes: &[u8]) -> Result<BoardConfig, DigitizeError> {
+    let image = image::load_from_memory(image_bytes)
+        .map_err(|e| DigitizeError::InvalidImage(e.to_string()))?;
+    digitize_board_image(&image.to_luma8())
+}
+
+/// Creates a draft board from a grayscale photo of a grid-based puzzle board.
+///
+/// Every cell between two neighbouring grid lines becomes a cell of the board. If grid lines are
+/// missing, for example because they are hidden by a blocked cell, the cell size of the other
+/// cells is used to fill the gap. Cells, which are considerably darker than the others, are
+/// blocked.
+///
+/// returns: Result<BoardConfig, DigitizeError>
+pub fn digitize_board_image(image: &GrayImage) -> Result<BoardConfig, DigitizeError> {
+    let image = scale_to_working_size(image);
+    let edges = canny(&image, EDGE_LOW_THRESHOLD, EDGE_HIGH_THRESHOLD);
+
+    let (width, height) = edges.dimensions();
+    let mut column_profile = vec![0u32; width as usize];
+    let mut row_profile = vec![0u32; height as usize];
+    for (x, y, pixel) in edges.enumerate_pixels() {
+        if pixel.0[0] > 0 {
+            column_profile[x as usize] += 1;
+            row_profile[y as usize] += 1;
+        }
+    }
+
+    let columns =
+        cell_boundaries(&line_positions(&column_profile)).ok_or(DigitizeError::GridNotFound)?;
+    let rows = cell_boundaries(&line_positions(&row_profile)).ok_or(DigitizeError::GridNotFound)?;
+
+    let mut brightness = Vec::with_capacity((columns.len() - 1) * (rows.len() - 1));
+    for y in rows.windows(2) {
+        for x in columns.windows(2) {
+            brightness.push(cell_brightness(&image, (x[0], x[1]), (y[0], y[1])));
+        }
+    }
+    let blocked = blocked_cells(&brightness);
+
+    let board_width = columns.len() - 1;
+    let board_height = rows.len() - 1;
+    let mut layout = Shape::from_elem((board_width, board_height), Square, true);
+    for y in 0..board_height {
+        for x in 0..board_width {
+            layout[(x, y)] = !blocked[y * board_width + x];
+        }
+    }
+    Ok(BoardConfig::Simple { layout })
+}
+
+fn scale_to_working_size(image: &GrayImage) -> GrayImage {
+    let (width, height) = image.dimensions();
+    let longest_side = width.max(height);
+    if longest_side <= WORKING_SIZE {
+        return image.clone();
+    }
+    let scale = WORKING_SIZE as f32 / longest_side as f32;
+    image::imageops::resize(
+        image,
+        ((width as f32 * scale).round() as u32).max(1),
+        ((height as f32 * scale).round() as u32).max(1),
+        FilterType::Triangle,
+    )
+}
+
+/// Finds the positions of lines in the number of edge pixels per row or column.
+/// Neighbouring rows or columns belong to the same line, since a drawn line has an edge on both
+/// sides.
+fn line_positions(profile: &[u32]) -> Vec<f32> {
+    let Some(&max) = profile.iter().max() else {
+        return Vec::new();
+    };
+    if max == 0 {
+        return Vec::new();
+    }
+    let threshold = max as f32 * LINE_THRESHOLD;
+    let merge_distance = (profile.len() / (2 * MAX_CELLS_PER_SIDE)).max(5);
+
+    let mut lines = Vec::new();
+    let mut current: Option<(usize, usize)> = None;
+    for (index, &count) in profile.iter().enumerate() {
+        if (count as f32) < threshold {
+            continue;
+        }
+        current = match current {
+            Some((start, end)) if index - end <= merge_distance => Some((start, index)),
+            Some((start, end)) => {
+                lines.push((start + end) as f32 / 2.0);
+                Some((index, index))
+            }
+            None => Some((index, index)),
+        };
+    }
+    if let Some((start, end)) = current {
+        lines.push((start + end) as f32 / 2.0);
+    }
+    lines
+}
+
+/// Turns the found lines into the boundaries of evenly sized cells.
+///
+/// Lines much closer than the typical cell size are dropped and gaps of multiple cell sizes are
+/// split into multiple cells.
+///
+/// returns: Option<Vec<f32>> `None`, if there are not at least two lines
+fn cell_boundaries(lines: &[f32]) -> Option<Vec<f32>> {
+    if lines.len() < 2 {
+        return None;
+    }
+    let mut gaps: Vec<f32> = lines.windows(2).map(|pair| pair[1] - pair[0]).collect();
+    gaps.sort_by(f32::total_cmp);
+    let cell_size = gaps[gaps.len() / 2];
+
+    let mut kept = vec![lines[0]];
+    for &line in &lines[1..] {
+        if line - kept[kept.len() - 1] >= cell_size / 2.0 {
+            kept.push(line);
+        }
+    }
+    if kept.len() < 2 {
+        return None;
+    }
+
+    let mut boundaries = vec![kept[0]];
+    for pair in kept.windows(2) {
+        let gap = pair[1] - pair[0];
+        let cells = (gap / cell_size).round().max(1.0) as usize;
+        for i in 1..=cells {
+            boundaries.push(pair[0] + gap * i as f32 / cells as f32);
+        }
+    }
+    Some(boundaries)
+}
+
+/// Returns the mean brightness of the inner part of a cell.
+fn cell_brightness(image: &GrayImage, (left, right): (f32, f32), (top, bottom): (f32, f32)) -> u8 {
+    let inset_x = (right - left) * CELL_INSET;
+    let inset_y = (bottom - top) * CELL_INSET;
+    let x_range = (left + inset_x).round() as u32..=(right - inset_x).round() as u32;
+    let y_range = (top + inset_y).round() as u32..=(bottom - inset_y).round() as u32;
+
+    let mut sum = 0u64;
+    let mut count = 0u64;
+    for y in y_range {
+        for x in x_range.clone() {
+            if let Some(pixel) = image.get_pixel_checked(x, y) {
+                sum += pixel.0[0] as u64;
+                count += 1;
+            }
+        }
+    }
+    if count == 0 {
+        return u8::MAX;
+    }
+    (sum / count) as u8
+}
+
+/// Splits the cells into bright free and dark blocked cells using Otsu's method.
+/// If all cells have a similar brightness, none of them is blocked.
+fn blocked_cells(brightness: &[u8]) -> Vec<bool> {
+    let darkest = brightness.iter().copied().min().unwrap_or(0);
+    let brightest = brightness.iter().copied().max().unwrap_or(0);
+    if brightest - darkest < MIN_CELL_CONTRAST {
+        return vec![false; brightness.len()];
+    }
+    let image = GrayImage::from_raw(brightness.len() as u32, 1, brightness.to_vec())
+        .expect("the buffer has exactly one pixel per cell");
+    let level = otsu_level(&image);
+    brightness.iter().map(|&value| value <= level).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, Luma};
+    use puzzled_common::shape::shape_square;
+    use std::io::Cursor;
+
+    const MARGIN: u32 = 20;
+    const CELL_SIZE: u32 = 40;
+    const LINE_WIDTH: u32 = 3;
+
+    /// Draws a board with black grid lines on white paper. Blocked cells are filled dark gray.
+    fn draw_board(width: u32, height: u32, blocked: &[(u32, u32)]) -> GrayImage {
+        GrayImage::from_fn(
+            2 * MARGIN + width * CELL_SIZE + LINE_WIDTH,
+            2 * MARGIN + height * CELL_SIZE + LINE_WIDTH,
+            |x, y| {
+                if x < MARGIN || y < MARGIN {
+                    return Luma([255]);
+                }
+                let (board_x, board_y) = (x - MARGIN, y - MARGIN);
+                if board_x >= width * CELL_SIZE + LINE_WIDTH
+                    || board_y >= height * CELL_SIZE + LINE_WIDTH
+                {
+                    return Luma([255]);
+                }
+                if board_x % CELL_SIZE < LINE_WIDTH || board_y % CELL_SIZE < LINE_WIDTH {
+                    return Luma([0]);
+                }
+                if blocked.contains(&(board_x / CELL_SIZE, board_y / CELL_SIZE)) {
+                    Luma([60])
+                } else {
+                    Luma([235])
+                }
+            },
+        )
+    }
+
+    #[test]
+    fn test_digitize_board_image() {
+        let image = draw_board(4, 3, &[(1, 0), (3, 2)]);
+
+        let board = digitize_board_image(&image).unwrap();
+
+        assert_eq!(
+            board.layout(),
+            &shape_square(&[
+                [true, true, true],
+                [false, true, true],
+                [true, true, true],
+                [true, true, false],
+            ])
+        );
+    }
+
+    #[test]
+    fn test_digitize_board_without_blocked_cells() {
+        let image = draw_board(3, 2, &[]);
+
+        let board = digitize_board_image(&image).unwrap();
+
+        assert_eq!(board.layout(), &Shape::from_elem((3, 2), Square, true));
+    }
+
+    #[test]
+    fn test_digitize_board_from_png() {
+        let image = draw_board(2, 2, &[(0, 0)]);
+        let mut png = Vec::new();
+        image
+            .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+            .unwrap();
+
+        let board = digitize_board(&png).unwrap();
+
+        assert_eq!(
+            board.layout(),
+            &shape_square(&[[false, true], [true, true]])
+        );
+    }
+
+    #[test]
+    fn test_digitize_board_errors() {
+        let blank = GrayImage::from_pixel(100, 100, Luma([255]));
+
+        assert_eq!(
+            digitize_board_image(&blank),
+            Err(DigitizeError::GridNotFound)
+        );
+        assert!(matches!(
+            digitize_board(b"not an image"),
+            Err(DigitizeError::InvalidImage(_))
+        ));
+    }
+
+    #[test]
+    fn test_cell_boundaries_fill_missing_lines() {
+        let boundaries = cell_boundaries(&[10.0, 30.0, 31.0, 70.0, 90.0]).unwrap();
+
+        assert_eq!(boundaries, vec![10.0, 30.0, 50.0, 70.0, 90.0]);
+    }
+}
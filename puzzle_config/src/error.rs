@@ -4,7 +4,17 @@ pub enum ReadError {
     MissingVersion,
     MalformedVersion,
     UnsupportedVersion,
+    /// The collection has a newer format version than supported.
+    UnsupportedFormat(u64),
     JsonError(String),
+    /// The JSON could not be parsed or does not match the expected structure at the given
+    /// location. The path is a JSON pointer to the offending element.
+    InvalidJson {
+        message: String,
+        path: String,
+        line: usize,
+        column: usize,
+    },
     UnknownPredefinedTile {
         name: String,
     },
@@ -14,9 +24,91 @@ pub enum ReadError {
     },
     TileWidthOrHeightCannotBeZero,
     BoardWidthOrHeightCannotBeZero,
+    /// A tile or board layout contains a code, which does not stand for a cell.
+    /// The cell is given as `(x, y)` in the layout.
+    InvalidCellCode {
+        code: i32,
+        cell: (usize, usize),
+    },
+    /// The puzzle defines neither a `board` nor any `boards`, or both of them.
+    InvalidBoardCount {
+        puzzle_name: String,
+    },
+    /// The puzzle combines multiple boards, of which at least one is an area board.
+    AreaBoardInMultipleBoards {
+        puzzle_name: String,
+    },
+    /// The values or the value order of an area board do not have the same size as its area
+    /// layout.
+    AreaBoardSizeMismatch,
+    /// The area layout of a board references an area, which is not defined.
+    UnknownArea {
+        index: i32,
+        area_count: usize,
+    },
+    /// A constraint of the puzzle references a tile, which is not part of the puzzle.
+    UnknownConstraintTile {
+        puzzle_name: String,
+        tile: String,
+    },
+    /// A constraint of the puzzle references a cell, which is not on the board.
+    ConstraintCellOffBoard {
+        puzzle_name: String,
+        cell: (usize, usize),
+    },
+    /// The month formatter of an area does not define exactly one name per month.
+    InvalidMonthNames {
+        area_name: String,
+        count: usize,
+    },
+    /// The zero padded formatter of an area is wider than [crate::MAX_PADDED_WIDTH].
+    InvalidPaddedWidth {
+        area_name: String,
+        width: usize,
+    },
+    /// A custom tile or board references itself, directly or through other custom tiles or
+    /// boards.
+    ReferenceCycle {
+        name: String,
+    },
     InvalidVersion(String),
     InvalidCollectionId(String),
+    /// A puzzle has a blank id.
+    InvalidPuzzleId(String),
+    /// Multiple puzzles of the collection have the same id.
+    DuplicatePuzzleId(String),
+    /// The namespace of the collection is not in the format of a collection id.
+    InvalidNamespace(String),
+    /// The music of the collection is not an `https` or `resource` URI.
+    InvalidMusicUri(String),
     InvalidColor {
         message: String,
     },
+    ChecksumMismatch,
+    InvalidSignature,
+    TooManyPuzzles {
+        count: usize,
+        max: usize,
+    },
+    TooManyCustomTiles {
+        count: usize,
+        max: usize,
+    },
+    TooManyTiles {
+        puzzle_name: String,
+        count: usize,
+        max: usize,
+    },
+    TileTooLarge {
+        puzzle_name: String,
+        width: usize,
+        height: usize,
+        max: usize,
+    },
+    BoardTooLarge {
+        puzzle_name: String,
+        width: usize,
+        height: usize,
+        max: usize,
+    },
 }
@@ -1,17 +1,24 @@
+use crate::config::area::{ENGLISH_MONTH_NAMES, MAX_PADDED_WIDTH};
 use crate::config::board;
 use crate::config::color::ColorConfig;
+use crate::config::hints::HintConfig;
 use crate::config::preview::PreviewConfig;
+use crate::json::limits::LoadLimits;
 use crate::json::model::*;
 use crate::json::predefined::{Custom, Predefined};
 use crate::{
     validation, AreaConfig, AreaValueFormatter, BoardConfig, ProgressionConfig,
-    PuzzleConfig, PuzzleConfigCollection, PuzzleDifficultyConfig, ReadError, TargetTemplate,
-    TileConfig,
+    PuzzleConfig, PuzzleConfigCollection, PuzzleDifficultyConfig, ReadError, SolutionStatistics,
+    TargetTemplate, TileConfig, TileConstraint,
 };
 use ndarray::Array2;
+use puzzled_common::shape::cell::CellShape;
 use puzzled_common::Shape;
 use puzzled_common::ShapeType::Square;
+use std::collections::HashMap;
 use std::num::NonZero;
+use std::time::Duration;
+use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
 /// Trait for converting JSON model types to config types.
@@ -45,6 +52,13 @@ impl Convertable<PuzzleConfigCollection> for PuzzleCollection {
             }
         }
 
+        validation::validate_namespace(self.namespace)?;
+
+        let palette = self.palette.convert(predefined, custom)?;
+        custom.set_palette(palette.clone());
+
+        validation::validate_puzzle_ids(self.puzzles.iter().map(|puzzle| puzzle.id.as_str()))?;
+
         let mut puzzle_configs = Vec::new();
         for (i, puzzle) in self.puzzles.into_iter().enumerate() {
             let difficulty_config = puzzle.difficulty.convert(predefined, custom)?;
@@ -59,21 +73,39 @@ impl Convertable<PuzzleConfigCollection> for PuzzleCollection {
                 tiles.extend(converted_tile);
             }
 
-            let mut board_config = puzzle.board.convert(predefined, custom)?;
+            let mut board_config = convert_boards(
+                &puzzle.name,
+                puzzle.board,
+                puzzle.boards,
+                predefined,
+                custom,
+            )?;
+            let mut tile_constraints =
+                convert_constraints(&puzzle.name, puzzle.constraints, &tiles, &board_config)?;
             if self.allow_board_rotation {
+                let (width, height) = board_config.layout().dim();
+                if width < height {
+                    tile_constraints = tile_constraints
+                        .iter()
+                        .map(TileConstraint::transposed)
+                        .collect();
+                }
                 board_config = rotate_board(board_config);
             }
             let puzzle_config = PuzzleConfig::new(
                 i,
-                puzzle.id.unwrap_or_else(|| format!("{i}")),
+                puzzle.id,
                 puzzle.name,
                 puzzle.description,
                 difficulty_config,
                 puzzle.unsolvable,
                 tiles,
                 board_config,
+                puzzle.solution_statistics.convert(predefined, custom)?,
                 puzzle.additional_info,
-            );
+                puzzle.hints.convert(predefined, custom)?,
+            )
+            .with_tile_constraints(tile_constraints);
             puzzle_configs.push(puzzle_config);
         }
 
@@ -85,11 +117,319 @@ impl Convertable<PuzzleConfigCollection> for PuzzleCollection {
             self.version,
             self.progression.convert(predefined, custom)?,
             self.preview.convert(predefined, custom)?,
+            palette,
+            self.allow_board_rotation,
+            validation::validate_music_uri(self.music)?,
             puzzle_configs,
         ))
     }
 }
 
+/// The number of columns outside the board between two boards of a puzzle with multiple boards.
+const MULTIPLE_BOARDS_GAP: usize = 1;
+
+/// Converts the board or boards of a puzzle into a single board.
+///
+/// Multiple boards are combined into one simple board, in which they are placed next to each
+/// other from left to right, separated by a gap of cells outside the board. Tiles cannot be
+/// placed next to the board, so they cannot cover the gap and have to fill each board on its own.
+///
+/// # Arguments
+///
+/// * `puzzle_name`: the name of the puzzle for error messages
+/// * `board`: the `board` of the puzzle
+/// * `boards`: the `boards` of the puzzle
+/// * `predefined`: Predefined tiles and boards
+/// * `custom`: Custom tiles and boards
+///
+/// returns: Result<BoardConfig, ReadError>
+fn convert_boards(
+    puzzle_name: &str,
+    board: Option<Board>,
+    boards: Option<Vec<Board>>,
+    predefined: &Predefined,
+    custom: &mut Custom,
+) -> Result<BoardConfig, ReadError> {
+    let invalid_board_count = || ReadError::InvalidBoardCount {
+        puzzle_name: puzzle_name.to_string(),
+    };
+    let boards = match (board, boards) {
+        (Some(board), None) => return board.convert(predefined, custom),
+        (None, Some(boards)) if !boards.is_empty() => boards,
+        _ => return Err(invalid_board_count()),
+    };
+
+    let layouts = boards
+        .into_iter()
+        .map(|board| match board.convert(predefined, custom)? {
+            BoardConfig::Simple { layout } => Ok(layout),
+            BoardConfig::Area { .. } => Err(ReadError::AreaBoardInMultipleBoards {
+                puzzle_name: puzzle_name.to_string(),
+            }),
+        })
+        .collect::<Result<Vec<Shape>, ReadError>>()?;
+
+    let width = layouts.iter().map(|layout| layout.dim().0).sum::<usize>()
+        + MULTIPLE_BOARDS_GAP * (layouts.len() - 1);
+    let height = layouts
+        .iter()
+        .map(|layout| layout.dim().1)
+        .max()
+        .unwrap_or_default();
+    let mut combined = Shape::from_elem((width, height), Square, false);
+    let mut x_offset = 0;
+    for layout in &layouts {
+        combined = combined.or_at(layout, x_offset as isize, 0);
+        x_offset += layout.dim().0 + MULTIPLE_BOARDS_GAP;
+    }
+    Ok(BoardConfig::Simple { layout: combined })
+}
+
+/// Converts the constraints of a puzzle.
+///
+/// A constraint references a tile by its name or id. If multiple tiles of the puzzle match, the
+/// first one is used, since they are identical.
+///
+/// # Arguments
+///
+/// * `puzzle_name`: the name of the puzzle for error messages
+/// * `constraints`: the `constraints` of the puzzle
+/// * `tiles`: the converted tiles of the puzzle
+/// * `board_config`: the converted board of the puzzle, before it is rotated
+///
+/// returns: Result<Vec<TileConstraint>, ReadError>
+fn convert_constraints(
+    puzzle_name: &str,
+    constraints: Vec<Constraint>,
+    tiles: &[TileConfig],
+    board_config: &BoardConfig,
+) -> Result<Vec<TileConstraint>, ReadError> {
+    constraints
+        .into_iter()
+        .map(|constraint| {
+            let cell = (constraint.cell[0], constraint.cell[1]);
+            if board_config.layout().get(cell) != Some(&true) {
+                return Err(ReadError::ConstraintCellOffBoard {
+                    puzzle_name: puzzle_name.to_string(),
+                    cell,
+                });
+            }
+            let tile = tiles
+                .iter()
+                .find(|tile| {
+                    tile.name().as_deref() == Some(constraint.tile.as_str())
+                        || tile.id() == constraint.tile
+                })
+                .ok_or_else(|| ReadError::UnknownConstraintTile {
+                    puzzle_name: puzzle_name.to_string(),
+                    tile: constraint.tile.clone(),
+                })?;
+            Ok(TileConstraint::new(
+                cell,
+                constraint.tile,
+                tile.base().clone(),
+            ))
+        })
+        .collect()
+}
+
+/// Checks the limits of the collection before converting it, so the tiles and boards are never
+/// allocated if they are too large or too many.
+///
+/// References, which cannot be resolved, are skipped. They are reported when converting the
+/// collection.
+pub(crate) fn check_collection_limits(
+    collection: &PuzzleCollection,
+    predefined: &Predefined,
+    limits: &LoadLimits,
+) -> Result<(), ReadError> {
+    if collection.puzzles.len() > limits.max_puzzles {
+        return Err(ReadError::TooManyPuzzles {
+            count: collection.puzzles.len(),
+            max: limits.max_puzzles,
+        });
+    }
+    let custom_tile_count = collection
+        .custom_tiles
+        .as_ref()
+        .map_or(0, |tiles| tiles.len());
+    if custom_tile_count > limits.max_custom_tiles {
+        return Err(ReadError::TooManyCustomTiles {
+            count: custom_tile_count,
+            max: limits.max_custom_tiles,
+        });
+    }
+
+    let references = References {
+        custom_tiles: collection.custom_tiles.as_ref(),
+        custom_boards: collection.custom_boards.as_ref(),
+        predefined,
+    };
+    for puzzle in &collection.puzzles {
+        let count = puzzle.tiles.iter().fold(0usize, |count, tile| {
+            count.saturating_add(references.tile_count(tile, &mut Vec::new()))
+        });
+        if count > limits.max_tiles_per_puzzle {
+            return Err(ReadError::TooManyTiles {
+                puzzle_name: puzzle.name.clone(),
+                count,
+                max: limits.max_tiles_per_puzzle,
+            });
+        }
+
+        for tile in &puzzle.tiles {
+            if let Some((width, height)) = references.tile_size(tile, &mut Vec::new())
+                && (width > limits.max_tile_size || height > limits.max_tile_size)
+            {
+                return Err(ReadError::TileTooLarge {
+                    puzzle_name: puzzle.name.clone(),
+                    width,
+                    height,
+                    max: limits.max_tile_size,
+                });
+            }
+        }
+
+        if let Some((width, height)) = references.puzzle_board_size(puzzle)
+            && (width > limits.max_board_size || height > limits.max_board_size)
+        {
+            return Err(ReadError::BoardTooLarge {
+                puzzle_name: puzzle.name.clone(),
+                width,
+                height,
+                max: limits.max_board_size,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Resolves the tiles and boards referenced by a collection, which has not been converted yet.
+/// The custom tiles and boards take precedence over the predefined ones like when converting.
+struct References<'a> {
+    custom_tiles: Option<&'a HashMap<String, Tile>>,
+    custom_boards: Option<&'a HashMap<String, Board>>,
+    predefined: &'a Predefined,
+}
+
+impl<'a> References<'a> {
+    fn tile(&self, name: &str) -> Option<&'a Tile> {
+        self.custom_tiles
+            .and_then(|tiles| tiles.get(name))
+            .or_else(|| self.predefined.tile(name))
+    }
+
+    fn board(&self, name: &str) -> Option<&'a Board> {
+        self.custom_boards
+            .and_then(|boards| boards.get(name))
+            .or_else(|| self.predefined.board(name))
+    }
+
+    /// Returns the number of tiles the given tile is converted to.
+    /// `resolving` contains the names of the references followed to get to the tile, so cycles
+    /// are detected. Cycles are reported when converting the tile.
+    fn tile_count(&self, tile: &'a Tile, resolving: &mut Vec<&'a str>) -> usize {
+        match tile {
+            Tile::Ref(name) => self
+                .resolve(
+                    name,
+                    resolving,
+                    |name| self.tile(name),
+                    |tile, resolving| Some(self.tile_count(tile, resolving)),
+                )
+                .unwrap_or(1),
+            Tile::Layout(_) => 1,
+            Tile::Custom { count, .. } => count.map_or(1, |count| count.get() as usize),
+        }
+    }
+
+    /// Returns the width and height of the given tile.
+    fn tile_size(&self, tile: &'a Tile, resolving: &mut Vec<&'a str>) -> Option<(usize, usize)> {
+        let layout = match tile {
+            Tile::Ref(name) => {
+                return self.resolve(
+                    name,
+                    resolving,
+                    |name| self.tile(name),
+                    |tile, resolving| self.tile_size(tile, resolving),
+                );
+            }
+            Tile::Layout(layout) | Tile::Custom { layout, .. } => layout,
+        };
+        match layout {
+            TileLayout::Ref(name) => self.resolve(
+                name,
+                resolving,
+                |name| self.tile(name),
+                |tile, resolving| self.tile_size(tile, resolving),
+            ),
+            TileLayout::Custom(rows) => Some(layout_size(rows)),
+        }
+    }
+
+    /// Returns the width and height of the board, to which the board or boards of the puzzle are
+    /// combined.
+    fn puzzle_board_size(&self, puzzle: &'a Puzzle) -> Option<(usize, usize)> {
+        match (&puzzle.board, &puzzle.boards) {
+            (Some(board), None) => self.board_size(board, &mut Vec::new()),
+            (None, Some(boards)) if !boards.is_empty() => {
+                let sizes = boards
+                    .iter()
+                    .map(|board| self.board_size(board, &mut Vec::new()))
+                    .collect::<Option<Vec<(usize, usize)>>>()?;
+                let width = sizes.iter().fold(
+                    MULTIPLE_BOARDS_GAP.saturating_mul(sizes.len() - 1),
+                    |width, size| width.saturating_add(size.0),
+                );
+                let height = sizes.iter().map(|size| size.1).max().unwrap_or_default();
+                Some((width, height))
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the width and height of the given board.
+    fn board_size(&self, board: &'a Board, resolving: &mut Vec<&'a str>) -> Option<(usize, usize)> {
+        match board {
+            Board::Ref(name) => self
+                .resolve(
+                    name,
+                    resolving,
+                    |name| self.board(name),
+                    |board, resolving| self.board_size(board, resolving),
+                )
+                .or_else(|| board::predefined_board_size(name)),
+            Board::SimpleBoard { layout } => Some(layout_size(layout)),
+            Board::AreaBoard { area_layout, .. } => Some(layout_size(area_layout)),
+        }
+    }
+
+    /// Looks up the referenced tile or board with `get` and passes it to `resolve`.
+    /// Returns None, if the reference is unknown or part of a cycle.
+    fn resolve<T: 'a, R>(
+        &self,
+        name: &'a str,
+        resolving: &mut Vec<&'a str>,
+        get: impl FnOnce(&str) -> Option<&'a T>,
+        resolve: impl FnOnce(&'a T, &mut Vec<&'a str>) -> Option<R>,
+    ) -> Option<R> {
+        if resolving.contains(&name) {
+            return None;
+        }
+        let referenced = get(name)?;
+        resolving.push(name);
+        let result = resolve(referenced, resolving);
+        resolving.pop();
+        result
+    }
+}
+
+/// Returns the width and height of a layout given as rows.
+fn layout_size<T>(rows: &[Vec<T>]) -> (usize, usize) {
+    let width = rows.iter().map(Vec::len).max().unwrap_or_default();
+    (width, rows.len())
+}
+
 fn rotate_board_to_landscape<T>(arr: Array2<T>) -> Array2<T> {
     let dim = arr.dim();
     if dim.0 < dim.1 {
@@ -112,6 +452,8 @@ fn rotate_board(board: BoardConfig) -> BoardConfig {
             value_order,
             area_configs,
             target_template,
+            show_legend,
+            show_area_boundaries,
         } => {
             layout.rotate_to_landscape();
             let area_indices = Box::new(rotate_board_to_landscape(*area_indices));
@@ -124,6 +466,8 @@ fn rotate_board(board: BoardConfig) -> BoardConfig {
                 value_order,
                 area_configs,
                 target_template,
+                show_legend,
+                show_area_boundaries,
             }
         }
     }
@@ -135,12 +479,43 @@ impl Convertable<Option<PuzzleDifficultyConfig>> for Option<PuzzleDifficulty> {
         _: &Predefined,
         _: &mut Custom,
     ) -> Result<Option<PuzzleDifficultyConfig>, ReadError> {
-        Ok(self.map(|difficulty| match difficulty {
+        Ok(self.map(PuzzleDifficultyConfig::from))
+    }
+}
+
+impl From<PuzzleDifficulty> for PuzzleDifficultyConfig {
+    fn from(difficulty: PuzzleDifficulty) -> Self {
+        match difficulty {
             PuzzleDifficulty::Easy => PuzzleDifficultyConfig::Easy,
             PuzzleDifficulty::Medium => PuzzleDifficultyConfig::Medium,
             PuzzleDifficulty::Hard => PuzzleDifficultyConfig::Hard,
             PuzzleDifficulty::Expert => PuzzleDifficultyConfig::Expert,
-        }))
+        }
+    }
+}
+
+impl Convertable<Option<SolutionStatistics>> for Option<Statistics> {
+    fn convert(
+        self,
+        _: &Predefined,
+        _: &mut Custom,
+    ) -> Result<Option<SolutionStatistics>, ReadError> {
+        let Some(statistics) = self else {
+            return Ok(None);
+        };
+        let computed_on = statistics
+            .computed_on
+            .map(|computed_on| OffsetDateTime::parse(&computed_on, &Rfc3339))
+            .transpose()
+            .map_err(|e| ReadError::JsonError(format!("Invalid `computed_on` timestamp: {}", e)))?;
+        Ok(Some(SolutionStatistics::new(
+            statistics.min,
+            statistics.max,
+            statistics.median,
+            statistics.total,
+            statistics.targets,
+            computed_on,
+        )))
     }
 }
 
@@ -167,6 +542,19 @@ impl Convertable<PreviewConfig> for Option<Preview> {
     }
 }
 
+impl Convertable<HintConfig> for Option<Hints> {
+    fn convert(self, _: &Predefined, _: &mut Custom) -> Result<HintConfig, ReadError> {
+        match self {
+            None => Ok(HintConfig::default()),
+            Some(hints) => Ok(HintConfig::new(
+                hints.enabled,
+                hints.max,
+                hints.cooldown.map(Duration::from_secs),
+            )),
+        }
+    }
+}
+
 impl Convertable<Vec<TileConfig>> for (usize, Tile, Option<String>) {
     fn convert(
         self,
@@ -175,13 +563,13 @@ impl Convertable<Vec<TileConfig>> for (usize, Tile, Option<String>) {
     ) -> Result<Vec<TileConfig>, ReadError> {
         match self.1 {
             Tile::Ref(name) => {
-                if let Some(predefined_tile) = predefined.get_tile(&name) {
-                    (self.0, predefined_tile, Some(name)).convert(predefined, custom)
-                } else if let Some(custom_tile) = custom.get_tile(&name) {
-                    (self.0, custom_tile, Some(name)).convert(predefined, custom)
-                } else {
-                    Err(ReadError::UnknownPredefinedTile { name })
-                }
+                let tile = predefined
+                    .get_tile(&name)
+                    .or_else(|| custom.get_tile(&name))
+                    .ok_or_else(|| ReadError::UnknownPredefinedTile { name: name.clone() })?;
+                custom.resolve_reference(&name, |custom| {
+                    (self.0, tile, Some(name.clone())).convert(predefined, custom)
+                })
             }
             Tile::Layout(layout) => {
                 let (base, name) = (self.0, layout).convert(predefined, custom)?;
@@ -192,6 +580,7 @@ impl Convertable<Vec<TileConfig>> for (usize, Tile, Option<String>) {
                 layout,
                 color,
                 count,
+                id,
             } => {
                 let (base, name) = (self.0, layout).convert(predefined, custom)?;
                 let count = count.unwrap_or_else(|| NonZero::new(1).unwrap());
@@ -200,7 +589,11 @@ impl Convertable<Vec<TileConfig>> for (usize, Tile, Option<String>) {
                 for i in 0..count.get() {
                     let tile_index = self.0 + i as usize;
                     let color = (tile_index, color.clone()).convert(predefined, custom)?;
-                    tiles.push(TileConfig::new(base.clone(), color, name.clone()));
+                    let tile = TileConfig::new(base.clone(), color, name.clone());
+                    tiles.push(match &id {
+                        Some(id) => tile.with_id(id.clone()),
+                        None => tile,
+                    });
                 }
                 Ok(tiles)
             }
@@ -216,29 +609,14 @@ impl Convertable<(Shape, Option<String>)> for (usize, TileLayout) {
     ) -> Result<(Shape, Option<String>), ReadError> {
         match self.1 {
             TileLayout::Ref(name) => {
-                if let Some(custom_tile) = custom.get_tile(&name) {
-                    Ok((
-                        (self.0, custom_tile, Some(name.clone()))
-                            .convert(predefined, custom)?
-                            .first()
-                            .unwrap()
-                            .base()
-                            .clone(),
-                        Some(name),
-                    ))
-                } else if let Some(predefined_tile) = predefined.get_tile(&name) {
-                    Ok((
-                        (self.0, predefined_tile, Some(name.clone()))
-                            .convert(predefined, custom)?
-                            .first()
-                            .unwrap()
-                            .base()
-                            .clone(),
-                        Some(name),
-                    ))
-                } else {
-                    Err(ReadError::UnknownPredefinedTile { name })
-                }
+                let tile = custom
+                    .get_tile(&name)
+                    .or_else(|| predefined.get_tile(&name))
+                    .ok_or_else(|| ReadError::UnknownPredefinedTile { name: name.clone() })?;
+                let base = custom.resolve_reference(&name, |custom| {
+                    tile_layout(self.0, tile, predefined, custom)
+                })?;
+                Ok((base, Some(name)))
             }
             TileLayout::Custom(array) => {
                 let height = array.len();
@@ -246,11 +624,15 @@ impl Convertable<(Shape, Option<String>)> for (usize, TileLayout) {
                     return Err(ReadError::TileWidthOrHeightCannotBeZero);
                 }
                 let width = array[0].len();
+                if width == 0 {
+                    return Err(ReadError::TileWidthOrHeightCannotBeZero);
+                }
                 for row in &array {
                     if row.len() != width {
                         return Err(ReadError::TileWidthOrHeightCannotBeZero);
                     }
                 }
+                check_cell_codes(&array)?;
                 let mut base = Shape::from_elem((height, width), Square, false);
                 for (i, row) in array.iter().enumerate() {
                     for (j, &value) in row.iter().enumerate() {
@@ -258,16 +640,38 @@ impl Convertable<(Shape, Option<String>)> for (usize, TileLayout) {
                     }
                 }
                 base.transpose();
+                for (i, row) in array.iter().enumerate() {
+                    for (j, &value) in row.iter().enumerate() {
+                        if let Some(cell_shape) = half_cell_from_code(value as i32) {
+                            base.set_cell_shape((j, i), cell_shape);
+                        }
+                    }
+                }
                 Ok((base, None))
             }
         }
     }
 }
 
+/// Converts only the layout of the given tile.
+/// Unlike converting the whole tile, this does not create a config for each copy of the tile.
+fn tile_layout(
+    index: usize,
+    tile: Tile,
+    predefined: &Predefined,
+    custom: &mut Custom,
+) -> Result<Shape, ReadError> {
+    let layout = match tile {
+        Tile::Ref(name) => TileLayout::Ref(name),
+        Tile::Layout(layout) | Tile::Custom { layout, .. } => layout,
+    };
+    Ok((index, layout).convert(predefined, custom)?.0)
+}
+
 impl Convertable<ColorConfig> for (usize, Option<Color>) {
-    fn convert(self, _: &Predefined, _: &mut Custom) -> Result<ColorConfig, ReadError> {
+    fn convert(self, _: &Predefined, custom: &mut Custom) -> Result<ColorConfig, ReadError> {
         match self.1 {
-            None => Ok(ColorConfig::default_with_index(self.0)),
+            None => Ok(custom.color_with_index(self.0)),
             Some(Color::Hex(hex)) => {
                 ColorConfig::try_from(hex).map_err(|e| ReadError::InvalidColor { message: e })
             }
@@ -275,6 +679,19 @@ impl Convertable<ColorConfig> for (usize, Option<Color>) {
     }
 }
 
+impl Convertable<Vec<ColorConfig>> for Option<Vec<Color>> {
+    fn convert(self, _: &Predefined, _: &mut Custom) -> Result<Vec<ColorConfig>, ReadError> {
+        self.unwrap_or_default()
+            .into_iter()
+            .map(|color| match color {
+                Color::Hex(hex) => {
+                    ColorConfig::try_from(hex).map_err(|e| ReadError::InvalidColor { message: e })
+                }
+            })
+            .collect()
+    }
+}
+
 impl Convertable<BoardConfig> for Board {
     fn convert(
         self,
@@ -283,10 +700,11 @@ impl Convertable<BoardConfig> for Board {
     ) -> Result<BoardConfig, ReadError> {
         match self {
             Board::Ref(name) => {
-                if let Some(custom_board) = custom.get_board(&name) {
-                    Ok(custom_board.convert(predefined, custom)?)
-                } else if let Some(predefined_board) = predefined.get_board(&name) {
-                    Ok(predefined_board.convert(predefined, custom)?)
+                if let Some(board) = custom
+                    .get_board(&name)
+                    .or_else(|| predefined.get_board(&name))
+                {
+                    custom.resolve_reference(&name, |custom| board.convert(predefined, custom))
                 } else if let Some(predefined_board) = board::from_predefined_board(&name) {
                     Ok(predefined_board)
                 } else {
@@ -302,11 +720,15 @@ impl Convertable<BoardConfig> for Board {
                     return Err(ReadError::BoardWidthOrHeightCannotBeZero);
                 }
                 let width = layout[0].len();
+                if width == 0 {
+                    return Err(ReadError::BoardWidthOrHeightCannotBeZero);
+                }
                 for row in &layout {
                     if row.len() != width {
                         return Err(ReadError::BoardWidthOrHeightCannotBeZero);
                     }
                 }
+                check_cell_codes(&layout)?;
                 let mut array = Shape::from_elem((height, width), Square, false);
                 for (i, row) in layout.iter().enumerate() {
                     for (j, &value) in row.iter().enumerate() {
@@ -314,6 +736,14 @@ impl Convertable<BoardConfig> for Board {
                     }
                 }
                 array.transpose();
+                for (i, row) in layout.iter().enumerate() {
+                    for (j, &value) in row.iter().enumerate() {
+                        if let Some(cell_shape) = half_cell_from_code(value as i32) {
+                            array[(j, i)] = true;
+                            array.set_cell_shape((j, i), cell_shape);
+                        }
+                    }
+                }
                 Ok(BoardConfig::Simple { layout: array })
             }
             Board::AreaBoard {
@@ -322,6 +752,8 @@ impl Convertable<BoardConfig> for Board {
                 value_order,
                 areas,
                 target_template,
+                legend,
+                area_boundaries,
             } => {
                 let area_configs = areas
                     .into_iter()
@@ -334,6 +766,9 @@ impl Convertable<BoardConfig> for Board {
                         return Err(ReadError::BoardWidthOrHeightCannotBeZero);
                     }
                     let width = area_layout[0].len();
+                    if width == 0 {
+                        return Err(ReadError::BoardWidthOrHeightCannotBeZero);
+                    }
                     for row in &area_layout {
                         if row.len() != width {
                             return Err(ReadError::BoardWidthOrHeightCannotBeZero);
@@ -350,6 +785,21 @@ impl Convertable<BoardConfig> for Board {
                     array
                 };
 
+                let (width, height) = board_layout.dim();
+                if !has_size(&values, width, height) || !has_size(&value_order, width, height) {
+                    return Err(ReadError::AreaBoardSizeMismatch);
+                }
+                if let Some(&index) = area_layout
+                    .iter()
+                    .flatten()
+                    .find(|&&index| index >= 0 && index as usize >= area_configs.len())
+                {
+                    return Err(ReadError::UnknownArea {
+                        index,
+                        area_count: area_configs.len(),
+                    });
+                }
+
                 Ok(BoardConfig::Area {
                     layout: Box::new(board_layout),
                     area_indices: Box::new(vec_vec_to_array2(&area_layout).reversed_axes()),
@@ -357,12 +807,49 @@ impl Convertable<BoardConfig> for Board {
                     value_order: Box::new(vec_vec_to_array2(&value_order).reversed_axes()),
                     area_configs,
                     target_template: TargetTemplate::new(&target_template),
+                    show_legend: legend,
+                    show_area_boundaries: area_boundaries,
                 })
             }
         }
     }
 }
 
+/// Checks that the tile or board layout only contains the codes `0` and `1` for empty and full
+/// cells and `2` to `5` for half cells.
+///
+/// returns: Result<(), ReadError> `InvalidCellCode` with the position `(x, y)` of the first cell
+/// with another code
+fn check_cell_codes<T: Copy + Into<i32>>(layout: &[Vec<T>]) -> Result<(), ReadError> {
+    for (y, row) in layout.iter().enumerate() {
+        for (x, &code) in row.iter().enumerate() {
+            let code = code.into();
+            if !(0..=5).contains(&code) {
+                return Err(ReadError::InvalidCellCode { code, cell: (x, y) });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the half cell for the given code in a tile or board layout.
+/// The codes `2` to `5` are the halves with their right angle in the top left, top right, bottom
+/// right and bottom left corner. All other codes are full or empty cells.
+fn half_cell_from_code(code: i32) -> Option<CellShape> {
+    match code {
+        2 => Some(CellShape::TopLeft),
+        3 => Some(CellShape::TopRight),
+        4 => Some(CellShape::BottomRight),
+        5 => Some(CellShape::BottomLeft),
+        _ => None,
+    }
+}
+
+/// Whether the given rows are all `width` long and there are `height` rows.
+fn has_size<T>(data: &[Vec<T>], width: usize, height: usize) -> bool {
+    data.len() == height && data.iter().all(|row| row.len() == width)
+}
+
 fn vec_vec_to_array2<T: Clone + Default>(data: &[Vec<T>]) -> Array2<T> {
     let height = data.len();
     let width = if height > 0 { data[0].len() } else { 0 };
@@ -387,6 +874,27 @@ impl Convertable<AreaConfig> for Area {
             AreaFormatter::PrefixSuffix { prefix, suffix } => {
                 AreaValueFormatter::PrefixSuffix { prefix, suffix }
             }
+            AreaFormatter::Roman => AreaValueFormatter::Roman,
+            AreaFormatter::LocalizedMonth { names } => {
+                let names = match names {
+                    Some(names) if names.len() != ENGLISH_MONTH_NAMES.len() => {
+                        return Err(ReadError::InvalidMonthNames {
+                            area_name: self.name,
+                            count: names.len(),
+                        });
+                    }
+                    Some(names) => names,
+                    None => custom.month_names(),
+                };
+                AreaValueFormatter::LocalizedMonth { names }
+            }
+            AreaFormatter::ZeroPadded { width } if width > MAX_PADDED_WIDTH => {
+                return Err(ReadError::InvalidPaddedWidth {
+                    area_name: self.name,
+                    width,
+                });
+            }
+            AreaFormatter::ZeroPadded { width } => AreaValueFormatter::ZeroPadded { width },
         };
 
         Ok(AreaConfig::new(
@@ -497,6 +1005,103 @@ mod tests {
         assert_eq!(*converted_tile.first().unwrap(), expected_tile);
     }
 
+    #[test]
+    fn test_convert_tile_with_count() {
+        let mut predefined = Predefined::default();
+        predefined.add_tile(
+            "O4".to_string(),
+            Tile::Layout(TileLayout::Custom(vec![vec![1, 1], vec![1, 1]])),
+        );
+
+        let tile: Tile = serde_json::from_str(r#"{"tile": "O4", "count": 5}"#).unwrap();
+        let converted_tiles = (2, tile, None)
+            .convert(&predefined, &mut Custom::default())
+            .unwrap();
+
+        assert_eq!(converted_tiles.len(), 5);
+        let expected_base = shape_square(&[[true, true], [true, true]]);
+        for (i, converted_tile) in converted_tiles.iter().enumerate() {
+            assert_eq!(converted_tile.base(), &expected_base);
+            assert_eq!(
+                converted_tile.color(),
+                ColorConfig::default_with_index(2 + i)
+            );
+        }
+    }
+
+    #[test]
+    fn test_convert_tile_with_id() {
+        let tile: Tile =
+            serde_json::from_str(r#"{"layout": [[1, 1]], "id": "bar", "count": 2}"#).unwrap();
+        let converted_tiles = (0, tile, None)
+            .convert(&Predefined::default(), &mut Custom::default())
+            .unwrap();
+
+        let ids: Vec<&str> = converted_tiles.iter().map(TileConfig::id).collect();
+        assert_eq!(ids, vec!["bar", "bar"]);
+    }
+
+    #[test]
+    fn test_convert_custom_tile_with_half_cells() {
+        let tile = Tile::Layout(TileLayout::Custom(vec![vec![1, 3], vec![0, 1]]));
+        let converted_tile = (0, tile, None)
+            .convert(&Predefined::default(), &mut Custom::default())
+            .unwrap();
+        let base = converted_tile.first().unwrap().base();
+        assert_eq!(base, &{
+            let mut expected = shape_square(&[[true, false], [true, true]]);
+            expected.set_cell_shape((1, 0), CellShape::TopRight);
+            expected
+        });
+        assert_eq!(base.cell_shape((0, 0)), CellShape::Full);
+    }
+
+    #[test]
+    fn test_convert_simple_board_with_half_cells() {
+        let board = Board::SimpleBoard {
+            layout: vec![vec![0, 5], vec![1, 0]],
+        };
+        let converted_board = board
+            .convert(&Predefined::default(), &mut Custom::default())
+            .unwrap();
+        let layout = converted_board.layout();
+        assert!(layout[(1, 0)]);
+        assert_eq!(layout.cell_shape((1, 0)), CellShape::BottomLeft);
+        assert!(!layout[(0, 1)]);
+        assert_eq!(layout.cell_shape((0, 0)), CellShape::Full);
+    }
+
+    #[test]
+    fn test_convert_custom_tile_invalid_cell_code() {
+        for (layout, code, cell) in [
+            (vec![vec![1, 0], vec![1, 9]], 9, (1, 1)),
+            (vec![vec![-1, 1]], -1, (0, 0)),
+        ] {
+            let tile = Tile::Layout(TileLayout::Custom(layout));
+            let converted_tile =
+                (0, tile, None).convert(&Predefined::default(), &mut Custom::default());
+            assert_eq!(
+                converted_tile.err().unwrap(),
+                ReadError::InvalidCellCode { code, cell }
+            );
+        }
+    }
+
+    #[test]
+    fn test_convert_simple_board_invalid_cell_code() {
+        let board = Board::SimpleBoard {
+            layout: vec![vec![0, 0, 7], vec![0, 1, 0]],
+        };
+        let converted_board = board.convert(&Predefined::default(), &mut Custom::default());
+        assert_eq!(
+            converted_board.err().unwrap(),
+            ReadError::InvalidCellCode {
+                code: 7,
+                cell: (2, 0)
+            }
+        );
+    }
+
     #[test]
     fn test_convert_custom_tile_zero_dimension() {
         let tile = Tile::Layout(TileLayout::Custom(vec![]));
@@ -516,5 +1121,580 @@ mod tests {
             converted_tile.err().unwrap(),
             ReadError::TileWidthOrHeightCannotBeZero
         );
+
+        let tile = Tile::Layout(TileLayout::Custom(vec![vec![]]));
+        let converted_tile = (0, tile, Some("L3".to_string()))
+            .convert(&Predefined::default(), &mut Custom::default());
+        assert_eq!(
+            converted_tile.err().unwrap(),
+            ReadError::TileWidthOrHeightCannotBeZero
+        );
+    }
+
+    fn create_area_board(area_layout: Vec<Vec<i32>>, values: Vec<Vec<&str>>) -> Board {
+        let value_order = area_layout.clone();
+        Board::AreaBoard {
+            area_layout,
+            values: values
+                .into_iter()
+                .map(|row| row.into_iter().map(str::to_string).collect())
+                .collect(),
+            value_order,
+            areas: vec![Area {
+                name: "Day".to_string(),
+                formatter: AreaFormatter::Plain,
+                default_factory: DefaultFactory::Fixed {
+                    value: "1".to_string(),
+                },
+            }],
+            target_template: "{0}".to_string(),
+            legend: false,
+            area_boundaries: false,
+        }
+    }
+
+    #[test]
+    fn test_convert_multiple_boards() {
+        let boards = vec![
+            Board::SimpleBoard {
+                layout: vec![vec![0, 0], vec![0, 1]],
+            },
+            Board::SimpleBoard {
+                layout: vec![vec![0], vec![2], vec![0]],
+            },
+        ];
+
+        let board_config = convert_boards(
+            "Puzzle",
+            None,
+            Some(boards),
+            &Predefined::default(),
+            &mut Custom::default(),
+        )
+        .unwrap();
+
+        let layout = board_config.layout();
+        assert_eq!(layout.dim(), (4, 3));
+        assert!(layout[(0, 0)] && layout[(1, 0)] && layout[(0, 1)]);
+        assert!(!layout[(1, 1)] && !layout[(0, 2)] && !layout[(1, 2)]);
+        assert!((0..3).all(|y| !layout[(2, y)]));
+        assert!(layout[(3, 0)] && layout[(3, 2)]);
+        assert_eq!(layout.cell_shape((3, 1)), CellShape::TopLeft);
+    }
+
+    #[test]
+    fn test_convert_multiple_boards_invalid() {
+        let simple_board = Board::SimpleBoard {
+            layout: vec![vec![0]],
+        };
+        let expected_count_error = Err(ReadError::InvalidBoardCount {
+            puzzle_name: "Puzzle".to_string(),
+        });
+        let convert = |board, boards| {
+            convert_boards(
+                "Puzzle",
+                board,
+                boards,
+                &Predefined::default(),
+                &mut Custom::default(),
+            )
+        };
+
+        assert_eq!(convert(None, None), expected_count_error);
+        assert_eq!(convert(None, Some(Vec::new())), expected_count_error);
+        assert_eq!(
+            convert(Some(simple_board.clone()), Some(vec![simple_board.clone()])),
+            expected_count_error
+        );
+        assert_eq!(
+            convert(
+                None,
+                Some(vec![
+                    simple_board,
+                    create_area_board(vec![vec![0]], vec![vec!["1"]]),
+                ])
+            ),
+            Err(ReadError::AreaBoardInMultipleBoards {
+                puzzle_name: "Puzzle".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_convert_area_board_size_mismatch() {
+        let board = create_area_board(vec![vec![0, 0]], vec![vec!["1"], vec!["2", "3"]]);
+        assert_eq!(
+            board
+                .convert(&Predefined::default(), &mut Custom::default())
+                .err(),
+            Some(ReadError::AreaBoardSizeMismatch)
+        );
+
+        let board = create_area_board(vec![vec![0, 0]], vec![vec!["1"]]);
+        assert_eq!(
+            board
+                .convert(&Predefined::default(), &mut Custom::default())
+                .err(),
+            Some(ReadError::AreaBoardSizeMismatch)
+        );
+
+        let board = create_area_board(vec![vec![0, -1]], vec![vec!["1", ""]]);
+        assert!(board
+            .convert(&Predefined::default(), &mut Custom::default())
+            .is_ok());
+    }
+
+    #[test]
+    fn test_convert_area_board_unknown_area() {
+        let board = create_area_board(vec![vec![0, 5]], vec![vec!["1", "2"]]);
+        assert_eq!(
+            board
+                .convert(&Predefined::default(), &mut Custom::default())
+                .err(),
+            Some(ReadError::UnknownArea {
+                index: 5,
+                area_count: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_convert_area_formatter() {
+        let area = |formatter: &str| -> Area {
+            serde_json::from_str(&format!(
+                r#"{{"name": "Month", "formatter": {},
+                    "default_factory": {{"type": "Fixed", "value": "1"}}}}"#,
+                formatter
+            ))
+            .unwrap()
+        };
+        let convert = |area: Area| area.convert(&Predefined::default(), &mut Custom::default());
+
+        assert_eq!(
+            convert(area(r#"{"type": "Roman"}"#)).unwrap().formatter(),
+            &AreaValueFormatter::Roman
+        );
+        assert_eq!(
+            convert(area(r#"{"type": "ZeroPadded", "width": 2}"#))
+                .unwrap()
+                .formatter(),
+            &AreaValueFormatter::ZeroPadded { width: 2 }
+        );
+        assert_eq!(
+            convert(area(r#"{"type": "LocalizedMonth"}"#))
+                .unwrap()
+                .formatter(),
+            &AreaValueFormatter::LocalizedMonth {
+                names: ENGLISH_MONTH_NAMES.map(str::to_string).to_vec()
+            }
+        );
+        assert_eq!(
+            convert(area(r#"{"type": "LocalizedMonth", "names": ["Januar"]}"#)),
+            Err(ReadError::InvalidMonthNames {
+                area_name: "Month".to_string(),
+                count: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_convert_area_formatter_locale_month_names() {
+        let area: Area = serde_json::from_str(
+            r#"{"name": "Month", "formatter": {"type": "LocalizedMonth"},
+                "default_factory": {"type": "Fixed", "value": "1"}}"#,
+        )
+        .unwrap();
+        let names = [
+            "Januar",
+            "Februar",
+            "März",
+            "April",
+            "Mai",
+            "Juni",
+            "Juli",
+            "August",
+            "September",
+            "Oktober",
+            "November",
+            "Dezember",
+        ]
+        .map(str::to_string);
+        let mut custom = Custom::default();
+        custom.set_month_names(Some(names.clone()));
+
+        assert_eq!(
+            area.convert(&Predefined::default(), &mut custom)
+                .unwrap()
+                .formatter(),
+            &AreaValueFormatter::LocalizedMonth {
+                names: names.to_vec()
+            }
+        );
+    }
+
+    #[test]
+    fn test_convert_area_formatter_padded_width() {
+        let area = |width: usize| -> Area {
+            serde_json::from_str(&format!(
+                r#"{{"name": "Day", "formatter": {{"type": "ZeroPadded", "width": {}}},
+                    "default_factory": {{"type": "Fixed", "value": "1"}}}}"#,
+                width
+            ))
+            .unwrap()
+        };
+        let convert = |area: Area| area.convert(&Predefined::default(), &mut Custom::default());
+
+        assert_eq!(
+            convert(area(MAX_PADDED_WIDTH)).unwrap().formatter(),
+            &AreaValueFormatter::ZeroPadded {
+                width: MAX_PADDED_WIDTH
+            }
+        );
+        assert_eq!(
+            convert(area(70000)),
+            Err(ReadError::InvalidPaddedWidth {
+                area_name: "Day".to_string(),
+                width: 70000
+            })
+        );
+    }
+
+    #[test]
+    fn test_convert_area_board_legend() {
+        let board: Board = serde_json::from_str(
+            r#"{"area_layout": [[0, 1]], "values": [["1", "A"]], "value_order": [[0, 0]],
+                "areas": [
+                    {"name": "Day", "formatter": {"type": "Plain"},
+                     "default_factory": {"type": "Fixed", "value": "1"}},
+                    {"name": "Letter", "formatter": {"type": "Plain"},
+                     "default_factory": {"type": "Fixed", "value": "A"}}
+                ],
+                "target_template": "{0} {1}", "legend": true, "area_boundaries": true}"#,
+        )
+        .unwrap();
+        let board = board
+            .convert(&Predefined::default(), &mut Custom::default())
+            .unwrap();
+        assert!(matches!(
+            board,
+            BoardConfig::Area {
+                show_legend: true,
+                show_area_boundaries: true,
+                ..
+            }
+        ));
+
+        let board = create_area_board(vec![vec![0]], vec![vec!["1"]])
+            .convert(&Predefined::default(), &mut Custom::default())
+            .unwrap();
+        assert!(matches!(
+            board,
+            BoardConfig::Area {
+                show_legend: false,
+                show_area_boundaries: false,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_convert_reference_cycle() {
+        let mut custom = Custom::default();
+        custom.add_tile("A".to_string(), Tile::Ref("B".to_string()));
+        custom.add_tile(
+            "B".to_string(),
+            Tile::Layout(TileLayout::Ref("A".to_string())),
+        );
+        custom.add_board("C".to_string(), Board::Ref("C".to_string()));
+
+        let converted_tile =
+            (0, Tile::Ref("A".to_string()), None).convert(&Predefined::default(), &mut custom);
+        assert_eq!(
+            converted_tile.err(),
+            Some(ReadError::ReferenceCycle {
+                name: "A".to_string()
+            })
+        );
+        let converted_board =
+            Board::Ref("C".to_string()).convert(&Predefined::default(), &mut custom);
+        assert_eq!(
+            converted_board.err(),
+            Some(ReadError::ReferenceCycle {
+                name: "C".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_convert_same_reference_twice() {
+        let mut custom = Custom::default();
+        custom.add_tile(
+            "A".to_string(),
+            Tile::Layout(TileLayout::Custom(vec![vec![1]])),
+        );
+        custom.add_tile(
+            "B".to_string(),
+            Tile::Custom {
+                layout: TileLayout::Ref("A".to_string()),
+                color: None,
+                count: NonZero::new(2),
+                id: None,
+            },
+        );
+
+        for _ in 0..2 {
+            let converted_tile = (0, Tile::Ref("B".to_string()), None)
+                .convert(&Predefined::default(), &mut custom)
+                .unwrap();
+            assert_eq!(converted_tile.len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_convert_collection_with_palette() {
+        let collection: PuzzleCollection = serde_json::from_str(
+            r##"{"name": "Test", "author": "Test", "id": "a.b",
+                "palette": ["#ff0000", "#00ff00"],
+                "puzzles": [
+                    {"id": "a", "name": "A", "tiles": [[[1]], [[1]], {"layout": [[1]], "color": "#0000ff"}, [[1]]],
+                    "board": {"layout": [[0, 0, 0, 0]]}}
+                ]}"##,
+        )
+        .unwrap();
+
+        let collection = collection
+            .convert(&Predefined::default(), &mut Custom::default())
+            .unwrap();
+
+        let red = ColorConfig::from_rgb_hex(0xff0000);
+        let green = ColorConfig::from_rgb_hex(0x00ff00);
+        assert_eq!(collection.palette(), &[red, green]);
+        let colors: Vec<ColorConfig> = collection.puzzles()[0]
+            .tiles()
+            .iter()
+            .map(|tile| tile.color())
+            .collect();
+        assert_eq!(
+            colors,
+            vec![red, green, ColorConfig::from_rgb_hex(0x0000ff), green]
+        );
+    }
+
+    #[test]
+    fn test_convert_collection_with_hints() {
+        let collection: PuzzleCollection = serde_json::from_str(
+            r##"{"name": "Test", "author": "Test", "id": "a.b",
+                "puzzles": [
+                    {"id": "a", "name": "A", "tiles": [[[1]]], "board": {"layout": [[0]]},
+                    "hints": {"max": 3, "cooldown": 30}},
+                    {"id": "b", "name": "B", "tiles": [[[1]]], "board": {"layout": [[0]]},
+                    "hints": {"enabled": false}},
+                    {"id": "c", "name": "C", "tiles": [[[1]]], "board": {"layout": [[0]]}}
+                ]}"##,
+        )
+        .unwrap();
+
+        let collection = collection
+            .convert(&Predefined::default(), &mut Custom::default())
+            .unwrap();
+
+        let hint_configs: Vec<&HintConfig> = collection
+            .puzzles()
+            .iter()
+            .map(|puzzle| puzzle.hint_config())
+            .collect();
+        assert_eq!(
+            hint_configs,
+            vec![
+                &HintConfig::new(true, Some(3), Some(Duration::from_secs(30))),
+                &HintConfig::new(false, None, None),
+                &HintConfig::default(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_convert_collection_with_constraints() {
+        let convert = |board: &str, allow_board_rotation: bool, constraints: &str| {
+            let collection: PuzzleCollection = serde_json::from_str(&format!(
+                r#"{{"name": "Test", "author": "Test", "id": "a.b",
+                    "allow_board_rotation": {},
+                    "custom_tiles": {{"D": [[1, 1]]}},
+                    "puzzles": [
+                        {{"id": "a", "name": "A", "tiles": ["D", [[1]]], "board": {{"layout": {}}},
+                        "constraints": {}}}
+                    ]}}"#,
+                allow_board_rotation, board, constraints
+            ))
+            .unwrap();
+            collection
+                .convert(&Predefined::default(), &mut Custom::default())
+                .map(|collection| collection.puzzles()[0].tile_constraints().to_vec())
+        };
+        let domino = shape_square(&[[true], [true]]);
+
+        assert_eq!(
+            convert("[[0, 0, 0]]", false, r#"[{"cell": [1, 0], "tile": "D"}]"#),
+            Ok(vec![TileConstraint::new(
+                (1, 0),
+                "D".to_string(),
+                domino.clone()
+            )])
+        );
+        assert_eq!(
+            convert(
+                "[[0], [0], [0]]",
+                true,
+                r#"[{"cell": [0, 2], "tile": "D"}]"#
+            ),
+            Ok(vec![TileConstraint::new((2, 0), "D".to_string(), domino)])
+        );
+        assert_eq!(
+            convert("[[0, 0, 0]]", false, r#"[{"cell": [3, 0], "tile": "D"}]"#),
+            Err(ReadError::ConstraintCellOffBoard {
+                puzzle_name: "A".to_string(),
+                cell: (3, 0)
+            })
+        );
+        assert_eq!(
+            convert("[[0, 0, 0]]", false, r#"[{"cell": [0, 0], "tile": "L3"}]"#),
+            Err(ReadError::UnknownConstraintTile {
+                puzzle_name: "A".to_string(),
+                tile: "L3".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_convert_palette_invalid_color() {
+        let palette = Some(vec![Color::Hex("red".to_string())]);
+        assert!(matches!(
+            palette.convert(&Predefined::default(), &mut Custom::default()),
+            Err(ReadError::InvalidColor { .. })
+        ));
+    }
+
+    #[test]
+    fn test_check_collection_limits() {
+        let collection: PuzzleCollection = serde_json::from_str(
+            r#"{"name": "Test", "author": "Test", "id": "a.b", "puzzles": [
+                {"id": "a", "name": "A", "tiles": [], "board": {"layout": [[0]]}},
+                {"id": "b", "name": "B", "tiles": [], "board": {"layout": [[0]]}}
+            ]}"#,
+        )
+        .unwrap();
+        let limits = LoadLimits {
+            max_puzzles: 1,
+            ..LoadLimits::default()
+        };
+
+        assert!(check_collection_limits(
+            &collection,
+            &Predefined::default(),
+            &LoadLimits::default()
+        )
+        .is_ok());
+        assert_eq!(
+            check_collection_limits(&collection, &Predefined::default(), &limits),
+            Err(ReadError::TooManyPuzzles { count: 2, max: 1 })
+        );
+    }
+
+    #[test]
+    fn test_check_collection_limits_tile_count() {
+        let collection: PuzzleCollection = serde_json::from_str(
+            r#"{"name": "Test", "author": "Test", "id": "a.b",
+                "custom_tiles": {"A": {"layout": [[1]], "count": 4000000000}, "B": "A", "C": "C"},
+                "puzzles": [
+                    {"id": "a", "name": "A", "tiles": ["C", [[1]], {"layout": [[1]], "count": 3}],
+                    "board": {"layout": [[0]]}},
+                    {"id": "b", "name": "B", "tiles": ["B"], "board": {"layout": [[0]]}}
+            ]}"#,
+        )
+        .unwrap();
+        let limits = LoadLimits {
+            max_tiles_per_puzzle: 5,
+            ..LoadLimits::default()
+        };
+
+        assert_eq!(
+            check_collection_limits(&collection, &Predefined::default(), &limits),
+            Err(ReadError::TooManyTiles {
+                puzzle_name: "B".to_string(),
+                count: 4000000000,
+                max: 5
+            })
+        );
+    }
+
+    fn create_limit_test_collection(puzzle: &str) -> PuzzleCollection {
+        serde_json::from_str(&format!(
+            r#"{{"name": "Test", "author": "Test", "id": "a.b",
+                "custom_tiles": {{"Long": [[1, 1, 1]], "Cycle": "Cycle"}},
+                "custom_boards": {{"Wide": {{"layout": [[0, 0, 0, 0, 0]]}}}},
+                "puzzles": [{}]}}"#,
+            puzzle
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_check_collection_limits_size() {
+        let limits = LoadLimits {
+            max_tile_size: 2,
+            max_board_size: 4,
+            ..LoadLimits::default()
+        };
+        let check = |puzzle: &str| {
+            check_collection_limits(
+                &create_limit_test_collection(puzzle),
+                &Predefined::default(),
+                &limits,
+            )
+        };
+
+        assert!(check(
+            r#"{"id": "a", "name": "A", "tiles": [[[1], [1]], "Cycle"], "board": "4x2"}"#
+        )
+        .is_ok());
+        assert_eq!(
+            check(r#"{"id": "a", "name": "A", "tiles": ["Long"], "board": "4x2"}"#),
+            Err(ReadError::TileTooLarge {
+                puzzle_name: "A".to_string(),
+                width: 3,
+                height: 1,
+                max: 2
+            })
+        );
+        assert_eq!(
+            check(r#"{"id": "a", "name": "A", "tiles": [], "board": "Wide"}"#),
+            Err(ReadError::BoardTooLarge {
+                puzzle_name: "A".to_string(),
+                width: 5,
+                height: 1,
+                max: 4
+            })
+        );
+        assert_eq!(
+            check(r#"{"id": "a", "name": "A", "tiles": [], "board": "2x100000"}"#),
+            Err(ReadError::BoardTooLarge {
+                puzzle_name: "A".to_string(),
+                width: 2,
+                height: 100000,
+                max: 4
+            })
+        );
+        assert_eq!(
+            check(
+                r#"{"id": "a", "name": "A", "tiles": [],
+                    "boards": [{"layout": [[0, 0]]}, {"layout": [[0, 0]]}]}"#
+            ),
+            Err(ReadError::BoardTooLarge {
+                puzzle_name: "A".to_string(),
+                width: 5,
+                height: 1,
+                max: 4
+            })
+        );
     }
 }
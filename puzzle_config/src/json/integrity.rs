@@ -0,0 +1,374 @@
+use crate::config::integrity::IntegrityConfig;
+use crate::ReadError;
+use ed25519_dalek::{Signature, VerifyingKey, PUBLIC_KEY_LENGTH, SIGNATURE_LENGTH};
+use serde::Deserialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+const CHECKSUM_FIELD: &str = "checksum";
+const SIGNATURE_FIELD: &str = "signature";
+const CHECKSUM_PREFIX: &str = "sha256:";
+
+/// Verifies signatures of collections for a set of known keys.
+///
+/// Collections can contain a signature over their canonicalized JSON. If the key used for the
+/// signature is known to the verifier, the author of the collection is considered verified.
+pub trait SignatureVerifier {
+    /// Verifies the signature of a message.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_id`: the id of the key the collection claims to be signed with
+    /// * `message`: the signed message
+    /// * `signature`: the signature as given in the collection
+    ///
+    /// returns: None, if the key is unknown. Otherwise, whether the signature is valid.
+    fn verify(&self, key_id: &str, message: &[u8], signature: &str) -> Option<bool>;
+}
+
+/// Verifies ed25519 signatures of collections for a set of trusted public keys.
+#[derive(Debug, Clone, Default)]
+pub struct Ed25519Verifier {
+    keys: HashMap<String, VerifyingKey>,
+}
+
+/// The file format of trusted keys read by [Ed25519Verifier::from_json].
+#[derive(Deserialize)]
+struct TrustedKeys {
+    keys: Vec<TrustedKey>,
+}
+
+#[derive(Deserialize)]
+struct TrustedKey {
+    key_id: String,
+    public_key: String,
+}
+
+impl Ed25519Verifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads trusted keys in the format `{"keys": [{"key_id": "...", "public_key": "..."}]}`.
+    pub fn from_json(json: &str) -> Result<Self, ReadError> {
+        let trusted_keys: TrustedKeys =
+            serde_json::from_str(json).map_err(|e| ReadError::JsonError(e.to_string()))?;
+        let mut verifier = Self::new();
+        for key in trusted_keys.keys {
+            verifier.add_key(&key.key_id, &key.public_key)?;
+        }
+        Ok(verifier)
+    }
+
+    /// Trusts the public key for signatures with the given key id.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_id`: the id collections use to reference the key
+    /// * `public_key`: the 32 byte ed25519 public key as hex digits
+    ///
+    /// returns: Result<(), ReadError> an error, if the public key is invalid
+    pub fn add_key(&mut self, key_id: &str, public_key: &str) -> Result<(), ReadError> {
+        let key = from_hex::<PUBLIC_KEY_LENGTH>(public_key)
+            .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+            .ok_or_else(|| {
+                ReadError::JsonError(format!("The public key of `{}` is invalid.", key_id))
+            })?;
+        self.keys.insert(key_id.to_string(), key);
+        Ok(())
+    }
+
+    /// Returns true, if no keys are trusted.
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+}
+
+impl SignatureVerifier for Ed25519Verifier {
+    fn verify(&self, key_id: &str, message: &[u8], signature: &str) -> Option<bool> {
+        let key = self.keys.get(key_id)?;
+        let Some(signature) = from_hex::<SIGNATURE_LENGTH>(signature) else {
+            return Some(false);
+        };
+        Some(
+            key.verify_strict(message, &Signature::from_bytes(&signature))
+                .is_ok(),
+        )
+    }
+}
+
+/// Returns the canonical form of a collection, which is used for checksums and signatures.
+///
+/// The canonical form is the compact JSON with the keys of all objects sorted and without the
+/// top-level `checksum` and `signature` fields.
+pub(crate) fn canonicalize(value: &Value) -> String {
+    let mut canonical = String::new();
+    match value {
+        Value::Object(object) => write_object(
+            object
+                .iter()
+                .filter(|(key, _)| *key != CHECKSUM_FIELD && *key != SIGNATURE_FIELD),
+            &mut canonical,
+        ),
+        value => write_canonical(value, &mut canonical),
+    }
+    canonical
+}
+
+/// Appends the canonical form of the value to the string.
+/// The keys of objects are sorted explicitly, so the result does not depend on the order in
+/// which serde_json stores them.
+fn write_canonical(value: &Value, canonical: &mut String) {
+    match value {
+        Value::Object(object) => write_object(object.iter(), canonical),
+        Value::Array(array) => {
+            canonical.push('[');
+            for (i, element) in array.iter().enumerate() {
+                if i > 0 {
+                    canonical.push(',');
+                }
+                write_canonical(element, canonical);
+            }
+            canonical.push(']');
+        }
+        value => canonical.push_str(&value.to_string()),
+    }
+}
+
+fn write_object<'a>(
+    entries: impl Iterator<Item = (&'a String, &'a Value)>,
+    canonical: &mut String,
+) {
+    let mut entries: Vec<(&String, &Value)> = entries.collect();
+    entries.sort_by_key(|(key, _)| *key);
+    canonical.push('{');
+    for (i, (key, value)) in entries.into_iter().enumerate() {
+        if i > 0 {
+            canonical.push(',');
+        }
+        canonical.push_str(&Value::String(key.clone()).to_string());
+        canonical.push(':');
+        write_canonical(value, canonical);
+    }
+    canonical.push('}');
+}
+
+/// Computes the checksum of a collection as it is expected in its `checksum` field.
+pub(crate) fn checksum(value: &Value) -> String {
+    format!(
+        "{}{}",
+        CHECKSUM_PREFIX,
+        to_hex(&sha256(canonicalize(value).as_bytes()))
+    )
+}
+
+/// Checks the checksum and signature of a collection.
+///
+/// Returns an error if the checksum does not match or if the signature does not match a known
+/// key. Signatures of unknown keys are ignored.
+pub(crate) fn check_integrity(
+    value: &Value,
+    verifier: Option<&dyn SignatureVerifier>,
+) -> Result<IntegrityConfig, ReadError> {
+    let mut integrity = IntegrityConfig::Unchecked;
+
+    if let Some(expected) = value.get(CHECKSUM_FIELD) {
+        let expected = expected.as_str().ok_or_else(|| {
+            ReadError::JsonError("The `checksum` field must be a string.".to_string())
+        })?;
+        if !expected.eq_ignore_ascii_case(&checksum(value)) {
+            return Err(ReadError::ChecksumMismatch);
+        }
+        integrity = IntegrityConfig::Checksum;
+    }
+
+    if let Some(signature) = value.get(SIGNATURE_FIELD) {
+        let key_id = signature.get("key_id").and_then(Value::as_str);
+        let signature_value = signature.get("value").and_then(Value::as_str);
+        let (Some(key_id), Some(signature_value)) = (key_id, signature_value) else {
+            return Err(ReadError::JsonError(
+                "The `signature` field must contain `key_id` and `value`.".to_string(),
+            ));
+        };
+        if let Some(verifier) = verifier {
+            match verifier.verify(key_id, canonicalize(value).as_bytes(), signature_value) {
+                Some(true) => {
+                    integrity = IntegrityConfig::VerifiedAuthor {
+                        key_id: key_id.to_string(),
+                    }
+                }
+                Some(false) => return Err(ReadError::InvalidSignature),
+                None => {}
+            }
+        }
+    }
+
+    Ok(integrity)
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Parses hex digits into bytes.
+/// Returns None, if the string is not valid hex or does not have exactly `N` bytes.
+fn from_hex<const N: usize>(hex: &str) -> Option<[u8; N]> {
+    if hex.len() != N * 2 || !hex.is_ascii() {
+        return None;
+    }
+    let mut bytes = [0u8; N];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).ok()?;
+    }
+    Some(bytes)
+}
+
+/// Computes the SHA-256 hash of the given data.
+fn sha256(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(data).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use serde_json::json;
+
+    struct TestVerifier;
+
+    impl SignatureVerifier for TestVerifier {
+        fn verify(&self, key_id: &str, _: &[u8], signature: &str) -> Option<bool> {
+            if key_id == "known" {
+                Some(signature == "valid")
+            } else {
+                None
+            }
+        }
+    }
+
+    #[test]
+    fn test_sha256() {
+        assert_eq!(
+            to_hex(&sha256(b"")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        assert_eq!(
+            to_hex(&sha256(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            to_hex(&sha256(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+    }
+
+    #[test]
+    fn test_canonicalize_ignores_order_and_integrity_fields() {
+        let a = json!({"b": 1, "a": [1, 2], "checksum": "x"});
+        let b = json!({"a": [1, 2], "b": 1, "signature": {"key_id": "k", "value": "v"}});
+
+        assert_eq!(canonicalize(&a), canonicalize(&b));
+        assert_eq!(canonicalize(&a), r#"{"a":[1,2],"b":1}"#);
+    }
+
+    #[test]
+    fn test_canonicalize_sorts_nested_objects() {
+        let value =
+            json!({"z": [{"y": "\"", "x": null}], "checksum": "x", "a": {"c": true, "b": 1.5}});
+
+        assert_eq!(
+            canonicalize(&value),
+            r#"{"a":{"b":1.5,"c":true},"z":[{"x":null,"y":"\""}]}"#
+        );
+    }
+
+    #[test]
+    fn test_ed25519_verifier() {
+        let signing_key = SigningKey::from_bytes(&[7; 32]);
+        let verifier = Ed25519Verifier::from_json(&format!(
+            r#"{{"keys": [{{"key_id": "author", "public_key": "{}"}}]}}"#,
+            to_hex(signing_key.verifying_key().as_bytes())
+        ))
+        .unwrap();
+        let mut value = json!({"name": "Test"});
+        let signature = to_hex(&signing_key.sign(canonicalize(&value).as_bytes()).to_bytes());
+        value["signature"] = json!({"key_id": "author", "value": signature});
+
+        assert_eq!(
+            check_integrity(&value, Some(&verifier)),
+            Ok(IntegrityConfig::VerifiedAuthor {
+                key_id: "author".to_string()
+            })
+        );
+
+        value["name"] = Value::String("Tampered".to_string());
+        assert_eq!(
+            check_integrity(&value, Some(&verifier)),
+            Err(ReadError::InvalidSignature)
+        );
+
+        value["signature"]["value"] = Value::String("not hex".to_string());
+        assert_eq!(
+            check_integrity(&value, Some(&verifier)),
+            Err(ReadError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn test_ed25519_verifier_invalid_key() {
+        let mut verifier = Ed25519Verifier::new();
+
+        assert!(verifier.add_key("author", "1234").is_err());
+        assert!(verifier.is_empty());
+    }
+
+    #[test]
+    fn test_check_integrity_unchecked() {
+        let value = json!({"name": "Test"});
+
+        assert_eq!(
+            check_integrity(&value, None),
+            Ok(IntegrityConfig::Unchecked)
+        );
+    }
+
+    #[test]
+    fn test_check_integrity_checksum() {
+        let mut value = json!({"name": "Test"});
+        value["checksum"] = Value::String(checksum(&value));
+
+        assert_eq!(check_integrity(&value, None), Ok(IntegrityConfig::Checksum));
+
+        value["name"] = Value::String("Tampered".to_string());
+        assert_eq!(
+            check_integrity(&value, None),
+            Err(ReadError::ChecksumMismatch)
+        );
+    }
+
+    #[test]
+    fn test_check_integrity_signature() {
+        let verifier = TestVerifier;
+        let valid = json!({"signature": {"key_id": "known", "value": "valid"}});
+        let invalid = json!({"signature": {"key_id": "known", "value": "invalid"}});
+        let unknown = json!({"signature": {"key_id": "unknown", "value": "valid"}});
+
+        assert_eq!(
+            check_integrity(&valid, Some(&verifier)),
+            Ok(IntegrityConfig::VerifiedAuthor {
+                key_id: "known".to_string()
+            })
+        );
+        assert_eq!(
+            check_integrity(&invalid, Some(&verifier)),
+            Err(ReadError::InvalidSignature)
+        );
+        assert_eq!(
+            check_integrity(&unknown, Some(&verifier)),
+            Ok(IntegrityConfig::Unchecked)
+        );
+    }
+}
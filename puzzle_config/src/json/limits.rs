@@ -0,0 +1,29 @@
+/// Limits for the size of collections loaded by a `JsonLoader`.
+///
+/// Collections exceeding these limits are rejected, so that broken or malicious collections
+/// cannot hang the application or exceed what the solver can handle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoadLimits {
+    /// The maximum number of puzzles in a collection.
+    pub max_puzzles: usize,
+    /// The maximum number of custom tiles defined in a collection.
+    pub max_custom_tiles: usize,
+    /// The maximum number of tiles in a single puzzle.
+    pub max_tiles_per_puzzle: usize,
+    /// The maximum width and height of a tile.
+    pub max_tile_size: usize,
+    /// The maximum width and height of a board.
+    pub max_board_size: usize,
+}
+
+impl Default for LoadLimits {
+    fn default() -> Self {
+        LoadLimits {
+            max_puzzles: 1000,
+            max_custom_tiles: 256,
+            max_tiles_per_puzzle: 64,
+            max_tile_size: 16,
+            max_board_size: 32,
+        }
+    }
+}
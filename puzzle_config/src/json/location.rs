@@ -0,0 +1,134 @@
+use crate::ReadError;
+use serde::de::DeserializeOwned;
+use serde_path_to_error::Segment;
+
+/// Deserializes the JSON string like `serde_json::from_str`, but keeps track of the path to
+/// the element being deserialized, so errors can point to it.
+pub(crate) fn from_str<T: DeserializeOwned>(json_str: &str) -> Result<T, ReadError> {
+    let mut deserializer = serde_json::Deserializer::from_str(json_str);
+    let value = serde_path_to_error::deserialize(&mut deserializer)
+        .map_err(|e| to_read_error(to_pointer(e.path()), e.inner()))?;
+    deserializer
+        .end()
+        .map_err(|e| to_read_error(String::new(), &e))?;
+    Ok(value)
+}
+
+/// Converts an error of serde_json to a ReadError.
+///
+/// If the error has a location in the JSON string, the resulting error contains the line, column
+/// and the JSON pointer to the element, which could not be deserialized.
+fn to_read_error(pointer: String, error: &serde_json::Error) -> ReadError {
+    let line = error.line();
+    let column = error.column();
+    if line == 0 {
+        return ReadError::JsonError(error.to_string());
+    }
+
+    let message = error.to_string();
+    let message = message
+        .strip_suffix(&format!(" at line {} column {}", line, column))
+        .unwrap_or(&message)
+        .to_string();
+    ReadError::InvalidJson {
+        message,
+        path: pointer,
+        line,
+        column,
+    }
+}
+
+/// Converts the path to a JSON pointer.
+fn to_pointer(path: &serde_path_to_error::Path) -> String {
+    path.iter()
+        .filter_map(|segment| match segment {
+            Segment::Seq { index } => Some(format!("/{}", index)),
+            Segment::Map { key } => Some(format!("/{}", key.replace('~', "~0").replace('/', "~1"))),
+            Segment::Enum { .. } | Segment::Unknown => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::Value;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Test {
+        items: Vec<Item>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Item {
+        name: String,
+        count: u32,
+    }
+
+    fn path_of<T: DeserializeOwned>(json_str: &str) -> String {
+        match from_str::<T>(json_str) {
+            Err(ReadError::InvalidJson { path, .. }) => path,
+            Err(e) => panic!("Unexpected error: {:?}", e),
+            Ok(_) => panic!("Expected an error"),
+        }
+    }
+
+    #[test]
+    fn test_pointer_of_syntax_error() {
+        let json_str = "{\n  \"a\": {\n    \"b\": [1, 2, x]\n  }\n}";
+
+        assert_eq!(path_of::<Value>(json_str), "/a/b/2");
+    }
+
+    #[test]
+    fn test_pointer_of_escaped_key() {
+        let json_str = r#"{"a/\"b": [0, "x"]}"#;
+
+        assert_eq!(path_of::<HashMap<String, Vec<u32>>>(json_str), "/a~1\"b/1");
+    }
+
+    #[test]
+    fn test_trailing_characters() {
+        assert_eq!(path_of::<Value>("{} x"), "");
+    }
+
+    #[test]
+    fn test_to_read_error_type_error() {
+        let json_str = "{\n  \"items\": [\n    {\"name\": \"a\", \"count\": 1},\n    {\"name\": \"b\", \"count\": \"2\"}\n  ]\n}";
+
+        let read_error = from_str::<Test>(json_str).unwrap_err();
+
+        match read_error {
+            ReadError::InvalidJson {
+                message,
+                path,
+                line,
+                column: _,
+            } => {
+                assert!(message.starts_with("invalid type"), "{}", message);
+                assert_eq!(path, "/items/1/count");
+                assert_eq!(line, 4);
+            }
+            _ => panic!("Unexpected error: {:?}", read_error),
+        }
+    }
+
+    #[test]
+    fn test_to_read_error_missing_field() {
+        let json_str = "{\n  \"items\": [\n    {\"name\": \"a\"}\n  ]\n}";
+
+        let read_error = from_str::<Test>(json_str).unwrap_err();
+
+        match read_error {
+            ReadError::InvalidJson { path, line, .. } => {
+                assert_eq!(path, "/items/0");
+                assert_eq!(line, 3);
+            }
+            _ => panic!("Unexpected error: {:?}", read_error),
+        }
+    }
+}
@@ -0,0 +1,400 @@
+use crate::{ReadError, PUZZLED_VERSION_FIELD};
+use semver::{Version, VersionReq};
+use serde_json::{Map, Value};
+
+/// The field containing the format version of a collection.
+const FORMAT_FIELD: &str = "format";
+/// The format version of collections, which do not contain the `format` field.
+const LEGACY_FORMAT_VERSION: u64 = 1;
+/// The format version, which the JSON model is written for.
+const CURRENT_FORMAT_VERSION: u64 = 4;
+
+/// A migration changes the structure of a collection to the next format version.
+type Migration = fn(&mut Value);
+
+/// The migrations in order with the format version they upgrade from.
+/// Format versions 1 and 2 have the same structure and only differ in whether the `puzzled`
+/// field is required, so there is no migration from format version 1.
+const MIGRATIONS: [(u64, Migration); 2] = [(2, migrate_2_to_3), (3, migrate_3_to_4)];
+
+/// Returns the format version of a collection.
+///
+/// The format version is independent of the version of Puzzled. It only changes, when the
+/// structure of collections changes in an incompatible way.
+pub(crate) fn format_version(value: &Value) -> Result<u64, ReadError> {
+    match value.get(FORMAT_FIELD) {
+        None => Ok(LEGACY_FORMAT_VERSION),
+        Some(format) => match format.as_u64() {
+            Some(format) if format >= LEGACY_FORMAT_VERSION => Ok(format),
+            _ => Err(ReadError::JsonError(
+                "The `format` field must be a positive integer.".to_string(),
+            )),
+        },
+    }
+}
+
+/// Upgrades a collection to the current format version.
+///
+/// The version of Puzzled the collection is made for is checked first. Then the migrations are
+/// applied one after another, starting with the format version of the collection.
+///
+/// # Arguments
+///
+/// * `value`: the collection to upgrade
+/// * `version_req`: the requirement for the `puzzled` field
+///
+/// returns: Result<(), ReadError>
+pub(crate) fn migrate(value: &mut Value, version_req: &VersionReq) -> Result<(), ReadError> {
+    let format = format_version(value)?;
+    if format > CURRENT_FORMAT_VERSION {
+        return Err(ReadError::UnsupportedFormat(format));
+    }
+    check_puzzled_version(value, format, version_req)?;
+
+    for (_, migration) in MIGRATIONS.iter().filter(|(from, _)| *from >= format) {
+        migration(value);
+    }
+    if let Value::Object(object) = value {
+        object.insert(FORMAT_FIELD.to_string(), CURRENT_FORMAT_VERSION.into());
+    }
+    Ok(())
+}
+
+/// Returns true, if the migrated collection only differs from the original in the format version.
+pub(crate) fn is_unchanged(original: &Value, migrated: &Value) -> bool {
+    match (original, migrated) {
+        (Value::Object(original), Value::Object(migrated)) => {
+            let without_format = |object: &Map<String, Value>| {
+                object
+                    .iter()
+                    .filter(|(key, _)| *key != FORMAT_FIELD)
+                    .count()
+            };
+            without_format(original) == without_format(migrated)
+                && migrated
+                    .iter()
+                    .filter(|(key, _)| *key != FORMAT_FIELD)
+                    .all(|(key, value)| original.get(key) == Some(value))
+        }
+        _ => original == migrated,
+    }
+}
+
+/// Checks that the collection is not made for a newer version of Puzzled than the loader
+/// supports.
+///
+/// Collections of the legacy format version require the `puzzled` field. For newer format
+/// versions it is optional, since the format version tells, whether the collection can be read,
+/// but it is still checked, if it is given.
+fn check_puzzled_version(
+    value: &Value,
+    format: u64,
+    version_req: &VersionReq,
+) -> Result<(), ReadError> {
+    match value.get(PUZZLED_VERSION_FIELD) {
+        Some(Value::String(s)) => {
+            let collection_version =
+                Version::parse(s).map_err(|e| ReadError::InvalidVersion(e.to_string()))?;
+            if version_req.matches(&collection_version) {
+                Ok(())
+            } else {
+                Err(ReadError::UnsupportedVersion)
+            }
+        }
+        None if format > LEGACY_FORMAT_VERSION => Ok(()),
+        _ => Err(ReadError::MissingVersion),
+    }
+}
+
+/// Puzzles of format version 3 require an `id`, so their progress is kept, when they are
+/// reordered. Older puzzles used their index as the id, which is added to keep their progress.
+fn migrate_2_to_3(value: &mut Value) {
+    let Some(Value::Array(puzzles)) = value.get_mut("puzzles") else {
+        return;
+    };
+    for (i, puzzle) in puzzles.iter_mut().enumerate() {
+        if let Value::Object(puzzle) = puzzle {
+            puzzle
+                .entry("id")
+                .or_insert_with(|| Value::String(format!("{i}")));
+        }
+    }
+}
+
+/// Format version 4 uses the codes `2` to `5` in tile and board layouts for half cells. Before,
+/// every code other than `0` was a filled cell of a tile or a cell outside the board, so these
+/// codes are replaced with `1` to keep their meaning.
+fn migrate_3_to_4(value: &mut Value) {
+    if let Some(Value::Object(tiles)) = value.get_mut("custom_tiles") {
+        tiles.values_mut().for_each(migrate_tile_layout);
+    }
+    if let Some(Value::Object(boards)) = value.get_mut("custom_boards") {
+        boards.values_mut().for_each(migrate_board_layout);
+    }
+    let Some(Value::Array(puzzles)) = value.get_mut("puzzles") else {
+        return;
+    };
+    for puzzle in puzzles {
+        if let Some(Value::Array(tiles)) = puzzle.get_mut("tiles") {
+            tiles.iter_mut().for_each(migrate_tile_layout);
+        }
+        if let Some(board) = puzzle.get_mut("board") {
+            migrate_board_layout(board);
+        }
+        if let Some(Value::Array(boards)) = puzzle.get_mut("boards") {
+            boards.iter_mut().for_each(migrate_board_layout);
+        }
+    }
+}
+
+/// Replaces the codes of the layout of a tile, which is given as a layout or as an object.
+fn migrate_tile_layout(tile: &mut Value) {
+    match tile {
+        Value::Array(_) => migrate_layout(tile),
+        Value::Object(tile) => {
+            for key in ["layout", "tile"] {
+                if let Some(layout) = tile.get_mut(key) {
+                    migrate_layout(layout);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Replaces the codes of the layout of a simple board. Area boards have no such codes.
+fn migrate_board_layout(board: &mut Value) {
+    if let Some(layout) = board.get_mut("layout") {
+        migrate_layout(layout);
+    }
+}
+
+/// Replaces all codes other than `0` with `1`.
+fn migrate_layout(layout: &mut Value) {
+    let Value::Array(rows) = layout else {
+        return;
+    };
+    for row in rows {
+        let Value::Array(cells) = row else {
+            continue;
+        };
+        for cell in cells {
+            if cell.as_i64().is_some_and(|code| code != 0) {
+                *cell = 1.into();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::create_json_loader;
+    use serde_json::json;
+
+    const PREDEFINED_JSON: &str = r#"{"tiles": {}, "boards": {}}"#;
+
+    #[test]
+    fn test_format_version() {
+        assert_eq!(format_version(&json!({})), Ok(1));
+        assert_eq!(format_version(&json!({"format": 2})), Ok(2));
+        assert!(format_version(&json!({"format": 0})).is_err());
+        assert!(format_version(&json!({"format": "2"})).is_err());
+    }
+
+    #[test]
+    fn test_migrate_legacy() {
+        let version_req = VersionReq::parse("<=0.4.0").unwrap();
+        let mut value = json!({"puzzled": "0.3.0"});
+
+        migrate(&mut value, &version_req).unwrap();
+
+        assert_eq!(value, json!({"puzzled": "0.3.0", "format": 4}));
+    }
+
+    #[test]
+    fn test_migrate_legacy_unsupported_version() {
+        let version_req = VersionReq::parse("<=0.2.0").unwrap();
+
+        assert_eq!(
+            migrate(&mut json!({"puzzled": "0.3.0"}), &version_req),
+            Err(ReadError::UnsupportedVersion)
+        );
+        assert_eq!(
+            migrate(&mut json!({}), &version_req),
+            Err(ReadError::MissingVersion)
+        );
+    }
+
+    #[test]
+    fn test_migrate_checks_puzzled_version_of_newer_formats() {
+        let version_req = VersionReq::parse("<=0.2.0").unwrap();
+
+        assert_eq!(
+            migrate(&mut json!({"format": 2, "puzzled": "0.3.0"}), &version_req),
+            Err(ReadError::UnsupportedVersion)
+        );
+        assert_eq!(
+            migrate(&mut json!({"format": 3, "puzzled": "0.3.0"}), &version_req),
+            Err(ReadError::UnsupportedVersion)
+        );
+        assert!(migrate(&mut json!({"format": 2, "puzzled": "0.2.0"}), &version_req).is_ok());
+        assert!(migrate(&mut json!({"format": 2}), &version_req).is_ok());
+    }
+
+    #[test]
+    fn test_is_unchanged() {
+        let original = json!({"puzzled": "0.3.0", "name": "Test"});
+
+        assert!(is_unchanged(
+            &original,
+            &json!({"puzzled": "0.3.0", "name": "Test", "format": 4})
+        ));
+        assert!(!is_unchanged(
+            &original,
+            &json!({"puzzled": "0.3.0", "name": "Other", "format": 4})
+        ));
+    }
+
+    #[test]
+    fn test_migrate_unsupported_format() {
+        let version_req = VersionReq::parse("<=0.4.0").unwrap();
+
+        assert_eq!(
+            migrate(&mut json!({"format": 5}), &version_req),
+            Err(ReadError::UnsupportedFormat(5))
+        );
+    }
+
+    #[test]
+    fn test_migrate_2_to_3_adds_puzzle_ids() {
+        let version_req = VersionReq::parse("<=0.4.0").unwrap();
+        let mut value = json!({"format": 2, "puzzles": [{"name": "A"}, {"id": "b", "name": "B"}, {"name": "C"}]});
+
+        migrate(&mut value, &version_req).unwrap();
+
+        assert_eq!(
+            value,
+            json!({"format": 4, "puzzles": [
+                {"id": "0", "name": "A"},
+                {"id": "b", "name": "B"},
+                {"id": "2", "name": "C"}
+            ]})
+        );
+    }
+
+    #[test]
+    fn test_migrate_3_to_4_keeps_full_cells() {
+        let version_req = VersionReq::parse("<=0.4.0").unwrap();
+        let mut value = json!({
+            "format": 3,
+            "custom_tiles": {"A": [[1, 3]], "B": {"layout": [[2, 0]], "color": "#ffffff"}},
+            "custom_boards": {"C": {"layout": [[0, 5]]}, "D": "C"},
+            "puzzles": [{
+                "id": "a",
+                "tiles": ["A", [[4, -1]], {"tile": [[0, 5]]}],
+                "board": {"layout": [[0, 2]]},
+                "boards": [{"layout": [[3, 0]]}, {"area_layout": [[0, 2]]}]
+            }]
+        });
+
+        migrate(&mut value, &version_req).unwrap();
+
+        assert_eq!(
+            value,
+            json!({
+                "format": 4,
+                "custom_tiles": {"A": [[1, 1]], "B": {"layout": [[1, 0]], "color": "#ffffff"}},
+                "custom_boards": {"C": {"layout": [[0, 1]]}, "D": "C"},
+                "puzzles": [{
+                    "id": "a",
+                    "tiles": ["A", [[1, 1]], {"tile": [[0, 1]]}],
+                    "board": {"layout": [[0, 1]]},
+                    "boards": [{"layout": [[1, 0]]}, {"area_layout": [[0, 2]]}]
+                }]
+            })
+        );
+    }
+
+    #[test]
+    fn test_load_format_3_requires_puzzle_ids() {
+        let loader = create_json_loader(PREDEFINED_JSON, "0.1.0").unwrap();
+        let collection = |puzzles: &str| {
+            format!(
+                r#"{{"format": 3, "name": "Test", "author": "Test", "id": "a.b", "puzzles": [{}]}}"#,
+                puzzles
+            )
+        };
+        let puzzle = |id: &str| {
+            format!(
+                r#"{{{}"name": "A", "tiles": [], "board": {{"layout": [[0]]}}}}"#,
+                id
+            )
+        };
+
+        assert!(matches!(
+            loader.load_puzzle_collection(&collection(&puzzle(""))),
+            Err(ReadError::InvalidJson { .. })
+        ));
+        assert_eq!(
+            loader
+                .load_puzzle_collection(&collection(&format!(
+                    "{}, {}",
+                    puzzle(r#""id": "a", "#),
+                    puzzle(r#""id": "a", "#)
+                )))
+                .unwrap_err(),
+            ReadError::DuplicatePuzzleId("a".to_string())
+        );
+        assert!(loader
+            .load_puzzle_collection(&collection(&puzzle(r#""id": "a", "#)))
+            .is_ok());
+    }
+
+    #[test]
+    fn test_load_format_1_0_2_0() {
+        let loader = create_json_loader(PREDEFINED_JSON, "0.2.0").unwrap();
+
+        let collection = loader
+            .load_puzzle_collection(include_str!("fixtures/format_1_0.2.0.json"))
+            .unwrap();
+
+        assert_eq!(collection.puzzles().len(), 2);
+        assert_eq!(collection.puzzles()[0].tiles().len(), 2);
+    }
+
+    #[test]
+    fn test_load_format_1_0_3_0() {
+        let loader = create_json_loader(PREDEFINED_JSON, "0.3.0").unwrap();
+
+        let collection = loader
+            .load_puzzle_collection(include_str!("fixtures/format_1_0.3.0.json"))
+            .unwrap();
+
+        assert_eq!(collection.puzzles().len(), 2);
+        assert!(!collection.preview().show_tiles());
+        assert!(collection.puzzles()[1].is_unsolvable());
+    }
+
+    #[test]
+    fn test_load_format_1_0_4_0() {
+        let loader = create_json_loader(PREDEFINED_JSON, "0.4.0").unwrap();
+
+        let collection = loader
+            .load_puzzle_collection(include_str!("fixtures/format_1_0.4.0.json"))
+            .unwrap();
+
+        assert_eq!(collection.puzzles()[0].tiles().len(), 2);
+    }
+
+    #[test]
+    fn test_load_format_2() {
+        let loader = create_json_loader(PREDEFINED_JSON, "0.1.0").unwrap();
+
+        let collection = loader
+            .load_puzzle_collection(include_str!("fixtures/format_2.json"))
+            .unwrap();
+
+        assert_eq!(collection.puzzles()[0].tiles().len(), 2);
+    }
+}
@@ -1,19 +1,36 @@
 use crate::json::converter::Convertable;
 use crate::json::model::PuzzleCollection;
-use crate::json::predefined::{Custom, Predefined};
-use crate::{PuzzleConfigCollection, ReadError, PUZZLED_VERSION_FIELD};
-use semver::{Version, VersionReq};
+use crate::json::namespace::NamespaceHeader;
+use crate::json::predefined::{ConfigStore, Custom, Predefined};
+use crate::json::summary::CollectionHeader;
+use crate::{CollectionSummary, PuzzleConfigCollection, ReadError};
+use semver::VersionReq;
 use serde_json::Value;
+use std::borrow::Cow;
 
 mod converter;
+mod integrity;
+mod limits;
+mod location;
+mod migration;
 mod model;
+mod namespace;
 mod predefined;
+mod summary;
+
+pub use integrity::{Ed25519Verifier, SignatureVerifier};
+pub use limits::LoadLimits;
 
 /// Loader for puzzle configuration from JSON strings.
 /// Instances can be reused to load multiple collections.
 pub struct JsonLoader {
     predefined: Predefined,
+    /// The custom tiles and boards of registered namespaces by their qualified names.
+    namespaces: ConfigStore,
     version_req: VersionReq,
+    signature_verifier: Option<Box<dyn SignatureVerifier>>,
+    limits: LoadLimits,
+    month_names: Option<[String; 12]>,
 }
 
 impl JsonLoader {
@@ -26,56 +43,137 @@ impl JsonLoader {
             serde_json::from_str(predefined_json).expect("Failed to parse predefined JSON");
         Self {
             predefined,
+            namespaces: ConfigStore::default(),
             version_req: VersionReq::parse(format!("<={}", puzzled_version).as_str()).unwrap(),
+            signature_verifier: None,
+            limits: LoadLimits::default(),
+            month_names: None,
         }
     }
 
+    /// Set the limits for the size of collections loaded by this loader.
+    pub fn set_limits(&mut self, limits: LoadLimits) {
+        self.limits = limits;
+    }
+
+    /// Set the verifier used to check the signatures of collections.
+    /// Without a verifier, signatures are ignored and only checksums are checked.
+    pub fn set_signature_verifier(&mut self, signature_verifier: Box<dyn SignatureVerifier>) {
+        self.signature_verifier = Some(signature_verifier);
+    }
+
+    /// Set the names of the months in the locale of the app, starting with January.
+    /// They are used by month formatters, which do not define their own names.
+    /// Without names, the English names are used.
+    pub fn set_month_names(&mut self, month_names: [String; 12]) {
+        self.month_names = Some(month_names);
+    }
+
+    /// Makes the custom tiles and boards of the collection available to collections loaded
+    /// afterwards, if it declares a namespace. They are referenced as `namespace/name`.
+    /// Registering a namespace again replaces its tiles and boards with the same name.
+    ///
+    /// Only the namespace, tiles and boards are read, so the rest of the collection is not
+    /// checked.
+    ///
+    /// returns: Result<Option<String>, ReadError> the registered namespace, if any
+    pub fn register_namespace(&mut self, json_str: &str) -> Result<Option<String>, ReadError> {
+        let mut value: Value = location::from_str(json_str)?;
+        migration::migrate(&mut value, &self.version_req)?;
+        let header = serde_json::from_value::<NamespaceHeader>(value)
+            .map_err(|e| ReadError::JsonError(e.to_string()))?;
+        header.register(&mut self.namespaces)
+    }
+
+    /// Returns the predefined tiles and boards, which can be referenced by name in collections
+    /// loaded by this loader.
+    pub fn predefined_catalog(&self) -> crate::Predefined {
+        to_catalog(self.predefined.clone())
+    }
+
     /// Load a puzzle configuration collection from a JSON string.
-    /// Returns an error if the JSON is invalid or the version is unsupported.
+    /// Collections of older format versions are migrated to the current format version first.
+    /// Returns an error if the JSON is invalid, the version is unsupported or the checksum or
+    /// signature does not match the content.
     pub fn load_puzzle_collection(
         &self,
         json_str: &str,
     ) -> Result<PuzzleConfigCollection, ReadError> {
-        let value: Value =
-            serde_json::from_str(json_str).map_err(|e| ReadError::JsonError(e.to_string()))?;
-
-        let version: Result<i32, ReadError> = match &value {
-            Value::Object(object) => {
-                let version_value = object.get(PUZZLED_VERSION_FIELD);
-                match version_value {
-                    Some(Value::String(s)) => {
-                        let collection_version = Version::parse(s)
-                            .map_err(|e| ReadError::InvalidVersion(e.to_string()))?;
-                        if self.version_req.matches(&collection_version) {
-                            Ok(1)
-                        } else {
-                            Err(ReadError::UnsupportedVersion)
-                        }
-                    }
-                    _ => Err(ReadError::MissingVersion),
-                }
-            }
-            _ => Err(ReadError::MissingVersion),
-        };
-        if version? == 1 {
-            self.load(value)
+        let value: Value = location::from_str(json_str)?;
+
+        let mut migrated = value.clone();
+        migration::migrate(&mut migrated, &self.version_req)?;
+        self.load(json_str, value, migrated)
+    }
+
+    /// Load only the metadata of a collection from a JSON string, without converting its tiles
+    /// and boards.
+    /// The version, checksum and signature are checked the same way as by
+    /// [load_puzzle_collection](Self::load_puzzle_collection), but errors in the puzzles are only
+    /// found once the whole collection is loaded.
+    pub fn load_collection_summary(&self, json_str: &str) -> Result<CollectionSummary, ReadError> {
+        let value: Value = location::from_str(json_str)?;
+
+        let mut migrated = value.clone();
+        migration::migrate(&mut migrated, &self.version_req)?;
+        let integrity = integrity::check_integrity(&value, self.signature_verifier.as_deref())?;
+        let header = serde_json::from_value::<CollectionHeader>(migrated)
+            .map_err(|e| ReadError::JsonError(e.to_string()))?;
+        header.check_limits(&self.limits)?;
+        Ok(header.into_summary(integrity))
+    }
+
+    fn load(
+        &self,
+        json_str: &str,
+        original: Value,
+        migrated: Value,
+    ) -> Result<PuzzleConfigCollection, ReadError> {
+        let integrity = integrity::check_integrity(&original, self.signature_verifier.as_deref())?;
+        // Deserialize from the string if the migrations did not change the structure to keep the
+        // location of errors.
+        let collection = if migration::is_unchanged(&original, &migrated) {
+            location::from_str::<PuzzleCollection>(json_str)?
         } else {
-            Err(ReadError::UnsupportedVersion)
-        }
+            serde_json::from_value::<PuzzleCollection>(migrated)
+                .map_err(|e| ReadError::JsonError(e.to_string()))?
+        };
+
+        let predefined = self.predefined();
+        converter::check_collection_limits(&collection, &predefined, &self.limits)?;
+        let mut custom = Custom::default();
+        custom.set_month_names(self.month_names.clone());
+        let mut collection = collection.convert(&predefined, &mut custom)?;
+        collection.set_integrity(integrity);
+        Ok(collection)
     }
+}
 
-    fn load(&self, json_data: Value) -> Result<PuzzleConfigCollection, ReadError> {
-        let result = serde_json::from_value::<PuzzleCollection>(json_data);
-        match result {
-            Ok(collection) => collection.convert(&self.predefined, &mut Custom::default()),
-            Err(e) => Err(ReadError::JsonError(e.to_string())),
+impl JsonLoader {
+    /// Returns the predefined tiles and boards together with those of the registered namespaces.
+    fn predefined(&self) -> Cow<'_, Predefined> {
+        if self.namespaces.is_empty() {
+            return Cow::Borrowed(&self.predefined);
         }
+        let mut predefined = self.predefined.clone();
+        predefined.extend(&self.namespaces);
+        Cow::Owned(predefined)
     }
 }
 
+pub(crate) fn compute_checksum(json_str: &str) -> Result<String, ReadError> {
+    let value: Value =
+        serde_json::from_str(json_str).map_err(|e| ReadError::JsonError(e.to_string()))?;
+    Ok(integrity::checksum(&value))
+}
+
 pub fn read_predefined(json_data: &str, _: &str) -> crate::Predefined {
-    let mut predefined: Predefined =
+    let predefined: Predefined =
         serde_json::from_str(json_data).expect("Failed to parse predefined JSON");
+    to_catalog(predefined)
+}
+
+fn to_catalog(mut predefined: Predefined) -> crate::Predefined {
     crate::Predefined {
         tiles: predefined.take_tiles(),
         boards: predefined.take_boards(),
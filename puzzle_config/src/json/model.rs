@@ -14,6 +14,12 @@ pub struct PuzzleCollection {
     #[serde(default)]
     pub progression: Progression,
     pub preview: Option<Preview>,
+    /// Colors used for tiles without an explicit color instead of the default colors.
+    pub palette: Option<Vec<Color>>,
+    /// The URI of the background music played while a puzzle of the collection is shown.
+    pub music: Option<String>,
+    /// Makes the custom tiles and boards available to other collections as `namespace/name`.
+    pub namespace: Option<String>,
     /// Custom tiles to override or extend predefined tiles.
     pub custom_tiles: Option<HashMap<String, Tile>>,
     pub custom_boards: Option<HashMap<String, Board>>,
@@ -46,7 +52,9 @@ pub struct Preview {
 
 #[derive(Deserialize)]
 pub struct Puzzle {
-    pub id: Option<String>,
+    /// Identifies the puzzle in the collection, so its progress is kept, when puzzles are
+    /// reordered. Added by the migration for older format versions.
+    pub id: String,
     pub name: String,
     pub description: Option<String>,
     pub difficulty: Option<PuzzleDifficulty>,
@@ -55,10 +63,50 @@ pub struct Puzzle {
     /// The tiles to use in this puzzle. Can reference predefined tiles, custom tiles or define
     /// them inline.
     pub tiles: Vec<Tile>,
-    pub board: Board,
+    /// The board to fill with the tiles. Either this or `boards` must be given.
+    pub board: Option<Board>,
+    /// Separate boards, which must all be filled with the tiles at the same time.
+    /// Only simple boards can be combined.
+    pub boards: Option<Vec<Board>>,
+    /// Statistics about the number of solutions per target.
+    pub solution_statistics: Option<Statistics>,
     /// Additional metadata for the puzzle.
-    /// This is shown in the Puzzle Info dialog and may contain solution statistics or other info.
+    /// This is shown in the Puzzle Info dialog.
     pub additional_info: Option<HashMap<String, String>>,
+    /// Restrictions for the hints, which can be requested for the puzzle.
+    pub hints: Option<Hints>,
+    /// Cells of the board, which have to be covered by a specific tile.
+    #[serde(default)]
+    pub constraints: Vec<Constraint>,
+}
+
+#[derive(Deserialize)]
+pub struct Constraint {
+    /// The position of the cell on the board as `[x, y]`.
+    pub cell: [usize; 2],
+    /// The name of the tile, which has to cover the cell.
+    pub tile: String,
+}
+
+#[derive(Deserialize)]
+pub struct Hints {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// How many hints can be requested per puzzle.
+    pub max: Option<u32>,
+    /// How many seconds to wait after a hint, before the next one can be requested.
+    pub cooldown: Option<u64>,
+}
+
+#[derive(Deserialize)]
+pub struct Statistics {
+    pub min: u64,
+    pub max: u64,
+    pub median: u64,
+    pub total: u64,
+    pub targets: u64,
+    /// RFC 3339 timestamp of when the statistics were computed.
+    pub computed_on: Option<String>,
 }
 
 #[derive(Deserialize)]
@@ -76,9 +124,13 @@ pub enum Tile {
     Ref(String),
     Layout(TileLayout),
     Custom {
+        #[serde(alias = "tile")]
         layout: TileLayout,
         color: Option<Color>,
+        /// How many copies of the tile to add to the puzzle.
         count: Option<NonZeroU32>,
+        /// Identifies the tile within the puzzle. Derived from the name or layout, if missing.
+        id: Option<String>,
     },
 }
 
@@ -110,6 +162,12 @@ pub enum Board {
         value_order: Vec<Vec<i32>>,
         areas: Vec<Area>,
         target_template: String,
+        /// Whether to show the names of the areas beside the board.
+        #[serde(default)]
+        legend: bool,
+        /// Whether to draw thicker lines between cells of different areas.
+        #[serde(default)]
+        area_boundaries: bool,
     },
 }
 
@@ -131,6 +189,18 @@ pub enum AreaFormatter {
         prefix: String,
         suffix: String,
     },
+    /// Formats a number as Roman numerals.
+    Roman,
+    /// Formats a month number or short name as the full name of the month.
+    LocalizedMonth {
+        /// The names of the months starting with January. Defaults to the names of the locale.
+        #[serde(default)]
+        names: Option<Vec<String>>,
+    },
+    /// Pads a number with leading zeros.
+    ZeroPadded {
+        width: usize,
+    },
 }
 
 #[derive(Debug, Deserialize, Clone)]
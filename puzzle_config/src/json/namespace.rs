@@ -0,0 +1,166 @@
+//! Custom tiles and boards shared between collections.
+//! A collection declaring a `namespace` makes its custom tiles and boards available to other
+//! collections, which reference them as `namespace/name`.
+
+use crate::json::model::{Board, Tile, TileLayout};
+use crate::json::predefined::ConfigStore;
+use crate::{validation, ReadError};
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Separates the namespace from the name of a tile or board in a reference.
+const NAMESPACE_SEPARATOR: char = '/';
+
+/// The parts of a collection, which are shared through its namespace.
+#[derive(Deserialize)]
+pub struct NamespaceHeader {
+    pub namespace: Option<String>,
+    pub custom_tiles: Option<HashMap<String, Tile>>,
+    pub custom_boards: Option<HashMap<String, Board>>,
+}
+
+impl NamespaceHeader {
+    /// Adds the custom tiles and boards to the store under their qualified names.
+    /// References between them are qualified as well, so they still point into the namespace,
+    /// while references to predefined tiles and boards are kept.
+    ///
+    /// returns: Result<Option<String>, ReadError> the namespace, if the collection declares one
+    pub fn register(self, store: &mut ConfigStore) -> Result<Option<String>, ReadError> {
+        let Some(namespace) = validation::validate_namespace(self.namespace)? else {
+            return Ok(None);
+        };
+        let tiles = self.custom_tiles.unwrap_or_default();
+        let boards = self.custom_boards.unwrap_or_default();
+        let qualify_tile = |name: String| {
+            if tiles.contains_key(&name) {
+                qualified_name(&namespace, &name)
+            } else {
+                name
+            }
+        };
+
+        for (name, tile) in &tiles {
+            store.add_tile(
+                qualified_name(&namespace, name),
+                qualify_tile_references(tile.clone(), &qualify_tile),
+            );
+        }
+        for (name, board) in boards.iter() {
+            let board = match board {
+                Board::Ref(reference) if boards.contains_key(reference) => {
+                    Board::Ref(qualified_name(&namespace, reference))
+                }
+                board => board.clone(),
+            };
+            store.add_board(qualified_name(&namespace, name), board);
+        }
+        Ok(Some(namespace))
+    }
+}
+
+/// Returns the name, by which other collections reference a tile or board of the namespace.
+fn qualified_name(namespace: &str, name: &str) -> String {
+    format!("{}{}{}", namespace, NAMESPACE_SEPARATOR, name)
+}
+
+fn qualify_tile_references(tile: Tile, qualify: &impl Fn(String) -> String) -> Tile {
+    let qualify_layout = |layout: TileLayout| match layout {
+        TileLayout::Ref(name) => TileLayout::Ref(qualify(name)),
+        layout => layout,
+    };
+    match tile {
+        Tile::Ref(name) => Tile::Ref(qualify(name)),
+        Tile::Layout(layout) => Tile::Layout(qualify_layout(layout)),
+        Tile::Custom {
+            layout,
+            color,
+            count,
+            id,
+        } => Tile::Custom {
+            layout: qualify_layout(layout),
+            color,
+            count,
+            id,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{create_json_loader, BoardConfig, ReadError};
+
+    const PREDEFINED_JSON: &str = r#"{"tiles": {"I1": [[1]]}, "boards": {}}"#;
+
+    const LIBRARY_JSON: &str = r#"{
+        "format": 2,
+        "name": "Library",
+        "author": "Author",
+        "id": "de.til7701.Puzzled.Library",
+        "namespace": "shared.tiles",
+        "custom_tiles": {
+            "Bar": [[1, 1, 1]],
+            "Alias": "Bar",
+            "Single": "I1"
+        },
+        "custom_boards": {
+            "Strip": {"layout": [[0, 0, 0, 0]]}
+        },
+        "puzzles": []
+    }"#;
+
+    fn collection_json(tiles: &str, board: &str) -> String {
+        format!(
+            r#"{{
+                "format": 2,
+                "name": "User",
+                "author": "Author",
+                "id": "de.til7701.Puzzled.User",
+                "puzzles": [{{"name": "Puzzle", "tiles": {}, "board": {}}}]
+            }}"#,
+            tiles, board
+        )
+    }
+
+    #[test]
+    fn test_namespaced_references() {
+        let mut loader = create_json_loader(PREDEFINED_JSON, "1.3.0").unwrap();
+        assert_eq!(
+            loader.register_namespace(LIBRARY_JSON),
+            Ok(Some("shared.tiles".to_string()))
+        );
+
+        let json = collection_json(
+            r#"["shared.tiles/Alias", "shared.tiles/Single"]"#,
+            r#""shared.tiles/Strip""#,
+        );
+        let collection = loader.load_puzzle_collection(&json).unwrap();
+
+        let puzzle = &collection.puzzles()[0];
+        assert_eq!(puzzle.tiles().len(), 2);
+        assert_eq!(puzzle.tiles()[0].base().dim(), (3, 1));
+        assert_eq!(puzzle.tiles()[1].base().dim(), (1, 1));
+        assert!(matches!(puzzle.board_config(), BoardConfig::Simple { .. }));
+        assert_eq!(puzzle.board_config().layout().dim(), (4, 1));
+    }
+
+    #[test]
+    fn test_namespace_errors() {
+        let mut loader = create_json_loader(PREDEFINED_JSON, "1.3.0").unwrap();
+        let json = collection_json(r#"["shared.tiles/Bar"]"#, r#"{"layout": [[0, 0, 0]]}"#);
+
+        assert_eq!(
+            loader.load_puzzle_collection(&json).unwrap_err(),
+            ReadError::UnknownPredefinedTile {
+                name: "shared.tiles/Bar".to_string()
+            }
+        );
+        assert_eq!(
+            loader.register_namespace(&LIBRARY_JSON.replace("shared.tiles", "shared/tiles")),
+            Err(ReadError::InvalidNamespace("shared/tiles".to_string()))
+        );
+        assert_eq!(
+            loader.register_namespace(&collection_json("[]", r#"{"layout": [[0]]}"#)),
+            Ok(None)
+        );
+    }
+}
@@ -1,6 +1,7 @@
+use crate::config::area::ENGLISH_MONTH_NAMES;
 use crate::json::converter::Convertable;
 use crate::json::model::{Board, Tile};
-use crate::{BoardConfig, TileConfig};
+use crate::{ColorConfig, PredefinedBoard, ReadError, TileConfig};
 use serde::Deserialize;
 use std::collections::HashMap;
 use std::mem::take;
@@ -9,10 +10,18 @@ pub type Predefined = ConfigStore;
 pub type Custom = ConfigStore;
 
 /// Store for predefined or custom tiles and boards.
-#[derive(Debug, Default, Deserialize)]
+#[derive(Debug, Default, Clone, Deserialize)]
 pub struct ConfigStore {
     tiles: HashMap<String, Tile>,
     boards: HashMap<String, Board>,
+    #[serde(skip)]
+    palette: Vec<ColorConfig>,
+    /// The names of the months in the locale of the app.
+    #[serde(skip)]
+    month_names: Option<[String; 12]>,
+    /// The names of the tiles and boards, which are currently being resolved.
+    #[serde(skip)]
+    resolving: Vec<String>,
 }
 
 impl ConfigStore {
@@ -24,6 +33,11 @@ impl ConfigStore {
         self.tiles.get(name).cloned()
     }
 
+    /// Returns the tile with the given name without cloning it.
+    pub fn tile(&self, name: &str) -> Option<&Tile> {
+        self.tiles.get(name)
+    }
+
     pub fn add_board(&mut self, name: String, board: Board) {
         self.boards.insert(name, board);
     }
@@ -32,25 +46,110 @@ impl ConfigStore {
         self.boards.get(name).cloned()
     }
 
+    /// Returns the board with the given name without cloning it.
+    pub fn board(&self, name: &str) -> Option<&Board> {
+        self.boards.get(name)
+    }
+
+    /// Returns true, if the store contains neither tiles nor boards.
+    pub fn is_empty(&self) -> bool {
+        self.tiles.is_empty() && self.boards.is_empty()
+    }
+
+    /// Adds all tiles and boards of the other store, replacing those with the same name.
+    pub fn extend(&mut self, other: &ConfigStore) {
+        self.tiles
+            .extend(other.tiles.iter().map(|(k, v)| (k.clone(), v.clone())));
+        self.boards
+            .extend(other.boards.iter().map(|(k, v)| (k.clone(), v.clone())));
+    }
+
+    pub fn set_palette(&mut self, palette: Vec<ColorConfig>) {
+        self.palette = palette;
+    }
+
+    pub fn set_month_names(&mut self, month_names: Option<[String; 12]>) {
+        self.month_names = month_names;
+    }
+
+    /// Resolves the reference to the tile or board with the given name using `resolve`.
+    /// Returns an error instead of calling `resolve`, if the reference is already being resolved,
+    /// because the tile or board references itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `name`: The name of the referenced tile or board
+    /// * `resolve`: Converts the referenced tile or board
+    ///
+    /// returns: Result<R, ReadError>
+    pub fn resolve_reference<R>(
+        &mut self,
+        name: &str,
+        resolve: impl FnOnce(&mut Self) -> Result<R, ReadError>,
+    ) -> Result<R, ReadError> {
+        if self.resolving.iter().any(|resolving| resolving == name) {
+            return Err(ReadError::ReferenceCycle {
+                name: name.to_string(),
+            });
+        }
+        self.resolving.push(name.to_string());
+        let result = resolve(self);
+        self.resolving.pop();
+        result
+    }
+
+    /// Returns the color for the tile with the given index.
+    /// Uses the palette if one is set and the default colors otherwise.
+    pub fn color_with_index(&self, index: usize) -> ColorConfig {
+        if self.palette.is_empty() {
+            ColorConfig::default_with_index(index)
+        } else {
+            self.palette[index % self.palette.len()]
+        }
+    }
+
+    /// Returns the names of the months for month formatters, which do not define their own.
+    /// Uses the names of the locale if they are set and the English names otherwise.
+    pub fn month_names(&self) -> Vec<String> {
+        match &self.month_names {
+            Some(month_names) => month_names.to_vec(),
+            None => ENGLISH_MONTH_NAMES.map(str::to_string).to_vec(),
+        }
+    }
+
+    /// Converts and returns all tiles of this store sorted by the number of cells and name.
     pub(crate) fn take_tiles(&mut self) -> Vec<TileConfig> {
         let tiles: HashMap<String, Tile> = take(&mut self.tiles);
-        tiles
+        let mut tiles: Vec<TileConfig> = tiles
             .into_iter()
             .flat_map(|(name, tile)| {
                 (0, tile, Some(name))
                     .convert(&Predefined::default(), &mut Custom::default())
                     .unwrap()
             })
-            .collect()
+            .collect();
+        tiles.sort_by_key(|tile| {
+            (
+                tile.base().iter().filter(|cell| **cell).count(),
+                tile.name().clone(),
+            )
+        });
+        tiles
     }
 
-    pub(crate) fn take_boards(&mut self) -> Vec<BoardConfig> {
+    /// Converts and returns all boards of this store sorted by name.
+    pub(crate) fn take_boards(&mut self) -> Vec<PredefinedBoard> {
         let boards: HashMap<String, Board> = take(&mut self.boards);
-        boards.into_values().map(|board| {
-                board
+        let mut boards: Vec<PredefinedBoard> = boards
+            .into_iter()
+            .map(|(name, board)| {
+                let board = board
                     .convert(&Predefined::default(), &mut Custom::default())
-                    .unwrap()
+                    .unwrap();
+                PredefinedBoard::new(name, board)
             })
-            .collect()
+            .collect();
+        boards.sort_by(|a, b| a.name().cmp(b.name()));
+        boards
     }
 }
@@ -0,0 +1,95 @@
+//! A lightweight model of a collection, which only contains the metadata needed to list it.
+//! Unknown fields like tiles and boards are skipped instead of being deserialized and converted.
+
+use crate::config::summary::{CollectionSummary, PuzzleSummary};
+use crate::json::model::PuzzleDifficulty;
+use crate::{IntegrityConfig, LoadLimits, PuzzleDifficultyConfig, ReadError};
+use serde::Deserialize;
+
+#[derive(Deserialize)]
+pub struct CollectionHeader {
+    pub name: String,
+    pub description: Option<String>,
+    pub author: String,
+    pub id: String,
+    pub version: Option<String>,
+    pub puzzles: Vec<PuzzleHeader>,
+}
+
+#[derive(Deserialize)]
+pub struct PuzzleHeader {
+    pub id: String,
+    pub name: String,
+    pub difficulty: Option<PuzzleDifficulty>,
+    #[serde(default)]
+    pub unsolvable: bool,
+}
+
+impl CollectionHeader {
+    pub fn check_limits(&self, limits: &LoadLimits) -> Result<(), ReadError> {
+        if self.puzzles.len() > limits.max_puzzles {
+            return Err(ReadError::TooManyPuzzles {
+                count: self.puzzles.len(),
+                max: limits.max_puzzles,
+            });
+        }
+        Ok(())
+    }
+
+    pub fn into_summary(self, integrity: IntegrityConfig) -> CollectionSummary {
+        let puzzles = self
+            .puzzles
+            .into_iter()
+            .map(|puzzle| {
+                PuzzleSummary::new(
+                    puzzle.id,
+                    puzzle.name,
+                    puzzle.difficulty.map(PuzzleDifficultyConfig::from),
+                    puzzle.unsolvable,
+                )
+            })
+            .collect();
+        CollectionSummary::new(
+            self.name,
+            self.description,
+            self.author,
+            self.id,
+            self.version,
+            integrity,
+            puzzles,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{create_json_loader, CollectionSummary, LoadLimits, ReadError};
+
+    const PREDEFINED_JSON: &str = r#"{"tiles": {}, "boards": {}}"#;
+
+    #[test]
+    fn test_load_collection_summary() {
+        let loader = create_json_loader(PREDEFINED_JSON, "0.3.0").unwrap();
+        let json = include_str!("fixtures/format_1_0.3.0.json");
+
+        let summary = loader.load_collection_summary(json).unwrap();
+        let collection = loader.load_puzzle_collection(json).unwrap();
+
+        assert_eq!(summary, CollectionSummary::from(&collection));
+        assert!(summary.puzzles()[1].is_unsolvable());
+    }
+
+    #[test]
+    fn test_load_collection_summary_too_many_puzzles() {
+        let mut loader = create_json_loader(PREDEFINED_JSON, "0.1.0").unwrap();
+        loader.set_limits(LoadLimits {
+            max_puzzles: 0,
+            ..LoadLimits::default()
+        });
+
+        assert_eq!(
+            loader.load_collection_summary(include_str!("fixtures/format_2.json")),
+            Err(ReadError::TooManyPuzzles { count: 1, max: 0 })
+        );
+    }
+}
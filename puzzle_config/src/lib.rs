@@ -1,23 +1,36 @@
 mod config;
+#[cfg(feature = "digitizer")]
+pub mod digitizer;
 mod error;
 mod json;
+pub mod practice;
 pub mod random;
-mod validation;
+pub mod statistics;
+pub mod validation;
 
 pub use config::area::AreaConfig;
 pub use config::area::AreaValueFormatter;
+pub use config::area::MAX_PADDED_WIDTH;
 pub use config::board::BoardConfig;
 pub use config::collection::PuzzleConfigCollection;
 pub use config::color::ColorConfig;
+pub use config::constraint::TileConstraint;
+pub use config::coverage::{AreaCoverage, InventoryCheck, RemainingCoverage};
 pub use config::difficulty::PuzzleDifficultyConfig;
+pub use config::hints::HintConfig;
+pub use config::integrity::IntegrityConfig;
 pub use config::preview::PreviewConfig;
 pub use config::progression::ProgressionConfig;
 pub use config::puzzle::PuzzleConfig;
 pub use config::puzzle::PuzzleId;
-pub use config::target::{Target, TargetIndex, TargetTemplate};
+pub use config::statistics::SolutionStatistics;
+pub use config::summary::{CollectionSummary, PuzzleSummary};
+pub use config::target::{AreaTarget, Target, TargetIndex, TargetTemplate, ANY_DISPLAY_VALUE};
 pub use config::tile::TileConfig;
 pub use error::ReadError;
 pub use json::JsonLoader;
+pub use json::LoadLimits;
+pub use json::{Ed25519Verifier, SignatureVerifier};
 
 const PUZZLED_VERSION_FIELD: &str = "puzzled";
 
@@ -34,28 +47,60 @@ pub fn create_json_loader(
     Ok(json_loader)
 }
 
+/// Computes the checksum of a collection JSON.
+/// The result can be put into the `checksum` field of the collection to allow verifying that it
+/// has not been modified.
+pub fn compute_checksum(collection_json: &str) -> Result<String, ReadError> {
+    json::compute_checksum(collection_json)
+}
+
 pub fn get_predefined(predefined_json_str: &str, puzzled_version: &str) -> Predefined {
     json::read_predefined(predefined_json_str, puzzled_version)
 }
 
+/// The predefined tiles and boards, which can be referenced by name in collections.
 pub struct Predefined {
     tiles: Vec<TileConfig>,
-    boards: Vec<BoardConfig>,
+    boards: Vec<PredefinedBoard>,
 }
 
 impl Predefined {
+    /// The predefined tiles sorted by the number of cells and name.
+    /// The name of each tile is set.
     pub fn tiles(&self) -> &[TileConfig] {
         &self.tiles
     }
 
-    pub fn boards(&self) -> &[BoardConfig] {
+    /// The predefined boards sorted by name.
+    pub fn boards(&self) -> &[PredefinedBoard] {
         &self.boards
     }
 }
 
+/// A predefined board with the name it can be referenced by.
+#[derive(Debug, Clone)]
+pub struct PredefinedBoard {
+    name: String,
+    board: BoardConfig,
+}
+
+impl PredefinedBoard {
+    pub fn new(name: String, board: BoardConfig) -> PredefinedBoard {
+        PredefinedBoard { name, board }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn board(&self) -> &BoardConfig {
+        &self.board
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::create_json_loader;
+    use crate::{compute_checksum, create_json_loader, IntegrityConfig, ReadError};
     use puzzled_common::shape::shape_square;
 
     #[test]
@@ -138,4 +183,72 @@ mod tests {
             &shape_square(&[[true, true], [false, true], [true, true]])
         );
     }
+
+    #[test]
+    fn test_load_puzzle_collection_with_checksum() {
+        let predefined_json_str = r#"{"tiles": {}, "boards": {}}"#;
+        let json_loader = create_json_loader(predefined_json_str, "0.1.0").unwrap();
+        let json_str = r#"
+        {
+          "puzzled": "0.1.0",
+          "name": "Test Collection",
+          "author": "Test Author",
+          "id": "de.til7701.Puzzled.test-collection",
+          "puzzles": [
+            {
+              "name": "Simple",
+              "tiles": [[[1, 1]]],
+              "board": {"layout": [[0, 0]]}
+            }
+          ]
+        }
+        "#;
+        let checksum = compute_checksum(json_str).unwrap();
+        let signed_json_str = json_str.replacen(
+            "\"puzzled\"",
+            &format!("\"checksum\": \"{}\",\n          \"puzzled\"", checksum),
+            1,
+        );
+
+        let collection = json_loader
+            .load_puzzle_collection(&signed_json_str)
+            .unwrap();
+        assert_eq!(collection.integrity(), &IntegrityConfig::Checksum);
+
+        let tampered_json_str = signed_json_str.replace("Test Author", "Someone Else");
+        assert_eq!(
+            json_loader.load_puzzle_collection(&tampered_json_str).err(),
+            Some(ReadError::ChecksumMismatch)
+        );
+    }
+
+    #[test]
+    fn test_predefined_catalog() {
+        let predefined_json_str = r#"
+        {
+            "tiles": {
+                "L3": [[1, 0], [1, 1]],
+                "I1": [[1]],
+                "D2": [[1, 1]]
+            },
+            "boards": {
+                "2x2": {"layout": [[0, 0], [0, 0]]},
+                "1x2": {"layout": [[0, 0]]}
+            }
+        }
+        "#;
+        let json_loader = create_json_loader(predefined_json_str, "0.1.0").unwrap();
+
+        let catalog = json_loader.predefined_catalog();
+
+        let tile_names: Vec<&str> = catalog
+            .tiles()
+            .iter()
+            .map(|tile| tile.name().as_deref().unwrap())
+            .collect();
+        assert_eq!(tile_names, vec!["I1", "D2", "L3"]);
+        let board_names: Vec<&str> = catalog.boards().iter().map(|board| board.name()).collect();
+        assert_eq!(board_names, vec!["1x2", "2x2"]);
+        assert_eq!(catalog.boards()[1].board().layout().dim(), (2, 2));
+    }
 }
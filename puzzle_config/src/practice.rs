@@ -0,0 +1,266 @@
+//! Practice puzzles, which contain only a part of the board and some of the tiles of a puzzle.
+//!
+//! They are built on the fly, so sections of hard puzzles can be learned one at a time. The
+//! practice puzzle is wrapped in its own collection, so it can be shown like any other puzzle
+//! without affecting the progress of the original one.
+
+use crate::{
+    BoardConfig, HintConfig, PreviewConfig, ProgressionConfig, PuzzleConfig,
+    PuzzleConfigCollection, TileConfig, TileConstraint,
+};
+use puzzled_common::{Shape, ShapeType};
+
+/// The id of the collections containing practice puzzles.
+pub const PRACTICE_COLLECTION_ID: &str = "de.til7701.Puzzled.Practice";
+
+/// A rectangular part of a board in the coordinates of its layout. `x` is the column and `y`
+/// the row of the top left cell.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PracticeRegion {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+/// An error while creating a practice puzzle.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PracticeError {
+    /// The region has no cell, on which a tile can be placed.
+    EmptyRegion,
+    /// The region extends beyond the board.
+    RegionOffBoard,
+    /// The board is not made of squares and the region starts at an odd column or row, which
+    /// would change the orientation of its cells.
+    MisalignedRegion,
+    /// No tile was selected.
+    NoTiles,
+    /// A selected tile is not part of the puzzle.
+    UnknownTile(usize),
+}
+
+/// Creates a collection containing exactly one puzzle, which consists of the given region of the
+/// board and the given tiles of the puzzle.
+///
+/// Area boards become simple boards, because the targets of the areas do not fit a part of the
+/// board. Tile constraints are kept, if their cell is inside the region and their tile was
+/// selected. Hints are not restricted, as the puzzle is meant for learning.
+///
+/// # Arguments
+///
+/// * `collection`: The collection of the puzzle.
+/// * `puzzle`: The puzzle to practice a part of.
+/// * `region`: The part of the board to practice on.
+/// * `tile_indices`: The indices of the tiles of the puzzle to practice with.
+///
+/// returns: Result<PuzzleConfigCollection, PracticeError>
+pub fn practice_puzzle(
+    collection: &PuzzleConfigCollection,
+    puzzle: &PuzzleConfig,
+    region: &PracticeRegion,
+    tile_indices: &[usize],
+) -> Result<PuzzleConfigCollection, PracticeError> {
+    if tile_indices.is_empty() {
+        return Err(PracticeError::NoTiles);
+    }
+    let tiles = tile_indices
+        .iter()
+        .map(|&i| {
+            puzzle
+                .tiles()
+                .get(i)
+                .cloned()
+                .ok_or(PracticeError::UnknownTile(i))
+        })
+        .collect::<Result<Vec<TileConfig>, PracticeError>>()?;
+    let layout = crop_layout(puzzle.board_config().layout(), region)?;
+    let tile_constraints = puzzle
+        .tile_constraints()
+        .iter()
+        .filter_map(|constraint| crop_constraint(constraint, region, &tiles))
+        .collect();
+
+    let practice = PuzzleConfig::new(
+        0,
+        "practice".to_string(),
+        format!("Practice: {}", puzzle.name()),
+        None,
+        None,
+        false,
+        tiles,
+        BoardConfig::Simple { layout },
+        None,
+        None,
+        HintConfig::default(),
+    )
+    .with_tile_constraints(tile_constraints);
+    Ok(PuzzleConfigCollection::new(
+        format!("Practice: {}", collection.name()),
+        None,
+        collection.author().to_string(),
+        PRACTICE_COLLECTION_ID.to_string(),
+        None,
+        ProgressionConfig::Any,
+        PreviewConfig::default(),
+        collection.palette().to_vec(),
+        false,
+        collection.music().clone(),
+        vec![practice],
+    ))
+}
+
+/// Returns the cells of the layout inside the region.
+fn crop_layout(layout: &Shape, region: &PracticeRegion) -> Result<Shape, PracticeError> {
+    let (width, height) = layout.dim();
+    if region.width == 0 || region.height == 0 {
+        return Err(PracticeError::EmptyRegion);
+    }
+    if region.x + region.width > width || region.y + region.height > height {
+        return Err(PracticeError::RegionOffBoard);
+    }
+    if layout.shape_type() != ShapeType::Square
+        && (!region.x.is_multiple_of(2) || !region.y.is_multiple_of(2))
+    {
+        return Err(PracticeError::MisalignedRegion);
+    }
+
+    let mut cropped = Shape::from_elem((region.width, region.height), layout.shape_type(), false);
+    for x in 0..region.width {
+        for y in 0..region.height {
+            let quarters = layout.quarters((region.x + x, region.y + y));
+            cropped.set_quarters((x, y), quarters);
+        }
+    }
+    if !cropped.iter().any(|&cell| cell) {
+        return Err(PracticeError::EmptyRegion);
+    }
+    Ok(cropped)
+}
+
+/// Returns the constraint moved into the region, if its cell is inside the region and one of
+/// the tiles can cover it.
+fn crop_constraint(
+    constraint: &TileConstraint,
+    region: &PracticeRegion,
+    tiles: &[TileConfig],
+) -> Option<TileConstraint> {
+    let (x, y) = constraint.cell();
+    let inside = (region.x..region.x + region.width).contains(&x)
+        && (region.y..region.y + region.height).contains(&y);
+    if !inside || !tiles.iter().any(|tile| tile.base() == constraint.base()) {
+        return None;
+    }
+    Some(TileConstraint::new(
+        (x - region.x, y - region.y),
+        constraint.tile_name().to_string(),
+        constraint.base().clone(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ColorConfig;
+    use puzzled_common::shape::shape_square;
+
+    fn collection() -> PuzzleConfigCollection {
+        let tiles = vec![
+            TileConfig::new(
+                shape_square(&[[true, true]]),
+                ColorConfig::default_with_index(0),
+                Some("I2".to_string()),
+            ),
+            TileConfig::new(
+                shape_square(&[[true]]),
+                ColorConfig::default_with_index(1),
+                Some("I1".to_string()),
+            ),
+        ];
+        let layout = shape_square(&[[true, true, false], [true, true, true], [false, true, true]]);
+        let puzzle = PuzzleConfig::new(
+            0,
+            "0".to_string(),
+            "Puzzle".to_string(),
+            None,
+            None,
+            false,
+            tiles.clone(),
+            BoardConfig::Simple { layout },
+            None,
+            None,
+            HintConfig::default(),
+        )
+        .with_tile_constraints(vec![
+            TileConstraint::new((1, 1), "I1".to_string(), tiles[1].base().clone()),
+            TileConstraint::new((0, 0), "I2".to_string(), tiles[0].base().clone()),
+        ]);
+        PuzzleConfigCollection::new(
+            "Collection".to_string(),
+            None,
+            "Author".to_string(),
+            "de.til7701.Puzzled.test".to_string(),
+            None,
+            ProgressionConfig::Any,
+            PreviewConfig::default(),
+            Vec::new(),
+            true,
+            None,
+            vec![puzzle],
+        )
+    }
+
+    #[test]
+    fn test_practice_puzzle() {
+        let collection = collection();
+        let region = PracticeRegion {
+            x: 1,
+            y: 1,
+            width: 2,
+            height: 2,
+        };
+
+        let practice =
+            practice_puzzle(&collection, &collection.puzzles()[0], &region, &[1]).unwrap();
+
+        assert_eq!(practice.id(), PRACTICE_COLLECTION_ID);
+        let puzzle = &practice.puzzles()[0];
+        assert_eq!(puzzle.name(), "Practice: Puzzle");
+        assert_eq!(puzzle.tiles().len(), 1);
+        assert_eq!(puzzle.tiles()[0].name(), &Some("I1".to_string()));
+        assert_eq!(
+            puzzle.board_config().layout(),
+            &shape_square(&[[true, true], [true, true]])
+        );
+        assert_eq!(puzzle.tile_constraints().len(), 1);
+        assert_eq!(puzzle.tile_constraints()[0].cell(), (0, 0));
+    }
+
+    #[test]
+    fn test_practice_puzzle_errors() {
+        let collection = collection();
+        let puzzle = &collection.puzzles()[0];
+        let region = |x, y, width, height| PracticeRegion {
+            x,
+            y,
+            width,
+            height,
+        };
+
+        assert_eq!(
+            practice_puzzle(&collection, puzzle, &region(0, 0, 1, 1), &[]).unwrap_err(),
+            PracticeError::NoTiles
+        );
+        assert_eq!(
+            practice_puzzle(&collection, puzzle, &region(0, 0, 1, 1), &[2]).unwrap_err(),
+            PracticeError::UnknownTile(2)
+        );
+        assert_eq!(
+            practice_puzzle(&collection, puzzle, &region(2, 0, 2, 1), &[0]).unwrap_err(),
+            PracticeError::RegionOffBoard
+        );
+        assert_eq!(
+            practice_puzzle(&collection, puzzle, &region(2, 0, 1, 1), &[0]).unwrap_err(),
+            PracticeError::EmptyRegion
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    BoardConfig, ColorConfig, PreviewConfig, ProgressionConfig, PuzzleConfig,
+    BoardConfig, ColorConfig, HintConfig, PreviewConfig, ProgressionConfig, PuzzleConfig,
     PuzzleConfigCollection, TileConfig,
 };
 use puzzled_common::Shape;
@@ -50,6 +50,8 @@ pub fn random_puzzle(settings: &RandomPuzzleSettings) -> PuzzleConfigCollection
         tiles,
         board,
         None,
+        None,
+        HintConfig::default(),
     );
     PuzzleConfigCollection::new(
         "Random".to_string(),
@@ -59,6 +61,9 @@ pub fn random_puzzle(settings: &RandomPuzzleSettings) -> PuzzleConfigCollection
         None,
         ProgressionConfig::Any,
         PreviewConfig::default(),
+        Vec::new(),
+        true,
+        None,
         vec![puzzle],
     )
 }
@@ -188,7 +193,8 @@ mod tests {
                 shape_square(&[[true]]),
                 ColorConfig::default_with_index(1),
                 None
-            ),
+            )
+            .with_id("f#2".to_string()),
             puzzle.tiles()[1]
         );
         assert_eq!(
@@ -196,7 +202,8 @@ mod tests {
                 shape_square(&[[true]]),
                 ColorConfig::default_with_index(2),
                 None
-            ),
+            )
+            .with_id("f#3".to_string()),
             puzzle.tiles()[2]
         );
         assert_eq!(
@@ -204,7 +211,8 @@ mod tests {
                 shape_square(&[[true]]),
                 ColorConfig::default_with_index(3),
                 None
-            ),
+            )
+            .with_id("f#4".to_string()),
             puzzle.tiles()[3]
         );
     }
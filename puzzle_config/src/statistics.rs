@@ -0,0 +1,287 @@
+use crate::{BoardConfig, PuzzleConfig, ReadError, SolutionStatistics, TargetIndex};
+use puzzle_solver::board::{Board, CellRequirement};
+use puzzle_solver::count_all_filling;
+use puzzle_solver::result::UnsolvableReason;
+use puzzle_solver::tile::Tile;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+use tokio_util::sync::CancellationToken;
+
+/// The field in a puzzle of a collection JSON, which contains the solution statistics.
+const SOLUTION_STATISTICS_FIELD: &str = "solution_statistics";
+
+/// Computes the solution statistics for the given puzzle.
+///
+/// The solver counts the solutions for every possible target of the puzzle. For puzzles with
+/// areas, these are all combinations of one cell per area. Simple puzzles only have one target.
+/// Depending on the puzzle, this may take a very long time. The cancellation token can be used
+/// to stop the computation.
+///
+/// # Arguments
+///
+/// * `puzzle_config`: The puzzle to compute the statistics for.
+/// * `cancel_token`: A cancellation token to cancel the computation.
+///
+/// returns: Result<SolutionStatistics, UnsolvableReason>
+pub async fn compute_solution_statistics(
+    puzzle_config: &PuzzleConfig,
+    cancel_token: CancellationToken,
+) -> Result<SolutionStatistics, UnsolvableReason> {
+    let tiles: Vec<Tile> = puzzle_config
+        .tiles()
+        .iter()
+        .map(|tile| Tile::new(tile.base().clone()))
+        .collect();
+
+    let mut solution_counts = Vec::new();
+    for target in all_targets(puzzle_config.board_config()) {
+        let mut board = create_board(puzzle_config.board_config(), &target);
+        for tile_constraint in puzzle_config.tile_constraints() {
+            tile_constraint.apply(&mut board);
+        }
+        let count = count_all_filling(board, &tiles, cancel_token.clone()).await?;
+        solution_counts.push(count);
+    }
+
+    Ok(SolutionStatistics::from_solution_counts(
+        &solution_counts,
+        Some(OffsetDateTime::now_utc()),
+    ))
+}
+
+/// Returns all combinations of one target index per area.
+fn all_targets(board_config: &BoardConfig) -> Vec<Vec<TargetIndex>> {
+    let mut targets: Vec<Vec<TargetIndex>> = vec![Vec::new()];
+    for area_index in 0..board_config.area_count() {
+        let values = board_config.get_display_values_for_area(area_index as i32);
+        targets = targets
+            .iter()
+            .flat_map(|target| {
+                values.iter().map(move |(_, target_index)| {
+                    let mut target = target.clone();
+                    target.push(target_index.clone());
+                    target
+                })
+            })
+            .collect();
+    }
+    targets
+}
+
+/// Creates the board for the solver, where the cells outside the board are blocked and the target
+/// cells must stay empty.
+pub(crate) fn create_board(board_config: &BoardConfig, target: &[TargetIndex]) -> Board {
+    let mut board: Board = board_config.layout().map(|on_board| !on_board).into();
+    for TargetIndex(x, y) in target {
+        board.set_requirement([*x, *y], CellRequirement::MustStayEmpty);
+    }
+    board
+}
+
+/// Embeds the given statistics into the puzzles of a collection JSON.
+///
+/// Existing statistics of the puzzles are replaced. All other content of the collection is kept
+/// as is.
+///
+/// # Arguments
+///
+/// * `collection_json`: The collection JSON to embed the statistics into.
+/// * `statistics`: The statistics to embed, mapped by the id of the puzzle. Puzzles of older
+///   collections without an `id` are identified by their index like when they are loaded.
+///
+/// returns: Result<String, ReadError> an error, if an id does not match any puzzle
+pub fn embed_solution_statistics(
+    collection_json: &str,
+    statistics: &HashMap<String, SolutionStatistics>,
+) -> Result<String, ReadError> {
+    let mut value: Value =
+        serde_json::from_str(collection_json).map_err(|e| ReadError::JsonError(e.to_string()))?;
+
+    let puzzles = value
+        .get_mut("puzzles")
+        .and_then(Value::as_array_mut)
+        .ok_or_else(|| ReadError::JsonError("Missing `puzzles` array.".to_string()))?;
+    for (id, puzzle_statistics) in statistics {
+        let puzzle = puzzles
+            .iter_mut()
+            .enumerate()
+            .find(|(index, puzzle)| puzzle_id(puzzle, *index) == *id)
+            .and_then(|(_, puzzle)| puzzle.as_object_mut())
+            .ok_or_else(|| ReadError::JsonError(format!("No puzzle with the id '{}'.", id)))?;
+        puzzle.insert(
+            SOLUTION_STATISTICS_FIELD.to_string(),
+            statistics_to_json(puzzle_statistics),
+        );
+    }
+
+    serde_json::to_string_pretty(&value).map_err(|e| ReadError::JsonError(e.to_string()))
+}
+
+/// Returns the id of the puzzle or its index, if it has none.
+fn puzzle_id(puzzle: &Value, index: usize) -> String {
+    match puzzle.get("id").and_then(Value::as_str) {
+        Some(id) => id.to_string(),
+        None => index.to_string(),
+    }
+}
+
+fn statistics_to_json(statistics: &SolutionStatistics) -> Value {
+    let mut value = json!({
+        "min": statistics.min(),
+        "max": statistics.max(),
+        "median": statistics.median(),
+        "total": statistics.total(),
+        "targets": statistics.target_count(),
+    });
+    if let Some(computed_on) = statistics
+        .computed_on()
+        .and_then(|computed_on| computed_on.format(&Rfc3339).ok())
+    {
+        value["computed_on"] = Value::String(computed_on);
+    }
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::area::{AreaConfig, AreaValueFormatter};
+    use crate::{ColorConfig, HintConfig, TargetTemplate, TileConfig};
+    use ndarray::arr2;
+    use puzzled_common::shape::shape_square;
+
+    fn create_puzzle(board_config: BoardConfig, tiles: Vec<TileConfig>) -> PuzzleConfig {
+        PuzzleConfig::new(
+            0,
+            "0".to_string(),
+            "Test".to_string(),
+            None,
+            None,
+            false,
+            tiles,
+            board_config,
+            None,
+            None,
+            HintConfig::default(),
+        )
+    }
+
+    fn create_tile(base: &[[bool; 2]]) -> TileConfig {
+        TileConfig::new(shape_square(base), ColorConfig::default_with_index(0), None)
+    }
+
+    #[tokio::test]
+    async fn test_compute_solution_statistics_simple() {
+        let board_config = BoardConfig::Simple {
+            layout: shape_square(&[[true, true, true], [true, true, true]]),
+        };
+        let tiles = vec![
+            create_tile(&[[true, true], [true, false]]),
+            create_tile(&[[true, true], [true, false]]),
+        ];
+        let puzzle = create_puzzle(board_config, tiles);
+
+        let statistics = compute_solution_statistics(&puzzle, CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert_eq!(statistics.min(), 2);
+        assert_eq!(statistics.max(), 2);
+        assert_eq!(statistics.total(), 2);
+        assert_eq!(statistics.target_count(), 1);
+        assert!(statistics.computed_on().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_compute_solution_statistics_area() {
+        let board_config = BoardConfig::Area {
+            layout: Box::new(shape_square(&[[true, true, true], [true, true, true]])),
+            area_indices: Box::new(arr2(&[[0, 0, 0], [0, 0, 0]])),
+            display_values: Box::new(arr2(&[
+                ["1".to_string(), "2".to_string(), "3".to_string()],
+                ["4".to_string(), "5".to_string(), "6".to_string()],
+            ])),
+            value_order: Box::new(arr2(&[[0, 1, 2], [3, 4, 5]])),
+            area_configs: vec![AreaConfig::new(
+                "Number".to_string(),
+                AreaValueFormatter::Plain,
+                "1".to_string(),
+            )],
+            target_template: TargetTemplate::new("{0}"),
+            show_legend: false,
+            show_area_boundaries: false,
+        };
+        let tiles = vec![
+            create_tile(&[[true, true], [true, true]]),
+            create_tile(&[[true, false], [false, false]]),
+        ];
+        let puzzle = create_puzzle(board_config, tiles);
+
+        let statistics = compute_solution_statistics(&puzzle, CancellationToken::new())
+            .await
+            .unwrap();
+
+        assert_eq!(statistics.min(), 0);
+        assert_eq!(statistics.max(), 1);
+        assert_eq!(statistics.total(), 4);
+        assert_eq!(statistics.target_count(), 6);
+    }
+
+    #[test]
+    fn test_embed_solution_statistics() {
+        let collection_json =
+            r#"{"name": "Test", "puzzles": [{"id": "b", "name": "B"}, {"id": "a", "name": "A"}]}"#;
+        let mut statistics = HashMap::new();
+        statistics.insert(
+            "a".to_string(),
+            SolutionStatistics::new(1, 5, 3, 9, 3, None),
+        );
+
+        let result = embed_solution_statistics(collection_json, &statistics).unwrap();
+
+        let value: Value = serde_json::from_str(&result).unwrap();
+        assert_eq!(value["name"], "Test");
+        assert!(value["puzzles"][0].get(SOLUTION_STATISTICS_FIELD).is_none());
+        assert_eq!(
+            value["puzzles"][1][SOLUTION_STATISTICS_FIELD],
+            json!({"min": 1, "max": 5, "median": 3, "total": 9, "targets": 3})
+        );
+    }
+
+    #[test]
+    fn test_embed_solution_statistics_without_ids() {
+        let collection_json = r#"{"puzzles": [{"name": "A"}, {"name": "B"}]}"#;
+        let mut statistics = HashMap::new();
+        statistics.insert(
+            "1".to_string(),
+            SolutionStatistics::new(1, 1, 1, 1, 1, None),
+        );
+
+        let result = embed_solution_statistics(collection_json, &statistics).unwrap();
+
+        let value: Value = serde_json::from_str(&result).unwrap();
+        assert!(value["puzzles"][0].get(SOLUTION_STATISTICS_FIELD).is_none());
+        assert!(value["puzzles"][1].get(SOLUTION_STATISTICS_FIELD).is_some());
+    }
+
+    #[test]
+    fn test_embed_solution_statistics_unknown_id() {
+        let collection_json = r#"{"puzzles": [{"id": "a", "name": "A"}]}"#;
+        let mut statistics = HashMap::new();
+        statistics.insert(
+            "b".to_string(),
+            SolutionStatistics::new(1, 1, 1, 1, 1, None),
+        );
+
+        let result = embed_solution_statistics(collection_json, &statistics);
+
+        assert_eq!(
+            result,
+            Err(ReadError::JsonError(
+                "No puzzle with the id 'b'.".to_string()
+            ))
+        );
+    }
+}
@@ -1,5 +1,112 @@
-use crate::ReadError;
+use crate::statistics::create_board;
+use crate::{
+    AreaCoverage, BoardConfig, ColorConfig, PreviewConfig, ProgressionConfig, PuzzleConfig,
+    PuzzleConfigCollection, ReadError, TargetIndex, TileConstraint,
+};
+use puzzle_solver::board::Board;
+use puzzle_solver::result::UnsolvableReason;
+use puzzle_solver::tile::Tile;
+use puzzle_solver::{has_unique_solution, solve_all_filling};
+use puzzled_common::Shape;
 use regex::Regex;
+use std::collections::HashSet;
+use tokio_util::sync::CancellationToken;
+
+/// Tiles with colors closer than this Delta-E are hard to tell apart.
+pub const MIN_COLOR_DELTA_E: f32 = 5.0;
+
+/// A color used by the app to highlight parts of the board, which tiles should not be confused
+/// with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HighlightColor {
+    /// The default accent color used for the selected target.
+    Accent,
+    /// The default error color used for wrongly placed tiles.
+    Error,
+}
+
+impl HighlightColor {
+    pub const ALL: [HighlightColor; 2] = [HighlightColor::Accent, HighlightColor::Error];
+
+    pub const fn color(&self) -> ColorConfig {
+        match self {
+            HighlightColor::Accent => ColorConfig::from_rgb_hex(0x3584e4),
+            HighlightColor::Error => ColorConfig::from_rgb_hex(0xe01b24),
+        }
+    }
+}
+
+/// A problem found when validating a puzzle with [validate_puzzle] or the settings of a
+/// collection with [check_collection_settings].
+/// See [ValidationIssue::is_warning] to distinguish warnings from errors.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationIssue {
+    /// The default value of an area is not one of the values of the area on the board, so the
+    /// default target is incomplete. The puzzle is not checked for its default target then.
+    UnknownDefaultValue {
+        /// The index of the puzzle in the collection.
+        puzzle: usize,
+        /// The index of the area on the board.
+        area: usize,
+        name: String,
+        value: String,
+    },
+    /// The tiles do not cover exactly the cells of the board, which have to be covered for the
+    /// default target. The values are in cells.
+    AreaMismatch { tile_cells: f32, board_cells: f32 },
+    /// The solver found no solution for the default target.
+    Unsolvable(UnsolvableReason),
+    /// Two tiles have nearly identical colors. The values are the indices of the tiles.
+    SimilarTileColors {
+        first: usize,
+        second: usize,
+        delta_e: f32,
+    },
+    /// The color of a tile is nearly identical to a highlight color.
+    HighlightColorClash {
+        tile: usize,
+        highlight: HighlightColor,
+        delta_e: f32,
+    },
+    /// Puzzles are unlocked one by one, but locked puzzles show no preview at all, so players
+    /// cannot see what they are unlocking.
+    SequentialWithoutPreview,
+    /// The preview is configured, but all puzzles are unlocked from the start, so it is never
+    /// shown.
+    UnusedPreview,
+}
+
+impl ValidationIssue {
+    /// Returns true, if the puzzle can still be played with this issue, but it should be fixed
+    /// to make the puzzle easier to play.
+    pub fn is_warning(&self) -> bool {
+        match self {
+            ValidationIssue::AreaMismatch { .. } | ValidationIssue::Unsolvable(_) => false,
+            ValidationIssue::UnknownDefaultValue { .. }
+            | ValidationIssue::SimilarTileColors { .. }
+            | ValidationIssue::HighlightColorClash { .. }
+            | ValidationIssue::SequentialWithoutPreview
+            | ValidationIssue::UnusedPreview => true,
+        }
+    }
+
+    /// Returns a JSON pointer to the part of the collection file, which causes the issue, if it
+    /// can be told apart from the rest of the puzzle.
+    pub fn path(&self) -> Option<String> {
+        match self {
+            ValidationIssue::UnknownDefaultValue { puzzle, .. } => {
+                Some(format!("/puzzles/{}/board", puzzle))
+            }
+            ValidationIssue::SequentialWithoutPreview | ValidationIssue::UnusedPreview => {
+                Some("/preview".to_string())
+            }
+            ValidationIssue::AreaMismatch { .. }
+            | ValidationIssue::Unsolvable(_)
+            | ValidationIssue::SimilarTileColors { .. }
+            | ValidationIssue::HighlightColorClash { .. } => None,
+        }
+    }
+}
 
 pub(crate) fn validate_collection_id(id: String) -> Result<String, ReadError> {
     if id.trim().is_empty() {
@@ -13,9 +120,487 @@ pub(crate) fn validate_collection_id(id: String) -> Result<String, ReadError> {
     }
 }
 
+/// Checks, that the ids of the puzzles of a collection are neither blank nor duplicated, because
+/// the progress of the puzzles is stored by their id.
+pub(crate) fn validate_puzzle_ids<'a>(ids: impl Iterator<Item = &'a str>) -> Result<(), ReadError> {
+    let mut seen = HashSet::new();
+    for id in ids {
+        if id.trim().is_empty() {
+            return Err(ReadError::InvalidPuzzleId(id.to_string()));
+        }
+        if !seen.insert(id) {
+            return Err(ReadError::DuplicatePuzzleId(id.to_string()));
+        }
+    }
+    Ok(())
+}
+
+/// Checks, that the namespace has the same format as a collection id, so it cannot contain the
+/// `/` separating it from the names of its tiles and boards.
+pub(crate) fn validate_namespace(namespace: Option<String>) -> Result<Option<String>, ReadError> {
+    namespace
+        .map(|namespace| {
+            validate_collection_id(namespace).map_err(|e| match e {
+                ReadError::InvalidCollectionId(namespace) => ReadError::InvalidNamespace(namespace),
+                e => e,
+            })
+        })
+        .transpose()
+}
+
+/// A puzzle, which cannot be played, because there is nothing to do.
+/// These are usually mistakes of the author, which the app should explain instead of showing an
+/// empty puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrivialPuzzle {
+    /// The puzzle has no tiles to place.
+    NoTiles,
+    /// The board has no cells, which can be covered by tiles.
+    BoardComplete,
+}
+
+/// Checks, whether there is nothing to do in the given puzzle.
+/// This is cheap, so it can be checked every time a puzzle is shown.
+///
+/// # Arguments
+///
+/// * `puzzle_config`: The puzzle to check.
+///
+/// returns: Option<TrivialPuzzle> `None`, if the puzzle can be played.
+pub fn trivial_puzzle(puzzle_config: &PuzzleConfig) -> Option<TrivialPuzzle> {
+    if !puzzle_config
+        .board_config()
+        .layout()
+        .iter()
+        .any(|&cell| cell)
+    {
+        Some(TrivialPuzzle::BoardComplete)
+    } else if puzzle_config.tiles().is_empty() {
+        Some(TrivialPuzzle::NoTiles)
+    } else {
+        None
+    }
+}
+
+/// The schemes of URIs allowed for the music of a collection.
+const MUSIC_URI_SCHEMES: [&str; 2] = ["https://", "resource://"];
+
+pub(crate) fn validate_music_uri(uri: Option<String>) -> Result<Option<String>, ReadError> {
+    match uri {
+        Some(uri)
+            if !MUSIC_URI_SCHEMES
+                .iter()
+                .any(|scheme| uri.starts_with(scheme) && uri.len() > scheme.len()) =>
+        {
+            Err(ReadError::InvalidMusicUri(uri))
+        }
+        uri => Ok(uri),
+    }
+}
+
+/// Checks, whether the given puzzle can be played as intended.
+///
+/// The puzzle is checked for its default target, which is the target shown when the puzzle is
+/// opened. The checks are:
+/// - the default value of every area is one of its values, which only results in warnings,
+/// - the tiles cover exactly the cells to cover and
+/// - the solver finds a solution.
+///
+/// Additionally, the colors of the tiles are checked with [check_tile_colors], which only
+/// results in warnings.
+///
+/// The solver is only run, if all other checks pass. Depending on the puzzle, this may take a
+/// long time. The cancellation token can be used to stop it.
+///
+/// # Arguments
+///
+/// * `puzzle_config`: The puzzle to validate.
+/// * `cancel_token`: A cancellation token to cancel the validation.
+///
+/// returns: Result<Vec<ValidationIssue>, UnsolvableReason> The found issues with the warnings last,
+/// which are empty, if the puzzle is valid. Err with [UnsolvableReason::Cancelled], if the validation was cancelled.
+pub async fn validate_puzzle(
+    puzzle_config: &PuzzleConfig,
+    cancel_token: CancellationToken,
+) -> Result<Vec<ValidationIssue>, UnsolvableReason> {
+    let board_config = puzzle_config.board_config();
+    let target = board_config.default_target();
+    let mut issues = check_default_values(puzzle_config);
+
+    let coverage = AreaCoverage::new(puzzle_config, target.as_ref());
+    if !coverage.is_plausible() {
+        issues.push(ValidationIssue::AreaMismatch {
+            tile_cells: coverage.tile_cells(),
+            board_cells: coverage.board_cells(),
+        });
+    }
+
+    if !issues.is_empty() {
+        issues.extend(check_tile_colors(puzzle_config));
+        return Ok(issues);
+    }
+
+    let (board, tiles) = default_target_puzzle(
+        board_config,
+        &tile_shapes(puzzle_config),
+        puzzle_config.tile_constraints(),
+    );
+    match solve_all_filling(board, &tiles, cancel_token).await {
+        Ok(_) => {}
+        Err(UnsolvableReason::Cancelled) => return Err(UnsolvableReason::Cancelled),
+        Err(reason) => issues.push(ValidationIssue::Unsolvable(reason)),
+    }
+    issues.extend(check_tile_colors(puzzle_config));
+    Ok(issues)
+}
+
+/// Checks, whether the default value of each area of the puzzle is one of the values of the
+/// area. Dates, which are not on the board, are common for default values using the current
+/// date, so this only results in warnings.
+fn check_default_values(puzzle_config: &PuzzleConfig) -> Vec<ValidationIssue> {
+    let board_config = puzzle_config.board_config();
+    let BoardConfig::Area { area_configs, .. } = board_config else {
+        return Vec::new();
+    };
+    area_configs
+        .iter()
+        .enumerate()
+        .filter(|(area, area_config)| {
+            !board_config
+                .get_display_values_for_area(*area as i32)
+                .iter()
+                .any(|(value, _)| value == area_config.default_value())
+        })
+        .map(|(area, area_config)| ValidationIssue::UnknownDefaultValue {
+            puzzle: puzzle_config.index(),
+            area,
+            name: area_config.name().to_string(),
+            value: area_config.default_value().to_string(),
+        })
+        .collect()
+}
+
+/// Checks the settings of the collection for contradictions, which make the collection behave
+/// differently than the author likely intended. The checks are:
+/// - locked puzzles of a sequential collection show at least some preview and
+/// - the preview is only configured, if puzzles can be locked.
+///
+/// # Arguments
+///
+/// * `collection`: The collection to check.
+///
+/// returns: Vec<ValidationIssue> The found warnings.
+pub fn check_collection_settings(collection: &PuzzleConfigCollection) -> Vec<ValidationIssue> {
+    let preview = collection.preview();
+    let shows_preview = preview.show_board()
+        || preview.show_board_size()
+        || preview.show_tiles()
+        || preview.show_tile_count();
+    match collection.progression() {
+        ProgressionConfig::Sequential if !shows_preview => {
+            vec![ValidationIssue::SequentialWithoutPreview]
+        }
+        ProgressionConfig::Any if *preview != PreviewConfig::default() => {
+            vec![ValidationIssue::UnusedPreview]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Checks, whether the given puzzle has exactly one solution for its default target.
+///
+/// Many authors want to guarantee this, so the puzzle can only be solved in the intended way.
+/// The search stops after a second solution was found, but proving that a solution is unique may
+/// still take a long time. The cancellation token can be used to stop it.
+///
+/// # Arguments
+///
+/// * `puzzle_config`: The puzzle to check.
+/// * `cancel_token`: A cancellation token to cancel the check.
+///
+/// returns: Result<bool, UnsolvableReason> False, if there is no or more than one solution.
+/// Err with [UnsolvableReason::Cancelled], if the check was cancelled.
+pub async fn check_unique_solution(
+    puzzle_config: &PuzzleConfig,
+    cancel_token: CancellationToken,
+) -> Result<bool, UnsolvableReason> {
+    let (board, tiles) = default_target_puzzle(
+        puzzle_config.board_config(),
+        &tile_shapes(puzzle_config),
+        puzzle_config.tile_constraints(),
+    );
+    has_unique_solution(board, &tiles, cancel_token).await
+}
+
+/// Works like [check_unique_solution], but for a board and tiles, which are not part of a puzzle
+/// yet, e.g. while they are edited.
+///
+/// # Arguments
+///
+/// * `board_config`: The board to check the default target of.
+/// * `tiles`: The base shapes of the tiles to place on the board.
+/// * `cancel_token`: A cancellation token to cancel the check.
+///
+/// returns: Result<bool, UnsolvableReason>
+pub async fn check_unique_board_solution(
+    board_config: &BoardConfig,
+    tiles: &[Shape],
+    cancel_token: CancellationToken,
+) -> Result<bool, UnsolvableReason> {
+    let (board, tiles) = default_target_puzzle(board_config, tiles, &[]);
+    has_unique_solution(board, &tiles, cancel_token).await
+}
+
+fn tile_shapes(puzzle_config: &PuzzleConfig) -> Vec<Shape> {
+    puzzle_config
+        .tiles()
+        .iter()
+        .map(|tile| tile.base().clone())
+        .collect()
+}
+
+/// Creates the board with the default target blocked and the constrained cells reserved and the
+/// tiles to solve it.
+fn default_target_puzzle(
+    board_config: &BoardConfig,
+    tiles: &[Shape],
+    tile_constraints: &[TileConstraint],
+) -> (Board, Vec<Tile>) {
+    let target_indices: Vec<TargetIndex> = board_config
+        .default_target()
+        .iter()
+        .flat_map(|target| target.fixed_indices().cloned())
+        .collect();
+    let mut board = create_board(board_config, &target_indices);
+    for tile_constraint in tile_constraints {
+        tile_constraint.apply(&mut board);
+    }
+    let tiles = tiles.iter().map(|tile| Tile::new(tile.clone())).collect();
+    (board, tiles)
+}
+
+/// Checks, whether the tiles of the given puzzle can be told apart by their colors.
+///
+/// A warning is returned for each pair of tiles with a Delta-E below [MIN_COLOR_DELTA_E] and for
+/// each tile with a color that close to one of the [HighlightColor]s.
+///
+/// # Arguments
+///
+/// * `puzzle_config`: The puzzle to check.
+///
+/// returns: Vec<ValidationIssue> The found warnings.
+pub fn check_tile_colors(puzzle_config: &PuzzleConfig) -> Vec<ValidationIssue> {
+    let colors: Vec<ColorConfig> = puzzle_config
+        .tiles()
+        .iter()
+        .map(|tile| tile.color())
+        .collect();
+    let mut issues = Vec::new();
+
+    for (first, first_color) in colors.iter().enumerate() {
+        for (second, second_color) in colors.iter().enumerate().skip(first + 1) {
+            let delta_e = first_color.delta_e(second_color);
+            if delta_e < MIN_COLOR_DELTA_E {
+                issues.push(ValidationIssue::SimilarTileColors {
+                    first,
+                    second,
+                    delta_e,
+                });
+            }
+        }
+    }
+
+    for (tile, color) in colors.iter().enumerate() {
+        for highlight in HighlightColor::ALL {
+            let delta_e = color.delta_e(&highlight.color());
+            if delta_e < MIN_COLOR_DELTA_E {
+                issues.push(ValidationIssue::HighlightColorClash {
+                    tile,
+                    highlight,
+                    delta_e,
+                });
+            }
+        }
+    }
+
+    issues
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::{ColorConfig, HintConfig, TileConfig};
+    use puzzled_common::shape::shape_square;
+
+    fn create_puzzle(tiles: Vec<Shape>, layout: Shape) -> PuzzleConfig {
+        let colors = (0..tiles.len())
+            .map(ColorConfig::default_with_index)
+            .collect();
+        create_puzzle_with_colors(tiles, colors, layout)
+    }
+
+    fn create_puzzle_with_colors(
+        tiles: Vec<Shape>,
+        colors: Vec<ColorConfig>,
+        layout: Shape,
+    ) -> PuzzleConfig {
+        let tiles = tiles
+            .into_iter()
+            .zip(colors)
+            .map(|(base, color)| TileConfig::new(base, color, None))
+            .collect();
+        PuzzleConfig::new(
+            0,
+            "0".to_string(),
+            "Test".to_string(),
+            None,
+            None,
+            false,
+            tiles,
+            BoardConfig::Simple { layout },
+            None,
+            None,
+            HintConfig::default(),
+        )
+    }
+
+    #[test]
+    fn test_trivial_puzzle() {
+        let playable = create_puzzle(
+            vec![shape_square(&[[true, true]])],
+            shape_square(&[[true, true]]),
+        );
+        let no_tiles = create_puzzle(vec![], shape_square(&[[true, true]]));
+        let board_complete = create_puzzle(
+            vec![shape_square(&[[true, true]])],
+            shape_square(&[[false, false]]),
+        );
+
+        assert_eq!(trivial_puzzle(&playable), None);
+        assert_eq!(trivial_puzzle(&no_tiles), Some(TrivialPuzzle::NoTiles));
+        assert_eq!(
+            trivial_puzzle(&board_complete),
+            Some(TrivialPuzzle::BoardComplete)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_puzzle() {
+        let valid = create_puzzle(
+            vec![shape_square(&[[true, true]]), shape_square(&[[true, true]])],
+            shape_square(&[[true, true], [true, true]]),
+        );
+        let area_mismatch = create_puzzle(
+            vec![shape_square(&[[true, true]])],
+            shape_square(&[[true, true], [true, true]]),
+        );
+        let unsolvable = create_puzzle(
+            vec![shape_square(&[[true, true, true]]), shape_square(&[[true]])],
+            shape_square(&[[true, true], [true, true]]),
+        );
+
+        assert_eq!(
+            validate_puzzle(&valid, CancellationToken::new()).await,
+            Ok(vec![])
+        );
+        assert_eq!(
+            validate_puzzle(&area_mismatch, CancellationToken::new()).await,
+            Ok(vec![ValidationIssue::AreaMismatch {
+                tile_cells: 2.0,
+                board_cells: 4.0
+            }])
+        );
+        assert!(matches!(
+            validate_puzzle(&unsolvable, CancellationToken::new())
+                .await
+                .unwrap()
+                .as_slice(),
+            [ValidationIssue::Unsolvable(_)]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_check_unique_solution() {
+        let unique = create_puzzle(
+            vec![shape_square(&[[true, true, true]]); 2],
+            shape_square(&[[true, true, true], [true, true, true]]),
+        );
+        let ambiguous = create_puzzle(
+            vec![shape_square(&[[true, true]]), shape_square(&[[true, true]])],
+            shape_square(&[[true, true], [true, true]]),
+        );
+
+        assert_eq!(
+            check_unique_solution(&unique, CancellationToken::new()).await,
+            Ok(true)
+        );
+        assert_eq!(
+            check_unique_solution(&ambiguous, CancellationToken::new()).await,
+            Ok(false)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_validate_puzzle_with_similar_colors() {
+        let similar_colors = create_puzzle_with_colors(
+            vec![shape_square(&[[true, true]]), shape_square(&[[true, true]])],
+            vec![
+                ColorConfig::from_rgb_hex(0x2ec27e),
+                ColorConfig::from_rgb_hex(0x2fc27f),
+            ],
+            shape_square(&[[true, true], [true, true]]),
+        );
+
+        assert!(matches!(
+            validate_puzzle(&similar_colors, CancellationToken::new())
+                .await
+                .unwrap()
+                .as_slice(),
+            [ValidationIssue::SimilarTileColors {
+                first: 0,
+                second: 1,
+                ..
+            }]
+        ));
+    }
+
+    #[test]
+    fn test_check_tile_colors() {
+        let tiles = vec![shape_square(&[[true]]); 3];
+        let distinct = create_puzzle(tiles.clone(), shape_square(&[[true, true, true]]));
+        let highlight_clash = create_puzzle_with_colors(
+            tiles,
+            vec![
+                ColorConfig::from_rgb_hex(0x1c71d8),
+                HighlightColor::Error.color(),
+                ColorConfig::from_rgb_hex(0xf5c211),
+            ],
+            shape_square(&[[true, true, true]]),
+        );
+
+        assert_eq!(check_tile_colors(&distinct), vec![]);
+        let issues = check_tile_colors(&highlight_clash);
+        assert_eq!(
+            issues,
+            vec![ValidationIssue::HighlightColorClash {
+                tile: 1,
+                highlight: HighlightColor::Error,
+                delta_e: 0.0
+            }]
+        );
+        assert!(issues[0].is_warning());
+    }
+
+    #[test]
+    fn test_default_colors_are_distinguishable() {
+        for first in 0..35 {
+            for second in first + 1..35 {
+                let delta_e = ColorConfig::default_with_index(first)
+                    .delta_e(&ColorConfig::default_with_index(second));
+                assert!(delta_e >= MIN_COLOR_DELTA_E);
+            }
+        }
+    }
 
     #[test]
     fn test_validate_collection_id_valid() {
@@ -35,6 +620,119 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_music_uri() {
+        assert_eq!(validate_music_uri(None), Ok(None));
+        for uri in [
+            "https://example.com/music.ogg",
+            "resource:///de/til7701/Puzzled/sounds/solved.wav",
+        ] {
+            assert_eq!(
+                validate_music_uri(Some(uri.to_string())),
+                Ok(Some(uri.to_string()))
+            );
+        }
+        for uri in [
+            "file:///home/music.ogg",
+            "http://example.com/music.ogg",
+            "https://",
+            "",
+        ] {
+            assert_eq!(
+                validate_music_uri(Some(uri.to_string())),
+                Err(ReadError::InvalidMusicUri(uri.to_string()))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_validate_puzzle_unknown_default_value() {
+        let json = r#"{
+            "format": 3,
+            "name": "Test",
+            "author": "Author",
+            "id": "de.til7701.Puzzled.Test",
+            "puzzles": [{
+                "id": "calendar",
+                "name": "Calendar",
+                "tiles": [[[1, 1]]],
+                "board": {
+                    "area_layout": [[0, 0, 1]], "values": [["1", "2", "A"]],
+                    "value_order": [[0, 1, 0]],
+                    "areas": [
+                        {"name": "Day", "formatter": {"type": "Plain"},
+                         "default_factory": {"type": "Fixed", "value": "3"}},
+                        {"name": "Letter", "formatter": {"type": "Plain"},
+                         "default_factory": {"type": "Fixed", "value": "A"}}
+                    ],
+                    "target_template": "{0} {1}"
+                }
+            }]
+        }"#;
+        let collection = crate::create_json_loader(r#"{"tiles": {}, "boards": {}}"#, "1.3.0")
+            .unwrap()
+            .load_puzzle_collection(json)
+            .unwrap();
+
+        let issues = validate_puzzle(&collection.puzzles()[0], CancellationToken::new())
+            .await
+            .unwrap();
+        let unknown = ValidationIssue::UnknownDefaultValue {
+            puzzle: 0,
+            area: 0,
+            name: "Day".to_string(),
+            value: "3".to_string(),
+        };
+        assert_eq!(issues, vec![unknown.clone()]);
+        assert!(unknown.is_warning());
+        assert_eq!(unknown.path(), Some("/puzzles/0/board".to_string()));
+    }
+
+    #[test]
+    fn test_check_collection_settings() {
+        let collection = |progression: ProgressionConfig, preview: PreviewConfig| {
+            PuzzleConfigCollection::new(
+                "Test".to_string(),
+                None,
+                "Author".to_string(),
+                "de.til7701.Puzzled.Test".to_string(),
+                None,
+                progression,
+                preview,
+                Vec::new(),
+                false,
+                None,
+                Vec::new(),
+            )
+        };
+        let hidden = PreviewConfig::new(false, false, false, false);
+        let board_only = PreviewConfig::new(true, false, false, false);
+
+        assert_eq!(
+            check_collection_settings(&collection(ProgressionConfig::Sequential, hidden.clone())),
+            vec![ValidationIssue::SequentialWithoutPreview]
+        );
+        assert!(check_collection_settings(&collection(
+            ProgressionConfig::Sequential,
+            board_only.clone()
+        ))
+        .is_empty());
+        assert_eq!(
+            check_collection_settings(&collection(ProgressionConfig::Any, board_only)),
+            vec![ValidationIssue::UnusedPreview]
+        );
+        assert!(check_collection_settings(&collection(
+            ProgressionConfig::Any,
+            PreviewConfig::default()
+        ))
+        .is_empty());
+        assert_eq!(
+            ValidationIssue::UnusedPreview.path(),
+            Some("/preview".to_string())
+        );
+        assert!(ValidationIssue::SequentialWithoutPreview.is_warning());
+    }
+
     #[test]
     fn test_validate_collection_id_invalid() {
         let invalid_ids = vec![
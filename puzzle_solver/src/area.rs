@@ -0,0 +1,31 @@
+use puzzled_common::Shape;
+
+/// Number of quarters in a cell, which is the smallest unit of area.
+/// Half cells count as two quarters.
+pub const QUARTERS_PER_CELL: usize = 4;
+
+/// Counts the `true` quarters of all cells in the shape.
+///
+/// For tiles, this is the area they cover. For the shape of a [crate::board::Board], it is the
+/// blocked area.
+pub fn quarter_count(shape: &Shape) -> usize {
+    shape
+        .indexed_iter()
+        .map(|(index, _)| shape.quarters(index).count_ones() as usize)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use puzzled_common::shape::cell::CellShape;
+    use puzzled_common::shape::shape_square;
+
+    #[test]
+    fn test_quarter_count() {
+        let mut shape = shape_square(&[[true, true], [false, true]]);
+        shape.set_cell_shape((0, 1), CellShape::TopLeft);
+
+        assert_eq!(quarter_count(&shape), 10);
+    }
+}
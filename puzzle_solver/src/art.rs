@@ -0,0 +1,123 @@
+use puzzled_common::Shape;
+use puzzled_common::ShapeType::Square;
+
+/// The character marking a cell in text art.
+pub const CELL_CHAR: char = '#';
+/// The character marking the absence of a cell in text art.
+pub const EMPTY_CHAR: char = '.';
+
+/// Represents the reason why rows or text art could not be converted to a shape.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ArtError {
+    /// The row at the given index has a different length than the first row.
+    RaggedRow { row: usize },
+    /// The character at the given row and column is neither [CELL_CHAR] nor [EMPTY_CHAR].
+    InvalidChar {
+        row: usize,
+        column: usize,
+        char: char,
+    },
+}
+
+/// Creates a square shape from rows of numbers, where any number except `0` marks a cell.
+///
+/// The rows are laid out like the arrays given to
+/// [shape_square](puzzled_common::shape::shape_square), so `rows[x][y]` is the cell at (x, y).
+///
+/// # Arguments
+///
+/// * `rows`: The rows of the shape. All rows must have the same length.
+///
+/// returns: Result<Shape, ArtError>
+pub(crate) fn shape_from_rows<R: AsRef<[u8]>>(rows: &[R]) -> Result<Shape, ArtError> {
+    let width = rows.first().map_or(0, |row| row.as_ref().len());
+    let mut shape = Shape::from_elem((rows.len(), width), Square, false);
+    for (x, row) in rows.iter().enumerate() {
+        let row = row.as_ref();
+        if row.len() != width {
+            return Err(ArtError::RaggedRow { row: x });
+        }
+        for (y, value) in row.iter().enumerate() {
+            shape[(x, y)] = *value != 0;
+        }
+    }
+    Ok(shape)
+}
+
+/// Creates a square shape from text art, where [CELL_CHAR] marks a cell and [EMPTY_CHAR] marks
+/// the absence of one.
+///
+/// Each line is a row as in [shape_from_rows]. Leading and trailing whitespace of the lines is
+/// ignored, as well as empty lines, so the art can be indented in the source code.
+///
+/// # Arguments
+///
+/// * `art`: The text art of the shape.
+///
+/// returns: Result<Shape, ArtError>
+pub(crate) fn shape_from_str_art(art: &str) -> Result<Shape, ArtError> {
+    let rows = art
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .enumerate()
+        .map(|(row, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(column, char)| match char {
+                    CELL_CHAR => Ok(1),
+                    EMPTY_CHAR => Ok(0),
+                    _ => Err(ArtError::InvalidChar { row, column, char }),
+                })
+                .collect::<Result<Vec<u8>, ArtError>>()
+        })
+        .collect::<Result<Vec<Vec<u8>>, ArtError>>()?;
+    shape_from_rows(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use puzzled_common::shape::shape_square;
+
+    #[test]
+    fn test_shape_from_rows() {
+        assert_eq!(
+            shape_from_rows(&[[1, 1, 0], [0, 1, 2]]),
+            Ok(shape_square(&[[true, true, false], [false, true, true]]))
+        );
+        assert_eq!(
+            shape_from_rows(&[vec![1, 1], vec![1]]),
+            Err(ArtError::RaggedRow { row: 1 })
+        );
+        assert_eq!(
+            shape_from_rows::<[u8; 0]>(&[]).map(|shape| shape.dim()),
+            Ok((0, 0))
+        );
+    }
+
+    #[test]
+    fn test_shape_from_str_art() {
+        let art = "
+            ##.
+            ###
+        ";
+
+        assert_eq!(
+            shape_from_str_art(art),
+            Ok(shape_square(&[[true, true, false], [true, true, true]]))
+        );
+        assert_eq!(
+            shape_from_str_art("#.\n#x"),
+            Err(ArtError::InvalidChar {
+                row: 1,
+                column: 1,
+                char: 'x'
+            })
+        );
+        assert_eq!(
+            shape_from_str_art("##\n#"),
+            Err(ArtError::RaggedRow { row: 1 })
+        );
+    }
+}
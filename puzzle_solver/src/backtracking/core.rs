@@ -1,28 +1,131 @@
 use crate::backtracking::positioned::PositionedTile;
 use crate::backtracking::pruner::Pruner;
 use crate::bitmask::Bitmask;
+use crate::search_tree::{NodeKind, PruneReason, SearchTreeRecorder};
 use log::debug;
 use std::iter;
-use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::task::JoinSet;
 use tokio_util::sync::CancellationToken;
 
+/// The partial solution covering the most cells, which was found so far.
+#[derive(Debug, Default)]
+pub struct BestPartial {
+    covered_bits: usize,
+    placements: Vec<usize>,
+}
+
+impl BestPartial {
+    /// Returns the index of the placement for each tile of the partial solution.
+    /// Only the first tiles are placed, so this may be shorter than the list of tiles.
+    pub fn placements(&self) -> &[usize] {
+        &self.placements
+    }
+}
+
+/// The state of the search passed to the callback of a [SearchTracer].
+pub struct SearchState<'a> {
+    /// The index of the placement for each tile placed on the current branch.
+    pub placements: &'a [usize],
+    /// The index of the tile and of its placement, which was rejected by the pruner last.
+    pub last_pruned: Option<(usize, usize)>,
+    /// The number of placements rejected by the pruner so far.
+    pub prune_hits: u64,
+}
+
+type TraceCallback = Box<dyn Fn(SearchState) + Send + Sync>;
+
+/// Reports the state of the search to a callback at most once per interval.
+pub struct SearchTracer {
+    interval: Duration,
+    last_report: Mutex<Option<Instant>>,
+    prune_hits: AtomicU64,
+    callback: TraceCallback,
+}
+
+impl SearchTracer {
+    pub fn new(interval: Duration, callback: impl Fn(SearchState) + Send + Sync + 'static) -> Self {
+        SearchTracer {
+            interval,
+            last_report: Mutex::new(None),
+            prune_hits: AtomicU64::new(0),
+            callback: Box::new(callback),
+        }
+    }
+
+    /// Calls the callback, if the interval passed since the last report.
+    /// Returns true, if the state was reported.
+    ///
+    /// Another solver may report at the same time. In that case, this one skips its report
+    /// instead of waiting.
+    fn report(
+        &self,
+        placements: &[usize],
+        last_pruned: Option<(usize, usize)>,
+        new_prune_hits: u64,
+    ) -> bool {
+        let Ok(mut last_report) = self.last_report.try_lock() else {
+            return false;
+        };
+        let now = Instant::now();
+        if last_report.is_some_and(|last| now.duration_since(last) < self.interval) {
+            return false;
+        }
+        *last_report = Some(now);
+        let prune_hits =
+            self.prune_hits.fetch_add(new_prune_hits, Ordering::Relaxed) + new_prune_hits;
+        (self.callback)(SearchState {
+            placements,
+            last_pruned,
+            prune_hits,
+        });
+        true
+    }
+}
+
+/// Searches for placements of all tiles filling the board.
+///
+/// If `best_partial` is given, the partial solution covering the most cells is recorded in it
+/// while searching. It can be read after the search was cancelled.
+/// If `tracer` is given, the state of the search is reported to it while searching.
+/// If `search_tree` is given, the placements tried are recorded in it.
+///
+/// Returns the index of the placement for each tile or `None`, if there is no solution or the
+/// search was cancelled.
+#[allow(clippy::too_many_arguments)]
 pub async fn solve_filling(
     board_width: i32,
     board_bitmask: &Bitmask,
     positioned_tiles: &[PositionedTile],
     pruner: Pruner,
+    best_partial: Option<Arc<Mutex<BestPartial>>>,
+    tracer: Option<SearchTracer>,
+    search_tree: Option<SearchTreeRecorder>,
     cancel_token: CancellationToken,
 ) -> Option<Vec<usize>> {
     if board_bitmask.all_relevant_bits_set() {
         return Some(Vec::new());
     }
 
-    let solvers: Vec<AllFillingSolver> = prepare_solvers(board_bitmask, positioned_tiles, &pruner);
+    let solvers: Vec<AllFillingSolver> = prepare_solvers(
+        board_bitmask,
+        positioned_tiles,
+        &pruner,
+        search_tree.as_ref(),
+    );
     let shared = Arc::new(AllFillingShared {
         board_width,
         positioned_tiles: positioned_tiles.to_vec(),
+        first_set_bits: first_set_bits(positioned_tiles),
         pruner,
+        best_partial,
+        tracer,
+        search_tree,
+        solution_limit: None,
+        solutions_found: AtomicU64::new(0),
+        solutions: None,
         cancel_token: cancel_token.clone(),
     });
     let mut set: JoinSet<Option<Vec<usize>>> = JoinSet::new();
@@ -49,6 +152,139 @@ pub async fn solve_filling(
     result
 }
 
+/// Counts all placements of the tiles, which fill the board completely.
+///
+/// Placements, which only differ by swapping identical tiles, are counted once.
+/// If a limit is given, the search stops as soon as that many solutions were found and the limit
+/// is returned.
+///
+/// Returns `None`, if the operation was cancelled.
+pub async fn count_filling(
+    board_width: i32,
+    board_bitmask: &Bitmask,
+    positioned_tiles: &[PositionedTile],
+    pruner: Pruner,
+    solution_limit: Option<u64>,
+    cancel_token: CancellationToken,
+) -> Option<u64> {
+    search_all_filling(
+        board_width,
+        board_bitmask,
+        positioned_tiles,
+        pruner,
+        solution_limit,
+        false,
+        cancel_token,
+    )
+    .await
+    .map(|(count, _)| count)
+}
+
+/// Finds the placements of the tiles, which fill the board completely, like [count_filling].
+///
+/// Each solution is given as the index of the placement of each tile. The solutions are sorted
+/// by these indices, so the result does not depend on the order, in which the solvers finished.
+/// At most `solution_limit` solutions are returned.
+///
+/// Returns `None`, if the operation was cancelled.
+pub async fn enumerate_filling(
+    board_width: i32,
+    board_bitmask: &Bitmask,
+    positioned_tiles: &[PositionedTile],
+    pruner: Pruner,
+    solution_limit: u64,
+    cancel_token: CancellationToken,
+) -> Option<Vec<Vec<usize>>> {
+    search_all_filling(
+        board_width,
+        board_bitmask,
+        positioned_tiles,
+        pruner,
+        Some(solution_limit),
+        true,
+        cancel_token,
+    )
+    .await
+    .map(|(_, solutions)| solutions)
+}
+
+/// Searches all placements of the tiles, which fill the board completely.
+/// Returns the number of solutions and, if `collect_solutions` is set, their placements.
+async fn search_all_filling(
+    board_width: i32,
+    board_bitmask: &Bitmask,
+    positioned_tiles: &[PositionedTile],
+    pruner: Pruner,
+    solution_limit: Option<u64>,
+    collect_solutions: bool,
+    cancel_token: CancellationToken,
+) -> Option<(u64, Vec<Vec<usize>>)> {
+    if board_bitmask.all_relevant_bits_set() {
+        let solutions = if collect_solutions {
+            vec![Vec::new()]
+        } else {
+            Vec::new()
+        };
+        return Some((1, solutions));
+    }
+
+    let solvers: Vec<AllFillingSolver> =
+        prepare_solvers(board_bitmask, positioned_tiles, &pruner, None);
+    let shared = Arc::new(AllFillingShared {
+        board_width,
+        positioned_tiles: positioned_tiles.to_vec(),
+        first_set_bits: first_set_bits(positioned_tiles),
+        pruner,
+        best_partial: None,
+        tracer: None,
+        search_tree: None,
+        solution_limit,
+        solutions_found: AtomicU64::new(0),
+        solutions: collect_solutions.then(|| Mutex::new(Vec::new())),
+        cancel_token: cancel_token.clone(),
+    });
+    let mut set: JoinSet<u64> = JoinSet::new();
+
+    let result: Option<u64> = {
+        for mut solver in solvers.into_iter() {
+            set.spawn({
+                let shared = shared.clone();
+                async move { solver.count(&shared).await }
+            });
+        }
+        tokio::select! {
+            _ = cancel_token.cancelled() => {
+                debug!("Cancellation requested, aborting all counting tasks.");
+                None
+            }
+            count = await_counts(&mut set) => Some(count),
+        }
+    };
+    set.abort_all();
+    if cancel_token.is_cancelled() {
+        return None;
+    }
+    let count = result.map(|count| solution_limit.map_or(count, |limit| count.min(limit)))?;
+    let mut solutions = shared
+        .solutions
+        .as_ref()
+        .map(|solutions| solutions.lock().unwrap().clone())
+        .unwrap_or_default();
+    solutions.sort();
+    solutions.truncate(count as usize);
+    Some((count, solutions))
+}
+
+async fn await_counts(set: &mut JoinSet<u64>) -> u64 {
+    let mut total = 0;
+    while let Some(res) = set.join_next().await {
+        if let Ok(count) = res {
+            total += count;
+        }
+    }
+    total
+}
+
 async fn await_completion(set: &mut JoinSet<Option<Vec<usize>>>) -> Option<Vec<usize>> {
     let mut result: Option<Vec<usize>> = None;
     while let Some(res) = set.join_next().await {
@@ -62,10 +298,13 @@ async fn await_completion(set: &mut JoinSet<Option<Vec<usize>>>) -> Option<Vec<u
     result
 }
 
+/// Creates a solver for each placement of the first tile, which is not rejected by the pruner.
+/// The placements are recorded in the search tree, if it is given.
 fn prepare_solvers(
     board_bitmask: &Bitmask,
     positioned_tiles: &[PositionedTile],
     pruner: &Pruner,
+    search_tree: Option<&SearchTreeRecorder>,
 ) -> Vec<AllFillingSolver> {
     if positioned_tiles.is_empty() {
         return Vec::new();
@@ -80,17 +319,23 @@ fn prepare_solvers(
             board_with_placements.xor(board_bitmask, placement);
 
             if pruner.prune(&board_with_placements) {
+                if let Some(search_tree) = search_tree {
+                    search_tree.add(None, 0, i, NodeKind::Pruned(PruneReason::BannedPattern));
+                }
                 continue;
             }
 
             let mut used_tile_indices: Vec<usize> = vec![0; 1];
             used_tile_indices[0] = i;
 
-            let solver = AllFillingSolver::new(
+            let mut solver = AllFillingSolver::new(
                 &board_with_placements,
                 &used_tile_indices,
                 positioned_tiles.len(),
             );
+            if let Some(search_tree) = search_tree {
+                solver.tree_nodes[0] = search_tree.add(None, 0, i, NodeKind::Placed);
+            }
 
             solvers.push(solver);
         }
@@ -99,14 +344,70 @@ fn prepare_solvers(
     solvers
 }
 
+/// Returns the index of the first cell covered by each placement of each tile.
+fn first_set_bits(positioned_tiles: &[PositionedTile]) -> Vec<Vec<usize>> {
+    positioned_tiles
+        .iter()
+        .map(|positioned_tile| {
+            positioned_tile
+                .bitmasks()
+                .iter()
+                .map(|bitmask| bitmask.first_set_bit().unwrap_or_default())
+                .collect()
+        })
+        .collect()
+}
+
+/// Whether the first empty cell of the board cannot be covered by any placement of the tiles after
+/// the given one, which fits on the board. The board cannot be filled then.
+///
+/// Only the placements covering the cell are checked, using the lists precomputed for each tile.
+fn leaves_uncoverable_cell(board: &Bitmask, tile_index: usize, shared: &AllFillingShared) -> bool {
+    let Some(cell) = board.first_unset_bit() else {
+        return false;
+    };
+    !shared.positioned_tiles[tile_index + 1..]
+        .iter()
+        .any(|positioned_tile| {
+            positioned_tile
+                .placements_covering(cell)
+                .iter()
+                .any(|&placement| {
+                    board.and_is_zero(&positioned_tile.bitmasks()[placement as usize])
+                })
+        })
+}
+
 /// Shared data for the AllFillingSolver.
 struct AllFillingShared {
     board_width: i32,
     positioned_tiles: Vec<PositionedTile>,
+    /// The index of the first cell covered by each placement of each tile.
+    first_set_bits: Vec<Vec<usize>>,
     pruner: Pruner,
+    /// Records the best partial solution, if set.
+    best_partial: Option<Arc<Mutex<BestPartial>>>,
+    /// Receives the state of the search, if set.
+    tracer: Option<SearchTracer>,
+    /// Records the placements tried, if set.
+    search_tree: Option<SearchTreeRecorder>,
+    /// The number of solutions after which counting stops, if set.
+    solution_limit: Option<u64>,
+    /// The solutions found so far by all solvers while counting.
+    solutions_found: AtomicU64,
+    /// Collects the placements of the solutions found while counting, if set.
+    solutions: Option<Mutex<Vec<Vec<usize>>>>,
     cancel_token: CancellationToken,
 }
 
+impl AllFillingShared {
+    /// Returns true, if enough solutions were found to stop counting.
+    fn solution_limit_reached(&self) -> bool {
+        self.solution_limit
+            .is_some_and(|limit| self.solutions_found.load(Ordering::Relaxed) >= limit)
+    }
+}
+
 /// Solver for filling the board with all tiles using recursive backtracking.
 struct AllFillingSolver {
     start_tile_index: usize,
@@ -114,6 +415,14 @@ struct AllFillingSolver {
     used_tile_indices: Vec<usize>,
     tmp_bitmask: Bitmask,
     yield_counter: u8,
+    /// The most bits covered by a partial solution of this solver.
+    best_covered_bits: usize,
+    /// The placements rejected by the pruner, which were not reported to the tracer yet.
+    prune_hits: u64,
+    /// The index of the tile and of its placement, which was rejected by the pruner last.
+    last_pruned: Option<(usize, usize)>,
+    /// The node in the search tree of the placement of each tile on the current branch.
+    tree_nodes: Vec<Option<u32>>,
 }
 
 impl AllFillingSolver {
@@ -129,6 +438,10 @@ impl AllFillingSolver {
             used_tile_indices: use_tile_indices_vec,
             tmp_bitmask: Bitmask::new(board_bitmasks.relevant_bits()),
             yield_counter: 0,
+            best_covered_bits: 0,
+            prune_hits: 0,
+            last_pruned: None,
+            tree_nodes: vec![None; num_tiles],
         }
     }
 
@@ -147,6 +460,74 @@ impl AllFillingSolver {
         }
     }
 
+    /// The entry point for the AllFillingSolver to count all solutions.
+    ///
+    /// returns: u64: the number of solutions found.
+    async fn count(&mut self, shared: &AllFillingShared) -> u64 {
+        self.count_recursive(self.start_tile_index, shared).await
+    }
+
+    /// The recursive counting function.
+    ///
+    /// It works like [Self::solve_recursive], but does not stop at the first solution and
+    /// instead counts all solutions.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile_index`:
+    ///
+    /// returns: u64
+    async fn count_recursive(&mut self, tile_index: usize, shared: &AllFillingShared) -> u64 {
+        self.yield_counter = self.yield_counter.wrapping_add(1);
+        if self.yield_counter == 0 {
+            tokio::task::yield_now().await;
+            if shared.cancel_token.is_cancelled() {
+                return 0;
+            }
+        }
+
+        // All tiles placed
+        if tile_index >= shared.positioned_tiles.len() {
+            if !self.submit_solution() {
+                return 0;
+            }
+            if let Some(solutions) = &shared.solutions {
+                solutions
+                    .lock()
+                    .unwrap()
+                    .push(self.used_tile_indices.clone());
+            }
+            shared.solutions_found.fetch_add(1, Ordering::Relaxed);
+            return 1;
+        }
+
+        let mut count = 0;
+        let num_placements = shared.positioned_tiles[tile_index].bitmasks().len();
+        for i in 0..num_placements {
+            let placement = &shared.positioned_tiles[tile_index].bitmasks()[i];
+            if self.board_bitmasks[tile_index - 1].and_is_zero(placement)
+                && !self.repeats_swapped_branch(tile_index, i, shared)
+            {
+                self.tmp_bitmask
+                    .xor(&self.board_bitmasks[tile_index - 1], placement);
+                if shared.pruner.prune(&self.tmp_bitmask)
+                    || leaves_uncoverable_cell(&self.tmp_bitmask, tile_index, shared)
+                {
+                    continue;
+                }
+                self.used_tile_indices[tile_index] = i;
+                self.board_bitmasks[tile_index] = self.tmp_bitmask.clone();
+                count +=
+                    Box::pin(async { self.count_recursive(tile_index + 1, shared).await }).await;
+                if shared.solution_limit_reached() {
+                    break;
+                }
+            }
+        }
+
+        count
+    }
+
     /// The main recursive solver function.
     ///
     /// This function attempts to place tiles on the board recursively.
@@ -172,31 +553,143 @@ impl AllFillingSolver {
             }
         }
 
+        if let Some(best_partial) = &shared.best_partial {
+            self.record_partial(tile_index, best_partial);
+        }
+        if let Some(tracer) = &shared.tracer {
+            self.trace(tile_index, tracer);
+        }
+
         // All tiles placed
         if tile_index >= shared.positioned_tiles.len() {
-            return self.submit_solution();
+            let solved = self.submit_solution();
+            if solved
+                && let Some(search_tree) = &shared.search_tree
+                && let Some(node) = self.tree_nodes[tile_index - 1]
+            {
+                search_tree.mark_solution(node);
+            }
+            return solved;
         }
 
         let num_placements = shared.positioned_tiles[tile_index].bitmasks().len();
         for i in 0..num_placements {
             let placement = &shared.positioned_tiles[tile_index].bitmasks()[i];
-            if self.board_bitmasks[tile_index - 1].and_is_zero(placement) {
-                self.tmp_bitmask
-                    .xor(&self.board_bitmasks[tile_index - 1], placement);
-                if shared.pruner.prune(&self.tmp_bitmask) {
-                    continue;
-                }
-                self.used_tile_indices[tile_index] = i;
-                self.board_bitmasks[tile_index] = self.tmp_bitmask.clone();
-                if Box::pin(async { self.solve_recursive(tile_index + 1, shared).await }).await {
-                    return true;
-                }
+            if !self.board_bitmasks[tile_index - 1].and_is_zero(placement) {
+                continue;
+            }
+            if self.repeats_swapped_branch(tile_index, i, shared) {
+                self.record_node(
+                    tile_index,
+                    i,
+                    NodeKind::Pruned(PruneReason::IdenticalTileOrder),
+                    shared,
+                );
+                continue;
+            }
+            self.tmp_bitmask
+                .xor(&self.board_bitmasks[tile_index - 1], placement);
+            if shared.pruner.prune(&self.tmp_bitmask) {
+                self.prune_hits += 1;
+                self.last_pruned = Some((tile_index, i));
+                self.record_node(
+                    tile_index,
+                    i,
+                    NodeKind::Pruned(PruneReason::BannedPattern),
+                    shared,
+                );
+                continue;
+            }
+            if leaves_uncoverable_cell(&self.tmp_bitmask, tile_index, shared) {
+                self.record_node(
+                    tile_index,
+                    i,
+                    NodeKind::Pruned(PruneReason::UncoverableCell),
+                    shared,
+                );
+                continue;
+            }
+            self.used_tile_indices[tile_index] = i;
+            self.board_bitmasks[tile_index] = self.tmp_bitmask.clone();
+            self.record_node(tile_index, i, NodeKind::Placed, shared);
+            if Box::pin(async { self.solve_recursive(tile_index + 1, shared).await }).await {
+                return true;
             }
         }
 
         false
     }
 
+    /// Whether placing the tile with the given placement would only search a branch again, which
+    /// differs from another one by swapping identical tiles.
+    ///
+    /// Identical tiles have the same placements. Swapping them in a solution results in another
+    /// solution, so identical tiles are only placed in the order of the first cell they cover.
+    fn repeats_swapped_branch(
+        &self,
+        tile_index: usize,
+        placement_index: usize,
+        shared: &AllFillingShared,
+    ) -> bool {
+        match shared.positioned_tiles[tile_index].identical_predecessor() {
+            Some(predecessor) => {
+                shared.first_set_bits[tile_index][placement_index]
+                    < shared.first_set_bits[predecessor][self.used_tile_indices[predecessor]]
+            }
+            None => false,
+        }
+    }
+
+    /// Records the tiles placed before the given tile as the best partial solution, if they cover
+    /// more cells than the best partial solution of all solvers.
+    fn record_partial(&mut self, tile_index: usize, best_partial: &Mutex<BestPartial>) {
+        let covered_bits = self.board_bitmasks[tile_index - 1].count_ones();
+        if covered_bits <= self.best_covered_bits {
+            return;
+        }
+        self.best_covered_bits = covered_bits;
+        let mut best_partial = best_partial.lock().unwrap();
+        if covered_bits > best_partial.covered_bits {
+            best_partial.covered_bits = covered_bits;
+            best_partial.placements = self.used_tile_indices[..tile_index].to_vec();
+        }
+    }
+
+    /// Reports the tiles placed before the given tile and the prune hits since the last report to
+    /// the tracer, if it is time for a report.
+    fn trace(&mut self, tile_index: usize, tracer: &SearchTracer) {
+        if tracer.report(
+            &self.used_tile_indices[..tile_index],
+            self.last_pruned,
+            self.prune_hits,
+        ) {
+            self.prune_hits = 0;
+            self.last_pruned = None;
+        }
+    }
+
+    /// Records the placement of the tile on the current branch in the search tree, if one is
+    /// recorded. Placed nodes become the parent of the placements of the next tile.
+    fn record_node(
+        &mut self,
+        tile_index: usize,
+        placement_index: usize,
+        kind: NodeKind,
+        shared: &AllFillingShared,
+    ) {
+        if let Some(search_tree) = &shared.search_tree {
+            let node = search_tree.add(
+                self.tree_nodes[tile_index - 1],
+                tile_index,
+                placement_index,
+                kind,
+            );
+            if kind == NodeKind::Placed {
+                self.tree_nodes[tile_index] = node;
+            }
+        }
+    }
+
     /// Determines if the current board state represents a complete solution.
     ///
     /// If the current board is a correct solution, it returns true.
@@ -228,3 +721,60 @@ impl AllFillingSolver {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_partial() {
+        let best_partial = Mutex::new(BestPartial::default());
+        let mut board = Bitmask::new(4);
+        board.set_bit(0);
+        board.set_bit(1);
+        let mut solver = AllFillingSolver::new(&board, &[3], 2);
+
+        solver.record_partial(1, &best_partial);
+        assert_eq!(best_partial.lock().unwrap().placements(), &[3]);
+
+        let mut other_board = Bitmask::new(4);
+        other_board.set_bit(2);
+        let mut other_solver = AllFillingSolver::new(&other_board, &[5], 2);
+
+        other_solver.record_partial(1, &best_partial);
+        assert_eq!(best_partial.lock().unwrap().placements(), &[3]);
+    }
+
+    #[test]
+    fn test_trace() {
+        type Report = (Vec<usize>, Option<(usize, usize)>, u64);
+        let reports: Arc<Mutex<Vec<Report>>> = Arc::new(Mutex::new(Vec::new()));
+        let tracer = SearchTracer::new(Duration::from_secs(3600), {
+            let reports = reports.clone();
+            move |state| {
+                reports.lock().unwrap().push((
+                    state.placements.to_vec(),
+                    state.last_pruned,
+                    state.prune_hits,
+                ))
+            }
+        });
+        let board = Bitmask::new(4);
+        let mut solver = AllFillingSolver::new(&board, &[3, 1], 3);
+        solver.prune_hits = 2;
+        solver.last_pruned = Some((1, 4));
+
+        solver.trace(2, &tracer);
+        assert_eq!(solver.prune_hits, 0);
+        assert_eq!(solver.last_pruned, None);
+
+        solver.prune_hits = 5;
+        solver.trace(2, &tracer);
+        assert_eq!(solver.prune_hits, 5);
+
+        assert_eq!(
+            *reports.lock().unwrap(),
+            vec![(vec![3, 1], Some((1, 4)), 2)]
+        );
+    }
+}
@@ -1,20 +1,57 @@
-use crate::backtracking::positioned::PositionedTile;
+use crate::backtracking::core::{BestPartial, SearchTracer};
+use crate::backtracking::positioned::{compact, PositionedTile};
 use crate::backtracking::pruner::Pruner;
-use crate::bitmask::Bitmask;
+use crate::bitmask::{Bitmask, CellResolution};
 use crate::board::Board;
 use crate::result::{Solution, TilePlacement, UnsolvableReason};
+use crate::search_tree::SearchTreeRecorder;
+use crate::session::{PlacementFrame, SolverSession};
 use crate::tile::Tile;
+use crate::trace::{SearchSnapshot, SearchTrace};
 use log::debug;
+use puzzled_common::shape::TrimSides;
 use puzzled_common::ShapeType::Square;
+use std::sync::{Arc, Mutex};
 use tokio_util::sync::CancellationToken;
 
 pub mod core;
-mod positioned;
-mod pruner;
+pub(crate) mod positioned;
+pub(crate) mod pruner;
 
+/// Solves the trimmed board.
+///
+/// The placements of the last solution in the session are tried first. If a solution is found,
+/// its placements are remembered in the session.
+///
+/// In anytime mode, the partial solution covering the most cells is returned, if the search is
+/// cancelled before a complete solution is found.
+///
+/// If a trace is given, snapshots of the search on the untrimmed board are sent to it.
+/// If a search tree recorder is given, the placements tried are recorded in it.
+///
+/// # Arguments
+///
+/// * `board`: The trimmed board.
+/// * `tiles`: The tiles to place on the board.
+/// * `session`: The session of previous solver calls.
+/// * `untrimmed_dim`: The dimensions of the board before it was trimmed.
+/// * `trim_sides`: How the board was trimmed.
+/// * `anytime`: Whether to return the best partial solution, if the search is cancelled.
+/// * `trace`: Receives snapshots of the search, if set.
+/// * `search_tree`: Records the placements tried, if set.
+/// * `cancel_token`: A cancellation token to cancel the operation.
+///
+/// returns: Result<Solution, UnsolvableReason>
+#[allow(clippy::too_many_arguments)]
 pub async fn solve_all_filling(
     board: Board,
     tiles: &[Tile],
+    session: &mut SolverSession,
+    untrimmed_dim: (usize, usize),
+    trim_sides: &TrimSides,
+    anytime: bool,
+    trace: Option<&SearchTrace>,
+    search_tree: Option<&SearchTreeRecorder>,
     cancel_token: CancellationToken,
 ) -> Result<Solution, UnsolvableReason> {
     let mut tiles = tiles.to_vec();
@@ -22,12 +59,15 @@ pub async fn solve_all_filling(
     let tiles = tiles;
 
     let pruner = Pruner::new_for_filling(&board, &tiles);
+    let resolution = CellResolution::for_puzzle(&board, &tiles);
 
-    let board_bitmask = Bitmask::from(board.get_shape());
-    let positioned_tiles: Vec<PositionedTile> = tiles
-        .iter()
-        .map(|tile| PositionedTile::new(tile, &board, &pruner))
-        .collect();
+    let frame = PlacementFrame::new(untrimmed_dim, trim_sides, resolution);
+
+    let board_bitmask = Bitmask::from_shape(board.get_shape(), resolution);
+    let mut positioned_tiles = create_positioned_tiles(&tiles, &board, &pruner, resolution);
+    session.prioritize(&frame, &tiles, &mut positioned_tiles);
+    compact(&mut positioned_tiles);
+    let positioned_tiles = positioned_tiles;
 
     for (i, positioned_tile) in positioned_tiles.iter().enumerate() {
         if positioned_tile.bitmasks().is_empty() {
@@ -38,39 +78,238 @@ pub async fn solve_all_filling(
         }
     }
 
+    let best_partial = anytime.then(|| Arc::new(Mutex::new(BestPartial::default())));
+    let tracer =
+        trace.map(|trace| create_tracer(trace, &positioned_tiles, &board, trim_sides, resolution));
     let result = core::solve_filling(
         board.get_shape().dim().0 as i32,
         &board_bitmask,
         &positioned_tiles,
         pruner,
+        best_partial.clone(),
+        tracer,
+        search_tree.cloned(),
         cancel_token.clone(),
     )
     .await;
 
     match result {
-        Some(placements) => Ok(create_solution(
-            placements,
-            &positioned_tiles,
-            &tiles,
-            &board,
-        )),
+        Some(placements) => {
+            session.remember(frame, &tiles, &positioned_tiles, &placements);
+            Ok(Solution::new(create_placements(
+                &placements,
+                &positioned_tiles,
+                &tiles,
+                &board,
+                resolution,
+            )))
+        }
         None => {
-            if cancel_token.is_cancelled() {
+            if !cancel_token.is_cancelled() {
+                return Err(UnsolvableReason::NoFit);
+            }
+            let partial_placements = best_partial
+                .map(|best_partial| best_partial.lock().unwrap().placements().to_vec())
+                .unwrap_or_default();
+            if partial_placements.is_empty() {
                 Err(UnsolvableReason::Cancelled)
             } else {
-                Err(UnsolvableReason::NoFit)
+                debug!(
+                    "Returning partial solution with {} of {} tiles.",
+                    partial_placements.len(),
+                    tiles.len()
+                );
+                Ok(Solution::partial(create_placements(
+                    &partial_placements,
+                    &positioned_tiles,
+                    &tiles,
+                    &board,
+                    resolution,
+                )))
             }
         }
     }
 }
 
-fn create_solution(
-    placements: Vec<usize>,
+pub async fn count_all_filling(
+    board: Board,
+    tiles: &[Tile],
+    solution_limit: Option<u64>,
+    cancel_token: CancellationToken,
+) -> Result<u64, UnsolvableReason> {
+    let Some((board_bitmask, positioned_tiles, pruner, _)) = prepare_all_filling(&board, tiles)
+    else {
+        return Ok(0);
+    };
+
+    let result = core::count_filling(
+        board.get_shape().dim().0 as i32,
+        &board_bitmask,
+        &positioned_tiles,
+        pruner,
+        solution_limit,
+        cancel_token,
+    )
+    .await;
+
+    result.ok_or(UnsolvableReason::Cancelled)
+}
+
+/// Finds up to `solution_limit` solutions on the trimmed board, which are distinguished like in
+/// [count_all_filling]. Which solutions are found first depends on the parallel search, so only
+/// the limit is fixed. The placements of each solution are in the order of the given tiles.
+pub async fn enumerate_all_filling(
+    board: Board,
+    tiles: &[Tile],
+    solution_limit: u64,
+    cancel_token: CancellationToken,
+) -> Result<Vec<Solution>, UnsolvableReason> {
+    let Some((board_bitmask, positioned_tiles, pruner, resolution)) =
+        prepare_all_filling(&board, tiles)
+    else {
+        return Ok(Vec::new());
+    };
+
+    let result = core::enumerate_filling(
+        board.get_shape().dim().0 as i32,
+        &board_bitmask,
+        &positioned_tiles,
+        pruner,
+        solution_limit,
+        cancel_token,
+    )
+    .await;
+
+    let solutions = result.ok_or(UnsolvableReason::Cancelled)?;
+    Ok(solutions
+        .iter()
+        .map(|placements| {
+            Solution::new(create_placements(
+                placements,
+                &positioned_tiles,
+                tiles,
+                &board,
+                resolution,
+            ))
+        })
+        .collect())
+}
+
+/// Creates the bitmask of the board and the placements of the tiles to search all solutions.
+/// The tiles keep their order, so the placements of a solution can be mapped back to them.
+///
+/// Returns `None`, if a tile cannot be placed on the board at all, so there is no solution.
+fn prepare_all_filling(
+    board: &Board,
+    tiles: &[Tile],
+) -> Option<(Bitmask, Vec<PositionedTile>, Pruner, CellResolution)> {
+    let pruner = Pruner::new_for_filling(board, tiles);
+    let resolution = CellResolution::for_puzzle(board, tiles);
+
+    let board_bitmask = Bitmask::from_shape(board.get_shape(), resolution);
+    let mut positioned_tiles = create_positioned_tiles(tiles, board, &pruner, resolution);
+    compact(&mut positioned_tiles);
+
+    if positioned_tiles
+        .iter()
+        .any(|positioned_tile| positioned_tile.bitmasks().is_empty())
+    {
+        return None;
+    }
+    Some((board_bitmask, positioned_tiles, pruner, resolution))
+}
+
+/// Creates the placements of the tiles on the board.
+/// A tile, for which a cell is reserved and which has no identical tile, only keeps the
+/// placements covering the cell.
+/// Each tile is linked to the last tile before it, which is identical to it. Tiles are identical,
+/// if one is a rotation or flip of the other.
+fn create_positioned_tiles(
+    tiles: &[Tile],
+    board: &Board,
+    pruner: &Pruner,
+    resolution: CellResolution,
+) -> Vec<PositionedTile> {
+    tiles
+        .iter()
+        .enumerate()
+        .map(|(tile_index, tile)| {
+            let mut positioned_tile = PositionedTile::new(tile, board, pruner, resolution);
+            for position in board.cells_reserved_only_for(tile, tiles) {
+                positioned_tile.retain_covering(position, board, resolution);
+            }
+            positioned_tile.set_identical_predecessor(
+                (0..tile_index)
+                    .rev()
+                    .find(|&other| tiles[other].all_rotations.contains(&tile.base)),
+            );
+            positioned_tile
+        })
+        .collect()
+}
+
+/// Creates a tracer, which sends the state of the search as snapshots on the untrimmed board to
+/// the given trace.
+fn create_tracer(
+    trace: &SearchTrace,
+    positioned_tiles: &[PositionedTile],
+    board: &Board,
+    trim_sides: &TrimSides,
+    resolution: CellResolution,
+) -> SearchTracer {
+    let trace = trace.clone();
+    let positioned_tiles = positioned_tiles.to_vec();
+    let dim = board.get_shape().dim();
+    let offset = (trim_sides.lower_x, trim_sides.lower_y);
+    SearchTracer::new(trace.interval(), move |state| {
+        let placement_cells = |tile_index: usize, placement_index: usize| {
+            let placement = &positioned_tiles[tile_index].bitmasks()[placement_index];
+            placement_cells(placement, dim, resolution, offset)
+        };
+        let tried_cells = state
+            .placements
+            .iter()
+            .enumerate()
+            .flat_map(|(tile_index, &placement_index)| placement_cells(tile_index, placement_index))
+            .collect();
+        let pruned_cells = state
+            .last_pruned
+            .map(|(tile_index, placement_index)| placement_cells(tile_index, placement_index))
+            .unwrap_or_default();
+        trace.send(SearchSnapshot::new(
+            tried_cells,
+            pruned_cells,
+            state.prune_hits,
+        ));
+    })
+}
+
+/// Returns the cells covered by the placement moved by the given offset.
+fn placement_cells(
+    placement: &Bitmask,
+    (rows, cols): (usize, usize),
+    resolution: CellResolution,
+    (offset_x, offset_y): (usize, usize),
+) -> Vec<(usize, usize)> {
+    let placement_board = placement.to_shape_with_resolution(rows, cols, Square, resolution);
+    let (width, height) = placement_board.dim();
+    (0..width)
+        .flat_map(|x| (0..height).map(move |y| (x, y)))
+        .filter(|position| placement_board[*position])
+        .map(|(x, y)| (x + offset_x, y + offset_y))
+        .collect()
+}
+
+/// Creates the placements of the tiles from the index of the placement of each tile.
+/// If fewer placements than tiles are given, only the first tiles are placed.
+fn create_placements(
+    placements: &[usize],
     positioned_tiles: &[PositionedTile],
     tiles: &[Tile],
     board: &Board,
-) -> Solution {
-    let tile_placements: Vec<TilePlacement> = placements
+    resolution: CellResolution,
+) -> Vec<TilePlacement> {
+    placements
         .iter()
         .enumerate()
         .map(|(tile_index, &placement_index)| {
@@ -79,10 +318,10 @@ fn create_solution(
                 &positioned_tiles[tile_index],
                 &tiles[tile_index],
                 board,
+                resolution,
             )
         })
-        .collect();
-    Solution::new(tile_placements)
+        .collect()
 }
 
 fn create_tile_placement(
@@ -90,10 +329,12 @@ fn create_tile_placement(
     positioned_tile: &PositionedTile,
     tile: &Tile,
     board: &Board,
+    resolution: CellResolution,
 ) -> TilePlacement {
     let bitmask_placement = &positioned_tile.bitmasks()[placement_index];
+    let (rows, cols) = board.get_shape().dim();
     let placement_board =
-        bitmask_placement.to_shape(board.get_shape().dim().0, board.get_shape().dim().1, Square);
+        bitmask_placement.to_shape_with_resolution(rows, cols, Square, resolution);
     let mut inverted_placement = placement_board.map(|v| !v);
     inverted_placement.trim_matching(true);
     let rotation = inverted_placement.map(|v| !v);
@@ -1,66 +1,257 @@
 use crate::backtracking::pruner::Pruner;
-use crate::bitmask::Bitmask;
+use crate::bitmask::{Bitmask, CellResolution};
 use crate::board::Board;
 use crate::tile::Tile;
 use log::debug;
 use puzzled_common::Shape;
+use puzzled_common::ShapeType::Square;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::sync::Arc;
 
 /// A tile with all its possible placements on the board represented as bitmasks.
 ///
 /// The bitmasks are 1 for filled cells and 0 for empty cells.
 /// If the cell is 1 in the bitmask, it means that the tile occupies that cell on the board.
 /// The board itself is not represented in the bitmask.
+///
+/// The bitmasks are stored in an arena, which may be shared with other tiles after
+/// [compact] was called. Only the range of the arena belongs to this tile.
 #[derive(Clone)]
 pub struct PositionedTile {
-    bitmasks: Vec<Bitmask>,
+    bitmasks: Arc<[Bitmask]>,
+    range: Range<usize>,
+    /// The placements covering each bit of the board.
+    covering: Arc<CoveringPlacements>,
+    /// The index of the last tile before this one, which is identical to it.
+    identical_predecessor: Option<usize>,
 }
 
 impl PositionedTile {
     /// Creates a new PositionedTile from a Tile and a Board.
     ///
     /// The resulting PositionedTile contains all possible placements of the Tile on the Board,
-    /// represented as Bitmasks.
+    /// represented as Bitmasks. Placements covering a cell reserved for another tile are left
+    /// out.
     ///
     /// # Arguments
     ///
     /// * `tile`: The Tile to be placed on the Board.
     /// * `board`: The Board on which the Tile will be placed.
+    /// * `pruner`: The Pruner to remove placements, which cannot lead to a solution.
+    /// * `resolution`: The resolution of the bitmasks.
     ///
     /// returns: PositionedTile
-    pub(crate) fn new(tile: &Tile, board: &Board, pruner: &Pruner) -> Self {
+    pub(crate) fn new(
+        tile: &Tile,
+        board: &Board,
+        pruner: &Pruner,
+        resolution: CellResolution,
+    ) -> Self {
         let all_placements: Vec<Shape> = tile
             .all_rotations
             .iter()
-            .flat_map(|rotation| board.get_shape().place_on_all_positions(rotation))
-            .map(|array| {
-                let mut array = array.clone();
-                array.remove_parent(board.get_shape());
-                array
+            .flat_map(|rotation| {
+                let reserved_cells = board.cells_reserved_for_others(rotation);
+                board
+                    .get_shape()
+                    .place_on_all_positions(rotation)
+                    .into_iter()
+                    .map(|mut array| {
+                        array.remove_parent(board.get_shape());
+                        array
+                    })
+                    .filter(move |array| {
+                        reserved_cells
+                            .iter()
+                            .all(|position| array.quarters(*position) == 0)
+                    })
             })
             .collect();
 
         let bitmasks: Vec<Bitmask> = all_placements
             .iter()
-            .map(Bitmask::from)
+            .map(|placement| Bitmask::from_shape(placement, resolution))
             .filter(|bitmask| !pruner.prune(bitmask))
             .collect();
 
-        PositionedTile { bitmasks }
+        PositionedTile {
+            range: 0..bitmasks.len(),
+            covering: Arc::new(CoveringPlacements::new(&bitmasks)),
+            bitmasks: Arc::from(bitmasks),
+            identical_predecessor: None,
+        }
     }
 
     /// Returns a reference to Bitmasks representing all possible placements of the Tile on the Board.
     pub fn bitmasks(&self) -> &[Bitmask] {
-        &self.bitmasks
+        &self.bitmasks[self.range.clone()]
+    }
+
+    /// Returns the indices of the placements, which cover the given bit of the board.
+    /// The indices are in the order of [Self::bitmasks()].
+    pub(crate) fn placements_covering(&self, bit: usize) -> &[u32] {
+        self.covering.get(bit)
+    }
+
+    /// Replaces the placements with the given ones in a new arena owned by this tile.
+    fn set_bitmasks(&mut self, bitmasks: Vec<Bitmask>) {
+        self.range = 0..bitmasks.len();
+        self.covering = Arc::new(CoveringPlacements::new(&bitmasks));
+        self.bitmasks = Arc::from(bitmasks);
+    }
+
+    /// Returns the index of the last tile before this one, which is identical to it.
+    /// Identical tiles have the same placements, so they are placed in a fixed order to avoid
+    /// searching the same branch again with the tiles swapped.
+    pub fn identical_predecessor(&self) -> Option<usize> {
+        self.identical_predecessor
+    }
+
+    /// Sets the index of the last tile before this one, which is identical to it.
+    pub(crate) fn set_identical_predecessor(&mut self, identical_predecessor: Option<usize>) {
+        self.identical_predecessor = identical_predecessor;
+    }
+
+    /// Keeps only the placements, which cover the given cell.
+    pub(crate) fn retain_covering(
+        &mut self,
+        position: (usize, usize),
+        board: &Board,
+        resolution: CellResolution,
+    ) {
+        let mut cell = Shape::from_elem(board.get_shape().dim(), Square, false);
+        cell[position] = true;
+        let cell = Bitmask::from_shape(&cell, resolution);
+        let mut bitmasks = self.bitmasks().to_vec();
+        bitmasks.retain(|bitmask| (bitmask.clone() & cell.clone()).count_ones() > 0);
+        self.set_bitmasks(bitmasks);
+    }
+
+    /// Moves the placements matching the predicate to the front, so they are tried first.
+    /// The order within both groups of placements is kept.
+    pub(crate) fn move_to_front<P: Fn(&Bitmask) -> bool>(&mut self, predicate: P) {
+        let (mut front, back): (Vec<Bitmask>, Vec<Bitmask>) = self
+            .bitmasks()
+            .iter()
+            .cloned()
+            .partition(|bitmask| predicate(bitmask));
+        front.extend(back);
+        self.set_bitmasks(front);
     }
 
     #[allow(dead_code)]
     fn print_debug(&self, board_width: i32) {
-        for bitmask in self.bitmasks.iter() {
+        for bitmask in self.bitmasks().iter() {
             debug!("{}", &bitmask.to_string(board_width));
         }
     }
 }
 
+/// The indices of the placements of a tile covering each bit of the board.
+///
+/// The indices of all bits are stored in one flat vector. The indices for a bit start at its
+/// offset and end at the offset of the next bit.
+#[derive(Debug, Default)]
+struct CoveringPlacements {
+    offsets: Vec<u32>,
+    placements: Vec<u32>,
+}
+
+impl CoveringPlacements {
+    fn new(bitmasks: &[Bitmask]) -> Self {
+        let bits = bitmasks.first().map_or(0, Bitmask::relevant_bits);
+        let mut offsets = vec![0u32; bits + 1];
+        for bitmask in bitmasks {
+            for bit in (0..bits).filter(|&bit| bitmask.get_bit(bit)) {
+                offsets[bit + 1] += 1;
+            }
+        }
+        for bit in 0..bits {
+            offsets[bit + 1] += offsets[bit];
+        }
+
+        let mut next = offsets.clone();
+        let mut placements = vec![0u32; offsets[bits] as usize];
+        for (index, bitmask) in bitmasks.iter().enumerate() {
+            for bit in (0..bits).filter(|&bit| bitmask.get_bit(bit)) {
+                placements[next[bit] as usize] = index as u32;
+                next[bit] += 1;
+            }
+        }
+        CoveringPlacements {
+            offsets,
+            placements,
+        }
+    }
+
+    fn get(&self, bit: usize) -> &[u32] {
+        match (self.offsets.get(bit), self.offsets.get(bit + 1)) {
+            (Some(&start), Some(&end)) => &self.placements[start as usize..end as usize],
+            _ => &[],
+        }
+    }
+}
+
+/// Stores the placements of all tiles in a single arena to reduce the overhead of many small
+/// allocations. A tile shares the placements of its identical predecessor, if both have the same
+/// placements, so duplicate tiles do not store their placements again. The shared placements are
+/// in the order of the predecessor, which may differ from the order of the tile, e.g. after the
+/// placements of the last solution were moved to the front.
+///
+/// This should be called after the placements are final, since changing the placements of a
+/// tile afterward moves them out of the arena again.
+pub(crate) fn compact(positioned_tiles: &mut [PositionedTile]) {
+    let mut arena: Vec<Bitmask> = Vec::new();
+    let mut ranges: Vec<Range<usize>> = Vec::with_capacity(positioned_tiles.len());
+    let mut covering: Vec<Arc<CoveringPlacements>> = Vec::with_capacity(positioned_tiles.len());
+    for positioned_tile in positioned_tiles.iter() {
+        let shared = positioned_tile
+            .identical_predecessor
+            .filter(|&predecessor| {
+                same_placements(
+                    positioned_tiles[predecessor].bitmasks(),
+                    positioned_tile.bitmasks(),
+                )
+            });
+        match shared {
+            Some(predecessor) => {
+                ranges.push(ranges[predecessor].clone());
+                covering.push(covering[predecessor].clone());
+            }
+            None => {
+                let start = arena.len();
+                arena.extend_from_slice(positioned_tile.bitmasks());
+                ranges.push(start..arena.len());
+                covering.push(positioned_tile.covering.clone());
+            }
+        }
+    }
+
+    let placements: usize = positioned_tiles
+        .iter()
+        .map(|positioned_tile| positioned_tile.bitmasks().len())
+        .sum();
+    debug!(
+        "Stored {} of {} placements after sharing the placements of identical tiles.",
+        arena.len(),
+        placements
+    );
+    let arena: Arc<[Bitmask]> = Arc::from(arena);
+    for ((positioned_tile, range), covering) in
+        positioned_tiles.iter_mut().zip(ranges).zip(covering)
+    {
+        positioned_tile.bitmasks = arena.clone();
+        positioned_tile.range = range;
+        positioned_tile.covering = covering;
+    }
+}
+
+/// Returns true, if both lists contain the same placements, regardless of their order.
+fn same_placements(a: &[Bitmask], b: &[Bitmask]) -> bool {
+    a.len() == b.len() && a.iter().collect::<HashSet<_>>() == b.iter().collect::<HashSet<_>>()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -76,6 +267,7 @@ mod tests {
             &tile,
             &board,
             &Pruner::new_for_filling(&board, &[tile.clone()]),
+            CellResolution::Full,
         );
         assert_eq!(positioned_tile.bitmasks().len(), 15);
 
@@ -298,6 +490,7 @@ mod tests {
             &tile,
             &board,
             &Pruner::new_for_filling(&board, &[tile.clone()]),
+            CellResolution::Full,
         );
         assert!(positioned_tile.bitmasks.is_empty());
     }
@@ -315,6 +508,7 @@ mod tests {
             &tile,
             &board,
             &Pruner::new_for_filling(&board, &[tile.clone()]),
+            CellResolution::Full,
         );
         assert!(positioned_tile.bitmasks.is_empty());
     }
@@ -328,7 +522,90 @@ mod tests {
             &tile,
             &board,
             &Pruner::new_for_filling(&board, &[tile.clone()]),
+            CellResolution::Full,
         );
         assert_eq!(positioned_tile.bitmasks.len(), 4);
     }
+
+    #[test]
+    fn test_compact() {
+        let board = Board::new((3, 3));
+        let tiles = vec![
+            Tile::new(shape_square(&[[true, true]])),
+            Tile::new(shape_square(&[[true, true]])),
+            Tile::new(shape_square(&[[true]])),
+        ];
+        let pruner = Pruner::new_for_filling(&board, &tiles);
+        let mut positioned_tiles: Vec<PositionedTile> = tiles
+            .iter()
+            .map(|tile| PositionedTile::new(tile, &board, &pruner, CellResolution::Full))
+            .collect();
+        positioned_tiles[1].set_identical_predecessor(Some(0));
+        let expected: Vec<Vec<Bitmask>> = positioned_tiles
+            .iter()
+            .map(|positioned_tile| positioned_tile.bitmasks().to_vec())
+            .collect();
+
+        compact(&mut positioned_tiles);
+
+        for (positioned_tile, expected) in positioned_tiles.iter().zip(&expected) {
+            assert_eq!(positioned_tile.bitmasks(), expected.as_slice());
+            assert!(Arc::ptr_eq(
+                &positioned_tile.bitmasks,
+                &positioned_tiles[0].bitmasks
+            ));
+        }
+        assert_eq!(positioned_tiles[1].range, positioned_tiles[0].range);
+        assert_eq!(
+            positioned_tiles[0].bitmasks.len(),
+            expected[0].len() + expected[2].len()
+        );
+    }
+
+    #[test]
+    fn test_compact_reordered() {
+        let board = Board::new((3, 3));
+        let tiles = vec![
+            Tile::new(shape_square(&[[true, true]])),
+            Tile::new(shape_square(&[[true, true]])),
+        ];
+        let pruner = Pruner::new_for_filling(&board, &tiles);
+        let mut positioned_tiles: Vec<PositionedTile> = tiles
+            .iter()
+            .map(|tile| PositionedTile::new(tile, &board, &pruner, CellResolution::Full))
+            .collect();
+        positioned_tiles[1].set_identical_predecessor(Some(0));
+        let last = positioned_tiles[1].bitmasks().last().unwrap().clone();
+        positioned_tiles[1].move_to_front(|bitmask| *bitmask == last);
+        let expected = positioned_tiles[0].bitmasks().to_vec();
+
+        compact(&mut positioned_tiles);
+
+        assert_eq!(positioned_tiles[1].bitmasks(), expected.as_slice());
+        assert_eq!(positioned_tiles[0].bitmasks.len(), expected.len());
+    }
+
+    #[test]
+    fn test_placements_covering() {
+        let board = Board::new((3, 3));
+        let tile = Tile::new(shape_square(&[[true, true]]));
+        let mut positioned_tile = PositionedTile::new(
+            &tile,
+            &board,
+            &Pruner::new_for_filling(&board, &[tile.clone()]),
+            CellResolution::Full,
+        );
+        positioned_tile.move_to_front(|bitmask| bitmask.get_bit(4));
+
+        for bit in 0..9 {
+            let expected: Vec<u32> = (0..positioned_tile.bitmasks().len() as u32)
+                .filter(|&i| positioned_tile.bitmasks()[i as usize].get_bit(bit))
+                .collect();
+            assert_eq!(
+                positioned_tile.placements_covering(bit),
+                expected.as_slice()
+            );
+        }
+        assert!(positioned_tile.placements_covering(9).is_empty());
+    }
 }
@@ -1,4 +1,4 @@
-use crate::bitmask::Bitmask;
+use crate::bitmask::{Bitmask, CellResolution};
 use crate::board::Board;
 use crate::tile::Tile;
 use banned::BannedBitmask;
@@ -14,8 +14,12 @@ pub struct Pruner {
 
 impl Pruner {
     /// Creates a new Pruner for use while filling the board with tiles.
+    /// Puzzles with half cells are not pruned, since the banned patterns only cover full cells.
     pub fn new_for_filling(board: &Board, tiles: &[Tile]) -> Self {
-        let banned_bitmasks = banned::create_banned_bitmasks_for_filling(board, tiles);
+        let banned_bitmasks = match CellResolution::for_puzzle(board, tiles) {
+            CellResolution::Full => banned::create_banned_bitmasks_for_filling(board, tiles),
+            CellResolution::Quarters => Vec::new(),
+        };
 
         Pruner { banned_bitmasks }
     }
@@ -30,6 +34,9 @@ impl Pruner {
     ///
     /// returns: bool
     pub fn prune(&self, current_board: &Bitmask) -> bool {
+        if self.banned_bitmasks.is_empty() {
+            return false;
+        }
         // TODO start from the first empty cell on the board and end at the last empty cell
         for index in 0..current_board.relevant_bits() {
             if !current_board.get_bit(index) {
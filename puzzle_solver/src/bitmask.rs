@@ -1,3 +1,6 @@
+use crate::board::Board;
+use crate::tile::Tile;
+use puzzled_common::shape::cell::QUARTERS_ALL;
 use puzzled_common::{Shape, ShapeType};
 use std::ops::{BitAnd, BitOr, BitXor, Index};
 
@@ -13,6 +16,40 @@ const FULL_PRIMITIVE_MASK: u128 = u128::MAX;
 /// Total number of bits in the Bitmask. This is not necessarily the number of relevant bits.
 const TOTAL_BITS: usize = BITMASK_ARRAY_LENGTH * BITS_IN_PRIMITIVE;
 
+/// Number of bits used per cell, if the cells are divided into quarters.
+const QUARTERS_PER_CELL: usize = 4;
+
+/// Defines how many bits are used for each cell of a shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum CellResolution {
+    /// One bit per cell. Used if all cells are full cells.
+    Full,
+    /// One bit per quarter of a cell. Used if any cell is a half cell, so that halves cut along
+    /// different diagonals can be checked for overlaps.
+    Quarters,
+}
+
+impl CellResolution {
+    /// Returns the resolution needed to solve the given puzzle.
+    pub(crate) fn for_puzzle(board: &Board, tiles: &[Tile]) -> CellResolution {
+        if board.get_shape().has_half_cells()
+            || tiles.iter().any(|tile| tile.base().has_half_cells())
+        {
+            CellResolution::Quarters
+        } else {
+            CellResolution::Full
+        }
+    }
+
+    /// Returns the number of bits used for each cell.
+    pub(crate) const fn bits_per_cell(&self) -> usize {
+        match self {
+            CellResolution::Full => 1,
+            CellResolution::Quarters => QUARTERS_PER_CELL,
+        }
+    }
+}
+
 /// A Bitmask represents a set of bits, allowing for efficient bitwise operations.
 /// The size is limited to `BITMASK_ARRAY_LENGTH * BITS_IN_PRIMITIVE` bits to hopefully
 /// allow for optimized operations.
@@ -110,6 +147,49 @@ impl Bitmask {
         self.relevant_bits
     }
 
+    /// Returns the number of bits set to 1.
+    ///
+    /// # Arguments
+    ///
+    /// returns: usize
+    #[inline]
+    pub(crate) fn count_ones(&self) -> usize {
+        self.bits
+            .iter()
+            .map(|bits| bits.count_ones() as usize)
+            .sum()
+    }
+
+    /// Returns the index of the lowest bit set to 1 or `None`, if no bit is set.
+    ///
+    /// # Arguments
+    ///
+    /// returns: Option<usize>
+    #[inline]
+    pub(crate) fn first_set_bit(&self) -> Option<usize> {
+        self.bits
+            .iter()
+            .enumerate()
+            .find(|(_, bits)| **bits != 0)
+            .map(|(i, bits)| i * BITS_IN_PRIMITIVE + bits.trailing_zeros() as usize)
+    }
+
+    /// Returns the index of the lowest relevant bit set to 0 or `None`, if all relevant bits are
+    /// set.
+    ///
+    /// # Arguments
+    ///
+    /// returns: Option<usize>
+    #[inline]
+    pub(crate) fn first_unset_bit(&self) -> Option<usize> {
+        self.bits
+            .iter()
+            .enumerate()
+            .find(|(_, bits)| **bits != FULL_PRIMITIVE_MASK)
+            .map(|(i, bits)| i * BITS_IN_PRIMITIVE + bits.trailing_ones() as usize)
+            .filter(|&index| index < self.relevant_bits)
+    }
+
     /// Checks if all relevant bits are set to 1.
     ///
     /// # Arguments
@@ -304,6 +384,58 @@ impl Bitmask {
         TOTAL_BITS
     }
 
+    /// Creates a Bitmask from a shape with the given resolution.
+    /// With [CellResolution::Full], this is the same as [Bitmask::from].
+    /// With [CellResolution::Quarters], each cell uses four consecutive bits for its quarters.
+    pub(crate) fn from_shape(shape: &Shape, resolution: CellResolution) -> Self {
+        match resolution {
+            CellResolution::Full => Bitmask::from(shape),
+            CellResolution::Quarters => {
+                let mut bitmask = Bitmask::new(shape.len() * QUARTERS_PER_CELL);
+                let (xs, ys) = shape.dim();
+                for x in 0..ys {
+                    for y in 0..xs {
+                        let quarters = shape.quarters((y, x));
+                        let index = (x * xs + y) * QUARTERS_PER_CELL;
+                        for quarter in 0..QUARTERS_PER_CELL {
+                            if quarters & (1 << quarter) != 0 {
+                                bitmask.set_bit(index + quarter);
+                            }
+                        }
+                    }
+                }
+                bitmask
+            }
+        }
+    }
+
+    /// Converts the bitmask back to a shape.
+    /// This is the inverse of [Bitmask::from_shape].
+    pub(crate) fn to_shape_with_resolution(
+        &self,
+        rows: usize,
+        cols: usize,
+        shape_type: ShapeType,
+        resolution: CellResolution,
+    ) -> Shape {
+        match resolution {
+            CellResolution::Full => self.to_shape(rows, cols, shape_type),
+            CellResolution::Quarters => {
+                let mut shape = Shape::from_elem((rows, cols), shape_type, false);
+                for x in 0..cols {
+                    for y in 0..rows {
+                        let index = (x * rows + y) * QUARTERS_PER_CELL;
+                        let quarters = (0..QUARTERS_PER_CELL)
+                            .filter(|quarter| self.get_bit(index + quarter))
+                            .fold(0, |quarters, quarter| quarters | (1 << quarter));
+                        shape.set_quarters((y, x), quarters & QUARTERS_ALL);
+                    }
+                }
+                shape
+            }
+        }
+    }
+
     pub(crate) fn to_shape(&self, rows: usize, cols: usize, shape_type: ShapeType) -> Shape {
         let mut array = Shape::from_elem((rows, cols), shape_type, false);
         for x in 0..cols {
@@ -403,9 +535,40 @@ impl Index<usize> for Bitmask {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use puzzled_common::shape::cell::CellShape;
     use puzzled_common::shape::shape_square;
     use puzzled_common::ShapeType::Square;
 
+    #[test]
+    fn test_first_set_bit() {
+        let mut bitmask = Bitmask::new(TOTAL_BITS);
+        assert_eq!(bitmask.first_set_bit(), None);
+
+        bitmask.set_bit(TOTAL_BITS - 1);
+        assert_eq!(bitmask.first_set_bit(), Some(TOTAL_BITS - 1));
+
+        bitmask.set_bit(BITS_IN_PRIMITIVE + 3);
+        assert_eq!(bitmask.first_set_bit(), Some(BITS_IN_PRIMITIVE + 3));
+
+        bitmask.set_bit(5);
+        assert_eq!(bitmask.first_set_bit(), Some(5));
+    }
+
+    #[test]
+    fn test_first_unset_bit() {
+        let mut bitmask = Bitmask::new(BITS_IN_PRIMITIVE + 2);
+        assert_eq!(bitmask.first_unset_bit(), Some(0));
+
+        for index in 0..BITS_IN_PRIMITIVE {
+            bitmask.set_bit(index);
+        }
+        assert_eq!(bitmask.first_unset_bit(), Some(BITS_IN_PRIMITIVE));
+
+        bitmask.set_bit(BITS_IN_PRIMITIVE);
+        bitmask.set_bit(BITS_IN_PRIMITIVE + 1);
+        assert_eq!(bitmask.first_unset_bit(), None);
+    }
+
     #[test]
     fn test_new() {
         let bitmask = Bitmask::new(10);
@@ -761,4 +924,19 @@ mod tests {
 
         assert_eq!(array, expected);
     }
+
+    #[test]
+    fn test_from_shape_quarters() {
+        let mut shape = shape_square(&[[true, false]]);
+        shape.set_cell_shape((0, 1), CellShape::TopLeft);
+
+        let bitmask = Bitmask::from_shape(&shape, CellResolution::Quarters);
+
+        assert_eq!(bitmask.relevant_bits(), 8);
+        assert_eq!(bitmask.to_string(4), "1111_0110");
+        assert_eq!(
+            bitmask.to_shape_with_resolution(1, 2, Square, CellResolution::Quarters),
+            shape
+        );
+    }
 }
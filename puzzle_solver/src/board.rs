@@ -1,7 +1,12 @@
+use crate::art::{shape_from_rows, shape_from_str_art, ArtError};
+use crate::result::TilePlacement;
+use crate::tile::Tile;
 use log::debug;
+use puzzled_common::shape::cell::{CellShape, QUARTERS_ALL};
 use puzzled_common::shape::TrimSides;
 use puzzled_common::Shape;
 use puzzled_common::ShapeType::Square;
+use std::collections::{HashMap, HashSet};
 use std::ops::{Index, IndexMut};
 
 /// Represents a 2D board for the puzzle, where each cell is either true (filled) or false (empty).
@@ -19,7 +24,38 @@ use std::ops::{Index, IndexMut};
 /// board[[2, 3]] = true;
 /// assert_eq!(board[[2, 3]], true);
 /// ```
-pub struct Board(Shape);
+#[derive(Debug, Clone)]
+pub struct Board {
+    cells: Shape,
+    /// The filled cells, which are on the board, but must not be covered by a tile.
+    must_stay_empty: HashSet<(usize, usize)>,
+    /// The empty cells, which may only be covered by the given tile in any of its rotations.
+    reserved_cells: HashMap<(usize, usize), Tile>,
+}
+
+/// Describes what has to happen to a cell of the board for the puzzle to be solved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellRequirement {
+    /// The cell is empty and must be covered by a tile.
+    MustFill,
+    /// The cell is on the board, but must not be covered by a tile, like the cells of a target.
+    MustStayEmpty,
+    /// The cell is not on the board or already covered by a tile.
+    Blocked,
+}
+
+/// Represents the reason why a tile cannot be placed at a position on a board.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlacementError {
+    /// A cell of the tile is outside the board.
+    OutOfBounds { position: (usize, usize) },
+    /// A cell of the tile covers a cell, which must stay empty.
+    CoversMustStayEmptyCell { position: (usize, usize) },
+    /// A cell of the tile covers a cell, which is not on the board or already covered.
+    CoversBlockedCell { position: (usize, usize) },
+    /// A cell of the tile covers a cell, which is reserved for another tile.
+    CoversReservedCell { position: (usize, usize) },
+}
 
 impl Board {
     /// Creates a new Board with the given dimensions, initialized to all false (empty).
@@ -40,7 +76,99 @@ impl Board {
     /// assert!(board.get_shape().iter().all(|&b| b == false));
     /// ```
     pub fn new(dims: (usize, usize)) -> Self {
-        Board(Shape::from_elem(dims, Square, false))
+        Board::from(Shape::from_elem(dims, Square, false))
+    }
+
+    /// Creates a new Board from rows of numbers, where any number except `0` marks a cell of the
+    /// board, which has to be covered. Cells marked with `0` are filled.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows`: The rows of the board, where `rows[x][y]` is the cell at (x, y).
+    ///
+    /// returns: Result<Board, ArtError> Err, if the rows have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use puzzle_solver::board::Board;
+    ///
+    /// let board = Board::from_rows(&[[1, 1, 0], [1, 1, 1]]).unwrap();
+    /// assert_eq!(board.get_shape().dim(), (2, 3));
+    /// assert_eq!(board[[0, 2]], true);
+    /// assert_eq!(board[[1, 2]], false);
+    /// ```
+    pub fn from_rows<R: AsRef<[u8]>>(rows: &[R]) -> Result<Self, ArtError> {
+        Ok(Board::from_cells(shape_from_rows(rows)?))
+    }
+
+    /// Creates a new Board from text art, where `#` marks a cell of the board, which has to be
+    /// covered, and `.` marks a filled cell. The lines are the rows as in [Board::from_rows].
+    ///
+    /// # Arguments
+    ///
+    /// * `art`: The text art of the board. Whitespace around the lines and empty lines are
+    ///   ignored.
+    ///
+    /// returns: Result<Board, ArtError> Err, if the art contains other characters or the rows
+    /// have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use puzzle_solver::board::Board;
+    ///
+    /// let board = Board::from_str_art("##.\n###").unwrap();
+    /// let expected = Board::from_rows(&[[1, 1, 0], [1, 1, 1]]).unwrap();
+    /// assert_eq!(board.get_shape(), expected.get_shape());
+    /// ```
+    pub fn from_str_art(art: &str) -> Result<Self, ArtError> {
+        Ok(Board::from_cells(shape_from_str_art(art)?))
+    }
+
+    /// Creates a new Board from the requirements of its cells.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows`: The rows of requirements, where `rows[x][y]` is the requirement of the cell at
+    ///   (x, y).
+    ///
+    /// returns: Result<Board, ArtError> Err, if the rows have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use puzzle_solver::board::{Board, CellRequirement::*};
+    ///
+    /// let board = Board::from_requirements(&[[MustFill, MustStayEmpty], [MustFill, Blocked]])
+    ///     .unwrap();
+    /// assert_eq!(board.requirement([0, 1]), MustStayEmpty);
+    /// assert_eq!(board.requirement([1, 1]), Blocked);
+    /// ```
+    pub fn from_requirements<R: AsRef<[CellRequirement]>>(rows: &[R]) -> Result<Self, ArtError> {
+        let numbers: Vec<Vec<u8>> = rows
+            .iter()
+            .map(|row| {
+                row.as_ref()
+                    .iter()
+                    .map(|requirement| (*requirement == CellRequirement::MustFill) as u8)
+                    .collect()
+            })
+            .collect();
+        let mut board = Board::from_rows(&numbers)?;
+        for (x, row) in rows.iter().enumerate() {
+            for (y, requirement) in row.as_ref().iter().enumerate() {
+                if *requirement == CellRequirement::MustStayEmpty {
+                    board.must_stay_empty.insert((x, y));
+                }
+            }
+        }
+        Ok(board)
+    }
+
+    /// Creates a board, on which the cells of the given shape have to be covered.
+    fn from_cells(cells: Shape) -> Self {
+        Board::from(cells.map(|cell| !cell))
     }
 
     /// Returns a reference to the internal 2D array representing the board.
@@ -62,7 +190,7 @@ impl Board {
     /// assert_eq!(board.get_shape(), &Shape::from_elem((3, 4), Square, false));
     /// ```
     pub fn get_shape(&self) -> &Shape {
-        &self.0
+        &self.cells
     }
 
     /// Prints the board to the debug log.
@@ -70,14 +198,239 @@ impl Board {
     pub(crate) fn debug_print(&self) {
         if log::log_enabled!(log::Level::Debug) {
             debug!("Board:");
-            self.0.debug_print();
+            self.cells.debug_print();
+        }
+    }
+
+    /// Sets the shape of the cell at the given position.
+    /// The filled part of the cell is described by the cell shape, the rest of the cell is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use puzzle_solver::board::Board;
+    /// use puzzled_common::shape::cell::{CellShape, QUARTERS_ALL};
+    ///
+    /// let mut board = Board::new((1, 1));
+    /// board[[0, 0]] = true;
+    /// board.set_cell_shape([0, 0], CellShape::TopLeft);
+    /// assert!(board.get_shape().has_half_cells());
+    /// ```
+    pub fn set_cell_shape(&mut self, index: [usize; 2], cell_shape: CellShape) {
+        self.cells.set_cell_shape((index[0], index[1]), cell_shape);
+    }
+
+    /// Fills the given quarters of the cell at the given position and empties the others.
+    /// If the filled quarters do not form a full or half cell, the whole cell is filled.
+    pub fn set_quarters(&mut self, index: [usize; 2], quarters: u8) {
+        self.cells.set_quarters((index[0], index[1]), quarters);
+    }
+
+    /// Sets the requirement of the cell at the given position.
+    /// Cells, which must stay empty or are blocked, are filled completely.
+    pub fn set_requirement(&mut self, index: [usize; 2], requirement: CellRequirement) {
+        let position = (index[0], index[1]);
+        match requirement {
+            CellRequirement::MustFill => {
+                self.cells.set_quarters(position, 0);
+                self.must_stay_empty.remove(&position);
+            }
+            CellRequirement::MustStayEmpty => {
+                self.cells.set_quarters(position, QUARTERS_ALL);
+                self.must_stay_empty.insert(position);
+            }
+            CellRequirement::Blocked => {
+                self.cells.set_quarters(position, QUARTERS_ALL);
+                self.must_stay_empty.remove(&position);
+            }
+        }
+    }
+
+    /// Returns the requirement of the cell at the given position.
+    /// A cell, of which only some quarters are filled, must be filled.
+    pub fn requirement(&self, index: [usize; 2]) -> CellRequirement {
+        let position = (index[0], index[1]);
+        if self.cells.quarters(position) != QUARTERS_ALL {
+            CellRequirement::MustFill
+        } else if self.must_stay_empty.contains(&position) {
+            CellRequirement::MustStayEmpty
+        } else {
+            CellRequirement::Blocked
         }
     }
 
+    /// Reserves the cell at the given position for the tile, so it can only be covered by the
+    /// tile or a tile identical to it. Since empty cells have to be covered, one of these tiles
+    /// has to cover the cell in every solution.
+    ///
+    /// # Arguments
+    ///
+    /// * `index`: The position of the cell.
+    /// * `tile`: The tile, which has to cover the cell.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use puzzle_solver::board::Board;
+    /// use puzzle_solver::tile::Tile;
+    ///
+    /// let mut board = Board::from_str_art("###").unwrap();
+    /// let domino = Tile::from_str_art("##").unwrap();
+    /// board.reserve_cell([0, 0], &domino);
+    /// assert_eq!(board.reserved_tile([0, 0]), Some(&domino));
+    /// assert_eq!(board.placements(&Tile::from_str_art("#").unwrap()).len(), 2);
+    /// ```
+    pub fn reserve_cell(&mut self, index: [usize; 2], tile: &Tile) {
+        self.reserved_cells
+            .insert((index[0], index[1]), tile.clone());
+    }
+
+    /// Returns the tile, for which the cell at the given position is reserved, if any.
+    /// See [Self::reserve_cell].
+    pub fn reserved_tile(&self, index: [usize; 2]) -> Option<&Tile> {
+        self.reserved_cells.get(&(index[0], index[1]))
+    }
+
+    /// Returns the reserved cells, which the tile in the given rotation must not cover, since
+    /// they are reserved for a different tile.
+    pub(crate) fn cells_reserved_for_others(&self, rotation: &Shape) -> Vec<(usize, usize)> {
+        self.reserved_cells
+            .iter()
+            .filter(|(_, tile)| !tile.all_rotations.contains(rotation))
+            .map(|(position, _)| *position)
+            .collect()
+    }
+
+    /// Returns the reserved cells, which only the given tile can cover, since no other of the
+    /// given tiles is identical to it.
+    pub(crate) fn cells_reserved_only_for(
+        &self,
+        tile: &Tile,
+        tiles: &[Tile],
+    ) -> Vec<(usize, usize)> {
+        let matching_tiles = tiles
+            .iter()
+            .filter(|other| tile.all_rotations.contains(&other.base))
+            .count();
+        if matching_tiles != 1 {
+            return Vec::new();
+        }
+        self.reserved_cells
+            .iter()
+            .filter(|(_, reserved)| reserved.all_rotations.contains(&tile.base))
+            .map(|(position, _)| *position)
+            .collect()
+    }
+
+    /// Checks, whether a tile can be placed at the given position without covering cells, which
+    /// must not be covered.
+    ///
+    /// # Arguments
+    ///
+    /// * `rotation`: The tile in the rotation in which it is placed.
+    /// * `position`: The position of the tile on the board, like [crate::result::TilePlacement::position].
+    ///
+    /// returns: Result<(), PlacementError> The error for the first cell, which cannot be covered.
+    pub fn check_placement(
+        &self,
+        rotation: &Shape,
+        position: (usize, usize),
+    ) -> Result<(), PlacementError> {
+        for (offset, _) in rotation.indexed_iter().filter(|(_, cell)| **cell) {
+            let cell_position = (position.0 + offset.0, position.1 + offset.1);
+            let Some(_) = self.cells.get(cell_position) else {
+                return Err(PlacementError::OutOfBounds {
+                    position: cell_position,
+                });
+            };
+            if self.cells.quarters(cell_position) & rotation.quarters(offset) == 0 {
+                if self
+                    .reserved_cells
+                    .get(&cell_position)
+                    .is_some_and(|tile| !tile.all_rotations.contains(rotation))
+                {
+                    return Err(PlacementError::CoversReservedCell {
+                        position: cell_position,
+                    });
+                }
+                continue;
+            }
+            return Err(match self.requirement([cell_position.0, cell_position.1]) {
+                CellRequirement::MustStayEmpty => PlacementError::CoversMustStayEmptyCell {
+                    position: cell_position,
+                },
+                _ => PlacementError::CoversBlockedCell {
+                    position: cell_position,
+                },
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns all placements of the tile in any of its rotations, which fit on the board.
+    /// See [Self::check_placement].
+    ///
+    /// Only the single tile is considered, so other tiles may not fit anymore after it was placed
+    /// like this.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile`: The tile to place.
+    ///
+    /// returns: Vec<TilePlacement>
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use puzzle_solver::board::Board;
+    /// use puzzle_solver::tile::Tile;
+    ///
+    /// let board = Board::from_str_art("###").unwrap();
+    /// let tile = Tile::from_str_art("##").unwrap();
+    /// assert_eq!(board.placements(&tile).len(), 2);
+    /// ```
+    pub fn placements(&self, tile: &Tile) -> Vec<TilePlacement> {
+        let (width, height) = self.cells.dim();
+        let mut placements = Vec::new();
+        for rotation in &tile.all_rotations {
+            for x in 0..width {
+                for y in 0..height {
+                    if self.check_placement(rotation, (x, y)).is_ok() {
+                        placements.push(TilePlacement::new(
+                            tile.base.clone(),
+                            rotation.clone(),
+                            (x, y),
+                        ));
+                    }
+                }
+            }
+        }
+        placements
+    }
+
     /// Trims the board by removing any rows or columns on the edges that are entirely
     /// true (filled).
+    /// The cells, which must stay empty, and the reserved cells are moved with the board or
+    /// dropped, if they are trimmed.
     pub(crate) fn trim(&mut self) -> TrimSides {
-        self.0.trim_matching(true)
+        let trim_sides = self.cells.trim_matching(true);
+        let dim = self.cells.dim();
+        let trimmed = |(x, y): (usize, usize)| {
+            let x = x.checked_sub(trim_sides.lower_x)?;
+            let y = y.checked_sub(trim_sides.lower_y)?;
+            (x < dim.0 && y < dim.1).then_some((x, y))
+        };
+        self.must_stay_empty = self
+            .must_stay_empty
+            .iter()
+            .filter_map(|position| trimmed(*position))
+            .collect();
+        self.reserved_cells = self
+            .reserved_cells
+            .drain()
+            .filter_map(|(position, tile)| Some((trimmed(position)?, tile)))
+            .collect();
+        trim_sides
     }
 }
 
@@ -85,25 +438,30 @@ impl Index<[usize; 2]> for Board {
     type Output = bool;
 
     fn index(&self, index: [usize; 2]) -> &Self::Output {
-        &self.0[(index[0], index[1])]
+        &self.cells[(index[0], index[1])]
     }
 }
 
 impl IndexMut<[usize; 2]> for Board {
     fn index_mut(&mut self, index: [usize; 2]) -> &mut Self::Output {
-        &mut self.0[(index[0], index[1])]
+        &mut self.cells[(index[0], index[1])]
     }
 }
 
 impl From<Shape> for Board {
     fn from(array: Shape) -> Self {
-        Board(array)
+        Board {
+            cells: array,
+            must_stay_empty: HashSet::new(),
+            reserved_cells: HashMap::new(),
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Board;
+    use super::*;
+    use puzzled_common::shape::shape_square;
 
     #[test]
     fn test_new_0_0() {
@@ -118,6 +476,130 @@ mod tests {
         assert!(board.get_shape().iter().all(|&b| b == false));
     }
 
+    #[test]
+    fn test_from_str_art() {
+        let board = Board::from_str_art(
+            "
+            #..
+            ###
+            ",
+        )
+        .unwrap();
+
+        assert_eq!(board.get_shape().dim(), (2, 3));
+        assert!(!board[[0, 0]]);
+        assert!(board[[0, 1]]);
+        assert!(board[[0, 2]]);
+        assert!((0..3).all(|y| !board[[1, y]]));
+        assert!(Board::from_str_art("#\n##").is_err());
+    }
+
+    #[test]
+    fn test_requirements() {
+        use CellRequirement::*;
+        let rows = [
+            [Blocked, Blocked, Blocked],
+            [MustFill, MustStayEmpty, MustFill],
+        ];
+        let mut board = Board::from_requirements(&rows).unwrap();
+
+        assert!(board[[0, 0]]);
+        assert!(!board[[1, 0]]);
+        assert!(board[[1, 1]]);
+        assert_eq!(board.requirement([0, 0]), Blocked);
+        assert_eq!(board.requirement([1, 1]), MustStayEmpty);
+
+        board.set_requirement([1, 2], MustStayEmpty);
+        board.set_requirement([1, 1], MustFill);
+        assert_eq!(board.requirement([1, 2]), MustStayEmpty);
+        assert_eq!(board.requirement([1, 1]), MustFill);
+        assert!(!board[[1, 1]]);
+
+        let mut board = Board::from_requirements(&rows).unwrap();
+        let trim_sides = board.trim();
+        assert_eq!(trim_sides.lower_x, 1);
+        assert_eq!(board.get_shape().dim(), (1, 3));
+        assert_eq!(board.requirement([0, 1]), MustStayEmpty);
+    }
+
+    #[test]
+    fn test_check_placement() {
+        use CellRequirement::*;
+        let board =
+            Board::from_requirements(&[[MustFill, MustFill, MustStayEmpty, Blocked]]).unwrap();
+        let domino = shape_square(&[[true, true]]);
+
+        assert_eq!(board.check_placement(&domino, (0, 0)), Ok(()));
+        assert_eq!(
+            board.check_placement(&domino, (0, 1)),
+            Err(PlacementError::CoversMustStayEmptyCell { position: (0, 2) })
+        );
+        assert_eq!(
+            board.check_placement(&shape_square(&[[false, true]]), (0, 2)),
+            Err(PlacementError::CoversBlockedCell { position: (0, 3) })
+        );
+        assert_eq!(
+            board.check_placement(&domino, (0, 3)),
+            Err(PlacementError::CoversBlockedCell { position: (0, 3) })
+        );
+        assert_eq!(
+            board.check_placement(&domino, (1, 0)),
+            Err(PlacementError::OutOfBounds { position: (1, 0) })
+        );
+    }
+
+    #[test]
+    fn test_check_placement_reserved_cell() {
+        let mut board = Board::from_str_art("###").unwrap();
+        let domino = Tile::from_str_art("##").unwrap();
+        let monomino = shape_square(&[[true]]);
+        board.reserve_cell([0, 1], &domino);
+
+        assert_eq!(
+            board.check_placement(&shape_square(&[[true, true]]), (0, 0)),
+            Ok(())
+        );
+        assert_eq!(board.check_placement(&monomino, (0, 2)), Ok(()));
+        assert_eq!(
+            board.check_placement(&monomino, (0, 1)),
+            Err(PlacementError::CoversReservedCell { position: (0, 1) })
+        );
+
+        let mut board = Board::from_str_art(".##").unwrap();
+        board.reserve_cell([0, 2], &domino);
+        board.trim();
+        assert_eq!(board.reserved_tile([0, 1]), Some(&domino));
+        assert_eq!(board.reserved_tile([0, 2]), None);
+    }
+
+    #[test]
+    fn test_placements() {
+        let board = Board::from_str_art(
+            "
+            ##
+            #.
+            ",
+        )
+        .unwrap();
+        let tile = Tile::from_str_art("##").unwrap();
+
+        let positions: Vec<(Shape, (usize, usize))> = board
+            .placements(&tile)
+            .into_iter()
+            .map(|placement| (placement.rotation().clone(), placement.position()))
+            .collect();
+        assert_eq!(
+            positions,
+            vec![
+                (shape_square(&[[true, true]]), (0, 0)),
+                (shape_square(&[[true], [true]]), (0, 0)),
+            ]
+        );
+        assert!(board
+            .placements(&Tile::from_str_art("###").unwrap())
+            .is_empty());
+    }
+
     #[test]
     fn test_trim() {
         let mut board = Board::new((5, 5));
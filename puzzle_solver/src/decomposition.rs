@@ -0,0 +1,147 @@
+use proptest::collection::vec;
+use proptest::prelude::*;
+use proptest::sample::Index;
+use puzzled_common::Shape;
+use puzzled_common::ShapeType::Square;
+
+/// A board together with tiles, which cover its empty cells exactly.
+///
+/// The puzzle is solvable by construction, since the tiles were cut out of the board.
+#[derive(Debug, Clone)]
+pub(crate) struct Decomposition {
+    /// The board, where `true` cells are outside the puzzle area.
+    pub board: Shape,
+    /// The tiles in random orientations.
+    pub tiles: Vec<Shape>,
+}
+
+/// The random choices a [Decomposition] is built from, one entry for each cell of the board.
+/// Tiles and grown cells never outnumber the cells, so there is always a choice left.
+#[derive(Debug, Clone)]
+struct Choices {
+    blocked: Vec<bool>,
+    tile_sizes: Vec<usize>,
+    growth: Vec<Index>,
+    orientations: Vec<(usize, bool)>,
+}
+
+/// Generates a random board and cuts its empty cells into random connected tiles.
+///
+/// # Arguments
+///
+/// * `max_dim`: The maximum width and height of the board. Must be at least 2.
+/// * `max_tile_size`: The maximum number of cells of a tile. Must be at least 1.
+///
+/// returns: impl Strategy<Value=Decomposition>
+pub(crate) fn decomposition(
+    max_dim: usize,
+    max_tile_size: usize,
+) -> impl Strategy<Value = Decomposition> {
+    (2..=max_dim, 2..=max_dim).prop_flat_map(move |dim| {
+        let cells = dim.0 * dim.1;
+        (
+            vec(prop::bool::weighted(0.125), cells),
+            vec(1..=max_tile_size, cells),
+            vec(any::<Index>(), cells),
+            vec((0..4usize, any::<bool>()), cells),
+        )
+            .prop_map(move |(blocked, tile_sizes, growth, orientations)| {
+                build_decomposition(
+                    dim,
+                    Choices {
+                        blocked,
+                        tile_sizes,
+                        growth,
+                        orientations,
+                    },
+                )
+            })
+    })
+}
+
+fn build_decomposition(dim: (usize, usize), choices: Choices) -> Decomposition {
+    let mut board = Shape::from_elem(dim, Square, false);
+    for x in 0..dim.0 {
+        for y in 0..dim.1 {
+            board[(x, y)] = choices.blocked[x * dim.1 + y];
+        }
+    }
+
+    let mut assigned = board.clone();
+    let mut growth = choices.growth.iter();
+    let mut tiles = Vec::new();
+    for x in 0..dim.0 {
+        for y in 0..dim.1 {
+            if assigned[(x, y)] {
+                continue;
+            }
+            let size = choices.tile_sizes[x * dim.1 + y];
+            let cells = grow_tile(&mut growth, &mut assigned, (x, y), size);
+            let (rotations, flipped) = choices.orientations[tiles.len()];
+            tiles.push(oriented(tile_shape(&cells), rotations, flipped));
+        }
+    }
+    Decomposition { board, tiles }
+}
+
+/// Collects up to `size` connected cells, which are not yet assigned to a tile, starting at the
+/// given cell. The collected cells are marked as assigned.
+fn grow_tile<'a>(
+    growth: &mut impl Iterator<Item = &'a Index>,
+    assigned: &mut Shape,
+    start: (usize, usize),
+    size: usize,
+) -> Vec<(usize, usize)> {
+    let (width, height) = assigned.dim();
+    let mut cells = vec![start];
+    assigned[start] = true;
+    while cells.len() < size {
+        let mut candidates = Vec::new();
+        for &(x, y) in &cells {
+            let neighbours = [
+                (x.wrapping_sub(1), y),
+                (x + 1, y),
+                (x, y.wrapping_sub(1)),
+                (x, y + 1),
+            ];
+            candidates.extend(
+                neighbours
+                    .into_iter()
+                    .filter(|&(x, y)| x < width && y < height && !assigned[(x, y)]),
+            );
+        }
+        if candidates.is_empty() {
+            break;
+        }
+        let Some(choice) = growth.next() else {
+            break;
+        };
+        let cell = candidates[choice.index(candidates.len())];
+        assigned[cell] = true;
+        cells.push(cell);
+    }
+    cells
+}
+
+/// Creates the smallest shape containing the given cells.
+fn tile_shape(cells: &[(usize, usize)]) -> Shape {
+    let min_x = cells.iter().map(|(x, _)| *x).min().unwrap();
+    let min_y = cells.iter().map(|(_, y)| *y).min().unwrap();
+    let max_x = cells.iter().map(|(x, _)| *x).max().unwrap();
+    let max_y = cells.iter().map(|(_, y)| *y).max().unwrap();
+    let mut shape = Shape::from_elem((max_x - min_x + 1, max_y - min_y + 1), Square, false);
+    for (x, y) in cells {
+        shape[(x - min_x, y - min_y)] = true;
+    }
+    shape
+}
+
+fn oriented(mut shape: Shape, rotations: usize, flipped: bool) -> Shape {
+    for _ in 0..rotations {
+        shape.rotate_counterclockwise();
+    }
+    if flipped {
+        shape.flip_default();
+    }
+    shape
+}
@@ -1,17 +1,27 @@
-use crate::bitmask::Bitmask;
+use crate::bitmask::{Bitmask, CellResolution};
 use crate::board::Board;
 use crate::plausibility::check;
-use crate::result::{Solution, TilePlacement, UnsolvableReason};
+use crate::result::{Solution, UnsolvableReason};
+use crate::search_tree::SearchTreeRecorder;
+use crate::session::SolverSession;
 use crate::tile::Tile;
+use crate::trace::SearchTrace;
 use log::debug;
 use tokio_util::sync::CancellationToken;
 
+pub mod area;
+pub mod art;
 mod backtracking;
 mod bitmask;
 pub mod board;
+#[cfg(test)]
+mod decomposition;
 mod plausibility;
 pub mod result;
+pub mod search_tree;
+pub mod session;
 pub mod tile;
+pub mod trace;
 
 /// Tries to place all given tiles on the board, filling it completely.
 /// If successful, returns a Solution; otherwise, returns an UnsolvableReason.
@@ -56,45 +66,370 @@ pub async fn solve_all_filling(
     tiles: &[Tile],
     cancel_token: CancellationToken,
 ) -> Result<Solution, UnsolvableReason> {
-    if !check(&board, tiles) {
-        debug!("Plausibility check failed.");
-        return Err(UnsolvableReason::PlausibilityCheckFailed);
+    solve_all_filling_in_session(board, tiles, &mut SolverSession::default(), cancel_token).await
+}
+
+/// Works like [solve_all_filling], but tries the placements of the last solution found in the
+/// given session first and remembers the placements of the new solution in it.
+///
+/// This reduces the time needed to find a solution, if the board and tiles only changed slightly
+/// since the last call with the same session, e.g. after a single tile was moved.
+///
+/// # Arguments
+///
+/// * `board`: The board to place the tiles on to fill it completely.
+/// * `tiles`: The tiles to place on the board.
+/// * `session`: The session of previous solver calls for the same puzzle.
+/// * `cancel_token`: A cancellation token to cancel the operation.
+///
+/// returns: Result<Solution, UnsolvableReason>
+pub async fn solve_all_filling_in_session(
+    board: Board,
+    tiles: &[Tile],
+    session: &mut SolverSession,
+    cancel_token: CancellationToken,
+) -> Result<Solution, UnsolvableReason> {
+    solve(board, tiles, session, false, None, None, cancel_token).await
+}
+
+/// Works like [solve_all_filling_in_session], but can be interrupted to get the best result found
+/// so far.
+///
+/// If the operation is cancelled before a complete solution is found, the partial solution
+/// covering the most cells found so far is returned. It places only some of the tiles and
+/// [Solution::is_complete] returns false for it. The placements of a partial solution do not
+/// necessarily lead to a complete solution.
+/// If no tile could be placed before the cancellation, [UnsolvableReason::Cancelled] is returned.
+///
+/// # Arguments
+///
+/// * `board`: The board to place the tiles on to fill it completely.
+/// * `tiles`: The tiles to place on the board.
+/// * `session`: The session of previous solver calls for the same puzzle.
+/// * `cancel_token`: A cancellation token to stop the search and get the best partial solution.
+///
+/// returns: Result<Solution, UnsolvableReason>
+pub async fn solve_all_filling_anytime(
+    board: Board,
+    tiles: &[Tile],
+    session: &mut SolverSession,
+    cancel_token: CancellationToken,
+) -> Result<Solution, UnsolvableReason> {
+    solve(board, tiles, session, true, None, None, cancel_token).await
+}
+
+/// Works like [solve_all_filling_in_session] or [solve_all_filling_anytime] depending on
+/// `anytime`, but sends snapshots of the search to the given trace while solving.
+///
+/// This slows down the search, so it should only be used to debug the solver.
+///
+/// # Arguments
+///
+/// * `board`: The board to place the tiles on to fill it completely.
+/// * `tiles`: The tiles to place on the board.
+/// * `session`: The session of previous solver calls for the same puzzle.
+/// * `anytime`: Whether to return the best partial solution, if the search is cancelled.
+/// * `trace`: Receives snapshots of the search. Positions are cells of the given board.
+/// * `cancel_token`: A cancellation token to cancel the operation.
+///
+/// returns: Result<Solution, UnsolvableReason>
+pub async fn solve_all_filling_traced(
+    board: Board,
+    tiles: &[Tile],
+    session: &mut SolverSession,
+    anytime: bool,
+    trace: &SearchTrace,
+    cancel_token: CancellationToken,
+) -> Result<Solution, UnsolvableReason> {
+    solve(
+        board,
+        tiles,
+        session,
+        anytime,
+        Some(trace),
+        None,
+        cancel_token,
+    )
+    .await
+}
+
+/// Works like [solve_all_filling_traced], but records the explored search tree in the given
+/// recorder. The trace is optional here.
+///
+/// Recording slows down the search considerably, so it should only be used to analyze single
+/// runs, which take unexpectedly long.
+///
+/// # Arguments
+///
+/// * `board`: The board to place the tiles on to fill it completely.
+/// * `tiles`: The tiles to place on the board.
+/// * `session`: The session of previous solver calls for the same puzzle.
+/// * `anytime`: Whether to return the best partial solution, if the search is cancelled.
+/// * `trace`: Receives snapshots of the search, if set.
+/// * `search_tree`: Records the placements tried by the solver.
+/// * `cancel_token`: A cancellation token to cancel the operation.
+///
+/// returns: Result<Solution, UnsolvableReason>
+pub async fn solve_all_filling_recorded(
+    board: Board,
+    tiles: &[Tile],
+    session: &mut SolverSession,
+    anytime: bool,
+    trace: Option<&SearchTrace>,
+    search_tree: &SearchTreeRecorder,
+    cancel_token: CancellationToken,
+) -> Result<Solution, UnsolvableReason> {
+    solve(
+        board,
+        tiles,
+        session,
+        anytime,
+        trace,
+        Some(search_tree),
+        cancel_token,
+    )
+    .await
+}
+
+async fn solve(
+    board: Board,
+    tiles: &[Tile],
+    session: &mut SolverSession,
+    anytime: bool,
+    trace: Option<&SearchTrace>,
+    search_tree: Option<&SearchTreeRecorder>,
+    cancel_token: CancellationToken,
+) -> Result<Solution, UnsolvableReason> {
+    if let Err(reason) = check(&board, tiles) {
+        debug!("Plausibility check failed: {:?}", reason);
+        return Err(reason);
     }
 
     let mut board = board;
+    let untrimmed_dim = board.get_shape().dim();
     let trim_sides = board.trim();
 
-    if board.get_shape().iter().filter(|c| !*c).count() > Bitmask::max_bits() {
+    if required_bits(&board, tiles) > Bitmask::max_bits() {
         debug!("Board too large for bitmask representation.");
         return Err(UnsolvableReason::BoardTooLarge);
     }
 
-    let result = backtracking::solve_all_filling(board, tiles, cancel_token).await;
-    match &result {
-        Ok(solution) => {
-            let trim_adjusted_placements: Vec<TilePlacement> = solution
-                .placements()
-                .iter()
-                .map(|placement| {
-                    let (x, y) = placement.position();
-                    let (trimmed_x, trimmed_y) = (x + trim_sides.lower_x, y + trim_sides.lower_y);
-                    TilePlacement::new(
-                        placement.base().clone(),
-                        placement.rotation().clone(),
-                        (trimmed_x, trimmed_y),
-                    )
-                })
-                .collect();
-            Ok(Solution::new(trim_adjusted_placements))
+    let result = backtracking::solve_all_filling(
+        board,
+        tiles,
+        session,
+        untrimmed_dim,
+        &trim_sides,
+        anytime,
+        trace,
+        search_tree,
+        cancel_token,
+    )
+    .await;
+    result.map(|solution| solution.untrimmed((trim_sides.lower_x, trim_sides.lower_y)))
+}
+
+/// Counts the number of distinct solutions, which fill the board completely with all given tiles.
+///
+/// Solutions which only differ by swapping identical tiles are counted once. Tiles are identical,
+/// if one is a rotation or flip of the other.
+/// If the puzzle cannot be solved, `Ok(0)` is returned.
+///
+/// Counting has to explore the whole search tree, so this may take much longer than
+/// [solve_all_filling]. The cancellation token can be used to cancel the operation, in which case
+/// [UnsolvableReason::Cancelled] is returned.
+///
+/// # Arguments
+///
+/// * `board`: The board to place the tiles on to fill it completely.
+/// * `tiles`: The tiles to place on the board.
+/// * `cancel_token`: A cancellation token to cancel the operation.
+///
+/// returns: Result<u64, UnsolvableReason>
+///
+/// # Examples
+///
+/// ```
+/// use puzzle_solver::board::Board;
+/// use puzzle_solver::tile::Tile;
+/// use puzzle_solver::count_all_filling;
+/// use tokio_util::sync::CancellationToken;
+/// use puzzled_common::shape::shape_square;
+///
+/// let board = Board::new((2, 2));
+/// let tiles = vec![
+///     Tile::new(shape_square(&[[true, true]])),
+///     Tile::new(shape_square(&[[true, true]])),
+/// ];
+/// let cancel_token = CancellationToken::new();
+///
+/// let result = tokio::runtime::Runtime::new().unwrap().block_on(count_all_filling(board, &tiles, cancel_token));
+/// assert_eq!(result, Ok(2));
+/// ```
+pub async fn count_all_filling(
+    board: Board,
+    tiles: &[Tile],
+    cancel_token: CancellationToken,
+) -> Result<u64, UnsolvableReason> {
+    count(board, tiles, None, cancel_token).await
+}
+
+/// Checks, whether there is exactly one solution, which fills the board completely with all given
+/// tiles.
+///
+/// Solutions are distinguished like in [count_all_filling]. The search stops as soon as a second
+/// solution is found, so this is usually much faster than counting all solutions. Proving that a
+/// solution is unique still requires exploring the whole search tree.
+/// If the puzzle cannot be solved, `Ok(false)` is returned. If the operation is cancelled,
+/// [UnsolvableReason::Cancelled] is returned.
+///
+/// # Arguments
+///
+/// * `board`: The board to place the tiles on to fill it completely.
+/// * `tiles`: The tiles to place on the board.
+/// * `cancel_token`: A cancellation token to cancel the operation.
+///
+/// returns: Result<bool, UnsolvableReason>
+///
+/// # Examples
+///
+/// ```
+/// use puzzle_solver::board::Board;
+/// use puzzle_solver::tile::Tile;
+/// use puzzle_solver::has_unique_solution;
+/// use tokio_util::sync::CancellationToken;
+/// use puzzled_common::shape::shape_square;
+///
+/// let board = Board::new((2, 2));
+/// let tiles = vec![
+///     Tile::new(shape_square(&[[true, true]])),
+///     Tile::new(shape_square(&[[true, true]])),
+/// ];
+/// let cancel_token = CancellationToken::new();
+///
+/// let result = tokio::runtime::Runtime::new().unwrap().block_on(has_unique_solution(board, &tiles, cancel_token));
+/// assert_eq!(result, Ok(false));
+/// ```
+pub async fn has_unique_solution(
+    board: Board,
+    tiles: &[Tile],
+    cancel_token: CancellationToken,
+) -> Result<bool, UnsolvableReason> {
+    let count = count(board, tiles, Some(2), cancel_token).await?;
+    Ok(count == 1)
+}
+
+/// Finds the distinct solutions, which fill the board completely with all given tiles.
+///
+/// Solutions are distinguished like in [count_all_filling]. At most `solution_limit` solutions
+/// are returned, so the memory used stays bounded for puzzles with many solutions. Only this limit
+/// is guaranteed: the search runs in parallel, so if there are more solutions than the limit,
+/// which of them are returned and their order may differ between calls. The placements of each
+/// solution are in the order of the given tiles and their positions are relative to the given
+/// board.
+/// If the puzzle cannot be solved, an empty list is returned. If the operation is cancelled,
+/// [UnsolvableReason::Cancelled] is returned.
+///
+/// # Arguments
+///
+/// * `board`: The board to place the tiles on to fill it completely.
+/// * `tiles`: The tiles to place on the board.
+/// * `solution_limit`: The maximum number of solutions to return.
+/// * `cancel_token`: A cancellation token to cancel the operation.
+///
+/// returns: Result<Vec<Solution>, UnsolvableReason>
+///
+/// # Examples
+///
+/// ```
+/// use puzzle_solver::board::Board;
+/// use puzzle_solver::tile::Tile;
+/// use puzzle_solver::enumerate_all_filling;
+/// use tokio_util::sync::CancellationToken;
+/// use puzzled_common::shape::shape_square;
+///
+/// let board = Board::new((2, 2));
+/// let tiles = vec![
+///     Tile::new(shape_square(&[[true, true]])),
+///     Tile::new(shape_square(&[[true, true]])),
+/// ];
+/// let cancel_token = CancellationToken::new();
+///
+/// let result = tokio::runtime::Runtime::new().unwrap().block_on(enumerate_all_filling(board, &tiles, 10, cancel_token));
+/// assert_eq!(result.unwrap().len(), 2);
+/// ```
+pub async fn enumerate_all_filling(
+    board: Board,
+    tiles: &[Tile],
+    solution_limit: u64,
+    cancel_token: CancellationToken,
+) -> Result<Vec<Solution>, UnsolvableReason> {
+    if let Err(reason) = check(&board, tiles) {
+        debug!("Plausibility check failed: {:?}", reason);
+        return Ok(Vec::new());
+    }
+
+    let mut board = board;
+    let trim_sides = board.trim();
+
+    if required_bits(&board, tiles) > Bitmask::max_bits() {
+        debug!("Board too large for bitmask representation.");
+        return Err(UnsolvableReason::BoardTooLarge);
+    }
+
+    let solutions =
+        backtracking::enumerate_all_filling(board, tiles, solution_limit, cancel_token).await?;
+    Ok(solutions
+        .into_iter()
+        .map(|solution| solution.untrimmed((trim_sides.lower_x, trim_sides.lower_y)))
+        .collect())
+}
+
+/// Counts the solutions like [count_all_filling], but stops at the given limit, if set.
+async fn count(
+    board: Board,
+    tiles: &[Tile],
+    solution_limit: Option<u64>,
+    cancel_token: CancellationToken,
+) -> Result<u64, UnsolvableReason> {
+    if let Err(reason) = check(&board, tiles) {
+        debug!("Plausibility check failed: {:?}", reason);
+        return Ok(0);
+    }
+
+    let mut board = board;
+    board.trim();
+
+    if required_bits(&board, tiles) > Bitmask::max_bits() {
+        debug!("Board too large for bitmask representation.");
+        return Err(UnsolvableReason::BoardTooLarge);
+    }
+
+    backtracking::count_all_filling(board, tiles, solution_limit, cancel_token).await
+}
+
+/// Returns the number of bits needed to represent the board in a bitmask.
+fn required_bits(board: &Board, tiles: &[Tile]) -> usize {
+    match CellResolution::for_puzzle(board, tiles) {
+        CellResolution::Full => board.get_shape().iter().filter(|c| !*c).count(),
+        CellResolution::Quarters => {
+            board.get_shape().len() * CellResolution::Quarters.bits_per_cell()
         }
-        Err(_) => result,
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::decomposition::{decomposition, Decomposition};
+    use crate::result::TilePlacement;
+    use proptest::prelude::{any, prop_assert, prop_assume, proptest, ProptestConfig};
+    use proptest::sample::Index;
+    use puzzled_common::shape::cell::CellShape;
     use puzzled_common::shape::shape_square;
+    use puzzled_common::Shape;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
     use tokio_util::sync::CancellationToken;
 
     #[tokio::test]
@@ -150,6 +485,7 @@ mod tests {
         let solution = result.unwrap();
         let placements = solution.placements();
         dbg!(&placements);
+        assert_eq!(solution.trim_offset(), (3, 1));
         assert_eq!(placements.len(), 2);
         let expected_placement_1 = TilePlacement::new(
             shape_square(&[[true, true, true], [true, true, false]]),
@@ -309,7 +645,110 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_solve_tile_can_not_be_placed() {
+    async fn test_solve_all_filling_anytime_complete() {
+        let board = Board::new((2, 2));
+        let tiles = vec![
+            Tile::new(shape_square(&[[true, true]])),
+            Tile::new(shape_square(&[[true, true]])),
+        ];
+
+        let result = solve_all_filling_anytime(
+            board,
+            &tiles,
+            &mut SolverSession::default(),
+            CancellationToken::new(),
+        )
+        .await;
+
+        let solution = result.unwrap();
+        assert!(solution.is_complete());
+        assert_eq!(solution.placements().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_solve_all_filling_traced() {
+        let board_shape = shape_square(&[
+            [true, true, true, true, true, true, true],
+            [true, true, false, false, false, true, true],
+            [true, false, false, false, false, true, true],
+            [true, false, false, false, false, true, true],
+            [true, true, true, true, true, true, true],
+        ]);
+        let tiles = vec![
+            Tile::new(shape_square(&[[true, true, true], [true, true, false]])),
+            Tile::new(shape_square(&[[true, true, true], [true, true, true]])),
+        ];
+        let snapshots = Arc::new(Mutex::new(Vec::new()));
+        let trace = SearchTrace::new(Duration::ZERO, {
+            let snapshots = snapshots.clone();
+            move |snapshot| snapshots.lock().unwrap().push(snapshot)
+        });
+
+        let result = solve_all_filling_traced(
+            board_shape.clone().into(),
+            &tiles,
+            &mut SolverSession::default(),
+            false,
+            &trace,
+            CancellationToken::new(),
+        )
+        .await;
+
+        assert!(result.unwrap().is_complete());
+        let snapshots = snapshots.lock().unwrap();
+        assert!(!snapshots.is_empty());
+        assert!(snapshots
+            .iter()
+            .any(|snapshot| !snapshot.tried_cells().is_empty()));
+        for snapshot in snapshots.iter() {
+            for cell in snapshot.tried_cells().iter().chain(snapshot.pruned_cells()) {
+                assert_eq!(board_shape.get(*cell), Some(&false), "{:?}", cell);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_solve_all_filling_recorded() {
+        let board = shape_square(&[
+            [true, true, false, false, false],
+            [true, false, false, false, false],
+            [true, false, false, false, false],
+        ])
+        .into();
+        let tiles = vec![
+            Tile::new(shape_square(&[[true, true, true], [true, true, false]])),
+            Tile::new(shape_square(&[[true, true, true], [true, true, true]])),
+        ];
+        let recorder = SearchTreeRecorder::new(1000);
+
+        let result = solve_all_filling_recorded(
+            board,
+            &tiles,
+            &mut SolverSession::default(),
+            false,
+            None,
+            &recorder,
+            CancellationToken::new(),
+        )
+        .await;
+
+        assert!(result.unwrap().is_complete());
+        let tree = recorder.take_tree();
+        assert!(!tree.is_truncated());
+        let stats = tree.stats();
+        assert_eq!(stats.solutions, 1);
+        assert_eq!(stats.nodes_per_depth.len(), 2);
+        let solution = tree
+            .nodes()
+            .iter()
+            .find(|node| node.kind == search_tree::NodeKind::Solution)
+            .unwrap();
+        assert_eq!(solution.tile, 1);
+        assert_eq!(tree.nodes()[solution.parent.unwrap() as usize].tile, 0);
+    }
+
+    #[tokio::test]
+    async fn test_solve_tile_larger_than_board() {
         let board = shape_square(&[[false, false], [false, false]]).into();
         let tiles = vec![Tile::new(shape_square(&[[true, true, true, true]]))];
 
@@ -318,7 +757,7 @@ mod tests {
         let error = result.unwrap_err();
         assert_eq!(
             error,
-            UnsolvableReason::TileCannotBePlaced {
+            UnsolvableReason::TileLargerThanBoard {
                 base: shape_square(&[[true, true, true, true]]),
             }
         );
@@ -381,4 +820,321 @@ mod tests {
             UnsolvableReason::BoardTooLarge
         );
     }
+
+    #[tokio::test]
+    async fn test_count_all_filling() {
+        let board = Board::new((2, 3));
+        let tiles = vec![
+            Tile::new(shape_square(&[[true, true, true]])),
+            Tile::new(shape_square(&[[true, true, true]])),
+        ];
+
+        let result = count_all_filling(board, &tiles, CancellationToken::new()).await;
+        assert_eq!(result, Ok(1));
+    }
+
+    #[tokio::test]
+    async fn test_count_all_filling_multiple_solutions() {
+        let board = Board::new((2, 3));
+        let tiles = vec![
+            Tile::new(shape_square(&[[true, true], [true, false]])),
+            Tile::new(shape_square(&[[true, true], [false, true]])),
+        ];
+
+        let result = count_all_filling(board, &tiles, CancellationToken::new()).await;
+        assert_eq!(result, Ok(2));
+    }
+
+    #[tokio::test]
+    async fn test_count_all_filling_identical_tiles() {
+        let board = Board::new((3, 4));
+        let tiles = vec![Tile::new(shape_square(&[[true, true]])); 6];
+
+        let result = count_all_filling(board, &tiles, CancellationToken::new()).await;
+        assert_eq!(result, Ok(11));
+    }
+
+    #[tokio::test]
+    async fn test_count_all_filling_unsolvable() {
+        let board = Board::new((3, 4));
+        let tiles = vec![
+            Tile::new(shape_square(&[[true, true, true], [false, true, true]])),
+            Tile::new(shape_square(&[[true, true, true], [true, true, false]])),
+        ];
+
+        let result = count_all_filling(board, &tiles, CancellationToken::new()).await;
+        assert_eq!(result, Ok(0));
+    }
+
+    #[tokio::test]
+    async fn test_count_all_filling_plausibility_check_failed() {
+        let board = Board::new((3, 4));
+        let tiles = vec![Tile::new(shape_square(&[[true, true, true]]))];
+
+        let result = count_all_filling(board, &tiles, CancellationToken::new()).await;
+        assert_eq!(result, Ok(0));
+    }
+
+    #[tokio::test]
+    async fn test_count_all_filling_cancelled() {
+        let board = Board::new((5, 5));
+        let tiles = vec![Tile::new(shape_square(&[[true]])); 25];
+        let cancel_token = CancellationToken::new();
+        cancel_token.cancel();
+
+        let result = count_all_filling(board, &tiles, cancel_token).await;
+        assert_eq!(result, Err(UnsolvableReason::Cancelled));
+    }
+
+    #[tokio::test]
+    async fn test_enumerate_all_filling() {
+        let create_board = || {
+            let mut board = Board::new((3, 4));
+            board[[0, 0]] = true;
+            board[[0, 1]] = true;
+            board
+        };
+        let tiles = vec![Tile::new(shape_square(&[[true, true]])); 5];
+
+        let solutions =
+            enumerate_all_filling(create_board(), &tiles, 100, CancellationToken::new())
+                .await
+                .unwrap();
+        let count = count_all_filling(create_board(), &tiles, CancellationToken::new()).await;
+        assert_eq!(Ok(solutions.len() as u64), count);
+        for solution in &solutions {
+            assert!(solution.is_complete());
+            assert_eq!(solution.placements().len(), tiles.len());
+            let mut filled = create_board().get_shape().clone();
+            for placement in solution.placements() {
+                let (x, y) = placement.position();
+                for ((dx, dy), cell) in placement.rotation().indexed_iter() {
+                    if *cell {
+                        assert!(!filled[(x + dx, y + dy)]);
+                        filled[(x + dx, y + dy)] = true;
+                    }
+                }
+            }
+            assert!(filled.iter().all(|cell| *cell));
+        }
+
+        let limited =
+            enumerate_all_filling(create_board(), &tiles, 2, CancellationToken::new()).await;
+        assert_eq!(limited.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_enumerate_all_filling_unsolvable() {
+        let board = Board::new((3, 4));
+        let tiles = vec![Tile::new(shape_square(&[[true, true, true]]))];
+
+        let result = enumerate_all_filling(board, &tiles, 10, CancellationToken::new()).await;
+        assert_eq!(result.unwrap().len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_has_unique_solution() {
+        let board = Board::new((2, 3));
+        let tiles = vec![
+            Tile::new(shape_square(&[[true, true, true]])),
+            Tile::new(shape_square(&[[true, true, true]])),
+        ];
+
+        let result = has_unique_solution(board, &tiles, CancellationToken::new()).await;
+        assert_eq!(result, Ok(true));
+    }
+
+    #[tokio::test]
+    async fn test_has_unique_solution_multiple_solutions() {
+        let board = Board::new((3, 4));
+        let tiles = vec![Tile::new(shape_square(&[[true, true]])); 6];
+
+        let result = has_unique_solution(board, &tiles, CancellationToken::new()).await;
+        assert_eq!(result, Ok(false));
+    }
+
+    #[tokio::test]
+    async fn test_has_unique_solution_unsolvable() {
+        let board = Board::new((3, 4));
+        let tiles = vec![
+            Tile::new(shape_square(&[[true, true, true], [false, true, true]])),
+            Tile::new(shape_square(&[[true, true, true], [true, true, false]])),
+        ];
+
+        let result = has_unique_solution(board, &tiles, CancellationToken::new()).await;
+        assert_eq!(result, Ok(false));
+    }
+
+    #[tokio::test]
+    async fn test_count_all_filling_reserved_cell() {
+        let domino = Tile::new(shape_square(&[[true, true]]));
+        let monomino = Tile::new(shape_square(&[[true]]));
+        let tiles = vec![domino.clone(), monomino.clone(), monomino.clone()];
+        let count = |board| count_all_filling(board, &tiles, CancellationToken::new());
+
+        assert_eq!(count(Board::new((1, 4))).await, Ok(3));
+
+        let mut board = Board::new((1, 4));
+        board.reserve_cell([0, 1], &domino);
+        assert_eq!(count(board).await, Ok(2));
+
+        let mut board = Board::new((1, 4));
+        board.reserve_cell([0, 0], &monomino);
+        board.reserve_cell([0, 3], &monomino);
+        assert_eq!(count(board).await, Ok(1));
+
+        let mut board = Board::new((1, 4));
+        board.reserve_cell([0, 0], &domino);
+        board.reserve_cell([0, 3], &domino);
+        assert_eq!(count(board).await, Ok(0));
+    }
+
+    fn half_cell_tiles() -> Vec<Tile> {
+        let mut domino = shape_square(&[[true], [true]]);
+        domino.set_cell_shape((1, 0), CellShape::BottomRight);
+        let mut half = shape_square(&[[true]]);
+        half.set_cell_shape((0, 0), CellShape::TopLeft);
+        vec![Tile::new(domino), Tile::new(half)]
+    }
+
+    #[tokio::test]
+    async fn test_solve_all_filling_half_cells() {
+        let board = Board::new((2, 1));
+
+        let result = solve_all_filling(board, &half_cell_tiles(), CancellationToken::new()).await;
+        assert!(result.is_ok());
+        let solution = result.unwrap();
+        let covered: Vec<u8> = (0..2)
+            .map(|x| {
+                solution
+                    .placements()
+                    .iter()
+                    .filter(|placement| placement.position().0 <= x)
+                    .map(|placement| {
+                        let rotation: &Shape = placement.rotation();
+                        rotation
+                            .get((x - placement.position().0, 0))
+                            .map_or(0, |_| rotation.quarters((x - placement.position().0, 0)))
+                    })
+                    .fold(0, |covered, quarters| {
+                        assert_eq!(covered & quarters, 0);
+                        covered | quarters
+                    })
+            })
+            .collect();
+        assert_eq!(covered, vec![0b1111, 0b1111]);
+    }
+
+    #[tokio::test]
+    async fn test_count_all_filling_half_cells() {
+        let board = Board::new((2, 1));
+
+        let result = count_all_filling(board, &half_cell_tiles(), CancellationToken::new()).await;
+        assert_eq!(result, Ok(4));
+    }
+
+    #[tokio::test]
+    async fn test_solve_all_filling_half_cells_on_board() {
+        let mut board = Board::new((1, 1));
+        board[[0, 0]] = true;
+        board.set_cell_shape([0, 0], CellShape::TopLeft);
+        let mut half = shape_square(&[[true]]);
+        half.set_cell_shape((0, 0), CellShape::TopLeft);
+
+        let result = solve_all_filling(board, &[Tile::new(half)], CancellationToken::new()).await;
+        assert!(result.is_ok());
+        let rotation = result.unwrap().placements()[0].rotation().clone();
+        assert_eq!(rotation.cell_shape((0, 0)), CellShape::BottomRight);
+    }
+
+    /// Number of random puzzles checked by each property test.
+    const PROPERTY_CASES: u32 = 64;
+
+    /// Waits for the future on a new runtime, since the cases of property tests cannot be async.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(future)
+    }
+
+    /// Asserts, that the placements use every tile once and cover every empty cell of the board
+    /// exactly once without covering filled cells.
+    fn assert_exact_tiling(decomposition: &Decomposition, solution: &Solution) {
+        let board = &decomposition.board;
+        let mut unused_tiles: Vec<Tile> =
+            decomposition.tiles.iter().cloned().map(Tile::new).collect();
+        let mut covered = board.clone();
+        for placement in solution.placements() {
+            let tile_index = unused_tiles
+                .iter()
+                .position(|tile| tile.all_rotations.contains(placement.rotation()))
+                .expect("rotation of no unused tile placed");
+            unused_tiles.swap_remove(tile_index);
+
+            let (x, y) = placement.position();
+            for ((cell_x, cell_y), cell) in placement.rotation().indexed_iter() {
+                if !*cell {
+                    continue;
+                }
+                let position = (x + cell_x, y + cell_y);
+                assert!(
+                    !covered.get(position).copied().unwrap_or(true),
+                    "cell {:?} is outside the board or covered twice",
+                    position
+                );
+                covered[position] = true;
+            }
+        }
+        assert!(unused_tiles.is_empty(), "not all tiles placed");
+        assert!(covered.iter().all(|c| *c), "board not covered");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(PROPERTY_CASES))]
+
+        #[test]
+        fn test_solve_all_filling_random_decompositions(decomposition in decomposition(6, 5)) {
+            let tiles: Vec<Tile> = decomposition.tiles.iter().cloned().map(Tile::new).collect();
+
+            let result = block_on(solve_all_filling(
+                decomposition.board.clone().into(),
+                &tiles,
+                CancellationToken::new(),
+            ));
+
+            let solution = result.unwrap_or_else(|e| panic!("unsolved: {:?}", e));
+            prop_assert!(solution.is_complete());
+            assert_exact_tiling(&decomposition, &solution);
+        }
+
+        #[test]
+        fn test_solve_all_filling_random_decompositions_cell_removed(
+            decomposition in decomposition(6, 5),
+            removed in any::<Index>(),
+        ) {
+            let empty_cells: Vec<(usize, usize)> = decomposition
+                .board
+                .indexed_iter()
+                .filter(|(_, cell)| !**cell)
+                .map(|(position, _)| position)
+                .collect();
+            prop_assume!(!empty_cells.is_empty());
+            let mut board = decomposition.board.clone();
+            board[empty_cells[removed.index(empty_cells.len())]] = true;
+            let tiles: Vec<Tile> = decomposition.tiles.iter().cloned().map(Tile::new).collect();
+
+            let result = block_on(solve_all_filling(
+                board.into(),
+                &tiles,
+                CancellationToken::new(),
+            ));
+
+            prop_assert!(
+                matches!(result, Err(reason) if reason != UnsolvableReason::Cancelled),
+                "solved with a cell removed"
+            );
+        }
+    }
 }
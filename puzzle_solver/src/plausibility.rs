@@ -1,32 +1,132 @@
+use crate::area::{quarter_count, QUARTERS_PER_CELL};
 use crate::board::Board;
+use crate::result::UnsolvableReason;
 use crate::tile::Tile;
 use log::debug;
+use puzzled_common::shape::cell::QUARTERS_ALL;
+use puzzled_common::Shape;
 
 /// Performs a plausibility check for the given board and tiles.
 /// It checks the following conditions:
 ///
-/// 1. The total area of the tiles must equal the area of the board.
-/// 2. Each tile must fit within the board dimensions in at least one rotation. TODO
+/// 1. The total area of the tiles must equal the area of the board. Half cells count as half of
+///    a cell.
+/// 2. Each tile must fit within the empty part of the board in at least one rotation.
+/// 3. Each empty cell of the board must be coverable by at least one tile in some rotation and
+///    position.
 ///
-/// If all conditions are met, the function returns true; otherwise, it returns false.
+/// If all conditions are met, the function returns `Ok(())`; otherwise, it returns the reason
+/// for the first condition, which is not met.
 ///
 /// # Arguments
 ///
 /// * `board`: The board the tiles should be placed on.
 /// * `tiles`: A slice of tiles to be placed on the board.
 ///
-/// returns: bool
-pub(crate) fn check(board: &Board, tiles: &[Tile]) -> bool {
-    let board_area = board.get_shape().iter().filter(|&&cell| !cell).count();
-    let tiles_area: usize = tiles
-        .iter()
-        .map(|tile| tile.base.iter().filter(|&&cell| cell).count())
-        .sum();
+/// returns: Result<(), UnsolvableReason>
+#[allow(clippy::result_large_err)]
+pub(crate) fn check(board: &Board, tiles: &[Tile]) -> Result<(), UnsolvableReason> {
+    let reason = check_area(board, tiles)
+        .or_else(|| check_tiles_fit(board, tiles))
+        .or_else(|| check_cells_coverable(board, tiles));
+    match reason {
+        Some(reason) => Err(reason),
+        None => Ok(()),
+    }
+}
+
+/// Checks condition 1 of [check] and returns the reason, if it is not met.
+fn check_area(board: &Board, tiles: &[Tile]) -> Option<UnsolvableReason> {
+    let board_area = QUARTERS_PER_CELL * board.get_shape().len() - quarter_count(board.get_shape());
+    let tiles_area: usize = tiles.iter().map(|tile| quarter_count(&tile.base)).sum();
     debug!(
         "Plausibility check: board area = {}, tiles area = {}",
         board_area, tiles_area
     );
-    tiles_area == board_area
+    (tiles_area != board_area).then_some(UnsolvableReason::AreaMismatch {
+        tiles_area,
+        board_area,
+    })
+}
+
+/// Checks condition 2 of [check] and returns the reason, if it is not met.
+fn check_tiles_fit(board: &Board, tiles: &[Tile]) -> Option<UnsolvableReason> {
+    // Without empty cells, there is nothing to check, since condition 1 only holds for tiles
+    // without cells.
+    let (width, height) = empty_dimensions(board.get_shape())?;
+    for tile in tiles {
+        let fits = tile.all_rotations.iter().any(|rotation| {
+            let (x, y) = rotation.dim();
+            x <= width && y <= height
+        });
+        if !fits {
+            debug!("Plausibility check: tile does not fit on the board");
+            return Some(UnsolvableReason::TileLargerThanBoard {
+                base: tile.base.clone(),
+            });
+        }
+    }
+    None
+}
+
+/// Checks condition 3 of [check] and returns the reason, if it is not met.
+/// Cells reserved for another tile cannot be covered by a tile.
+fn check_cells_coverable(board: &Board, tiles: &[Tile]) -> Option<UnsolvableReason> {
+    let cells = board.get_shape();
+    let (board_x, board_y) = cells.dim();
+    let mut coverable = vec![0u8; board_x * board_y];
+    for rotation in tiles.iter().flat_map(|tile| &tile.all_rotations) {
+        let (tile_x, tile_y) = rotation.dim();
+        if tile_x > board_x || tile_y > board_y {
+            continue;
+        }
+        let reserved_cells = board.cells_reserved_for_others(rotation);
+        for offset_x in 0..=board_x - tile_x {
+            for offset_y in 0..=board_y - tile_y {
+                let fits = rotation.indexed_iter().all(|((x, y), _)| {
+                    let position = (offset_x + x, offset_y + y);
+                    let quarters = rotation.quarters((x, y));
+                    quarters & cells.quarters(position) == 0
+                        && (quarters == 0 || !reserved_cells.contains(&position))
+                });
+                if fits {
+                    for ((x, y), _) in rotation.indexed_iter() {
+                        coverable[(offset_x + x) * board_y + offset_y + y] |=
+                            rotation.quarters((x, y));
+                    }
+                }
+            }
+        }
+    }
+
+    for (position, _) in cells.indexed_iter() {
+        let empty = QUARTERS_ALL & !cells.quarters(position);
+        if empty & !coverable[position.0 * board_y + position.1] != 0 {
+            debug!(
+                "Plausibility check: cell {:?} cannot be covered by any tile",
+                position
+            );
+            return Some(UnsolvableReason::UncoverableCell { position });
+        }
+    }
+    None
+}
+
+/// Returns the dimensions of the smallest rectangle containing all empty cells of the board or
+/// none, if there are no empty cells.
+fn empty_dimensions(board: &Shape) -> Option<(usize, usize)> {
+    let mut empty_cells = board
+        .indexed_iter()
+        .map(|(index, _)| index)
+        .filter(|index| board.quarters(*index) != QUARTERS_ALL);
+    let (first_x, first_y) = empty_cells.next()?;
+    let (min_x, max_x, min_y, max_y) = empty_cells.fold(
+        (first_x, first_x, first_y, first_y),
+        |(min_x, max_x, min_y, max_y), (x, y)| {
+            (min_x.min(x), max_x.max(x), min_y.min(y), max_y.max(y))
+        },
+    );
+    Some((max_x - min_x + 1, max_y - min_y + 1))
 }
 
 #[cfg(test)]
@@ -43,7 +143,7 @@ mod tests {
         let tile2 = Tile::new(shape_square(&[[false, true], [false, true], [true, true]]));
         let tiles = vec![tile1, tile2];
 
-        assert!(check(&board, &tiles));
+        assert_eq!(check(&board, &tiles), Ok(()));
     }
 
     #[test]
@@ -53,6 +153,66 @@ mod tests {
         let tile2 = Tile::new(shape_square(&[[false, true], [false, true]]));
         let tiles = vec![tile1, tile2];
 
-        assert!(!check(&board, &tiles));
+        assert_eq!(
+            check(&board, &tiles),
+            Err(UnsolvableReason::AreaMismatch {
+                tiles_area: 20,
+                board_area: 36,
+            })
+        );
+    }
+
+    #[test]
+    fn test_check_tile_larger_than_board() {
+        let mut board = Board::new((4, 4));
+        for x in 0..4 {
+            for y in 2..4 {
+                board[[x, y]] = true;
+            }
+        }
+        let tile1 = Tile::new(shape_square(&[[true, true, true]]));
+        let tile2 = Tile::new(shape_square(&[
+            [false, true, false],
+            [true, true, true],
+            [false, true, false],
+        ]));
+        let tiles = vec![tile1, tile2.clone()];
+
+        assert_eq!(
+            check(&board, &tiles),
+            Err(UnsolvableReason::TileLargerThanBoard {
+                base: tile2.base().clone()
+            })
+        );
+    }
+
+    #[test]
+    fn test_check_cell_reserved_for_missing_tile() {
+        let mut board = Board::new((1, 3));
+        board.reserve_cell([0, 0], &Tile::new(shape_square(&[[true, true]])));
+        let tiles = vec![Tile::new(shape_square(&[[true, true, true]]))];
+
+        assert_eq!(
+            check(&board, &tiles),
+            Err(UnsolvableReason::UncoverableCell { position: (0, 0) })
+        );
+    }
+
+    #[test]
+    fn test_check_uncoverable_cell() {
+        let mut board = Board::new((3, 3));
+        board[[1, 0]] = true;
+        board[[0, 1]] = true;
+        board[[1, 1]] = true;
+        board[[2, 1]] = true;
+        board[[1, 2]] = true;
+        let tile1 = Tile::new(shape_square(&[[true, true]]));
+        let tile2 = Tile::new(shape_square(&[[true, true]]));
+        let tiles = vec![tile1, tile2];
+
+        assert_eq!(
+            check(&board, &tiles),
+            Err(UnsolvableReason::UncoverableCell { position: (0, 0) })
+        );
     }
 }
@@ -1,21 +1,98 @@
 use puzzled_common::Shape;
 
 /// Represents a successful solution to the puzzle.
+///
+/// A solution found in anytime mode may be partial, if the search was cancelled before a complete
+/// solution was found. Then it contains the placements of only some tiles.
 #[derive(Debug)]
 pub struct Solution {
     placements: Vec<TilePlacement>,
+    complete: bool,
+    /// The number of rows and columns removed from the lower sides of the board by trimming.
+    trim_offset: (usize, usize),
 }
 
 impl Solution {
-    /// Creates a new `Solution` with the given tile placements.
+    /// Creates a new complete `Solution` with the given tile placements.
     pub(crate) fn new(placements: Vec<TilePlacement>) -> Self {
-        Self { placements }
+        Self {
+            placements,
+            complete: true,
+            trim_offset: (0, 0),
+        }
+    }
+
+    /// Creates a complete `Solution` found earlier, like one restored from a cache.
+    ///
+    /// # Arguments
+    ///
+    /// * `placements`: the placements of all tiles, whose positions already include the offset
+    /// * `trim_offset`: see [Self::trim_offset]
+    ///
+    /// returns: Solution
+    pub fn restored(placements: Vec<TilePlacement>, trim_offset: (usize, usize)) -> Self {
+        Self {
+            placements,
+            complete: true,
+            trim_offset,
+        }
+    }
+
+    /// Creates a new partial `Solution`, which places only some tiles.
+    pub(crate) fn partial(placements: Vec<TilePlacement>) -> Self {
+        Self {
+            placements,
+            complete: false,
+            trim_offset: (0, 0),
+        }
+    }
+
+    /// Creates a solution of the same kind for the board before it was trimmed.
+    /// The placements of this solution must be relative to the trimmed board.
+    ///
+    /// # Arguments
+    ///
+    /// * `trim_offset`: the number of rows and columns removed from the lower sides of the board
+    ///
+    /// returns: Solution
+    pub(crate) fn untrimmed(self, trim_offset: (usize, usize)) -> Self {
+        let placements = self
+            .placements
+            .into_iter()
+            .map(|placement| {
+                let (x, y) = placement.position;
+                TilePlacement {
+                    position: (x + trim_offset.0, y + trim_offset.1),
+                    ..placement
+                }
+            })
+            .collect();
+        Self {
+            placements,
+            complete: self.complete,
+            trim_offset,
+        }
+    }
+
+    /// Returns true, if all tiles are placed and the board is filled completely.
+    pub fn is_complete(&self) -> bool {
+        self.complete
     }
 
     /// Returns a reference to the tile placements in the solution.
+    /// Their positions are relative to the board given to the solver.
     pub fn placements(&self) -> &[TilePlacement] {
         &self.placements
     }
+
+    /// Returns the number of filled rows and columns, which were removed from the lower x and y
+    /// sides of the board before solving.
+    ///
+    /// The positions of the placements already include this offset. It can be used to map
+    /// positions on the trimmed board, which the solver works on, back to the given board.
+    pub fn trim_offset(&self) -> (usize, usize) {
+        self.trim_offset
+    }
 }
 
 /// Represents the placement of a tile at a specific position in the puzzle.
@@ -31,7 +108,7 @@ pub struct TilePlacement {
 
 impl TilePlacement {
     /// Creates a new `TilePlacement` with the given base, rotation, and position.
-    pub(crate) fn new(base: Shape, rotation: Shape, position: (usize, usize)) -> Self {
+    pub fn new(base: Shape, rotation: Shape, position: (usize, usize)) -> Self {
         Self {
             base,
             rotation,
@@ -56,14 +133,23 @@ impl TilePlacement {
 }
 
 /// Represents the reason why a puzzle is unsolvable.
-///
-/// Currently, the only reason is `NoFit`, indicating that no tiles can fit in the remaining spaces.
-///
-/// In the future, more reasons can be added as needed.
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum UnsolvableReason {
     NoFit,
-    PlausibilityCheckFailed,
+    /// The tiles cover a different area than the empty cells of the board.
+    /// Both areas are given in quarters of a cell, since tiles and boards may contain half cells.
+    AreaMismatch {
+        tiles_area: usize,
+        board_area: usize,
+    },
+    /// The tile does not fit within the empty part of the board in any rotation.
+    TileLargerThanBoard {
+        base: Shape,
+    },
+    /// No tile can be placed to cover the empty cell at the given position.
+    UncoverableCell {
+        position: (usize, usize),
+    },
     TileCannotBePlaced {
         base: Shape,
     },
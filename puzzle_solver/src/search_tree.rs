@@ -0,0 +1,372 @@
+use std::fmt::{Display, Formatter};
+use std::io;
+use std::io::{Read, Write};
+use std::sync::{Arc, Mutex};
+
+/// The bytes at the start of every dumped search tree.
+const MAGIC: &[u8; 4] = b"PZST";
+/// The version of the binary format written by [SearchTree::write_to].
+const FORMAT_VERSION: u8 = 1;
+/// Marks a node without parent in the binary format.
+const NO_PARENT: u32 = u32::MAX;
+
+/// Why a placement was not explored further.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PruneReason {
+    /// The pruner found a pattern of empty cells on the board, which no tile can fill.
+    BannedPattern,
+    /// The branch only differs from another one by swapping identical tiles.
+    IdenticalTileOrder,
+    /// The first empty cell on the board cannot be covered by any of the remaining tiles.
+    UncoverableCell,
+}
+
+/// What happened to a placement tried by the solver.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NodeKind {
+    /// The tile was placed and the search continued with the next tile.
+    Placed,
+    /// The tile was placed and completed a solution.
+    Solution,
+    /// The placement was rejected without searching the branch below it.
+    Pruned(PruneReason),
+}
+
+impl NodeKind {
+    fn to_byte(self) -> u8 {
+        match self {
+            NodeKind::Placed => 0,
+            NodeKind::Solution => 1,
+            NodeKind::Pruned(PruneReason::BannedPattern) => 2,
+            NodeKind::Pruned(PruneReason::IdenticalTileOrder) => 3,
+            NodeKind::Pruned(PruneReason::UncoverableCell) => 4,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<NodeKind> {
+        match byte {
+            0 => Some(NodeKind::Placed),
+            1 => Some(NodeKind::Solution),
+            2 => Some(NodeKind::Pruned(PruneReason::BannedPattern)),
+            3 => Some(NodeKind::Pruned(PruneReason::IdenticalTileOrder)),
+            4 => Some(NodeKind::Pruned(PruneReason::UncoverableCell)),
+            _ => None,
+        }
+    }
+}
+
+/// A placement of a tile tried by the solver.
+///
+/// Tiles are numbered in the order the solver places them, which is the largest tile first.
+/// Placements are numbered in the order the solver tries them for the tile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SearchNode {
+    /// The index of the node, on whose branch this placement was tried. Placements of the first
+    /// tile have no parent.
+    pub parent: Option<u32>,
+    pub tile: u16,
+    pub placement: u32,
+    pub kind: NodeKind,
+}
+
+/// The placements explored by the solver in a single run.
+///
+/// Placements overlapping tiles placed before are not recorded, since they are rejected
+/// immediately and would make up most of the tree.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SearchTree {
+    nodes: Vec<SearchNode>,
+    truncated: bool,
+}
+
+impl SearchTree {
+    /// Returns the recorded placements. A parent is always recorded before its children.
+    pub fn nodes(&self) -> &[SearchNode] {
+        &self.nodes
+    }
+
+    /// Returns true, if the recording stopped, because the tree reached the maximum number of
+    /// nodes.
+    pub fn is_truncated(&self) -> bool {
+        self.truncated
+    }
+
+    /// Writes the tree in a compact binary format, which can be read with [Self::read_from].
+    pub fn write_to(&self, mut writer: impl Write) -> io::Result<()> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&[FORMAT_VERSION, self.truncated as u8])?;
+        writer.write_all(&(self.nodes.len() as u64).to_le_bytes())?;
+        for node in &self.nodes {
+            writer.write_all(&node.parent.unwrap_or(NO_PARENT).to_le_bytes())?;
+            writer.write_all(&node.tile.to_le_bytes())?;
+            writer.write_all(&node.placement.to_le_bytes())?;
+            writer.write_all(&[node.kind.to_byte()])?;
+        }
+        writer.flush()
+    }
+
+    /// Reads a tree written by [Self::write_to].
+    pub fn read_from(mut reader: impl Read) -> io::Result<SearchTree> {
+        let invalid = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        let mut magic = [0; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(invalid("Not a search tree file"));
+        }
+        let [version, truncated] = read_bytes(&mut reader)?;
+        if version != FORMAT_VERSION {
+            return Err(invalid("Unsupported search tree format version"));
+        }
+        let count = u64::from_le_bytes(read_bytes(&mut reader)?);
+
+        let mut nodes = Vec::new();
+        for _ in 0..count {
+            let parent = u32::from_le_bytes(read_bytes(&mut reader)?);
+            let tile = u16::from_le_bytes(read_bytes(&mut reader)?);
+            let placement = u32::from_le_bytes(read_bytes(&mut reader)?);
+            let [kind] = read_bytes(&mut reader)?;
+            let parent = (parent != NO_PARENT).then_some(parent);
+            if parent.is_some_and(|parent| parent as usize >= nodes.len()) {
+                return Err(invalid("Node recorded before its parent"));
+            }
+            nodes.push(SearchNode {
+                parent,
+                tile,
+                placement,
+                kind: NodeKind::from_byte(kind).ok_or_else(|| invalid("Unknown node kind"))?,
+            });
+        }
+        Ok(SearchTree {
+            nodes,
+            truncated: truncated != 0,
+        })
+    }
+
+    /// Counts the nodes of the tree by kind and depth.
+    pub fn stats(&self) -> SearchTreeStats {
+        let mut stats = SearchTreeStats {
+            truncated: self.truncated,
+            ..SearchTreeStats::default()
+        };
+        let mut depths: Vec<usize> = Vec::with_capacity(self.nodes.len());
+        let mut has_children = vec![false; self.nodes.len()];
+        for node in &self.nodes {
+            let depth = match node.parent {
+                Some(parent) => {
+                    has_children[parent as usize] = true;
+                    depths[parent as usize] + 1
+                }
+                None => 0,
+            };
+            depths.push(depth);
+            if stats.nodes_per_depth.len() <= depth {
+                stats.nodes_per_depth.resize(depth + 1, 0);
+            }
+            stats.nodes_per_depth[depth] += 1;
+            match node.kind {
+                NodeKind::Placed => stats.placed += 1,
+                NodeKind::Solution => stats.solutions += 1,
+                NodeKind::Pruned(PruneReason::BannedPattern) => stats.pruned_banned_pattern += 1,
+                NodeKind::Pruned(PruneReason::IdenticalTileOrder) => {
+                    stats.pruned_identical_tiles += 1
+                }
+                NodeKind::Pruned(PruneReason::UncoverableCell) => {
+                    stats.pruned_uncoverable_cell += 1
+                }
+            }
+        }
+        stats.dead_ends = self
+            .nodes
+            .iter()
+            .zip(has_children)
+            .filter(|(node, has_children)| node.kind == NodeKind::Placed && !has_children)
+            .count() as u64;
+        stats
+    }
+}
+
+fn read_bytes<const N: usize>(reader: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut bytes = [0; N];
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Statistics about a [SearchTree].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SearchTreeStats {
+    pub placed: u64,
+    pub solutions: u64,
+    pub pruned_banned_pattern: u64,
+    pub pruned_identical_tiles: u64,
+    pub pruned_uncoverable_cell: u64,
+    /// Placements, after which no placement of the next tile was tried, since all of them
+    /// overlapped other tiles.
+    pub dead_ends: u64,
+    /// The number of nodes for each number of tiles placed before them.
+    pub nodes_per_depth: Vec<u64>,
+    pub truncated: bool,
+}
+
+impl Display for SearchTreeStats {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let total = self.placed
+            + self.solutions
+            + self.pruned_banned_pattern
+            + self.pruned_identical_tiles
+            + self.pruned_uncoverable_cell;
+        writeln!(f, "Nodes: {}", total)?;
+        writeln!(f, "Placed: {}", self.placed)?;
+        writeln!(f, "Solutions: {}", self.solutions)?;
+        writeln!(f, "Dead ends: {}", self.dead_ends)?;
+        writeln!(f, "Pruned (banned pattern): {}", self.pruned_banned_pattern)?;
+        writeln!(
+            f,
+            "Pruned (identical tile order): {}",
+            self.pruned_identical_tiles
+        )?;
+        writeln!(
+            f,
+            "Pruned (uncoverable cell): {}",
+            self.pruned_uncoverable_cell
+        )?;
+        writeln!(f, "Nodes per depth:")?;
+        for (depth, count) in self.nodes_per_depth.iter().enumerate() {
+            writeln!(f, "  {:>3}: {}", depth, count)?;
+        }
+        if self.truncated {
+            writeln!(
+                f,
+                "The tree is truncated, the search went on after recording stopped."
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Records the search tree of a solver run.
+///
+/// All solver tasks record into the same tree, so recording slows down the search considerably.
+/// It should only be used to analyze single runs, which take unexpectedly long.
+///
+/// Clones record into the same tree.
+#[derive(Debug, Clone)]
+pub struct SearchTreeRecorder {
+    max_nodes: usize,
+    tree: Arc<Mutex<SearchTree>>,
+}
+
+impl SearchTreeRecorder {
+    /// Creates a recorder, which stops recording after the given number of nodes.
+    pub fn new(max_nodes: usize) -> Self {
+        SearchTreeRecorder {
+            max_nodes,
+            tree: Arc::new(Mutex::new(SearchTree::default())),
+        }
+    }
+
+    /// Returns the tree recorded so far and starts a new one.
+    pub fn take_tree(&self) -> SearchTree {
+        std::mem::take(&mut *self.tree.lock().unwrap())
+    }
+
+    /// Adds a node to the tree and returns its index.
+    /// Returns `None`, if the tree is full. Once it is full, no more nodes are added.
+    pub(crate) fn add(
+        &self,
+        parent: Option<u32>,
+        tile: usize,
+        placement: usize,
+        kind: NodeKind,
+    ) -> Option<u32> {
+        let mut tree = self.tree.lock().unwrap();
+        if tree.nodes.len() >= self.max_nodes.min(NO_PARENT as usize) {
+            tree.truncated = true;
+            return None;
+        }
+        tree.nodes.push(SearchNode {
+            parent,
+            tile: tile as u16,
+            placement: placement as u32,
+            kind,
+        });
+        Some(tree.nodes.len() as u32 - 1)
+    }
+
+    /// Marks the placed node as completing a solution.
+    pub(crate) fn mark_solution(&self, node: u32) {
+        let mut tree = self.tree.lock().unwrap();
+        if let Some(node) = tree.nodes.get_mut(node as usize) {
+            node.kind = NodeKind::Solution;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_tree() -> SearchTree {
+        let recorder = SearchTreeRecorder::new(100);
+        let root = recorder.add(None, 0, 0, NodeKind::Placed);
+        recorder.add(None, 0, 1, NodeKind::Pruned(PruneReason::BannedPattern));
+        let child = recorder.add(root, 1, 3, NodeKind::Placed);
+        recorder.add(
+            root,
+            1,
+            4,
+            NodeKind::Pruned(PruneReason::IdenticalTileOrder),
+        );
+        recorder.add(root, 1, 5, NodeKind::Placed);
+        recorder.add(root, 1, 6, NodeKind::Pruned(PruneReason::UncoverableCell));
+        recorder.mark_solution(child.unwrap());
+        recorder.take_tree()
+    }
+
+    #[test]
+    fn test_write_read_roundtrip() {
+        let tree = example_tree();
+        let mut bytes = Vec::new();
+
+        tree.write_to(&mut bytes).unwrap();
+        let read_tree = SearchTree::read_from(bytes.as_slice()).unwrap();
+
+        assert_eq!(read_tree, tree);
+    }
+
+    #[test]
+    fn test_read_rejects_other_files() {
+        assert!(SearchTree::read_from(b"{\"nodes\": []}".as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_stats() {
+        let stats = example_tree().stats();
+
+        assert_eq!(
+            stats,
+            SearchTreeStats {
+                placed: 2,
+                solutions: 1,
+                pruned_banned_pattern: 1,
+                pruned_identical_tiles: 1,
+                pruned_uncoverable_cell: 1,
+                dead_ends: 1,
+                nodes_per_depth: vec![2, 4],
+                truncated: false,
+            }
+        );
+    }
+
+    #[test]
+    fn test_recorder_stops_at_max_nodes() {
+        let recorder = SearchTreeRecorder::new(1);
+
+        assert_eq!(recorder.add(None, 0, 0, NodeKind::Placed), Some(0));
+        assert_eq!(recorder.add(Some(0), 1, 0, NodeKind::Placed), None);
+
+        let tree = recorder.take_tree();
+        assert_eq!(tree.nodes().len(), 1);
+        assert!(tree.is_truncated());
+    }
+}
@@ -0,0 +1,173 @@
+use crate::backtracking::positioned::PositionedTile;
+use crate::bitmask::{Bitmask, CellResolution};
+use crate::tile::Tile;
+use puzzled_common::shape::TrimSides;
+use puzzled_common::Shape;
+
+/// Keeps information between consecutive solver calls for the same puzzle to speed up the
+/// search.
+///
+/// After a tile was moved, most placements of the previous solution are usually still part of a
+/// solution. The session remembers the placements of the last solution and the search tries them
+/// first for each tile (move-to-front), before trying the other placements.
+///
+/// The remembered placements are only used, if the board has the same size and is trimmed the same
+/// way as in the call, which found them. Otherwise, the search runs in its default order.
+///
+/// # Examples
+///
+/// ```
+/// use puzzle_solver::board::Board;
+/// use puzzle_solver::session::SolverSession;
+/// use puzzle_solver::solve_all_filling_in_session;
+/// use puzzle_solver::tile::Tile;
+/// use puzzled_common::shape::shape_square;
+/// use tokio_util::sync::CancellationToken;
+///
+/// let tiles = vec![
+///     Tile::new(shape_square(&[[true, true]])),
+///     Tile::new(shape_square(&[[true, true]])),
+/// ];
+/// let mut session = SolverSession::default();
+///
+/// let runtime = tokio::runtime::Runtime::new().unwrap();
+/// let first = runtime.block_on(solve_all_filling_in_session(Board::new((2, 2)), &tiles, &mut session, CancellationToken::new()));
+/// let second = runtime.block_on(solve_all_filling_in_session(Board::new((2, 2)), &tiles, &mut session, CancellationToken::new()));
+/// assert_eq!(first.unwrap().placements(), second.unwrap().placements());
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct SolverSession {
+    frame: Option<PlacementFrame>,
+    preferred_placements: Vec<(Shape, Bitmask)>,
+}
+
+/// Describes the board, for which placements were computed.
+/// Placements can only be compared, if their frames are equal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct PlacementFrame {
+    dim: (usize, usize),
+    trim_sides: (usize, usize, usize, usize),
+    resolution: CellResolution,
+}
+
+impl PlacementFrame {
+    pub(crate) fn new(
+        dim: (usize, usize),
+        trim_sides: &TrimSides,
+        resolution: CellResolution,
+    ) -> PlacementFrame {
+        PlacementFrame {
+            dim,
+            trim_sides: (
+                trim_sides.lower_x,
+                trim_sides.upper_x,
+                trim_sides.lower_y,
+                trim_sides.upper_y,
+            ),
+            resolution,
+        }
+    }
+}
+
+impl SolverSession {
+    /// Forgets the placements of the last solution.
+    /// This should be called, when a different puzzle is solved.
+    pub fn clear(&mut self) {
+        self.frame = None;
+        self.preferred_placements.clear();
+    }
+
+    /// Moves the placements of the last solution to the front of the placements of each tile,
+    /// if they were computed for the same frame.
+    pub(crate) fn prioritize(
+        &self,
+        frame: &PlacementFrame,
+        tiles: &[Tile],
+        positioned_tiles: &mut [PositionedTile],
+    ) {
+        if self.frame.as_ref() != Some(frame) {
+            return;
+        }
+        for (tile, positioned_tile) in tiles.iter().zip(positioned_tiles.iter_mut()) {
+            positioned_tile.move_to_front(|bitmask| {
+                self.preferred_placements
+                    .iter()
+                    .any(|(base, preferred)| *base == tile.base && preferred == bitmask)
+            });
+        }
+    }
+
+    /// Remembers the placements of a solution to try them first in the next call.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame`: The frame, for which the placements were computed.
+    /// * `tiles`: The tiles in the order they were placed.
+    /// * `positioned_tiles`: The placements of the tiles in the same order.
+    /// * `placements`: The index of the placement used for each tile.
+    ///
+    /// returns: ()
+    pub(crate) fn remember(
+        &mut self,
+        frame: PlacementFrame,
+        tiles: &[Tile],
+        positioned_tiles: &[PositionedTile],
+        placements: &[usize],
+    ) {
+        self.frame = Some(frame);
+        self.preferred_placements = placements
+            .iter()
+            .enumerate()
+            .map(|(tile_index, &placement_index)| {
+                (
+                    tiles[tile_index].base.clone(),
+                    positioned_tiles[tile_index].bitmasks()[placement_index].clone(),
+                )
+            })
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backtracking::pruner::Pruner;
+    use crate::board::Board;
+    use puzzled_common::shape::shape_square;
+
+    #[test]
+    fn test_prioritize() {
+        let board = Board::new((2, 3));
+        let tiles = vec![Tile::new(shape_square(&[[true, true]]))];
+        let pruner = Pruner::new_for_filling(&board, &tiles);
+        let positioned_tiles = vec![PositionedTile::new(
+            &tiles[0],
+            &board,
+            &pruner,
+            CellResolution::Full,
+        )];
+        let last = positioned_tiles[0].bitmasks().len() - 1;
+        let frame = PlacementFrame::new((2, 3), &TrimSides::default(), CellResolution::Full);
+        let mut session = SolverSession::default();
+        session.remember(frame.clone(), &tiles, &positioned_tiles, &[last]);
+
+        let mut other_frame_tiles = positioned_tiles.clone();
+        let other_frame = PlacementFrame::new((3, 3), &TrimSides::default(), CellResolution::Full);
+        session.prioritize(&other_frame, &tiles, &mut other_frame_tiles);
+        assert_eq!(
+            other_frame_tiles[0].bitmasks(),
+            positioned_tiles[0].bitmasks()
+        );
+
+        let mut prioritized_tiles = positioned_tiles.clone();
+        session.prioritize(&frame, &tiles, &mut prioritized_tiles);
+        assert_eq!(
+            prioritized_tiles[0].bitmasks()[0],
+            positioned_tiles[0].bitmasks()[last]
+        );
+        assert_eq!(
+            prioritized_tiles[0].bitmasks().len(),
+            positioned_tiles[0].bitmasks().len()
+        );
+    }
+}
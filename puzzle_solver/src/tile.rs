@@ -1,6 +1,6 @@
+use crate::art::{shape_from_rows, shape_from_str_art, ArtError};
 use log::debug;
 use puzzled_common::Shape;
-use std::collections::HashSet;
 
 /// Represents a tile to place on a board.
 /// It is based on a 2D array of booleans, where `true` indicates the presence of a feature
@@ -36,19 +36,58 @@ impl Tile {
     /// let tile = Tile::new(base);
     /// ```
     pub fn new(base: Shape) -> Tile {
-        let mut all_rotations_set: HashSet<Shape> = HashSet::new();
-
-        base.rotations_flips_iter().for_each(|rotation| {
-            all_rotations_set.insert(rotation);
-        });
-
-        let all_rotations = all_rotations_set.into_iter().collect();
+        let all_rotations = base.unique_rotations_flips();
         Tile {
             base,
             all_rotations,
         }
     }
 
+    /// Creates a new Tile from rows of numbers, where any number except `0` marks a cell of the
+    /// tile.
+    ///
+    /// # Arguments
+    ///
+    /// * `rows`: The rows of the base, where `rows[x][y]` is the cell at (x, y).
+    ///
+    /// returns: Result<Tile, ArtError> Err, if the rows have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use puzzle_solver::tile::Tile;
+    /// use puzzled_common::shape::shape_square;
+    ///
+    /// let tile = Tile::from_rows(&[[1, 1], [1, 0]]).unwrap();
+    /// assert_eq!(tile.base(), &shape_square(&[[true, true], [true, false]]));
+    /// ```
+    pub fn from_rows<R: AsRef<[u8]>>(rows: &[R]) -> Result<Tile, ArtError> {
+        Ok(Tile::new(shape_from_rows(rows)?))
+    }
+
+    /// Creates a new Tile from text art, where `#` marks a cell of the tile and `.` marks the
+    /// absence of one. The lines are the rows as in [Tile::from_rows].
+    ///
+    /// # Arguments
+    ///
+    /// * `art`: The text art of the base. Whitespace around the lines and empty lines are
+    ///   ignored.
+    ///
+    /// returns: Result<Tile, ArtError> Err, if the art contains other characters or the rows
+    /// have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use puzzle_solver::tile::Tile;
+    ///
+    /// let tile = Tile::from_str_art("##\n#.").unwrap();
+    /// assert_eq!(tile, Tile::from_rows(&[[1, 1], [1, 0]]).unwrap());
+    /// ```
+    pub fn from_str_art(art: &str) -> Result<Tile, ArtError> {
+        Ok(Tile::new(shape_from_str_art(art)?))
+    }
+
     /// Returns a reference to the base 2D boolean array of the tile.
     /// This is the same array that was used to create the Tile.
     ///
@@ -0,0 +1,91 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// The state of the backtracking search at some point while solving.
+///
+/// All positions are cells of the board given to the solver.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchSnapshot {
+    tried_cells: Vec<(usize, usize)>,
+    pruned_cells: Vec<(usize, usize)>,
+    prune_hits: u64,
+}
+
+impl SearchSnapshot {
+    pub(crate) fn new(
+        tried_cells: Vec<(usize, usize)>,
+        pruned_cells: Vec<(usize, usize)>,
+        prune_hits: u64,
+    ) -> Self {
+        Self {
+            tried_cells,
+            pruned_cells,
+            prune_hits,
+        }
+    }
+
+    /// Returns the cells covered by the tiles placed on the branch, which is currently tried.
+    pub fn tried_cells(&self) -> &[(usize, usize)] {
+        &self.tried_cells
+    }
+
+    /// Returns the cells of the last placement rejected by the pruner since the previous
+    /// snapshot. It is empty, if no placement was rejected in the meantime.
+    pub fn pruned_cells(&self) -> &[(usize, usize)] {
+        &self.pruned_cells
+    }
+
+    /// Returns the number of placements rejected by the pruner so far.
+    ///
+    /// The solver searches on multiple tasks, which report their rejections only when they take
+    /// a snapshot, so this may lag behind a little.
+    pub fn prune_hits(&self) -> u64 {
+        self.prune_hits
+    }
+}
+
+/// Receives snapshots of the search while the solver is running.
+///
+/// Taking a snapshot slows down the search, so at most one snapshot is taken per interval.
+#[derive(Clone)]
+pub struct SearchTrace {
+    interval: Duration,
+    on_snapshot: Arc<dyn Fn(SearchSnapshot) + Send + Sync>,
+}
+
+impl SearchTrace {
+    /// Creates a new trace.
+    ///
+    /// # Arguments
+    ///
+    /// * `interval`: the minimum time between two snapshots
+    /// * `on_snapshot`: called with each snapshot on the thread of the solver task taking it
+    ///
+    /// returns: SearchTrace
+    pub fn new(
+        interval: Duration,
+        on_snapshot: impl Fn(SearchSnapshot) + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            interval,
+            on_snapshot: Arc::new(on_snapshot),
+        }
+    }
+
+    pub(crate) fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub(crate) fn send(&self, snapshot: SearchSnapshot) {
+        (self.on_snapshot)(snapshot);
+    }
+}
+
+impl Debug for SearchTrace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SearchTrace")
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
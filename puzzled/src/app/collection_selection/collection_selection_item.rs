@@ -1,16 +1,19 @@
+use crate::app::collection_selection::collection_selection_page::CollectionSelectionPage;
+use crate::app::components::progress_menu;
+use crate::app::presenter::collection_row::CollectionRow;
 use crate::model::collection::CollectionModel;
 use adw::gio;
 use adw::glib;
 use adw::subclass::prelude::*;
-use gtk::prelude::{BoxExt, WidgetExt};
+use gettextrs::gettext;
+use gtk::prelude::{BoxExt, CastNone, PopoverExt, StaticType, WidgetExt};
 use gtk::Widget;
-use puzzle_config::PuzzleDifficultyConfig;
 
 mod imp {
     use super::*;
     use crate::app::components::info_pill::InfoPill;
     use crate::model::collection::CollectionModel;
-    use std::cell::OnceCell;
+    use std::cell::{Cell, OnceCell};
 
     #[derive(Debug, Default, gtk::CompositeTemplate)]
     #[template(resource = "/de/til7701/Puzzled/ui/widget/puzzle-collection-item.ui")]
@@ -30,11 +33,19 @@ mod imp {
         #[template_child]
         pub author_pill: TemplateChild<InfoPill>,
         #[template_child]
+        pub verified_pill: TemplateChild<InfoPill>,
+        #[template_child]
         pub version_pill: TemplateChild<InfoPill>,
         #[template_child]
+        pub progress_bar: TemplateChild<gtk::ProgressBar>,
+        #[template_child]
+        pub watch_button: TemplateChild<gtk::Button>,
+        #[template_child]
         pub delete_button: TemplateChild<gtk::Button>,
 
         pub(super) collection: OnceCell<CollectionModel>,
+        pub(super) core: Cell<bool>,
+        pub(super) context_menu: OnceCell<gtk::PopoverMenu>,
     }
 
     #[glib::object_subclass]
@@ -48,6 +59,12 @@ mod imp {
             klass.install_action("app.delete_community_collection", None, |item, _, _| {
                 item.collection().delete();
             });
+            klass.install_action("app.export_collection", None, |item, _, _| {
+                item.show_export_collection_dialog();
+            });
+            klass.install_action("app.watch_collection_file", None, |item, _, _| {
+                item.watch_collection_file();
+            });
         }
 
         fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
@@ -55,8 +72,21 @@ mod imp {
         }
     }
 
-    impl ObjectImpl for PuzzledCollectionSelectionItem {}
-    impl WidgetImpl for PuzzledCollectionSelectionItem {}
+    impl ObjectImpl for PuzzledCollectionSelectionItem {
+        fn dispose(&self) {
+            if let Some(context_menu) = self.context_menu.get() {
+                context_menu.unparent();
+            }
+        }
+    }
+    impl WidgetImpl for PuzzledCollectionSelectionItem {
+        fn size_allocate(&self, width: i32, height: i32, baseline: i32) {
+            self.parent_size_allocate(width, height, baseline);
+            if let Some(context_menu) = self.context_menu.get() {
+                context_menu.present();
+            }
+        }
+    }
     impl ListBoxRowImpl for PuzzledCollectionSelectionItem {}
 }
 
@@ -75,6 +105,8 @@ impl CollectionSelectionItem {
     /// If the collection is not a core collection, the delete button is shown and can be
     /// used to call delete on the collection.
     ///
+    /// The context menu of the item marks all puzzles of the collection as solved or unsolved.
+    ///
     /// # Arguments
     ///
     /// * `model`: the collection model to show
@@ -88,22 +120,25 @@ impl CollectionSelectionItem {
         imp.collection
             .set(model.clone())
             .expect("Failed to set collection");
+        imp.core.set(core);
 
-        obj.set_name(model.config().name());
+        let row = obj.row();
+
+        obj.set_name(&row.name);
 
         obj.update_data();
 
-        obj.set_difficulty(model.config().average_difficulty());
+        obj.set_difficulty(row.difficulty);
 
-        if core {
-            obj.set_author(None);
-        } else {
-            obj.set_author(Some(model.config().author()));
-        }
+        obj.set_author(row.author.as_deref());
+
+        obj.set_verified(row.verified);
 
-        obj.set_version(model.config().version());
+        obj.set_version(&row.version);
 
-        obj.show_delete_button(!core);
+        obj.show_delete_button(row.show_delete_button);
+
+        imp.watch_button.set_visible(!core);
 
         model.connect_progress_changed({
             let obj = obj.clone();
@@ -112,31 +147,77 @@ impl CollectionSelectionItem {
             }
         });
 
+        let context_menu = progress_menu::add_progress_menu(
+            &obj,
+            &gettext("all puzzles of {}").replace("{}", &row.name),
+            {
+                let model = model.clone();
+                move || model.mark_puzzles_as_solved_manually()
+            },
+            {
+                let model = model.clone();
+                move || model.reset_progress()
+            },
+        );
+        imp.context_menu
+            .set(context_menu)
+            .expect("Failed to set context menu");
+
         obj
     }
 
     /// Updates dynamic data from the collection model. Should be called, when the corresponding
     /// signals are emitted by the collection.
+    ///
+    /// The stars and the progress are hidden, until the collection is loaded.
     fn update_data(&self) {
-        let (stars_reached, stars_total) = self.imp().collection.get().unwrap().stars();
-        self.set_star_counts(stars_reached as usize, stars_total as usize);
+        let loaded = self.collection().is_loaded();
+        self.imp().puzzle_stars_pill.set_visible(loaded);
+        self.imp().progress_bar.set_visible(loaded);
+        if loaded {
+            let row = self.row();
+            self.set_stars(&row.stars, row.all_stars_reached);
+            self.set_progress(row.progress_percent, &row.progress, &row.progress_breakdown);
+        }
+    }
+
+    /// Creates the data to show for the collection in its current state.
+    /// The collection is not loaded by this, so the stars and the progress are only correct, if
+    /// it is loaded already.
+    fn row(&self) -> CollectionRow {
+        let collection = self.imp().collection.get().unwrap();
+        let (stars, solved) = if collection.is_loaded() {
+            let solved: Vec<bool> = collection
+                .puzzles()
+                .iter()
+                .map(|puzzle| puzzle.is_solved_default())
+                .collect();
+            (collection.stars(), solved)
+        } else {
+            ((0, 0), Vec::new())
+        };
+        CollectionRow::new(collection.summary(), self.imp().core.get(), stars, &solved)
     }
 
     fn set_name(&self, name: &str) {
         self.imp().name.set_text(name);
     }
 
-    fn set_star_counts(&self, solved: usize, total: usize) {
-        self.imp()
-            .puzzle_stars_pill
-            .set_label(format!("{} / {}", solved, total));
-        self.imp().puzzle_stars_pill.highlight(solved == total);
+    fn set_stars(&self, stars: &str, all_stars_reached: bool) {
+        self.imp().puzzle_stars_pill.set_label(stars);
+        self.imp().puzzle_stars_pill.highlight(all_stars_reached);
     }
 
-    fn set_difficulty(&self, difficulty: Option<PuzzleDifficultyConfig>) {
+    fn set_progress(&self, percent: u32, label: &str, breakdown: &str) {
+        let progress_bar = &self.imp().progress_bar;
+        progress_bar.set_fraction(percent as f64 / 100.0);
+        progress_bar.set_text(Some(label));
+        progress_bar.set_tooltip_text(Some(breakdown));
+    }
+
+    fn set_difficulty(&self, difficulty: Option<String>) {
         if let Some(difficulty) = difficulty {
-            let text: String = difficulty.into();
-            self.imp().difficulty_pill.set_label(text);
+            self.imp().difficulty_pill.set_label(difficulty);
             if self.imp().difficulty_pill.parent().is_none() {
                 self.imp()
                     .info_box
@@ -162,6 +243,11 @@ impl CollectionSelectionItem {
         }
     }
 
+    fn set_verified(&self, verified: bool) {
+        self.imp().verified_pill.set_visible(verified);
+        self.imp().verified_pill.highlight(verified);
+    }
+
     fn set_version(&self, version: &Option<String>) {
         if let Some(version) = version {
             self.imp().version_pill.set_label(version.as_str());
@@ -175,6 +261,16 @@ impl CollectionSelectionItem {
         }
     }
 
+    /// Lets the author choose the file of the collection to reload it, whenever it changes.
+    fn watch_collection_file(&self) {
+        if let Some(page) = self
+            .ancestor(CollectionSelectionPage::static_type())
+            .and_downcast::<CollectionSelectionPage>()
+        {
+            page.show_watch_collection_dialog(self.collection());
+        }
+    }
+
     fn show_delete_button(&self, show: bool) {
         if show {
             if self.imp().delete_button.get().parent().is_none() {
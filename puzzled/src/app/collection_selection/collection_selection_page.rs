@@ -1,31 +1,45 @@
 use crate::app::collection_selection::collection_selection_item::CollectionSelectionItem;
 use crate::model::collection::CollectionModel;
+use crate::model::featured;
+use crate::model::featured::FeaturedPuzzle;
+use crate::model::puzzle::PuzzleModel;
 use crate::model::store::with_puzzle_collection_store;
 use crate::window::PuzzledWindow;
 use adw::gio;
-use adw::prelude::{Cast, ObjectExt};
+use adw::prelude::{ActionRowExt, Cast, ObjectExt};
 use adw::subclass::prelude::*;
 use gtk::glib;
+use gtk::glib::ControlFlow;
 use gtk::prelude::WidgetExt;
 use log::debug;
+use std::collections::VecDeque;
 
 const COLLECTION_SELECTED_SIGNAL_NAME: &str = "collection-selected";
 const RANDOM_SELECTED_SIGNAL_NAME: &str = "random-selected";
+const FEATURED_SELECTED_SIGNAL_NAME: &str = "featured-selected";
 
 mod imp {
-    use super::{COLLECTION_SELECTED_SIGNAL_NAME, RANDOM_SELECTED_SIGNAL_NAME};
+    use super::{
+        COLLECTION_SELECTED_SIGNAL_NAME, FEATURED_SELECTED_SIGNAL_NAME, RANDOM_SELECTED_SIGNAL_NAME,
+    };
     use crate::model::collection::CollectionModel;
+    use crate::model::puzzle::PuzzleModel;
     use crate::window::PuzzledWindow;
+    use adw::gio;
     use adw::glib::subclass::Signal;
     use adw::prelude::StaticType;
     use adw::subclass::prelude::*;
     use gtk::glib;
-    use std::cell::OnceCell;
+    use std::cell::{OnceCell, RefCell};
     use std::sync::OnceLock;
 
     #[derive(Debug, Default, gtk::CompositeTemplate)]
     #[template(resource = "/de/til7701/Puzzled/ui/page/collection-selection-page.ui")]
     pub struct PuzzledCollectionSelectionPage {
+        #[template_child]
+        pub featured_list: TemplateChild<gtk::ListBox>,
+        #[template_child]
+        pub featured_row: TemplateChild<adw::ActionRow>,
         #[template_child]
         pub extra_options_list: TemplateChild<gtk::ListBox>,
         #[template_child]
@@ -34,6 +48,8 @@ mod imp {
         pub community_collection_list: TemplateChild<gtk::ListBox>,
 
         pub window: OnceCell<PuzzledWindow>,
+        /// Watches the file of a community collection, which is being edited.
+        pub collection_monitor: RefCell<Option<gio::FileMonitor>>,
     }
 
     #[glib::object_subclass]
@@ -50,6 +66,9 @@ mod imp {
             klass.install_action("app.random_puzzle", None, |page, _, _| {
                 page.emit_random_selected();
             });
+            klass.install_action("app.featured_puzzle", None, |page, _, _| {
+                page.emit_featured_selected();
+            });
         }
 
         fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
@@ -66,6 +85,9 @@ mod imp {
                         .param_types([CollectionModel::static_type()])
                         .build(),
                     Signal::builder(RANDOM_SELECTED_SIGNAL_NAME).build(),
+                    Signal::builder(FEATURED_SELECTED_SIGNAL_NAME)
+                        .param_types([PuzzleModel::static_type()])
+                        .build(),
                 ]
             })
         }
@@ -101,6 +123,8 @@ impl CollectionSelectionPage {
     pub(super) fn setup(&self) {
         self.load_core_collections();
         self.load_community_collections();
+        self.setup_featured_puzzle();
+        load_collections_when_idle();
 
         self.imp().extra_options_list.connect_row_selected({
             let self_clone = self.clone();
@@ -154,6 +178,36 @@ impl CollectionSelectionPage {
         });
     }
 
+    /// Shows the puzzle of the week and updates it, when its progress changes.
+    /// The banner is hidden, if there is no puzzle to feature.
+    fn setup_featured_puzzle(&self) {
+        let Some(featured) = featured::featured_puzzle() else {
+            self.imp().featured_list.set_visible(false);
+            return;
+        };
+        self.update_featured_puzzle(&featured);
+        featured.puzzle.collection().connect_progress_changed({
+            let self_clone = self.clone();
+            move || {
+                if let Some(featured) = featured::featured_puzzle() {
+                    self_clone.update_featured_puzzle(&featured);
+                }
+            }
+        });
+    }
+
+    fn update_featured_puzzle(&self, featured: &FeaturedPuzzle) {
+        let mut subtitle = format!(
+            "{} in {}",
+            featured.puzzle.config().name(),
+            featured.puzzle.collection().config().name()
+        );
+        if featured.is_solved() {
+            subtitle.push_str(" · Solved");
+        }
+        self.imp().featured_row.set_subtitle(&subtitle);
+    }
+
     fn load_community_collections(&self) {
         self.imp().community_collection_list.remove_all();
 
@@ -197,7 +251,7 @@ impl CollectionSelectionPage {
     fn emit_collection_selected(&self, collection: &CollectionModel) {
         debug!(
             "Emitting collection selected signal for collection: {}",
-            collection.config().id()
+            collection.summary().id()
         );
         self.emit_by_name::<()>(COLLECTION_SELECTED_SIGNAL_NAME, &[collection]);
     }
@@ -216,6 +270,28 @@ impl CollectionSelectionPage {
         self.emit_by_name::<()>(RANDOM_SELECTED_SIGNAL_NAME, &[]);
     }
 
+    /// The `featured-selected` signal is emitted, if the user wants to play the puzzle of the
+    /// week. The [PuzzlePage] should show the puzzle.
+    pub fn connect_featured_selected<F: Fn(&PuzzleModel) + 'static>(&self, callback: F) {
+        self.connect_local(FEATURED_SELECTED_SIGNAL_NAME, false, move |values| {
+            let puzzle = values[1]
+                .get::<PuzzleModel>()
+                .expect("Failed to get PuzzleModel from signal");
+            callback(&puzzle);
+            None
+        });
+    }
+
+    fn emit_featured_selected(&self) {
+        if let Some(featured) = featured::featured_puzzle() {
+            debug!(
+                "Emitting featured selected signal for puzzle: {}",
+                featured.puzzle.config().name()
+            );
+            self.emit_by_name::<()>(FEATURED_SELECTED_SIGNAL_NAME, &[&featured.puzzle]);
+        }
+    }
+
     /// Selects the last community collection in the list.
     ///
     /// The callee has to be sure that there is at least one community collection, otherwise this
@@ -245,3 +321,23 @@ impl CollectionSelectionPage {
         }
     }
 }
+
+/// Loads the collections one by one, while the main loop is idle, so their stars and progress
+/// are shown without delaying the startup.
+fn load_collections_when_idle() {
+    let mut collections: VecDeque<CollectionModel> = with_puzzle_collection_store(|store| {
+        store
+            .core_puzzle_collections()
+            .iter()
+            .chain(store.community_puzzle_collections())
+            .cloned()
+            .collect()
+    });
+    glib::idle_add_local(move || match collections.pop_front() {
+        Some(collection) => {
+            collection.load();
+            ControlFlow::Continue
+        }
+        None => ControlFlow::Break,
+    });
+}
@@ -0,0 +1,72 @@
+use crate::app::collection_selection::collection_selection_item::CollectionSelectionItem;
+use crate::model::store::with_puzzle_collection_store;
+use adw::gio::{Cancellable, File, FileCreateFlags};
+use adw::prelude::{AdwDialogExt, AlertDialogExt, FileExt};
+use adw::{AlertDialog, ResponseAppearance};
+use gtk::prelude::{CastNone, WidgetExt};
+use gtk::FileFilter;
+use log::{debug, error};
+
+impl CollectionSelectionItem {
+    /// Lets the user choose a file and writes the JSON, from which the collection was loaded, to
+    /// it. This allows sharing collections, which were imported before.
+    pub(super) fn show_export_collection_dialog(&self) {
+        let json_str = with_puzzle_collection_store(|store| {
+            store.collection_json(self.collection()).map(str::to_string)
+        });
+        let Some(json_str) = json_str else {
+            error!(
+                "No JSON found for collection {}",
+                self.collection().summary().id()
+            );
+            return;
+        };
+
+        debug!("Showing export collection dialog.");
+        let filter = FileFilter::new();
+        filter.set_name(Some("Puzzled Collection Files"));
+        filter.add_pattern("*.json");
+        let dialog = gtk::FileDialog::builder()
+            .default_filter(&filter)
+            .initial_name(format!("{}.json", self.collection().summary().id()))
+            .build();
+        let window = self.root().and_downcast::<gtk::Window>();
+        dialog.save(window.as_ref(), None::<&Cancellable>, {
+            let self_clone = self.clone();
+            move |result| match result {
+                Ok(file) => self_clone.export_collection(&file, &json_str),
+                Err(error) => {
+                    debug!("File dialog error: {:?}", error);
+                }
+            }
+        });
+    }
+
+    fn export_collection(&self, file: &File, json_str: &str) {
+        let result = file.replace_contents(
+            json_str.as_bytes(),
+            None,
+            false,
+            FileCreateFlags::REPLACE_DESTINATION,
+            None::<&Cancellable>,
+        );
+        if let Err(e) = result {
+            error!("Failed to export collection: {}", e);
+            self.show_export_collection_error(e.to_string());
+        }
+    }
+
+    fn show_export_collection_error(&self, message: String) {
+        let dialog = AlertDialog::builder()
+            .heading("Export Failed")
+            .body(message)
+            .build();
+
+        let ok_id = "ok";
+        dialog.add_response(ok_id, "OK");
+        dialog.set_default_response(Some(ok_id));
+        dialog.set_close_response(ok_id);
+        dialog.set_response_appearance(ok_id, ResponseAppearance::Suggested);
+        dialog.present(Some(self));
+    }
+}
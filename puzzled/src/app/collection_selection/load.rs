@@ -1,5 +1,6 @@
 use crate::app::collection_selection::collection_selection_page::CollectionSelectionPage;
 use crate::config;
+use crate::model::collection::CollectionModel;
 use crate::model::store::with_puzzle_collection_store;
 use adw::gio::{Cancellable, File};
 use adw::prelude::{AdwDialogExt, AlertDialogExt, FileExtManual};
@@ -35,89 +36,51 @@ impl CollectionSelectionPage {
                 debug!("Successfully loaded collection.");
             }
             Err(e) => {
-                let message: String = match e {
-                    FileReadError(e) => e,
-                    ReadError::MissingVersion => "The `puzzled` field is missing.".to_string(),
-                    ReadError::MalformedVersion => "The `puzzled` field is malformed.".to_string(),
-                    ReadError::UnsupportedVersion => {
-                        format!(
-                            "The collection is requiring a higher version of Puzzled. Only version {} or lower is supported.",
-                            config::VERSION
-                        )
-                    }
-                    ReadError::JsonError(e) => {
-                        format!("The collection file could not be parsed correctly: {}", e)
-                    }
-                    ReadError::UnknownPredefinedTile { name } => {
-                        format!(
-                            "The collection file contains an unknown predefined tile in puzzle '{}'.",
-                            name
-                        )
-                    }
-                    ReadError::UnknownCustomBoard {
-                        puzzle_name,
-                        board_name,
-                    } => {
-                        format!(
-                            "The collection file contains an unknown custom board '{}' in puzzle '{}'.",
-                            board_name, puzzle_name
-                        )
-                    }
-                    ReadError::TileWidthOrHeightCannotBeZero => {
-                        "The collection file contains a tile with zero width or height.".to_string()
-                    }
-                    ReadError::BoardWidthOrHeightCannotBeZero => {
-                        "The collection file contains a board with zero width or height."
-                            .to_string()
-                    }
-                    ReadError::InvalidVersion(_) => {
-                        "The version in the `puzzled` field is invalid.".to_string()
-                    }
-                    ReadError::InvalidCollectionId(_) => {
-                        "The collection file contains an invalid collection ID.".to_string()
-                    }
-                    ReadError::InvalidColor { message } => {
-                        format!("The collection file contains an invalid color: {}", message)
-                    }
-                };
+                let message = read_error_message(e);
                 self.show_load_collection_error(message);
             }
         }
     }
 
     fn try_load_collection(&self, file: File) -> Result<(), ReadError> {
-        match file.load_contents(None::<&Cancellable>) {
-            Ok((bytes, _etag)) => match std::str::from_utf8(bytes.as_ref()) {
-                Ok(text) => {
-                    // Load collection
-                    let json_loader = crate::model::store::create_json_loader();
-                    let collection_config = json_loader.load_puzzle_collection(text)?;
-                    // Delete old collection if it exists
-                    with_puzzle_collection_store(|store| {
-                        store
-                            .find_community_collection_by_id(collection_config.id())
-                            .cloned()
-                    })
-                    .iter()
-                    .for_each(|collection| {
-                        collection.delete();
-                    });
-                    // Add new collection
-                    let new_collection = with_puzzle_collection_store(|store| {
-                        store.add_community_collection_from_string(collection_config, text)?;
-                        Ok(store.community_puzzle_collections().last().cloned())
-                    })?;
-                    self.add_community_collection(&new_collection.unwrap());
-                    self.select_last_community_collection();
-                    Ok(())
-                }
-                Err(e) => Err(FileReadError(format!("{}", e))),
-            },
-            Err(e) => Err(FileReadError(format!("{}", e))),
-        }
+        let text = read_collection_file(&file)?;
+        self.import_collection(&text)?;
+        Ok(())
+    }
+
+    /// Adds the collection to the community collections and selects it. A community collection
+    /// with the same id is replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `text`: the JSON of the collection
+    ///
+    /// returns: Result<CollectionModel, ReadError> the added collection
+    pub(super) fn import_collection(&self, text: &str) -> Result<CollectionModel, ReadError> {
+        // Load collection
+        let collection_config = crate::model::store::load_community_collection(text)?;
+        // Delete old collection if it exists
+        with_puzzle_collection_store(|store| {
+            store
+                .find_community_collection_by_id(collection_config.id())
+                .cloned()
+        })
+        .iter()
+        .for_each(|collection| {
+            collection.delete();
+        });
+        // Add new collection
+        let new_collection = with_puzzle_collection_store(|store| {
+            store.add_community_collection_from_string(collection_config, text)?;
+            Ok(store.community_puzzle_collections().last().cloned())
+        })?
+        .unwrap();
+        self.add_community_collection(&new_collection);
+        self.select_last_community_collection();
+        Ok(new_collection)
     }
 
-    fn show_load_collection_error(&self, message: String) {
+    pub(super) fn show_load_collection_error(&self, message: String) {
         let dialog = AlertDialog::builder()
             .heading("Error")
             .body(message)
@@ -131,3 +94,207 @@ impl CollectionSelectionPage {
         dialog.present(self.imp().window.get());
     }
 }
+
+/// Reads the content of a collection file.
+pub(super) fn read_collection_file(file: &File) -> Result<String, ReadError> {
+    let (bytes, _etag) = file
+        .load_contents(None::<&Cancellable>)
+        .map_err(|e| FileReadError(format!("{}", e)))?;
+    std::str::from_utf8(bytes.as_ref())
+        .map(str::to_string)
+        .map_err(|e| FileReadError(format!("{}", e)))
+}
+
+/// Returns a message for the player explaining why the collection could not be loaded.
+pub(crate) fn read_error_message(error: ReadError) -> String {
+    match error {
+        FileReadError(e) => e,
+        ReadError::MissingVersion => "The `puzzled` field is missing.".to_string(),
+        ReadError::MalformedVersion => "The `puzzled` field is malformed.".to_string(),
+        ReadError::UnsupportedVersion => {
+            format!(
+                "The collection is requiring a higher version of Puzzled. Only version {} or lower is supported.",
+                config::VERSION
+            )
+        }
+        ReadError::UnsupportedFormat(format) => {
+            format!(
+                "The collection uses format version {}, which is not supported by this version of Puzzled.",
+                format
+            )
+        }
+        ReadError::JsonError(e) => {
+            format!("The collection file could not be parsed correctly: {}", e)
+        }
+        ReadError::InvalidJson {
+            message,
+            path,
+            line,
+            column,
+        } => {
+            let path = if path.is_empty() { "/" } else { path.as_str() };
+            format!(
+                "The collection file could not be parsed correctly at line {}, column {} (`{}`): {}",
+                line, column, path, message
+            )
+        }
+        ReadError::UnknownPredefinedTile { name } => {
+            format!(
+                "The collection file contains an unknown predefined tile in puzzle '{}'.",
+                name
+            )
+        }
+        ReadError::UnknownCustomBoard {
+            puzzle_name,
+            board_name,
+        } => {
+            format!(
+                "The collection file contains an unknown custom board '{}' in puzzle '{}'.",
+                board_name, puzzle_name
+            )
+        }
+        ReadError::TileWidthOrHeightCannotBeZero => {
+            "The collection file contains a tile with zero width or height.".to_string()
+        }
+        ReadError::BoardWidthOrHeightCannotBeZero => {
+            "The collection file contains a board with zero width or height."
+                .to_string()
+        }
+        ReadError::InvalidCellCode { code, cell } => {
+            format!(
+                "The collection file contains a tile or board with the unknown cell value {} at ({}, {}).",
+                code, cell.0, cell.1
+            )
+        }
+        ReadError::InvalidBoardCount { puzzle_name } => {
+            format!(
+                "The puzzle '{}' must define either a board or a list of boards.",
+                puzzle_name
+            )
+        }
+        ReadError::AreaBoardInMultipleBoards { puzzle_name } => {
+            format!(
+                "The puzzle '{}' combines multiple boards, but only simple boards can be combined.",
+                puzzle_name
+            )
+        }
+        ReadError::AreaBoardSizeMismatch => {
+            "The collection file contains a board whose values do not match the size of its area layout.".to_string()
+        }
+        ReadError::UnknownArea { index, area_count } => {
+            format!(
+                "The collection file contains a board referencing area {}, but only {} areas are defined.",
+                index, area_count
+            )
+        }
+        ReadError::UnknownConstraintTile { puzzle_name, tile } => {
+            format!(
+                "The puzzle '{}' has a constraint for the tile '{}', which is not part of the puzzle.",
+                puzzle_name, tile
+            )
+        }
+        ReadError::ConstraintCellOffBoard { puzzle_name, cell } => {
+            format!(
+                "The puzzle '{}' has a constraint for the cell ({}, {}), which is not on the board.",
+                puzzle_name, cell.0, cell.1
+            )
+        }
+        ReadError::InvalidMonthNames { area_name, count } => {
+            format!(
+                "The area '{}' defines {} month names, but exactly 12 are required.",
+                area_name, count
+            )
+        }
+        ReadError::InvalidPaddedWidth { area_name, width } => {
+            format!(
+                "The area '{}' pads its values to {} digits, but at most {} are allowed.",
+                area_name,
+                width,
+                puzzle_config::MAX_PADDED_WIDTH
+            )
+        }
+        ReadError::ReferenceCycle { name } => {
+            format!(
+                "The collection file contains a tile or board '{}' referencing itself.",
+                name
+            )
+        }
+        ReadError::InvalidVersion(_) => {
+            "The version in the `puzzled` field is invalid.".to_string()
+        }
+        ReadError::InvalidCollectionId(_) => {
+            "The collection file contains an invalid collection ID.".to_string()
+        }
+        ReadError::InvalidPuzzleId(_) => {
+            "The collection file contains a puzzle with a blank ID.".to_string()
+        }
+        ReadError::DuplicatePuzzleId(id) => {
+            format!(
+                "The collection file contains multiple puzzles with the ID '{}'.",
+                id
+            )
+        }
+        ReadError::InvalidNamespace(namespace) => {
+            format!(
+                "The namespace '{}' of the collection is invalid. It must have the same format as a collection ID.",
+                namespace
+            )
+        }
+        ReadError::InvalidMusicUri(_) => {
+            "The collection file contains music with an unsupported URI. Only https and resource URIs are allowed.".to_string()
+        }
+        ReadError::InvalidColor { message } => {
+            format!("The collection file contains an invalid color: {}", message)
+        }
+        ReadError::ChecksumMismatch => {
+            "The checksum of the collection file does not match its content. It may have been modified.".to_string()
+        }
+        ReadError::InvalidSignature => {
+            "The signature of the collection file is invalid. It may have been modified.".to_string()
+        }
+        ReadError::TooManyPuzzles { count, max } => {
+            format!(
+                "The collection file contains {} puzzles. At most {} puzzles are supported.",
+                count, max
+            )
+        }
+        ReadError::TooManyCustomTiles { count, max } => {
+            format!(
+                "The collection file defines {} custom tiles. At most {} custom tiles are supported.",
+                count, max
+            )
+        }
+        ReadError::TooManyTiles {
+            puzzle_name,
+            count,
+            max,
+        } => {
+            format!(
+                "The puzzle '{}' contains {} tiles. At most {} tiles per puzzle are supported.",
+                puzzle_name, count, max
+            )
+        }
+        ReadError::TileTooLarge {
+            puzzle_name,
+            width,
+            height,
+            max,
+        } => {
+            format!(
+                "The puzzle '{}' contains a tile of size {} x {}. Tiles may be at most {} cells wide and high.",
+                puzzle_name, width, height, max
+            )
+        }
+        ReadError::BoardTooLarge {
+            puzzle_name,
+            width,
+            height,
+            max,
+        } => {
+            format!(
+                "The board of the puzzle '{}' has a size of {} x {}. Boards may be at most {} cells wide and high.",
+                puzzle_name, width, height, max
+            )
+        }
+    }
+}
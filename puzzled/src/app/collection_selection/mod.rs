@@ -1,3 +1,5 @@
 mod collection_selection_item;
 pub mod collection_selection_page;
-mod load;
+mod export;
+pub(crate) mod load;
+mod watch;
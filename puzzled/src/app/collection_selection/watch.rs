@@ -0,0 +1,128 @@
+use crate::app::collection_selection::collection_selection_page::CollectionSelectionPage;
+use crate::app::collection_selection::load::{read_collection_file, read_error_message};
+use crate::model::collection::CollectionModel;
+use crate::model::store::{create_json_loader, with_puzzle_collection_store};
+use adw::gio::{Cancellable, File, FileMonitorEvent, FileMonitorFlags};
+use adw::prelude::{FileExt, FileMonitorExt};
+use adw::subclass::prelude::ObjectSubclassIsExt;
+use gtk::FileFilter;
+use log::{debug, info};
+
+impl CollectionSelectionPage {
+    /// Lets the author choose the file of a community collection, which is being edited. The
+    /// collection is reloaded from the file now and every time the file changes on disk.
+    ///
+    /// Only one file is watched at a time. Watching another file stops watching the previous one.
+    pub(super) fn show_watch_collection_dialog(&self, collection: &CollectionModel) {
+        debug!("Showing watch collection dialog.");
+        let filter = FileFilter::new();
+        filter.set_name(Some("Puzzled Collection Files"));
+        filter.add_pattern("*.json");
+        let dialog = gtk::FileDialog::builder()
+            .title("Watch Collection File")
+            .default_filter(&filter)
+            .build();
+        dialog.open(self.imp().window.get(), None::<&Cancellable>, {
+            let self_clone = self.clone();
+            let collection_id = collection.summary().id().to_string();
+            move |result| match result {
+                Ok(file) => self_clone.watch_collection_file(&file, collection_id),
+                Err(error) => {
+                    debug!("File dialog error: {:?}", error);
+                }
+            }
+        });
+    }
+
+    fn watch_collection_file(&self, file: &File, collection_id: String) {
+        self.stop_watching_collection_file();
+        let monitor = match file.monitor_file(FileMonitorFlags::NONE, None::<&Cancellable>) {
+            Ok(monitor) => monitor,
+            Err(e) => {
+                self.show_load_collection_error(format!("The file cannot be watched: {}", e));
+                return;
+            }
+        };
+        info!("Watching {} for changes", file.uri());
+        monitor.connect_changed({
+            let self_clone = self.clone();
+            let collection_id = collection_id.clone();
+            move |_, file, _, event| {
+                if matches!(
+                    event,
+                    FileMonitorEvent::ChangesDoneHint | FileMonitorEvent::Created
+                ) {
+                    self_clone.reload_watched_collection(file, &collection_id);
+                }
+            }
+        });
+        self.imp().collection_monitor.replace(Some(monitor));
+        self.reload_watched_collection(file, &collection_id);
+    }
+
+    fn stop_watching_collection_file(&self) {
+        if let Some(monitor) = self.imp().collection_monitor.take() {
+            monitor.cancel();
+        }
+    }
+
+    /// Replaces the collection with the content of the watched file. If a puzzle of the
+    /// collection is open, the puzzle with the same id is opened in the reloaded collection.
+    ///
+    /// Watching stops, when the collection was deleted or the file contains another collection.
+    fn reload_watched_collection(&self, file: &File, collection_id: &str) {
+        let exists = with_puzzle_collection_store(|store| {
+            store
+                .find_community_collection_by_id(collection_id)
+                .is_some()
+        });
+        if !exists {
+            debug!("Watched collection {} was deleted", collection_id);
+            self.stop_watching_collection_file();
+            return;
+        }
+        let text = match read_collection_file(file) {
+            Ok(text) => text,
+            Err(e) => {
+                self.show_load_collection_error(read_error_message(e));
+                return;
+            }
+        };
+        if let Ok(summary) = create_json_loader().load_collection_summary(&text)
+            && summary.id() != collection_id
+        {
+            self.stop_watching_collection_file();
+            self.show_load_collection_error(format!(
+                "The file contains the collection '{}' instead of '{}'. It is no longer watched.",
+                summary.id(),
+                collection_id
+            ));
+            return;
+        }
+
+        let window = self.imp().window.get();
+        let open_puzzle_id = window
+            .and_then(|window| window.puzzle_area_nav_page().puzzle())
+            .filter(|puzzle| puzzle.collection().summary().id() == collection_id)
+            .map(|puzzle| puzzle.config().id().clone());
+        let shows_puzzle = window.is_some_and(|window| window.outer_view().shows_content());
+
+        match self.import_collection(&text) {
+            Ok(collection) => {
+                info!("Reloaded watched collection {}", collection_id);
+                let puzzle = open_puzzle_id.and_then(|puzzle_id| {
+                    collection
+                        .puzzles()
+                        .iter()
+                        .find(|puzzle| puzzle.config().id() == &puzzle_id)
+                        .cloned()
+                });
+                if let (Some(window), Some(puzzle)) = (window, puzzle) {
+                    window.puzzle_area_nav_page().show_puzzle(&puzzle);
+                    window.outer_view().set_show_content(shows_puzzle);
+                }
+            }
+            Err(e) => self.show_load_collection_error(read_error_message(e)),
+        }
+    }
+}
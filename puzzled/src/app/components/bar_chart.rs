@@ -0,0 +1,91 @@
+use crate::app::presenter::insights::ChartBar;
+use gtk::cairo::Context;
+use gtk::prelude::*;
+use log::error;
+
+/// The height of a bar including the gap to the next one in pixels.
+const ROW_HEIGHT: i32 = 28;
+/// The gap between two bars in pixels.
+const BAR_GAP: f64 = 6.0;
+/// The part of the width, which is used for the labels in front of the bars.
+const LABEL_WIDTH_FRACTION: f64 = 0.3;
+const FONT_SIZE: f64 = 13.0;
+
+/// Creates a chart showing each value as a horizontal bar with its label in front of it.
+/// The longest bar fills the available width.
+///
+/// # Arguments
+///
+/// * `bars`: the values to show from top to bottom
+/// * `format_value`: formats a value to show at the end of its bar
+///
+/// returns: DrawingArea
+pub fn bar_chart(
+    bars: Vec<ChartBar>,
+    format_value: impl Fn(f64) -> String + 'static,
+) -> gtk::DrawingArea {
+    let chart = gtk::DrawingArea::builder()
+        .content_height(bars.len() as i32 * ROW_HEIGHT)
+        .hexpand(true)
+        .build();
+    chart.set_draw_func(move |area, cr, width, _| {
+        if let Err(e) = draw_bars(area, cr, width as f64, &bars, &format_value) {
+            error!("Failed to draw the chart: {}", e);
+        }
+    });
+    chart
+}
+
+fn draw_bars(
+    area: &gtk::DrawingArea,
+    cr: &Context,
+    width: f64,
+    bars: &[ChartBar],
+    format_value: &dyn Fn(f64) -> String,
+) -> Result<(), gtk::cairo::Error> {
+    let foreground = area.color();
+    let accent = adw::StyleManager::default().accent_color_rgba();
+    let max_value = bars.iter().map(|bar| bar.value).fold(0.0, f64::max);
+    let label_width = width * LABEL_WIDTH_FRACTION;
+    let bar_space = width - label_width;
+    cr.set_font_size(FONT_SIZE);
+
+    for (i, bar) in bars.iter().enumerate() {
+        let top = (i as i32 * ROW_HEIGHT) as f64;
+        let text_baseline = top + (ROW_HEIGHT as f64 + FONT_SIZE) / 2.0 - 2.0;
+
+        cr.set_source_rgba(
+            foreground.red() as f64,
+            foreground.green() as f64,
+            foreground.blue() as f64,
+            foreground.alpha() as f64,
+        );
+        cr.move_to(0.0, text_baseline);
+        cr.show_text(&bar.label)?;
+
+        let value_text = format_value(bar.value);
+        let value_width = cr.text_extents(&value_text)?.x_advance() + BAR_GAP;
+        let bar_width = if max_value > 0.0 {
+            (bar_space - value_width).max(0.0) * bar.value / max_value
+        } else {
+            0.0
+        };
+        cr.move_to(label_width + bar_width + BAR_GAP, text_baseline);
+        cr.show_text(&value_text)?;
+
+        cr.set_source_rgba(
+            accent.red() as f64,
+            accent.green() as f64,
+            accent.blue() as f64,
+            accent.alpha() as f64,
+        );
+        cr.rectangle(
+            label_width,
+            top + BAR_GAP / 2.0,
+            bar_width,
+            ROW_HEIGHT as f64 - BAR_GAP,
+        );
+        cr.fill()?;
+    }
+    Ok(())
+}
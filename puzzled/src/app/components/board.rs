@@ -1,19 +1,280 @@
+use crate::adw_ext;
 use crate::global::settings::{Preferences, ShowBoardGridLines};
+use adw::gdk::RGBA;
 use adw::gio;
 use adw::glib;
-use adw::prelude::Cast;
+use adw::prelude::GdkCairoContextExt;
 use adw::subclass::prelude::*;
 use gtk::prelude::*;
-use gtk::{Frame, Label, Widget};
+use gtk::{graphene, gsk, Widget};
+use ndarray::Array2;
 use puzzle_config::BoardConfig;
-use std::cell::Ref;
+use puzzled_common::shape::cell::CellShape;
+use puzzled_common::Shape;
 
-const SHOW_GRID_LINES_CLASS: &str = "show-grid-lines";
+const GRID_LINE_WIDTH: f32 = 1.0;
+const AREA_BOUNDARY_WIDTH: f32 = 3.0;
+const TARGET_SELECTION_WIDTH: f32 = 3.0;
+const TARGET_ANY_WIDTH: f32 = 2.0;
+/// The radius of the dot marking a cell with a tile constraint relative to the cell size.
+const TILE_CONSTRAINT_RADIUS: f32 = 0.12;
+/// The distance between the stripes of a hole in pixels.
+const HOLE_STRIPE_SPACING: f32 = 6.0;
+/// The size of the swatches in the legend of the areas in pixels.
+const AREA_SWATCH_SIZE: i32 = 16;
+
+/// The colors of the areas in the light style.
+/// Areas with a higher index than there are colors start again with the first color.
+/// Simple boards use the first color.
+const LIGHT_AREA_COLORS: [RGBA; 6] = [
+    adw_ext::LIGHT_2,
+    adw_ext::LIGHT_3,
+    adw_ext::LIGHT_4,
+    adw_ext::LIGHT_5,
+    adw_ext::DARK_1,
+    adw_ext::DARK_2,
+];
+/// The colors of the areas in the dark style. See [LIGHT_AREA_COLORS].
+const DARK_AREA_COLORS: [RGBA; 6] = [
+    gray(0x33),
+    gray(0x44),
+    gray(0x55),
+    gray(0x66),
+    gray(0x77),
+    gray(0x88),
+];
+
+const fn gray(value: u8) -> RGBA {
+    let value = value as f32 / 255.0;
+    RGBA::new(value, value, value, 1.0)
+}
+
+/// Returns the color of the cells of the area.
+pub fn area_color(area_index: i32, dark: bool) -> RGBA {
+    let colors = if dark {
+        &DARK_AREA_COLORS
+    } else {
+        &LIGHT_AREA_COLORS
+    };
+    colors[area_index.rem_euclid(colors.len() as i32) as usize]
+}
+
+/// Creates a small square in the color of the area to show in a legend.
+pub fn area_swatch(area_index: i32) -> gtk::DrawingArea {
+    let swatch = gtk::DrawingArea::builder()
+        .content_width(AREA_SWATCH_SIZE)
+        .content_height(AREA_SWATCH_SIZE)
+        .overflow(gtk::Overflow::Hidden)
+        .css_classes(["area-legend-swatch"])
+        .build();
+    swatch.set_draw_func(move |_, cr, width, height| {
+        cr.set_source_color(&area_color(
+            area_index,
+            adw::StyleManager::default().is_dark(),
+        ));
+        cr.rectangle(0.0, 0.0, width as f64, height as f64);
+        let _ = cr.fill();
+    });
+    swatch
+}
+
+/// A highlight drawn on top of a cell of the board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CellMark {
+    /// The cell is the selected target of its area.
+    TargetSelection,
+    /// Any cell of the area of this cell may be left empty.
+    TargetAny,
+    /// The cell is covered by the solver in the shown snapshot of its search.
+    SolverTried,
+    /// The cell is covered by the last placement pruned by the solver.
+    SolverPruned,
+    /// The cell has to be covered by a specific tile.
+    TileConstraint,
+}
+
+impl CellMark {
+    const fn bit(self) -> u8 {
+        1 << self as u8
+    }
+}
+
+/// What is drawn in a cell of the board before any marks.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+enum BoardCell {
+    /// The cell is not on the board.
+    #[default]
+    Outside,
+    /// The cell is not on the board, but enclosed by it. See [find_holes].
+    Hole,
+    Simple {
+        cell_shape: CellShape,
+    },
+    Area {
+        area_index: i32,
+        label: String,
+        /// The sides, on which a boundary to another area is drawn, in the order top, right,
+        /// bottom and left like the borders in GTK.
+        boundaries: [bool; 4],
+    },
+}
+
+impl BoardCell {
+    fn is_on_board(&self) -> bool {
+        matches!(self, BoardCell::Simple { .. } | BoardCell::Area { .. })
+    }
+}
+
+/// Returns the sides of the cell, which border a cell of another area, in the order top, right,
+/// bottom and left.
+/// Sides at the edge of the board have no boundary, since the edge is visible anyway.
+fn area_boundaries(area_indices: &Array2<i32>, (x, y): (usize, usize)) -> [bool; 4] {
+    let area = area_indices[(x, y)];
+    let neighbours = [
+        (x, y.wrapping_sub(1)),
+        (x + 1, y),
+        (x, y + 1),
+        (x.wrapping_sub(1), y),
+    ];
+    neighbours.map(|neighbour| {
+        area >= 0
+            && area_indices
+                .get(neighbour)
+                .is_some_and(|neighbour_area| *neighbour_area >= 0 && *neighbour_area != area)
+    })
+}
+
+/// Finds the cells, which are not on the board but enclosed by it.
+///
+/// Cells outside the board, which are connected to the edge of the layout by other cells
+/// outside the board, are not holes. All other cells outside the board are holes, since they are
+/// surrounded by the board.
+fn find_holes(layout: &Shape) -> Array2<bool> {
+    let (width, height) = layout.dim();
+    let mut holes = Array2::from_shape_fn((width, height), |index| !layout[index]);
+    let mut stack: Vec<(usize, usize)> = holes
+        .indexed_iter()
+        .filter(|((x, y), hole)| {
+            **hole && (*x == 0 || *y == 0 || *x == width - 1 || *y == height - 1)
+        })
+        .map(|(index, _)| index)
+        .collect();
+    while let Some((x, y)) = stack.pop() {
+        if !holes[(x, y)] {
+            continue;
+        }
+        holes[(x, y)] = false;
+        let neighbours = [
+            (x.wrapping_sub(1), y),
+            (x + 1, y),
+            (x, y.wrapping_sub(1)),
+            (x, y + 1),
+        ];
+        for neighbour in neighbours {
+            if *holes.get(neighbour).unwrap_or(&false) {
+                stack.push(neighbour);
+            }
+        }
+    }
+    holes
+}
+
+/// Determines what is drawn in each cell of the board.
+fn board_cells(board_config: &BoardConfig) -> Array2<BoardCell> {
+    let layout = board_config.layout();
+    let holes = find_holes(layout);
+    Array2::from_shape_fn(layout.dim(), |(x, y)| {
+        if !layout[(x, y)] {
+            return if holes[(x, y)] {
+                BoardCell::Hole
+            } else {
+                BoardCell::Outside
+            };
+        }
+        match board_config {
+            BoardConfig::Simple { .. } => BoardCell::Simple {
+                cell_shape: layout.cell_shape((x, y)),
+            },
+            BoardConfig::Area {
+                area_indices,
+                display_values,
+                show_area_boundaries,
+                ..
+            } => BoardCell::Area {
+                area_index: area_indices[(x, y)],
+                label: display_values[(x, y)].clone(),
+                boundaries: if *show_area_boundaries {
+                    area_boundaries(area_indices, (x, y))
+                } else {
+                    [false; 4]
+                },
+            },
+        }
+    })
+}
+
+/// Returns the bounds of the cell, when the board is drawn with the given size.
+/// The edges are rounded to whole pixels, so there are no gaps between the cells.
+fn cell_bounds(
+    (x, y): (usize, usize),
+    (dim_x, dim_y): (usize, usize),
+    (width, height): (i32, i32),
+) -> graphene::Rect {
+    let edge =
+        |index: usize, dim: usize, size: i32| (index as f32 * size as f32 / dim as f32).round();
+    let left = edge(x, dim_x, width);
+    let top = edge(y, dim_y, height);
+    graphene::Rect::new(
+        left,
+        top,
+        edge(x + 1, dim_x, width) - left,
+        edge(y + 1, dim_y, height) - top,
+    )
+}
+
+/// The colors of the current style, with which the cells are drawn.
+struct BoardStyle {
+    dark: bool,
+    foreground: RGBA,
+    accent: RGBA,
+    accent_bg: RGBA,
+    show_grid_lines: bool,
+}
+
+impl BoardStyle {
+    fn new(board_view: &BoardView) -> Self {
+        let style_manager = adw::StyleManager::default();
+        let dark = style_manager.is_dark();
+        BoardStyle {
+            dark,
+            foreground: board_view.color(),
+            accent: style_manager.accent_color().to_standalone_rgba(dark),
+            accent_bg: style_manager.accent_color_rgba(),
+            show_grid_lines: board_view.show_grid_lines(),
+        }
+    }
+
+    fn border(&self) -> RGBA {
+        self.foreground.with_alpha(0.15)
+    }
+
+    fn shade(&self) -> RGBA {
+        RGBA::new(0.0, 0.0, 0.0, if self.dark { 0.25 } else { 0.07 })
+    }
+
+    fn error_bg(&self) -> RGBA {
+        if self.dark {
+            adw_ext::ERROR_BG_DARK
+        } else {
+            adw_ext::ERROR_BG_LIGHT
+        }
+    }
+}
 
 mod imp {
     use super::*;
     use adw::glib::Properties;
-    use std::cell::RefCell;
+    use std::cell::{Cell, RefCell};
 
     #[derive(Debug, Default, Properties)]
     #[properties(wrapper_type = super::BoardView)]
@@ -21,14 +282,22 @@ mod imp {
         #[property(name = "show-grid-lines", get, set)]
         pub show_grid_lines: RefCell<bool>,
 
-        pub elements: RefCell<Vec<Widget>>,
+        pub(super) cells: RefCell<Array2<BoardCell>>,
+        /// The marks of each cell as bits. See [CellMark::bit].
+        pub marks: RefCell<Array2<u8>>,
+        /// The cells rendered for the last snapshot, so only changed cells are rendered again.
+        /// Cells, which have to be rendered again, are `None`.
+        pub rendered_cells: RefCell<Array2<Option<gsk::RenderNode>>>,
+        /// The size of the board, for which the cells were rendered.
+        pub rendered_size: Cell<(i32, i32)>,
+        pub style_handlers: RefCell<Vec<glib::SignalHandlerId>>,
     }
 
     #[glib::object_subclass]
     impl ObjectSubclass for PuzzledBoardView {
         const NAME: &'static str = "PuzzledBoardView";
         type Type = BoardView;
-        type ParentType = gtk::Grid;
+        type ParentType = Widget;
 
         fn class_init(_: &mut Self::Class) {}
 
@@ -36,112 +305,406 @@ mod imp {
     }
 
     #[glib::derived_properties]
-    impl ObjectImpl for PuzzledBoardView {}
-    impl WidgetImpl for PuzzledBoardView {}
-    impl GridImpl for PuzzledBoardView {}
+    impl ObjectImpl for PuzzledBoardView {
+        fn dispose(&self) {
+            let style_manager = adw::StyleManager::default();
+            for handler in self.style_handlers.take() {
+                style_manager.disconnect(handler);
+            }
+        }
+    }
+
+    impl WidgetImpl for PuzzledBoardView {
+        fn measure(&self, orientation: gtk::Orientation, _for_size: i32) -> (i32, i32, i32, i32) {
+            let (dim_x, dim_y) = self.cells.borrow().dim();
+            let (label_width, label_height) = self.obj().max_label_size();
+            let minimum = match orientation {
+                gtk::Orientation::Horizontal => dim_x as i32 * label_width,
+                _ => dim_y as i32 * label_height,
+            };
+            (minimum, minimum, -1, -1)
+        }
+
+        fn snapshot(&self, snapshot: &gtk::Snapshot) {
+            let obj = self.obj();
+            let size = (obj.width(), obj.height());
+            if self.rendered_size.replace(size) != size {
+                self.rendered_cells.borrow_mut().fill(None);
+            }
+            let cells = self.cells.borrow();
+            let marks = self.marks.borrow();
+            let mut rendered_cells = self.rendered_cells.borrow_mut();
+            let style = BoardStyle::new(&obj);
+            for ((x, y), cell) in cells.indexed_iter() {
+                // Cells outside the board draw nothing, so they are rendered again every time.
+                if rendered_cells[(x, y)].is_none() {
+                    let bounds = cell_bounds((x, y), cells.dim(), size);
+                    rendered_cells[(x, y)] = obj.render_cell(cell, marks[(x, y)], &bounds, &style);
+                }
+                if let Some(node) = &rendered_cells[(x, y)] {
+                    snapshot.append_node(node);
+                }
+            }
+        }
+    }
 }
 
 glib::wrapper! {
+    /// Shows the cells of a board.
+    ///
+    /// All cells are drawn by this single widget. Each cell is rendered once and reused until it
+    /// changes, so changing the marks of a few cells does not render the whole board again.
     pub struct BoardView(ObjectSubclass<imp::PuzzledBoardView>)
-        @extends Widget, gtk::Grid,
+        @extends Widget,
          @implements gtk::Buildable, gtk::Accessible, gtk::ConstraintTarget,
-                  gtk::Native, gio::ActionGroup, gio::ActionMap, gtk::Orientable;
+                  gtk::Native, gio::ActionGroup, gio::ActionMap;
 }
 
 impl BoardView {
     pub fn new(board_config: &BoardConfig) -> Result<BoardView, String> {
         let obj: BoardView = glib::Object::builder().build();
-
-        let board_layout = &board_config.layout();
-
         obj.add_css_class("board-grid");
-        obj.set_row_homogeneous(true);
-        obj.set_column_homogeneous(true);
-
-        let mut elements: Vec<Widget> = Vec::new();
-
-        for ((x, y), value) in board_layout.indexed_iter() {
-            let cell = if *value {
-                match board_config {
-                    BoardConfig::Simple { .. } => {
-                        let css_classes: Vec<String> =
-                            vec!["board-cell".to_string(), "board-cell-simple".to_string()];
-
-                        Frame::builder().css_classes(css_classes).build()
-                    }
-                    BoardConfig::Area {
-                        area_indices,
-                        display_values,
-                        ..
-                    } => {
-                        let css_classes: Vec<String> = vec![
-                            "board-cell".to_string(),
-                            format!("board-cell-{}", area_indices[[x, y]]),
-                        ];
-                        let cell = Frame::builder().css_classes(css_classes).build();
-
-                        let label = Label::new(Some(&display_values[[x, y]]));
-                        cell.set_child(Some(&label));
-                        cell
-                    }
-                }
-            } else {
-                let css_classes: Vec<String> =
-                    vec!["board-cell".to_string(), "board-cell-outside".to_string()];
-                Frame::builder().css_classes(css_classes).build()
-            };
-            obj.attach(&cell, x as i32, y as i32, 1, 1);
-            elements.push(cell.upcast::<Widget>());
-        }
-        obj.imp().elements.replace(elements);
+
+        let cells = board_cells(board_config);
+        let dim = cells.dim();
+        obj.imp().cells.replace(cells);
+        obj.imp().marks.replace(Array2::zeros(dim));
+        obj.imp().rendered_cells.replace(Array2::default(dim));
 
         let preferences = Preferences::default();
         preferences.bind(ShowBoardGridLines, &obj, "show-grid-lines");
-        obj.update_grid_lines(preferences.get(ShowBoardGridLines));
-        obj.connect_show_grid_lines_notify({
-            move |obj| {
-                let show_grid_lines = preferences.get(ShowBoardGridLines);
-                obj.update_grid_lines(show_grid_lines);
+        obj.connect_show_grid_lines_notify(|obj| obj.invalidate_cells());
+
+        let style_manager = adw::StyleManager::default();
+        let weak_obj = obj.downgrade();
+        let on_style_changed = move |_: &adw::StyleManager| {
+            if let Some(obj) = weak_obj.upgrade() {
+                obj.invalidate_cells();
             }
-        });
+        };
+        obj.imp().style_handlers.replace(vec![
+            style_manager.connect_dark_notify(on_style_changed.clone()),
+            style_manager.connect_accent_color_rgba_notify(on_style_changed),
+        ]);
 
         Ok(obj)
     }
 
-    pub fn elements(&self) -> Ref<'_, Vec<Widget>> {
-        self.imp().elements.borrow()
+    /// Adds the mark to the cell.
+    /// Positions outside the board are ignored.
+    pub fn add_mark(&self, position: (usize, usize), mark: CellMark) {
+        let mut marks = self.imp().marks.borrow_mut();
+        let Some(cell_marks) = marks.get_mut(position) else {
+            return;
+        };
+        if *cell_marks & mark.bit() == 0 {
+            *cell_marks |= mark.bit();
+            self.invalidate_cell(position);
+        }
     }
 
+    /// Removes the given marks from all cells.
+    pub fn clear_marks(&self, marks_to_clear: &[CellMark]) {
+        let bits = marks_to_clear
+            .iter()
+            .fold(0, |bits, mark| bits | mark.bit());
+        let mut marks = self.imp().marks.borrow_mut();
+        for (position, cell_marks) in marks.indexed_iter_mut() {
+            if *cell_marks & bits != 0 {
+                *cell_marks &= !bits;
+                self.invalidate_cell(position);
+            }
+        }
+    }
+
+    /// Returns the minimum size of a cell, so its label fits into it with some space around it.
     pub fn get_min_element_size(&self) -> u32 {
-        self.elements()
+        let (width, height) = self.max_label_size();
+        (width.max(height) as f64 * 1.4) as u32
+    }
+
+    /// Returns the largest width and height of the labels of the cells in pixels.
+    fn max_label_size(&self) -> (i32, i32) {
+        self.imp()
+            .cells
+            .borrow()
             .iter()
-            .map(|w| {
-                let frame: Option<&Frame> = w.downcast_ref::<Frame>();
-                frame
-                    .iter()
-                    .flat_map(|frame| frame.child())
-                    .flat_map(|child| child.downcast::<Label>())
-                    .map(|label| {
-                        let size = label
-                            .layout()
-                            .pixel_size()
-                            .0
-                            .max(label.layout().pixel_size().1);
-                        (size as f64 * 1.4) as u32
-                    })
-                    .next()
-                    .unwrap_or(0)
+            .filter_map(|cell| match cell {
+                BoardCell::Area { label, .. } => {
+                    Some(self.create_pango_layout(Some(label)).pixel_size())
+                }
+                _ => None,
+            })
+            .fold((0, 0), |(max_width, max_height), (width, height)| {
+                (max_width.max(width), max_height.max(height))
             })
-            .chain(0..1)
-            .max()
-            .unwrap_or(0)
     }
 
-    fn update_grid_lines(&self, show_grid_lines: bool) {
-        if show_grid_lines {
-            self.add_css_class(SHOW_GRID_LINES_CLASS);
+    /// Renders the cell again for the next snapshot.
+    fn invalidate_cell(&self, position: (usize, usize)) {
+        if let Some(rendered_cell) = self.imp().rendered_cells.borrow_mut().get_mut(position) {
+            *rendered_cell = None;
+        }
+        self.queue_draw();
+    }
+
+    /// Renders all cells again for the next snapshot.
+    fn invalidate_cells(&self) {
+        self.imp().rendered_cells.borrow_mut().fill(None);
+        self.queue_draw();
+    }
+
+    fn render_cell(
+        &self,
+        cell: &BoardCell,
+        marks: u8,
+        bounds: &graphene::Rect,
+        style: &BoardStyle,
+    ) -> Option<gsk::RenderNode> {
+        let snapshot = gtk::Snapshot::new();
+        let has_mark = |mark: CellMark| marks & mark.bit() != 0;
+        match cell {
+            BoardCell::Outside => {}
+            BoardCell::Hole => Self::draw_hole(&snapshot, bounds, style),
+            BoardCell::Simple { cell_shape } => {
+                let color = area_color(0, style.dark);
+                if cell_shape.is_half() {
+                    snapshot.append_fill(
+                        &cell_path(*cell_shape, bounds),
+                        gsk::FillRule::Winding,
+                        &color,
+                    );
+                } else {
+                    snapshot.append_color(&color, bounds);
+                }
+            }
+            BoardCell::Area { area_index, .. } => {
+                snapshot.append_color(&area_color(*area_index, style.dark), bounds);
+            }
+        }
+
+        if has_mark(CellMark::SolverTried) {
+            snapshot.append_color(&style.accent_bg.with_alpha(0.4), bounds);
+        }
+        if has_mark(CellMark::SolverPruned) {
+            snapshot.append_color(&style.error_bg().with_alpha(0.5), bounds);
+        }
+
+        if has_mark(CellMark::TileConstraint) {
+            Self::draw_tile_constraint(&snapshot, bounds, style);
+        }
+
+        if let BoardCell::Area { label, .. } = cell {
+            let layout = self.create_pango_layout(Some(label));
+            let (width, height) = layout.pixel_size();
+            snapshot.save();
+            snapshot.translate(&graphene::Point::new(
+                bounds.x() + ((bounds.width() - width as f32) / 2.0).round(),
+                bounds.y() + ((bounds.height() - height as f32) / 2.0).round(),
+            ));
+            snapshot.append_layout(&layout, &style.foreground);
+            snapshot.restore();
+        }
+
+        if has_mark(CellMark::TargetSelection) {
+            Self::draw_outline(&snapshot, bounds, TARGET_SELECTION_WIDTH, &[], style.accent);
+        } else if has_mark(CellMark::TargetAny) {
+            Self::draw_outline(
+                &snapshot,
+                bounds,
+                TARGET_ANY_WIDTH,
+                &[TARGET_ANY_WIDTH * 2.0],
+                style.accent,
+            );
+        } else if cell.is_on_board() {
+            Self::draw_borders(&snapshot, cell, bounds, style);
+        }
+        snapshot.to_node()
+    }
+
+    /// Draws stripes across the hole, so it can be told apart from the space around the board.
+    fn draw_hole(snapshot: &gtk::Snapshot, bounds: &graphene::Rect, style: &BoardStyle) {
+        snapshot.push_clip(bounds);
+        let stripes = gsk::PathBuilder::new();
+        let mut offset = -bounds.height();
+        while offset < bounds.width() {
+            stripes.move_to(bounds.x() + offset, bounds.y() + bounds.height());
+            stripes.line_to(bounds.x() + offset + bounds.height(), bounds.y());
+            offset += HOLE_STRIPE_SPACING;
+        }
+        snapshot.append_stroke(
+            &stripes.to_path(),
+            &gsk::Stroke::new(GRID_LINE_WIDTH),
+            &style.border(),
+        );
+        snapshot.pop();
+        snapshot.append_inset_shadow(
+            &gsk::RoundedRect::from_rect(*bounds, 0.0),
+            &style.shade(),
+            0.0,
+            0.0,
+            0.0,
+            4.0,
+        );
+    }
+
+    /// Draws the grid lines and the boundaries to other areas on the sides of the cell.
+    fn draw_borders(
+        snapshot: &gtk::Snapshot,
+        cell: &BoardCell,
+        bounds: &graphene::Rect,
+        style: &BoardStyle,
+    ) {
+        let boundaries = match cell {
+            BoardCell::Area { boundaries, .. } => *boundaries,
+            _ => [false; 4],
+        };
+        let grid_line_width = if style.show_grid_lines {
+            GRID_LINE_WIDTH
         } else {
-            self.remove_css_class(SHOW_GRID_LINES_CLASS);
+            0.0
+        };
+        let widths = boundaries.map(|boundary| {
+            if boundary {
+                AREA_BOUNDARY_WIDTH
+            } else {
+                grid_line_width
+            }
+        });
+        if widths.iter().all(|width| *width == 0.0) {
+            return;
         }
+        let colors = boundaries.map(|boundary| {
+            if boundary {
+                style.foreground
+            } else {
+                style.border()
+            }
+        });
+        snapshot.append_border(&gsk::RoundedRect::from_rect(*bounds, 0.0), &widths, &colors);
+    }
+
+    /// Draws a dot in the top left corner of the cell, so the label of the cell stays readable.
+    fn draw_tile_constraint(snapshot: &gtk::Snapshot, bounds: &graphene::Rect, style: &BoardStyle) {
+        let size = bounds.width().min(bounds.height());
+        let radius = size * TILE_CONSTRAINT_RADIUS;
+        let dot = gsk::PathBuilder::new();
+        dot.add_circle(
+            &graphene::Point::new(bounds.x() + 2.0 * radius, bounds.y() + 2.0 * radius),
+            radius,
+        );
+        snapshot.append_fill(&dot.to_path(), gsk::FillRule::Winding, &style.accent);
+    }
+
+    /// Draws a line of the given width along the inside of the cell.
+    /// The line is dashed, if a dash pattern is given.
+    fn draw_outline(
+        snapshot: &gtk::Snapshot,
+        bounds: &graphene::Rect,
+        width: f32,
+        dash: &[f32],
+        color: RGBA,
+    ) {
+        let inset = width / 2.0;
+        let outline = gsk::PathBuilder::new();
+        outline.add_rect(&graphene::Rect::new(
+            bounds.x() + inset,
+            bounds.y() + inset,
+            bounds.width() - width,
+            bounds.height() - width,
+        ));
+        let stroke = gsk::Stroke::new(width);
+        stroke.set_dash(dash);
+        snapshot.append_stroke(&outline.to_path(), &stroke, &color);
+    }
+}
+
+/// Creates the outline of a cell with the given shape filling the bounds.
+fn cell_path(cell_shape: CellShape, bounds: &graphene::Rect) -> gsk::Path {
+    let path = gsk::PathBuilder::new();
+    for (i, (corner_x, corner_y)) in cell_shape.corners().iter().enumerate() {
+        let x = bounds.x() + *corner_x as f32 * bounds.width();
+        let y = bounds.y() + *corner_y as f32 * bounds.height();
+        if i == 0 {
+            path.move_to(x, y);
+        } else {
+            path.line_to(x, y);
+        }
+    }
+    path.close();
+    path.to_path()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::arr2;
+    use puzzled_common::shape::shape_square;
+
+    #[test]
+    fn test_area_boundaries() {
+        let area_indices = arr2(&[[0, 0, -1], [1, 0, 1]]);
+
+        assert_eq!(
+            area_boundaries(&area_indices, (0, 0)),
+            [false, true, false, false]
+        );
+        assert_eq!(area_boundaries(&area_indices, (0, 1)), [false; 4]);
+        assert_eq!(area_boundaries(&area_indices, (0, 2)), [false; 4]);
+        assert_eq!(
+            area_boundaries(&area_indices, (1, 1)),
+            [true, false, true, false]
+        );
+    }
+
+    #[test]
+    fn test_find_holes() {
+        let layout = shape_square(&[
+            [false, true, true, true],
+            [true, true, false, true],
+            [true, true, true, true],
+        ]);
+
+        let holes = find_holes(&layout);
+
+        assert_eq!(
+            holes,
+            arr2(&[
+                [false, false, false, false],
+                [false, false, true, false],
+                [false, false, false, false],
+            ])
+        );
+    }
+
+    #[test]
+    fn test_cell_bounds() {
+        let bounds = cell_bounds((1, 0), (3, 2), (100, 50));
+
+        assert_eq!(bounds.x(), 33.0);
+        assert_eq!(bounds.y(), 0.0);
+        assert_eq!(bounds.width(), 34.0);
+        assert_eq!(bounds.height(), 25.0);
+    }
+
+    #[test]
+    fn test_area_color() {
+        assert_eq!(area_color(0, false), adw_ext::LIGHT_2);
+        assert_eq!(area_color(6, false), adw_ext::LIGHT_2);
+        assert_eq!(area_color(1, true), gray(0x44));
+    }
+
+    #[test]
+    fn test_cell_mark_bits_are_distinct() {
+        let marks = [
+            CellMark::TargetSelection,
+            CellMark::TargetAny,
+            CellMark::SolverTried,
+            CellMark::SolverPruned,
+            CellMark::TileConstraint,
+        ];
+        let bits = marks.iter().fold(0, |bits, mark| bits | mark.bit());
+
+        assert_eq!(bits.count_ones(), marks.len() as u32);
     }
 }
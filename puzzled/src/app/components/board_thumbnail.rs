@@ -0,0 +1,136 @@
+use crate::app::components::board::area_color;
+use crate::app::components::tile;
+use adw::gdk;
+use adw::gdk::RGBA;
+use adw::glib;
+use adw::prelude::GdkCairoContextExt;
+use gtk::cairo::{Context, Format, ImageSurface};
+use log::error;
+use ndarray::Array2;
+use puzzle_config::{BoardConfig, ColorConfig};
+use puzzled_common::Shape;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// How many pixels a cell should have in a board thumbnail.
+const THUMBNAIL_CELL_SIZE: i32 = 8;
+/// The gap between cells in a board thumbnail in pixels.
+const THUMBNAIL_CELL_GAP: f64 = 1.0;
+
+/// Everything a thumbnail is rendered from.
+/// Thumbnails are cached by their content instead of the puzzle, so a reloaded collection or a
+/// rotated board gets a new thumbnail.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct ThumbnailKey {
+    layout: Shape,
+    /// The index of the area of each cell. `None` for simple boards.
+    area_indices: Option<Array2<i32>>,
+    dark: bool,
+    palette: Vec<ColorConfig>,
+}
+
+thread_local! {
+    /// Thumbnails rendered so far.
+    static THUMBNAIL_CACHE: RefCell<HashMap<ThumbnailKey, gdk::Texture>> =
+        RefCell::new(HashMap::new());
+}
+
+/// Returns a small image of the shape of the given board.
+///
+/// Cells are drawn in the color of their area in the current style like on the board. If the
+/// collection defines a palette, its colors are used for the areas instead.
+/// The thumbnail is rendered offscreen with cairo the first time it is requested and cached
+/// afterward.
+///
+/// # Arguments
+///
+/// * `board_config`: the board to render
+/// * `palette`: the palette of the collection, empty if it has none
+///
+/// returns: Option<Texture>
+pub fn board_thumbnail(
+    board_config: &BoardConfig,
+    palette: &[ColorConfig],
+) -> Option<gdk::Texture> {
+    let key = ThumbnailKey {
+        layout: board_config.layout().clone(),
+        area_indices: match board_config {
+            BoardConfig::Simple { .. } => None,
+            BoardConfig::Area { area_indices, .. } => Some(area_indices.clone()),
+        },
+        dark: adw::StyleManager::default().is_dark(),
+        palette: palette.to_vec(),
+    };
+    if let Some(texture) = THUMBNAIL_CACHE.with_borrow(|cache| cache.get(&key).cloned()) {
+        return Some(texture);
+    }
+
+    match render_thumbnail(&key) {
+        Ok(texture) => {
+            THUMBNAIL_CACHE.with_borrow_mut(|cache| cache.insert(key, texture.clone()));
+            Some(texture)
+        }
+        Err(e) => {
+            error!("Failed to render board thumbnail: {}", e);
+            None
+        }
+    }
+}
+
+/// Returns the color of the cells of the area.
+fn cell_color(key: &ThumbnailKey, area_index: i32) -> RGBA {
+    if key.palette.is_empty() {
+        return area_color(area_index, key.dark);
+    }
+    let color = key.palette[area_index.rem_euclid(key.palette.len() as i32) as usize];
+    RGBA::new(
+        color.red() as f32 / 255.0,
+        color.green() as f32 / 255.0,
+        color.blue() as f32 / 255.0,
+        color.alpha() as f32 / 255.0,
+    )
+}
+
+fn render_thumbnail(key: &ThumbnailKey) -> Result<gdk::Texture, String> {
+    let layout = &key.layout;
+    let (width, height) = layout.dim();
+    let width = width as i32 * THUMBNAIL_CELL_SIZE;
+    let height = height as i32 * THUMBNAIL_CELL_SIZE;
+
+    let mut surface =
+        ImageSurface::create(Format::ARgb32, width, height).map_err(|e| e.to_string())?;
+    {
+        let cr = Context::new(&surface).map_err(|e| e.to_string())?;
+        for ((x, y), on_board) in layout.indexed_iter() {
+            if *on_board {
+                let area_index = key
+                    .area_indices
+                    .as_ref()
+                    .and_then(|area_indices| area_indices.get((x, y)).copied())
+                    .unwrap_or(0);
+                cr.set_source_color(&cell_color(key, area_index));
+                tile::cell_path(
+                    &cr,
+                    layout.cell_shape((x, y)),
+                    (x as i32 * THUMBNAIL_CELL_SIZE) as f64 + THUMBNAIL_CELL_GAP / 2.0,
+                    (y as i32 * THUMBNAIL_CELL_SIZE) as f64 + THUMBNAIL_CELL_GAP / 2.0,
+                    THUMBNAIL_CELL_SIZE as f64 - THUMBNAIL_CELL_GAP,
+                    THUMBNAIL_CELL_SIZE as f64 - THUMBNAIL_CELL_GAP,
+                );
+                cr.fill().map_err(|e| e.to_string())?;
+            }
+        }
+    }
+    surface.flush();
+
+    let stride = surface.stride() as usize;
+    let data = surface.data().map_err(|e| e.to_string())?.to_vec();
+    let texture = gdk::MemoryTexture::new(
+        width,
+        height,
+        gdk::MemoryFormat::B8g8r8a8Premultiplied,
+        &glib::Bytes::from_owned(data),
+        stride,
+    );
+    Ok(texture.into())
+}
@@ -0,0 +1,26 @@
+use crate::app::presenter::markdown;
+use adw::gio::Cancellable;
+use adw::glib;
+use gtk::prelude::{CastNone, LabelExt, WidgetExt};
+use gtk::UriLauncher;
+use log::error;
+
+/// Shows the description of a collection or puzzle on the label.
+/// The description may contain Markdown, see [markdown::to_pango_markup].
+pub fn set_description(label: &gtk::Label, description: &str) {
+    label.set_markup(&markdown::to_pango_markup(description));
+}
+
+/// Opens links in the label with the [UriLauncher], which uses the OpenURI portal inside a
+/// sandbox. This should be called once for each label showing a description.
+pub fn open_links_with_launcher(label: &gtk::Label) {
+    label.connect_activate_link(|label, uri| {
+        let window = label.root().and_downcast::<gtk::Window>();
+        UriLauncher::new(uri).launch(window.as_ref(), None::<&Cancellable>, |result| {
+            if let Err(e) = result {
+                error!("Failed to open link: {}", e);
+            }
+        });
+        glib::Propagation::Stop
+    });
+}
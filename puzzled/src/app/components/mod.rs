@@ -1,5 +1,10 @@
+pub mod bar_chart;
 pub mod board;
+pub mod board_thumbnail;
+pub mod description;
 pub mod info_pill;
+pub mod preview;
+pub mod progress_menu;
 pub mod solved_dialog;
 pub mod stars;
 pub mod tile;
@@ -0,0 +1,126 @@
+use crate::app::components::board::BoardView;
+use crate::app::components::board_thumbnail;
+use crate::app::components::tile::TileView;
+use adw::prelude::ObjectExt;
+use gtk::prelude::{BoxExt, FixedExt, WidgetExt};
+use gtk::{Align, Fixed};
+use log::error;
+use puzzle_config::{BoardConfig, ColorConfig, TileConfig};
+use std::cell::RefCell;
+
+/// How many pixels a cell should have in the preview of tiles and boards.
+/// This is NOT the total size of the preview.
+const PREVIEW_CELL_SIZE: f64 = 20.0;
+
+/// Adds small views of the given tiles to the fixed container.
+///
+/// The tiles are placed next to each other and vertically centered.
+///
+/// # Arguments
+///
+/// * `tiles`: the tiles to show
+/// * `fixed`: the container to add the tiles to
+pub fn create_tiles_preview(tiles: &[TileConfig], fixed: &Fixed) {
+    let max_tile_cell_height = tiles
+        .iter()
+        .map(|tile| tile.base().dim().1)
+        .max()
+        .unwrap_or(1) as i32;
+    let mut current_x_offset_cells = 0;
+
+    for (i, tile) in tiles.iter().enumerate() {
+        let tile_view = TileView::new(i, tile.base().clone(), tile.color());
+
+        let tile_height = tile.base().dim().1 as i32;
+        let y_offset = (max_tile_cell_height - tile_height) as f64 / 2.0;
+
+        fixed.put(
+            &tile_view,
+            current_x_offset_cells as f64 * PREVIEW_CELL_SIZE,
+            y_offset * PREVIEW_CELL_SIZE,
+        );
+        tile_view.set_width_request((PREVIEW_CELL_SIZE * tile.base().dim().0 as f64) as i32);
+        tile_view.set_height_request((PREVIEW_CELL_SIZE * tile.base().dim().1 as f64) as i32);
+        let tile_width = tile.base().dim().0;
+        let next_x_offset = current_x_offset_cells + tile_width + 1;
+        current_x_offset_cells = next_x_offset;
+    }
+}
+
+/// Adds a small view of the given board to the box.
+///
+/// # Arguments
+///
+/// * `board`: the board to show
+/// * `preview_box`: the box to add the board to
+pub fn create_board_preview(board: &BoardConfig, preview_box: &gtk::Box) {
+    let board_view = BoardView::new(board);
+
+    match board_view {
+        Ok(bv) => {
+            bv.set_property("halign", Align::Center);
+            preview_box.append(&bv);
+
+            let min_element_width = bv.get_min_element_size();
+            let size_per_cell = PREVIEW_CELL_SIZE.max(min_element_width as f64);
+
+            bv.set_width_request(size_per_cell as i32 * board.layout().dim().0 as i32);
+            bv.set_height_request(size_per_cell as i32 * board.layout().dim().1 as i32);
+        }
+        Err(e) => {
+            error!("Failed to create board preview: {}", e);
+        }
+    }
+}
+
+/// Adds a cached thumbnail of the shape of the given board to the box.
+/// This is cheaper than `create_board_preview` but does not show the values of the cells.
+///
+/// # Arguments
+///
+/// * `board`: the board to show
+/// * `palette`: the palette of the collection, empty if it has none
+/// * `preview_box`: the box to add the thumbnail to
+pub fn create_board_thumbnail(
+    board: &BoardConfig,
+    palette: &[ColorConfig],
+    preview_box: &gtk::Box,
+) {
+    let picture = board_thumbnail_picture(board, palette);
+    picture.set_halign(Align::Center);
+    preview_box.append(&picture);
+}
+
+/// Creates a picture showing the cached thumbnail of the given board.
+/// The thumbnail is replaced when switching between the light and dark style.
+///
+/// # Arguments
+///
+/// * `board`: the board to show
+/// * `palette`: the palette of the collection, empty if it has none
+///
+/// returns: Picture
+pub fn board_thumbnail_picture(board: &BoardConfig, palette: &[ColorConfig]) -> gtk::Picture {
+    let picture = gtk::Picture::new();
+    picture.set_can_shrink(false);
+    picture.set_paintable(board_thumbnail::board_thumbnail(board, palette).as_ref());
+
+    let style_manager = adw::StyleManager::default();
+    let handler = style_manager.connect_dark_notify({
+        let picture = picture.downgrade();
+        let board = board.clone();
+        let palette = palette.to_vec();
+        move |_| {
+            if let Some(picture) = picture.upgrade() {
+                picture.set_paintable(board_thumbnail::board_thumbnail(&board, &palette).as_ref());
+            }
+        }
+    });
+    let handler = RefCell::new(Some(handler));
+    picture.connect_destroy(move |_| {
+        if let Some(handler) = handler.take() {
+            style_manager.disconnect(handler);
+        }
+    });
+    picture
+}
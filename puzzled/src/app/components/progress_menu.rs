@@ -0,0 +1,128 @@
+use adw::gdk::{Rectangle, BUTTON_SECONDARY};
+use adw::gio::{Menu, SimpleAction, SimpleActionGroup};
+use adw::prelude::{AdwDialogExt, AlertDialogExt};
+use adw::{AlertDialog, ResponseAppearance};
+use gettextrs::gettext;
+use gtk::prelude::*;
+use gtk::{EventSequenceState, GestureClick, GestureLongPress, PopoverMenu, Widget};
+use std::rc::Rc;
+
+/// Adds a context menu to the row to mark puzzles as solved or unsolved.
+/// It is opened with a secondary click or a long press. Both actions are only applied after the
+/// user confirmed them.
+///
+/// The returned menu is parented to the row. The row has to present it, when it is allocated,
+/// and unparent it, when it is disposed.
+///
+/// # Arguments
+///
+/// * `row`: the row to add the context menu to
+/// * `subject`: describes what is marked in the confirmation, e.g. "all puzzles of Tutorial"
+/// * `mark_solved`: marks the puzzles as solved
+/// * `mark_unsolved`: marks the puzzles as unsolved
+///
+/// returns: PopoverMenu
+pub fn add_progress_menu(
+    row: &impl IsA<Widget>,
+    subject: &str,
+    mark_solved: impl Fn() + 'static,
+    mark_unsolved: impl Fn() + 'static,
+) -> PopoverMenu {
+    let row = row.upcast_ref::<Widget>();
+
+    let action_group = SimpleActionGroup::new();
+    action_group.add_action(&confirmed_action(
+        "mark-solved",
+        row,
+        gettext("Mark {} as solved? No stars are reached, until they are solved.")
+            .replace("{}", subject),
+        gettext("Mark as Solved"),
+        ResponseAppearance::Suggested,
+        Rc::new(mark_solved),
+    ));
+    action_group.add_action(&confirmed_action(
+        "mark-unsolved",
+        row,
+        gettext("Mark {} as unsolved? The hints used are removed as well.").replace("{}", subject),
+        gettext("Mark as Unsolved"),
+        ResponseAppearance::Destructive,
+        Rc::new(mark_unsolved),
+    ));
+    row.insert_action_group("progress", Some(&action_group));
+
+    let menu = Menu::new();
+    menu.append(
+        Some(&gettext("Mark as Solved")),
+        Some("progress.mark-solved"),
+    );
+    menu.append(
+        Some(&gettext("Mark as Unsolved")),
+        Some("progress.mark-unsolved"),
+    );
+    let context_menu = PopoverMenu::from_model(Some(&menu));
+    context_menu.set_parent(row);
+    context_menu.set_has_arrow(false);
+
+    let click = GestureClick::new();
+    click.set_button(BUTTON_SECONDARY);
+    click.connect_pressed({
+        let context_menu = context_menu.clone();
+        move |gesture, _, x, y| {
+            gesture.set_state(EventSequenceState::Claimed);
+            popup(&context_menu, x, y);
+        }
+    });
+    row.add_controller(click);
+
+    let long_press = GestureLongPress::new();
+    long_press.connect_pressed({
+        let context_menu = context_menu.clone();
+        move |gesture, x, y| {
+            gesture.set_state(EventSequenceState::Claimed);
+            popup(&context_menu, x, y);
+        }
+    });
+    row.add_controller(long_press);
+
+    context_menu
+}
+
+fn popup(context_menu: &PopoverMenu, x: f64, y: f64) {
+    context_menu.set_pointing_to(Some(&Rectangle::new(x as i32, y as i32, 1, 1)));
+    context_menu.popup();
+}
+
+/// Creates an action, which asks the user for confirmation before calling `apply`.
+fn confirmed_action(
+    name: &str,
+    row: &Widget,
+    body: String,
+    confirm_label: String,
+    appearance: ResponseAppearance,
+    apply: Rc<dyn Fn()>,
+) -> SimpleAction {
+    let action = SimpleAction::new(name, None);
+    action.connect_activate({
+        let row = row.clone();
+        move |_, _| {
+            let dialog = AlertDialog::builder()
+                .heading(gettext("Are you sure?"))
+                .body(&body)
+                .build();
+
+            let cancel_id = "cancel";
+            let confirm_id = "confirm";
+            dialog.add_response(cancel_id, &gettext("Cancel"));
+            dialog.add_response(confirm_id, &confirm_label);
+            dialog.set_close_response(cancel_id);
+            dialog.set_default_response(Some(confirm_id));
+            dialog.set_response_appearance(confirm_id, appearance);
+            dialog.connect_response(Some(confirm_id), {
+                let apply = apply.clone();
+                move |_, _| apply()
+            });
+            dialog.present(Some(&row));
+        }
+    });
+    action
+}
@@ -1,7 +1,20 @@
+use crate::app::presenter::solve_summary::SolveSummary;
 use crate::model::stars::Stars;
 use adw::gio;
+use adw::gio::Cancellable;
 use adw::glib;
+use adw::prelude::*;
 use adw::subclass::prelude::*;
+use gtk::cairo::{Context, Format, ImageSurface};
+use gtk::FileFilter;
+use log::{debug, error, info};
+use std::path::Path;
+use std::rc::Rc;
+
+/// The size of a cell in the miniature of the board in pixels.
+const PREVIEW_CELL_SIZE: f64 = 12.0;
+/// The size of a cell in an exported image in pixels.
+const EXPORT_CELL_SIZE: f64 = 32.0;
 
 mod imp {
     use super::*;
@@ -12,6 +25,16 @@ mod imp {
     pub struct PuzzledSolvedDialog {
         #[template_child]
         pub stars_view: TemplateChild<StarsView>,
+        #[template_child]
+        pub summary_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub board_preview: TemplateChild<gtk::DrawingArea>,
+        #[template_child]
+        pub share_box: TemplateChild<gtk::Box>,
+        #[template_child]
+        pub copy_position_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub export_image_button: TemplateChild<gtk::Button>,
     }
 
     #[glib::object_subclass]
@@ -51,4 +74,133 @@ impl SolvedDialog {
         let imp = self.imp();
         imp.stars_view.set_stars(stars);
     }
+
+    /// Shows the time and hints needed, a miniature of the final board and the buttons to share
+    /// it.
+    pub fn set_summary(&self, summary: SolveSummary) {
+        let imp = self.imp();
+        let summary = Rc::new(summary);
+
+        let text = match &summary.time {
+            Some(time) => format!("{} · {}", time, summary.hints),
+            None => summary.hints.clone(),
+        };
+        imp.summary_label.set_label(&text);
+        imp.summary_label.set_visible(true);
+
+        let (width, height) = board_size(&summary, PREVIEW_CELL_SIZE);
+        imp.board_preview.set_content_width(width);
+        imp.board_preview.set_content_height(height);
+        imp.board_preview.set_draw_func({
+            let summary = summary.clone();
+            move |_, cr, _, _| {
+                if let Err(e) = draw_board(cr, &summary, PREVIEW_CELL_SIZE) {
+                    error!("Failed to draw the solved board: {}", e);
+                }
+            }
+        });
+        imp.board_preview.set_visible(true);
+
+        imp.copy_position_button.connect_clicked({
+            let summary = summary.clone();
+            move |button| {
+                button.clipboard().set_text(&summary.position);
+                button.set_label("Copied");
+            }
+        });
+        imp.export_image_button.connect_clicked({
+            let self_clone = self.clone();
+            let summary = summary.clone();
+            move |_| self_clone.show_export_image_dialog(summary.clone())
+        });
+        imp.share_box.set_visible(true);
+    }
+
+    fn show_export_image_dialog(&self, summary: Rc<SolveSummary>) {
+        let filter = FileFilter::new();
+        filter.set_name(Some("PNG Images"));
+        filter.add_pattern("*.png");
+        let dialog = gtk::FileDialog::builder()
+            .title("Export Image")
+            .default_filter(&filter)
+            .initial_name("Solved Puzzle.png")
+            .build();
+        let window = self.root().and_downcast::<gtk::Window>();
+        dialog.save(window.as_ref(), None::<&Cancellable>, {
+            let button = self.imp().export_image_button.get();
+            move |result| match result {
+                Ok(file) => {
+                    let Some(path) = file.path() else {
+                        button.set_label("Export Failed");
+                        button.set_tooltip_text(Some("Only local files can be written."));
+                        return;
+                    };
+                    match export_image(&path, &summary) {
+                        Ok(()) => {
+                            info!("Exported the solved board to {}", path.display());
+                            button.set_label("Image Exported");
+                        }
+                        Err(e) => {
+                            error!("Failed to export the solved board: {}", e);
+                            button.set_label("Export Failed");
+                            button.set_tooltip_text(Some(&e));
+                        }
+                    }
+                }
+                Err(error) => {
+                    debug!("File dialog error: {:?}", error);
+                }
+            }
+        });
+    }
+}
+
+/// Writes the final board as a PNG image to the given path.
+fn export_image(path: &Path, summary: &SolveSummary) -> Result<(), String> {
+    let (width, height) = board_size(summary, EXPORT_CELL_SIZE);
+    let surface = ImageSurface::create(Format::ARgb32, width, height).map_err(|e| e.to_string())?;
+    {
+        let cr = Context::new(&surface).map_err(|e| e.to_string())?;
+        draw_board(&cr, summary, EXPORT_CELL_SIZE).map_err(|e| e.to_string())?;
+    }
+    let mut file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+    surface.write_to_png(&mut file).map_err(|e| e.to_string())
+}
+
+/// Returns the width and height of the board drawn with the given cell size.
+fn board_size(summary: &SolveSummary, cell_size: f64) -> (i32, i32) {
+    let (width, height) = summary.cells.dim();
+    (
+        (width as f64 * cell_size).ceil() as i32,
+        (height as f64 * cell_size).ceil() as i32,
+    )
+}
+
+/// Draws each cell of the board in the color of the tile covering it. Cells without a tile, like
+/// the cells of the target, stay transparent.
+fn draw_board(
+    cr: &Context,
+    summary: &SolveSummary,
+    cell_size: f64,
+) -> Result<(), gtk::cairo::Error> {
+    let gap = (cell_size / 16.0).max(1.0);
+    for ((x, y), tile) in summary.cells.indexed_iter() {
+        let Some(color) = tile.and_then(|tile| summary.tile_colors.get(tile)) else {
+            continue;
+        };
+        cr.set_source_rgba(
+            color.red() as f64 / 255.0,
+            color.green() as f64 / 255.0,
+            color.blue() as f64 / 255.0,
+            color.alpha() as f64 / 255.0,
+        );
+        cr.rectangle(
+            x as f64 * cell_size + gap / 2.0,
+            y as f64 * cell_size + gap / 2.0,
+            cell_size - gap,
+            cell_size - gap,
+        );
+        cr.fill()?;
+    }
+    Ok(())
 }
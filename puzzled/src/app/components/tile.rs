@@ -5,15 +5,21 @@ use adw::glib;
 use adw::prelude::GdkCairoContextExt;
 use adw::subclass::prelude::*;
 use gtk::cairo::Context;
-use gtk::prelude::{DrawingAreaExtManual, WidgetExt};
+use gtk::gdk::Rectangle;
+use gtk::prelude::{DrawingAreaExtManual, PopoverExt, WidgetExt};
 use ndarray::Array2;
 use puzzle_config::ColorConfig;
+use puzzled_common::shape::cell::CellShape;
 use puzzled_common::Shape;
 use std::cell::Ref;
 use std::collections::HashMap;
 
 const HIGHLIGHT_OVERLAPPING_COLOR: RGBA = adw_ext::ERROR_BG_LIGHT;
 const HIGHLIGHT_OUT_OF_BOUNDS_COLOR: RGBA = adw_ext::WARNING_BG_LIGHT;
+const HIGHLIGHT_ON_HOLE_COLOR: RGBA = adw_ext::BLUE_4;
+const HIGHLIGHT_CONFLICTING_COLOR: RGBA = adw_ext::PURPLE_3;
+/// The opacity of the tint drawn over the cells, so the color of the tile stays recognizable.
+const TINT_ALPHA: f32 = 0.6;
 
 /// Defines how a cell of a tile should be drawn, based on its state in the puzzle area.
 #[derive(Debug, Default, Clone, Hash, PartialEq, Eq)]
@@ -25,6 +31,53 @@ pub enum DrawingMode {
     Overlapping,
     /// Draw with a highlight indicating that this cell is out of bounds of the board
     OutOfBounds,
+    /// Draw with a highlight indicating that this cell covers a hole in the board
+    OnHole,
+    /// Draw with a highlight indicating that this tile likely prevents a solution
+    Conflicting,
+}
+
+/// Adds the outline of a cell with the given shape to the current path of the context.
+/// Full cells are added as a rectangle and half cells as a triangle.
+pub fn cell_path(cr: &Context, cell_shape: CellShape, x: f64, y: f64, width: f64, height: f64) {
+    for (i, (corner_x, corner_y)) in cell_shape.corners().iter().enumerate() {
+        let corner_x = x + corner_x * width;
+        let corner_y = y + corner_y * height;
+        if i == 0 {
+            cr.move_to(corner_x, corner_y);
+        } else {
+            cr.line_to(corner_x, corner_y);
+        }
+    }
+    cr.close_path();
+}
+
+/// Returns true, if the shape has a cell at the index, which is not a half cell.
+pub fn is_full_cell(shape: &Shape, index: (usize, usize)) -> bool {
+    *shape.get(index).unwrap_or(&false) && !shape.cell_shape(index).is_half()
+}
+
+/// Returns true, if the point lies in a filled part of the shape, when the shape is drawn with the
+/// given size.
+/// Points in empty cells or the empty half of half cells are not contained, so pointer events
+/// there reach the widgets below.
+fn shape_contains(shape: &Shape, x: f64, y: f64, width: f64, height: f64) -> bool {
+    if x < 0.0 || y < 0.0 || x >= width || y >= height {
+        return false;
+    }
+
+    let (dim_x, dim_y) = shape.dim();
+    let cell_width = width / dim_x as f64;
+    let cell_height = height / dim_y as f64;
+
+    let cell_x = (x / cell_width) as usize;
+    let cell_y = (y / cell_height) as usize;
+
+    *shape.get((cell_x, cell_y)).unwrap_or(&false)
+        && shape.cell_shape((cell_x, cell_y)).contains(
+            x / cell_width - cell_x as f64,
+            y / cell_height - cell_y as f64,
+        )
 }
 
 mod imp {
@@ -39,6 +92,9 @@ mod imp {
         pub current_rotation: RefCell<Shape>,
         pub color: RefCell<HashMap<DrawingMode, RGBA>>,
         pub drawing_modes: RefCell<Array2<DrawingMode>>,
+        /// A color drawn translucently over all cells, if set.
+        pub tint: Cell<Option<RGBA>>,
+        pub context_menu: RefCell<Option<gtk::PopoverMenu>>,
     }
 
     #[glib::object_subclass]
@@ -52,33 +108,30 @@ mod imp {
         fn instance_init(_: &glib::subclass::InitializingObject<Self>) {}
     }
 
-    impl ObjectImpl for PuzzledTileView {}
+    impl ObjectImpl for PuzzledTileView {
+        fn dispose(&self) {
+            if let Some(context_menu) = self.context_menu.take() {
+                context_menu.unparent();
+            }
+        }
+    }
     impl WidgetImpl for PuzzledTileView {
-        fn contains(&self, x: f64, y: f64) -> bool {
-            if x < 0.0 || y < 0.0 {
-                return false;
+        fn size_allocate(&self, width: i32, height: i32, baseline: i32) {
+            self.parent_size_allocate(width, height, baseline);
+            if let Some(context_menu) = self.context_menu.borrow().as_ref() {
+                context_menu.present();
             }
+        }
 
+        fn contains(&self, x: f64, y: f64) -> bool {
             let obj = self.obj();
-            let width = obj.width() as f64;
-            if x > width {
-                return false;
-            }
-            let height = obj.height() as f64;
-            if y > height {
-                return false;
-            }
-
-            let current_rotation = self.current_rotation.borrow();
-            let tile_dims = current_rotation.dim();
-
-            let cell_width = width / tile_dims.0 as f64;
-            let cell_height = height / tile_dims.1 as f64;
-
-            let cell_x = (x / cell_width) as usize;
-            let cell_y = (y / cell_height) as usize;
-
-            *current_rotation.get((cell_x, cell_y)).unwrap_or(&false)
+            shape_contains(
+                &self.current_rotation.borrow(),
+                x,
+                y,
+                obj.width() as f64,
+                obj.height() as f64,
+            )
         }
     }
     impl DrawingAreaImpl for PuzzledTileView {}
@@ -111,6 +164,24 @@ impl TileView {
         obj
     }
 
+    /// Sets the menu shown by [TileView::popup_context_menu].
+    pub fn set_context_menu(&self, menu: &gio::MenuModel) {
+        let context_menu = gtk::PopoverMenu::from_model(Some(menu));
+        context_menu.set_parent(self);
+        context_menu.set_has_arrow(false);
+        if let Some(old) = self.imp().context_menu.replace(Some(context_menu)) {
+            old.unparent();
+        }
+    }
+
+    /// Shows the context menu pointing to the given position relative to this tile.
+    pub fn popup_context_menu(&self, x: f64, y: f64) {
+        if let Some(context_menu) = self.imp().context_menu.borrow().as_ref() {
+            context_menu.set_pointing_to(Some(&Rectangle::new(x as i32, y as i32, 1, 1)));
+            context_menu.popup();
+        }
+    }
+
     fn init_color(&self, color: ColorConfig) {
         let color = RGBA::new(
             (color.red() as f64 / 255.0) as f32,
@@ -123,6 +194,8 @@ impl TileView {
         color_map.insert(DrawingMode::Normal, color);
         color_map.insert(DrawingMode::Overlapping, color.with_alpha(0.5));
         color_map.insert(DrawingMode::OutOfBounds, color.with_alpha(0.5));
+        color_map.insert(DrawingMode::OnHole, color.with_alpha(0.5));
+        color_map.insert(DrawingMode::Conflicting, color);
         self.imp().color.replace(color_map);
     }
 
@@ -138,38 +211,50 @@ impl TileView {
                 let cell_x = x as f64 * cell_width;
                 let cell_y = y as f64 * cell_height;
 
+                let cell_shape = current_rotation.cell_shape((x, y));
                 let drawing_mode = &drawing_modes[(x, y)];
                 let color = &color_map[drawing_mode];
                 cr.set_source_color(color);
-                cr.rectangle(cell_x, cell_y, cell_width, cell_height);
+                cell_path(cr, cell_shape, cell_x, cell_y, cell_width, cell_height);
                 cr.fill().expect("Failed to fill");
                 // Due to floating point inaccuracies, there might be 2px gaps between cells, so
                 // additional rectangles are drawn to fill those gaps if the adjacent cells are filled.
                 // This only solves the problem, if the color is not transparent, otherwise there
                 // would be visible lines between the cells of the tile.
-                if color.alpha() == 1.0 {
-                    if *current_rotation.get((x + 1, y)).unwrap_or(&false) {
+                // Half cells do not share a full edge with their neighbours, so they are skipped.
+                if color.alpha() == 1.0 && !cell_shape.is_half() {
+                    if is_full_cell(&current_rotation, (x + 1, y)) {
                         cr.rectangle(cell_x + cell_width - 1.0, cell_y, 2.0, cell_height);
                         cr.fill().expect("Failed to fill");
                     }
-                    if *current_rotation.get((x, y + 1)).unwrap_or(&false) {
+                    if is_full_cell(&current_rotation, (x, y + 1)) {
                         cr.rectangle(cell_x, cell_y + cell_height - 1.0, cell_width, 2.0);
                         cr.fill().expect("Failed to fill");
                     }
                 }
 
+                if let Some(tint) = self.imp().tint.get() {
+                    cr.set_source_color(&tint.with_alpha(TINT_ALPHA));
+                    cell_path(cr, cell_shape, cell_x, cell_y, cell_width, cell_height);
+                    cr.fill().expect("Failed to fill");
+                }
+
                 // Border
                 let border_color = match drawing_mode {
                     DrawingMode::Normal => None,
                     DrawingMode::Overlapping => Some(HIGHLIGHT_OVERLAPPING_COLOR),
                     DrawingMode::OutOfBounds => Some(HIGHLIGHT_OUT_OF_BOUNDS_COLOR),
+                    DrawingMode::OnHole => Some(HIGHLIGHT_ON_HOLE_COLOR),
+                    DrawingMode::Conflicting => Some(HIGHLIGHT_CONFLICTING_COLOR),
                 };
                 if let Some(border_color) = border_color {
                     cr.set_source_color(&border_color);
                     const BORDER_WIDTH: f64 = 3.0;
                     const HALF_BORDER_WIDTH: f64 = BORDER_WIDTH / 2.0;
                     cr.set_line_width(BORDER_WIDTH);
-                    cr.rectangle(
+                    cell_path(
+                        cr,
+                        cell_shape,
                         cell_x + HALF_BORDER_WIDTH,
                         cell_y + HALF_BORDER_WIDTH,
                         cell_width - BORDER_WIDTH,
@@ -225,6 +310,23 @@ impl TileView {
         self.queue_draw();
     }
 
+    /// Sets the drawing mode for all cells, which are drawn normally.
+    /// Cells with another highlight keep it.
+    pub fn set_drawing_mode_of_normal_cells(&self, drawing_mode: DrawingMode) {
+        self.imp().drawing_modes.borrow_mut().map_inplace(|mode| {
+            if *mode == DrawingMode::Normal {
+                *mode = drawing_mode.clone();
+            }
+        });
+        self.queue_draw();
+    }
+
+    /// Sets a color, which is drawn translucently over all cells of the tile, or removes it.
+    pub fn set_tint(&self, tint: Option<RGBA>) {
+        self.imp().tint.set(tint);
+        self.queue_draw();
+    }
+
     /// Resets the drawing mode for all cells to [DrawingMode::Normal].
     pub fn reset_drawing_modes(&self) {
         self.imp()
@@ -234,3 +336,22 @@ impl TileView {
         self.queue_draw();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use puzzled_common::shape::shape_square;
+
+    #[test]
+    fn test_shape_contains() {
+        let mut shape = shape_square(&[[true, true], [true, false]]);
+        shape.set_cell_shape((1, 0), CellShape::TopLeft);
+
+        assert!(shape_contains(&shape, 5.0, 15.0, 20.0, 20.0));
+        assert!(!shape_contains(&shape, 15.0, 15.0, 20.0, 20.0));
+        assert!(shape_contains(&shape, 11.0, 1.0, 20.0, 20.0));
+        assert!(!shape_contains(&shape, 18.0, 8.0, 20.0, 20.0));
+        assert!(!shape_contains(&shape, 20.0, 5.0, 20.0, 20.0));
+        assert!(!shape_contains(&shape, -1.0, 5.0, 20.0, 20.0));
+    }
+}
@@ -0,0 +1,253 @@
+use puzzle_config::BoardConfig;
+
+/// The tool used to draw on an [EditableBoardView](super::editable_board::EditableBoardView).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum BrushMode {
+    /// Adds or removes all cells in the rectangle spanned by the start and end of a stroke.
+    Rectangle,
+    /// Adds or removes all cells on the straight line from the start to the end of a stroke.
+    Line,
+    /// Paints the cells along the stroke into an area of an [BoardConfig::Area] board.
+    /// Painted cells are added to the board, if they were not on it already.
+    Area {
+        area_index: i32,
+        display_value: String,
+    },
+}
+
+impl BrushMode {
+    /// Returns true, if the brush is applied continuously while drawing the stroke.
+    /// Otherwise, it is applied once, when the stroke ends.
+    pub fn is_continuous(&self) -> bool {
+        matches!(self, BrushMode::Area { .. })
+    }
+}
+
+/// Mirror axes applied to every stroke of a [Brush].
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+pub enum Symmetry {
+    #[default]
+    None,
+    /// Mirrors strokes between the left and the right half of the board.
+    Horizontal,
+    /// Mirrors strokes between the top and the bottom half of the board.
+    Vertical,
+    /// Mirrors strokes into all four quadrants of the board.
+    Both,
+}
+
+/// A brush to edit the layout and areas of a board.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Brush {
+    pub mode: BrushMode,
+    pub symmetry: Symmetry,
+}
+
+impl Default for Brush {
+    fn default() -> Self {
+        Brush {
+            mode: BrushMode::Rectangle,
+            symmetry: Symmetry::None,
+        }
+    }
+}
+
+impl Brush {
+    /// Returns the cells affected by a stroke of this brush.
+    ///
+    /// # Arguments
+    ///
+    /// * `start`: The cell, where the stroke started.
+    /// * `end`: The cell, where the stroke ended.
+    /// * `dim`: The dimensions of the board. Both cells must be inside of it.
+    ///
+    /// returns: Vec<(usize, usize)>
+    pub fn stroke_cells(
+        &self,
+        start: (usize, usize),
+        end: (usize, usize),
+        dim: (usize, usize),
+    ) -> Vec<(usize, usize)> {
+        let cells = match self.mode {
+            BrushMode::Rectangle => rectangle_cells(start, end),
+            BrushMode::Line | BrushMode::Area { .. } => line_cells(start, end),
+        };
+        mirror_cells(&cells, dim, self.symmetry)
+    }
+
+    /// Applies this brush to the given cells of the board.
+    ///
+    /// # Arguments
+    ///
+    /// * `board_config`: The board to edit.
+    /// * `cells`: The cells to apply the brush to, usually from [Self::stroke_cells].
+    /// * `erase`: Whether to remove the cells from the board instead of adding them.
+    ///   Ignored for [BrushMode::Area].
+    ///
+    /// returns: bool true, if the board was changed
+    pub fn apply(
+        &self,
+        board_config: &mut BoardConfig,
+        cells: &[(usize, usize)],
+        erase: bool,
+    ) -> bool {
+        match &self.mode {
+            BrushMode::Rectangle | BrushMode::Line => {
+                let layout = match board_config {
+                    BoardConfig::Simple { layout } => layout,
+                    BoardConfig::Area { layout, .. } => layout.as_mut(),
+                };
+                let mut changed = false;
+                for &cell in cells {
+                    if layout[cell] == erase {
+                        layout[cell] = !erase;
+                        changed = true;
+                    }
+                }
+                changed
+            }
+            BrushMode::Area {
+                area_index,
+                display_value,
+            } => {
+                let BoardConfig::Area {
+                    layout,
+                    area_indices,
+                    display_values,
+                    ..
+                } = board_config
+                else {
+                    return false;
+                };
+                let mut changed = false;
+                for &cell in cells {
+                    if !layout[cell]
+                        || area_indices[cell] != *area_index
+                        || display_values[cell] != *display_value
+                    {
+                        layout[cell] = true;
+                        area_indices[cell] = *area_index;
+                        display_values[cell] = display_value.clone();
+                        changed = true;
+                    }
+                }
+                changed
+            }
+        }
+    }
+}
+
+/// Returns all cells in the rectangle spanned by the two corners.
+fn rectangle_cells(start: (usize, usize), end: (usize, usize)) -> Vec<(usize, usize)> {
+    let (min_x, max_x) = (start.0.min(end.0), start.0.max(end.0));
+    let (min_y, max_y) = (start.1.min(end.1), start.1.max(end.1));
+    (min_x..=max_x)
+        .flat_map(|x| (min_y..=max_y).map(move |y| (x, y)))
+        .collect()
+}
+
+/// Returns the cells on the straight line between both cells including them.
+///
+/// Uses Bresenham's line algorithm, so consecutive cells are always neighbours, horizontally,
+/// vertically or diagonally.
+fn line_cells(start: (usize, usize), end: (usize, usize)) -> Vec<(usize, usize)> {
+    let (mut x, mut y) = (start.0 as isize, start.1 as isize);
+    let (end_x, end_y) = (end.0 as isize, end.1 as isize);
+    let dx = (end_x - x).abs();
+    let dy = -(end_y - y).abs();
+    let step_x = if x < end_x { 1 } else { -1 };
+    let step_y = if y < end_y { 1 } else { -1 };
+    let mut error = dx + dy;
+
+    let mut cells = vec![(x as usize, y as usize)];
+    while x != end_x || y != end_y {
+        let doubled_error = 2 * error;
+        if doubled_error >= dy {
+            error += dy;
+            x += step_x;
+        }
+        if doubled_error <= dx {
+            error += dx;
+            y += step_y;
+        }
+        cells.push((x as usize, y as usize));
+    }
+    cells
+}
+
+/// Adds the mirrored cells for the given symmetry.
+/// Each cell is only contained once in the result.
+fn mirror_cells(
+    cells: &[(usize, usize)],
+    (width, height): (usize, usize),
+    symmetry: Symmetry,
+) -> Vec<(usize, usize)> {
+    let mut result: Vec<(usize, usize)> = Vec::with_capacity(cells.len() * 4);
+    for &(x, y) in cells {
+        let mirrored_x = width - 1 - x;
+        let mirrored_y = height - 1 - y;
+        let mirrored = match symmetry {
+            Symmetry::None => vec![(x, y)],
+            Symmetry::Horizontal => vec![(x, y), (mirrored_x, y)],
+            Symmetry::Vertical => vec![(x, y), (x, mirrored_y)],
+            Symmetry::Both => vec![
+                (x, y),
+                (mirrored_x, y),
+                (x, mirrored_y),
+                (mirrored_x, mirrored_y),
+            ],
+        };
+        for cell in mirrored {
+            if !result.contains(&cell) {
+                result.push(cell);
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use puzzled_common::shape::shape_square;
+
+    #[test]
+    fn test_line_cells() {
+        assert_eq!(
+            line_cells((0, 0), (3, 1)),
+            vec![(0, 0), (1, 0), (2, 1), (3, 1)]
+        );
+        assert_eq!(line_cells((2, 2), (2, 0)), vec![(2, 2), (2, 1), (2, 0)]);
+    }
+
+    #[test]
+    fn test_rectangle_with_symmetry() {
+        let brush = Brush {
+            mode: BrushMode::Rectangle,
+            symmetry: Symmetry::Horizontal,
+        };
+
+        let mut cells = brush.stroke_cells((1, 1), (0, 0), (3, 2));
+        cells.sort();
+
+        assert_eq!(cells, vec![(0, 0), (0, 1), (1, 0), (1, 1), (2, 0), (2, 1)]);
+    }
+
+    #[test]
+    fn test_apply_erase() {
+        let mut board_config = BoardConfig::Simple {
+            layout: shape_square(&[[true, true], [true, true]]),
+        };
+        let brush = Brush {
+            mode: BrushMode::Line,
+            symmetry: Symmetry::Both,
+        };
+
+        let cells = brush.stroke_cells((0, 0), (0, 0), (2, 2));
+        assert!(brush.apply(&mut board_config, &cells, true));
+        assert!(!brush.apply(&mut board_config, &cells, true));
+
+        assert_eq!(board_config.layout().len(), 4);
+        assert!(board_config.layout().iter().all(|cell| !*cell));
+    }
+}
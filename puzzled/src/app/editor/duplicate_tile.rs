@@ -0,0 +1,79 @@
+use crate::app::editor::tile_library::TileLibrary;
+use adw::prelude::{AdwDialogExt, AlertDialogExt, IsA};
+use adw::{AlertDialog, ResponseAppearance};
+use puzzled_common::Shape;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// The tile a puzzle should reference after a tile was defined in the editor.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TileReference {
+    /// The tile was defined with the requested name.
+    Defined(String),
+    /// The tile was already defined with this name and should be referenced instead.
+    Existing(String),
+}
+
+/// Defines a custom tile in the library.
+///
+/// If the same tile is already defined up to rotation and flipping, the author is warned and can
+/// choose to reference the existing tile instead, to define the tile anyway or to cancel.
+///
+/// # Arguments
+///
+/// * `parent`: The widget to show the warning on.
+/// * `library`: The library to define the tile in.
+/// * `name`: The name of the new tile.
+/// * `shape`: The shape of the new tile.
+/// * `on_defined`: Called with the tile to reference, unless the author cancelled.
+///
+/// returns: ()
+pub fn define_tile<F: Fn(TileReference) + 'static>(
+    parent: &impl IsA<gtk::Widget>,
+    library: &Rc<RefCell<TileLibrary>>,
+    name: &str,
+    shape: Shape,
+    on_defined: F,
+) {
+    let result = library.borrow_mut().define(name, shape.clone());
+    let duplicate = match result {
+        Ok(()) => {
+            on_defined(TileReference::Defined(name.to_string()));
+            return;
+        }
+        Err(duplicate) => duplicate,
+    };
+
+    let dialog = AlertDialog::builder()
+        .heading("Duplicate Tile")
+        .body(format!(
+            "The tile '{}' has the same shape as the tile '{}', when it is rotated or flipped. Do you want to use the existing tile instead?",
+            name, duplicate.existing_name
+        ))
+        .build();
+
+    let cancel_id = "cancel";
+    let define_id = "define";
+    let reference_id = "reference";
+    dialog.add_response(cancel_id, "Cancel");
+    dialog.add_response(define_id, "Define Anyway");
+    dialog.add_response(reference_id, &format!("Use '{}'", duplicate.existing_name));
+    dialog.set_default_response(Some(reference_id));
+    dialog.set_close_response(cancel_id);
+    dialog.set_response_appearance(reference_id, ResponseAppearance::Suggested);
+    dialog.connect_response(None, {
+        let library = library.clone();
+        let name = name.to_string();
+        move |_, response| match response {
+            "define" => {
+                library.borrow_mut().define_duplicate(&name, shape.clone());
+                on_defined(TileReference::Defined(name.clone()));
+            }
+            "reference" => {
+                on_defined(TileReference::Existing(duplicate.existing_name.clone()));
+            }
+            _ => {}
+        }
+    });
+    dialog.present(Some(parent));
+}
@@ -0,0 +1,208 @@
+use crate::app::components::board::BoardView;
+use crate::app::editor::brush::Brush;
+use adw::gdk::BUTTON_SECONDARY;
+use adw::gio;
+use adw::glib;
+use adw::subclass::prelude::*;
+use gtk::prelude::*;
+use gtk::GestureDrag;
+use log::error;
+use puzzle_config::BoardConfig;
+
+const BOARD_CHANGED_SIGNAL_NAME: &str = "board-changed";
+
+mod imp {
+    use super::*;
+    use adw::glib::subclass::Signal;
+    use std::cell::{Cell, RefCell};
+    use std::sync::OnceLock;
+
+    #[derive(Debug, Default)]
+    pub struct PuzzledEditableBoardView {
+        pub board_config: RefCell<Option<BoardConfig>>,
+        pub board_view: RefCell<Option<BoardView>>,
+        pub brush: RefCell<Brush>,
+        pub stroke_start: Cell<Option<(usize, usize)>>,
+        pub stroke_last: Cell<Option<(usize, usize)>>,
+        pub erase: Cell<bool>,
+    }
+
+    #[glib::object_subclass]
+    impl ObjectSubclass for PuzzledEditableBoardView {
+        const NAME: &'static str = "PuzzledEditableBoardView";
+        type Type = EditableBoardView;
+        type ParentType = gtk::Box;
+    }
+
+    impl ObjectImpl for PuzzledEditableBoardView {
+        fn signals() -> &'static [Signal] {
+            static SIGNALS: OnceLock<Vec<Signal>> = OnceLock::new();
+            SIGNALS.get_or_init(|| vec![Signal::builder(BOARD_CHANGED_SIGNAL_NAME).build()])
+        }
+    }
+    impl WidgetImpl for PuzzledEditableBoardView {}
+    impl BoxImpl for PuzzledEditableBoardView {}
+}
+
+glib::wrapper! {
+    /// A [BoardView], which can be drawn on with a [Brush].
+    ///
+    /// A stroke starts, when the board is pressed, and ends, when it is released. Strokes with the
+    /// secondary button remove cells from the board instead of adding them.
+    pub struct EditableBoardView(ObjectSubclass<imp::PuzzledEditableBoardView>)
+        @extends gtk::Widget, gtk::Box,
+         @implements gtk::Buildable, gtk::Accessible, gtk::ConstraintTarget,
+                  gtk::Native, gio::ActionGroup, gio::ActionMap, gtk::Orientable;
+}
+
+impl EditableBoardView {
+    pub fn new(board_config: BoardConfig) -> EditableBoardView {
+        let obj: EditableBoardView = glib::Object::builder().build();
+        obj.imp().board_config.replace(Some(board_config));
+        obj.rebuild_board_view();
+        obj.setup_stroke_gesture();
+        obj
+    }
+
+    /// Returns the board with all edits applied.
+    pub fn board_config(&self) -> Option<BoardConfig> {
+        self.imp().board_config.borrow().clone()
+    }
+
+    /// Replaces the board with the given one.
+    pub fn set_board_config(&self, board_config: BoardConfig) {
+        self.imp().board_config.replace(Some(board_config));
+        self.rebuild_board_view();
+        self.emit_by_name::<()>(BOARD_CHANGED_SIGNAL_NAME, &[]);
+    }
+
+    pub fn set_brush(&self, brush: Brush) {
+        self.imp().brush.replace(brush);
+    }
+
+    /// Connects a callback, which is called every time a stroke changed or replaced the board.
+    pub fn connect_board_changed<F: Fn(&Self) + 'static>(&self, callback: F) {
+        self.connect_local(BOARD_CHANGED_SIGNAL_NAME, false, move |values| {
+            let view = values[0]
+                .get::<EditableBoardView>()
+                .expect("Failed to get EditableBoardView from signal");
+            callback(&view);
+            None
+        });
+    }
+
+    fn setup_stroke_gesture(&self) {
+        let drag = GestureDrag::new();
+        drag.set_button(0);
+
+        drag.connect_drag_begin({
+            let self_clone = self.clone();
+            move |gesture, x, y| {
+                let imp = self_clone.imp();
+                let cell = self_clone.cell_at(x, y);
+                imp.erase.set(gesture.current_button() == BUTTON_SECONDARY);
+                imp.stroke_start.set(cell);
+                imp.stroke_last.set(cell);
+                let Some(cell) = cell else {
+                    return;
+                };
+                if imp.brush.borrow().mode.is_continuous() {
+                    self_clone.apply_stroke(cell, cell);
+                }
+            }
+        });
+
+        drag.connect_drag_update({
+            let self_clone = self.clone();
+            move |gesture, dx, dy| {
+                let imp = self_clone.imp();
+                if !imp.brush.borrow().mode.is_continuous() {
+                    return;
+                }
+                let Some((x, y)) = gesture.start_point() else {
+                    return;
+                };
+                let (Some(last), Some(cell)) =
+                    (imp.stroke_last.get(), self_clone.cell_at(x + dx, y + dy))
+                else {
+                    return;
+                };
+                if last != cell {
+                    self_clone.apply_stroke(last, cell);
+                    imp.stroke_last.set(Some(cell));
+                }
+            }
+        });
+
+        drag.connect_drag_end({
+            let self_clone = self.clone();
+            move |gesture, dx, dy| {
+                let imp = self_clone.imp();
+                let start = imp.stroke_start.take();
+                imp.stroke_last.set(None);
+                if imp.brush.borrow().mode.is_continuous() {
+                    return;
+                }
+                let Some((x, y)) = gesture.start_point() else {
+                    return;
+                };
+                if let (Some(start), Some(end)) = (start, self_clone.cell_at(x + dx, y + dy)) {
+                    self_clone.apply_stroke(start, end);
+                }
+            }
+        });
+
+        self.add_controller(drag);
+    }
+
+    /// Returns the cell of the board at the given position in pixels.
+    /// Positions outside the board are moved to the nearest cell on the edge.
+    fn cell_at(&self, x: f64, y: f64) -> Option<(usize, usize)> {
+        let board_config = self.imp().board_config.borrow();
+        let (board_width, board_height) = board_config.as_ref()?.layout().dim();
+        let (width, height) = (self.width(), self.height());
+        if width <= 0 || height <= 0 || board_width == 0 || board_height == 0 {
+            return None;
+        }
+        let cell_x = (x / width as f64 * board_width as f64).max(0.0) as usize;
+        let cell_y = (y / height as f64 * board_height as f64).max(0.0) as usize;
+        Some((cell_x.min(board_width - 1), cell_y.min(board_height - 1)))
+    }
+
+    fn apply_stroke(&self, start: (usize, usize), end: (usize, usize)) {
+        let changed = {
+            let brush = self.imp().brush.borrow();
+            let mut board_config = self.imp().board_config.borrow_mut();
+            let Some(board_config) = board_config.as_mut() else {
+                return;
+            };
+            let cells = brush.stroke_cells(start, end, board_config.layout().dim());
+            brush.apply(board_config, &cells, self.imp().erase.get())
+        };
+        if changed {
+            self.rebuild_board_view();
+            self.emit_by_name::<()>(BOARD_CHANGED_SIGNAL_NAME, &[]);
+        }
+    }
+
+    fn rebuild_board_view(&self) {
+        if let Some(board_view) = self.imp().board_view.take() {
+            self.remove(&board_view);
+        }
+        let board_config = self.imp().board_config.borrow();
+        let Some(board_config) = board_config.as_ref() else {
+            return;
+        };
+        match BoardView::new(board_config) {
+            Ok(board_view) => {
+                board_view.set_hexpand(true);
+                board_view.set_vexpand(true);
+                self.append(&board_view);
+                self.imp().board_view.replace(Some(board_view));
+            }
+            Err(e) => {
+                error!("Failed to create editable board view: {}", e);
+            }
+        }
+    }
+}
@@ -0,0 +1,447 @@
+use crate::app::components::tile::TileView;
+use crate::app::editor::brush::{Brush, BrushMode, Symmetry};
+use crate::app::editor::duplicate_tile::{define_tile, TileReference};
+use crate::app::editor::editable_board::EditableBoardView;
+use crate::app::editor::inventory::connect_inventory_banner;
+use crate::app::editor::tile_library::TileLibrary;
+use crate::app::editor::uniqueness::connect_uniqueness_label;
+use crate::model::store;
+use adw::gio::{Cancellable, File};
+use adw::prelude::*;
+use adw::{AlertDialog, ResponseAppearance};
+use gettextrs::gettext;
+use gtk::{FileFilter, StringList};
+use log::debug;
+use puzzle_config::digitizer::{digitize_board, DigitizeError};
+use puzzle_config::{BoardConfig, ColorConfig, PuzzleConfig};
+use puzzled_common::Shape;
+use puzzled_common::ShapeType::Square;
+use std::cell::RefCell;
+use std::rc::Rc;
+
+const RESOURCE_PATH: &str = "/de/til7701/Puzzled/board-editor-dialog.ui";
+/// The size of the board the editor starts with, if no puzzle was opened.
+const EMPTY_BOARD_SIZE: (usize, usize) = (8, 8);
+/// The size of the canvas new tiles are drawn on.
+const TILE_CANVAS_SIZE: (usize, usize) = (5, 5);
+/// How many pixels a cell of the listed tiles should have.
+const TILE_CELL_SIZE: i32 = 12;
+/// The position of [BrushMode::Line] in the tool dropdown.
+const LINE_MODE_INDEX: u32 = 1;
+/// The position of [BrushMode::Area] in the tool dropdown. It is only listed for area boards.
+const AREA_MODE_INDEX: u32 = 2;
+
+/// Shows the board editor.
+///
+/// The editor starts with a copy of the board of the given puzzle, so the puzzle itself is not
+/// changed. Without a puzzle, it starts with a rectangular board.
+/// The board can be replaced with a draft detected in a photo of a physical puzzle.
+/// New tiles are drawn on a canvas and added to the tiles of the puzzle. If the same tile is
+/// already known, the author is asked to reference it instead.
+/// A banner warns, while the tiles cannot cover the board for any target, and a label below the
+/// board shows, whether the puzzle has a unique solution.
+///
+/// # Arguments
+///
+/// * `parent`: the widget to present the dialog on
+/// * `puzzle`: the puzzle to take the board from
+pub fn show_board_editor(parent: &impl IsA<gtk::Widget>, puzzle: Option<&PuzzleConfig>) {
+    let builder = gtk::Builder::from_resource(RESOURCE_PATH);
+    let dialog: adw::Dialog = builder
+        .object("board_editor_dialog")
+        .expect("Missing `board_editor_dialog` in resource");
+    let brush_mode_row: adw::ComboRow = builder
+        .object("brush_mode_row")
+        .expect("Missing `brush_mode_row` in resource");
+    let symmetry_row: adw::ComboRow = builder
+        .object("symmetry_row")
+        .expect("Missing `symmetry_row` in resource");
+    let area_row: adw::ComboRow = builder
+        .object("area_row")
+        .expect("Missing `area_row` in resource");
+    let display_value_row: adw::EntryRow = builder
+        .object("display_value_row")
+        .expect("Missing `display_value_row` in resource");
+    let board_box: gtk::Box = builder
+        .object("board_box")
+        .expect("Missing `board_box` in resource");
+
+    let board_config = puzzle
+        .map(|puzzle| puzzle.board_config().clone())
+        .unwrap_or_else(|| BoardConfig::Simple {
+            layout: Shape::from_elem(EMPTY_BOARD_SIZE, Square, true),
+        });
+
+    set_brush_modes(&brush_mode_row, &area_row, &board_config);
+    let board_view = EditableBoardView::new(board_config);
+    board_view.set_hexpand(true);
+    board_view.set_vexpand(true);
+    board_box.append(&board_view);
+
+    let update_brush = Rc::new({
+        let board_view = board_view.clone();
+        let brush_mode_row = brush_mode_row.clone();
+        let symmetry_row = symmetry_row.clone();
+        let area_row = area_row.clone();
+        let display_value_row = display_value_row.clone();
+        move || {
+            let mode = match brush_mode_row.selected() {
+                LINE_MODE_INDEX => BrushMode::Line,
+                AREA_MODE_INDEX => BrushMode::Area {
+                    area_index: area_row.selected() as i32,
+                    display_value: display_value_row.text().to_string(),
+                },
+                _ => BrushMode::Rectangle,
+            };
+            let symmetry = match symmetry_row.selected() {
+                1 => Symmetry::Horizontal,
+                2 => Symmetry::Vertical,
+                3 => Symmetry::Both,
+                _ => Symmetry::None,
+            };
+            area_row.set_visible(matches!(mode, BrushMode::Area { .. }));
+            display_value_row.set_visible(matches!(mode, BrushMode::Area { .. }));
+            board_view.set_brush(Brush { mode, symmetry });
+        }
+    });
+    for row in [&brush_mode_row, &symmetry_row, &area_row] {
+        let update_brush = update_brush.clone();
+        row.connect_selected_notify(move |_| update_brush());
+    }
+    display_value_row.connect_changed({
+        let update_brush = update_brush.clone();
+        move |_| update_brush()
+    });
+    update_brush();
+
+    setup_photo_import(&builder, &dialog, &board_view, &brush_mode_row, &area_row);
+    let tiles = setup_tiles(&builder, &dialog, puzzle);
+
+    let inventory_banner: adw::Banner = builder
+        .object("inventory_banner")
+        .expect("Missing `inventory_banner` in resource");
+    let update_inventory_banner =
+        connect_inventory_banner(&inventory_banner, &board_view, tile_shapes(&tiles));
+    tiles.connect_changed(update_inventory_banner);
+
+    let uniqueness_label: gtk::Label = builder
+        .object("uniqueness_label")
+        .expect("Missing `uniqueness_label` in resource");
+    let update_uniqueness_label =
+        connect_uniqueness_label(&uniqueness_label, &board_view, tile_shapes(&tiles));
+    tiles.connect_changed(update_uniqueness_label);
+
+    dialog.present(Some(parent));
+}
+
+/// Lists the tools for the board in the tool dropdown and its areas in the area dropdown.
+fn set_brush_modes(
+    brush_mode_row: &adw::ComboRow,
+    area_row: &adw::ComboRow,
+    board_config: &BoardConfig,
+) {
+    let area_names: Vec<&str> = match board_config {
+        BoardConfig::Simple { .. } => Vec::new(),
+        BoardConfig::Area { area_configs, .. } => area_configs
+            .iter()
+            .map(|area_config| area_config.name())
+            .collect(),
+    };
+    let mut brush_modes = vec![gettext("Rectangle"), gettext("Line")];
+    if !area_names.is_empty() {
+        brush_modes.push(gettext("Area"));
+    }
+    let brush_modes: Vec<&str> = brush_modes.iter().map(String::as_str).collect();
+    brush_mode_row.set_model(Some(&StringList::new(&brush_modes)));
+    area_row.set_model(Some(&StringList::new(&area_names)));
+}
+
+/// Replaces the board with the board detected in a photo chosen by the author.
+/// See [digitize_board].
+fn setup_photo_import(
+    builder: &gtk::Builder,
+    dialog: &adw::Dialog,
+    board_view: &EditableBoardView,
+    brush_mode_row: &adw::ComboRow,
+    area_row: &adw::ComboRow,
+) {
+    let import_photo_button: gtk::Button = builder
+        .object("import_photo_button")
+        .expect("Missing `import_photo_button` in resource");
+
+    import_photo_button.connect_clicked({
+        let dialog = dialog.clone();
+        let board_view = board_view.clone();
+        let brush_mode_row = brush_mode_row.clone();
+        let area_row = area_row.clone();
+        move |_| {
+            let filter = FileFilter::new();
+            filter.set_name(Some(gettext("Photos").as_str()));
+            filter.add_mime_type("image/png");
+            filter.add_mime_type("image/jpeg");
+            let file_dialog = gtk::FileDialog::builder().default_filter(&filter).build();
+            let window = dialog.root().and_downcast::<gtk::Window>();
+            file_dialog.open(window.as_ref(), None::<&Cancellable>, {
+                let dialog = dialog.clone();
+                let board_view = board_view.clone();
+                let brush_mode_row = brush_mode_row.clone();
+                let area_row = area_row.clone();
+                move |result| {
+                    let file = match result {
+                        Ok(file) => file,
+                        Err(error) => {
+                            debug!("File dialog error: {:?}", error);
+                            return;
+                        }
+                    };
+                    match digitize_photo(&file) {
+                        Ok(board_config) => {
+                            set_brush_modes(&brush_mode_row, &area_row, &board_config);
+                            board_view.set_board_config(board_config);
+                        }
+                        Err(message) => show_photo_import_error(&dialog, &message),
+                    }
+                }
+            });
+        }
+    });
+}
+
+/// Detects the board in the photo.
+///
+/// returns: Result<BoardConfig, String> Err with a message for the author
+fn digitize_photo(file: &File) -> Result<BoardConfig, String> {
+    let (bytes, _etag) = file
+        .load_contents(None::<&Cancellable>)
+        .map_err(|e| e.to_string())?;
+    digitize_board(bytes.as_ref()).map_err(|e| match e {
+        DigitizeError::InvalidImage(message) => {
+            format!("{}: {}", gettext("The photo could not be read"), message)
+        }
+        DigitizeError::GridNotFound => gettext("No grid was found in the photo."),
+    })
+}
+
+fn show_photo_import_error(parent: &adw::Dialog, message: &str) {
+    let dialog = AlertDialog::builder()
+        .heading(gettext("Import Failed"))
+        .body(message)
+        .build();
+
+    let ok_id = "ok";
+    dialog.add_response(ok_id, "OK");
+    dialog.set_default_response(Some(ok_id));
+    dialog.set_close_response(ok_id);
+    dialog.set_response_appearance(ok_id, ResponseAppearance::Suggested);
+    dialog.present(Some(parent));
+}
+
+/// The tiles of the edited puzzle with the rows listing them.
+#[derive(Default)]
+struct EditorTiles {
+    tiles: RefCell<Vec<(adw::ActionRow, Shape)>>,
+    changed_callbacks: RefCell<Vec<Box<dyn Fn()>>>,
+}
+
+impl EditorTiles {
+    fn len(&self) -> usize {
+        self.tiles.borrow().len()
+    }
+
+    /// Returns the base shapes of the tiles.
+    fn shapes(&self) -> Vec<Shape> {
+        self.tiles
+            .borrow()
+            .iter()
+            .map(|(_, shape)| shape.clone())
+            .collect()
+    }
+
+    fn push(&self, row: adw::ActionRow, shape: Shape) {
+        self.tiles.borrow_mut().push((row, shape));
+        self.notify_changed();
+    }
+
+    fn remove(&self, row: &adw::ActionRow) {
+        self.tiles
+            .borrow_mut()
+            .retain(|(tile_row, _)| tile_row != row);
+        self.notify_changed();
+    }
+
+    /// Connects a callback, which is called every time a tile was added or removed.
+    fn connect_changed<F: Fn() + 'static>(&self, callback: F) {
+        self.changed_callbacks.borrow_mut().push(Box::new(callback));
+    }
+
+    fn notify_changed(&self) {
+        for callback in self.changed_callbacks.borrow().iter() {
+            callback();
+        }
+    }
+}
+
+/// Lists the tiles of the puzzle and sets up the canvas to draw new tiles on.
+fn setup_tiles(
+    builder: &gtk::Builder,
+    dialog: &adw::Dialog,
+    puzzle: Option<&PuzzleConfig>,
+) -> Rc<EditorTiles> {
+    let tiles_group: adw::PreferencesGroup = builder
+        .object("tiles_group")
+        .expect("Missing `tiles_group` in resource");
+    let tile_name_row: adw::EntryRow = builder
+        .object("tile_name_row")
+        .expect("Missing `tile_name_row` in resource");
+    let add_tile_button: gtk::Button = builder
+        .object("add_tile_button")
+        .expect("Missing `add_tile_button` in resource");
+    let tile_canvas_box: gtk::Box = builder
+        .object("tile_canvas_box")
+        .expect("Missing `tile_canvas_box` in resource");
+
+    let tiles: Rc<EditorTiles> = Rc::default();
+    for tile in puzzle
+        .map(|puzzle| puzzle.tiles().as_slice())
+        .unwrap_or_default()
+    {
+        let name = tile
+            .name()
+            .clone()
+            .unwrap_or_else(|| gettext("Custom Tile"));
+        add_tile_row(
+            &tiles_group,
+            &tiles,
+            name,
+            tile.base().clone(),
+            tile.color(),
+        );
+    }
+
+    let tile_canvas = EditableBoardView::new(empty_tile_canvas());
+    tile_canvas.set_hexpand(true);
+    tile_canvas.set_vexpand(true);
+    tile_canvas_box.append(&tile_canvas);
+
+    let update_add_tile_button = Rc::new({
+        let add_tile_button = add_tile_button.clone();
+        let tile_name_row = tile_name_row.clone();
+        let tile_canvas = tile_canvas.downgrade();
+        move || {
+            let Some(tile_canvas) = tile_canvas.upgrade() else {
+                return;
+            };
+            add_tile_button.set_sensitive(
+                !tile_name_row.text().is_empty() && drawn_tile(&tile_canvas).is_some(),
+            );
+        }
+    });
+    tile_name_row.connect_changed({
+        let update_add_tile_button = update_add_tile_button.clone();
+        move |_| update_add_tile_button()
+    });
+    tile_canvas.connect_board_changed(move |_| update_add_tile_button());
+
+    let library = Rc::new(RefCell::new(TileLibrary::new(
+        &store::create_json_loader().predefined_catalog(),
+    )));
+    add_tile_button.connect_clicked({
+        let dialog = dialog.clone();
+        let tiles = tiles.clone();
+        move |_| {
+            let Some(shape) = drawn_tile(&tile_canvas) else {
+                return;
+            };
+            let on_defined = {
+                let tiles_group = tiles_group.clone();
+                let tiles = tiles.clone();
+                let tile_name_row = tile_name_row.clone();
+                let tile_canvas = tile_canvas.clone();
+                let shape = shape.clone();
+                move |reference: TileReference| {
+                    let name = match reference {
+                        TileReference::Defined(name) | TileReference::Existing(name) => name,
+                    };
+                    let color = ColorConfig::default_with_index(tiles.len());
+                    add_tile_row(&tiles_group, &tiles, name, shape.clone(), color);
+                    tile_name_row.set_text("");
+                    tile_canvas.set_board_config(empty_tile_canvas());
+                }
+            };
+            define_tile(&dialog, &library, &tile_name_row.text(), shape, on_defined);
+        }
+    });
+
+    tiles
+}
+
+/// Returns a function returning the base shapes of the current tiles.
+/// It does not keep the tiles alive, so it can be connected to their changes.
+fn tile_shapes(tiles: &Rc<EditorTiles>) -> impl Fn() -> Vec<Shape> + 'static {
+    let tiles = Rc::downgrade(tiles);
+    move || {
+        tiles
+            .upgrade()
+            .map(|tiles| tiles.shapes())
+            .unwrap_or_default()
+    }
+}
+
+/// Adds a row for the tile to the group, which removes the tile again with its button.
+fn add_tile_row(
+    tiles_group: &adw::PreferencesGroup,
+    tiles: &Rc<EditorTiles>,
+    name: String,
+    shape: Shape,
+    color: ColorConfig,
+) {
+    let (width, height) = shape.dim();
+    let tile_view = TileView::new(tiles.len(), shape.clone(), color);
+    tile_view.set_width_request(TILE_CELL_SIZE * width as i32);
+    tile_view.set_height_request(TILE_CELL_SIZE * height as i32);
+    tile_view.set_valign(gtk::Align::Center);
+    let remove_button = gtk::Button::builder()
+        .icon_name("user-trash-symbolic")
+        .tooltip_text(gettext("Remove Tile"))
+        .valign(gtk::Align::Center)
+        .css_classes(["flat"])
+        .build();
+
+    let row = adw::ActionRow::builder()
+        .title(name)
+        .use_markup(false)
+        .build();
+    row.add_suffix(&tile_view);
+    row.add_suffix(&remove_button);
+    tiles_group.add(&row);
+
+    remove_button.connect_clicked({
+        let tiles_group = tiles_group.clone();
+        let tiles = tiles.clone();
+        let row = row.downgrade();
+        move |_| {
+            let Some(row) = row.upgrade() else {
+                return;
+            };
+            tiles_group.remove(&row);
+            tiles.remove(&row);
+        }
+    });
+    tiles.push(row, shape);
+}
+
+fn empty_tile_canvas() -> BoardConfig {
+    BoardConfig::Simple {
+        layout: Shape::from_elem(TILE_CANVAS_SIZE, Square, false),
+    }
+}
+
+/// Returns the tile drawn on the canvas without the empty rows and columns around it.
+fn drawn_tile(tile_canvas: &EditableBoardView) -> Option<Shape> {
+    let mut shape = tile_canvas.board_config()?.layout().clone();
+    if !shape.iter().any(|cell| *cell) {
+        return None;
+    }
+    shape.trim_matching(false);
+    Some(shape)
+}
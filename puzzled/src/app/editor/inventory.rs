@@ -0,0 +1,83 @@
+use crate::app::editor::editable_board::EditableBoardView;
+use adw::prelude::ObjectExt;
+use adw::Banner;
+use puzzle_config::{BoardConfig, InventoryCheck};
+use puzzled_common::Shape;
+use std::rc::Rc;
+
+/// Shows a warning on the banner, while the tiles of the edited puzzle cannot cover the board for
+/// any target.
+///
+/// The banner is updated immediately and every time the board changed. It does not block editing,
+/// since the author may still be adding tiles or cells.
+///
+/// # Arguments
+///
+/// * `banner`: The banner to show the warning on.
+/// * `board_view`: The board of the edited puzzle.
+/// * `tiles`: Returns the base shapes of the current tiles of the puzzle.
+///
+/// returns: impl Fn() updates the banner and has to be called every time the tiles changed
+pub fn connect_inventory_banner<F: Fn() -> Vec<Shape> + 'static>(
+    banner: &Banner,
+    board_view: &EditableBoardView,
+    tiles: F,
+) -> impl Fn() + 'static {
+    let update = Rc::new({
+        let banner = banner.clone();
+        move |board_view: &EditableBoardView| {
+            let warning = board_view
+                .board_config()
+                .and_then(|board_config| inventory_warning(&board_config, &tiles()));
+            banner.set_title(warning.as_deref().unwrap_or_default());
+            banner.set_revealed(warning.is_some());
+        }
+    });
+    update(board_view);
+    board_view.connect_board_changed({
+        let update = update.clone();
+        move |board_view| update(board_view)
+    });
+
+    let board_view = board_view.downgrade();
+    move || {
+        if let Some(board_view) = board_view.upgrade() {
+            update(&board_view);
+        }
+    }
+}
+
+/// Returns a warning, if the tiles cannot cover the board for any target.
+fn inventory_warning(board_config: &BoardConfig, tiles: &[Shape]) -> Option<String> {
+    let check = InventoryCheck::new(board_config, tiles);
+    if check.matches_any_target() {
+        return None;
+    }
+    Some(format!(
+        "The tiles cover {} cells, but {} cells need to be covered.",
+        check.tile_cells(),
+        check.closest_board_cells()
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use puzzled_common::shape::shape_square;
+
+    #[test]
+    fn test_inventory_warning() {
+        let board_config = BoardConfig::Simple {
+            layout: shape_square(&[[true, true, true]]),
+        };
+
+        assert_eq!(
+            inventory_warning(&board_config, &[shape_square(&[[true, true]])]),
+            Some("The tiles cover 2 cells, but 3 cells need to be covered.".to_string())
+        );
+        assert_eq!(
+            inventory_warning(&board_config, &[shape_square(&[[true, true, true]])]),
+            None
+        );
+    }
+}
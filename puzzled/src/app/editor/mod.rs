@@ -0,0 +1,7 @@
+pub mod brush;
+pub mod duplicate_tile;
+pub mod editable_board;
+pub mod editor_dialog;
+pub mod inventory;
+pub mod tile_library;
+pub mod uniqueness;
@@ -0,0 +1,139 @@
+use puzzle_config::Predefined;
+use puzzled_common::Shape;
+
+/// The named tiles, which can be referenced by puzzles in the collection editor.
+///
+/// Contains the predefined tiles and the custom tiles defined in the edited collection. Tiles are
+/// compared by their canonical form, so a tile is a duplicate of another one, if it can be
+/// rotated or flipped into it.
+#[derive(Debug, Default, Clone)]
+pub struct TileLibrary {
+    tiles: Vec<LibraryTile>,
+}
+
+#[derive(Debug, Clone)]
+struct LibraryTile {
+    name: String,
+    canonical: Shape,
+    predefined: bool,
+}
+
+/// Returned, when a tile should be defined, which is already in the [TileLibrary].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DuplicateTile {
+    /// The name of the existing tile, which can be referenced instead.
+    pub existing_name: String,
+}
+
+impl TileLibrary {
+    /// Creates a library containing the named predefined tiles.
+    pub fn new(predefined: &Predefined) -> TileLibrary {
+        let tiles = predefined
+            .tiles()
+            .iter()
+            .filter_map(|tile| {
+                let name = tile.name().clone()?;
+                Some(LibraryTile::new(name, tile.base().clone(), true))
+            })
+            .collect();
+        TileLibrary { tiles }
+    }
+
+    /// Defines a custom tile with the given name.
+    ///
+    /// A custom tile with the same name is replaced. A predefined tile with the same name is
+    /// overridden.
+    ///
+    /// # Arguments
+    ///
+    /// * `name`: The name to reference the tile by.
+    /// * `shape`: The shape of the tile.
+    ///
+    /// returns: Result<(), DuplicateTile> Err with the name of an existing tile, if the shape is
+    /// already defined under another name. Use [Self::define_duplicate] to define it anyway.
+    pub fn define(&mut self, name: &str, shape: Shape) -> Result<(), DuplicateTile> {
+        let duplicate = self
+            .tiles
+            .iter()
+            .filter(|tile| tile.name != name)
+            .find(|tile| tile.canonical == shape.canonical());
+        if let Some(duplicate) = duplicate {
+            return Err(DuplicateTile {
+                existing_name: duplicate.name.clone(),
+            });
+        }
+        self.define_duplicate(name, shape);
+        Ok(())
+    }
+
+    /// Defines a custom tile with the given name, even if the same shape is already defined under
+    /// another name.
+    pub fn define_duplicate(&mut self, name: &str, shape: Shape) {
+        self.tiles
+            .retain(|tile| tile.name != name || tile.predefined);
+        self.tiles
+            .push(LibraryTile::new(name.to_string(), shape, false));
+    }
+}
+
+impl LibraryTile {
+    fn new(name: String, base: Shape, predefined: bool) -> LibraryTile {
+        LibraryTile {
+            name,
+            canonical: base.canonical(),
+            predefined,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use puzzled_common::shape::shape_square;
+
+    fn custom_tile_names(library: &TileLibrary) -> Vec<&str> {
+        library
+            .tiles
+            .iter()
+            .filter(|tile| !tile.predefined)
+            .map(|tile| tile.name.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_define_detects_rotated_duplicate() {
+        let mut library = TileLibrary::default();
+        library
+            .define("L", shape_square(&[[true, false], [true, true]]))
+            .unwrap();
+
+        let result = library.define("J", shape_square(&[[true, true], [false, true]]));
+
+        assert_eq!(
+            result,
+            Err(DuplicateTile {
+                existing_name: "L".to_string()
+            })
+        );
+        assert_eq!(custom_tile_names(&library), vec!["L"]);
+    }
+
+    #[test]
+    fn test_redefine_same_name() {
+        let mut library = TileLibrary::default();
+        library.define("I", shape_square(&[[true, true]])).unwrap();
+
+        library
+            .define("I", shape_square(&[[true], [true]]))
+            .unwrap();
+        library.define_duplicate("I2", shape_square(&[[true, true]]));
+
+        assert_eq!(custom_tile_names(&library), vec!["I", "I2"]);
+        assert_eq!(
+            library.define("I3", shape_square(&[[true], [true]])),
+            Err(DuplicateTile {
+                existing_name: "I".to_string()
+            })
+        );
+    }
+}
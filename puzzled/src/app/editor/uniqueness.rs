@@ -0,0 +1,84 @@
+use crate::app::editor::editable_board::EditableBoardView;
+use crate::solver::queue;
+use crate::solver::queue::SolverPriority;
+use crate::ui_bridge;
+use gtk::prelude::{ObjectExt, WidgetExt};
+use gtk::Label;
+use puzzle_config::validation::check_unique_board_solution;
+use puzzled_common::Shape;
+use std::cell::RefCell;
+use std::rc::Rc;
+use tokio_util::sync::CancellationToken;
+
+/// Shows on the label, whether the edited puzzle has exactly one solution for its default target.
+///
+/// The check runs in the background immediately and every time the board changed. A running
+/// check is cancelled, when the board changes again. The label is hidden while checking.
+///
+/// # Arguments
+///
+/// * `label`: The label to show the result on.
+/// * `board_view`: The board of the edited puzzle.
+/// * `tiles`: Returns the base shapes of the current tiles of the puzzle.
+///
+/// returns: impl Fn() restarts the check and has to be called every time the tiles changed
+pub fn connect_uniqueness_label<F: Fn() -> Vec<Shape> + 'static>(
+    label: &Label,
+    board_view: &EditableBoardView,
+    tiles: F,
+) -> impl Fn() + 'static {
+    let running: Rc<RefCell<Option<CancellationToken>>> = Rc::new(RefCell::new(None));
+    let update = Rc::new({
+        let label = label.clone();
+        move |board_view: &EditableBoardView| {
+            if let Some(cancel_token) = running.take() {
+                cancel_token.cancel();
+            }
+            label.set_visible(false);
+            let Some(board_config) = board_view.board_config() else {
+                return;
+            };
+
+            let cancel_token = CancellationToken::new();
+            running.replace(Some(cancel_token.clone()));
+            let sender = ui_bridge::oneshot({
+                let label = label.clone();
+                move |unique: Option<bool>| {
+                    let Some(unique) = unique else {
+                        return;
+                    };
+                    label.set_label(uniqueness_text(unique));
+                    label.set_visible(true);
+                }
+            });
+            let tiles = tiles();
+            queue::submit(SolverPriority::SolvabilityCheck, async move {
+                if let Ok(unique) =
+                    check_unique_board_solution(&board_config, &tiles, cancel_token).await
+                {
+                    sender.send(unique);
+                }
+            });
+        }
+    });
+    update(board_view);
+    board_view.connect_board_changed({
+        let update = update.clone();
+        move |board_view| update(board_view)
+    });
+
+    let board_view = board_view.downgrade();
+    move || {
+        if let Some(board_view) = board_view.upgrade() {
+            update(&board_view);
+        }
+    }
+}
+
+fn uniqueness_text(unique: bool) -> &'static str {
+    if unique {
+        "Unique solution"
+    } else {
+        "No unique solution"
+    }
+}
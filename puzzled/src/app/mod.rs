@@ -1,5 +1,8 @@
 pub mod collection_selection;
 pub mod components;
+pub mod editor;
+pub mod presenter;
+pub mod progress_drop;
 pub mod puzzle;
 pub mod puzzle_selection;
 pub mod random_puzzle;
@@ -0,0 +1,120 @@
+use puzzle_config::{CollectionSummary, PuzzleDifficultyConfig};
+
+/// The order in which the difficulties are listed in the progress breakdown.
+const DIFFICULTY_ORDER: [Option<PuzzleDifficultyConfig>; 5] = [
+    Some(PuzzleDifficultyConfig::Easy),
+    Some(PuzzleDifficultyConfig::Medium),
+    Some(PuzzleDifficultyConfig::Hard),
+    Some(PuzzleDifficultyConfig::Expert),
+    None,
+];
+
+/// The data shown by a row in the collection selection.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CollectionRow {
+    pub name: String,
+    pub difficulty: Option<String>,
+    /// The author of the collection. `None` for core collections.
+    pub author: Option<String>,
+    pub verified: bool,
+    pub version: Option<String>,
+    /// The label of the stars pill, like `3 / 10`.
+    pub stars: String,
+    /// Whether all stars of the collection were reached. The stars pill is highlighted then.
+    pub all_stars_reached: bool,
+    /// The percentage of solvable puzzles solved for their default target, rounded down.
+    pub progress_percent: u32,
+    /// The label of the progress bar, like `50%`.
+    pub progress: String,
+    /// The tooltip of the progress bar with a line per difficulty, like `Easy: 1 / 2`.
+    pub progress_breakdown: String,
+    /// Only community collections can be deleted.
+    pub show_delete_button: bool,
+}
+
+impl CollectionRow {
+    /// Creates the data for the row of the given collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `summary`: the summary of the collection to show
+    /// * `core`: true, if the collection is a core collection. False otherwise.
+    /// * `stars`: the number of stars reached and the total number of stars of the collection
+    /// * `solved`: whether each puzzle of the collection is solved for its default target
+    ///
+    /// returns: CollectionRow
+    pub fn new(
+        summary: &CollectionSummary,
+        core: bool,
+        stars: (u32, u32),
+        solved: &[bool],
+    ) -> Self {
+        let (stars_reached, stars_total) = stars;
+        let solvable: Vec<(Option<PuzzleDifficultyConfig>, bool)> = summary
+            .puzzles()
+            .iter()
+            .zip(solved)
+            .filter(|(puzzle, _)| !puzzle.is_unsolvable())
+            .map(|(puzzle, solved)| (*puzzle.difficulty(), *solved))
+            .collect();
+        let (solved_count, total_count) = count_solved(solvable.iter());
+        let progress_percent = if total_count == 0 {
+            100
+        } else {
+            (solved_count * 100 / total_count) as u32
+        };
+        let progress_breakdown = DIFFICULTY_ORDER
+            .iter()
+            .filter_map(|difficulty| {
+                let (solved_count, total_count) = count_solved(
+                    solvable
+                        .iter()
+                        .filter(|(puzzle_difficulty, _)| puzzle_difficulty == difficulty),
+                );
+                let name = difficulty.map_or("No Difficulty".to_string(), String::from);
+                (total_count > 0).then(|| format!("{}: {} / {}", name, solved_count, total_count))
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        CollectionRow {
+            name: summary.name().to_string(),
+            difficulty: summary.average_difficulty().map(String::from),
+            author: (!core).then(|| summary.author().to_string()),
+            verified: summary.integrity().is_verified_author(),
+            version: summary.version().clone(),
+            stars: format!("{} / {}", stars_reached, stars_total),
+            all_stars_reached: stars_reached == stars_total,
+            progress_percent,
+            progress: format!("{}%", progress_percent),
+            progress_breakdown,
+            show_delete_button: !core,
+        }
+    }
+}
+
+/// Returns how many of the given puzzles are solved and how many there are.
+fn count_solved<'a>(
+    puzzles: impl Iterator<Item = &'a (Option<PuzzleDifficultyConfig>, bool)>,
+) -> (usize, usize) {
+    puzzles.fold((0, 0), |(solved, total), (_, puzzle_solved)| {
+        (solved + *puzzle_solved as usize, total + 1)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::presenter::fixtures::{assert_snapshot, load_fixture_collection};
+
+    #[test]
+    fn test_collection_rows() {
+        let summary = CollectionSummary::from(&load_fixture_collection());
+        let rows = vec![
+            CollectionRow::new(&summary, true, (0, 11), &[false, false, false]),
+            CollectionRow::new(&summary, false, (11, 11), &[true, false, true]),
+        ];
+
+        assert_snapshot("collection_rows", &rows);
+    }
+}
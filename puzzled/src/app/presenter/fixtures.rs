@@ -0,0 +1,33 @@
+use crate::config;
+use puzzle_config::PuzzleConfigCollection;
+use std::fmt::Debug;
+use std::fs;
+
+const FIXTURES_PATH: &str = "src/app/presenter/fixtures";
+/// The directory of the snapshots relative to this file.
+const SNAPSHOTS_DIR: &str = "snapshots";
+
+/// Loads the collection used to create the data for the views in tests.
+pub fn load_fixture_collection() -> PuzzleConfigCollection {
+    let predefined_json_str = fs::read_to_string("resources/predefined.json").unwrap();
+    let json_loader =
+        puzzle_config::create_json_loader(&predefined_json_str, config::VERSION).unwrap();
+    let json = fs::read_to_string(format!("{}/collection.json", FIXTURES_PATH)).unwrap();
+    json_loader.load_puzzle_collection(&json).unwrap()
+}
+
+/// Compares the debug output of the value with the snapshot with the given name.
+///
+/// The snapshots are stored in `snapshots` beside this file. Review changed or new snapshots with
+/// `cargo insta review`, after checking, that the change is intended.
+///
+/// # Arguments
+///
+/// * `name`: the name of the snapshot file without extension
+/// * `value`: the data for the view
+pub fn assert_snapshot<T: Debug>(name: &str, value: &T) {
+    let mut settings = insta::Settings::clone_current();
+    settings.set_snapshot_path(SNAPSHOTS_DIR);
+    settings.set_prepend_module_to_snapshot(false);
+    settings.bind(|| insta::assert_debug_snapshot!(name, value));
+}
@@ -0,0 +1,173 @@
+use crate::model::play_history::PlayRecord;
+use std::collections::BTreeMap;
+
+/// The abbreviated names of the days of the week starting with Monday.
+const WEEKDAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+/// The number of weeks shown in the hint usage trend.
+const TREND_WEEKS: usize = 8;
+
+/// A bar in a chart of the insights.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChartBar {
+    pub label: String,
+    pub value: f64,
+}
+
+impl ChartBar {
+    fn new(label: &str, value: f64) -> Self {
+        ChartBar {
+            label: label.to_string(),
+            value,
+        }
+    }
+}
+
+/// The data shown by the insights dialog.
+#[derive(Debug, PartialEq)]
+pub struct Insights {
+    pub solved_count: usize,
+    /// The total time needed to solve the puzzles in minutes.
+    pub minutes_played: u32,
+    /// Minutes played per collection, the collection played the longest first.
+    pub minutes_per_collection: Vec<ChartBar>,
+    /// Solved puzzles per day of the week starting with Monday.
+    pub solves_per_weekday: Vec<ChartBar>,
+    /// The average number of hints per solved puzzle in the last weeks, in which puzzles were
+    /// solved. The oldest week is first.
+    pub hints_per_week: Vec<ChartBar>,
+}
+
+impl Insights {
+    /// Aggregates the play history.
+    ///
+    /// # Arguments
+    ///
+    /// * `records`: the recorded puzzles
+    ///
+    /// returns: Insights
+    pub fn new(records: &[PlayRecord]) -> Self {
+        let seconds_played: u32 = records.iter().map(|record| record.seconds).sum();
+        Insights {
+            solved_count: records.len(),
+            minutes_played: seconds_played / 60,
+            minutes_per_collection: minutes_per_collection(records),
+            solves_per_weekday: solves_per_weekday(records),
+            hints_per_week: hints_per_week(records),
+        }
+    }
+}
+
+fn minutes_per_collection(records: &[PlayRecord]) -> Vec<ChartBar> {
+    let mut seconds: BTreeMap<&str, u32> = BTreeMap::new();
+    for record in records {
+        *seconds.entry(&record.collection).or_default() += record.seconds;
+    }
+    let mut bars: Vec<ChartBar> = seconds
+        .into_iter()
+        .map(|(collection, seconds)| ChartBar::new(collection, seconds as f64 / 60.0))
+        .collect();
+    bars.sort_by(|a, b| b.value.total_cmp(&a.value));
+    bars
+}
+
+fn solves_per_weekday(records: &[PlayRecord]) -> Vec<ChartBar> {
+    WEEKDAYS
+        .iter()
+        .enumerate()
+        .map(|(i, weekday)| {
+            let count = records
+                .iter()
+                .filter(|record| record.weekday as usize == i + 1)
+                .count();
+            ChartBar::new(weekday, count as f64)
+        })
+        .collect()
+}
+
+fn hints_per_week(records: &[PlayRecord]) -> Vec<ChartBar> {
+    let mut weeks: BTreeMap<&str, (u32, u32)> = BTreeMap::new();
+    for record in records {
+        let (hints, solves) = weeks.entry(&record.week).or_default();
+        *hints += record.hints;
+        *solves += 1;
+    }
+    let skipped = weeks.len().saturating_sub(TREND_WEEKS);
+    weeks
+        .into_iter()
+        .skip(skipped)
+        .map(|(week, (hints, solves))| {
+            // The year is left out to keep the label short.
+            let label = week.split_once('-').map_or(week, |(_, number)| number);
+            ChartBar::new(label, hints as f64 / solves as f64)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(week: &str, weekday: u8, collection: &str, seconds: u32, hints: u32) -> PlayRecord {
+        PlayRecord {
+            week: week.to_string(),
+            weekday,
+            collection: collection.to_string(),
+            seconds,
+            hints,
+        }
+    }
+
+    #[test]
+    fn test_insights() {
+        let records = vec![
+            record("2026-W41", 1, "Puzzle a Day", 120, 2),
+            record("2026-W41", 1, "Puzzled", 600, 0),
+            record("2026-W42", 7, "Puzzle a Day", 60, 1),
+        ];
+
+        let insights = Insights::new(&records);
+
+        assert_eq!(insights.solved_count, 3);
+        assert_eq!(insights.minutes_played, 13);
+        assert_eq!(
+            insights.minutes_per_collection,
+            vec![
+                ChartBar::new("Puzzled", 10.0),
+                ChartBar::new("Puzzle a Day", 3.0)
+            ]
+        );
+        assert_eq!(insights.solves_per_weekday.len(), 7);
+        assert_eq!(insights.solves_per_weekday[0], ChartBar::new("Mon", 2.0));
+        assert_eq!(insights.solves_per_weekday[6], ChartBar::new("Sun", 1.0));
+        assert_eq!(
+            insights.hints_per_week,
+            vec![ChartBar::new("W41", 1.0), ChartBar::new("W42", 1.0)]
+        );
+    }
+
+    #[test]
+    fn test_hints_per_week_keeps_last_weeks() {
+        let records: Vec<PlayRecord> = (1..=10)
+            .map(|week| record(&format!("2026-W{:02}", week), 1, "Puzzled", 60, week))
+            .collect();
+
+        let bars = hints_per_week(&records);
+
+        assert_eq!(bars.len(), TREND_WEEKS);
+        assert_eq!(bars[0], ChartBar::new("W03", 3.0));
+        assert_eq!(bars[TREND_WEEKS - 1], ChartBar::new("W10", 10.0));
+    }
+
+    #[test]
+    fn test_insights_without_records() {
+        let insights = Insights::new(&[]);
+
+        assert_eq!(insights.solved_count, 0);
+        assert!(insights.minutes_per_collection.is_empty());
+        assert!(insights
+            .solves_per_weekday
+            .iter()
+            .all(|bar| bar.value == 0.0));
+        assert!(insights.hints_per_week.is_empty());
+    }
+}
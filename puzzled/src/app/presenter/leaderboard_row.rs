@@ -0,0 +1,97 @@
+use crate::model::leaderboard::token::Challenge;
+
+/// The data shown by a row in the table of a daily challenge in the leaderboard.
+#[derive(Debug, PartialEq, Eq)]
+pub struct LeaderboardRow {
+    /// The rank and the name of the player.
+    pub title: String,
+    /// The time needed and the hints used.
+    pub subtitle: String,
+    /// Whether the result is the player's own, so it can be shared.
+    pub own: bool,
+}
+
+impl LeaderboardRow {
+    /// Creates the rows for the results of the challenge in the order of their rank.
+    /// Results with the same number of hints and time share a rank.
+    ///
+    /// # Arguments
+    ///
+    /// * `challenge`: the challenge to show
+    /// * `player_name`: the name of the player to mark the own result
+    ///
+    /// returns: Vec<LeaderboardRow>
+    pub fn for_challenge(challenge: &Challenge, player_name: &str) -> Vec<Self> {
+        let mut rank = 0;
+        let mut previous = None;
+        challenge
+            .results
+            .iter()
+            .enumerate()
+            .map(|(i, result)| {
+                if previous != Some((result.hints, result.seconds)) {
+                    rank = i + 1;
+                    previous = Some((result.hints, result.seconds));
+                }
+                let hints = match result.hints {
+                    0 => "no hints".to_string(),
+                    1 => "1 hint".to_string(),
+                    hints => format!("{} hints", hints),
+                };
+                LeaderboardRow {
+                    title: format!("{}. {}", rank, result.player),
+                    subtitle: format!(
+                        "{}:{:02} · {}",
+                        result.seconds / 60,
+                        result.seconds % 60,
+                        hints
+                    ),
+                    own: result.player == player_name,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::leaderboard::token::ResultToken;
+
+    fn result(player: &str, seconds: u32, hints: u32) -> ResultToken {
+        ResultToken {
+            date: "2026-10-16".to_string(),
+            puzzle: "Month".to_string(),
+            puzzle_hash: "0123456789abcdef".to_string(),
+            seconds,
+            hints,
+            player: player.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_leaderboard_rows() {
+        let challenge = Challenge {
+            date: "2026-10-16".to_string(),
+            puzzle: "Month".to_string(),
+            results: vec![
+                result("Bob", 65, 0),
+                result("Alice", 65, 0),
+                result("Carol", 3600, 2),
+                result("Dave", 5, 1),
+            ],
+        };
+
+        let rows = LeaderboardRow::for_challenge(&challenge, "Alice");
+
+        let titles: Vec<&str> = rows.iter().map(|row| row.title.as_str()).collect();
+        assert_eq!(titles, vec!["1. Bob", "1. Alice", "3. Carol", "4. Dave"]);
+        assert_eq!(rows[0].subtitle, "1:05 · no hints");
+        assert_eq!(rows[2].subtitle, "60:00 · 2 hints");
+        assert_eq!(rows[3].subtitle, "0:05 · 1 hint");
+        assert_eq!(
+            rows.iter().map(|row| row.own).collect::<Vec<_>>(),
+            vec![false, true, false, false]
+        );
+    }
+}
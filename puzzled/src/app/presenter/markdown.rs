@@ -0,0 +1,186 @@
+/// The schemes of links, which are kept in descriptions. Other links only show their text.
+const ALLOWED_LINK_SCHEMES: [&str; 3] = ["https://", "http://", "mailto:"];
+
+/// Converts the Markdown subset allowed in descriptions to Pango markup.
+///
+/// Supported are paragraphs separated by blank lines, `**bold**`, `*italic*`, `` `code` ``,
+/// lists starting with `-`, `*` or a number and links like `[text](https://example.org)`.
+/// Everything else is shown as written. All text is escaped, so the result is always valid
+/// markup and descriptions cannot inject markup of their own.
+///
+/// # Arguments
+///
+/// * `markdown`: the description as written in the collection
+///
+/// returns: String
+pub fn to_pango_markup(markdown: &str) -> String {
+    let mut lines: Vec<Line> = Vec::new();
+    for line in markdown.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            if lines.last().is_some_and(|last| *last != Line::Blank) {
+                lines.push(Line::Blank);
+            }
+        } else if let Some(item) = list_item(line) {
+            lines.push(Line::Item(item));
+        } else if let Some(Line::Paragraph(paragraph)) = lines.last_mut() {
+            paragraph.push(' ');
+            paragraph.push_str(line);
+        } else {
+            lines.push(Line::Paragraph(line.to_string()));
+        }
+    }
+    if lines.last() == Some(&Line::Blank) {
+        lines.pop();
+    }
+
+    lines
+        .iter()
+        .map(|line| match line {
+            Line::Blank => String::new(),
+            Line::Paragraph(text) => inline_markup(text),
+            Line::Item(item) => item.clone(),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Line {
+    Blank,
+    Paragraph(String),
+    /// A list item, which is already converted to markup.
+    Item(String),
+}
+
+/// Returns the markup for the line, if it is an item of a list.
+fn list_item(line: &str) -> Option<String> {
+    if let Some(text) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+        return Some(format!("• {}", inline_markup(text.trim_start())));
+    }
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    let text = line[digits..].strip_prefix(". ")?;
+    if digits == 0 {
+        return None;
+    }
+    Some(format!(
+        "{}. {}",
+        &line[..digits],
+        inline_markup(text.trim_start())
+    ))
+}
+
+/// Converts the emphasis, code and links of a single line to markup.
+/// Markers without a closing counterpart are shown as written.
+fn inline_markup(text: &str) -> String {
+    let mut markup = String::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c == '\\'
+            && let Some(escaped) = rest[1..].chars().next().filter(char::is_ascii_punctuation)
+        {
+            markup.push_str(&escape(&escaped.to_string()));
+            rest = &rest[1 + escaped.len_utf8()..];
+        } else if let Some((inner, after)) = enclosed(rest, "**") {
+            markup.push_str(&format!("<b>{}</b>", inline_markup(inner)));
+            rest = after;
+        } else if let Some((inner, after)) = enclosed(rest, "*") {
+            markup.push_str(&format!("<i>{}</i>", inline_markup(inner)));
+            rest = after;
+        } else if let Some((inner, after)) = enclosed(rest, "`") {
+            markup.push_str(&format!("<tt>{}</tt>", escape(inner)));
+            rest = after;
+        } else if let Some((label, url, after)) = link(rest) {
+            if ALLOWED_LINK_SCHEMES
+                .iter()
+                .any(|scheme| url.starts_with(scheme))
+            {
+                markup.push_str(&format!(
+                    "<a href=\"{}\">{}</a>",
+                    escape(url),
+                    inline_markup(label)
+                ));
+            } else {
+                markup.push_str(&inline_markup(label));
+            }
+            rest = after;
+        } else {
+            markup.push_str(&escape(&c.to_string()));
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+    markup
+}
+
+/// Returns the non-empty text between the marker at the start of the text and the next marker
+/// and the text after the closing marker.
+fn enclosed<'a>(text: &'a str, marker: &str) -> Option<(&'a str, &'a str)> {
+    let text = text.strip_prefix(marker)?;
+    let end = text.find(marker)?;
+    if end == 0 || text.starts_with(' ') {
+        return None;
+    }
+    Some((&text[..end], &text[end + marker.len()..]))
+}
+
+/// Returns the label and url of a link at the start of the text and the text after the link.
+fn link(text: &str) -> Option<(&str, &str, &str)> {
+    let text = text.strip_prefix('[')?;
+    let label_end = text.find("](")?;
+    let after_label = &text[label_end + 2..];
+    let url_end = after_label.find(')')?;
+    Some((
+        &text[..label_end],
+        after_label[..url_end].trim(),
+        &after_label[url_end + 1..],
+    ))
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_pango_markup_inline() {
+        assert_eq!(
+            to_pango_markup("Fill **all** cells with *exactly* `5` tiles."),
+            "Fill <b>all</b> cells with <i>exactly</i> <tt>5</tt> tiles."
+        );
+        assert_eq!(
+            to_pango_markup("Tiles <b> & 2 * 3 and \\*stars\\*"),
+            "Tiles &lt;b&gt; &amp; 2 * 3 and *stars*"
+        );
+    }
+
+    #[test]
+    fn test_to_pango_markup_links() {
+        assert_eq!(
+            to_pango_markup("See [the **wiki**](https://example.org/?a=1&b=2)."),
+            "See <a href=\"https://example.org/?a=1&amp;b=2\">the <b>wiki</b></a>."
+        );
+        assert_eq!(to_pango_markup("[Run](javascript:alert(1))"), "Run)");
+    }
+
+    #[test]
+    fn test_to_pango_markup_blocks() {
+        assert_eq!(
+            to_pango_markup("First line\ncontinued.\n\n\n- one\n* two\n2. three\n\nEnd\n\n"),
+            "First line continued.\n\n• one\n• two\n2. three\n\nEnd"
+        );
+    }
+}
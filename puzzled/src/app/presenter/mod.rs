@@ -0,0 +1,10 @@
+pub mod collection_row;
+#[cfg(test)]
+mod fixtures;
+pub mod insights;
+pub mod leaderboard_row;
+pub mod markdown;
+pub mod puzzle_info;
+pub mod puzzle_row;
+pub mod solve_summary;
+pub mod target_items;
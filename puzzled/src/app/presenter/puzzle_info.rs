@@ -0,0 +1,152 @@
+use crate::app::presenter::markdown;
+use puzzle_config::{PreviewConfig, PuzzleConfig};
+
+/// A row in the puzzle info dialog showing a single property.
+#[derive(Debug, PartialEq, Eq)]
+pub struct InfoRow {
+    pub title: String,
+    pub value: String,
+}
+
+impl InfoRow {
+    fn new(title: &str, value: &str) -> Self {
+        InfoRow {
+            title: title.to_string(),
+            value: value.to_string(),
+        }
+    }
+}
+
+/// The data shown by the puzzle info dialog.
+/// Groups without rows are hidden.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PuzzleInfo {
+    pub general: Vec<InfoRow>,
+    /// The description of the puzzle as Pango markup.
+    pub description: Option<String>,
+    pub show_tiles_preview: bool,
+    pub show_board_preview: bool,
+    pub statistics: Vec<InfoRow>,
+    pub additional_info: Vec<InfoRow>,
+}
+
+impl PuzzleInfo {
+    /// Creates the data for the info dialog of the given puzzle.
+    ///
+    /// # Arguments
+    ///
+    /// * `puzzle_config`: the puzzle to show
+    /// * `locked`: whether the puzzle is locked by the progression of the collection
+    /// * `preview_config`: what the collection allows to show about locked puzzles
+    ///
+    /// returns: PuzzleInfo
+    pub fn new(puzzle_config: &PuzzleConfig, locked: bool, preview_config: &PreviewConfig) -> Self {
+        PuzzleInfo {
+            general: general_rows(puzzle_config, locked, preview_config),
+            description: puzzle_config
+                .description()
+                .as_deref()
+                .map(markdown::to_pango_markup),
+            show_tiles_preview: !locked || preview_config.show_tiles(),
+            show_board_preview: !locked || preview_config.show_board(),
+            statistics: statistics_rows(puzzle_config),
+            additional_info: additional_info_rows(puzzle_config),
+        }
+    }
+}
+
+fn general_rows(
+    puzzle_config: &PuzzleConfig,
+    locked: bool,
+    preview_config: &PreviewConfig,
+) -> Vec<InfoRow> {
+    let mut rows = vec![InfoRow::new("Puzzle Name", puzzle_config.name())];
+
+    if !locked || preview_config.show_board_size() {
+        let (width, height) = puzzle_config.board_config().layout().dim();
+        rows.push(InfoRow::new(
+            "Board Dimensions",
+            &format!("{} x {}", width, height),
+        ));
+    }
+
+    if !locked || preview_config.show_tile_count() {
+        rows.push(InfoRow::new(
+            "Number of Tiles",
+            &format!("{}", puzzle_config.tiles().len()),
+        ));
+    }
+
+    rows
+}
+
+fn statistics_rows(puzzle_config: &PuzzleConfig) -> Vec<InfoRow> {
+    let mut rows = Vec::new();
+
+    if let Some(statistics) = puzzle_config.solution_statistics() {
+        rows.push(InfoRow::new(
+            "Minimum Solutions per Target",
+            &statistics.min().to_string(),
+        ));
+        rows.push(InfoRow::new(
+            "Maximum Solutions per Target",
+            &statistics.max().to_string(),
+        ));
+        rows.push(InfoRow::new(
+            "Average Solutions per Target",
+            &format!("{:.2}", statistics.average()),
+        ));
+        rows.push(InfoRow::new(
+            "Median Solutions per Target",
+            &statistics.median().to_string(),
+        ));
+        rows.push(InfoRow::new(
+            "Total Solutions",
+            &statistics.total().to_string(),
+        ));
+        if let Some(computed_on) = statistics.computed_on() {
+            let date = computed_on.date();
+            let computed_on = format!(
+                "{}-{:02}-{:02}",
+                date.year(),
+                u8::from(date.month()),
+                date.day()
+            );
+            rows.push(InfoRow::new("Computed On", &computed_on));
+        }
+    }
+
+    rows
+}
+
+fn additional_info_rows(puzzle_config: &PuzzleConfig) -> Vec<InfoRow> {
+    let mut rows = Vec::new();
+
+    if let Some(additional_info) = puzzle_config.additional_info() {
+        let mut entries: Vec<(&String, &String)> = additional_info.iter().collect();
+        entries.sort_by_key(|(title, _)| *title);
+        for (title, value) in entries {
+            rows.push(InfoRow::new(title, value));
+        }
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::presenter::fixtures::{assert_snapshot, load_fixture_collection};
+
+    #[test]
+    fn test_puzzle_infos() {
+        let collection = load_fixture_collection();
+        let infos: Vec<PuzzleInfo> = collection
+            .puzzles()
+            .iter()
+            .map(|puzzle| PuzzleInfo::new(puzzle, puzzle.index() > 1, collection.preview()))
+            .collect();
+
+        assert_snapshot("puzzle_infos", &infos);
+    }
+}
@@ -0,0 +1,101 @@
+use crate::model::stars::Stars;
+use puzzle_config::{PreviewConfig, PuzzleConfig};
+
+/// Used to set what the [crate::app::puzzle_selection::puzzle_mod::PuzzleMod] should display.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PuzzleModState {
+    /// Displays only the stars using [crate::app::components::stars::StarsView].
+    Stars(Stars),
+    /// Displays a lock and shows the text `Locked`.
+    Locked,
+    /// Displays a cross and the test `Unsolvable`.
+    Unsolvable,
+}
+
+/// The data shown by a row in the puzzle selection.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PuzzleRow {
+    pub name: String,
+    pub description: Option<String>,
+    pub difficulty: Option<String>,
+    pub state: PuzzleModState,
+    /// Whether the row can be activated to open the puzzle. Rows, which cannot be activated, are
+    /// dimmed.
+    pub activatable: bool,
+    /// The labels of the info pills. `None` if the pill is hidden.
+    pub board_size: Option<String>,
+    pub cell_count: Option<String>,
+    pub tile_count: Option<String>,
+    pub show_tile_preview: bool,
+    pub show_board_preview: bool,
+}
+
+impl PuzzleRow {
+    /// Creates the data for the row of the given puzzle.
+    ///
+    /// # Arguments
+    ///
+    /// * `puzzle_config`: the puzzle to show
+    /// * `preview_config`: what the collection allows to show about locked puzzles
+    /// * `locked`: whether the puzzle is locked by the progression of the collection
+    /// * `stars`: the stars reached for the puzzle
+    ///
+    /// returns: PuzzleRow
+    pub fn new(
+        puzzle_config: &PuzzleConfig,
+        preview_config: &PreviewConfig,
+        locked: bool,
+        stars: Stars,
+    ) -> Self {
+        let state = if locked {
+            PuzzleModState::Locked
+        } else if puzzle_config.is_unsolvable() {
+            PuzzleModState::Unsolvable
+        } else {
+            PuzzleModState::Stars(stars)
+        };
+
+        let layout = puzzle_config.board_config().layout();
+        let (width, height) = layout.dim();
+        let cell_count = layout.iter().filter(|c| **c).count();
+        let show_board_size = !locked || preview_config.show_board_size();
+        let show_tile_count = !locked || preview_config.show_tile_count();
+
+        PuzzleRow {
+            name: puzzle_config.name().to_string(),
+            description: puzzle_config.description().clone(),
+            difficulty: puzzle_config.difficulty().map(String::from),
+            state,
+            activatable: !locked,
+            board_size: show_board_size.then(|| format!("{} x {}", width, height)),
+            cell_count: show_board_size.then(|| format!("{}", cell_count)),
+            tile_count: show_tile_count.then(|| format!("{}", puzzle_config.tiles().len())),
+            show_tile_preview: !locked || preview_config.show_tiles(),
+            show_board_preview: !locked || preview_config.show_board(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::presenter::fixtures::{assert_snapshot, load_fixture_collection};
+    use crate::model::stars;
+
+    #[test]
+    fn test_puzzle_rows() {
+        let collection = load_fixture_collection();
+        let rows: Vec<PuzzleRow> = collection
+            .puzzles()
+            .iter()
+            .map(|puzzle| {
+                let locked = puzzle.index() > 1;
+                let stars =
+                    stars::calculate_stars(puzzle.index() == 0, Some(2), puzzle.difficulty());
+                PuzzleRow::new(puzzle, collection.preview(), locked, stars)
+            })
+            .collect();
+
+        assert_snapshot("puzzle_rows", &rows);
+    }
+}
@@ -0,0 +1,124 @@
+use ndarray::Array2;
+use puzzle_config::ColorConfig;
+use std::time::Duration;
+
+/// Marks a cell of the board, which is not covered by a tile, in the position text.
+const EMPTY_CELL: char = '.';
+
+/// The data shown by the solved dialog about how the puzzle was solved.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SolveSummary {
+    /// The time needed to solve the puzzle, if it was measured.
+    pub time: Option<String>,
+    /// The number of hints used.
+    pub hints: String,
+    /// The index of the tile covering each cell of the board as it was shown, indexed by `(x, y)`.
+    /// Cells without a tile, like the cells of the target, are `None`.
+    pub cells: Array2<Option<usize>>,
+    /// The colors of the tiles by their index.
+    pub tile_colors: Vec<ColorConfig>,
+    /// The final board as text, which can be shared with other players.
+    pub position: String,
+}
+
+impl SolveSummary {
+    /// Creates the summary of a solved puzzle.
+    ///
+    /// # Arguments
+    ///
+    /// * `puzzle_name`: the name of the puzzle
+    /// * `elapsed`: the time needed, if the timer was running
+    /// * `hint_count`: the number of hints used
+    /// * `cells`: the index of the tile covering each cell of the board
+    /// * `tile_colors`: the colors of the tiles of the puzzle
+    ///
+    /// returns: SolveSummary
+    pub fn new(
+        puzzle_name: &str,
+        elapsed: Option<Duration>,
+        hint_count: u32,
+        cells: Array2<Option<usize>>,
+        tile_colors: Vec<ColorConfig>,
+    ) -> Self {
+        let hints = match hint_count {
+            0 => "No hints".to_string(),
+            1 => "1 hint".to_string(),
+            hints => format!("{} hints", hints),
+        };
+        let time = elapsed.map(|elapsed| {
+            let seconds = elapsed.as_secs();
+            format!("{}:{:02}", seconds / 60, seconds % 60)
+        });
+        let position = position_text(puzzle_name, time.as_deref(), &hints, &cells);
+        SolveSummary {
+            time,
+            hints,
+            cells,
+            tile_colors,
+            position,
+        }
+    }
+}
+
+/// Describes the final board with one letter for each tile, so it can be pasted into a chat.
+/// Uncovered cells are shown as [EMPTY_CELL].
+fn position_text(
+    puzzle_name: &str,
+    time: Option<&str>,
+    hints: &str,
+    cells: &Array2<Option<usize>>,
+) -> String {
+    let mut lines = vec![match time {
+        Some(time) => format!("Puzzled · {} · {} · {}", puzzle_name, time, hints),
+        None => format!("Puzzled · {} · {}", puzzle_name, hints),
+    }];
+    let (width, height) = cells.dim();
+    for y in 0..height {
+        lines.push(
+            (0..width)
+                .map(|x| cells[(x, y)].map_or(EMPTY_CELL, tile_letter))
+                .collect(),
+        );
+    }
+    lines.join("\n")
+}
+
+/// Returns the letter of the tile with the given index. Letters are repeated for puzzles with
+/// more tiles than letters.
+fn tile_letter(index: usize) -> char {
+    const LETTERS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+    LETTERS[index % LETTERS.len()] as char
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::array;
+
+    #[test]
+    fn test_solve_summary() {
+        let cells = array![[Some(0), Some(1)], [None, Some(1)], [Some(27), Some(1)]];
+        let summary = SolveSummary::new(
+            "Monday",
+            Some(Duration::from_secs(125)),
+            1,
+            cells,
+            Vec::new(),
+        );
+
+        assert_eq!(summary.time.as_deref(), Some("2:05"));
+        assert_eq!(summary.hints, "1 hint");
+        assert_eq!(
+            summary.position,
+            "Puzzled · Monday · 2:05 · 1 hint\nA.b\nBBB"
+        );
+    }
+
+    #[test]
+    fn test_solve_summary_without_time() {
+        let summary = SolveSummary::new("Monday", None, 0, Array2::default((1, 1)), Vec::new());
+
+        assert_eq!(summary.time, None);
+        assert_eq!(summary.position, "Puzzled · Monday · No hints\n.");
+    }
+}
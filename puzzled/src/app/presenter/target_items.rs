@@ -0,0 +1,170 @@
+use puzzle_config::{AreaTarget, BoardConfig, Target, ANY_DISPLAY_VALUE};
+
+/// An item in the dropdown of an area in the target selection dialog.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TargetItem {
+    pub display_value: String,
+    pub area_target: AreaTarget,
+}
+
+/// The dropdown for an area in the target selection dialog.
+#[derive(Debug, PartialEq)]
+pub struct TargetArea {
+    pub name: String,
+    /// The values of the area in their value order, followed by an item allowing any value.
+    pub items: Vec<TargetItem>,
+    /// The index of the item in the current target, if there is one.
+    pub selected: Option<usize>,
+}
+
+/// Formats the selected target, so it can be shown on the target selection button and in the
+/// title.
+///
+/// # Arguments
+///
+/// * `board_config`: the board the target is selected for
+/// * `target`: the selected target
+///
+/// returns: Option<String> `None`, if the board has no areas or no target is selected.
+pub fn target_label(board_config: &BoardConfig, target: &Option<Target>) -> Option<String> {
+    match (board_config, target) {
+        (BoardConfig::Area { .. }, Some(target)) => Some(board_config.format_target(target)),
+        _ => None,
+    }
+}
+
+/// Creates the title of the puzzle page.
+/// The selected target is appended, so it stays visible, when the target selection button is
+/// truncated in narrow windows.
+///
+/// # Arguments
+///
+/// * `collection_name`: the name of the collection of the puzzle
+/// * `puzzle_name`: the name of the puzzle
+/// * `target_label`: the formatted target, see [target_label]
+///
+/// returns: String
+pub fn page_title(collection_name: &str, puzzle_name: &str, target_label: Option<&str>) -> String {
+    match target_label {
+        Some(target_label) => format!("{} - {} · {}", collection_name, puzzle_name, target_label),
+        None => format!("{} - {}", collection_name, puzzle_name),
+    }
+}
+
+/// Creates the dropdowns for the target selection dialog.
+/// Simple boards have no areas, so no dropdowns are returned for them.
+///
+/// # Arguments
+///
+/// * `board_config`: the board to select the target for
+/// * `current_selection`: the currently selected target
+///
+/// returns: Vec<TargetArea>
+pub fn target_areas(
+    board_config: &BoardConfig,
+    current_selection: &Option<Target>,
+) -> Vec<TargetArea> {
+    let (area_configs, value_order) = match board_config {
+        BoardConfig::Simple { .. } => return Vec::new(),
+        BoardConfig::Area {
+            area_configs,
+            value_order,
+            ..
+        } => (area_configs, value_order),
+    };
+
+    area_configs
+        .iter()
+        .enumerate()
+        .map(|(area_index, area_config)| {
+            let mut items: Vec<TargetItem> = board_config
+                .get_display_values_for_area(area_index as i32)
+                .into_iter()
+                .map(|(display_value, target_index)| TargetItem {
+                    display_value,
+                    area_target: AreaTarget::Index(target_index),
+                })
+                .collect();
+            items.sort_by_key(|item| {
+                item.area_target
+                    .index()
+                    .and_then(|target_index| value_order.get((target_index.0, target_index.1)))
+                    .cloned()
+                    .unwrap_or(i32::MAX)
+            });
+            items.push(TargetItem {
+                display_value: ANY_DISPLAY_VALUE.to_string(),
+                area_target: AreaTarget::Any,
+            });
+
+            let selected = current_selection
+                .as_ref()
+                .and_then(|target| target.indices.get(area_index))
+                .and_then(|area_target| {
+                    items
+                        .iter()
+                        .position(|item| item.area_target == *area_target)
+                });
+
+            TargetArea {
+                name: area_config.name().to_string(),
+                items,
+                selected,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::presenter::fixtures::{assert_snapshot, load_fixture_collection};
+    use puzzle_config::TargetIndex;
+
+    #[test]
+    fn test_target_areas() {
+        let collection = load_fixture_collection();
+        let board_config = collection.puzzles()[2].board_config();
+        let current_selection = Some(Target {
+            indices: vec![AreaTarget::Index(TargetIndex(1, 0)), AreaTarget::Any],
+        });
+
+        assert_snapshot(
+            "target_areas",
+            &target_areas(board_config, &current_selection),
+        );
+        assert_snapshot(
+            "target_areas_without_selection",
+            &target_areas(board_config, &None),
+        );
+        assert!(target_areas(collection.puzzles()[0].board_config(), &None).is_empty());
+    }
+
+    #[test]
+    fn test_target_label() {
+        let collection = load_fixture_collection();
+        let board_config = collection.puzzles()[2].board_config();
+
+        assert_eq!(
+            target_label(board_config, &board_config.default_target()),
+            Some("Jan 1st".to_string())
+        );
+        assert_eq!(target_label(board_config, &None), None);
+        assert_eq!(
+            target_label(collection.puzzles()[0].board_config(), &None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_page_title() {
+        assert_eq!(
+            page_title("Collection", "Calendar", Some("Jan 1st")),
+            "Collection - Calendar · Jan 1st"
+        );
+        assert_eq!(
+            page_title("Collection", "Calendar", None),
+            "Collection - Calendar"
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use crate::model::puzzle_meta::PuzzleMeta;
+use crate::sync;
+use crate::sync::error::SyncError;
+use crate::sync::progress::Progress;
+use crate::window::PuzzledWindow;
+use adw::prelude::{AdwDialogExt, AlertDialogExt, FileExt, FileExtManual, StaticType};
+use adw::{gio, AlertDialog, ResponseAppearance};
+use gtk::gdk;
+use gtk::prelude::WidgetExt;
+use log::{debug, info};
+
+impl PuzzledWindow {
+    /// Lets the player drop a progress file anywhere on the window to restore it.
+    /// The progress is only applied after the player confirmed the changes.
+    pub(crate) fn setup_progress_drop(&self) {
+        let drop_target = gtk::DropTarget::new(gio::File::static_type(), gdk::DragAction::COPY);
+        drop_target.connect_drop({
+            let self_clone = self.clone();
+            move |_, value, _, _| {
+                let Ok(file) = value.get::<gio::File>() else {
+                    return false;
+                };
+                self_clone.load_progress_file(&file);
+                true
+            }
+        });
+        self.add_controller(drop_target);
+    }
+
+    fn load_progress_file(&self, file: &gio::File) {
+        debug!("Loading dropped progress file {}", file.uri());
+        let result = file
+            .load_contents(None::<&gio::Cancellable>)
+            .map_err(|e| e.to_string())
+            .and_then(|(bytes, _etag)| {
+                std::str::from_utf8(bytes.as_ref())
+                    .map_err(|e| e.to_string())
+                    .and_then(|json| Progress::from_json(json).map_err(progress_error_message))
+            });
+        match result {
+            Ok(progress) => self.confirm_progress(progress),
+            Err(message) => self.show_progress_message("Cannot Restore Progress", &message),
+        }
+    }
+
+    /// Asks the player, whether the progress should be restored, describing what will change.
+    /// The dropped progress is merged with the local one like a synced progress, so no solved
+    /// puzzle is lost.
+    fn confirm_progress(&self, progress: Progress) {
+        let local = PuzzleMeta::new().progress();
+        let merged = local.merge(&progress);
+        let changes = local.changes(&merged);
+        if changes.is_empty() {
+            self.show_progress_message(
+                "Nothing to Restore",
+                "The file does not contain any progress, which is not already stored.",
+            );
+            return;
+        }
+
+        let dialog = AlertDialog::builder()
+            .heading("Restore Progress?")
+            .body(format!(
+                "{} puzzles will be marked as solved and the hints of {} puzzles will be updated.",
+                changes.solved, changes.hints
+            ))
+            .build();
+        let cancel_id = "cancel";
+        let restore_id = "restore";
+        dialog.add_response(cancel_id, "Cancel");
+        dialog.add_response(restore_id, "Restore");
+        dialog.set_default_response(Some(restore_id));
+        dialog.set_close_response(cancel_id);
+        dialog.set_response_appearance(restore_id, ResponseAppearance::Suggested);
+        dialog.connect_response(Some(restore_id), move |_, _| {
+            info!("Restoring dropped progress");
+            sync::apply_progress(&merged);
+        });
+        dialog.present(Some(self));
+    }
+
+    fn show_progress_message(&self, heading: &str, message: &str) {
+        let dialog = AlertDialog::builder()
+            .heading(heading)
+            .body(message)
+            .build();
+
+        let ok_id = "ok";
+        dialog.add_response(ok_id, "OK");
+        dialog.set_default_response(Some(ok_id));
+        dialog.set_close_response(ok_id);
+        dialog.set_response_appearance(ok_id, ResponseAppearance::Suggested);
+        dialog.present(Some(self));
+    }
+}
+
+/// Returns a message for the player explaining why the dropped file could not be read.
+fn progress_error_message(error: SyncError) -> String {
+    match error {
+        SyncError::UnsupportedVersion(version) => format!(
+            "The progress file was written by a newer version of Puzzled (version {}).",
+            version
+        ),
+        SyncError::MalformedProgress(message) => {
+            format!("The file is not a valid progress file: {}", message)
+        }
+        error => format!("The progress file could not be read: {:?}", error),
+    }
+}
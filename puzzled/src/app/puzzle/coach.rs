@@ -0,0 +1,75 @@
+use crate::app::puzzle::puzzle_page::PuzzlePage;
+use crate::global::events;
+use crate::global::events::AppEvent;
+use crate::global::idle_monitor;
+use crate::global::settings::{CoachDelay, CoachMode, Preferences};
+use crate::model::inactivity::InactivityTracker;
+use adw::subclass::prelude::ObjectSubclassIsExt;
+use gtk::glib;
+use gtk::glib::ControlFlow;
+
+/// How often the coach checks, whether the player is stuck.
+const COACH_CHECK_INTERVAL_SECS: u32 = 10;
+
+impl PuzzlePage {
+    /// Counts moved tiles as progress and hides the coach banner, when the player continues.
+    pub(super) fn setup_coach(&self) {
+        events::subscribe({
+            let self_clone = self.clone();
+            move |event| {
+                if *event == AppEvent::TileMoved {
+                    self_clone.reset_coach();
+                }
+            }
+        });
+        idle_monitor::connect_idle_changed({
+            let self_clone = self.clone();
+            move |idle| {
+                if !idle {
+                    self_clone.imp().coach.borrow_mut().record_progress();
+                }
+            }
+        });
+    }
+
+    /// Starts checking whether the player is stuck on the shown puzzle.
+    pub(super) fn start_coach(&self) {
+        self.stop_coach();
+        self.imp().coach.replace(InactivityTracker::default());
+        let source_id = glib::timeout_add_seconds_local(COACH_CHECK_INTERVAL_SECS, {
+            let self_clone = self.clone();
+            move || {
+                self_clone.offer_hint_if_stuck();
+                ControlFlow::Continue
+            }
+        });
+        self.imp().coach_check.replace(Some(source_id));
+    }
+
+    /// Stops checking whether the player is stuck and hides the coach banner.
+    pub(super) fn stop_coach(&self) {
+        if let Some(source_id) = self.imp().coach_check.take() {
+            source_id.remove();
+        }
+        self.imp().coach_banner.set_revealed(false);
+    }
+
+    /// Hides the coach banner and waits for the player to get stuck again.
+    pub(super) fn reset_coach(&self) {
+        self.imp().coach.borrow_mut().record_progress();
+        self.imp().coach_banner.set_revealed(false);
+    }
+
+    /// Reveals the coach banner, if the player enabled coach mode and made no progress for the
+    /// configured delay. Nothing is offered while the session is idle or no hint can be requested.
+    fn offer_hint_if_stuck(&self) {
+        let preferences = Preferences::default();
+        if !preferences.get(CoachMode) || idle_monitor::is_idle() || !self.can_request_hint() {
+            return;
+        }
+        let delay = preferences.get(CoachDelay);
+        if self.imp().coach.borrow_mut().should_offer(delay) {
+            self.imp().coach_banner.set_revealed(true);
+        }
+    }
+}
@@ -0,0 +1,45 @@
+use crate::app::puzzle::puzzle_page::PuzzlePage;
+use adw::subclass::prelude::ObjectSubclassIsExt;
+use gtk::prelude::WidgetExt;
+use puzzle_config::{AreaCoverage, BoardConfig, Target};
+
+impl PuzzlePage {
+    /// Updates the coverage info popover for the current puzzle and the given target.
+    ///
+    /// # Arguments
+    ///
+    /// * `target`: The currently selected target or `None`, if no target is selected.
+    ///
+    /// returns: ()
+    pub(super) fn update_coverage_info(&self, target: Option<&Target>) {
+        let puzzle = self.imp().puzzle.borrow();
+        let Some(puzzle) = puzzle.as_ref() else {
+            return;
+        };
+        let puzzle_config = puzzle.config();
+        let coverage = AreaCoverage::new(puzzle_config, target);
+
+        let needs_target =
+            matches!(puzzle_config.board_config(), BoardConfig::Area { .. }) && target.is_none();
+        let text = if needs_target {
+            format!(
+                "Tiles cover {} cells, board has {} cells.\nSelect a target to see how many cells need to be covered.",
+                coverage.tile_cells(),
+                coverage.board_cells()
+            )
+        } else {
+            format!(
+                "Tiles cover {} cells, board needs {}.",
+                coverage.tile_cells(),
+                coverage.board_cells()
+            )
+        };
+        let label = &self.imp().coverage_label;
+        label.set_label(&text);
+        if needs_target || coverage.is_plausible() {
+            label.remove_css_class("error");
+        } else {
+            label.add_css_class("error");
+        }
+    }
+}
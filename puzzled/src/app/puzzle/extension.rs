@@ -1,21 +1,25 @@
+use crate::app::presenter::target_items::{self, TargetArea};
+use crate::app::puzzle::puzzle_area::puzzle_state::{PuzzleState, UnusedTile};
 use crate::app::puzzle::puzzle_page::PuzzlePage;
 use crate::model::extension::PuzzleTypeExtension;
+use crate::solver::queue::SolverPriority;
+use crate::solver::Solver;
+use crate::ui_bridge;
 use adw::prelude::{
     AdwDialogExt, AlertDialogExt, AlertDialogExtManual, ComboRowExt, PreferencesGroupExt,
     PreferencesPageExt,
 };
 use adw::subclass::prelude::ObjectSubclassIsExt;
-use adw::{AlertDialog, ComboRow, PreferencesGroup, PreferencesPage, ResponseAppearance};
+use adw::{AlertDialog, ComboRow, PreferencesGroup, PreferencesPage, ResponseAppearance, Toast};
 use gtk::prelude::{ButtonExt, WidgetExt};
 use gtk::StringList;
-use ndarray::Array2;
-use puzzle_config::{AreaConfig, BoardConfig, PuzzleConfig, Target, TargetIndex};
+use puzzle_config::{AreaTarget, BoardConfig, PuzzleConfig, Target};
+use puzzle_solver::result::UnsolvableReason;
+use std::cell::RefCell;
+use tokio_util::sync::CancellationToken;
 
-#[derive(Debug, Clone, PartialEq)]
-struct TargetIndexListItem {
-    display_value: String,
-    target_index: TargetIndex,
-}
+/// How many random targets are tried, before giving up on finding a solvable one.
+const MAX_RANDOM_TARGET_ATTEMPTS: u32 = 20;
 
 impl PuzzlePage {
     pub fn show_puzzle_extension(&self) {
@@ -24,14 +28,17 @@ impl PuzzlePage {
             None => {
                 self.imp().extension_separator.get().set_visible(false);
                 self.imp().target_selection_button.set_visible(false);
+                self.imp().random_target_button.set_visible(false);
             }
             Some(PuzzleTypeExtension::Simple) => {
                 self.imp().extension_separator.get().set_visible(false);
                 self.imp().target_selection_button.set_visible(false);
+                self.imp().random_target_button.set_visible(false);
             }
             Some(PuzzleTypeExtension::Area { .. }) => {
                 self.imp().extension_separator.get().set_visible(true);
                 self.imp().target_selection_button.set_visible(true);
+                self.imp().random_target_button.set_visible(true);
             }
         }
         self.update_target_selection_button();
@@ -42,6 +49,99 @@ impl PuzzlePage {
         dialog.present(self.imp().window.get());
     }
 
+    /// Selects a random target for the current puzzle.
+    ///
+    /// Unless the puzzle is marked as unsolvable, the solver is used to check, that the puzzle
+    /// can be solved for the chosen target from an empty board. If it cannot, another random
+    /// target is tried up to [MAX_RANDOM_TARGET_ATTEMPTS] times.
+    pub(crate) fn select_random_target(&self) {
+        self.try_random_target(MAX_RANDOM_TARGET_ATTEMPTS);
+    }
+
+    fn try_random_target(&self, remaining_attempts: u32) {
+        let puzzle = self.imp().puzzle.borrow().clone();
+        let Some(puzzle) = puzzle else {
+            return;
+        };
+        let puzzle_config = puzzle.config();
+        let Some(target) = puzzle_config.board_config().random_target(&mut rand::rng()) else {
+            return;
+        };
+        let extension = Some(PuzzleTypeExtension::Area {
+            target: Some(target),
+        });
+
+        if puzzle_config.is_unsolvable() {
+            self.update_extension(&extension);
+            return;
+        }
+
+        let puzzle_state = Self::create_empty_puzzle_state(puzzle_config, &extension);
+        let sender = ui_bridge::oneshot({
+            let self_clone = self.clone();
+            move |result| match result {
+                Some(Ok(_)) => {
+                    self_clone.imp().random_target_button.set_sensitive(true);
+                    self_clone.update_extension(&extension);
+                }
+                Some(Err(UnsolvableReason::Cancelled)) | None => {
+                    self_clone.imp().random_target_button.set_sensitive(true);
+                }
+                Some(Err(_)) => {
+                    if remaining_attempts > 1 {
+                        self_clone.try_random_target(remaining_attempts - 1);
+                    } else {
+                        self_clone.imp().random_target_button.set_sensitive(true);
+                        self_clone
+                            .imp()
+                            .toast_overlay
+                            .add_toast(Toast::new("No solvable target found"));
+                    }
+                }
+            }
+        });
+
+        self.imp().random_target_button.set_sensitive(false);
+        // The check runs beside hints and other solver calls instead of interrupting them.
+        let cancel_token = CancellationToken::new();
+        self.imp()
+            .random_target_cancel_token
+            .replace(Some(cancel_token.clone()));
+        Solver::default().solve_in_background(
+            &puzzle_state,
+            SolverPriority::Batch,
+            Box::new(move |result| sender.send(result)),
+            cancel_token,
+        );
+    }
+
+    /// Stops the search for a solvable random target, so its result is not applied to another
+    /// puzzle.
+    pub(super) fn cancel_random_target(&self) {
+        if let Some(cancel_token) = self.imp().random_target_cancel_token.take() {
+            cancel_token.cancel();
+        }
+        self.imp().random_target_button.set_sensitive(true);
+    }
+
+    /// Creates the state of the given puzzle with no tiles placed on the board.
+    pub(super) fn create_empty_puzzle_state(
+        puzzle_config: &PuzzleConfig,
+        extension: &Option<PuzzleTypeExtension>,
+    ) -> PuzzleState {
+        let extension = RefCell::new(extension.clone());
+        let mut puzzle_state = PuzzleState::new(puzzle_config.board_config(), extension.borrow());
+        puzzle_state.tile_constraints = puzzle_config.tile_constraints().to_vec();
+        for (i, tile) in puzzle_config.tiles().iter().enumerate() {
+            puzzle_state.unused_tiles.insert(UnusedTile {
+                id: i,
+                base: tile.base().clone(),
+                name: tile.name().clone(),
+            });
+        }
+        puzzle_state
+    }
+
     pub(crate) fn update_target_selection_button(&self) {
         let puzzle = self.imp().puzzle.borrow();
         let puzzle_config = puzzle.as_ref().map(|p| p.config());
@@ -54,14 +154,10 @@ impl PuzzlePage {
                 }
                 Some(PuzzleTypeExtension::Area { target }) => {
                     self.imp().target_selection_button.set_visible(true);
-                    if let Some(target) = target {
-                        let target_string = &*puzzle_config.board_config().format_target(target);
-                        self.imp().target_selection_button.set_label(target_string);
-                    } else {
-                        self.imp()
-                            .target_selection_button
-                            .set_label("Select Target");
-                    }
+                    let label = target_items::target_label(puzzle_config.board_config(), target);
+                    self.imp()
+                        .target_selection_button
+                        .set_label(label.as_deref().unwrap_or("Select Target"));
                 }
             }
         }
@@ -80,26 +176,14 @@ impl PuzzlePage {
             Some(PuzzleTypeExtension::Area { target }) => target,
             _ => &None,
         };
-        let (area_configs, area_count) = match &puzzle_config.board_config() {
-            BoardConfig::Area { area_configs, .. } => {
-                (area_configs, puzzle_config.board_config().area_count())
-            }
-            _ => return dialog,
-        };
-        let mut area_items: Vec<Vec<TargetIndexListItem>> = Vec::new();
-        let mut dropdowns: Vec<ComboRow> = Vec::new();
-        for area_index in 0..area_count {
-            let (items, dropdown) = Self::create_dropdown_for_area(
-                &content,
-                puzzle_config,
-                current_selection,
-                &area_configs,
-                area_index,
-            );
-            area_items.push(items);
-            dropdowns.push(dropdown);
+        if let BoardConfig::Simple { .. } = puzzle_config.board_config() {
+            return dialog;
         }
-        let dropdowns = dropdowns;
+        let areas = target_items::target_areas(puzzle_config.board_config(), current_selection);
+        let dropdowns: Vec<ComboRow> = areas
+            .iter()
+            .map(|area| Self::create_dropdown_for_area(&content, area))
+            .collect();
 
         let accept_id = "accept";
         let cancel_id = "cancel";
@@ -121,11 +205,11 @@ impl PuzzlePage {
             let self_clone = self.clone();
             move |_, _| {
                 dbg!("Accepted target selection");
-                let mut selected_values: Vec<TargetIndex> = Vec::new();
+                let mut selected_values: Vec<AreaTarget> = Vec::new();
                 for (i, dropdown) in dropdowns.iter().enumerate() {
                     let sel = dropdown.selected();
-                    if (sel as usize) < area_items[i].len() {
-                        selected_values.push(area_items[i][sel as usize].target_index.clone());
+                    if let Some(item) = areas[i].items.get(sel as usize) {
+                        selected_values.push(item.area_target.clone());
                     }
                 }
                 self_clone.update_extension(&Some(PuzzleTypeExtension::Area {
@@ -150,60 +234,22 @@ impl PuzzlePage {
         dialog
     }
 
-    fn create_dropdown_for_area(
-        content: &PreferencesGroup,
-        puzzle_config: &PuzzleConfig,
-        current_selection: &Option<Target>,
-        area_configs: &&Vec<AreaConfig>,
-        area_index: usize,
-    ) -> (Vec<TargetIndexListItem>, ComboRow) {
-        let mut items: Vec<TargetIndexListItem> = puzzle_config
-            .board_config()
-            .get_display_values_for_area(area_index as i32)
-            .iter()
-            .map(|(display_value, target_index)| TargetIndexListItem {
-                display_value: display_value.clone(),
-                target_index: target_index.clone(),
-            })
-            .collect();
-        let value_order: &Array2<i32> = match puzzle_config.board_config() {
-            BoardConfig::Simple { .. } => {
-                return (Vec::new(), ComboRow::builder().build());
-            }
-            BoardConfig::Area { value_order, .. } => value_order,
-        };
-        items.sort_by(|a, b| {
-            let first = value_order
-                .get((a.target_index.0, a.target_index.1))
-                .cloned()
-                .unwrap_or(i32::MAX);
-            let second = value_order
-                .get((b.target_index.0, b.target_index.1))
-                .cloned()
-                .unwrap_or(i32::MAX);
-
-            first.cmp(&second)
-        });
-
+    fn create_dropdown_for_area(content: &PreferencesGroup, area: &TargetArea) -> ComboRow {
         let string_list = StringList::new(&[]);
-        for it in &items {
-            string_list.append(&it.display_value);
+        for item in &area.items {
+            string_list.append(&item.display_value);
         }
 
         let dropdown = ComboRow::builder()
-            .title(area_configs[area_index].name())
+            .title(area.name.as_str())
             .model(&string_list)
             .build();
 
-        if let Some(idx) = current_selection
-            .as_ref()
-            .and_then(|sel| sel.indices.get(area_index))
-            .and_then(|target_index| items.iter().position(|i| i.target_index == *target_index))
-        {
+        if let Some(idx) = area.selected {
             dropdown.set_selected(idx as u32);
         }
 
         content.add(&dropdown);
-        (items, dropdown)
+        dropdown
     }
 }
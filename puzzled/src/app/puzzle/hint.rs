@@ -1,38 +1,67 @@
 use crate::app::puzzle::puzzle_area::puzzle_state::PuzzleState;
 use crate::app::puzzle::puzzle_page::PuzzlePage;
 use crate::model::extension::PuzzleTypeExtension;
-use crate::solver::Solver;
-use adw::prelude::Cast;
+use crate::solver::{conflict, Solver};
+use crate::ui_bridge;
+use adw::gio;
+use adw::prelude::{AdwDialogExt, AlertDialogExt, ApplicationExt, Cast, CastNone};
 use adw::subclass::prelude::ObjectSubclassIsExt;
-use adw::{glib, Toast};
-use gtk::prelude::{BoxExt, ButtonExt, WidgetExt};
+use adw::{AlertDialog, ResponseAppearance, Toast};
+use gtk::glib;
+use gtk::glib::ControlFlow;
+use gtk::prelude::{BoxExt, ButtonExt, GtkWindowExt, WidgetExt};
 use gtk::{Image, Label, Widget};
+use puzzle_config::HintConfig;
 use puzzle_solver::result::{Solution, UnsolvableReason};
-use std::sync::mpsc;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
 use tokio_util::sync::CancellationToken;
 
+/// Number of quarters in a cell, in which the solver measures areas.
+const QUARTERS_PER_CELL: f32 = 4.0;
+/// How long to wait for a hint, before showing the [HintProgressDialog].
+const HINT_PROGRESS_DIALOG_DELAY: Duration = Duration::from_secs(1);
+/// How long a hint has to take, before the player is notified about it, while the window is not
+/// focused.
+const HINT_NOTIFICATION_DELAY: Duration = Duration::from_secs(5);
+/// The id of the notification about a finished hint, so a newer one replaces an older one.
+const HINT_NOTIFICATION_ID: &str = "hint-finished";
+
 pub type OnComplete = Box<dyn Fn(Result<Solution, UnsolvableReason>)>;
 
 impl PuzzlePage {
     pub fn on_hint_requested(&self) {
+        if !self.can_request_hint() {
+            return;
+        }
+        self.reset_coach();
         let puzzle_state = self.imp().grid.extract_puzzle_state();
 
         if let Ok(puzzle_state) = puzzle_state {
             self.imp().grid.remove_hint_tile();
             self.calculate_hint(&puzzle_state, {
                 let self_clone = self.clone();
+                let puzzle_state = puzzle_state.clone();
                 Box::new(move |result| {
                     self_clone.imp().toast_overlay.dismiss_all();
                     let hint_count = self_clone.imp().hint_count.get();
                     self_clone.imp().hint_count.replace(hint_count + 1);
+                    self_clone.start_hint_cooldown();
                     match result {
                         Ok(solution) => {
                             if let Some(placement) = solution.placements().last() {
                                 self_clone.imp().grid.show_hint_tile(placement)
                             }
+                            if !solution.is_complete() {
+                                self_clone.imp().toast_overlay.add_toast(Toast::new(
+                                    "Partial hint: this placement may not lead to a solution",
+                                ));
+                            }
                         }
                         Err(unsolvable_reason) => {
                             self_clone.show_unsolvable_toast(unsolvable_reason);
+                            self_clone.highlight_conflicting_tiles(&puzzle_state);
                         }
                     }
                 })
@@ -40,6 +69,90 @@ impl PuzzlePage {
         }
     }
 
+    /// Resets the hint button for a newly shown puzzle.
+    /// The button is hidden, if the puzzle does not allow hints.
+    pub fn reset_hint_button(&self) {
+        self.stop_hint_cooldown();
+        let enabled = self
+            .hint_config()
+            .map(|hint_config| hint_config.is_enabled())
+            .unwrap_or(true);
+        self.imp().hint_button.set_visible(enabled);
+        self.update_hint_button();
+    }
+
+    fn hint_config(&self) -> Option<HintConfig> {
+        self.imp()
+            .puzzle
+            .borrow()
+            .as_ref()
+            .map(|puzzle| puzzle.config().hint_config().clone())
+    }
+
+    /// Returns the number of hints the player can still request for the current puzzle or
+    /// `None`, if there is no limit.
+    fn remaining_hints(&self) -> Option<u32> {
+        self.hint_config()
+            .and_then(|hint_config| hint_config.remaining_hints(self.imp().hint_count.get()))
+    }
+
+    pub(super) fn can_request_hint(&self) -> bool {
+        let enabled = self
+            .hint_config()
+            .map(|hint_config| hint_config.is_enabled())
+            .unwrap_or(true);
+        enabled && self.remaining_hints() != Some(0) && self.imp().hint_cooldown.borrow().is_none()
+    }
+
+    /// Starts the cooldown of the current puzzle after a hint was shown, and counts it down on the
+    /// hint button.
+    fn start_hint_cooldown(&self) {
+        self.stop_hint_cooldown();
+        let cooldown = self
+            .hint_config()
+            .and_then(|hint_config| hint_config.cooldown())
+            .map(|cooldown| cooldown.as_secs() as u32)
+            .unwrap_or(0);
+        if cooldown == 0 || self.remaining_hints() == Some(0) {
+            self.update_hint_button();
+            return;
+        }
+
+        let remaining_secs = Rc::new(Cell::new(cooldown));
+        self.display_state(&HintButtonState::Cooldown(cooldown));
+        let source_id = glib::timeout_add_seconds_local(1, {
+            let self_clone = self.clone();
+            move || {
+                let secs = remaining_secs.get() - 1;
+                remaining_secs.set(secs);
+                if secs == 0 {
+                    self_clone.imp().hint_cooldown.replace(None);
+                    self_clone.update_hint_button();
+                    return ControlFlow::Break;
+                }
+                self_clone.display_state(&HintButtonState::Cooldown(secs));
+                ControlFlow::Continue
+            }
+        });
+        self.imp().hint_cooldown.replace(Some(source_id));
+        self.action_set_enabled("app.hint", false);
+    }
+
+    fn stop_hint_cooldown(&self) {
+        if let Some(source_id) = self.imp().hint_cooldown.take() {
+            source_id.remove();
+        }
+    }
+
+    /// Shows whether the player can request another hint on the hint button.
+    fn update_hint_button(&self) {
+        if self.remaining_hints() == Some(0) {
+            self.display_state(&HintButtonState::Exhausted);
+        } else {
+            self.display_state(&HintButtonState::Bulb);
+        }
+    }
+
     /// Calls the solver and updates the hint button state.
     ///
     /// When the solver is finished, the `on_complete` callback will be called with the result of
@@ -62,41 +175,77 @@ impl PuzzlePage {
         if calculate_solvability {
             self.calculate_solvability(puzzle_state, on_complete);
         } else {
-            self.display_state(&HintButtonState::Bulb);
+            self.update_hint_button();
         }
     }
 
     fn calculate_solvability(&self, puzzle_state: &PuzzleState, on_complete: OnComplete) {
-        let (tx, rx) = mpsc::channel::<Result<Solution, UnsolvableReason>>();
-        glib::idle_add_local({
+        let cancel_token = CancellationToken::new();
+        let partial_requested = Rc::new(Cell::new(false));
+        let progress_dialog = HintProgressDialog::schedule(self, &cancel_token, &partial_requested);
+        let started = Instant::now();
+        let sender = ui_bridge::oneshot({
             let self_clone = self.clone();
-            move || match rx.try_recv() {
-                Ok(result) => {
-                    self_clone.display_state(&HintButtonState::Bulb);
-                    on_complete(result);
-                    glib::ControlFlow::Break
+            move |result| {
+                progress_dialog.close();
+                self_clone.update_hint_button();
+                match result {
+                    Some(Err(UnsolvableReason::Cancelled)) | None => {}
+                    Some(Ok(solution)) if !solution.is_complete() && !partial_requested.get() => {}
+                    Some(result) => {
+                        if started.elapsed() >= HINT_NOTIFICATION_DELAY {
+                            self_clone.notify_hint_finished(result.is_ok());
+                        }
+                        on_complete(result)
+                    }
                 }
-                Err(mpsc::TryRecvError::Empty) => glib::ControlFlow::Continue,
-                Err(mpsc::TryRecvError::Disconnected) => glib::ControlFlow::Break,
             }
         });
 
-        let cancel_token = CancellationToken::new();
         let solver = Solver::default();
         self.display_state(&HintButtonState::Calculating);
         solver.interrupt_solver_call();
-        solver.solve_for_target(
+        solver.solve_for_hint(
             puzzle_state,
-            Box::new(move |result| {
-                let _ = tx.send(result);
-            }),
+            Box::new(move |result| sender.send(result)),
             cancel_token,
         );
     }
 
+    /// Sends a desktop notification about the finished hint, if the window is not focused.
+    /// Activating the notification focuses the window, which shows the result.
+    ///
+    /// # Arguments
+    ///
+    /// * `found`: Whether a hint was found or the puzzle cannot be solved.
+    ///
+    /// returns: ()
+    fn notify_hint_finished(&self, found: bool) {
+        let window_active = self
+            .root()
+            .and_downcast::<gtk::Window>()
+            .is_some_and(|window| window.is_active());
+        if window_active {
+            return;
+        }
+        let Some(application) = gio::Application::default() else {
+            return;
+        };
+
+        let notification = gio::Notification::new("Hint Ready");
+        notification.set_body(Some(if found {
+            "The hint for your puzzle was found."
+        } else {
+            "Your puzzle cannot be solved from its current state."
+        }));
+        notification.set_default_action("app.present_window");
+        application.send_notification(Some(HINT_NOTIFICATION_ID), &notification);
+    }
+
     fn display_state(&self, status: &HintButtonState) {
         match status {
             HintButtonState::Bulb => {
+                self.action_set_enabled("app.hint", true);
                 self.imp().hint_button.set_tooltip_text(Some("Hint"));
                 self.imp().hint_button.set_icon_name("lightbulb-symbolic");
             }
@@ -106,9 +255,42 @@ impl PuzzlePage {
                     .set_tooltip_text(Some("Hint: Calculating..."));
                 self.imp().hint_button.set_icon_name("timer-sand-symbolic");
             }
+            HintButtonState::Cooldown(secs) => {
+                self.imp()
+                    .hint_button
+                    .set_tooltip_text(Some("Hint: Wait for the next hint"));
+                self.imp().hint_button.set_label(&format!("{}s", secs));
+            }
+            HintButtonState::Exhausted => {
+                self.action_set_enabled("app.hint", false);
+                self.imp()
+                    .hint_button
+                    .set_tooltip_text(Some("Hint: No hints left for this puzzle"));
+                self.imp().hint_button.set_icon_name("lightbulb-symbolic");
+            }
         }
     }
 
+    /// Searches the placed tiles, which likely prevent a solution, and highlights them, so the
+    /// player knows which tiles to move.
+    fn highlight_conflicting_tiles(&self, puzzle_state: &PuzzleState) {
+        if puzzle_state.placed_tiles.is_empty() {
+            return;
+        }
+        conflict::find_conflicting_tiles(puzzle_state, {
+            let self_clone = self.clone();
+            move |tile_ids| {
+                if tile_ids.is_empty() {
+                    return;
+                }
+                self_clone.imp().grid.highlight_conflicting_tiles(&tile_ids);
+                self_clone.imp().toast_overlay.add_toast(Toast::new(
+                    "The highlighted tiles are likely in the way of a solution",
+                ));
+            }
+        });
+    }
+
     fn show_unsolvable_toast(&self, unsolvable_reason: UnsolvableReason) {
         fn build_label(content: &str) -> Widget {
             Label::builder().label(content).build().upcast()
@@ -141,10 +323,37 @@ impl PuzzlePage {
                     ),
                 ]
             }
-            UnsolvableReason::PlausibilityCheckFailed => {
+            UnsolvableReason::AreaMismatch {
+                tiles_area,
+                board_area,
+            } => {
+                let tiles_cells = tiles_area as f32 / QUARTERS_PER_CELL;
+                let board_cells = board_area as f32 / QUARTERS_PER_CELL;
+                let text = if tiles_area > board_area {
+                    format!(
+                        "The remaining tiles cover {} cells, but only {} cells are free! Some tiles may be overlapping or out of bounds.",
+                        tiles_cells, board_cells
+                    )
+                } else {
+                    format!(
+                        "The remaining tiles cover {} cells, but {} cells are free!",
+                        tiles_cells, board_cells
+                    )
+                };
+                vec![icon, build_label(&text)]
+            }
+            UnsolvableReason::TileLargerThanBoard { .. } => {
                 vec![
                     icon,
-                    build_label("Some tiles are overlapping or are out of bounds!"),
+                    build_label(
+                        "At least one of the remaining tiles is larger than the free space on the board!",
+                    ),
+                ]
+            }
+            UnsolvableReason::UncoverableCell { .. } => {
+                vec![
+                    icon,
+                    build_label("At least one free cell cannot be covered by any remaining tile!"),
                 ]
             }
             UnsolvableReason::Cancelled => {
@@ -166,7 +375,99 @@ impl PuzzlePage {
     }
 }
 
+/// A dialog, which is shown while a hint takes long to calculate.
+///
+/// It allows stopping the calculation or showing the best partial hint found so far.
+struct HintProgressDialog {
+    timeout: RefCell<Option<glib::SourceId>>,
+    dialog: RefCell<Option<AlertDialog>>,
+}
+
+impl HintProgressDialog {
+    /// Schedules the dialog to be shown, if the hint is not calculated after
+    /// [HINT_PROGRESS_DIALOG_DELAY].
+    ///
+    /// # Arguments
+    ///
+    /// * `page`: The page to show the dialog on.
+    /// * `cancel_token`: The token cancelling the calculation of the hint.
+    /// * `partial_requested`: Set to true, if the player requests a partial hint.
+    ///
+    /// returns: Rc<HintProgressDialog>
+    fn schedule(
+        page: &PuzzlePage,
+        cancel_token: &CancellationToken,
+        partial_requested: &Rc<Cell<bool>>,
+    ) -> Rc<HintProgressDialog> {
+        let progress_dialog = Rc::new(HintProgressDialog {
+            timeout: RefCell::new(None),
+            dialog: RefCell::new(None),
+        });
+        let timeout = glib::timeout_add_local_once(HINT_PROGRESS_DIALOG_DELAY, {
+            let page = page.clone();
+            let progress_dialog = progress_dialog.clone();
+            let cancel_token = cancel_token.clone();
+            let partial_requested = partial_requested.clone();
+            move || {
+                progress_dialog.timeout.replace(None);
+                progress_dialog.present(&page, cancel_token, partial_requested);
+            }
+        });
+        progress_dialog.timeout.replace(Some(timeout));
+        progress_dialog
+    }
+
+    fn present(
+        self: &Rc<Self>,
+        page: &PuzzlePage,
+        cancel_token: CancellationToken,
+        partial_requested: Rc<Cell<bool>>,
+    ) {
+        let dialog = AlertDialog::builder()
+            .heading("Calculating Hint")
+            .body("This puzzle takes a while to solve. You can keep waiting, stop the search or show the most promising placement found so far.")
+            .extra_child(&adw::Spinner::builder().height_request(32).build())
+            .build();
+
+        let cancel_id = "cancel";
+        let partial_id = "partial";
+        dialog.add_response(cancel_id, "Stop");
+        dialog.add_response(partial_id, "Show Partial Hint");
+        dialog.set_default_response(Some(partial_id));
+        dialog.set_close_response(cancel_id);
+        dialog.set_response_appearance(partial_id, ResponseAppearance::Suggested);
+        dialog.connect_response(None, move |_, response| {
+            partial_requested.set(response == partial_id);
+            cancel_token.cancel();
+        });
+        dialog.connect_closed({
+            let progress_dialog = self.clone();
+            move |_| {
+                progress_dialog.dialog.replace(None);
+            }
+        });
+
+        dialog.present(page.imp().window.get());
+        self.dialog.replace(Some(dialog));
+    }
+
+    /// Closes the dialog or prevents it from being shown, because the hint was calculated.
+    fn close(&self) {
+        if let Some(timeout) = self.timeout.take() {
+            timeout.remove();
+        }
+        let dialog = self.dialog.take();
+        if let Some(dialog) = dialog {
+            dialog.force_close();
+        }
+    }
+}
+
 enum HintButtonState {
     Bulb,
     Calculating,
+    /// The next hint can be requested after the given number of seconds.
+    Cooldown(u32),
+    /// The player requested all hints allowed for the puzzle.
+    Exhausted,
 }
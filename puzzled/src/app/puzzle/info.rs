@@ -1,10 +1,13 @@
+use crate::app::components::description;
+use crate::app::components::preview;
+use crate::app::presenter::puzzle_info::{InfoRow, PuzzleInfo};
 use crate::app::puzzle::puzzle_page::PuzzlePage;
 use crate::application::PuzzledApplication;
+use crate::model::puzzle::PuzzleModel;
 use adw::prelude::{ActionMapExtManual, AdwDialogExt, Cast, PreferencesGroupExt};
 use adw::subclass::prelude::ObjectSubclassIsExt;
 use adw::{gio, ActionRow, Dialog};
-use gtk::prelude::WidgetExt;
-use puzzle_config::PuzzleConfig;
+use gtk::prelude::{LabelExt, WidgetExt};
 use std::ops::Deref;
 
 impl PuzzlePage {
@@ -20,12 +23,17 @@ impl PuzzlePage {
 
     pub(super) fn show_puzzle_info(&self) {
         if let Some(puzzle) = self.imp().puzzle.borrow().deref() {
-            let dialog = self.create_puzzle_info(puzzle.config());
+            let dialog = self.create_puzzle_info(puzzle);
             dialog.present(self.imp().window.get());
         }
     }
 
-    fn create_puzzle_info(&self, puzzle_config: &PuzzleConfig) -> Dialog {
+    fn create_puzzle_info(&self, puzzle: &PuzzleModel) -> Dialog {
+        let puzzle_config = puzzle.config();
+        let locked = puzzle.is_locked_default();
+        let preview_config = puzzle.collection().config().preview();
+        let info = PuzzleInfo::new(puzzle_config, locked, preview_config);
+
         const RESOURCE_PATH: &str = "/de/til7701/Puzzled/puzzle-info-dialog.ui";
         let builder = gtk::Builder::from_resource(RESOURCE_PATH);
         let dialog: adw::PreferencesDialog = builder
@@ -35,74 +43,76 @@ impl PuzzlePage {
         let general_page: adw::PreferencesGroup = builder
             .object("general_info_group")
             .expect("Missing `general_info_group` in resource");
-        let general_rows = self.create_general_content_for_puzzle_info(puzzle_config);
-        for action_row in general_rows {
-            general_page.add(&action_row);
+        for row in &info.general {
+            general_page.add(&self.create_row(row));
         }
 
-        let additional_info_group: adw::PreferencesGroup = builder
-            .object("additional_info_group")
-            .expect("Missing `additional_info_group` in resource");
-        let additional_info_rows = self.create_additional_content_for_puzzle_info(puzzle_config);
-        if additional_info_rows.is_empty() {
-            additional_info_group.set_visible(false);
+        let description_group: adw::PreferencesGroup = builder
+            .object("description_group")
+            .expect("Missing `description_group` in resource");
+        if let Some(markup) = &info.description {
+            let description_label: gtk::Label = builder
+                .object("description_label")
+                .expect("Missing `description_label` in resource");
+            description_label.set_markup(markup);
+            description::open_links_with_launcher(&description_label);
         } else {
-            for action_row in additional_info_rows {
-                additional_info_group.add(&action_row);
-            }
+            description_group.set_visible(false);
         }
 
-        dialog.upcast()
-    }
-
-    fn create_general_content_for_puzzle_info(
-        &self,
-        puzzle_config: &PuzzleConfig,
-    ) -> Vec<ActionRow> {
-        let mut action_rows = Vec::new();
-
-        let name = self.create_row("Puzzle Name", puzzle_config.name());
-        action_rows.push(name);
-
-        let board_dimensions = self.create_row(
-            "Board Dimensions",
-            &format!(
-                "{} x {}",
-                puzzle_config.board_config().layout().dim().0,
-                puzzle_config.board_config().layout().dim().1
-            ),
-        );
-        action_rows.push(board_dimensions);
-
-        let tile_count = self.create_row(
-            "Number of Tiles",
-            &format!("{}", puzzle_config.tiles().len()),
-        );
-        action_rows.push(tile_count);
+        let tiles_preview_group: adw::PreferencesGroup = builder
+            .object("tiles_preview_group")
+            .expect("Missing `tiles_preview_group` in resource");
+        if !info.show_tiles_preview {
+            tiles_preview_group.set_visible(false);
+        } else {
+            let tiles_preview_fixed: gtk::Fixed = builder
+                .object("tiles_preview_fixed")
+                .expect("Missing `tiles_preview_fixed` in resource");
+            preview::create_tiles_preview(puzzle_config.tiles(), &tiles_preview_fixed);
+        }
 
-        action_rows
-    }
+        let board_preview_group: adw::PreferencesGroup = builder
+            .object("board_preview_group")
+            .expect("Missing `board_preview_group` in resource");
+        if !info.show_board_preview {
+            board_preview_group.set_visible(false);
+        } else {
+            let board_preview_box: gtk::Box = builder
+                .object("board_preview_box")
+                .expect("Missing `board_preview_box` in resource");
+            preview::create_board_preview(puzzle_config.board_config(), &board_preview_box);
+        }
 
-    fn create_additional_content_for_puzzle_info(
-        &self,
-        puzzle_config: &PuzzleConfig,
-    ) -> Vec<ActionRow> {
-        let mut action_rows = Vec::new();
+        let statistics_group: adw::PreferencesGroup = builder
+            .object("statistics_group")
+            .expect("Missing `statistics_group` in resource");
+        if info.statistics.is_empty() {
+            statistics_group.set_visible(false);
+        } else {
+            for row in &info.statistics {
+                statistics_group.add(&self.create_row(row));
+            }
+        }
 
-        if let Some(additional_info) = puzzle_config.additional_info() {
-            for (title, value) in additional_info {
-                let row = self.create_row(title, value);
-                action_rows.push(row);
+        let additional_info_group: adw::PreferencesGroup = builder
+            .object("additional_info_group")
+            .expect("Missing `additional_info_group` in resource");
+        if info.additional_info.is_empty() {
+            additional_info_group.set_visible(false);
+        } else {
+            for row in &info.additional_info {
+                additional_info_group.add(&self.create_row(row));
             }
         }
 
-        action_rows
+        dialog.upcast()
     }
 
-    fn create_row(&self, title: &str, value: &str) -> ActionRow {
+    fn create_row(&self, row: &InfoRow) -> ActionRow {
         ActionRow::builder()
-            .title(title)
-            .subtitle(value)
+            .title(row.title.as_str())
+            .subtitle(row.value.as_str())
             .focusable(false)
             .selectable(false)
             .can_focus(false)
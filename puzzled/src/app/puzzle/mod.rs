@@ -1,7 +1,14 @@
+mod coach;
 mod combinations;
+mod coverage;
 mod extension;
 mod hint;
 mod info;
+mod notes;
+mod practice;
 pub mod puzzle_area;
 pub mod puzzle_page;
+mod remaining_cells;
+mod solution_export;
 mod solved;
+mod trivial;
@@ -0,0 +1,62 @@
+use crate::app::puzzle::puzzle_page::PuzzlePage;
+use crate::model::puzzle::PuzzleModel;
+use adw::prelude::{AdwDialogExt, AlertDialogExt};
+use adw::subclass::prelude::ObjectSubclassIsExt;
+use adw::{AlertDialog, ResponseAppearance};
+use gtk::prelude::{TextBufferExt, TextViewExt};
+use std::ops::Deref;
+
+const NOTES_HEIGHT: i32 = 160;
+
+impl PuzzlePage {
+    /// Shows a dialog to edit the notes of the current puzzle, e.g. to remember a strategy.
+    /// The notes are stored, when the user saves them.
+    pub(super) fn show_notes_dialog(&self) {
+        if let Some(puzzle) = self.imp().puzzle.borrow().deref() {
+            let dialog = create_notes_dialog(puzzle);
+            dialog.present(self.imp().window.get());
+        }
+    }
+}
+
+fn create_notes_dialog(puzzle: &PuzzleModel) -> AlertDialog {
+    let text_view = gtk::TextView::builder()
+        .wrap_mode(gtk::WrapMode::WordChar)
+        .accepts_tab(false)
+        .top_margin(8)
+        .bottom_margin(8)
+        .left_margin(8)
+        .right_margin(8)
+        .build();
+    text_view
+        .buffer()
+        .set_text(&puzzle.notes().unwrap_or_default());
+    let scrolled_window = gtk::ScrolledWindow::builder()
+        .hscrollbar_policy(gtk::PolicyType::Never)
+        .height_request(NOTES_HEIGHT)
+        .child(&text_view)
+        .css_classes(["card"])
+        .build();
+
+    let dialog = AlertDialog::builder()
+        .heading("Notes")
+        .body(puzzle.config().name())
+        .extra_child(&scrolled_window)
+        .build();
+
+    let cancel_id = "cancel";
+    let save_id = "save";
+    dialog.add_response(cancel_id, "Cancel");
+    dialog.add_response(save_id, "Save");
+    dialog.set_close_response(cancel_id);
+    dialog.set_response_appearance(save_id, ResponseAppearance::Suggested);
+    dialog.connect_response(Some(save_id), {
+        let puzzle = puzzle.clone();
+        move |_, _| {
+            let buffer = text_view.buffer();
+            let notes = buffer.text(&buffer.start_iter(), &buffer.end_iter(), false);
+            puzzle.set_notes(notes.trim());
+        }
+    });
+    dialog
+}
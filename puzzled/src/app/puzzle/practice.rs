@@ -0,0 +1,155 @@
+use crate::app::puzzle::puzzle_page::PuzzlePage;
+use crate::model::collection::CollectionModel;
+use crate::model::puzzle::PuzzleModel;
+use crate::model::puzzle_meta::PuzzleMeta;
+use adw::prelude::{AdwDialogExt, AlertDialogExt, PreferencesRowExt};
+use adw::subclass::prelude::ObjectSubclassIsExt;
+use adw::{AlertDialog, ResponseAppearance, Toast};
+use gtk::prelude::{BoxExt, WidgetExt};
+use log::debug;
+use puzzle_config::practice::{practice_puzzle, PracticeError, PracticeRegion};
+
+const PRACTICE_TILES_HEIGHT: i32 = 240;
+
+impl PuzzlePage {
+    /// Shows a dialog to select a part of the board and some of the tiles of the current puzzle.
+    /// The selection is shown as a new puzzle, to practice that part of the puzzle.
+    pub(super) fn show_practice_dialog(&self) {
+        let puzzle = self.imp().puzzle.borrow().clone();
+        if let Some(puzzle) = puzzle {
+            let dialog = self.create_practice_dialog(&puzzle);
+            dialog.present(self.imp().window.get());
+        }
+    }
+
+    fn create_practice_dialog(&self, puzzle: &PuzzleModel) -> AlertDialog {
+        let (width, height) = puzzle.config().board_config().layout().dim();
+        let first_column = spin_row("First Column", width);
+        let first_row = spin_row("First Row", height);
+        let columns = spin_row("Columns", width);
+        columns.set_value(width as f64);
+        let rows = spin_row("Rows", height);
+        rows.set_value(height as f64);
+        let region_list = boxed_list();
+        region_list.append(&first_column);
+        region_list.append(&first_row);
+        region_list.append(&columns);
+        region_list.append(&rows);
+
+        let tile_list = boxed_list();
+        let tile_rows: Vec<adw::SwitchRow> = puzzle
+            .config()
+            .tiles()
+            .iter()
+            .enumerate()
+            .map(|(i, tile)| {
+                let row = adw::SwitchRow::builder()
+                    .title(
+                        tile.name()
+                            .clone()
+                            .unwrap_or_else(|| format!("Tile {}", i + 1)),
+                    )
+                    .active(true)
+                    .build();
+                tile_list.append(&row);
+                row
+            })
+            .collect();
+        let scrolled_window = gtk::ScrolledWindow::builder()
+            .hscrollbar_policy(gtk::PolicyType::Never)
+            .height_request(PRACTICE_TILES_HEIGHT)
+            .child(&tile_list)
+            .build();
+
+        let content = gtk::Box::new(gtk::Orientation::Vertical, 12);
+        content.append(&region_list);
+        content.append(&scrolled_window);
+
+        let dialog = AlertDialog::builder()
+            .heading("Practice a Part")
+            .body("Select a part of the board and the tiles to practice with. Your progress on this puzzle is not affected.")
+            .extra_child(&content)
+            .build();
+
+        let cancel_id = "cancel";
+        let practice_id = "practice";
+        dialog.add_response(cancel_id, "Cancel");
+        dialog.add_response(practice_id, "Practice");
+        dialog.set_close_response(cancel_id);
+        dialog.set_response_appearance(practice_id, ResponseAppearance::Suggested);
+        dialog.connect_response(Some(practice_id), {
+            let self_clone = self.clone();
+            let puzzle = puzzle.clone();
+            move |_, _| {
+                let region = PracticeRegion {
+                    x: first_column.value() as usize - 1,
+                    y: first_row.value() as usize - 1,
+                    width: columns.value() as usize,
+                    height: rows.value() as usize,
+                };
+                let tile_indices: Vec<usize> = tile_rows
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, row)| row.is_active())
+                    .map(|(i, _)| i)
+                    .collect();
+                self_clone.start_practice(&puzzle, &region, &tile_indices);
+            }
+        });
+        dialog
+    }
+
+    fn start_practice(
+        &self,
+        puzzle: &PuzzleModel,
+        region: &PracticeRegion,
+        tile_indices: &[usize],
+    ) {
+        let collection = puzzle.collection().config();
+        match practice_puzzle(collection, puzzle.config(), region, tile_indices) {
+            Ok(practice) => {
+                debug!(
+                    "Practicing {:?} of puzzle {}",
+                    region,
+                    puzzle.config().name()
+                );
+                let practice = CollectionModel::new(practice, &PuzzleMeta::new());
+                practice.mark_all_as_unsolved();
+                if let Some(practice_puzzle) = practice.puzzles().first() {
+                    self.show_puzzle(practice_puzzle);
+                }
+            }
+            Err(e) => {
+                self.imp()
+                    .toast_overlay
+                    .add_toast(Toast::new(practice_error_message(&e)));
+            }
+        }
+    }
+}
+
+fn spin_row(title: &str, max: usize) -> adw::SpinRow {
+    let row = adw::SpinRow::with_range(1.0, max.max(1) as f64, 1.0);
+    row.set_title(title);
+    row
+}
+
+fn boxed_list() -> gtk::ListBox {
+    let list = gtk::ListBox::builder()
+        .selection_mode(gtk::SelectionMode::None)
+        .build();
+    list.add_css_class("boxed-list");
+    list
+}
+
+fn practice_error_message(error: &PracticeError) -> &'static str {
+    match error {
+        PracticeError::EmptyRegion => "The selected part has no free cells",
+        PracticeError::RegionOffBoard => "The selected part does not fit on the board",
+        PracticeError::MisalignedRegion => {
+            "On this board, the part has to start at an odd column and row"
+        }
+        PracticeError::NoTiles => "Select at least one tile to practice with",
+        PracticeError::UnknownTile(_) => "A selected tile is not part of the puzzle",
+    }
+}
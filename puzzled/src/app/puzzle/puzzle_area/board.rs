@@ -1,27 +1,55 @@
-use crate::app::components::board::BoardView;
+use crate::app::components::board::{BoardView, CellMark};
+use crate::app::puzzle::puzzle_area::puzzle_state::PuzzleState;
 use crate::app::puzzle::puzzle_area::PuzzleArea;
 use crate::model::extension::PuzzleTypeExtension;
-use crate::offset::PixelOffset;
+use crate::offset::{CellOffset, PixelOffset};
 use adw::prelude::Cast;
 use adw::subclass::prelude::ObjectSubclassIsExt;
-use gtk::prelude::{FixedExt, GridExt, WidgetExt};
+use gtk::prelude::{FixedExt, WidgetExt};
 use gtk::Widget;
-use puzzle_config::{PuzzleConfig, TargetIndex};
-
-const TARGET_SELECTION_CLASS: &str = "target-selection";
+use puzzle_config::{AreaTarget, BoardConfig, TargetIndex};
+use puzzle_solver::trace::SearchSnapshot;
 
 impl PuzzleArea {
-    pub fn setup_board(&self, puzzle_config: &PuzzleConfig) {
-        let board_view =
-            BoardView::new(puzzle_config.board_config()).expect("Failed to initialize board view");
+    pub fn setup_board(&self, board_config: &BoardConfig) {
+        let board_view = BoardView::new(board_config).expect("Failed to initialize board view");
         let widget = board_view.upcast_ref::<Widget>();
         self.add(widget, &PixelOffset::default());
+        // Keep the board below the tiles
+        widget.insert_after(self, None::<&Widget>);
 
         self.imp().board.replace(Some(board_view));
     }
 
+    /// Rotates the board clockwise by 90 degrees together with the tiles placed on it.
+    pub fn rotate_board(&self) {
+        let Some(placement_model) = self.imp().placement_model.borrow().clone() else {
+            return;
+        };
+        self.remove_hint_tile();
+        let rotated_tiles = placement_model.rotate_board_clockwise();
+        {
+            let tiles = self.imp().tiles.borrow();
+            for tile_view in rotated_tiles.iter().filter_map(|i| tiles.get(*i)) {
+                tile_view.rotate_clockwise();
+            }
+        }
+
+        if let Some(board_view) = self.imp().board.replace(None) {
+            let widget = board_view.upcast::<Widget>();
+            self.imp()
+                .elements_in_fixed
+                .borrow_mut()
+                .retain(|element| *element != widget);
+            self.remove(&widget);
+        }
+        self.setup_board(&placement_model.board_config());
+        self.run_on_tile_moved();
+    }
+
     pub fn update_board_layout(&self) {
         self.update_target_selection();
+        self.update_tile_constraint_marks();
         let board = self.imp().board.borrow();
         let placement_borrow = self.imp().placement_model.borrow();
         let placement_model = placement_borrow.as_ref().unwrap();
@@ -37,30 +65,87 @@ impl PuzzleArea {
 
     fn update_target_selection(&self) {
         self.clear_target_selection();
-        let puzzle_type_extension = self.imp().puzzle_type_extension.borrow();
+        let placement_model = self.imp().placement_model.borrow();
+        let Some(placement_model) = placement_model.as_ref() else {
+            return;
+        };
+        let puzzle_type_extension =
+            placement_model.extension_for_shown_board(&self.imp().puzzle_type_extension.borrow());
+        let board_config = placement_model.board_config();
         let board = self.imp().board.borrow();
         if let Some(PuzzleTypeExtension::Area {
             target: Some(target),
         }) = puzzle_type_extension.as_ref()
             && let Some(board_view) = board.as_ref()
         {
-            target.indices.iter().for_each(|TargetIndex(x, y)| {
-                if let Some(widget) = board_view.child_at(*x as i32, *y as i32) {
-                    widget.add_css_class(TARGET_SELECTION_CLASS);
+            for (area_index, area_target) in target.indices.iter().enumerate() {
+                match area_target {
+                    AreaTarget::Index(TargetIndex(x, y)) => {
+                        board_view.add_mark((*x, *y), CellMark::TargetSelection);
+                    }
+                    AreaTarget::Any => {
+                        for (_, TargetIndex(x, y)) in
+                            board_config.get_display_values_for_area(area_index as i32)
+                        {
+                            board_view.add_mark((x, y), CellMark::TargetAny);
+                        }
+                    }
                 }
-            })
+            }
+        }
+    }
+
+    /// Marks the cells, which have to be covered by a specific tile.
+    fn update_tile_constraint_marks(&self) {
+        let board = self.imp().board.borrow();
+        let placement_model = self.imp().placement_model.borrow();
+        let (Some(board_view), Some(placement_model)) = (board.as_ref(), placement_model.as_ref())
+        else {
+            return;
+        };
+        board_view.clear_marks(&[CellMark::TileConstraint]);
+        for tile_constraint in placement_model.tile_constraints_for_shown_board() {
+            board_view.add_mark(tile_constraint.cell(), CellMark::TileConstraint);
         }
     }
 
     fn clear_target_selection(&self) {
         let board = self.imp().board.borrow();
         if let Some(board_view) = board.as_ref() {
-            board_view.elements().iter().for_each(|widget| {
-                widget.remove_css_class(TARGET_SELECTION_CLASS);
-            });
+            board_view.clear_marks(&[CellMark::TargetSelection, CellMark::TargetAny]);
         }
     }
 
+    /// Marks the cells covered by the solver and the cells of its last pruned placement in the
+    /// given snapshot of its search. The number of prune hits is shown as the tooltip of the
+    /// board. The marks of the previous snapshot are removed, so `None` only removes them.
+    pub fn show_search_snapshot(&self, snapshot: Option<&SearchSnapshot>) {
+        let board = self.imp().board.borrow();
+        let Some(board_view) = board.as_ref() else {
+            return;
+        };
+        board_view.clear_marks(&[CellMark::SolverTried, CellMark::SolverPruned]);
+        board_view.set_tooltip_text(
+            snapshot
+                .map(|snapshot| format!("Prune hits: {}", snapshot.prune_hits()))
+                .as_deref(),
+        );
+        let Some(snapshot) = snapshot else {
+            return;
+        };
+
+        let mark_cells = |cells: &[(usize, usize)], mark: CellMark| {
+            for cell in cells {
+                let CellOffset(x, y) = PuzzleState::state_to_board(CellOffset::from(*cell));
+                if let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) {
+                    board_view.add_mark((x, y), mark);
+                }
+            }
+        };
+        mark_cells(snapshot.tried_cells(), CellMark::SolverTried);
+        mark_cells(snapshot.pruned_cells(), CellMark::SolverPruned);
+    }
+
     pub fn get_min_element_width(&self) -> u32 {
         let board = self.imp().board.borrow();
         if let Some(board_view) = board.as_ref() {
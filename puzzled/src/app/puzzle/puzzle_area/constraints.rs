@@ -0,0 +1,127 @@
+use crate::adw_ext;
+use crate::app::puzzle::puzzle_area::puzzle_state::PuzzleState;
+use crate::app::puzzle::puzzle_area::PuzzleArea;
+use crate::global::settings::{Preferences, ShowTileConstraints};
+use crate::solver::Solver;
+use adw::gdk::RGBA;
+use adw::subclass::prelude::ObjectSubclassIsExt;
+use gtk::prelude::WidgetExt;
+
+/// How many placements a tile has left on the board, with the other tiles staying where they are.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConstraintLevel {
+    /// The tile does not fit anywhere.
+    Stuck,
+    /// The tile fits in very few places, so it should likely be placed next.
+    Tight,
+    /// The tile fits in some places.
+    Limited,
+    /// The tile fits in many places.
+    Free,
+}
+
+impl ConstraintLevel {
+    fn from_placement_count(placement_count: usize) -> Self {
+        match placement_count {
+            0 => ConstraintLevel::Stuck,
+            1..=2 => ConstraintLevel::Tight,
+            3..=8 => ConstraintLevel::Limited,
+            _ => ConstraintLevel::Free,
+        }
+    }
+
+    fn color(&self) -> RGBA {
+        match self {
+            ConstraintLevel::Stuck => adw_ext::RED_3,
+            ConstraintLevel::Tight => adw_ext::ORANGE_3,
+            ConstraintLevel::Limited => adw_ext::YELLOW_3,
+            ConstraintLevel::Free => adw_ext::GREEN_4,
+        }
+    }
+}
+
+impl PuzzleArea {
+    /// Updates the tiles, when the preference to show the constraints of the tiles changes.
+    pub(super) fn post_construct_setup_constraints(&self) {
+        let preferences = Preferences::default();
+        preferences.connect_changed(ShowTileConstraints, {
+            let self_clone = self.clone();
+            move || self_clone.update_highlights()
+        });
+        self.imp().preferences.replace(Some(preferences));
+    }
+
+    /// Colors each tile by how many placements it has left on the board, if enabled in the
+    /// preferences. Tiles with few placements are good candidates to place next.
+    ///
+    /// The placements of each tile are counted with the tile itself taken off the board. This
+    /// does not call the solver, so placements may not be part of a solution.
+    pub(super) fn update_tile_constraints(&self, puzzle_state: &PuzzleState) {
+        let show = Preferences::default().get(ShowTileConstraints);
+        let tile_views = self.imp().tiles.borrow();
+        for tile_view in tile_views.iter() {
+            if !show {
+                tile_view.set_tint(None);
+                tile_view.set_tooltip_text(None);
+                continue;
+            }
+            let placement_count = placement_count(puzzle_state, tile_view.id());
+            let level = ConstraintLevel::from_placement_count(placement_count);
+            tile_view.set_tint(Some(level.color()));
+            tile_view.set_tooltip_text(Some(&placements_text(placement_count)));
+        }
+    }
+}
+
+fn placement_count(puzzle_state: &PuzzleState, tile_id: usize) -> usize {
+    let puzzle_state = puzzle_state.without_tiles(&[tile_id]);
+    puzzle_state
+        .unused_tiles
+        .iter()
+        .find(|tile| tile.id == tile_id)
+        .map_or(0, |tile| {
+            Solver::default()
+                .placements_of_tile(&puzzle_state, &tile.base)
+                .len()
+        })
+}
+
+fn placements_text(placement_count: usize) -> String {
+    match placement_count {
+        0 => "Fits nowhere right now".to_string(),
+        1 => "Fits in 1 place".to_string(),
+        _ => format!("Fits in {} places", placement_count),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_constraint_level() {
+        assert_eq!(
+            ConstraintLevel::from_placement_count(0),
+            ConstraintLevel::Stuck
+        );
+        assert_eq!(
+            ConstraintLevel::from_placement_count(2),
+            ConstraintLevel::Tight
+        );
+        assert_eq!(
+            ConstraintLevel::from_placement_count(3),
+            ConstraintLevel::Limited
+        );
+        assert_eq!(
+            ConstraintLevel::from_placement_count(9),
+            ConstraintLevel::Free
+        );
+    }
+
+    #[test]
+    fn test_placements_text() {
+        assert_eq!(placements_text(0), "Fits nowhere right now");
+        assert_eq!(placements_text(1), "Fits in 1 place");
+        assert_eq!(placements_text(4), "Fits in 4 places");
+    }
+}
@@ -0,0 +1,139 @@
+use crate::adw_ext;
+use crate::app::puzzle::puzzle_area::PuzzleArea;
+use crate::global::settings::{DeveloperMode, Preferences};
+use crate::model::placement::grid::LayoutDiagnostics;
+use crate::offset::PixelOffset;
+use adw::gdk::RGBA;
+use adw::subclass::prelude::ObjectSubclassIsExt;
+use gtk::cairo::Context;
+use gtk::prelude::*;
+use gtk::Widget;
+use log::error;
+
+const FONT_SIZE: f64 = 12.0;
+const LINE_HEIGHT: f64 = 16.0;
+const TEXT_PADDING: f64 = 6.0;
+const GRID_LINE_ALPHA: f64 = 0.15;
+
+impl PuzzleArea {
+    /// Adds an overlay showing how the layout of the puzzle area was computed, if developer mode
+    /// is enabled. Changes of the preference apply to the next shown puzzle.
+    pub(super) fn setup_diagnostics(&self) {
+        if !Preferences::default().get(DeveloperMode) {
+            return;
+        }
+        let overlay = gtk::DrawingArea::builder().can_target(false).build();
+        overlay.set_draw_func({
+            let diagnostics = self.imp().layout_diagnostics.clone();
+            move |area, cr, _, _| {
+                let Some(diagnostics) = diagnostics.get() else {
+                    return;
+                };
+                if let Err(e) = draw_diagnostics(area, cr, &diagnostics) {
+                    error!("Failed to draw the layout diagnostics: {}", e);
+                }
+            }
+        });
+        self.add(overlay.upcast_ref::<Widget>(), &PixelOffset::default());
+        self.imp().diagnostics_overlay.replace(Some(overlay));
+    }
+
+    /// Updates the overlay to the current layout and keeps it above all tiles.
+    pub(super) fn update_diagnostics_layout(&self) {
+        let overlay = self.imp().diagnostics_overlay.borrow();
+        let placement_model = self.imp().placement_model.borrow();
+        let (Some(overlay), Some(placement_model)) = (overlay.as_ref(), placement_model.as_ref())
+        else {
+            return;
+        };
+        let diagnostics = placement_model.layout_diagnostics();
+        self.imp().layout_diagnostics.set(Some(diagnostics));
+
+        overlay.set_width_request(diagnostics.area_pixel_size.0 as i32);
+        overlay.set_height_request(diagnostics.area_pixel_size.1 as i32);
+        overlay.insert_before(self, None::<&Widget>);
+        overlay.queue_draw();
+    }
+}
+
+/// Draws the grid cells, the minimum grid, the board and the values of the diagnostics.
+fn draw_diagnostics(
+    area: &gtk::DrawingArea,
+    cr: &Context,
+    diagnostics: &LayoutDiagnostics,
+) -> Result<(), gtk::cairo::Error> {
+    let cell_size = diagnostics.cell_size_pixel as f64;
+    let foreground = area.color();
+    cr.set_line_width(1.0);
+
+    set_source(cr, &foreground, GRID_LINE_ALPHA);
+    for x in 0..=diagnostics.grid_cells.0 {
+        cr.move_to(x as f64 * cell_size, 0.0);
+        cr.line_to(
+            x as f64 * cell_size,
+            diagnostics.grid_cells.1 as f64 * cell_size,
+        );
+    }
+    for y in 0..=diagnostics.grid_cells.1 {
+        cr.move_to(0.0, y as f64 * cell_size);
+        cr.line_to(
+            diagnostics.grid_cells.0 as f64 * cell_size,
+            y as f64 * cell_size,
+        );
+    }
+    cr.stroke()?;
+
+    cr.set_line_width(2.0);
+    set_source(cr, &adw_ext::RED_3, 1.0);
+    cr.set_dash(&[6.0, 4.0], 0.0);
+    cr.rectangle(
+        1.0,
+        1.0,
+        diagnostics.min_grid_cells.0 as f64 * cell_size - 2.0,
+        diagnostics.min_grid_cells.1 as f64 * cell_size - 2.0,
+    );
+    cr.stroke()?;
+    cr.set_dash(&[], 0.0);
+
+    set_source(cr, &adw_ext::BLUE_4, 1.0);
+    cr.rectangle(
+        diagnostics.board_offset_cells.0 as f64 * cell_size,
+        diagnostics.board_offset_cells.1 as f64 * cell_size,
+        diagnostics.board_size_cells.0 as f64 * cell_size,
+        diagnostics.board_size_cells.1 as f64 * cell_size,
+    );
+    cr.stroke()?;
+
+    let lines = diagnostics.lines();
+    cr.set_font_size(FONT_SIZE);
+    let text_width = lines
+        .iter()
+        .map(|line| cr.text_extents(line).map(|extents| extents.x_advance()))
+        .try_fold(0.0, |max: f64, width| width.map(|width| max.max(width)))?;
+    set_source(cr, &adw_ext::DARK_4, 0.8);
+    cr.rectangle(
+        0.0,
+        0.0,
+        text_width + 2.0 * TEXT_PADDING,
+        lines.len() as f64 * LINE_HEIGHT + 2.0 * TEXT_PADDING,
+    );
+    cr.fill()?;
+    set_source(cr, &adw_ext::LIGHT_1, 1.0);
+    for (i, line) in lines.iter().enumerate() {
+        cr.move_to(
+            TEXT_PADDING,
+            TEXT_PADDING + (i + 1) as f64 * LINE_HEIGHT - 4.0,
+        );
+        cr.show_text(line)?;
+    }
+    Ok(())
+}
+
+fn set_source(cr: &Context, color: &RGBA, alpha: f64) {
+    cr.set_source_rgba(
+        color.red() as f64,
+        color.green() as f64,
+        color.blue() as f64,
+        color.alpha() as f64 * alpha,
+    );
+}
@@ -0,0 +1,136 @@
+use crate::app::components::tile::{cell_path, TileView};
+use crate::app::puzzle::puzzle_area::PuzzleArea;
+use crate::global::animation::AnimationPolicy;
+use crate::offset::PixelOffset;
+use adw::gdk::RGBA;
+use adw::prelude::{AnimationExt, GdkCairoContextExt};
+use adw::subclass::prelude::ObjectSubclassIsExt;
+use gtk::cairo::Context;
+use gtk::glib;
+use gtk::prelude::{DrawingAreaExtManual, FixedExt, WidgetExt};
+use log::error;
+use puzzled_common::Shape;
+use std::time::Duration;
+
+/// How long the outline of the previous position stays visible after a tile was dropped.
+const GHOST_DURATION: Duration = Duration::from_millis(1500);
+/// How far a tile has to be moved in pixels, before its previous position is shown.
+const GHOST_MIN_DISTANCE: f64 = 1.0;
+const GHOST_LINE_WIDTH: f64 = 2.0;
+
+/// The position and look of a tile when it was picked up.
+#[derive(Debug, Clone)]
+pub struct GhostOrigin {
+    tile_view_index: usize,
+    position: PixelOffset,
+    size: (i32, i32),
+    shape: Shape,
+    color: RGBA,
+}
+
+impl PuzzleArea {
+    /// Remembers where the tile was, before it is dragged, to show it after the tile is dropped.
+    pub(super) fn remember_ghost_origin(&self, tile_view_index: usize) {
+        let tiles = self.imp().tiles.borrow();
+        let Some(tile_view) = tiles.get(tile_view_index) else {
+            return;
+        };
+        self.imp().ghost_origin.replace(Some(GhostOrigin {
+            tile_view_index,
+            position: self.child_position(tile_view).into(),
+            size: (tile_view.width(), tile_view.height()),
+            shape: tile_view.current_rotation().clone(),
+            color: tile_view.color(),
+        }));
+    }
+
+    /// Briefly shows a fading outline at the position the dropped tile was picked up from, so a
+    /// misdrop can be undone by hand. Nothing is shown, if the tile did not move.
+    pub(super) fn show_ghost_trail(&self, tile_view: &TileView) {
+        let Some(origin) = self.imp().ghost_origin.take() else {
+            return;
+        };
+        let position: PixelOffset = self.child_position(tile_view).into();
+        let moved = position - origin.position;
+        if origin.tile_view_index != tile_view.id() || moved.0.hypot(moved.1) < GHOST_MIN_DISTANCE {
+            return;
+        }
+        self.remove_ghost_trail();
+
+        let ghost = gtk::DrawingArea::builder()
+            .can_target(false)
+            .width_request(origin.size.0)
+            .height_request(origin.size.1)
+            .build();
+        ghost.set_draw_func({
+            let shape = origin.shape.clone();
+            move |_, cr, width, height| {
+                if let Err(e) = draw_outline(cr, &shape, origin.color, width, height) {
+                    error!("Failed to draw the previous position of a tile: {}", e);
+                }
+            }
+        });
+        self.put(&ghost, origin.position.0, origin.position.1);
+        self.imp().ghost.replace(Some(ghost.clone()));
+        self.apply_tile_order();
+
+        let policy = AnimationPolicy::default();
+        if policy.animations_enabled() {
+            let animation = policy.animate(&ghost, 1.0, 0.0, GHOST_DURATION, {
+                let ghost = ghost.clone();
+                move |opacity| ghost.set_opacity(opacity)
+            });
+            self.imp().ghost_animation.replace(animation);
+        }
+        glib::timeout_add_local_once(GHOST_DURATION, {
+            let self_clone = self.clone();
+            move || {
+                let current = self_clone.imp().ghost.borrow().clone();
+                if current.as_ref() == Some(&ghost) {
+                    self_clone.remove_ghost_trail();
+                }
+            }
+        });
+    }
+
+    /// Removes the outline of the previous position, if one is currently shown.
+    pub(super) fn remove_ghost_trail(&self) {
+        if let Some(animation) = self.imp().ghost_animation.take() {
+            animation.skip();
+        }
+        if let Some(ghost) = self.imp().ghost.take() {
+            self.remove(&ghost);
+        }
+    }
+}
+
+/// Draws a dashed outline around each cell of the shape.
+fn draw_outline(
+    cr: &Context,
+    shape: &Shape,
+    color: RGBA,
+    width: i32,
+    height: i32,
+) -> Result<(), gtk::cairo::Error> {
+    let (dim_x, dim_y) = shape.dim();
+    let cell_width = width as f64 / dim_x as f64;
+    let cell_height = height as f64 / dim_y as f64;
+    let inset = GHOST_LINE_WIDTH / 2.0;
+
+    cr.set_source_color(&color);
+    cr.set_line_width(GHOST_LINE_WIDTH);
+    cr.set_dash(&[6.0, 4.0], 0.0);
+    for ((x, y), cell) in shape.indexed_iter() {
+        if *cell {
+            cell_path(
+                cr,
+                shape.cell_shape((x, y)),
+                x as f64 * cell_width + inset,
+                y as f64 * cell_height + inset,
+                cell_width - GHOST_LINE_WIDTH,
+                cell_height - GHOST_LINE_WIDTH,
+            );
+        }
+    }
+    cr.stroke()
+}
@@ -1,5 +1,5 @@
 use crate::app::components::tile::DrawingMode;
-use crate::app::puzzle::puzzle_area::puzzle_state::{Cell, PuzzleState};
+use crate::app::puzzle::puzzle_area::puzzle_state::{ConflictKind, PuzzleState};
 use crate::app::puzzle::puzzle_area::PuzzleArea;
 use adw::subclass::prelude::ObjectSubclassIsExt;
 
@@ -9,6 +9,7 @@ impl PuzzleArea {
         let puzzle_state = self.extract_puzzle_state();
         if let Ok(puzzle_state) = puzzle_state {
             self.highlight_invalid_tile_parts(&puzzle_state);
+            self.update_tile_constraints(&puzzle_state);
         }
     }
 
@@ -19,33 +20,44 @@ impl PuzzleArea {
         }
     }
 
+    /// Highlights the cells of tiles, which conflict with the board or another tile, with a color
+    /// depending on the kind of the conflict.
     pub fn highlight_invalid_tile_parts(&self, puzzle_state: &PuzzleState) {
         let tile_views = self.imp().tiles.borrow();
-
-        puzzle_state.grid.iter().for_each(|cell| match cell {
-            Cell::One(data, tile_cell_placement) => {
-                if !data.allowed
-                    && let Some(tile_view) = tile_views.get(tile_cell_placement.tile_id)
-                {
-                    tile_view.set_drawing_mode_at(
-                        tile_cell_placement.cell_position.0 as usize,
-                        tile_cell_placement.cell_position.1 as usize,
-                        DrawingMode::OutOfBounds,
-                    );
-                }
+        for (tile_cell_placement, kind) in puzzle_state.conflicts() {
+            if let Some(tile_view) = tile_views.get(tile_cell_placement.tile_id) {
+                tile_view.set_drawing_mode_at(
+                    tile_cell_placement.cell_position.0 as usize,
+                    tile_cell_placement.cell_position.1 as usize,
+                    drawing_mode(kind),
+                );
             }
-            Cell::Many(_, tile_cell_placements) => {
-                for tile_cell_placement in tile_cell_placements {
-                    if let Some(tile_view) = tile_views.get(tile_cell_placement.tile_id) {
-                        tile_view.set_drawing_mode_at(
-                            tile_cell_placement.cell_position.0 as usize,
-                            tile_cell_placement.cell_position.1 as usize,
-                            DrawingMode::Overlapping,
-                        );
-                    }
-                }
+        }
+    }
+
+    /// Highlights the given tiles as likely preventing a solution.
+    /// The highlight is removed with the other highlights, when a tile is moved.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile_ids`: The ids of the tiles to highlight.
+    ///
+    /// returns: ()
+    pub fn highlight_conflicting_tiles(&self, tile_ids: &[usize]) {
+        let tile_views = self.imp().tiles.borrow();
+        for tile_id in tile_ids {
+            if let Some(tile_view) = tile_views.get(*tile_id) {
+                tile_view.set_drawing_mode_of_normal_cells(DrawingMode::Conflicting);
             }
-            _ => {}
-        });
+        }
+    }
+}
+
+/// Returns how cells with the given conflict are drawn.
+fn drawing_mode(kind: ConflictKind) -> DrawingMode {
+    match kind {
+        ConflictKind::OffBoard => DrawingMode::OutOfBounds,
+        ConflictKind::Overlap => DrawingMode::Overlapping,
+        ConflictKind::OnHole => DrawingMode::OnHole,
     }
 }
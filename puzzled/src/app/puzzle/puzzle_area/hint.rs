@@ -1,4 +1,5 @@
 use crate::app::components::tile::TileView;
+use crate::app::puzzle::puzzle_area::puzzle_state::PuzzleState;
 use crate::app::puzzle::puzzle_area::PuzzleArea;
 use crate::offset::CellOffset;
 use adw::subclass::prelude::ObjectSubclassIsExt;
@@ -9,6 +10,12 @@ use puzzle_solver::result::TilePlacement;
 impl PuzzleArea {
     /// Show the placement of a tile as a hint.
     pub fn show_hint_tile(&self, placement: &TilePlacement) {
+        self.imp().tile_placements.borrow_mut().clear();
+        self.put_hint_tile(placement);
+    }
+
+    /// Show the placement of a tile as the hint tile, replacing the current one.
+    pub(super) fn put_hint_tile(&self, placement: &TilePlacement) {
         let tiles = self.imp().tiles.borrow();
         let tile_matching_base = {
             let placement_model = self.imp().placement_model.borrow();
@@ -45,7 +52,7 @@ impl PuzzleArea {
             .as_ref()
             .unwrap()
             .init_hint_tile(
-                CellOffset::from(placement.position()) - CellOffset(1, 1), // Plus 1, 1 because the puzzle state has a border of one cell to provide information for highlighting
+                PuzzleState::state_to_board(CellOffset::from(placement.position())),
                 placement.rotation().clone(),
             );
 
@@ -53,7 +60,7 @@ impl PuzzleArea {
         click_gesture.connect_pressed({
             let self_clone = self.clone();
             move |_, _, _, _| {
-                self_clone.remove_hint_tile();
+                self_clone.show_next_tile_placement();
             }
         });
         tile_view.add_controller(click_gesture);
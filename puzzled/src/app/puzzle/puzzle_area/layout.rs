@@ -1,9 +1,10 @@
 use crate::app::puzzle::puzzle_area::PuzzleArea;
 use crate::offset::PixelOffset;
 use crate::window::{MIN_WINDOW_HEIGHT, MIN_WINDOW_WIDTH};
+use adw::gdk::prelude::{DisplayExt, MonitorExt};
 use adw::glib;
 use adw::subclass::prelude::ObjectSubclassIsExt;
-use gtk::prelude::{WidgetExt, WidgetExtManual};
+use gtk::prelude::{NativeExt, WidgetExt, WidgetExtManual};
 use std::cell::Cell;
 use std::rc::Rc;
 
@@ -28,30 +29,67 @@ impl PuzzleArea {
     /// Update the layout based on the current state.
     ///
     /// This moves the puzzle area elements according to the current window size.
+    ///
+    /// If the puzzle does not fit on the screen, fewer empty cells are kept around the board. If
+    /// it still does not fit, the puzzle area gets the size it needs and can be scrolled instead.
     pub fn update_layout(&self) {
         let window = self.imp().window.get().unwrap();
-        let size = PixelOffset(
+        let header_bar_height = window.puzzle_area_nav_page().header_bar().height();
+        let mut size = PixelOffset(
             window.width() as f64,
-            (window.height() - window.puzzle_area_nav_page().header_bar().height()) as f64,
+            (window.height() - header_bar_height) as f64,
         );
 
         let placement_model = { self.imp().placement_model.borrow().clone() };
         if let Some(placement_model) = placement_model {
             let min_cell_size = self.get_min_element_width();
+            let work_area = self.work_area_size(header_bar_height);
+            let fits = |required: PixelOffset| {
+                work_area
+                    .is_none_or(|work_area| required.0 <= work_area.0 && required.1 <= work_area.1)
+            };
+            let compact = !fits(placement_model.required_area_size(false, min_cell_size));
+            placement_model.set_compact_margins(compact);
+
+            let required = placement_model.required_area_size(true, min_cell_size);
+            if compact && !fits(required) {
+                size = PixelOffset(size.0.max(required.0), size.1.max(required.1));
+                self.set_size_request(required.0 as i32, required.1 as i32);
+            } else {
+                self.set_size_request(-1, -1);
+            }
+
             placement_model.update_pixel_size(size, min_cell_size);
-            self.set_min_size();
+            self.set_min_size(work_area);
             self.update_board_layout();
             self.update_tile_layout();
             self.update_hint_tile_layout();
+            self.update_diagnostics_layout();
         }
     }
 
+    /// Returns the size of the monitor showing the window without the header bar, which is the
+    /// largest size the puzzle area can get. GTK does not tell the part of the monitor left free
+    /// by panels, so the whole monitor is used.
+    /// None, if the window is not shown on a monitor yet.
+    fn work_area_size(&self, header_bar_height: i32) -> Option<PixelOffset> {
+        let window = self.imp().window.get()?;
+        let surface = window.surface()?;
+        let monitor = window.display().monitor_at_surface(&surface)?;
+        let geometry = monitor.geometry();
+        Some(PixelOffset(
+            geometry.width() as f64,
+            (geometry.height() - header_bar_height) as f64,
+        ))
+    }
+
     /// Sets the minimum size of the window based on the current grid configuration.
+    /// The size never exceeds the work area, since the puzzle area can be scrolled then.
     ///
     /// This has to be set on the window instead of the Fixed, since the AdwBreakpointBin
     /// that everything is wrapped in, does not work well with changing width requests
     /// of the children.
-    fn set_min_size(&self) {
+    fn set_min_size(&self, work_area: Option<PixelOffset>) {
         let window = self.imp().window.get().unwrap();
         if !window.outer_view().shows_content() {
             return;
@@ -59,7 +97,10 @@ impl PuzzleArea {
 
         let placement_borrow = self.imp().placement_model.borrow();
         let placement_model = placement_borrow.as_ref().unwrap();
-        let fixed_min = placement_model.min_area_size();
+        let mut fixed_min = placement_model.min_area_size();
+        if let Some(work_area) = work_area {
+            fixed_min = PixelOffset(fixed_min.0.min(work_area.0), fixed_min.1.min(work_area.1));
+        }
 
         window.set_width_request((fixed_min.0 as i32).max(MIN_WINDOW_WIDTH));
         window.set_height_request(
@@ -1,11 +1,17 @@
 mod board;
+mod constraints;
+mod diagnostics;
+mod ghost;
 mod highlight;
 mod hint;
 mod layout;
+mod placements;
 pub mod puzzle_state;
 mod tile;
 
 use crate::app::puzzle::puzzle_area::puzzle_state::PuzzleState;
+use crate::global::events;
+use crate::global::events::AppEvent;
 use crate::model::extension::PuzzleTypeExtension;
 use crate::model::placement::PlacementModel;
 use crate::model::puzzle::PuzzleModel;
@@ -17,16 +23,18 @@ use gtk::prelude::*;
 use gtk::{glib, Widget};
 use log::debug;
 
-const TILE_MOVED_SIGNAL_NAME: &str = "tile-moved";
-
 mod imp {
     use super::*;
     use crate::app::components::board::BoardView;
     use crate::app::components::tile::TileView;
+    use crate::app::puzzle::puzzle_area::ghost::GhostOrigin;
+    use crate::global::settings::Preferences;
+    use crate::model::placement::grid::LayoutDiagnostics;
     use crate::model::placement::PlacementModel;
-    use adw::glib::subclass::Signal;
-    use std::cell::{OnceCell, RefCell};
-    use std::sync::OnceLock;
+    use puzzle_solver::result::TilePlacement;
+    use std::cell::{Cell, OnceCell, RefCell};
+    use std::collections::VecDeque;
+    use std::rc::Rc;
 
     #[derive(Debug, Default)]
     pub struct PuzzledPuzzleArea {
@@ -34,10 +42,27 @@ mod imp {
         pub(super) placement_model: RefCell<Option<PlacementModel>>,
         pub board: RefCell<Option<BoardView>>,
         pub tiles: RefCell<Vec<TileView>>,
+        /// Indices of the tiles ordered from the bottom to the top of the stack.
+        pub tile_order: RefCell<Vec<usize>>,
         pub hint_tile: RefCell<Option<TileView>>,
+        /// The placements of a single tile, which are shown one after another as hint tile.
+        pub tile_placements: RefCell<VecDeque<TilePlacement>>,
+        /// The number of placements found for the tile, whose placements are shown.
+        pub tile_placement_count: Cell<usize>,
         pub elements_in_fixed: RefCell<Vec<Widget>>,
         pub puzzle: RefCell<Option<PuzzleModel>>,
         pub puzzle_type_extension: RefCell<Option<PuzzleTypeExtension>>,
+        /// Shows the layout diagnostics in developer mode.
+        pub diagnostics_overlay: RefCell<Option<gtk::DrawingArea>>,
+        /// The diagnostics drawn by the overlay, shared with its draw function.
+        pub layout_diagnostics: Rc<Cell<Option<LayoutDiagnostics>>>,
+        /// Where the dragged tile was picked up from.
+        pub ghost_origin: RefCell<Option<GhostOrigin>>,
+        /// The fading outline at the previous position of the last dropped tile.
+        pub ghost: RefCell<Option<gtk::DrawingArea>>,
+        pub ghost_animation: RefCell<Option<adw::TimedAnimation>>,
+        /// Kept alive to be notified, when the preferences of the puzzle area change.
+        pub preferences: RefCell<Option<Preferences>>,
     }
 
     #[glib::object_subclass]
@@ -48,15 +73,11 @@ mod imp {
     }
 
     impl ObjectImpl for PuzzledPuzzleArea {
-        fn signals() -> &'static [Signal] {
-            static SIGNALS: OnceLock<Vec<Signal>> = OnceLock::new();
-            SIGNALS.get_or_init(|| vec![Signal::builder(TILE_MOVED_SIGNAL_NAME).build()])
-        }
-
         fn constructed(&self) {
             self.parent_constructed();
             let obj = self.obj();
             obj.post_construct_setup_layout();
+            obj.post_construct_setup_constraints();
         }
     }
     impl WidgetImpl for PuzzledPuzzleArea {}
@@ -103,11 +124,12 @@ impl PuzzleArea {
         });
         self.imp().placement_model.replace(Some(placement_model));
 
-        self.setup_board(puzzle_config);
+        self.setup_board(puzzle_config.board_config());
 
         for (i, tile) in puzzle_config.tiles().iter().enumerate() {
             self.setup_tile(tile, i);
         }
+        self.setup_diagnostics();
 
         self.update_highlights();
         self.update_layout();
@@ -124,27 +146,21 @@ impl PuzzleArea {
     pub fn run_on_tile_moved(&self) {
         self.update_highlights();
         self.update_layout();
-        self.emit_tile_moved();
-    }
-
-    pub fn connect_tile_moved<F: Fn() + 'static>(&self, callback: F) {
-        self.connect_local(TILE_MOVED_SIGNAL_NAME, false, move |_| {
-            callback();
-            None
-        });
-    }
-
-    fn emit_tile_moved(&self) {
-        debug!("Emitting tile moved signal",);
-        self.emit_by_name::<()>(TILE_MOVED_SIGNAL_NAME, &[]);
+        debug!("Publishing tile moved event");
+        events::publish(AppEvent::TileMoved);
     }
 
     fn clear_elements(&self) {
         let mut elements_in_fixed = self.imp().elements_in_fixed.borrow_mut();
         elements_in_fixed.drain(..).for_each(|e| self.remove(&e));
         self.imp().tiles.replace(vec![]);
+        self.imp().tile_order.replace(vec![]);
         self.imp().board.replace(None);
+        self.imp().diagnostics_overlay.replace(None);
+        self.imp().layout_diagnostics.set(None);
         self.remove_hint_tile();
+        self.imp().ghost_origin.replace(None);
+        self.remove_ghost_trail();
     }
 
     pub fn extract_puzzle_state(&self) -> Result<PuzzleState, String> {
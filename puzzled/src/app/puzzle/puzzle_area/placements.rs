@@ -0,0 +1,84 @@
+use crate::app::puzzle::puzzle_area::PuzzleArea;
+use crate::solver::Solver;
+use adw::prelude::WidgetExt;
+use adw::subclass::prelude::ObjectSubclassIsExt;
+use adw::Toast;
+use std::collections::VecDeque;
+
+impl PuzzleArea {
+    /// Shows where the tile can be placed on the board with the other tiles staying where they
+    /// are.
+    ///
+    /// The placements are shown one after another as hint tile. Clicking the hint tile shows the
+    /// next placement and removes it after the last one.
+    /// This does not reveal, which of the placements are part of a solution.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile_view_index`: The index of the tile to show the placements of.
+    ///
+    /// returns: ()
+    pub fn show_tile_placements(&self, tile_view_index: usize) {
+        let Ok(puzzle_state) = self.extract_puzzle_state() else {
+            return;
+        };
+        let puzzle_state = puzzle_state.without_tiles(&[tile_view_index]);
+        let Some(tile) = puzzle_state
+            .unused_tiles
+            .iter()
+            .find(|tile| tile.id == tile_view_index)
+        else {
+            return;
+        };
+
+        let placements = Solver::default().placements_of_tile(&puzzle_state, &tile.base);
+        if placements.is_empty() {
+            self.remove_hint_tile();
+            if let Some(window) = self.imp().window.get() {
+                window
+                    .puzzle_area_nav_page()
+                    .imp()
+                    .toast_overlay
+                    .add_toast(Toast::new("This tile does not fit anywhere right now"));
+            }
+            return;
+        }
+        self.imp().tile_placement_count.set(placements.len());
+        self.imp()
+            .tile_placements
+            .replace(VecDeque::from(placements));
+        self.show_next_tile_placement();
+    }
+
+    /// Shows the next placement of the tile, whose placements are shown, as hint tile.
+    /// The hint tile is removed, if there are no more placements to show.
+    pub(super) fn show_next_tile_placement(&self) {
+        let placement = self.imp().tile_placements.borrow_mut().pop_front();
+        let Some(placement) = placement else {
+            self.remove_hint_tile();
+            return;
+        };
+        self.put_hint_tile(&placement);
+
+        let count = self.imp().tile_placement_count.get();
+        let remaining = self.imp().tile_placements.borrow().len();
+        if let Some(hint_tile) = self.imp().hint_tile.borrow().as_ref() {
+            hint_tile.set_tooltip_text(Some(&placement_text(count - remaining, count)));
+        }
+    }
+}
+
+fn placement_text(number: usize, count: usize) -> String {
+    format!("Placement {} of {}", number, count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placement_text() {
+        assert_eq!(placement_text(1, 3), "Placement 1 of 3");
+        assert_eq!(placement_text(3, 3), "Placement 3 of 3");
+    }
+}
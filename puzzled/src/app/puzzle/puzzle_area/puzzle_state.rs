@@ -1,29 +1,50 @@
 use crate::model::extension::PuzzleTypeExtension;
+use crate::model::placement::geometry;
 use crate::offset::CellOffset;
 use ndarray::Array2;
-use puzzle_config::PuzzleConfig;
+use puzzle_config::{BoardConfig, TileConstraint};
 use puzzled_common::Shape;
 use std::cell::Ref;
 use std::collections::HashSet;
+use std::slice;
 
 /// Represents data associated with a cell in the puzzle grid.
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone)]
 pub struct CellData {
     /// Indicates whether the cell is part of the playable board area.
     pub is_on_board: bool,
+    /// The quarters of the cell, which are part of the playable board area.
+    /// See [QUARTERS_ALL](puzzled_common::shape::cell::QUARTERS_ALL).
+    pub board_quarters: u8,
     /// Indicates whether placing a tile in this cell is allowed.
     pub allowed: bool,
+    /// Indicates whether the cell lies within the bounds of the board, but is not part of the
+    /// playable board area, like a hole in the board or a cell of the target.
+    pub is_hole: bool,
 }
 
 /// Represents the presence of a cell of a tile in the puzzle grid.
 ///
 /// The tile_id is used to identify which tile is present, and the cell_position indicates
 /// the position of the cell of the tile inside the tile.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TileCellPlacement {
     pub tile_id: usize,
     /// The position of the cell of the tile inside the tile.
     pub cell_position: CellOffset,
+    /// The quarters of the grid cell covered by the cell of the tile.
+    pub quarters: u8,
+}
+
+/// Why a cell of a tile cannot stay where it is.
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+pub enum ConflictKind {
+    /// The cell lies next to the board or reaches outside a half cell of the board.
+    OffBoard,
+    /// The cell shares a quarter with a cell of another tile.
+    Overlap,
+    /// The cell covers a hole in the board or a cell of the target.
+    OnHole,
 }
 
 /// Represents a cell in the puzzle grid.
@@ -33,7 +54,7 @@ pub struct TileCellPlacement {
 /// A cell is not always a part of the playable board area.
 /// It may be part of the border area used to indicate out-of-bounds or the board design blocks
 /// placing a tile there.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Cell {
     Empty(CellData),
     One(CellData, TileCellPlacement),
@@ -58,77 +79,248 @@ pub struct UnusedTile {
 /// Represents the current state of the puzzle.
 ///
 /// The grid contains information about each cell, and unused_tiles keeps track of tiles that have
-/// not been placed yet.
-#[derive(Debug)]
+/// not been placed yet. placed_tiles contains the tiles with at least one cell on the board.
+/// Each entry of wildcard_areas contains the grid positions of the cells of an area, of which any
+/// one cell may remain uncovered. The cells of the tile constraints are positions relative to the
+/// board as it is shown.
+#[derive(Debug, Clone)]
 pub struct PuzzleState {
     pub grid: Array2<Cell>,
     pub unused_tiles: HashSet<UnusedTile>,
+    pub placed_tiles: HashSet<UnusedTile>,
+    pub wildcard_areas: Vec<Vec<(usize, usize)>>,
+    pub tile_constraints: Vec<TileConstraint>,
 }
 
 impl PuzzleState {
+    /// The offset of the board in the grid of the puzzle state.
+    ///
+    /// The grid has a border of one cell around the board, so it can provide information for
+    /// highlighting tiles next to the board.
+    const BORDER: CellOffset = CellOffset(1, 1);
+
     pub fn new(
-        puzzle_config: &PuzzleConfig,
+        board_config: &BoardConfig,
         puzzle_type_extension: Ref<Option<PuzzleTypeExtension>>,
     ) -> Self {
-        let board_config = &puzzle_config.board_config();
-        let layout = &board_config.layout();
+        let layout = board_config.layout();
 
         let dim = layout.dim();
-        // Add border to have a zone where tiles are not allowed to be placed to indicate out-of-bounds
-        let dim = (dim.0 + 2, dim.1 + 2);
+        let dim = (
+            dim.0 + 2 * Self::BORDER.0 as usize,
+            dim.1 + 2 * Self::BORDER.1 as usize,
+        );
         let mut grid: Array2<Cell> = Array2::default(dim);
 
         for ((x, y), cell) in grid.indexed_iter_mut() {
-            let board_index: (i32, i32) = (x as i32 - 1, y as i32 - 1);
-            let on_board = *layout
-                .get((board_index.0 as usize, board_index.1 as usize))
-                .unwrap_or(&false);
-            let is_adjacent = Self::is_adjacent_to_board(board_index, puzzle_config);
-            let allowed = !is_adjacent;
+            let board_position = Self::state_to_board(CellOffset::from((x, y)));
+            let on_board = is_on_layout(layout, board_position);
+            let board_quarters = if on_board {
+                layout.quarters(board_position.into())
+            } else {
+                0
+            };
+            let allowed = !Self::is_adjacent_to_board(board_position, layout);
+            let is_hole = !on_board && geometry::is_within(board_position, layout.dim());
             *cell = Cell::Empty(CellData {
                 is_on_board: on_board,
+                board_quarters,
                 allowed,
+                is_hole,
             });
         }
 
         let mut puzzle_state = PuzzleState {
             grid,
             unused_tiles: HashSet::new(),
+            placed_tiles: HashSet::new(),
+            wildcard_areas: Vec::new(),
+            tile_constraints: Vec::new(),
         };
         if let Some(extension) = puzzle_type_extension.as_ref() {
-            puzzle_state.handle_extension(extension);
+            puzzle_state.handle_extension(extension, board_config);
         }
         puzzle_state
     }
 
-    fn is_adjacent_to_board(position: (i32, i32), puzzle_config: &PuzzleConfig) -> bool {
-        const DELTAS: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
-        let this_is_on_board = puzzle_config
-            .board_config()
-            .layout()
-            .get((position.0 as usize, position.1 as usize))
-            .unwrap_or(&false);
-        for (dr, dc) in DELTAS.iter() {
-            let neighbor_pos = ((position.0 + dr) as usize, (position.1 + dc) as usize);
-            if let Some(neighbour_on_board) =
-                puzzle_config.board_config().layout().get(neighbor_pos)
-                && !this_is_on_board
-                && *neighbour_on_board
-            {
-                return true;
+    /// Returns the width and height of the board as it is shown, without the border of the grid.
+    pub fn board_dim(&self) -> (usize, usize) {
+        let (width, height) = self.grid.dim();
+        (
+            width - 2 * Self::BORDER.0 as usize,
+            height - 2 * Self::BORDER.1 as usize,
+        )
+    }
+
+    /// Converts a position relative to the board to the position in the grid of the state.
+    pub fn board_to_state(position: CellOffset) -> CellOffset {
+        position + Self::BORDER
+    }
+
+    /// Converts a position in the grid of the state, like the position of a solver placement, to
+    /// the position relative to the board.
+    pub fn state_to_board(position: CellOffset) -> CellOffset {
+        position - Self::BORDER
+    }
+
+    /// Creates a copy of this state, in which the given placed tiles were taken off the board.
+    /// Their cells are removed from the grid and the tiles are added to the unused tiles.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile_ids`: the ids of the placed tiles to take off the board
+    ///
+    /// returns: PuzzleState
+    pub fn without_tiles(&self, tile_ids: &[usize]) -> PuzzleState {
+        let grid = self.grid.map(|cell| match cell {
+            Cell::One(data, placement) if tile_ids.contains(&placement.tile_id) => {
+                Cell::Empty(data.clone())
+            }
+            Cell::Many(data, placements) => {
+                let mut placements: Vec<TileCellPlacement> = placements
+                    .iter()
+                    .filter(|placement| !tile_ids.contains(&placement.tile_id))
+                    .cloned()
+                    .collect();
+                match placements.len() {
+                    0 => Cell::Empty(data.clone()),
+                    1 => Cell::One(data.clone(), placements.remove(0)),
+                    _ => Cell::Many(data.clone(), placements),
+                }
             }
+            cell => cell.clone(),
+        });
+        let (removed, placed_tiles): (HashSet<UnusedTile>, HashSet<UnusedTile>) = self
+            .placed_tiles
+            .iter()
+            .cloned()
+            .partition(|tile| tile_ids.contains(&tile.id));
+        PuzzleState {
+            grid,
+            unused_tiles: self.unused_tiles.union(&removed).cloned().collect(),
+            placed_tiles,
+            wildcard_areas: self.wildcard_areas.clone(),
+            tile_constraints: self.tile_constraints.clone(),
+        }
+    }
+
+    /// Returns every cell of a tile in the grid, which conflicts with the board or another tile,
+    /// together with the kind of the conflict.
+    ///
+    /// A cell has at most one conflict. Overlapping another tile takes precedence over covering
+    /// a hole, which takes precedence over being off the board.
+    pub fn conflicts(&self) -> Vec<(&TileCellPlacement, ConflictKind)> {
+        self.grid
+            .iter()
+            .flat_map(|cell| {
+                let (data, placements) = match cell {
+                    Cell::Empty(_) => return Vec::new(),
+                    Cell::One(data, placement) => (data, slice::from_ref(placement)),
+                    Cell::Many(data, placements) => (data, placements.as_slice()),
+                };
+                placements
+                    .iter()
+                    .filter_map(|placement| {
+                        conflict_of(data, placement, placements).map(|kind| (placement, kind))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Returns the kinds of conflicts of the cells of the tile.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile_id`: the id of the tile to check
+    ///
+    /// returns: HashSet<ConflictKind> empty, if the tile has no conflicting cells
+    pub fn conflict_kinds(&self, tile_id: usize) -> HashSet<ConflictKind> {
+        self.conflicts()
+            .into_iter()
+            .filter(|(placement, _)| placement.tile_id == tile_id)
+            .map(|(_, kind)| kind)
+            .collect()
+    }
+
+    /// Returns the kinds of conflicts of the cells of all tiles.
+    pub fn all_conflict_kinds(&self) -> HashSet<ConflictKind> {
+        self.conflicts().into_iter().map(|(_, kind)| kind).collect()
+    }
+
+    /// Returns true, if a cell of the tile has any conflict. See [Self::conflicts].
+    /// These are the cells, which are highlighted in the puzzle area.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile_id`: the id of the tile to check
+    ///
+    /// returns: bool
+    pub fn has_invalid_cells(&self, tile_id: usize) -> bool {
+        !self.conflict_kinds(tile_id).is_empty()
+    }
+
+    /// Returns the cell at the given position relative to the board.
+    /// Cells of the border around the board are returned for positions just outside the board.
+    ///
+    /// # Arguments
+    ///
+    /// * `position`: the position relative to the board
+    ///
+    /// returns: Option<&Cell> `None`, if the position is outside the board and its border.
+    pub fn cell_at_board(&self, position: CellOffset) -> Option<&Cell> {
+        let position = Self::board_to_state(position);
+        if geometry::is_within(position, self.grid.dim()) {
+            self.grid.get::<(usize, usize)>(position.into())
+        } else {
+            None
+        }
+    }
+
+    fn cell_at_board_mut(&mut self, position: CellOffset) -> Option<&mut Cell> {
+        let position = Self::board_to_state(position);
+        if geometry::is_within(position, self.grid.dim()) {
+            self.grid.get_mut::<(usize, usize)>(position.into())
+        } else {
+            None
         }
-        false
     }
 
-    fn handle_extension(&mut self, puzzle_type_extension: &PuzzleTypeExtension) {
+    /// Returns true, if the position is not on the board, but one of its direct neighbours is.
+    fn is_adjacent_to_board(position: CellOffset, layout: &Shape) -> bool {
+        const DELTAS: [CellOffset; 4] = [
+            CellOffset(-1, 0),
+            CellOffset(1, 0),
+            CellOffset(0, -1),
+            CellOffset(0, 1),
+        ];
+        !is_on_layout(layout, position)
+            && DELTAS
+                .iter()
+                .any(|delta| is_on_layout(layout, position + *delta))
+    }
+
+    fn handle_extension(
+        &mut self,
+        puzzle_type_extension: &PuzzleTypeExtension,
+        board_config: &BoardConfig,
+    ) {
         if let PuzzleTypeExtension::Area {
             target: Some(target),
         } = puzzle_type_extension
         {
-            for index in &target.indices {
-                let cell = self.grid.get_mut((index.0 + 1, index.1 + 1));
-                if let Some(cell) = cell {
+            for area_index in target.any_areas() {
+                let cells = board_config
+                    .get_display_values_for_area(area_index as i32)
+                    .into_iter()
+                    .map(|(_, index)| {
+                        Self::board_to_state(CellOffset::from((index.0, index.1))).into()
+                    })
+                    .collect();
+                self.wildcard_areas.push(cells);
+            }
+            for index in target.fixed_indices() {
+                if let Some(cell) = self.cell_at_board_mut(CellOffset::from((index.0, index.1))) {
                     let data = match cell {
                         Cell::Empty(data) => data,
                         Cell::One(data, _) => data,
@@ -136,8 +328,203 @@ impl PuzzleState {
                     };
                     data.allowed = false;
                     data.is_on_board = false;
+                    data.board_quarters = 0;
+                    data.is_hole = true;
                 }
             }
         }
     }
 }
+
+/// Returns the conflict of the cell of a tile in a grid cell, if it has one.
+///
+/// # Arguments
+///
+/// * `data`: the data of the grid cell
+/// * `placement`: the cell of the tile
+/// * `placements`: all cells of tiles in the grid cell, including `placement`
+///
+/// returns: Option<ConflictKind>
+fn conflict_of(
+    data: &CellData,
+    placement: &TileCellPlacement,
+    placements: &[TileCellPlacement],
+) -> Option<ConflictKind> {
+    // Two halves of the same cell only overlap, if they share a quarter.
+    let overlapping = placements.iter().any(|other| {
+        other.tile_id != placement.tile_id && other.quarters & placement.quarters != 0
+    });
+    let outside_half_cell = data.is_on_board && placement.quarters & !data.board_quarters != 0;
+    if overlapping {
+        Some(ConflictKind::Overlap)
+    } else if data.is_hole {
+        Some(ConflictKind::OnHole)
+    } else if !data.allowed || outside_half_cell {
+        Some(ConflictKind::OffBoard)
+    } else {
+        None
+    }
+}
+
+/// Returns true, if the position is within the layout and the cell there is on the board.
+fn is_on_layout(layout: &Shape, position: CellOffset) -> bool {
+    geometry::is_within(position, layout.dim()) && *layout.get(position.into()).unwrap_or(&false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use puzzled_common::shape::cell::QUARTERS_ALL;
+    use puzzled_common::shape::shape_square;
+    use std::cell::RefCell;
+
+    fn create_state(layout: Shape) -> PuzzleState {
+        let extension = RefCell::new(None);
+        PuzzleState::new(&BoardConfig::Simple { layout }, extension.borrow())
+    }
+
+    fn cell_data(cell: Option<&Cell>) -> Option<&CellData> {
+        cell.map(|cell| match cell {
+            Cell::Empty(data) | Cell::One(data, _) | Cell::Many(data, _) => data,
+        })
+    }
+
+    #[test]
+    fn test_board_and_state_positions() {
+        assert_eq!(
+            PuzzleState::state_to_board(CellOffset(1, 1)),
+            CellOffset(0, 0)
+        );
+        assert_eq!(
+            PuzzleState::state_to_board(CellOffset(3, 1)),
+            CellOffset(2, 0)
+        );
+        assert_eq!(
+            PuzzleState::board_to_state(CellOffset(-1, 0)),
+            CellOffset(0, 1)
+        );
+    }
+
+    #[test]
+    fn test_border_cells() {
+        let state = create_state(shape_square(&[[true, false], [true, true]]));
+
+        let on_board = cell_data(state.cell_at_board(CellOffset(0, 0))).unwrap();
+        assert!(on_board.is_on_board && on_board.allowed);
+        let hole = cell_data(state.cell_at_board(CellOffset(0, 1))).unwrap();
+        assert!(!hole.is_on_board && !hole.allowed && hole.is_hole);
+        let left_border = cell_data(state.cell_at_board(CellOffset(-1, 0))).unwrap();
+        assert!(!left_border.is_on_board && !left_border.allowed && !left_border.is_hole);
+        let far_border = cell_data(state.cell_at_board(CellOffset(2, 1))).unwrap();
+        assert!(!far_border.is_on_board && !far_border.allowed);
+        let corner = cell_data(state.cell_at_board(CellOffset(-1, -1))).unwrap();
+        assert!(!corner.is_on_board && corner.allowed);
+        assert!(state.cell_at_board(CellOffset(3, 0)).is_none());
+        assert!(state.cell_at_board(CellOffset(-2, 0)).is_none());
+    }
+    #[test]
+    fn test_without_tiles() {
+        let mut state = create_state(shape_square(&[[true, true]]));
+        let placement = |tile_id| TileCellPlacement {
+            tile_id,
+            cell_position: CellOffset(0, 0),
+            quarters: QUARTERS_ALL,
+        };
+        let tile = |id| UnusedTile {
+            id,
+            base: shape_square(&[[true]]),
+            name: None,
+        };
+        let data = cell_data(state.cell_at_board(CellOffset(0, 0)))
+            .unwrap()
+            .clone();
+        state.grid[(1, 1)] = Cell::Many(data.clone(), vec![placement(0), placement(1)]);
+        state.grid[(1, 2)] = Cell::One(data, placement(2));
+        state.placed_tiles = HashSet::from([tile(0), tile(1), tile(2)]);
+
+        let removed = state.without_tiles(&[1, 2]);
+        assert!(matches!(
+            removed.cell_at_board(CellOffset(0, 0)),
+            Some(Cell::One(_, placement)) if placement.tile_id == 0
+        ));
+        assert!(matches!(
+            removed.cell_at_board(CellOffset(0, 1)),
+            Some(Cell::Empty(_))
+        ));
+        assert_eq!(removed.placed_tiles, HashSet::from([tile(0)]));
+        assert_eq!(removed.unused_tiles, HashSet::from([tile(1), tile(2)]));
+    }
+
+    #[test]
+    fn test_has_invalid_cells() {
+        let mut state = create_state(shape_square(&[[true, true]]));
+        let placement = |tile_id, quarters| TileCellPlacement {
+            tile_id,
+            cell_position: CellOffset(0, 0),
+            quarters,
+        };
+        let data = cell_data(state.cell_at_board(CellOffset(0, 0)))
+            .unwrap()
+            .clone();
+        state.grid[(1, 1)] = Cell::One(data.clone(), placement(0, QUARTERS_ALL));
+        assert!(!state.has_invalid_cells(0));
+
+        state.grid[(1, 1)] = Cell::Many(
+            data,
+            vec![placement(0, QUARTERS_ALL), placement(1, QUARTERS_ALL)],
+        );
+        assert!(state.has_invalid_cells(0));
+        assert!(state.has_invalid_cells(1));
+        assert!(!state.has_invalid_cells(2));
+
+        let border = cell_data(state.cell_at_board(CellOffset(-1, 0)))
+            .unwrap()
+            .clone();
+        state.grid[(0, 1)] = Cell::One(border, placement(2, QUARTERS_ALL));
+        assert!(state.has_invalid_cells(2));
+    }
+
+    #[test]
+    fn test_conflict_kinds() {
+        let mut state = create_state(shape_square(&[[true, true], [false, true]]));
+        let placement = |tile_id| TileCellPlacement {
+            tile_id,
+            cell_position: CellOffset(0, 0),
+            quarters: QUARTERS_ALL,
+        };
+        let data_at = |state: &PuzzleState, x, y| {
+            cell_data(state.cell_at_board(CellOffset(x, y)))
+                .unwrap()
+                .clone()
+        };
+        assert!(state.all_conflict_kinds().is_empty());
+
+        state.grid[(1, 1)] = Cell::One(data_at(&state, 0, 0), placement(0));
+        state.grid[(0, 1)] = Cell::One(data_at(&state, -1, 0), placement(0));
+        assert_eq!(
+            state.conflict_kinds(0),
+            HashSet::from([ConflictKind::OffBoard])
+        );
+
+        state.grid[(2, 2)] = Cell::One(data_at(&state, 1, 1), placement(1));
+        state.grid[(2, 1)] = Cell::One(data_at(&state, 1, 0), placement(1));
+        assert_eq!(
+            state.conflict_kinds(1),
+            HashSet::from([ConflictKind::OnHole])
+        );
+
+        state.grid[(1, 2)] = Cell::Many(data_at(&state, 0, 1), vec![placement(2), placement(3)]);
+        assert_eq!(
+            state.conflict_kinds(2),
+            HashSet::from([ConflictKind::Overlap])
+        );
+        assert_eq!(
+            state.all_conflict_kinds(),
+            HashSet::from([
+                ConflictKind::OffBoard,
+                ConflictKind::OnHole,
+                ConflictKind::Overlap
+            ])
+        );
+    }
+}
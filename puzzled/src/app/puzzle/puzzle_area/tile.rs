@@ -1,13 +1,25 @@
 use crate::app::components::tile::TileView;
+use crate::app::puzzle::puzzle_area::puzzle_state::ConflictKind;
 use crate::app::puzzle::puzzle_area::PuzzleArea;
+use crate::audio;
+use crate::audio::SoundEffect;
+use crate::global::settings::{Preferences, PushOverlappingTiles};
+use crate::model::placement::geometry;
 use crate::offset::PixelOffset;
 use adw::gdk::{BUTTON_MIDDLE, BUTTON_SECONDARY};
+use adw::gio::{Menu, SimpleAction, SimpleActionGroup};
 use adw::subclass::prelude::ObjectSubclassIsExt;
+use gettextrs::gettext;
 use gtk::prelude::{
-    Cast, EventControllerExt, FixedExt, GestureDragExt, GestureSingleExt, WidgetExt,
+    ActionMapExt, Cast, EventControllerExt, FixedExt, GestureDragExt, GestureExt, GestureSingleExt,
+    WidgetExt,
+};
+use gtk::{
+    EventController, EventSequenceState, GestureClick, GestureDrag, GestureLongPress,
+    PropagationPhase, Widget,
 };
-use gtk::{EventController, GestureClick, GestureDrag, PropagationPhase, Widget};
 use puzzle_config::TileConfig;
+use std::collections::HashSet;
 
 impl PuzzleArea {
     pub fn setup_tile(&self, tile: &TileConfig, tile_id: usize) {
@@ -15,21 +27,35 @@ impl PuzzleArea {
 
         self.setup_drag_and_drop(tile_id, tile_view.upcast_ref());
         self.setup_tile_rotation_and_flip(tile_id, tile_view.upcast_ref());
+        self.setup_tile_context_menu(tile_id, &tile_view);
         self.add(tile_view.upcast_ref(), &PixelOffset(0.0, 0.0));
         self.imp().tiles.borrow_mut().push(tile_view);
+        self.imp().tile_order.borrow_mut().push(tile_id);
     }
 
     fn setup_drag_and_drop(&self, tile_view_index: usize, draggable: &Widget) {
         let drag = GestureDrag::new();
         drag.set_propagation_phase(PropagationPhase::Capture);
 
+        // Raise the tile as soon as it is pressed, so it stays grabbable even if it was covered.
+        let press = GestureClick::new();
+        press.set_button(0);
+        press.set_propagation_phase(PropagationPhase::Capture);
+        press.connect_pressed({
+            let self_clone = self.clone();
+            move |_, _, _, _| self_clone.raise_tile(tile_view_index)
+        });
+        draggable.add_controller(press);
+
         drag.connect_drag_begin({
             let self_clone = self.clone();
             move |_, _x, _y| {
+                self_clone.remember_ghost_origin(tile_view_index);
                 let placement_model_borrow = self_clone.imp().placement_model.borrow();
                 let placement_model = placement_model_borrow.as_ref().unwrap();
                 placement_model.update_tile_dragged(tile_view_index, true);
                 self_clone.run_on_tile_moved();
+                audio::play(SoundEffect::PickUp);
             }
         });
 
@@ -44,15 +70,12 @@ impl PuzzleArea {
                             None => return,
                         }
                     };
-                    let mut pos: PixelOffset = self_clone.child_position(tile_view).into();
-                    pos = pos.add_tuple((dx, dy));
-
-                    let max_x = self_clone.width() as f64 - tile_view.width() as f64;
-                    let max_y = self_clone.height() as f64 - tile_view.height() as f64;
-                    pos.0 = pos.0.clamp(0.0, max_x);
-                    pos.1 = pos.1.clamp(0.0, max_y);
-
-                    pos
+                    let pos: PixelOffset = self_clone.child_position(tile_view).into();
+                    geometry::clamp_to_area(
+                        pos.add_tuple((dx, dy)),
+                        PixelOffset(tile_view.width() as f64, tile_view.height() as f64),
+                        PixelOffset(self_clone.width() as f64, self_clone.height() as f64),
+                    )
                 };
                 self_clone.move_to(tile_view_index, new);
             }
@@ -73,6 +96,15 @@ impl PuzzleArea {
                 let placement_model = placement_model_borrow.as_ref().unwrap();
                 placement_model.update_tile_dragged(tile_view_index, false);
                 placement_model.update_tile_pixel_position(tile_view_index, pos);
+                if Preferences::default().get(PushOverlappingTiles) {
+                    placement_model.push_aside_tiles_overlapping(tile_view_index);
+                }
+                let conflict_kinds = self_clone
+                    .extract_puzzle_state()
+                    .map(|state| state.conflict_kinds(tile_view_index))
+                    .unwrap_or_default();
+                audio::play(drop_sound(&conflict_kinds));
+                self_clone.show_ghost_trail(tile_view);
             }
         });
 
@@ -85,16 +117,7 @@ impl PuzzleArea {
         gesture.set_button(BUTTON_SECONDARY);
         self.setup_tile_updating_gesture(tile_view_index, &gesture, {
             let self_clone = self.clone();
-            move |tile_view| {
-                tile_view.rotate_clockwise();
-                self_clone
-                    .imp()
-                    .placement_model
-                    .borrow()
-                    .as_ref()
-                    .unwrap()
-                    .update_tile_shape(tile_view.id(), tile_view.current_rotation().clone());
-            }
+            move |tile_view| self_clone.rotate_tile(tile_view)
         });
         draggable.add_controller(gesture.upcast::<EventController>());
 
@@ -103,20 +126,51 @@ impl PuzzleArea {
         gesture.set_button(BUTTON_MIDDLE);
         self.setup_tile_updating_gesture(tile_view_index, &gesture, {
             let self_clone = self.clone();
-            move |tile_view| {
-                tile_view.flip_horizontal();
-                self_clone
-                    .imp()
-                    .placement_model
-                    .borrow()
-                    .as_ref()
-                    .unwrap()
-                    .update_tile_shape(tile_view.id(), tile_view.current_rotation().clone());
-            }
+            move |tile_view| self_clone.flip_tile(tile_view)
         });
         draggable.add_controller(gesture.upcast::<EventController>());
     }
 
+    fn rotate_tile(&self, tile_view: &TileView) {
+        tile_view.rotate_clockwise();
+        self.update_tile_shape(tile_view);
+    }
+
+    fn flip_tile(&self, tile_view: &TileView) {
+        tile_view.flip_horizontal();
+        self.update_tile_shape(tile_view);
+    }
+
+    fn update_tile_shape(&self, tile_view: &TileView) {
+        self.imp()
+            .placement_model
+            .borrow()
+            .as_ref()
+            .unwrap()
+            .update_tile_shape(tile_view.id(), tile_view.current_rotation().clone());
+    }
+
+    /// Rotates the tile on top of the stack clockwise.
+    /// This is the tile the player pressed last, so it can be rotated with the keyboard.
+    pub fn rotate_top_tile(&self) {
+        self.update_top_tile(|tile_view| self.rotate_tile(tile_view));
+    }
+
+    /// Flips the tile on top of the stack like [Self::rotate_top_tile] rotates it.
+    pub fn flip_top_tile(&self) {
+        self.update_top_tile(|tile_view| self.flip_tile(tile_view));
+    }
+
+    fn update_top_tile<F: FnOnce(&TileView)>(&self, tile_update_function: F) {
+        let Some(tile_view_index) = self.imp().tile_order.borrow().last().copied() else {
+            return;
+        };
+        let tiles = self.imp().tiles.borrow();
+        if let Some(tile_view) = tiles.get(tile_view_index) {
+            tile_update_function(tile_view);
+        }
+    }
+
     fn setup_tile_updating_gesture<F: Fn(&TileView) + 'static>(
         &self,
         tile_view_index: usize,
@@ -138,6 +192,77 @@ impl PuzzleArea {
         });
     }
 
+    /// Set up a context menu, which is opened by long pressing the tile.
+    /// Right click is already used for rotating tiles.
+    fn setup_tile_context_menu(&self, tile_view_index: usize, tile_view: &TileView) {
+        let send_to_back = SimpleAction::new("send-to-back", None);
+        send_to_back.connect_activate({
+            let self_clone = self.clone();
+            move |_, _| self_clone.send_tile_to_back(tile_view_index)
+        });
+        let show_placements = SimpleAction::new("show-placements", None);
+        show_placements.connect_activate({
+            let self_clone = self.clone();
+            move |_, _| self_clone.show_tile_placements(tile_view_index)
+        });
+        let action_group = SimpleActionGroup::new();
+        action_group.add_action(&send_to_back);
+        action_group.add_action(&show_placements);
+        tile_view.insert_action_group("tile", Some(&action_group));
+
+        let menu = Menu::new();
+        menu.append(Some(&gettext("Send to Back")), Some("tile.send-to-back"));
+        menu.append(
+            Some(&gettext("Where Can This Go?")),
+            Some("tile.show-placements"),
+        );
+        tile_view.set_context_menu(menu.upcast_ref());
+
+        let long_press = GestureLongPress::new();
+        long_press.connect_pressed({
+            let tile_view = tile_view.clone();
+            move |gesture, x, y| {
+                gesture.set_state(EventSequenceState::Claimed);
+                tile_view.popup_context_menu(x, y);
+            }
+        });
+        tile_view.add_controller(long_press);
+    }
+
+    /// Move the tile to the top of the stack, so it is drawn above all other tiles.
+    pub fn raise_tile(&self, tile_view_index: usize) {
+        {
+            let mut tile_order = self.imp().tile_order.borrow_mut();
+            tile_order.retain(|i| *i != tile_view_index);
+            tile_order.push(tile_view_index);
+        }
+        self.apply_tile_order();
+    }
+
+    /// Move the tile to the bottom of the stack, so it is drawn below all other tiles.
+    pub fn send_tile_to_back(&self, tile_view_index: usize) {
+        {
+            let mut tile_order = self.imp().tile_order.borrow_mut();
+            tile_order.retain(|i| *i != tile_view_index);
+            tile_order.insert(0, tile_view_index);
+        }
+        self.apply_tile_order();
+    }
+
+    /// Reorder the children of the puzzle area to match the stacking order of the tiles.
+    /// The board stays below and the hint tile above all tiles.
+    pub(super) fn apply_tile_order(&self) {
+        let tiles = self.imp().tiles.borrow();
+        for i in self.imp().tile_order.borrow().iter() {
+            if let Some(tile_view) = tiles.get(*i) {
+                tile_view.insert_before(self, None::<&Widget>);
+            }
+        }
+        if let Some(hint_tile) = self.imp().hint_tile.borrow().as_ref() {
+            hint_tile.insert_before(self, None::<&Widget>);
+        }
+    }
+
     pub fn update_tile_layout(&self) {
         let len = self.imp().tiles.borrow().len();
         for i in 0..len {
@@ -157,6 +282,7 @@ impl PuzzleArea {
                 self.move_to(i, pos);
             }
         }
+        self.apply_tile_order();
     }
 
     /// Move the tile to the specified (x, y) position in pixels.
@@ -164,7 +290,39 @@ impl PuzzleArea {
         let tiles = self.imp().tiles.borrow();
         if let Some(tile_view) = tiles.get(tile_view_index) {
             self.move_(tile_view, pos_pixel.0, pos_pixel.1);
-            tile_view.insert_before(self, None::<&Widget>); // Bring to front
         }
     }
 }
+
+/// Returns the sound played, when a tile is dropped with the given conflicts.
+/// Overlapping another tile takes precedence over the conflicts with the board.
+fn drop_sound(conflict_kinds: &HashSet<ConflictKind>) -> SoundEffect {
+    if conflict_kinds.contains(&ConflictKind::Overlap) {
+        SoundEffect::InvalidPlacement
+    } else if conflict_kinds.is_empty() {
+        SoundEffect::Drop
+    } else {
+        SoundEffect::OffBoard
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_drop_sound() {
+        assert_eq!(drop_sound(&HashSet::new()), SoundEffect::Drop);
+        assert_eq!(
+            drop_sound(&HashSet::from([ConflictKind::OnHole])),
+            SoundEffect::OffBoard
+        );
+        assert_eq!(
+            drop_sound(&HashSet::from([
+                ConflictKind::OffBoard,
+                ConflictKind::Overlap
+            ])),
+            SoundEffect::InvalidPlacement
+        );
+    }
+}
@@ -1,21 +1,34 @@
+use crate::app::components::board::area_swatch;
+use crate::app::presenter::target_items;
+use crate::audio;
+use crate::global::events;
+use crate::global::events::AppEvent;
+use crate::global::idle_monitor;
+use crate::global::settings::{Preferences, ShowTileConstraints};
 use crate::model::extension::PuzzleTypeExtension;
 use crate::model::puzzle::PuzzleModel;
+use crate::model::solve_timer::SolveTimer;
 use crate::solver::Solver;
 use crate::window::{PuzzledWindow, MIN_WINDOW_HEIGHT, MIN_WINDOW_WIDTH};
 use adw::gio;
-use adw::prelude::NavigationPageExt;
+use adw::prelude::{ActionMapExt, NavigationPageExt};
 use adw::subclass::prelude::*;
 use gtk::glib;
-use gtk::prelude::WidgetExt;
+use gtk::prelude::{GtkWindowExt, WidgetExt};
 use log::debug;
+use puzzle_config::{BoardConfig, Target};
 
 mod imp {
     use super::*;
     use crate::app::puzzle::puzzle_area::PuzzleArea;
+    use crate::global::idle_monitor::IdleInhibitor;
     use crate::model::extension::PuzzleTypeExtension;
+    use crate::model::inactivity::InactivityTracker;
+    use crate::model::solve_timer::SolveTimer;
     use crate::solver::combination_solutions::CombinationsSolver;
     use crate::window::PuzzledWindow;
     use std::cell::{Cell, OnceCell, RefCell};
+    use tokio_util::sync::CancellationToken;
 
     #[derive(Debug, Default, gtk::CompositeTemplate)]
     #[template(resource = "/de/til7701/Puzzled/ui/page/puzzle-page.ui")]
@@ -25,22 +38,55 @@ mod imp {
         #[template_child]
         pub toast_overlay: TemplateChild<adw::ToastOverlay>,
         #[template_child]
+        pub content_stack: TemplateChild<gtk::Stack>,
+        /// Scrolls the puzzle area, if it does not fit on the screen.
+        #[template_child]
+        pub grid_scroller: TemplateChild<gtk::ScrolledWindow>,
+        #[template_child]
         pub grid: TemplateChild<PuzzleArea>,
         #[template_child]
+        pub trivial_status: TemplateChild<adw::StatusPage>,
+        #[template_child]
+        pub trivial_next_button: TemplateChild<gtk::Button>,
+        #[template_child]
         pub puzzle_info_button: TemplateChild<gtk::Button>,
         #[template_child]
+        pub rotate_board_button: TemplateChild<gtk::Button>,
+        #[template_child]
         pub extension_separator: TemplateChild<gtk::Separator>,
         #[template_child]
         pub target_selection_button: TemplateChild<gtk::Button>,
         #[template_child]
+        pub random_target_button: TemplateChild<gtk::Button>,
+        #[template_child]
         pub hint_button: TemplateChild<gtk::Button>,
+        #[template_child]
+        pub coverage_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub remaining_cells_label: TemplateChild<gtk::Label>,
+        #[template_child]
+        pub area_legend: TemplateChild<adw::WrapBox>,
+        #[template_child]
+        pub coach_banner: TemplateChild<adw::Banner>,
 
         pub window: OnceCell<PuzzledWindow>,
 
         pub puzzle: RefCell<Option<PuzzleModel>>,
         pub extension: RefCell<Option<PuzzleTypeExtension>>,
         pub hint_count: Cell<u32>,
+        /// Started when the current target was selected, to measure the time needed to solve it.
+        pub timer: Cell<SolveTimer>,
+        /// Keeps the screen on while the puzzle is being solved, if the player enabled it.
+        pub idle_inhibitor: RefCell<Option<IdleInhibitor>>,
+        /// Counts down the cooldown after a hint, while it is running.
+        pub hint_cooldown: RefCell<Option<glib::SourceId>>,
+        /// Tracks how long the player made no progress, to offer a hint in coach mode.
+        pub coach: RefCell<InactivityTracker>,
+        /// Checks periodically whether the coach should offer a hint, while a puzzle is shown.
+        pub coach_check: RefCell<Option<glib::SourceId>>,
         pub combinations_solver: RefCell<CombinationsSolver>,
+        /// Cancels the search for a solvable random target, while it is running.
+        pub random_target_cancel_token: RefCell<Option<CancellationToken>>,
     }
 
     #[glib::object_subclass]
@@ -54,10 +100,35 @@ mod imp {
             klass.install_action("app.puzzle_info", None, |page, _, _| {
                 page.show_puzzle_info()
             });
+            klass.install_action("app.puzzle_notes", None, |page, _, _| {
+                page.show_notes_dialog()
+            });
+            klass.install_action("app.practice", None, |page, _, _| {
+                page.show_practice_dialog()
+            });
+            klass.install_action("app.export_solutions", None, |page, _, _| {
+                page.show_export_solutions_dialog()
+            });
             klass.install_action("app.select_target", None, |page, _, _| {
                 page.show_target_selection_dialog()
             });
+            klass.install_action("app.random_target", None, |page, _, _| {
+                page.select_random_target()
+            });
             klass.install_action("app.hint", None, |page, _, _| page.on_hint_requested());
+            klass.install_action("app.rotate_board", None, |page, _, _| page.rotate_board());
+            klass.install_action("app.next_puzzle", None, |page, _, _| {
+                page.show_next_puzzle()
+            });
+            klass.install_action("app.next_unsolved_puzzle", None, |page, _, _| {
+                page.show_next_unsolved_puzzle()
+            });
+            klass.install_action("app.rotate_tile", None, |page, _, _| {
+                page.imp().grid.rotate_top_tile()
+            });
+            klass.install_action("app.flip_tile", None, |page, _, _| {
+                page.imp().grid.flip_top_tile()
+            });
         }
 
         fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
@@ -92,25 +163,69 @@ impl PuzzlePage {
     }
 
     pub fn post_construct_setup(&self) {
+        events::subscribe({
+            let self_clone = self.clone();
+            move |event| {
+                if let AppEvent::TargetChanged(target) = event {
+                    self_clone.update_coverage_info(target.as_ref());
+                }
+            }
+        });
+        self.setup_remaining_cells();
+        self.setup_coach();
+        let actions = gio::SimpleActionGroup::new();
+        actions.add_action(&Preferences::default().create_action(ShowTileConstraints));
+        self.insert_action_group("puzzle", Some(&actions));
+        events::subscribe({
+            let self_clone = self.clone();
+            move |event| {
+                if let AppEvent::SolverSearchSnapshot(snapshot) = event {
+                    self_clone
+                        .imp()
+                        .grid
+                        .show_search_snapshot(snapshot.as_ref());
+                }
+            }
+        });
         let solver = Solver::default();
-        self.imp().grid.connect_tile_moved({
+        events::subscribe({
             let self_clone = self.clone();
-            move || {
+            move |event| {
+                if *event != AppEvent::TileMoved {
+                    return;
+                }
                 solver.interrupt_solver_call();
                 let puzzle_state = self_clone.imp().grid.extract_puzzle_state();
                 if let Ok(puzzle_state) = puzzle_state
                     && solver.is_solved(&puzzle_state)
                 {
-                    self_clone.on_solved();
+                    self_clone.on_solved(&puzzle_state);
                 }
             }
         });
+        idle_monitor::connect_idle_changed({
+            let self_clone = self.clone();
+            move |idle| {
+                let mut timer = self_clone.imp().timer.get();
+                if idle {
+                    timer.pause();
+                } else {
+                    timer.resume();
+                }
+                self_clone.imp().timer.set(timer);
+            }
+        });
         self.connect_hiding({
             let self_clone = self.clone();
             move |_| {
+                self_clone.imp().idle_inhibitor.replace(None);
+                self_clone.stop_coach();
                 Solver::default().interrupt_solver_call();
+                self_clone.cancel_random_target();
+                audio::play_music(None);
                 let window = self_clone.imp().window.get();
                 if let Some(window) = window {
+                    window.set_title(Some("Puzzled"));
                     window.set_width_request(MIN_WINDOW_WIDTH);
                     window.set_height_request(MIN_WINDOW_HEIGHT);
                 }
@@ -119,27 +234,102 @@ impl PuzzlePage {
     }
 
     pub fn show_puzzle(&self, puzzle: &PuzzleModel) {
+        Solver::default().clear_session();
+        self.cancel_random_target();
         self.imp().puzzle.replace(Some(puzzle.clone()));
         self.update_extension(&Some(PuzzleTypeExtension::default_for_puzzle(
             puzzle.config(),
         )));
         self.imp().hint_count.replace(0);
+        self.reset_hint_button();
         self.imp().grid.show_puzzle(puzzle);
         self.show_puzzle_extension();
+        self.show_area_legend(puzzle.config().board_config());
+        self.update_remaining_cells();
+        self.start_coach();
+        self.update_trivial_state(puzzle);
+        self.imp()
+            .rotate_board_button
+            .set_visible(puzzle.collection().config().allow_board_rotation());
+        audio::play_music(puzzle.collection().config().music().as_deref());
+        if let Some(window) = self.imp().window.get() {
+            self.imp()
+                .idle_inhibitor
+                .replace(idle_monitor::inhibit(window, "Solving a puzzle"));
+        }
+    }
 
-        let title = format!(
-            "{} - {}",
-            puzzle.collection().config().name(),
-            puzzle.config().name()
-        );
-        self.set_title(&title);
+    /// Shows which color belongs to which area, if the board asks for a legend.
+    fn show_area_legend(&self, board_config: &BoardConfig) {
+        let legend = &self.imp().area_legend;
+        while let Some(child) = legend.first_child() {
+            legend.remove(&child);
+        }
+        let BoardConfig::Area {
+            area_configs,
+            show_legend: true,
+            ..
+        } = board_config
+        else {
+            legend.set_visible(false);
+            return;
+        };
+        for (i, area_config) in area_configs.iter().enumerate() {
+            let entry = gtk::Box::new(gtk::Orientation::Horizontal, 6);
+            let swatch = area_swatch(i as i32);
+            swatch.set_valign(gtk::Align::Center);
+            entry.append(&swatch);
+            entry.append(&gtk::Label::new(Some(area_config.name())));
+            legend.append(&entry);
+        }
+        legend.set_visible(true);
+    }
+
+    /// Returns the puzzle shown last.
+    pub fn puzzle(&self) -> Option<PuzzleModel> {
+        self.imp().puzzle.borrow().clone()
     }
 
     pub fn update_extension(&self, extension: &Option<PuzzleTypeExtension>) {
         debug!("Updating puzzle type extension to: {:?}", extension);
         self.imp().extension.replace(extension.clone());
+        let mut timer = SolveTimer::default();
+        timer.start();
+        if idle_monitor::is_idle() {
+            timer.pause();
+        }
+        self.imp().timer.set(timer);
         self.imp().grid.set_puzzle_type_extension(extension.clone());
         self.update_target_selection_button();
+        let target = match extension {
+            Some(PuzzleTypeExtension::Area { target }) => target.clone(),
+            _ => None,
+        };
+        self.update_title(&target);
+        events::publish(AppEvent::TargetChanged(target));
+    }
+
+    /// Shows the puzzle and the selected target in the page and window title.
+    fn update_title(&self, target: &Option<Target>) {
+        let puzzle = self.imp().puzzle.borrow();
+        let Some(puzzle) = puzzle.as_ref() else {
+            return;
+        };
+        let target_label = target_items::target_label(puzzle.config().board_config(), target);
+        let title = target_items::page_title(
+            puzzle.collection().config().name(),
+            puzzle.config().name(),
+            target_label.as_deref(),
+        );
+        self.set_title(&title);
+        if let Some(window) = self.imp().window.get() {
+            window.set_title(Some(&title));
+        }
+    }
+
+    /// Rotates the board of the current puzzle clockwise together with the tiles on it.
+    fn rotate_board(&self) {
+        self.imp().grid.rotate_board();
     }
 
     pub fn header_bar(&self) -> adw::HeaderBar {
@@ -0,0 +1,134 @@
+use crate::app::puzzle::puzzle_area::puzzle_state::{Cell, ConflictKind, PuzzleState};
+use crate::app::puzzle::puzzle_page::PuzzlePage;
+use crate::global::events;
+use crate::global::events::AppEvent;
+use crate::global::settings::{Preferences, ShowRemainingCells};
+use adw::subclass::prelude::ObjectSubclassIsExt;
+use gtk::prelude::WidgetExt;
+use puzzle_config::RemainingCoverage;
+use puzzled_common::Shape;
+use std::collections::HashSet;
+
+impl PuzzlePage {
+    /// Shows the remaining cells label depending on the preference and keeps it up to date
+    /// while tiles are moved.
+    pub(super) fn setup_remaining_cells(&self) {
+        Preferences::default().bind(
+            ShowRemainingCells,
+            &*self.imp().remaining_cells_label,
+            "visible",
+        );
+        events::subscribe({
+            let self_clone = self.clone();
+            move |event| {
+                if matches!(event, AppEvent::TileMoved | AppEvent::TargetChanged(_)) {
+                    self_clone.update_remaining_cells();
+                }
+            }
+        });
+    }
+
+    /// Updates the label showing how many empty cells are left on the board.
+    /// It is marked as an error, if the tiles not placed yet cannot cover the empty cells, and as
+    /// a warning, if placed tiles conflict with the board or each other.
+    pub(super) fn update_remaining_cells(&self) {
+        let Ok(puzzle_state) = self.imp().grid.extract_puzzle_state() else {
+            return;
+        };
+        let coverage = remaining_coverage(&puzzle_state);
+
+        let label = &self.imp().remaining_cells_label;
+        label.set_label(&remaining_cells_text(coverage.empty_cells()));
+        label.remove_css_class("error");
+        label.remove_css_class("warning");
+        if !coverage.is_coverable() {
+            label.add_css_class("error");
+            label.set_tooltip_text(Some("The remaining tiles cannot cover the empty cells."));
+        } else if let Some(tooltip) = conflict_tooltip(&puzzle_state.all_conflict_kinds()) {
+            label.add_css_class("warning");
+            label.set_tooltip_text(Some(tooltip));
+        } else {
+            label.set_tooltip_text(None);
+        }
+    }
+}
+
+fn remaining_coverage(puzzle_state: &PuzzleState) -> RemainingCoverage {
+    let empty_quarters = puzzle_state
+        .grid
+        .iter()
+        .map(|cell| {
+            let empty = match cell {
+                Cell::Empty(data) => data.board_quarters,
+                Cell::One(data, placement) => data.board_quarters & !placement.quarters,
+                Cell::Many(data, placements) => placements
+                    .iter()
+                    .fold(data.board_quarters, |empty, placement| {
+                        empty & !placement.quarters
+                    }),
+            };
+            empty.count_ones() as usize
+        })
+        .sum();
+    let remaining_tiles: Vec<Shape> = puzzle_state
+        .unused_tiles
+        .iter()
+        .map(|tile| tile.base.clone())
+        .collect();
+    RemainingCoverage::new(
+        empty_quarters,
+        puzzle_state.wildcard_areas.len(),
+        &remaining_tiles,
+    )
+}
+
+/// Explains why the count may be off, if placed tiles conflict with the board or each other.
+/// Covered cells are counted, even if the tiles covering them overlap or reach off the board.
+fn conflict_tooltip(conflict_kinds: &HashSet<ConflictKind>) -> Option<&'static str> {
+    if conflict_kinds.contains(&ConflictKind::Overlap) {
+        Some("Some tiles overlap and need to be moved.")
+    } else if conflict_kinds.contains(&ConflictKind::OnHole) {
+        Some("Some tiles cover holes in the board.")
+    } else if conflict_kinds.contains(&ConflictKind::OffBoard) {
+        Some("Some tiles reach off the board.")
+    } else {
+        None
+    }
+}
+
+fn remaining_cells_text(empty_cells: f32) -> String {
+    if empty_cells == 1.0 {
+        "1 cell left".to_string()
+    } else {
+        format!("{} cells left", empty_cells)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_remaining_cells_text() {
+        assert_eq!(remaining_cells_text(13.0), "13 cells left");
+        assert_eq!(remaining_cells_text(1.0), "1 cell left");
+        assert_eq!(remaining_cells_text(2.5), "2.5 cells left");
+        assert_eq!(remaining_cells_text(0.0), "0 cells left");
+    }
+
+    #[test]
+    fn test_conflict_tooltip() {
+        assert_eq!(conflict_tooltip(&HashSet::new()), None);
+        assert_eq!(
+            conflict_tooltip(&HashSet::from([ConflictKind::OffBoard])),
+            Some("Some tiles reach off the board.")
+        );
+        assert_eq!(
+            conflict_tooltip(&HashSet::from([
+                ConflictKind::OffBoard,
+                ConflictKind::Overlap
+            ])),
+            Some("Some tiles overlap and need to be moved.")
+        );
+    }
+}
@@ -0,0 +1,473 @@
+use crate::adw_ext;
+use crate::app::components::tile;
+use crate::app::puzzle::puzzle_area::puzzle_state::PuzzleState;
+use crate::app::puzzle::puzzle_page::PuzzlePage;
+use crate::offset::CellOffset;
+use crate::solver::queue::SolverPriority;
+use crate::solver::{queue, Solver};
+use crate::ui_bridge;
+use adw::gdk::RGBA;
+use adw::gio::{Cancellable, File};
+use adw::prelude::{AdwDialogExt, AlertDialogExt, FileExt, GdkCairoContextExt};
+use adw::subclass::prelude::ObjectSubclassIsExt;
+use adw::{glib, AlertDialog, ResponseAppearance, Toast};
+use cairo::{Context, Format, ImageSurface, PdfSurface};
+use gtk::FileFilter;
+use log::{debug, error, info};
+use puzzle_config::{ColorConfig, PuzzleConfig};
+use puzzle_solver::result::{Solution, UnsolvableReason};
+use puzzle_solver::tile::Tile;
+use puzzled_common::Shape;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// The maximum number of solutions exported at once, so the export stays reasonably fast for
+/// puzzles with many solutions.
+const MAX_EXPORTED_SOLUTIONS: u64 = 200;
+/// How many pixels a cell has in a PNG image. In a PDF document, it is the size in points.
+const EXPORT_CELL_SIZE: f64 = 32.0;
+/// The gap between the cells of the board in an exported image.
+const EXPORT_CELL_GAP: f64 = 2.0;
+/// The empty space around the board in an exported image.
+const EXPORT_MARGIN: f64 = 16.0;
+const EXPORT_BACKGROUND_COLOR: RGBA = adw_ext::LIGHT_1;
+const EXPORT_BOARD_COLOR: RGBA = adw_ext::LIGHT_3;
+/// How often the progress bar moves while the solutions are searched.
+const SEARCH_PULSE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The file format, in which the solutions are exported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ExportFormat {
+    /// All solutions as pages of a single PDF document.
+    Pdf,
+    /// Each solution as a PNG image in a folder.
+    Png,
+}
+
+impl PuzzlePage {
+    /// Lets the player export images of the solutions of the puzzle for the current target.
+    /// Up to [MAX_EXPORTED_SOLUTIONS] solutions are rendered into a PDF document or a folder of
+    /// PNG images.
+    pub(super) fn show_export_solutions_dialog(&self) {
+        let puzzle = self.imp().puzzle.borrow().clone();
+        let Some(puzzle) = puzzle else {
+            return;
+        };
+        if puzzle.config().is_unsolvable() {
+            self.imp()
+                .toast_overlay
+                .add_toast(Toast::new("This puzzle has no solutions to export"));
+            return;
+        }
+
+        let dialog = AlertDialog::builder()
+            .heading("Export Solutions")
+            .body(format!(
+                "Saves images of up to {} solutions for the current target, either as pages of a PDF document or as PNG images in a folder.",
+                MAX_EXPORTED_SOLUTIONS
+            ))
+            .build();
+        let cancel_id = "cancel";
+        let png_id = "png";
+        let pdf_id = "pdf";
+        dialog.add_response(cancel_id, "Cancel");
+        dialog.add_response(png_id, "PNG Images");
+        dialog.add_response(pdf_id, "PDF Document");
+        dialog.set_default_response(Some(pdf_id));
+        dialog.set_close_response(cancel_id);
+        dialog.set_response_appearance(pdf_id, ResponseAppearance::Suggested);
+        dialog.connect_response(None, {
+            let self_clone = self.clone();
+            move |_, response| {
+                let format = if response == png_id {
+                    ExportFormat::Png
+                } else if response == pdf_id {
+                    ExportFormat::Pdf
+                } else {
+                    return;
+                };
+                self_clone.choose_export_destination(format);
+            }
+        });
+        dialog.present(self.imp().window.get());
+    }
+
+    fn choose_export_destination(&self, format: ExportFormat) {
+        let Some(puzzle) = self.imp().puzzle.borrow().clone() else {
+            return;
+        };
+        let window = self.imp().window.get();
+        let on_chosen = {
+            let self_clone = self.clone();
+            move |result: Result<File, glib::Error>| match result {
+                Ok(file) => self_clone.export_solutions(format, &file),
+                Err(error) => {
+                    debug!("File dialog error: {:?}", error);
+                }
+            }
+        };
+        match format {
+            ExportFormat::Pdf => {
+                let filter = FileFilter::new();
+                filter.set_name(Some("PDF Documents"));
+                filter.add_pattern("*.pdf");
+                let dialog = gtk::FileDialog::builder()
+                    .title("Export Solutions")
+                    .default_filter(&filter)
+                    .initial_name(format!("{} Solutions.pdf", puzzle.config().name()))
+                    .build();
+                dialog.save(window, None::<&Cancellable>, on_chosen);
+            }
+            ExportFormat::Png => {
+                let dialog = gtk::FileDialog::builder().title("Export Solutions").build();
+                dialog.select_folder(window, None::<&Cancellable>, on_chosen);
+            }
+        }
+    }
+
+    /// Searches the solutions in the background and renders them to the given file or folder
+    /// afterward. A dialog shows the progress and allows cancelling the export.
+    fn export_solutions(&self, format: ExportFormat, file: &File) {
+        let Some(puzzle) = self.imp().puzzle.borrow().clone() else {
+            return;
+        };
+        let puzzle_config = puzzle.config().clone();
+        let extension = self.imp().extension.borrow().clone();
+        let puzzle_state = Self::create_empty_puzzle_state(&puzzle_config, &extension);
+        let boards = Solver::default().create_boards(&puzzle_state);
+        let tiles: Vec<Tile> = puzzle_config
+            .tiles()
+            .iter()
+            .map(|tile_config| Tile::new(tile_config.base().clone()))
+            .collect();
+
+        let cancel_token = CancellationToken::new();
+        let progress_bar = gtk::ProgressBar::new();
+        let dialog = AlertDialog::builder()
+            .heading("Exporting Solutions")
+            .body("Searching solutions…")
+            .extra_child(&progress_bar)
+            .build();
+        let cancel_id = "cancel";
+        dialog.add_response(cancel_id, "Cancel");
+        dialog.set_close_response(cancel_id);
+        dialog.connect_response(None, {
+            let cancel_token = cancel_token.clone();
+            move |_, _| cancel_token.cancel()
+        });
+        dialog.present(self.imp().window.get());
+
+        let pulse = glib::timeout_add_local(SEARCH_PULSE_INTERVAL, {
+            let progress_bar = progress_bar.clone();
+            move || {
+                progress_bar.pulse();
+                glib::ControlFlow::Continue
+            }
+        });
+        let sender = ui_bridge::oneshot({
+            let self_clone = self.clone();
+            let file = file.clone();
+            let cancel_token = cancel_token.clone();
+            move |result: Option<Result<Vec<Solution>, UnsolvableReason>>| {
+                pulse.remove();
+                match result {
+                    Some(Ok(solutions)) if !solutions.is_empty() => {
+                        glib::spawn_future_local(async move {
+                            let message = write_solutions(
+                                format,
+                                &file,
+                                &puzzle_config,
+                                &solutions,
+                                &dialog,
+                                &progress_bar,
+                                &cancel_token,
+                            )
+                            .await;
+                            dialog.force_close();
+                            if let Some(message) = message {
+                                self_clone
+                                    .imp()
+                                    .toast_overlay
+                                    .add_toast(Toast::new(&message));
+                            }
+                        });
+                    }
+                    Some(Ok(_)) => {
+                        dialog.force_close();
+                        self_clone
+                            .imp()
+                            .toast_overlay
+                            .add_toast(Toast::new("No solutions found for the current target"));
+                    }
+                    Some(Err(UnsolvableReason::BoardTooLarge)) => {
+                        dialog.force_close();
+                        self_clone.imp().toast_overlay.add_toast(Toast::new(
+                            "The board is too large to search its solutions",
+                        ));
+                    }
+                    Some(Err(_)) | None => dialog.force_close(),
+                }
+            }
+        });
+
+        queue::submit(SolverPriority::Requested, async move {
+            let mut solutions = Vec::new();
+            for board in boards.iter() {
+                let remaining = MAX_EXPORTED_SOLUTIONS - solutions.len() as u64;
+                if remaining == 0 {
+                    break;
+                }
+                match puzzle_solver::enumerate_all_filling(
+                    board,
+                    &tiles,
+                    remaining,
+                    cancel_token.clone(),
+                )
+                .await
+                {
+                    Ok(board_solutions) => solutions.extend(board_solutions),
+                    Err(reason) => {
+                        sender.send(Err(reason));
+                        return;
+                    }
+                }
+            }
+            sender.send(Ok(solutions));
+        });
+    }
+}
+
+/// Renders the solutions and writes them in the given format.
+/// The main loop keeps running between two solutions, so the progress is shown and the export
+/// can be cancelled.
+///
+/// returns: Option<String> the message to show to the player, if the export was not cancelled
+async fn write_solutions(
+    format: ExportFormat,
+    file: &File,
+    puzzle_config: &PuzzleConfig,
+    solutions: &[Solution],
+    dialog: &AlertDialog,
+    progress_bar: &gtk::ProgressBar,
+    cancel_token: &CancellationToken,
+) -> Option<String> {
+    let layout = puzzle_config.board_config().layout();
+    let colors: Vec<RGBA> = puzzle_config
+        .tiles()
+        .iter()
+        .map(|tile_config| rgba(tile_config.color()))
+        .collect();
+    let (width, height) = image_size(layout);
+    let writer = match SolutionWriter::new(format, file, width, height) {
+        Ok(writer) => writer,
+        Err(e) => return Some(format!("Export failed: {}", e)),
+    };
+
+    for (i, solution) in solutions.iter().enumerate() {
+        if cancel_token.is_cancelled() {
+            info!("Solution export cancelled after {} solutions", i);
+            return None;
+        }
+        dialog.set_body(&format!(
+            "Rendering solution {} of {}…",
+            i + 1,
+            solutions.len()
+        ));
+        progress_bar.set_fraction(i as f64 / solutions.len() as f64);
+        let result = writer.write(i, |cr| draw_solution(cr, layout, solution, &colors));
+        if let Err(e) = result {
+            error!("Failed to export solution {}: {}", i + 1, e);
+            return Some(format!("Export failed: {}", e));
+        }
+        glib::timeout_future(Duration::ZERO).await;
+    }
+    writer.finish();
+    info!("Exported {} solutions to {}", solutions.len(), file.uri());
+
+    if solutions.len() as u64 >= MAX_EXPORTED_SOLUTIONS {
+        Some(format!(
+            "Exported the first {} solutions",
+            MAX_EXPORTED_SOLUTIONS
+        ))
+    } else {
+        Some(format!("Exported {} solutions", solutions.len()))
+    }
+}
+
+/// Writes the rendered solutions to the file or folder chosen by the player.
+enum SolutionWriter {
+    /// Each solution is a page of the document.
+    Pdf(PdfSurface),
+    /// Each solution is an image in the folder.
+    Png {
+        folder: PathBuf,
+        width: i32,
+        height: i32,
+    },
+}
+
+impl SolutionWriter {
+    fn new(format: ExportFormat, file: &File, width: f64, height: f64) -> Result<Self, String> {
+        let path = file
+            .path()
+            .ok_or_else(|| "Only local files can be written.".to_string())?;
+        match format {
+            ExportFormat::Pdf => PdfSurface::new(width, height, path)
+                .map(SolutionWriter::Pdf)
+                .map_err(|e| e.to_string()),
+            ExportFormat::Png => Ok(SolutionWriter::Png {
+                folder: path,
+                width: width.ceil() as i32,
+                height: height.ceil() as i32,
+            }),
+        }
+    }
+
+    /// Draws the solution with the given index and writes it.
+    fn write(
+        &self,
+        index: usize,
+        draw: impl Fn(&Context) -> Result<(), cairo::Error>,
+    ) -> Result<(), String> {
+        match self {
+            SolutionWriter::Pdf(surface) => {
+                let cr = Context::new(surface).map_err(|e| e.to_string())?;
+                draw(&cr).map_err(|e| e.to_string())?;
+                cr.show_page().map_err(|e| e.to_string())
+            }
+            SolutionWriter::Png {
+                folder,
+                width,
+                height,
+            } => {
+                let surface = ImageSurface::create(Format::ARgb32, *width, *height)
+                    .map_err(|e| e.to_string())?;
+                {
+                    let cr = Context::new(&surface).map_err(|e| e.to_string())?;
+                    draw(&cr).map_err(|e| e.to_string())?;
+                }
+                let path = folder.join(format!("solution-{:03}.png", index + 1));
+                let mut file = std::fs::File::create(path).map_err(|e| e.to_string())?;
+                surface.write_to_png(&mut file).map_err(|e| e.to_string())
+            }
+        }
+    }
+
+    fn finish(self) {
+        if let SolutionWriter::Pdf(surface) = self {
+            surface.finish();
+        }
+    }
+}
+
+/// Returns the width and height of the image of a solution on the given board.
+fn image_size(layout: &Shape) -> (f64, f64) {
+    let (width, height) = layout.dim();
+    (
+        width as f64 * EXPORT_CELL_SIZE + 2.0 * EXPORT_MARGIN,
+        height as f64 * EXPORT_CELL_SIZE + 2.0 * EXPORT_MARGIN,
+    )
+}
+
+/// Draws the board and the tiles placed like in the solution.
+/// The placements of the solution are in the order of the tiles of the puzzle, so the tile with
+/// the same index gives the color of a placement.
+fn draw_solution(
+    cr: &Context,
+    layout: &Shape,
+    solution: &Solution,
+    colors: &[RGBA],
+) -> Result<(), cairo::Error> {
+    cr.set_source_color(&EXPORT_BACKGROUND_COLOR);
+    cr.paint()?;
+
+    cr.set_source_color(&EXPORT_BOARD_COLOR);
+    for ((x, y), on_board) in layout.indexed_iter() {
+        if *on_board {
+            tile::cell_path(
+                cr,
+                layout.cell_shape((x, y)),
+                cell_position(x) + EXPORT_CELL_GAP / 2.0,
+                cell_position(y) + EXPORT_CELL_GAP / 2.0,
+                EXPORT_CELL_SIZE - EXPORT_CELL_GAP,
+                EXPORT_CELL_SIZE - EXPORT_CELL_GAP,
+            );
+        }
+    }
+    cr.fill()?;
+
+    for (placement, color) in solution.placements().iter().zip(colors) {
+        let position = PuzzleState::state_to_board(CellOffset::from(placement.position()));
+        let rotation = placement.rotation();
+        cr.set_source_color(color);
+        for ((x, y), cell) in rotation.indexed_iter() {
+            if !*cell {
+                continue;
+            }
+            let cell_x = cell_position((position.0 + x as i32) as usize);
+            let cell_y = cell_position((position.1 + y as i32) as usize);
+            let cell_shape = rotation.cell_shape((x, y));
+            tile::cell_path(
+                cr,
+                cell_shape,
+                cell_x,
+                cell_y,
+                EXPORT_CELL_SIZE,
+                EXPORT_CELL_SIZE,
+            );
+            cr.fill()?;
+        }
+        cr.set_source_color(&EXPORT_BACKGROUND_COLOR);
+        cr.set_line_width(EXPORT_CELL_GAP);
+        for ((x, y), cell) in rotation.indexed_iter() {
+            if *cell {
+                let cell_x = cell_position((position.0 + x as i32) as usize);
+                let cell_y = cell_position((position.1 + y as i32) as usize);
+                tile_outline(cr, rotation, (x, y), cell_x, cell_y);
+            }
+        }
+        cr.stroke()?;
+    }
+    Ok(())
+}
+
+/// Adds the edges of the cell, which are not shared with another full cell of the tile, to the
+/// current path. Stroking them separates the tile from the tiles next to it.
+fn tile_outline(cr: &Context, rotation: &Shape, (x, y): (usize, usize), cell_x: f64, cell_y: f64) {
+    let size = EXPORT_CELL_SIZE;
+    if rotation.cell_shape((x, y)).is_half() {
+        tile::cell_path(cr, rotation.cell_shape((x, y)), cell_x, cell_y, size, size);
+        return;
+    }
+    let is_full = |x: Option<usize>, y: Option<usize>| match (x, y) {
+        (Some(x), Some(y)) => tile::is_full_cell(rotation, (x, y)),
+        _ => false,
+    };
+    let edges = [
+        (is_full(Some(x), y.checked_sub(1)), (0.0, 0.0), (size, 0.0)),
+        (is_full(Some(x + 1), Some(y)), (size, 0.0), (size, size)),
+        (is_full(Some(x), Some(y + 1)), (0.0, size), (size, size)),
+        (is_full(x.checked_sub(1), Some(y)), (0.0, 0.0), (0.0, size)),
+    ];
+    for (shared, (start_x, start_y), (end_x, end_y)) in edges {
+        if !shared {
+            cr.move_to(cell_x + start_x, cell_y + start_y);
+            cr.line_to(cell_x + end_x, cell_y + end_y);
+        }
+    }
+}
+
+/// Returns the position of the cell with the given index in an exported image.
+fn cell_position(index: usize) -> f64 {
+    EXPORT_MARGIN + index as f64 * EXPORT_CELL_SIZE
+}
+
+fn rgba(color: ColorConfig) -> RGBA {
+    RGBA::new(
+        (color.red() as f64 / 255.0) as f32,
+        (color.green() as f64 / 255.0) as f32,
+        (color.blue() as f64 / 255.0) as f32,
+        (color.alpha() as f64 / 255.0) as f32,
+    )
+}
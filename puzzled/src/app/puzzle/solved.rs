@@ -1,43 +1,132 @@
 use crate::app::components::solved_dialog::SolvedDialog;
+use crate::app::presenter::solve_summary::SolveSummary;
+use crate::app::puzzle::puzzle_area::puzzle_state::{Cell, PuzzleState};
 use crate::app::puzzle::puzzle_page::PuzzlePage;
+use crate::global::events;
+use crate::global::events::AppEvent;
+use crate::model::featured;
+use crate::model::leaderboard;
+use crate::model::play_history;
+use crate::model::puzzle::PuzzleModel;
+use crate::offset::CellOffset;
 use adw::prelude::{AdwDialogExt, AlertDialogExt};
 use adw::subclass::prelude::ObjectSubclassIsExt;
+use adw::Toast;
 use log::{debug, error};
+use ndarray::Array2;
 
 impl PuzzlePage {
-    fn handle_solved(&self) {
+    /// Records the solved puzzle and returns the summary shown to the player.
+    fn handle_solved(&self, puzzle_state: &PuzzleState) -> Option<SolveSummary> {
         let puzzle = self.imp().puzzle.borrow();
-        if let Some(puzzle) = puzzle.as_ref() {
-            let hint_count = self.imp().hint_count.get();
-            let previous_hint_count = puzzle.best_hint_count(&self.imp().extension.borrow());
-            let best_hint_count = hint_count.min(previous_hint_count.unwrap_or(u32::MAX));
-
-            puzzle.set_solved(best_hint_count, &self.imp().extension.borrow());
-        } else {
+        let Some(puzzle) = puzzle.as_ref() else {
             error!("Could not mark puzzle as solved: missing puzzle collection or puzzle config");
+            return None;
+        };
+        let hint_count = self.imp().hint_count.get();
+        let previous_hint_count = puzzle.best_hint_count(&self.imp().extension.borrow());
+        let best_hint_count = hint_count.min(previous_hint_count.unwrap_or(u32::MAX));
+
+        featured::record_solved(puzzle, &self.imp().extension.borrow());
+        puzzle.set_solved(best_hint_count, &self.imp().extension.borrow());
+        self.add_daily_challenge_result(puzzle, hint_count);
+        self.add_play_record(puzzle, hint_count);
+        self.imp().idle_inhibitor.replace(None);
+
+        let tile_colors = puzzle
+            .config()
+            .tiles()
+            .iter()
+            .map(|tile| tile.color())
+            .collect();
+        Some(SolveSummary::new(
+            puzzle.config().name(),
+            self.imp().timer.get().elapsed(),
+            hint_count,
+            covered_cells(puzzle_state),
+            tile_colors,
+        ))
+    }
+
+    /// Adds the result to the leaderboard, if the puzzle was solved as a daily challenge.
+    fn add_daily_challenge_result(&self, puzzle: &PuzzleModel, hint_count: u32) {
+        let extension = self.imp().extension.borrow();
+        if !leaderboard::is_daily_challenge(puzzle, &extension) {
+            return;
+        }
+        let Some(elapsed) = self.imp().timer.get().elapsed() else {
+            return;
+        };
+        let Some(result) = leaderboard::create_result(puzzle, &extension, elapsed, hint_count)
+        else {
+            error!("Could not create the result of the daily challenge");
+            return;
+        };
+        if let Err(e) = leaderboard::add_result(&result) {
+            error!(
+                "Failed to add the daily challenge result to the leaderboard: {}",
+                e
+            );
         }
     }
 
-    pub fn on_solved(&self) {
-        self.handle_solved();
+    /// Adds the solved puzzle to the local play history shown in the insights.
+    fn add_play_record(&self, puzzle: &PuzzleModel, hint_count: u32) {
+        let Some(elapsed) = self.imp().timer.get().elapsed() else {
+            return;
+        };
+        let Some(record) = play_history::create_record(puzzle, elapsed, hint_count) else {
+            error!("Could not create the record for the play history");
+            return;
+        };
+        if let Err(e) = play_history::add_record(&record) {
+            error!("Failed to add the solved puzzle to the play history: {}", e);
+        }
+    }
+
+    pub fn on_solved(&self, puzzle_state: &PuzzleState) {
+        let summary = self.handle_solved(puzzle_state);
+        self.stop_coach();
+        events::publish(AppEvent::PuzzleSolved);
         let solved_dialog = SolvedDialog::new();
+        if let Some(summary) = summary {
+            solved_dialog.set_summary(summary);
+        }
         let extension = self.imp().extension.borrow();
         let puzzle = self.imp().puzzle.borrow();
-        let has_next = {
+        let (has_next, has_other_next_unsolved) = {
             if let Some(puzzle) = puzzle.as_ref() {
                 let stars = puzzle.stars(&extension);
                 solved_dialog.set_stars(&stars);
-                puzzle.has_next_puzzle()
+                let next_unsolved = puzzle.next_unsolved_puzzle();
+                let other_next_unsolved =
+                    next_unsolved.is_some() && next_unsolved != puzzle.next_puzzle();
+                (puzzle.has_next_puzzle(), other_next_unsolved)
             } else {
-                false
+                (false, false)
             }
         };
 
+        if has_other_next_unsolved {
+            solved_dialog.connect_response(Some("next_unsolved"), {
+                let self_clone = self.clone();
+                move |_, _| self_clone.show_next_unsolved_puzzle()
+            });
+        } else {
+            solved_dialog.remove_response("next_unsolved");
+        }
+
         if !has_next {
             debug!("No next puzzle available, removing 'Next' button");
             solved_dialog.remove_response("next");
-            solved_dialog.set_default_response(Some("back"));
-            solved_dialog.set_response_appearance("back", adw::ResponseAppearance::Suggested);
+            let default_response = if has_other_next_unsolved {
+                "next_unsolved"
+            } else {
+                "back"
+            };
+            solved_dialog.set_default_response(Some(default_response));
+            solved_dialog
+                .set_response_appearance(default_response, adw::ResponseAppearance::Suggested);
         } else {
             solved_dialog.connect_response(Some("next"), {
                 let self_clone = self.clone();
@@ -60,7 +149,30 @@ impl PuzzlePage {
         solved_dialog.present(self.imp().window.get());
     }
 
-    fn show_next_puzzle(&self) {
+    /// Shows the next puzzle, which is neither solved nor locked.
+    /// See [PuzzleModel::next_unsolved_puzzle].
+    pub(super) fn show_next_unsolved_puzzle(&self) {
+        let opt_puzzle = self.imp().puzzle.borrow();
+        let Some(puzzle) = opt_puzzle.as_ref() else {
+            error!("No current puzzle found when trying to show next unsolved puzzle");
+            return;
+        };
+        match puzzle.next_unsolved_puzzle() {
+            Some(next_puzzle) => {
+                let next_puzzle = next_puzzle.clone();
+                drop(opt_puzzle);
+                self.show_puzzle(&next_puzzle);
+            }
+            None => {
+                drop(opt_puzzle);
+                self.imp().toast_overlay.add_toast(Toast::new(
+                    "There are no other unsolved puzzles in this collection",
+                ));
+            }
+        }
+    }
+
+    pub(super) fn show_next_puzzle(&self) {
         let opt_puzzle = self.imp().puzzle.borrow();
         let puzzle = match opt_puzzle.as_ref() {
             Some(p) => p,
@@ -76,3 +188,18 @@ impl PuzzlePage {
         }
     }
 }
+
+/// Returns the index of the tile covering each cell of the board. A cell split between two half
+/// cells of different tiles is shown with the first of them.
+fn covered_cells(puzzle_state: &PuzzleState) -> Array2<Option<usize>> {
+    Array2::from_shape_fn(puzzle_state.board_dim(), |(x, y)| {
+        let position: (usize, usize) = PuzzleState::board_to_state(CellOffset::from((x, y))).into();
+        match &puzzle_state.grid[position] {
+            Cell::One(data, placement) if data.is_on_board => Some(placement.tile_id),
+            Cell::Many(data, placements) if data.is_on_board => {
+                placements.first().map(|placement| placement.tile_id)
+            }
+            _ => None,
+        }
+    })
+}
@@ -0,0 +1,34 @@
+use crate::app::puzzle::puzzle_page::PuzzlePage;
+use crate::model::puzzle::PuzzleModel;
+use adw::subclass::prelude::ObjectSubclassIsExt;
+use gettextrs::gettext;
+use gtk::prelude::WidgetExt;
+use puzzle_config::validation::{trivial_puzzle, TrivialPuzzle};
+
+impl PuzzlePage {
+    /// Shows a message instead of the board, if there is nothing to do in the puzzle.
+    /// The player can skip to the next puzzle from there, if there is one.
+    pub(super) fn update_trivial_state(&self, puzzle: &PuzzleModel) {
+        let imp = self.imp();
+        let Some(trivial) = trivial_puzzle(puzzle.config()) else {
+            imp.content_stack.set_visible_child(&*imp.grid_scroller);
+            return;
+        };
+        let (title, description) = match trivial {
+            TrivialPuzzle::NoTiles => (
+                gettext("This Puzzle Has No Tiles"),
+                gettext("The author did not add any tiles to place on the board."),
+            ),
+            TrivialPuzzle::BoardComplete => (
+                gettext("Board Is Already Complete"),
+                gettext("There are no free cells on the board, which could be filled with tiles."),
+            ),
+        };
+        imp.trivial_status.set_title(&title);
+        imp.trivial_status.set_description(Some(&description));
+        imp.trivial_next_button
+            .set_visible(puzzle.has_next_puzzle());
+        imp.content_stack.set_visible_child(&*imp.trivial_status);
+        self.stop_coach();
+    }
+}
@@ -1,3 +1,4 @@
 mod puzzle_mod;
 mod puzzle_selection_item;
 pub mod puzzle_selection_page;
+mod verify;
@@ -1,3 +1,4 @@
+use crate::app::presenter::puzzle_row::PuzzleModState;
 use crate::model::stars::Stars;
 use adw::gio;
 use adw::glib;
@@ -90,14 +91,3 @@ impl PuzzleMod {
         imp.stars.set_visible(false);
     }
 }
-
-/// Used to set what the [PuzzleMod] should display.
-#[derive(Debug, PartialEq, Eq)]
-pub enum PuzzleModState {
-    /// Displays only the stars using [StarsView].
-    Stars(Stars),
-    /// Displays a lock and shows the text `Locked`.
-    Locked,
-    /// Displays a cross and the test `Unsolvable`.
-    Unsolvable,
-}
@@ -1,23 +1,18 @@
-use crate::app::components::board::BoardView;
-use crate::app::components::tile::TileView;
-use crate::app::puzzle_selection::puzzle_mod::PuzzleModState;
+use crate::app::components::description;
+use crate::app::components::info_pill::InfoPill;
+use crate::app::components::preview;
+use crate::app::components::progress_menu;
+use crate::app::presenter::puzzle_row::PuzzleRow;
 use crate::model::puzzle::PuzzleModel;
 use adw::gio;
 use adw::glib;
-use adw::prelude::{ObjectExt, ToVariant};
+use adw::prelude::ToVariant;
 use adw::subclass::prelude::*;
-use gtk::prelude::{ActionableExt, BoxExt, FixedExt, ListBoxRowExt, WidgetExt};
-use gtk::{Align, Fixed, Widget};
-use log::error;
-use puzzle_config::{BoardConfig, ProgressionConfig, TileConfig};
-
-/// How many pixels a cell should have in the preview of tiles and boards.
-/// This is NOT the total size of the preview.
-const PREVIEW_CELL_SIZE: f64 = 20.0;
+use gtk::prelude::{ActionableExt, BoxExt, ListBoxRowExt, PopoverExt, WidgetExt};
+use gtk::Widget;
 
 mod imp {
     use super::*;
-    use crate::app::components::info_pill::InfoPill;
     use crate::app::puzzle_selection::puzzle_mod::PuzzleMod;
     use std::cell::OnceCell;
 
@@ -30,6 +25,8 @@ mod imp {
         #[template_child]
         pub name: TemplateChild<gtk::Label>,
         #[template_child]
+        pub notes_icon: TemplateChild<gtk::Image>,
+        #[template_child]
         pub puzzle_mod: TemplateChild<PuzzleMod>,
         #[template_child]
         pub description: TemplateChild<gtk::Label>,
@@ -54,6 +51,7 @@ mod imp {
         pub board_preview_box: TemplateChild<gtk::Box>,
 
         pub(super) puzzle: OnceCell<PuzzleModel>,
+        pub(super) context_menu: OnceCell<gtk::PopoverMenu>,
     }
 
     #[glib::object_subclass]
@@ -71,8 +69,21 @@ mod imp {
         }
     }
 
-    impl ObjectImpl for PuzzledPuzzleSelectionItem {}
-    impl WidgetImpl for PuzzledPuzzleSelectionItem {}
+    impl ObjectImpl for PuzzledPuzzleSelectionItem {
+        fn dispose(&self) {
+            if let Some(context_menu) = self.context_menu.get() {
+                context_menu.unparent();
+            }
+        }
+    }
+    impl WidgetImpl for PuzzledPuzzleSelectionItem {
+        fn size_allocate(&self, width: i32, height: i32, baseline: i32) {
+            self.parent_size_allocate(width, height, baseline);
+            if let Some(context_menu) = self.context_menu.get() {
+                context_menu.present();
+            }
+        }
+    }
     impl ListBoxRowImpl for PuzzledPuzzleSelectionItem {}
 }
 
@@ -92,6 +103,8 @@ impl PuzzleSelectionItem {
     /// If the state of the puzzles changes e.g. by solving it, this view updates automatically
     /// using the signals of the puzzle model.
     ///
+    /// The context menu of the item marks the puzzle as solved or unsolved.
+    ///
     /// # Arguments
     ///
     /// * `puzzle`: the puzzle to display
@@ -105,25 +118,30 @@ impl PuzzleSelectionItem {
             .set(puzzle.clone())
             .expect("Failed to set puzzle");
 
-        imp.name.set_text(puzzle.config().name());
+        let row = obj.row();
+        imp.name.set_text(&row.name);
 
         obj.update_data();
 
-        if let Some(description) = puzzle.config().description() {
-            imp.description.set_text(description);
+        if let Some(row_description) = &row.description {
+            description::set_description(&imp.description, row_description);
+            description::open_links_with_launcher(&imp.description);
         } else {
             imp.outer_box.remove(&imp.description.get());
         }
 
-        if let Some(difficulty) = puzzle.config().difficulty() {
-            let label: String = (*difficulty).into();
-            imp.difficulty_pill.set_label(label);
+        if let Some(difficulty) = row.difficulty {
+            imp.difficulty_pill.set_label(difficulty);
         } else {
             imp.info_box.remove(&imp.difficulty_pill.get());
         }
 
-        Self::create_tiles_preview(puzzle.config().tiles(), &imp.tile_preview_fixed.get());
-        Self::create_board_preview(puzzle.config().board_config(), &imp.board_preview_box.get());
+        preview::create_tiles_preview(puzzle.config().tiles(), &imp.tile_preview_fixed.get());
+        preview::create_board_thumbnail(
+            puzzle.config().board_config(),
+            puzzle.collection().config().palette(),
+            &imp.board_preview_box.get(),
+        );
 
         puzzle.connect_progress_improved({
             let obj = obj.clone();
@@ -137,134 +155,93 @@ impl PuzzleSelectionItem {
                 obj.update_data();
             }
         });
+        puzzle.connect_notes_changed({
+            let obj = obj.clone();
+            move || {
+                obj.update_data();
+            }
+        });
+        if let Some(previous_puzzle) = puzzle
+            .config()
+            .index()
+            .checked_sub(1)
+            .and_then(|index| puzzle.collection().puzzles().get(index))
+        {
+            previous_puzzle.connect_progress_improved({
+                let obj = obj.clone();
+                move || {
+                    obj.update_data();
+                }
+            });
+            previous_puzzle.connect_marked_unsolved({
+                let obj = obj.clone();
+                move || {
+                    obj.update_data();
+                }
+            });
+        }
+
+        let context_menu = progress_menu::add_progress_menu(
+            &obj,
+            puzzle.config().name(),
+            {
+                let puzzle = puzzle.clone();
+                move || puzzle.mark_as_solved_manually()
+            },
+            {
+                let puzzle = puzzle.clone();
+                move || puzzle.reset_progress()
+            },
+        );
+        imp.context_menu
+            .set(context_menu)
+            .expect("Failed to set context menu");
 
         obj.set_action_target_value(Some(&(puzzle.config().index() as i32).to_variant()));
 
         obj
     }
 
+    /// Creates the data to show for the puzzle in its current state.
+    fn row(&self) -> PuzzleRow {
+        let puzzle = self.imp().puzzle.get().unwrap();
+        PuzzleRow::new(
+            puzzle.config(),
+            puzzle.collection().config().preview(),
+            puzzle.is_locked_default(),
+            puzzle.stars_default(),
+        )
+    }
+
     /// Updates dynamic data of the puzzle.
     /// This should be called, if the puzzle emits signals for relevant changes.
     fn update_data(&self) {
         let imp = self.imp();
-        let puzzle = imp.puzzle.get().unwrap();
-        let collection = imp.puzzle.get().unwrap().collection();
-        let stars = puzzle.stars_default();
-        let solved = puzzle.is_solved_default();
-
-        let state = {
-            let state = match &collection.config().progression() {
-                ProgressionConfig::Any => PuzzleModState::Stars(stars),
-                ProgressionConfig::Sequential => {
-                    let previous_solved = puzzle.is_previous_solved_default().unwrap_or(true);
-                    if solved || previous_solved {
-                        PuzzleModState::Stars(stars)
-                    } else {
-                        PuzzleModState::Locked
-                    }
-                }
-            };
-            if let PuzzleModState::Stars(_) = state
-                && puzzle.config().is_unsolvable()
-            {
-                PuzzleModState::Unsolvable
-            } else {
-                state
-            }
-        };
-        imp.puzzle_mod.set_state(&state);
-        match state {
-            PuzzleModState::Stars(_) => {
-                self.set_activatable(true);
-                self.remove_css_class("dimmed");
-            }
-            PuzzleModState::Locked => {
-                self.set_activatable(false);
-                self.add_css_class("dimmed");
-            }
-            PuzzleModState::Unsolvable => {
-                self.set_activatable(true);
-                self.remove_css_class("dimmed");
-            }
-        }
-
-        if state != PuzzleModState::Locked || collection.config().preview().show_board_size() {
-            let (width, height) = puzzle.config().board_config().layout().dim();
-            imp.board_size_pill
-                .set_label(format!("{} x {}", width, height));
-            let cell_count = puzzle
-                .config()
-                .board_config()
-                .layout()
-                .iter()
-                .filter(|c| **c)
-                .count();
-            imp.cell_count_pill.set_label(format!("{}", cell_count));
-        } else {
-            imp.info_box.remove(&imp.board_size_pill.get());
-            imp.info_box.remove(&imp.cell_count_pill.get());
-        }
-
-        if state != PuzzleModState::Locked || collection.config().preview().show_tile_count() {
-            let tile_count = puzzle.config().tiles().len();
-            imp.tile_count_pill.set_label(format!("{}", tile_count));
+        let row = self.row();
+
+        imp.puzzle_mod.set_state(&row.state);
+        imp.notes_icon
+            .set_visible(imp.puzzle.get().unwrap().notes().is_some());
+        self.set_activatable(row.activatable);
+        if row.activatable {
+            self.remove_css_class("dimmed");
         } else {
-            imp.info_box.remove(&imp.tile_count_pill.get());
+            self.add_css_class("dimmed");
         }
 
-        let show_tile_preview =
-            state != PuzzleModState::Locked || collection.config().preview().show_tiles();
-        let show_board_preview =
-            state != PuzzleModState::Locked || collection.config().preview().show_board();
+        Self::set_pill(&imp.board_size_pill, row.board_size);
+        Self::set_pill(&imp.cell_count_pill, row.cell_count);
+        Self::set_pill(&imp.tile_count_pill, row.tile_count);
 
-        imp.tile_preview_parent.set_visible(show_tile_preview);
-        imp.board_preview_parent.set_visible(show_board_preview);
+        imp.tile_preview_parent.set_visible(row.show_tile_preview);
+        imp.board_preview_parent.set_visible(row.show_board_preview);
     }
 
-    fn create_tiles_preview(tiles: &[TileConfig], fixed: &Fixed) {
-        let max_tile_cell_height = tiles
-            .iter()
-            .map(|tile| tile.base().dim().1)
-            .max()
-            .unwrap_or(1) as i32;
-        let mut current_x_offset_cells = 0;
-
-        for (i, tile) in tiles.iter().enumerate() {
-            let tile_view = TileView::new(i, tile.base().clone(), tile.color());
-
-            let tile_height = tile.base().dim().1 as i32;
-            let y_offset = (max_tile_cell_height - tile_height) as f64 / 2.0;
-
-            fixed.put(
-                &tile_view,
-                current_x_offset_cells as f64 * PREVIEW_CELL_SIZE,
-                y_offset * PREVIEW_CELL_SIZE,
-            );
-            tile_view.set_width_request((PREVIEW_CELL_SIZE * tile.base().dim().0 as f64) as i32);
-            tile_view.set_height_request((PREVIEW_CELL_SIZE * tile.base().dim().1 as f64) as i32);
-            let tile_width = tile.base().dim().0;
-            let next_x_offset = current_x_offset_cells + tile_width + 1;
-            current_x_offset_cells = next_x_offset;
-        }
-    }
-
-    fn create_board_preview(board: &BoardConfig, preview_box: &gtk::Box) {
-        let board_view = BoardView::new(board);
-
-        match board_view {
-            Ok(bv) => {
-                bv.set_property("halign", Align::Center);
-                preview_box.append(&bv);
-
-                let min_element_width = bv.get_min_element_size();
-                let size_per_cell = PREVIEW_CELL_SIZE.max(min_element_width as f64);
-
-                bv.set_width_request(size_per_cell as i32 * board.layout().dim().0 as i32);
-                bv.set_height_request(size_per_cell as i32 * board.layout().dim().1 as i32);
-            }
-            Err(e) => {
-                error!("Failed to create board preview: {}", e);
-            }
+    /// Shows the pill with the given label or hides it, if there is no label.
+    fn set_pill(pill: &InfoPill, label: Option<String>) {
+        pill.set_visible(label.is_some());
+        if let Some(label) = label {
+            pill.set_label(label);
         }
     }
 
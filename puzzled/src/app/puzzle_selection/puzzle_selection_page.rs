@@ -1,3 +1,5 @@
+use crate::app::components::description;
+use crate::app::components::tile::TileView;
 use crate::app::puzzle_selection::puzzle_selection_item::PuzzleSelectionItem;
 use crate::model::collection::CollectionModel;
 use crate::model::puzzle::PuzzleModel;
@@ -7,9 +9,11 @@ use adw::subclass::prelude::*;
 use gtk::glib;
 use gtk::prelude::*;
 use log::debug;
-use puzzle_config::ProgressionConfig;
+use puzzle_config::{ColorConfig, ProgressionConfig};
+use puzzled_common::shape::shape_square;
 
 const PUZZLE_SELECTED_SIGNAL_NAME: &str = "puzzle-selected";
+const PALETTE_SWATCH_SIZE: i32 = 16;
 
 mod imp {
     use super::*;
@@ -34,6 +38,8 @@ mod imp {
         #[template_child]
         pub version_pill: TemplateChild<InfoPill>,
         #[template_child]
+        pub palette_box: TemplateChild<gtk::Box>,
+        #[template_child]
         pub puzzle_list: TemplateChild<gtk::ListBox>,
 
         pub collection: RefCell<Option<CollectionModel>>,
@@ -67,6 +73,9 @@ mod imp {
                     }
                 },
             );
+            klass.install_action("app.verify_collection", None, |page, _, _| {
+                page.show_verify_collection_dialog();
+            });
         }
 
         fn instance_init(obj: &glib::subclass::InitializingObject<Self>) {
@@ -75,6 +84,11 @@ mod imp {
     }
 
     impl ObjectImpl for PuzzleSelectionPage {
+        fn constructed(&self) {
+            self.parent_constructed();
+            description::open_links_with_launcher(&self.collection_description_label);
+        }
+
         fn signals() -> &'static [Signal] {
             static SIGNALS: OnceLock<Vec<Signal>> = OnceLock::new();
             SIGNALS.get_or_init(|| {
@@ -111,7 +125,7 @@ impl PuzzleSelectionPage {
         });
     }
 
-    fn emit_puzzle_selected(&self, puzzle: &PuzzleModel) {
+    pub(super) fn emit_puzzle_selected(&self, puzzle: &PuzzleModel) {
         debug!(
             "Emitting puzzle-selected signal for puzzle: {}",
             puzzle.config().id()
@@ -134,9 +148,7 @@ impl PuzzleSelectionPage {
 
         self.set_title(collection.config().name());
         if let Some(description) = collection.config().description() {
-            self.imp()
-                .collection_description_label
-                .set_label(description);
+            description::set_description(&self.imp().collection_description_label, description);
             self.imp().collection_description_label.set_visible(true);
         } else {
             self.imp().collection_description_label.set_visible(false);
@@ -162,6 +174,8 @@ impl PuzzleSelectionPage {
                 .remove(&self.imp().version_pill.get());
         }
 
+        self.show_palette(collection.config().palette());
+
         for puzzle in collection.puzzles().iter() {
             let row = PuzzleSelectionItem::new(puzzle);
             self.imp().puzzle_list.append(&row);
@@ -180,4 +194,18 @@ impl PuzzleSelectionPage {
             }
         }
     }
+
+    fn show_palette(&self, palette: &[ColorConfig]) {
+        let palette_box = &self.imp().palette_box;
+        while let Some(child) = palette_box.first_child() {
+            palette_box.remove(&child);
+        }
+        for (i, color) in palette.iter().enumerate() {
+            let swatch = TileView::new(i, shape_square(&[[true]]), *color);
+            swatch.set_width_request(PALETTE_SWATCH_SIZE);
+            swatch.set_height_request(PALETTE_SWATCH_SIZE);
+            palette_box.append(&swatch);
+        }
+        palette_box.set_visible(!palette.is_empty());
+    }
 }
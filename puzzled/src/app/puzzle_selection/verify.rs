@@ -0,0 +1,262 @@
+use crate::app::puzzle_selection::puzzle_selection_page::PuzzleSelectionPage;
+use crate::solver::queue;
+use crate::solver::queue::SolverPriority;
+use crate::ui_bridge;
+use adw::prelude::{ActionRowExt, AdwDialogExt, AlertDialogExt};
+use adw::subclass::prelude::ObjectSubclassIsExt;
+use adw::{ActionRow, AlertDialog};
+use gtk::prelude::{ListBoxRowExt, WidgetExt};
+use gtk::{Align, Image, Label, ListBox, ScrolledWindow, SelectionMode};
+use log::debug;
+use puzzle_config::validation::{
+    check_collection_settings, check_unique_solution, validate_puzzle, HighlightColor,
+    ValidationIssue,
+};
+use puzzle_config::PuzzleConfig;
+use puzzle_solver::result::UnsolvableReason;
+use std::cell::Cell;
+use std::rc::Rc;
+use tokio_util::sync::CancellationToken;
+
+/// Progress of the verification of a collection, sent for each puzzle.
+/// Puzzles without errors are additionally checked for a unique solution.
+enum VerificationUpdate {
+    Started(usize),
+    Finished(usize, Vec<ValidationIssue>, bool),
+}
+
+impl PuzzleSelectionPage {
+    /// Runs the validation of all puzzles of the shown collection and shows the results in a
+    /// dialog.
+    /// Puzzles with issues can be opened from the dialog. Closing the dialog stops the
+    /// verification.
+    pub(super) fn show_verify_collection_dialog(&self) {
+        let collection = self.imp().collection.borrow().clone();
+        let Some(collection) = collection else {
+            return;
+        };
+        debug!("Verifying collection: {}", collection.config().id());
+
+        let list = ListBox::builder()
+            .selection_mode(SelectionMode::None)
+            .css_classes(["boxed-list"])
+            .build();
+        let rows: Vec<ActionRow> = collection
+            .puzzles()
+            .iter()
+            .map(|puzzle| {
+                let row = ActionRow::builder()
+                    .title(puzzle.config().name())
+                    .subtitle("Waiting")
+                    .build();
+                list.append(&row);
+                row
+            })
+            .collect();
+        let settings_issues = check_collection_settings(collection.config());
+        if !settings_issues.is_empty() {
+            let messages: Vec<String> = settings_issues.iter().map(issue_message).collect();
+            let row = ActionRow::builder()
+                .title("Collection Settings")
+                .subtitle(format!("Warnings:\n{}", messages.join("\n")))
+                .build();
+            row.add_prefix(&status_icon("dialog-warning-symbolic", "warning"));
+            list.prepend(&row);
+        }
+        let scrolled_window = ScrolledWindow::builder()
+            .child(&list)
+            .propagate_natural_height(true)
+            .max_content_height(400)
+            .build();
+
+        let dialog = AlertDialog::builder()
+            .heading("Verify Collection")
+            .body(
+                "Checking that every puzzle can be solved for its default target, whether its \
+                solution is unique and that its tiles can be told apart by their colors.",
+            )
+            .extra_child(&scrolled_window)
+            .build();
+        let close_id = "close";
+        dialog.add_response(close_id, "Close");
+        dialog.set_default_response(Some(close_id));
+        dialog.set_close_response(close_id);
+
+        let cancel_token = CancellationToken::new();
+        dialog.connect_closed({
+            let cancel_token = cancel_token.clone();
+            move |_| cancel_token.cancel()
+        });
+
+        let puzzle_count = rows.len();
+        let passed = Rc::new(Cell::new(0));
+        let sender = ui_bridge::stream(
+            {
+                let self_clone = self.clone();
+                let dialog = dialog.clone();
+                let passed = passed.clone();
+                move |update| match update {
+                    VerificationUpdate::Started(index) => {
+                        rows[index].set_subtitle("Checking…");
+                    }
+                    VerificationUpdate::Finished(index, issues, unique) => {
+                        let row = &rows[index];
+                        if unique {
+                            row.add_suffix(&unique_solution_label());
+                        }
+                        if issues.is_empty() {
+                            passed.set(passed.get() + 1);
+                            row.set_subtitle("Passed");
+                            row.add_prefix(&status_icon("object-select-symbolic", "success"));
+                            return;
+                        }
+                        let messages: Vec<String> = issues.iter().map(issue_message).collect();
+                        if issues.iter().all(ValidationIssue::is_warning) {
+                            passed.set(passed.get() + 1);
+                            row.set_subtitle(&format!(
+                                "Passed with warnings:\n{}",
+                                messages.join("\n")
+                            ));
+                            row.add_prefix(&status_icon("dialog-warning-symbolic", "warning"));
+                        } else {
+                            row.set_subtitle(&messages.join("\n"));
+                            row.add_prefix(&status_icon("dialog-warning-symbolic", "error"));
+                        }
+                        row.add_suffix(&Image::from_icon_name("go-next-symbolic"));
+                        row.set_activatable(true);
+                        row.connect_activated({
+                            let self_clone = self_clone.clone();
+                            let dialog = dialog.clone();
+                            move |_| {
+                                let collection = self_clone.imp().collection.borrow().clone();
+                                if let Some(puzzle) = collection
+                                    .as_ref()
+                                    .and_then(|collection| collection.puzzles().get(index))
+                                {
+                                    dialog.close();
+                                    self_clone.emit_puzzle_selected(puzzle);
+                                }
+                            }
+                        });
+                    }
+                }
+            },
+            {
+                let dialog = dialog.clone();
+                let cancel_token = cancel_token.clone();
+                move || {
+                    if !cancel_token.is_cancelled() {
+                        dialog.set_body(&format!(
+                            "{} of {} puzzles passed.",
+                            passed.get(),
+                            puzzle_count
+                        ));
+                    }
+                }
+            },
+        );
+        dialog.present(Some(self));
+
+        let puzzle_configs: Vec<PuzzleConfig> = collection
+            .puzzles()
+            .iter()
+            .map(|puzzle| puzzle.config().clone())
+            .collect();
+        queue::submit(SolverPriority::Batch, async move {
+            for (index, puzzle_config) in puzzle_configs.iter().enumerate() {
+                sender.send(VerificationUpdate::Started(index));
+                let Ok(issues) = validate_puzzle(puzzle_config, cancel_token.clone()).await else {
+                    break;
+                };
+                let checkable = issues.iter().all(|issue| {
+                    issue.is_warning()
+                        && !matches!(issue, ValidationIssue::UnknownDefaultValue { .. })
+                });
+                let unique = if checkable {
+                    match check_unique_solution(puzzle_config, cancel_token.clone()).await {
+                        Ok(unique) => unique,
+                        Err(_) => break,
+                    }
+                } else {
+                    false
+                };
+                sender.send(VerificationUpdate::Finished(index, issues, unique));
+            }
+        });
+    }
+}
+
+fn unique_solution_label() -> Label {
+    Label::builder()
+        .label("Unique Solution")
+        .tooltip_text("The puzzle has exactly one solution for its default target")
+        .valign(Align::Center)
+        .css_classes(["caption", "success"])
+        .build()
+}
+
+fn status_icon(icon_name: &str, css_class: &str) -> Image {
+    let icon = Image::from_icon_name(icon_name);
+    icon.add_css_class(css_class);
+    icon
+}
+
+fn issue_message(issue: &ValidationIssue) -> String {
+    let message = match issue {
+        ValidationIssue::UnknownDefaultValue { name, value, .. } => format!(
+            "The default value '{}' of the area '{}' is not on the board, so the default target \
+            could not be checked.",
+            value, name
+        ),
+        ValidationIssue::AreaMismatch {
+            tile_cells,
+            board_cells,
+        } => format!(
+            "The tiles cover {} cells, but {} cells need to be covered.",
+            tile_cells, board_cells
+        ),
+        ValidationIssue::Unsolvable(reason) => match reason {
+            UnsolvableReason::NoFit
+            | UnsolvableReason::AreaMismatch { .. }
+            | UnsolvableReason::TileCannotBePlaced { .. } => {
+                "There is no solution for the default target.".to_string()
+            }
+            UnsolvableReason::TileLargerThanBoard { .. } => {
+                "A tile is larger than the board.".to_string()
+            }
+            UnsolvableReason::UncoverableCell { position } => format!(
+                "The cell at ({}, {}) cannot be covered by any tile.",
+                position.0, position.1
+            ),
+            UnsolvableReason::BoardTooLarge => "The board is too large to solve.".to_string(),
+            UnsolvableReason::Cancelled => "The verification was cancelled.".to_string(),
+        },
+        ValidationIssue::SimilarTileColors { first, second, .. } => format!(
+            "Tiles {} and {} have nearly identical colors.",
+            first + 1,
+            second + 1
+        ),
+        ValidationIssue::HighlightColorClash {
+            tile, highlight, ..
+        } => format!(
+            "The color of tile {} is nearly identical to the {} color.",
+            tile + 1,
+            match highlight {
+                HighlightColor::Accent => "accent",
+                HighlightColor::Error => "error",
+            }
+        ),
+        ValidationIssue::SequentialWithoutPreview => {
+            "Puzzles are unlocked one after another, but locked puzzles show no preview."
+                .to_string()
+        }
+        ValidationIssue::UnusedPreview => {
+            "A preview is configured, but it is never shown, because all puzzles are unlocked."
+                .to_string()
+        }
+    };
+    match issue.path() {
+        Some(path) => format!("{} (`{}`)", message, path),
+        None => message,
+    }
+}
@@ -17,21 +17,44 @@
  *
  * SPDX-License-Identifier: GPL-3.0-or-later
  */
+use crate::app::components::bar_chart::bar_chart;
+use crate::app::components::preview::board_thumbnail_picture;
 use crate::app::components::tile::{DrawingMode, TileView};
+use crate::app::editor::editor_dialog::show_board_editor;
+use crate::app::presenter::insights::Insights;
+use crate::app::presenter::leaderboard_row::LeaderboardRow;
+use crate::audio;
 use crate::config::VERSION;
-use crate::global::settings::{Preferences, ShowBoardGridLines};
+use crate::global::settings::{
+    Animations, BatterySaver, CoachDelay, CoachMode, DeveloperMode, InhibitIdle,
+    LastSeenReleaseNotes, Music, PlayerName, Preferences, PushOverlappingTiles, ShowBoardGridLines,
+    ShowRemainingCells, SoundEffects, SyncEnabled, SyncUrl, Volume,
+};
+use crate::global::shortcuts;
+use crate::model::leaderboard;
+use crate::model::leaderboard::token;
+use crate::model::leaderboard::token::TokenError;
+use crate::model::play_history;
+use crate::model::profiles;
+use crate::model::release_notes::{Release, ReleaseNotes};
 use crate::model::store;
 use crate::model::store::with_puzzle_collection_store;
+use crate::solver::battery_saver;
+use crate::solver::cache::SolverCache;
+use crate::sync;
+use crate::sync::error::SyncError;
 use crate::window::PuzzledWindow;
 use adw::gdk::Display;
 use adw::prelude::*;
 use adw::subclass::prelude::*;
 use gettextrs::gettext;
 use gtk::{gio, glib, CssProvider, License, Settings, STYLE_PROVIDER_PRIORITY_APPLICATION};
-use log::info;
+use log::{error, info};
 use puzzle_config::ColorConfig;
 use puzzled_common::shape::shape_square;
+use std::cell::RefCell;
 use std::fmt::Debug;
+use std::rc::Rc;
 
 mod imp {
     use super::*;
@@ -56,7 +79,6 @@ mod imp {
             self.parent_constructed();
             let obj = self.obj();
             obj.setup_gactions();
-            obj.set_accels_for_action("app.quit", &["<control>q"]);
         }
     }
 
@@ -72,6 +94,9 @@ mod imp {
                 .init()
                 .unwrap();
             store::init();
+            audio::init();
+            sync::init();
+            battery_saver::monitor();
 
             let application = self.obj();
             // Get the current window or create one if necessary
@@ -97,10 +122,13 @@ mod imp {
                 .downcast_ref::<PuzzledWindow>()
                 .unwrap()
                 .select_first_collection();
+            application.show_recovered_files_dialog();
+            application.show_unseen_release_notes();
         }
 
         fn shutdown(&self) {
             self.parent_shutdown();
+            SolverCache::default().save();
             let runtime = take_runtime();
             runtime.shutdown_background();
         }
@@ -135,6 +163,21 @@ impl PuzzledApplication {
         let how_to_play_action = gio::ActionEntry::builder("how_to_play")
             .activate(move |app: &Self, _, _| app.show_how_to_play())
             .build();
+        let whats_new_action = gio::ActionEntry::builder("whats_new")
+            .activate(move |app: &Self, _, _| app.show_whats_new())
+            .build();
+        let predefined_catalog_action = gio::ActionEntry::builder("predefined_catalog")
+            .activate(move |app: &Self, _, _| app.show_predefined_catalog())
+            .build();
+        let leaderboard_action = gio::ActionEntry::builder("leaderboard")
+            .activate(move |app: &Self, _, _| app.show_leaderboard())
+            .build();
+        let insights_action = gio::ActionEntry::builder("insights")
+            .activate(move |app: &Self, _, _| app.show_insights())
+            .build();
+        let board_editor_action = gio::ActionEntry::builder("board_editor")
+            .activate(move |app: &Self, _, _| app.show_board_editor())
+            .build();
         let preferences = gio::ActionEntry::builder("preferences")
             .activate(move |app: &Self, _, _| app.show_preferences())
             .build();
@@ -164,21 +207,27 @@ impl PuzzledApplication {
                 })
                 .build();
 
+        let present_window = gio::ActionEntry::builder("present_window")
+            .activate(move |app: &Self, _, _| app.imp().window.get().unwrap().present())
+            .build();
+
         self.add_action_entries([
             quit_action,
             about_action,
             how_to_play_action,
+            whats_new_action,
+            predefined_catalog_action,
+            leaderboard_action,
+            insights_action,
+            board_editor_action,
             preferences,
             mark_all_puzzles_unsolved,
             calculate_tile_combinations_to_solve,
             stop_calculate_tile_combinations_to_solve,
+            present_window,
         ]);
 
-        self.set_accels_for_action("app.calculate_tile_combinations_to_solve", &["<control>k"]);
-        self.set_accels_for_action(
-            "app.stop_calculate_tile_combinations_to_solve",
-            &["<control>l"],
-        );
+        shortcuts::apply(self.upcast_ref());
     }
 
     fn show_about(&self) {
@@ -210,14 +259,198 @@ impl PuzzledApplication {
         let show_board_grid_lines: adw::SwitchRow = builder
             .object("show_board_grid_lines")
             .expect("Missing `show_board_grid_lines` in resource");
+        let show_remaining_cells: adw::SwitchRow = builder
+            .object("show_remaining_cells")
+            .expect("Missing `show_remaining_cells` in resource");
+        let animations: adw::SwitchRow = builder
+            .object("animations")
+            .expect("Missing `animations` in resource");
+        let inhibit_idle: adw::SwitchRow = builder
+            .object("inhibit_idle")
+            .expect("Missing `inhibit_idle` in resource");
+        let push_overlapping_tiles: adw::SwitchRow = builder
+            .object("push_overlapping_tiles")
+            .expect("Missing `push_overlapping_tiles` in resource");
+        let sound_effects: adw::SwitchRow = builder
+            .object("sound_effects")
+            .expect("Missing `sound_effects` in resource");
+        let music: adw::SwitchRow = builder
+            .object("music")
+            .expect("Missing `music` in resource");
+        let volume: gtk::Adjustment = builder
+            .object("volume")
+            .expect("Missing `volume` in resource");
+        let coach_mode: adw::SwitchRow = builder
+            .object("coach_mode")
+            .expect("Missing `coach_mode` in resource");
+        let coach_delay: adw::SpinRow = builder
+            .object("coach_delay")
+            .expect("Missing `coach_delay` in resource");
+        let battery_saver: adw::ComboRow = builder
+            .object("battery_saver")
+            .expect("Missing `battery_saver` in resource");
+        let developer_mode: adw::SwitchRow = builder
+            .object("developer_mode")
+            .expect("Missing `developer_mode` in resource");
         let preferences = Preferences::default();
         preferences.bind(ShowBoardGridLines, &show_board_grid_lines, "active");
+        preferences.bind(ShowRemainingCells, &show_remaining_cells, "active");
+        preferences.bind(Animations, &animations, "active");
+        preferences.bind(InhibitIdle, &inhibit_idle, "active");
+        preferences.bind(PushOverlappingTiles, &push_overlapping_tiles, "active");
+        preferences.bind(SoundEffects, &sound_effects, "active");
+        preferences.bind(Music, &music, "active");
+        preferences.bind(Volume, &volume, "value");
+        preferences.bind(CoachMode, &coach_mode, "active");
+        preferences.bind(CoachDelay, &coach_delay, "value");
+        coach_mode
+            .bind_property("active", &coach_delay, "sensitive")
+            .sync_create()
+            .build();
+        preferences.bind(BatterySaver, &battery_saver, "selected");
+        preferences.bind(DeveloperMode, &developer_mode, "active");
+        self.setup_sync_preferences(&builder, &dialog, &preferences);
+        Self::setup_profile_preferences(&builder, &dialog);
+
+        let clear_solver_cache: adw::ButtonRow = builder
+            .object("clear_solver_cache")
+            .expect("Missing `clear_solver_cache` in resource");
+        clear_solver_cache.connect_activated({
+            let dialog = dialog.clone();
+            move |_| {
+                let message = match SolverCache::default().clear() {
+                    Ok(()) => "Solver cache cleared".to_string(),
+                    Err(e) => {
+                        error!("Failed to clear the solver cache: {}", e);
+                        "Failed to clear the solver cache".to_string()
+                    }
+                };
+                dialog.add_toast(adw::Toast::new(&message));
+            }
+        });
 
         if let Some(window) = self.active_window() {
             dialog.present(Some(&window));
         }
     }
 
+    /// Lists the profiles and lets the player select the profile used after the next start or
+    /// add a new one.
+    fn setup_profile_preferences(builder: &gtk::Builder, dialog: &adw::PreferencesDialog) {
+        let profile: adw::ComboRow = builder
+            .object("profile")
+            .expect("Missing `profile` in resource");
+        let new_profile: adw::EntryRow = builder
+            .object("new_profile")
+            .expect("Missing `new_profile` in resource");
+
+        let profiles = Rc::new(RefCell::new(profiles::load()));
+        let names = gtk::StringList::new(&[]);
+        for profile in profiles.borrow().profiles() {
+            names.append(&profile.name);
+        }
+        let active_index = {
+            let profiles = profiles.borrow();
+            let active = profiles.active();
+            profiles
+                .profiles()
+                .iter()
+                .position(|profile| profile == active)
+                .unwrap_or_default()
+        };
+        profile.set_model(Some(&names));
+        profile.set_selected(active_index as u32);
+
+        profile.connect_selected_notify({
+            let profiles = profiles.clone();
+            let dialog = dialog.clone();
+            move |profile| {
+                let mut profiles = profiles.borrow_mut();
+                let Some(selected) = profiles
+                    .profiles()
+                    .get(profile.selected() as usize)
+                    .map(|profile| profile.id.clone())
+                else {
+                    return;
+                };
+                if selected == profiles.active().id || !profiles.set_active(&selected) {
+                    return;
+                }
+                let message = match profiles::save(&profiles) {
+                    Ok(()) => "Restart Puzzled to switch the profile".to_string(),
+                    Err(e) => {
+                        error!("Failed to save the profiles: {}", e);
+                        "Failed to save the profile".to_string()
+                    }
+                };
+                dialog.add_toast(adw::Toast::new(&message));
+            }
+        });
+
+        new_profile.connect_apply({
+            let dialog = dialog.clone();
+            move |new_profile| {
+                let mut profiles = profiles.borrow_mut();
+                let Some(name) = profiles
+                    .add(&new_profile.text())
+                    .map(|profile| profile.name.clone())
+                else {
+                    dialog.add_toast(adw::Toast::new("Enter a name no other profile has"));
+                    return;
+                };
+                if let Err(e) = profiles::save(&profiles) {
+                    error!("Failed to save the profiles: {}", e);
+                    dialog.add_toast(adw::Toast::new("Failed to save the profile"));
+                    return;
+                }
+                drop(profiles);
+                names.append(&name);
+                new_profile.set_text("");
+            }
+        });
+    }
+
+    fn setup_sync_preferences(
+        &self,
+        builder: &gtk::Builder,
+        dialog: &adw::PreferencesDialog,
+        preferences: &Preferences,
+    ) {
+        let sync_enabled: adw::SwitchRow = builder
+            .object("sync_enabled")
+            .expect("Missing `sync_enabled` in resource");
+        let sync_url: adw::EntryRow = builder
+            .object("sync_url")
+            .expect("Missing `sync_url` in resource");
+        let sync_now: adw::ButtonRow = builder
+            .object("sync_now")
+            .expect("Missing `sync_now` in resource");
+        preferences.bind(SyncEnabled, &sync_enabled, "active");
+        preferences.bind(SyncUrl, &sync_url, "text");
+        sync_enabled
+            .bind_property("active", &sync_now, "sensitive")
+            .sync_create()
+            .build();
+
+        sync_now.connect_activated({
+            let application = self.clone();
+            let dialog = dialog.clone();
+            move |sync_now| {
+                sync_now.set_sensitive(false);
+                let sync_now = sync_now.clone();
+                let dialog = dialog.clone();
+                sync::sync(application.active_window(), move |result| {
+                    sync_now.set_sensitive(true);
+                    let message = match result {
+                        Ok(()) => "Progress synced".to_string(),
+                        Err(e) => sync_error_message(&e),
+                    };
+                    dialog.add_toast(adw::Toast::new(&message));
+                });
+            }
+        });
+    }
+
     fn show_mark_all_puzzles_unsolved_dialog(&self) {
         const RESOURCE_PATH: &str = "/de/til7701/Puzzled/ui/dialog/mark-unsolved-dialog.ui";
         let builder = gtk::Builder::from_resource(RESOURCE_PATH);
@@ -235,6 +468,100 @@ impl PuzzledApplication {
         dialog.present(self.active_window().as_ref());
     }
 
+    /// Informs the user about damaged files, which were backed up and reset during startup.
+    fn show_recovered_files_dialog(&self) {
+        let recovered_files =
+            with_puzzle_collection_store(|store| store.recovered_files().to_vec());
+        if recovered_files.is_empty() {
+            return;
+        }
+
+        let backups = recovered_files
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<String>>()
+            .join("\n");
+        let dialog = adw::AlertDialog::builder()
+            .heading(gettext("Damaged Data Recovered"))
+            .body(format!(
+                "{}\n\n{}",
+                gettext(
+                    "Some saved data could not be read and was reset. Backups of the damaged data were saved to:"
+                ),
+                backups
+            ))
+            .build();
+        dialog.add_response("ok", &gettext("OK"));
+        dialog.set_default_response(Some("ok"));
+        dialog.set_close_response("ok");
+        dialog.present(self.active_window().as_ref());
+    }
+
+    /// Shows the release notes of the versions released since the notes were shown last.
+    /// They are only shown once per version.
+    fn show_unseen_release_notes(&self) {
+        let preferences = Preferences::default();
+        let last_seen = preferences.get(LastSeenReleaseNotes);
+        if last_seen == VERSION {
+            return;
+        }
+        let notes = ReleaseNotes::load();
+        let unseen = notes.unseen(&last_seen, VERSION);
+        if !unseen.is_empty() {
+            self.present_release_notes(&unseen);
+        }
+        preferences.set_string(LastSeenReleaseNotes, VERSION);
+    }
+
+    fn show_whats_new(&self) {
+        let notes = ReleaseNotes::load();
+        let releases: Vec<&Release> = notes.releases().iter().collect();
+        self.present_release_notes(&releases);
+    }
+
+    /// Shows the changes of the given releases and the collections, which got new puzzles.
+    fn present_release_notes(&self, releases: &[&Release]) {
+        let page = adw::PreferencesPage::new();
+        for release in releases {
+            let group = adw::PreferencesGroup::builder()
+                .title(format!("{} {}", gettext("Version"), release.version))
+                .description(&release.date)
+                .build();
+            for change in &release.changes {
+                let row = adw::ActionRow::builder()
+                    .title(change)
+                    .use_markup(false)
+                    .build();
+                group.add(&row);
+            }
+            for collection in &release.collections {
+                let name = with_puzzle_collection_store(|store| {
+                    store
+                        .core_puzzle_collections()
+                        .iter()
+                        .find(|c| c.summary().id() == collection.id)
+                        .map(|c| c.summary().name().to_string())
+                })
+                .unwrap_or_else(|| collection.id.clone());
+                let row = adw::ActionRow::builder()
+                    .title(name)
+                    .subtitle(&collection.highlight)
+                    .use_markup(false)
+                    .build();
+                row.add_prefix(&gtk::Image::from_icon_name("star-large-symbolic"));
+                group.add(&row);
+            }
+            page.add(&group);
+        }
+
+        let dialog = adw::PreferencesDialog::builder()
+            .title(gettext("What's New"))
+            .search_enabled(false)
+            .build();
+        dialog.add(&page);
+        dialog.present(self.active_window().as_ref());
+    }
+
     fn show_how_to_play(&self) {
         const RESOURCE_PATH: &str = "/de/til7701/Puzzled/how-to-play-dialog.ui";
         let builder = gtk::Builder::from_resource(RESOURCE_PATH);
@@ -304,6 +631,150 @@ impl PuzzledApplication {
         dialog.present();
     }
 
+    fn show_predefined_catalog(&self) {
+        const RESOURCE_PATH: &str = "/de/til7701/Puzzled/predefined-catalog-dialog.ui";
+        let builder = gtk::Builder::from_resource(RESOURCE_PATH);
+        let dialog: adw::PreferencesDialog = builder
+            .object("predefined_catalog_dialog")
+            .expect("Missing `predefined_catalog_dialog` in resource");
+
+        const CELL_SIZE: i32 = 12;
+        let catalog = store::create_json_loader().predefined_catalog();
+
+        let tiles_group: adw::PreferencesGroup = builder
+            .object("tiles_group")
+            .expect("Missing `tiles_group` in resource");
+        for (i, tile) in catalog.tiles().iter().enumerate() {
+            let (width, height) = tile.base().dim();
+            let cell_count = tile.base().iter().filter(|cell| **cell).count();
+            let tile_view = TileView::new(i, tile.base().clone(), tile.color());
+            tile_view.set_width_request(CELL_SIZE * width as i32);
+            tile_view.set_height_request(CELL_SIZE * height as i32);
+            tile_view.set_valign(gtk::Align::Center);
+
+            let row = adw::ActionRow::builder()
+                .title(tile.name().as_deref().unwrap_or_default())
+                .subtitle(format!("{} cells", cell_count))
+                .use_markup(false)
+                .build();
+            row.add_suffix(&tile_view);
+            tiles_group.add(&row);
+        }
+
+        let boards_group: adw::PreferencesGroup = builder
+            .object("boards_group")
+            .expect("Missing `boards_group` in resource");
+        for board in catalog.boards() {
+            let (width, height) = board.board().layout().dim();
+            let row = adw::ActionRow::builder()
+                .title(board.name())
+                .subtitle(format!("{} x {}", width, height))
+                .use_markup(false)
+                .build();
+            let picture = board_thumbnail_picture(board.board(), &[]);
+            picture.set_valign(gtk::Align::Center);
+            row.add_suffix(&picture);
+            boards_group.add(&row);
+        }
+
+        if let Some(window) = self.active_window() {
+            dialog.present(Some(&window));
+        }
+    }
+
+    fn show_leaderboard(&self) {
+        const RESOURCE_PATH: &str = "/de/til7701/Puzzled/leaderboard-dialog.ui";
+        let builder = gtk::Builder::from_resource(RESOURCE_PATH);
+        let dialog: adw::PreferencesDialog = builder
+            .object("leaderboard_dialog")
+            .expect("Missing `leaderboard_dialog` in resource");
+        let results_page: adw::PreferencesPage = builder
+            .object("results_page")
+            .expect("Missing `results_page` in resource");
+        let player_name: adw::EntryRow = builder
+            .object("player_name")
+            .expect("Missing `player_name` in resource");
+        let import_token: adw::EntryRow = builder
+            .object("import_token")
+            .expect("Missing `import_token` in resource");
+
+        Preferences::default().bind(PlayerName, &player_name, "text");
+
+        let challenge_groups: Rc<RefCell<Vec<adw::PreferencesGroup>>> = Rc::default();
+        show_challenges(&dialog, &results_page, &challenge_groups);
+        import_token.connect_apply({
+            let dialog = dialog.clone();
+            move |import_token| {
+                let message = match leaderboard::import_token(&import_token.text()) {
+                    Ok(result) => {
+                        import_token.set_text("");
+                        show_challenges(&dialog, &results_page, &challenge_groups);
+                        format!("Imported the result of {}", result.player)
+                    }
+                    Err(e) => token_error_message(&e),
+                };
+                dialog.add_toast(adw::Toast::new(&message));
+            }
+        });
+
+        if let Some(window) = self.active_window() {
+            dialog.present(Some(&window));
+        }
+    }
+
+    fn show_board_editor(&self) {
+        let window = self.imp().window.get().unwrap();
+        let puzzle = window.puzzle_area_nav_page().puzzle();
+        show_board_editor(window, puzzle.as_ref().map(|puzzle| puzzle.config()));
+    }
+
+    fn show_insights(&self) {
+        const RESOURCE_PATH: &str = "/de/til7701/Puzzled/insights-dialog.ui";
+        let builder = gtk::Builder::from_resource(RESOURCE_PATH);
+        let dialog: adw::PreferencesDialog = builder
+            .object("insights_dialog")
+            .expect("Missing `insights_dialog` in resource");
+        let solved_count: adw::ActionRow = builder
+            .object("solved_count")
+            .expect("Missing `solved_count` in resource");
+        let time_played: adw::ActionRow = builder
+            .object("time_played")
+            .expect("Missing `time_played` in resource");
+        let collections_group: adw::PreferencesGroup = builder
+            .object("collections_group")
+            .expect("Missing `collections_group` in resource");
+        let weekdays_group: adw::PreferencesGroup = builder
+            .object("weekdays_group")
+            .expect("Missing `weekdays_group` in resource");
+        let hints_group: adw::PreferencesGroup = builder
+            .object("hints_group")
+            .expect("Missing `hints_group` in resource");
+
+        let insights = Insights::new(&play_history::records());
+        solved_count.set_subtitle(&insights.solved_count.to_string());
+        time_played.set_subtitle(&format!(
+            "{}:{:02} h",
+            insights.minutes_played / 60,
+            insights.minutes_played % 60
+        ));
+        collections_group.set_visible(!insights.minutes_per_collection.is_empty());
+        collections_group.add(&bar_chart(insights.minutes_per_collection, |minutes| {
+            format!("{:.0} min", minutes)
+        }));
+        weekdays_group.set_visible(insights.solved_count > 0);
+        weekdays_group.add(&bar_chart(insights.solves_per_weekday, |count| {
+            format!("{:.0}", count)
+        }));
+        hints_group.set_visible(!insights.hints_per_week.is_empty());
+        hints_group.add(&bar_chart(insights.hints_per_week, |hints| {
+            format!("{:.1}", hints)
+        }));
+
+        if let Some(window) = self.active_window() {
+            dialog.present(Some(&window));
+        }
+    }
+
     fn load_css(&self) {
         let provider = CssProvider::new();
         provider.load_from_resource("/de/til7701/Puzzled/style.css");
@@ -330,15 +801,96 @@ impl PuzzledApplication {
     }
 
     fn setup(&self, window: &PuzzledWindow) {
-        with_puzzle_collection_store(|store| {
-            window
-                .imp()
-                .puzzle_selection_nav_page
-                .show_collection(store.core_puzzle_collections().first().unwrap());
+        let first_collection = with_puzzle_collection_store(|store| {
+            store.core_puzzle_collections().first().unwrap().clone()
         });
+        window
+            .imp()
+            .puzzle_selection_nav_page
+            .show_collection(&first_collection);
 
         if cfg!(debug_assertions) {
             window.add_css_class("devel");
         }
     }
 }
+
+/// Replaces the groups showing the results of the daily challenges in the leaderboard dialog.
+fn show_challenges(
+    dialog: &adw::PreferencesDialog,
+    page: &adw::PreferencesPage,
+    groups: &RefCell<Vec<adw::PreferencesGroup>>,
+) {
+    for group in groups.borrow_mut().drain(..) {
+        page.remove(&group);
+    }
+
+    let player_name = leaderboard::player_name();
+    for challenge in token::challenges(&leaderboard::results()) {
+        let group = adw::PreferencesGroup::builder()
+            .title(format!("{} · {}", challenge.date, challenge.puzzle))
+            .build();
+        let rows = LeaderboardRow::for_challenge(&challenge, &player_name);
+        for (row, result) in rows.iter().zip(&challenge.results) {
+            let action_row = adw::ActionRow::builder()
+                .title(&row.title)
+                .subtitle(&row.subtitle)
+                .use_markup(false)
+                .build();
+            if row.own {
+                let copy_button = gtk::Button::builder()
+                    .icon_name("edit-copy-symbolic")
+                    .tooltip_text(gettext("Copy Result Token"))
+                    .valign(gtk::Align::Center)
+                    .css_classes(["flat"])
+                    .build();
+                copy_button.connect_clicked({
+                    let dialog = dialog.clone();
+                    let token = result.encode();
+                    move |button| {
+                        button.clipboard().set_text(&token);
+                        dialog.add_toast(adw::Toast::new(&gettext("Result token copied")));
+                    }
+                });
+                action_row.add_suffix(&copy_button);
+            }
+            group.add(&action_row);
+        }
+        page.add(&group);
+        groups.borrow_mut().push(group);
+    }
+}
+
+fn token_error_message(error: &TokenError) -> String {
+    match error {
+        TokenError::Malformed => "The text is not a result token.".to_string(),
+        TokenError::UnsupportedVersion => {
+            "The result token was created by a newer version of Puzzled.".to_string()
+        }
+        TokenError::InvalidChecksum => "The result token has been changed.".to_string(),
+    }
+}
+
+fn sync_error_message(error: &SyncError) -> String {
+    match error {
+        SyncError::NotConfigured => "Enter the URL of the file to sync with.".to_string(),
+        SyncError::UnsupportedUrl(url) => {
+            format!("The URL '{}' is not supported. Use an https URL.", url)
+        }
+        SyncError::AlreadyRunning => "The progress is being synced already.".to_string(),
+        SyncError::Connection(message) => {
+            format!("Failed to connect to the server: {}", message)
+        }
+        SyncError::Transfer(message) => format!("Failed to sync the progress file: {}", message),
+        SyncError::Conflict => {
+            "The progress file was changed by another device. Try again.".to_string()
+        }
+        SyncError::MalformedProgress(message) => {
+            format!("The progress file on the server is damaged: {}", message)
+        }
+        SyncError::UnsupportedVersion(version) => format!(
+            "The progress file was written by a newer version of Puzzled (version {}).",
+            version
+        ),
+    }
+}
@@ -0,0 +1,134 @@
+use crate::global::events;
+use crate::global::events::AppEvent;
+use crate::global::settings::{Music, Preferences, SoundEffects, Volume};
+use adw::gio;
+use gtk::prelude::MediaStreamExt;
+use gtk::MediaFile;
+use log::debug;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// A short sound played, when something happens in the game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoundEffect {
+    /// A tile is picked up to be dragged.
+    PickUp,
+    /// A tile is dropped at a valid position.
+    Drop,
+    /// A tile is dropped, where it overlaps another tile.
+    InvalidPlacement,
+    /// A tile is dropped, where it reaches off the board or covers a hole in it.
+    OffBoard,
+    /// The puzzle was solved.
+    Solved,
+}
+
+impl SoundEffect {
+    fn resource_path(&self) -> &'static str {
+        match self {
+            SoundEffect::PickUp => "/de/til7701/Puzzled/sounds/pick-up.wav",
+            SoundEffect::Drop => "/de/til7701/Puzzled/sounds/drop.wav",
+            SoundEffect::InvalidPlacement => "/de/til7701/Puzzled/sounds/invalid.wav",
+            SoundEffect::OffBoard => "/de/til7701/Puzzled/sounds/off-board.wav",
+            SoundEffect::Solved => "/de/til7701/Puzzled/sounds/solved.wav",
+        }
+    }
+}
+
+/// Plays sound effects and the background music of collections with GStreamer through
+/// [MediaFile].
+#[derive(Default)]
+struct Audio {
+    preferences: Preferences,
+    /// The loaded sound effects, so they do not have to be loaded every time they are played.
+    effects: RefCell<HashMap<SoundEffect, MediaFile>>,
+    /// The URI of the music, which should be played currently.
+    music_uri: RefCell<Option<String>>,
+    /// The music playing currently with its URI.
+    music: RefCell<Option<(String, MediaFile)>>,
+}
+
+thread_local! {
+    static AUDIO: Audio = Audio::default();
+}
+
+/// Keeps the volume and the music up to date with the preferences and plays the
+/// [SoundEffect::Solved] effect, when a puzzle is solved.
+///
+/// This must be called once on the main thread.
+pub fn init() {
+    AUDIO.with(|audio| {
+        audio.preferences.connect_changed(Volume, update_music);
+        audio.preferences.connect_changed(Music, update_music);
+    });
+    events::subscribe(|event| {
+        if *event == AppEvent::PuzzleSolved {
+            play(SoundEffect::Solved);
+        }
+    });
+}
+
+/// Plays the sound effect, if sound effects are enabled.
+/// A sound effect, which is still playing, is started again.
+///
+/// This must be called on the main thread.
+pub fn play(effect: SoundEffect) {
+    AUDIO.with(|audio| {
+        if !audio.preferences.get(SoundEffects) {
+            return;
+        }
+        let mut effects = audio.effects.borrow_mut();
+        let media = effects
+            .entry(effect)
+            .or_insert_with(|| MediaFile::for_resource(effect.resource_path()));
+        media.set_volume(audio.preferences.get(Volume));
+        media.seek(0);
+        media.play();
+    });
+}
+
+/// Plays the music at the given URI in a loop, if music is enabled, replacing the current music.
+/// If the music is already playing, it continues.
+///
+/// This must be called on the main thread.
+///
+/// # Arguments
+///
+/// * `uri`: The URI of the music or `None` to stop the music.
+///
+/// returns: ()
+pub fn play_music(uri: Option<&str>) {
+    AUDIO.with(|audio| {
+        audio.music_uri.replace(uri.map(str::to_string));
+    });
+    update_music();
+}
+
+/// Starts, stops or changes the volume of the music depending on the requested music and the
+/// preferences.
+fn update_music() {
+    AUDIO.with(|audio| {
+        let uri = audio.music_uri.borrow().clone();
+        let mut music = audio.music.borrow_mut();
+        let Some(uri) = uri.filter(|_| audio.preferences.get(Music)) else {
+            if let Some((_, media)) = music.take() {
+                media.pause();
+            }
+            return;
+        };
+
+        if music.as_ref().map(|(playing_uri, _)| playing_uri) != Some(&uri) {
+            if let Some((_, media)) = music.take() {
+                media.pause();
+            }
+            debug!("Playing music {}", uri);
+            let media = MediaFile::for_file(&gio::File::for_uri(&uri));
+            media.set_loop(true);
+            media.play();
+            *music = Some((uri, media));
+        }
+        if let Some((_, media)) = music.as_ref() {
+            media.set_volume(audio.preferences.get(Volume));
+        }
+    });
+}
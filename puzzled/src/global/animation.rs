@@ -0,0 +1,81 @@
+use crate::global::settings::{Animations, Preferences};
+use gtk::prelude::*;
+use std::time::Duration;
+
+/// Decides whether animations are played.
+///
+/// Animations are played only if the player did not turn them off in the preferences and the
+/// system neither disables animations nor asks for reduced motion.
+/// All animations should be started with [AnimationPolicy::animate], so they follow this policy.
+#[derive(Debug, Clone, Default)]
+pub struct AnimationPolicy {
+    preferences: Preferences,
+}
+
+impl AnimationPolicy {
+    /// Returns whether animations are currently played.
+    pub fn animations_enabled(&self) -> bool {
+        let Some(settings) = gtk::Settings::default() else {
+            return self.preferences.get(Animations);
+        };
+        animations_allowed(
+            self.preferences.get(Animations),
+            settings.is_gtk_enable_animations(),
+            settings.gtk_interface_reduced_motion() == gtk::ReducedMotion::Reduce,
+        )
+    }
+
+    /// Animates a value from `from` to `to` over the given duration.
+    ///
+    /// If animations are disabled, `on_value` is called once with `to` and no animation is
+    /// started.
+    ///
+    /// # Arguments
+    ///
+    /// * `widget`: the widget, which is animated
+    /// * `from`: the value at the start of the animation
+    /// * `to`: the value at the end of the animation
+    /// * `duration`: how long the animation plays
+    /// * `on_value`: called with the current value every frame
+    ///
+    /// returns: Option<TimedAnimation> the playing animation, if animations are enabled
+    pub fn animate(
+        &self,
+        widget: &impl IsA<gtk::Widget>,
+        from: f64,
+        to: f64,
+        duration: Duration,
+        on_value: impl Fn(f64) + 'static,
+    ) -> Option<adw::TimedAnimation> {
+        if !self.animations_enabled() {
+            on_value(to);
+            return None;
+        }
+        let animation = adw::TimedAnimation::new(
+            widget,
+            from,
+            to,
+            duration.as_millis() as u32,
+            adw::CallbackAnimationTarget::new(on_value),
+        );
+        animation.play();
+        Some(animation)
+    }
+}
+
+fn animations_allowed(preference: bool, system_animations: bool, reduced_motion: bool) -> bool {
+    preference && system_animations && !reduced_motion
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_animations_allowed() {
+        assert!(animations_allowed(true, true, false));
+        assert!(!animations_allowed(false, true, false));
+        assert!(!animations_allowed(true, false, false));
+        assert!(!animations_allowed(true, true, true));
+    }
+}
@@ -0,0 +1,124 @@
+use puzzle_config::Target;
+use puzzle_solver::trace::SearchSnapshot;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+/// Events, which are published when the state of the application changes.
+///
+/// Components subscribe to the events they are interested in via [subscribe] instead of passing
+/// callbacks to each other.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppEvent {
+    /// A tile was moved, rotated or flipped in the puzzle area.
+    TileMoved,
+    /// The target of the current puzzle changed. `None` means that no target is selected.
+    TargetChanged(Option<Target>),
+    /// The current puzzle was solved by the player.
+    PuzzleSolved,
+    /// The solver started or finished running.
+    SolverStateChanged { running: bool },
+    /// The solver took a snapshot of its search, while developer mode is enabled. `None` means
+    /// that the traced solver call finished, so the last snapshot is outdated.
+    SolverSearchSnapshot(Option<SearchSnapshot>),
+}
+
+/// Identifies a subscription, so it can be removed again with [unsubscribe].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubscriptionId(u64);
+
+type Subscriber = Rc<dyn Fn(&AppEvent)>;
+
+#[derive(Default)]
+struct EventBus {
+    next_id: Cell<u64>,
+    subscribers: RefCell<Vec<(SubscriptionId, Subscriber)>>,
+}
+
+thread_local! {
+    /// The event bus of the main thread. Events must be published on the main thread.
+    static EVENT_BUS: EventBus = EventBus::default();
+}
+
+/// Registers the callback to be called for every event published afterward.
+///
+/// returns: the id to pass to [unsubscribe] to remove the callback again
+pub fn subscribe<F: Fn(&AppEvent) + 'static>(callback: F) -> SubscriptionId {
+    EVENT_BUS.with(|bus| {
+        let id = SubscriptionId(bus.next_id.get());
+        bus.next_id.set(id.0 + 1);
+        bus.subscribers.borrow_mut().push((id, Rc::new(callback)));
+        id
+    })
+}
+
+/// Removes the callback registered with the given id.
+/// Nothing happens, if there is no such subscription.
+pub fn unsubscribe(id: SubscriptionId) {
+    EVENT_BUS.with(|bus| {
+        bus.subscribers
+            .borrow_mut()
+            .retain(|(subscription_id, _)| *subscription_id != id)
+    });
+}
+
+/// Calls all subscribers with the given event in the order they subscribed.
+///
+/// Subscribers may publish further events or change subscriptions. Those changes take effect for
+/// the next published event.
+pub fn publish(event: AppEvent) {
+    let subscribers: Vec<Subscriber> = EVENT_BUS.with(|bus| {
+        bus.subscribers
+            .borrow()
+            .iter()
+            .map(|(_, subscriber)| subscriber.clone())
+            .collect()
+    });
+    for subscriber in subscribers {
+        subscriber(&event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_publish_and_unsubscribe() {
+        let received = Rc::new(RefCell::new(Vec::new()));
+        let id = subscribe({
+            let received = received.clone();
+            move |event| received.borrow_mut().push(event.clone())
+        });
+
+        publish(AppEvent::TileMoved);
+        publish(AppEvent::SolverStateChanged { running: true });
+        unsubscribe(id);
+        publish(AppEvent::PuzzleSolved);
+
+        assert_eq!(
+            *received.borrow(),
+            vec![
+                AppEvent::TileMoved,
+                AppEvent::SolverStateChanged { running: true }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_publish_from_subscriber() {
+        let count = Rc::new(Cell::new(0));
+        subscribe({
+            let count = count.clone();
+            move |event| {
+                count.set(count.get() + 1);
+                if *event == AppEvent::TileMoved {
+                    publish(AppEvent::PuzzleSolved);
+                }
+            }
+        });
+
+        publish(AppEvent::TileMoved);
+
+        assert_eq!(count.get(), 2);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::global::settings::{InhibitIdle, Preferences};
+use adw::gio;
+use adw::glib::SignalHandlerId;
+use adw::prelude::{Cast, GtkApplicationExt, IsA};
+use log::{debug, warn};
+
+/// Keeps the session from blanking the screen or going idle, while it is alive.
+///
+/// Created by [inhibit]. Dropping it allows the session to go idle again.
+#[derive(Debug)]
+pub struct IdleInhibitor {
+    app: gtk::Application,
+    cookie: u32,
+}
+
+impl Drop for IdleInhibitor {
+    fn drop(&mut self) {
+        debug!("Allowing the session to go idle again");
+        self.app.uninhibit(self.cookie);
+    }
+}
+
+/// Asks the session manager not to blank the screen, if the player enabled this in the
+/// preferences. Use this while the player is likely watching the puzzle without touching it,
+/// like during a timed solve.
+///
+/// # Arguments
+///
+/// * `window`: The window, which should stay visible.
+/// * `reason`: Why the screen should not be blanked. The session may show it to the user.
+///
+/// returns: Option<IdleInhibitor> None, if the preference is disabled or the session manager
+/// refused.
+pub fn inhibit(window: &impl IsA<gtk::Window>, reason: &str) -> Option<IdleInhibitor> {
+    if !Preferences::default().get(InhibitIdle) {
+        return None;
+    }
+    let app = application()?;
+    let cookie = app.inhibit(
+        Some(window),
+        gtk::ApplicationInhibitFlags::IDLE,
+        Some(reason),
+    );
+    if cookie == 0 {
+        warn!("The session manager refused to keep the screen on");
+        return None;
+    }
+    debug!("Keeping the screen on: {}", reason);
+    Some(IdleInhibitor { app, cookie })
+}
+
+/// Returns true, if the session reports that it is idle, e.g. because the screensaver is active.
+pub fn is_idle() -> bool {
+    application().is_some_and(|app| app.is_screensaver_active())
+}
+
+/// Calls the callback with the new state every time the session becomes idle or active again.
+///
+/// The session reports it through the screensaver, which is only tracked on some platforms. On
+/// others, the callback is never called.
+///
+/// returns: Option<SignalHandlerId> None, if there is no application to monitor.
+pub fn connect_idle_changed<F: Fn(bool) + 'static>(callback: F) -> Option<SignalHandlerId> {
+    let app = application()?;
+    Some(app.connect_screensaver_active_notify(move |app| {
+        let idle = app.is_screensaver_active();
+        debug!("The session is {}", if idle { "idle" } else { "active" });
+        callback(idle);
+    }))
+}
+
+fn application() -> Option<gtk::Application> {
+    gio::Application::default().and_then(|app| app.downcast::<gtk::Application>().ok())
+}
@@ -1,2 +1,7 @@
+pub mod animation;
+pub mod events;
+pub mod idle_monitor;
 pub mod runtime;
 pub mod settings;
+pub mod shortcuts;
+pub mod storage;
@@ -1,6 +1,11 @@
+use crate::global::storage;
+use adw::gio;
 use adw::gio::Settings;
 use adw::glib;
-use adw::prelude::{IsA, SettingsExt, SettingsExtManual};
+use adw::prelude::{IsA, SettingsExt, SettingsExtManual, ToValue, ToVariant};
+use log::error;
+use std::collections::HashMap;
+use std::time::Duration;
 
 /// A reusable container for preferences/settings access.
 #[derive(Debug, Clone)]
@@ -11,7 +16,7 @@ pub struct Preferences {
 impl Default for Preferences {
     fn default() -> Self {
         Preferences {
-            settings: Settings::new("de.til7701.Puzzled"),
+            settings: storage::settings("de.til7701.Puzzled"),
         }
     }
 }
@@ -26,6 +31,26 @@ impl Preferences {
     pub fn bind<S: SettingKey>(&self, setting: S, obj: &impl IsA<glib::Object>, property: &str) {
         setting.bind(&self.settings, obj, property);
     }
+
+    /// Creates a stateful action, which toggles or sets the given setting when activated.
+    /// Menu items for boolean settings show a check mark.
+    pub fn create_action<S: SettingKey>(&self, setting: S) -> gio::Action {
+        self.settings.create_action(setting.key())
+    }
+
+    /// Sets the value of the given string setting.
+    pub fn set_string<S: SettingKey<Value = String>>(&self, setting: S, value: &str) {
+        if let Err(e) = self.settings.set_string(setting.key(), value) {
+            error!("Failed to set setting {}: {}", setting.key(), e);
+        }
+    }
+
+    /// Calls the callback every time the given setting changes.
+    /// The callback is called as long as this `Preferences` is not dropped.
+    pub fn connect_changed<S: SettingKey, F: Fn() + 'static>(&self, setting: S, callback: F) {
+        self.settings
+            .connect_changed(Some(setting.key()), move |_, _| callback());
+    }
 }
 
 pub trait SettingKey {
@@ -57,3 +82,318 @@ impl SettingKey for ShowBoardGridLines {
         settings.boolean(self.key())
     }
 }
+
+/// Whether animations are played. They are never played, if the system asks for reduced motion.
+/// See [crate::global::animation::AnimationPolicy].
+pub struct Animations;
+
+impl SettingKey for Animations {
+    type Value = bool;
+
+    fn key(&self) -> &'static str {
+        "animations"
+    }
+
+    fn get(&self, settings: &Settings) -> Self::Value {
+        settings.boolean(self.key())
+    }
+}
+
+pub struct PushOverlappingTiles;
+
+impl SettingKey for PushOverlappingTiles {
+    type Value = bool;
+
+    fn key(&self) -> &'static str {
+        "push-overlapping-tiles"
+    }
+
+    fn get(&self, settings: &Settings) -> Self::Value {
+        settings.boolean(self.key())
+    }
+}
+
+/// Whether the number of empty cells left on the board is shown in the header of a puzzle.
+pub struct ShowRemainingCells;
+
+impl SettingKey for ShowRemainingCells {
+    type Value = bool;
+
+    fn key(&self) -> &'static str {
+        "show-remaining-cells"
+    }
+
+    fn get(&self, settings: &Settings) -> Self::Value {
+        settings.boolean(self.key())
+    }
+}
+
+/// Whether the screen is kept on while a puzzle is being solved.
+/// See [crate::global::idle_monitor::inhibit].
+pub struct InhibitIdle;
+
+impl SettingKey for InhibitIdle {
+    type Value = bool;
+
+    fn key(&self) -> &'static str {
+        "inhibit-idle"
+    }
+
+    fn get(&self, settings: &Settings) -> Self::Value {
+        settings.boolean(self.key())
+    }
+}
+
+/// Whether the coach offers a hint, when the player made no progress for [CoachDelay].
+pub struct CoachMode;
+
+impl SettingKey for CoachMode {
+    type Value = bool;
+
+    fn key(&self) -> &'static str {
+        "coach-mode"
+    }
+
+    fn get(&self, settings: &Settings) -> Self::Value {
+        settings.boolean(self.key())
+    }
+}
+
+/// How long the player has to make no progress, before the coach offers a hint.
+/// It is stored in minutes, so it can be bound to the `value` property of a spin row.
+pub struct CoachDelay;
+
+impl SettingKey for CoachDelay {
+    type Value = Duration;
+
+    fn key(&self) -> &'static str {
+        "coach-delay"
+    }
+
+    fn get(&self, settings: &Settings) -> Self::Value {
+        Duration::from_secs_f64(settings.double(self.key()).max(0.0) * 60.0)
+    }
+}
+
+/// Whether tiles are colored by how many placements they have left on the board.
+pub struct ShowTileConstraints;
+
+impl SettingKey for ShowTileConstraints {
+    type Value = bool;
+
+    fn key(&self) -> &'static str {
+        "show-tile-constraints"
+    }
+
+    fn get(&self, settings: &Settings) -> Self::Value {
+        settings.boolean(self.key())
+    }
+}
+
+/// Whether sound effects are played, e.g. when a tile is dropped or a puzzle is solved.
+pub struct SoundEffects;
+
+impl SettingKey for SoundEffects {
+    type Value = bool;
+
+    fn key(&self) -> &'static str {
+        "sound-effects"
+    }
+
+    fn get(&self, settings: &Settings) -> Self::Value {
+        settings.boolean(self.key())
+    }
+}
+
+/// Whether the background music of collections is played.
+pub struct Music;
+
+impl SettingKey for Music {
+    type Value = bool;
+
+    fn key(&self) -> &'static str {
+        "music"
+    }
+
+    fn get(&self, settings: &Settings) -> Self::Value {
+        settings.boolean(self.key())
+    }
+}
+
+/// The volume of sound effects and music between 0 (silent) and 1 (full volume).
+pub struct Volume;
+
+impl SettingKey for Volume {
+    type Value = f64;
+
+    fn key(&self) -> &'static str {
+        "volume"
+    }
+
+    fn get(&self, settings: &Settings) -> Self::Value {
+        settings.double(self.key())
+    }
+}
+
+/// Whether the state of the search is shown on the board while the solver runs for the player.
+pub struct DeveloperMode;
+
+impl SettingKey for DeveloperMode {
+    type Value = bool;
+
+    fn key(&self) -> &'static str {
+        "developer-mode"
+    }
+
+    fn get(&self, settings: &Settings) -> Self::Value {
+        settings.boolean(self.key())
+    }
+}
+
+/// The name shown with the results of daily challenges.
+pub struct PlayerName;
+
+impl SettingKey for PlayerName {
+    type Value = String;
+
+    fn key(&self) -> &'static str {
+        "player-name"
+    }
+
+    fn get(&self, settings: &Settings) -> Self::Value {
+        settings.string(self.key()).to_string()
+    }
+}
+
+/// Whether the progress is synced with a file on a WebDAV server.
+pub struct SyncEnabled;
+
+impl SettingKey for SyncEnabled {
+    type Value = bool;
+
+    fn key(&self) -> &'static str {
+        "sync-enabled"
+    }
+
+    fn get(&self, settings: &Settings) -> Self::Value {
+        settings.boolean(self.key())
+    }
+}
+
+/// The URL of the file on a WebDAV server, in which the progress is synced.
+pub struct SyncUrl;
+
+impl SettingKey for SyncUrl {
+    type Value = String;
+
+    fn key(&self) -> &'static str {
+        "sync-url"
+    }
+
+    fn get(&self, settings: &Settings) -> Self::Value {
+        settings.string(self.key()).to_string()
+    }
+}
+
+/// The version, for which the "What's New" dialog was shown last.
+pub struct LastSeenReleaseNotes;
+
+impl SettingKey for LastSeenReleaseNotes {
+    type Value = String;
+
+    fn key(&self) -> &'static str {
+        "last-seen-release-notes"
+    }
+
+    fn get(&self, settings: &Settings) -> Self::Value {
+        settings.string(self.key()).to_string()
+    }
+}
+
+/// How the solver saves power, while the system is in power saver mode.
+///
+/// Only background solver calls are restricted. Solver calls requested by the player, like
+/// hints, always run immediately.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum BatterySaverMode {
+    /// Background solver calls are not restricted.
+    Off,
+    /// Background solver calls run one after another instead of at the same time.
+    #[default]
+    Reduced,
+    /// Background solver calls wait until power saver mode is disabled.
+    Deferred,
+}
+
+impl BatterySaverMode {
+    /// The modes in the order they are shown in the preferences.
+    const ALL: [BatterySaverMode; 3] = [
+        BatterySaverMode::Off,
+        BatterySaverMode::Reduced,
+        BatterySaverMode::Deferred,
+    ];
+
+    fn key(&self) -> &'static str {
+        match self {
+            BatterySaverMode::Off => "off",
+            BatterySaverMode::Reduced => "reduced",
+            BatterySaverMode::Deferred => "deferred",
+        }
+    }
+
+    fn from_key(key: &str) -> BatterySaverMode {
+        Self::ALL
+            .into_iter()
+            .find(|mode| mode.key() == key)
+            .unwrap_or_default()
+    }
+}
+
+/// The [BatterySaverMode]. It can be bound to the `selected` property of a combo row listing the
+/// modes in the order off, reduced and deferred.
+pub struct BatterySaver;
+
+impl SettingKey for BatterySaver {
+    type Value = BatterySaverMode;
+
+    fn key(&self) -> &'static str {
+        "battery-saver"
+    }
+
+    fn get(&self, settings: &Settings) -> Self::Value {
+        BatterySaverMode::from_key(&settings.string(self.key()))
+    }
+
+    fn bind(&self, settings: &Settings, obj: &impl IsA<glib::Object>, property: &str) {
+        settings
+            .bind(self.key(), obj, property)
+            .mapping(|variant, _| {
+                let mode = BatterySaverMode::from_key(variant.str()?);
+                let index = BatterySaverMode::ALL.iter().position(|m| *m == mode)?;
+                Some((index as u32).to_value())
+            })
+            .set_mapping(|value, _| {
+                let index = value.get::<u32>().ok()?;
+                let mode = BatterySaverMode::ALL.get(index as usize)?;
+                Some(mode.key().to_variant())
+            })
+            .build();
+    }
+}
+
+/// The keyboard shortcuts the player assigned to actions instead of their default ones.
+/// It maps the name of an action, like `app.rotate_tile`, to its accelerators.
+/// See [crate::global::shortcuts].
+pub struct ShortcutOverrides;
+
+impl SettingKey for ShortcutOverrides {
+    type Value = HashMap<String, Vec<String>>;
+
+    fn key(&self) -> &'static str {
+        "shortcut-overrides"
+    }
+
+    fn get(&self, settings: &Settings) -> Self::Value {
+        settings.value(self.key()).get().unwrap_or_default()
+    }
+}
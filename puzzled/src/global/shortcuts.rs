@@ -0,0 +1,130 @@
+use crate::global::settings::{Preferences, ShortcutOverrides};
+use gtk::prelude::GtkApplicationExt;
+use std::collections::HashMap;
+
+/// An action, which can be triggered with a keyboard shortcut.
+#[derive(Debug)]
+pub struct Shortcut {
+    /// The detailed name of the action, like `app.hint`.
+    pub action: &'static str,
+    /// The accelerators used, if the player did not assign other ones.
+    pub default_accels: &'static [&'static str],
+}
+
+/// All actions with keyboard shortcuts.
+/// They are listed in the shortcuts dialog, which shows the accelerators currently set.
+///
+/// `app.shortcuts` is not listed, since it is set up by libadwaita.
+pub const SHORTCUTS: [Shortcut; 11] = [
+    Shortcut {
+        action: "app.quit",
+        default_accels: &["<control>q"],
+    },
+    Shortcut {
+        action: "app.hint",
+        default_accels: &["<control>h"],
+    },
+    Shortcut {
+        action: "app.rotate_tile",
+        default_accels: &["<control>r"],
+    },
+    Shortcut {
+        action: "app.flip_tile",
+        default_accels: &["<control>m"],
+    },
+    Shortcut {
+        action: "app.rotate_board",
+        default_accels: &["<control><shift>r"],
+    },
+    Shortcut {
+        action: "app.select_target",
+        default_accels: &["<control>t"],
+    },
+    Shortcut {
+        action: "app.random_target",
+        default_accels: &["<control><shift>t"],
+    },
+    Shortcut {
+        action: "app.puzzle_info",
+        default_accels: &["<control>i"],
+    },
+    Shortcut {
+        action: "app.next_unsolved_puzzle",
+        default_accels: &["<control>n"],
+    },
+    Shortcut {
+        action: "app.calculate_tile_combinations_to_solve",
+        default_accels: &["<control>k"],
+    },
+    Shortcut {
+        action: "app.stop_calculate_tile_combinations_to_solve",
+        default_accels: &["<control>l"],
+    },
+];
+
+/// Sets the accelerators of all [SHORTCUTS] on the application and updates them, when the player
+/// changes the [ShortcutOverrides].
+///
+/// This must be called after the actions were added to the application.
+pub fn apply(application: &gtk::Application) {
+    let preferences = Preferences::default();
+    set_accels(application, &preferences.get(ShortcutOverrides));
+    preferences.connect_changed(ShortcutOverrides, {
+        let application = application.clone();
+        let preferences = preferences.clone();
+        move || set_accels(&application, &preferences.get(ShortcutOverrides))
+    });
+}
+
+fn set_accels(application: &gtk::Application, overrides: &HashMap<String, Vec<String>>) {
+    for shortcut in &SHORTCUTS {
+        let accels = accels_for(shortcut, overrides);
+        let accels: Vec<&str> = accels.iter().map(String::as_str).collect();
+        application.set_accels_for_action(shortcut.action, &accels);
+    }
+}
+
+/// Returns the accelerators of the shortcut, preferring the ones the player assigned to it.
+/// An empty list of overrides removes all accelerators from the action.
+fn accels_for(shortcut: &Shortcut, overrides: &HashMap<String, Vec<String>>) -> Vec<String> {
+    match overrides.get(shortcut.action) {
+        Some(accels) => accels.clone(),
+        None => shortcut
+            .default_accels
+            .iter()
+            .map(|accel| accel.to_string())
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_accels_for() {
+        let rotate_tile = SHORTCUTS
+            .iter()
+            .find(|shortcut| shortcut.action == "app.rotate_tile")
+            .unwrap();
+        let mut overrides = HashMap::new();
+        assert_eq!(accels_for(rotate_tile, &overrides), vec!["<control>r"]);
+
+        overrides.insert("app.rotate_tile".to_string(), vec!["r".to_string()]);
+        assert_eq!(accels_for(rotate_tile, &overrides), vec!["r"]);
+
+        overrides.insert("app.rotate_tile".to_string(), Vec::new());
+        assert!(accels_for(rotate_tile, &overrides).is_empty());
+    }
+
+    #[test]
+    fn test_default_accels_are_unique() {
+        let mut accels = HashSet::new();
+        for shortcut in &SHORTCUTS {
+            for accel in shortcut.default_accels {
+                assert!(accels.insert(*accel), "{} is used twice", accel);
+            }
+        }
+    }
+}
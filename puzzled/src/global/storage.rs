@@ -0,0 +1,225 @@
+use adw::gio;
+use adw::gio::{Settings, SettingsBackend};
+use adw::glib;
+use std::cell::{OnceCell, RefCell};
+use std::fmt::Debug;
+use std::path::PathBuf;
+use std::rc::Rc;
+
+/// Environment variable enabling the portable mode, if it is set to a directory.
+/// In portable mode, all files and settings are stored in that directory.
+pub const PORTABLE_DIR_ENV_VAR: &str = "PUZZLED_PORTABLE_DIR";
+
+/// The file in the config directory, in which settings are stored in portable mode and for
+/// profiles other than the default one.
+const PORTABLE_SETTINGS_FILE_NAME: &str = "settings.ini";
+
+/// The directory in the data and config directories of the backend, which contains a directory
+/// for each profile other than the default one.
+const PROFILES_DIR_NAME: &str = "profiles";
+
+/// Resolves the directories, in which the app stores its files.
+pub trait StorageBackend: Debug {
+    /// Directory for data created by the user, like imported collections.
+    fn data_dir(&self) -> PathBuf;
+
+    /// Directory for configuration.
+    fn config_dir(&self) -> PathBuf;
+
+    /// Directory for files, which can be recreated, if they are deleted.
+    fn cache_dir(&self) -> PathBuf;
+
+    /// Returns true, if all files and settings are kept in the directories of this backend.
+    /// Settings are then stored in a file in the config directory instead of the default
+    /// settings backend.
+    fn is_portable(&self) -> bool;
+}
+
+/// Uses the user directories from the XDG base directory specification.
+/// Inside a Flatpak, those are the directories of the sandbox.
+#[derive(Debug, Default)]
+pub struct XdgStorageBackend;
+
+impl StorageBackend for XdgStorageBackend {
+    fn data_dir(&self) -> PathBuf {
+        glib::user_data_dir()
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        glib::user_config_dir()
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        glib::user_cache_dir()
+    }
+
+    fn is_portable(&self) -> bool {
+        false
+    }
+}
+
+/// Stores everything in subdirectories of a single root directory.
+/// This is used in portable mode and for tests.
+#[derive(Debug)]
+pub struct DirectoryStorageBackend {
+    root: PathBuf,
+}
+
+impl DirectoryStorageBackend {
+    pub fn new(root: PathBuf) -> Self {
+        DirectoryStorageBackend { root }
+    }
+}
+
+impl StorageBackend for DirectoryStorageBackend {
+    fn data_dir(&self) -> PathBuf {
+        self.root.join("data")
+    }
+
+    fn config_dir(&self) -> PathBuf {
+        self.root.join("config")
+    }
+
+    fn cache_dir(&self) -> PathBuf {
+        self.root.join("cache")
+    }
+
+    fn is_portable(&self) -> bool {
+        true
+    }
+}
+
+thread_local! {
+    static STORAGE_BACKEND: RefCell<Rc<dyn StorageBackend>> = RefCell::new(default_backend());
+    static SETTINGS_BACKEND: OnceCell<Option<SettingsBackend>> = const { OnceCell::new() };
+    static PROFILE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Returns the backend used, if none is set explicitly.
+/// This is the [DirectoryStorageBackend] in portable mode and the [XdgStorageBackend] otherwise.
+fn default_backend() -> Rc<dyn StorageBackend> {
+    match std::env::var_os(PORTABLE_DIR_ENV_VAR) {
+        Some(dir) if !dir.is_empty() => Rc::new(DirectoryStorageBackend::new(PathBuf::from(dir))),
+        _ => Rc::new(XdgStorageBackend),
+    }
+}
+
+/// Replaces the backend resolving the directories for the current thread.
+/// This must be called before any settings are created.
+pub fn set_backend(backend: impl StorageBackend + 'static) {
+    STORAGE_BACKEND.with_borrow_mut(|current| *current = Rc::new(backend));
+}
+
+/// Selects the profile, whose files and settings are used on the current thread.
+///
+/// `None` selects the default profile, which uses the directories of the backend directly, so
+/// the data of versions without profiles belongs to it. Other profiles use a subdirectory named
+/// after their id and store their settings in a file like in portable mode.
+/// This must be called before any settings are created.
+pub fn set_profile(profile_id: Option<String>) {
+    PROFILE.with_borrow_mut(|current| *current = profile_id);
+}
+
+fn backend() -> Rc<dyn StorageBackend> {
+    STORAGE_BACKEND.with_borrow(|backend| backend.clone())
+}
+
+/// Returns the directory of the selected profile in the given directory of the backend.
+fn profile_dir(dir: PathBuf) -> PathBuf {
+    PROFILE.with_borrow(|profile| match profile {
+        Some(profile_id) => dir.join(PROFILES_DIR_NAME).join(profile_id),
+        None => dir,
+    })
+}
+
+/// See [StorageBackend::data_dir].
+/// The directory belongs to the selected profile. See [set_profile].
+pub fn data_dir() -> PathBuf {
+    profile_dir(backend().data_dir())
+}
+
+/// Like [data_dir], but shared by all profiles.
+/// It contains data, which is the same for everyone using the device, like imported collections.
+pub fn shared_data_dir() -> PathBuf {
+    backend().data_dir()
+}
+
+/// See [StorageBackend::config_dir].
+/// The directory belongs to the selected profile. See [set_profile].
+pub fn config_dir() -> PathBuf {
+    profile_dir(backend().config_dir())
+}
+
+/// See [StorageBackend::cache_dir].
+/// The cache is shared by all profiles.
+pub fn cache_dir() -> PathBuf {
+    backend().cache_dir()
+}
+
+/// See [StorageBackend::is_portable].
+pub fn is_portable() -> bool {
+    backend().is_portable()
+}
+
+/// Creates the settings for the given schema.
+/// In portable mode and for profiles other than the default one, a key file in the config
+/// directory is used for all schemas.
+pub fn settings(schema_id: &str) -> Settings {
+    let settings_backend = SETTINGS_BACKEND.with(|settings_backend| {
+        settings_backend
+            .get_or_init(|| {
+                let default_profile = PROFILE.with_borrow(Option::is_none);
+                if !is_portable() && default_profile {
+                    return None;
+                }
+                let config_dir = config_dir();
+                let _ = std::fs::create_dir_all(&config_dir);
+                let file_path = config_dir.join(PORTABLE_SETTINGS_FILE_NAME);
+                Some(gio::keyfile_settings_backend_new(
+                    &file_path.to_string_lossy(),
+                    "/",
+                    None,
+                ))
+            })
+            .clone()
+    });
+    match settings_backend {
+        Some(settings_backend) => Settings::with_backend(schema_id, &settings_backend),
+        None => Settings::new(schema_id),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_directory_backend() {
+        set_backend(DirectoryStorageBackend::new(PathBuf::from("/tmp/puzzled")));
+
+        assert_eq!(data_dir(), PathBuf::from("/tmp/puzzled/data"));
+        assert_eq!(config_dir(), PathBuf::from("/tmp/puzzled/config"));
+        assert_eq!(cache_dir(), PathBuf::from("/tmp/puzzled/cache"));
+        assert!(is_portable());
+    }
+
+    #[test]
+    fn test_profile_dirs() {
+        set_backend(DirectoryStorageBackend::new(PathBuf::from("/tmp/puzzled")));
+        set_profile(Some("profile-1".to_string()));
+
+        assert_eq!(
+            data_dir(),
+            PathBuf::from("/tmp/puzzled/data/profiles/profile-1")
+        );
+        assert_eq!(
+            config_dir(),
+            PathBuf::from("/tmp/puzzled/config/profiles/profile-1")
+        );
+        assert_eq!(shared_data_dir(), PathBuf::from("/tmp/puzzled/data"));
+        assert_eq!(cache_dir(), PathBuf::from("/tmp/puzzled/cache"));
+
+        set_profile(None);
+        assert_eq!(data_dir(), PathBuf::from("/tmp/puzzled/data"));
+    }
+}
@@ -0,0 +1,286 @@
+use crate::app::collection_selection::load::read_error_message;
+use crate::app::puzzle::puzzle_area::puzzle_state::PuzzleState;
+use crate::model::extension::PuzzleTypeExtension;
+use crate::model::store;
+use crate::solver::Solver;
+use gtk::glib;
+use puzzle_config::digitizer::{digitize_board, DigitizeError};
+use puzzle_config::PuzzleConfig;
+use puzzle_solver::result::{Solution, UnsolvableReason};
+use puzzle_solver::search_tree::SearchTree;
+use puzzle_solver::tile::Tile;
+use puzzled_common::Shape;
+use serde_json::json;
+use std::cell::RefCell;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::mpsc::RecvTimeoutError;
+use std::thread;
+use std::time::{Duration, Instant};
+use tokio::runtime;
+use tokio_util::sync::CancellationToken;
+
+/// The option to solve all puzzles of a collection file without showing the window.
+pub const SOLVE_COLLECTION_OPTION: &str = "--solve-collection";
+/// The option to print statistics about a search tree written by the solver.
+/// See [crate::solver::SEARCH_TREE_FILE_ENV_VAR].
+pub const SEARCH_TREE_STATS_OPTION: &str = "--search-tree-stats";
+/// The option to print a draft board detected in a photo of a physical puzzle.
+pub const DIGITIZE_BOARD_OPTION: &str = "--digitize-board";
+/// The time after which solving a single puzzle is given up and counted as a failure.
+const PUZZLE_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The outcome of solving a single puzzle.
+#[derive(Debug, PartialEq, Eq)]
+enum Outcome {
+    Solved,
+    /// The puzzle is marked as unsolvable in the collection, so it is not solved.
+    Skipped,
+    Unsolvable,
+    TimedOut,
+}
+
+/// Returns the collection file to solve, if the application was started with
+/// [SOLVE_COLLECTION_OPTION].
+///
+/// # Arguments
+///
+/// * `args`: the command line arguments without the name of the binary
+///
+/// returns: Option<Result<PathBuf, String>> `None`, if the option is not given, or an error
+/// message, if the file is missing.
+pub fn collection_path(args: &[String]) -> Option<Result<PathBuf, String>> {
+    option_path(args, SOLVE_COLLECTION_OPTION)
+}
+
+/// Returns the search tree file to print statistics about, if the application was started with
+/// [SEARCH_TREE_STATS_OPTION].
+///
+/// See [collection_path] for the arguments and the result.
+pub fn search_tree_path(args: &[String]) -> Option<Result<PathBuf, String>> {
+    option_path(args, SEARCH_TREE_STATS_OPTION)
+}
+
+/// Returns the photo to detect a board in, if the application was started with
+/// [DIGITIZE_BOARD_OPTION].
+///
+/// See [collection_path] for the arguments and the result.
+pub fn board_photo_path(args: &[String]) -> Option<Result<PathBuf, String>> {
+    option_path(args, DIGITIZE_BOARD_OPTION)
+}
+
+/// Returns the path following the option or a usage message, if it is missing.
+fn option_path(args: &[String], option: &str) -> Option<Result<PathBuf, String>> {
+    let position = args.iter().position(|arg| arg == option)?;
+    Some(
+        args.get(position + 1)
+            .map(PathBuf::from)
+            .ok_or_else(|| format!("Usage: puzzled {} <file>", option)),
+    )
+}
+
+/// Reads the search tree file and prints statistics about the placements tried by the solver.
+///
+/// returns: ExitCode `FAILURE`, if the file cannot be read.
+pub fn print_search_tree_stats(path: &Path) -> glib::ExitCode {
+    let tree = File::open(path).and_then(|file| SearchTree::read_from(BufReader::new(file)));
+    let tree = match tree {
+        Ok(tree) => tree,
+        Err(e) => {
+            eprintln!("Failed to read '{}': {}", path.display(), e);
+            return glib::ExitCode::FAILURE;
+        }
+    };
+    print!("{}", tree.stats());
+    glib::ExitCode::SUCCESS
+}
+
+/// Detects the board in the photo and prints it as a board of a collection file.
+/// The board is only a draft, which should be checked and refined before it is used.
+///
+/// returns: ExitCode `FAILURE`, if the photo cannot be read or no grid is found in it.
+pub fn print_digitized_board(path: &Path) -> glib::ExitCode {
+    let bytes = match std::fs::read(path) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            eprintln!("Failed to read '{}': {}", path.display(), e);
+            return glib::ExitCode::FAILURE;
+        }
+    };
+    match digitize_board(&bytes) {
+        Ok(board) => {
+            println!("{}", board_json(board.layout()));
+            glib::ExitCode::SUCCESS
+        }
+        Err(DigitizeError::InvalidImage(message)) => {
+            eprintln!("Failed to decode '{}': {}", path.display(), message);
+            glib::ExitCode::FAILURE
+        }
+        Err(DigitizeError::GridNotFound) => {
+            eprintln!("No grid found in '{}'", path.display());
+            glib::ExitCode::FAILURE
+        }
+    }
+}
+
+/// Formats the layout as a board of a collection file, where `1` marks blocked cells.
+fn board_json(layout: &Shape) -> String {
+    let (width, height) = layout.dim();
+    let rows: Vec<Vec<u8>> = (0..height)
+        .map(|y| (0..width).map(|x| u8::from(!layout[(x, y)])).collect())
+        .collect();
+    serde_json::to_string_pretty(&json!({ "layout": rows })).unwrap_or_default()
+}
+
+/// Loads the collection file and solves every puzzle in it with the default target.
+/// The result of each puzzle is printed, so the collection can be checked in CI.
+///
+/// The resources must be registered before, since the predefined tiles and boards are loaded
+/// from them.
+///
+/// returns: ExitCode `FAILURE`, if the collection cannot be loaded or a puzzle could not be
+/// solved within [PUZZLE_TIMEOUT].
+pub fn solve_collection(path: &Path) -> glib::ExitCode {
+    let json = match std::fs::read_to_string(path) {
+        Ok(json) => json,
+        Err(e) => {
+            eprintln!("Failed to read '{}': {}", path.display(), e);
+            return glib::ExitCode::FAILURE;
+        }
+    };
+    let collection = match store::create_json_loader().load_puzzle_collection(&json) {
+        Ok(collection) => collection,
+        Err(e) => {
+            eprintln!(
+                "Failed to load '{}': {}",
+                path.display(),
+                read_error_message(e)
+            );
+            return glib::ExitCode::FAILURE;
+        }
+    };
+
+    let runtime = runtime::Builder::new_multi_thread().build().unwrap();
+    let mut failures = 0;
+    for puzzle in collection.puzzles() {
+        let start_time = Instant::now();
+        let outcome = runtime.block_on(solve_puzzle(puzzle));
+        let elapsed = start_time.elapsed();
+        let status = match outcome {
+            Outcome::Solved => "solved",
+            Outcome::Skipped => "skipped (marked as unsolvable)",
+            Outcome::Unsolvable => "FAILED (unsolvable)",
+            Outcome::TimedOut => "FAILED (timed out)",
+        };
+        if matches!(outcome, Outcome::Unsolvable | Outcome::TimedOut) {
+            failures += 1;
+        }
+        println!("{}: {} in {:.2?}", puzzle.name(), status, elapsed);
+    }
+
+    println!(
+        "{} of {} puzzles in '{}' failed",
+        failures,
+        collection.puzzles().len(),
+        collection.name()
+    );
+    if failures > 0 {
+        glib::ExitCode::FAILURE
+    } else {
+        glib::ExitCode::SUCCESS
+    }
+}
+
+/// Solves the puzzle for its default target and gives up after [PUZZLE_TIMEOUT].
+async fn solve_puzzle(puzzle: &PuzzleConfig) -> Outcome {
+    if puzzle.is_unsolvable() {
+        return Outcome::Skipped;
+    }
+
+    let extension = RefCell::new(Some(PuzzleTypeExtension::default_for_puzzle(puzzle)));
+    let mut puzzle_state = PuzzleState::new(puzzle.board_config(), extension.borrow());
+    puzzle_state.tile_constraints = puzzle.tile_constraints().to_vec();
+    let boards = Solver::default().create_boards(&puzzle_state);
+    let tiles: Vec<Tile> = puzzle
+        .tiles()
+        .iter()
+        .map(|tile_config| Tile::new(tile_config.base().clone()))
+        .collect();
+
+    let cancel_token = CancellationToken::new();
+    let (finished_sender, finished_receiver) = mpsc::channel::<()>();
+    thread::spawn({
+        let cancel_token = cancel_token.clone();
+        move || {
+            if finished_receiver.recv_timeout(PUZZLE_TIMEOUT) == Err(RecvTimeoutError::Timeout) {
+                cancel_token.cancel();
+            }
+        }
+    });
+
+    let mut result: Result<Solution, UnsolvableReason> = Err(UnsolvableReason::NoFit);
+    for board in boards.iter() {
+        result = puzzle_solver::solve_all_filling(board, &tiles, cancel_token.clone()).await;
+        if matches!(result, Ok(_) | Err(UnsolvableReason::Cancelled)) {
+            break;
+        }
+    }
+    drop(finished_sender);
+
+    match result {
+        Ok(_) => Outcome::Solved,
+        Err(UnsolvableReason::Cancelled) => Outcome::TimedOut,
+        Err(_) => Outcome::Unsolvable,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use puzzled_common::shape::shape_square;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_collection_path() {
+        assert_eq!(collection_path(&args(&[])), None);
+        assert_eq!(collection_path(&args(&["--verbose"])), None);
+        assert_eq!(
+            collection_path(&args(&["--solve-collection", "collection.json"])),
+            Some(Ok(PathBuf::from("collection.json")))
+        );
+        assert!(matches!(
+            collection_path(&args(&["--solve-collection"])),
+            Some(Err(_))
+        ));
+    }
+
+    #[test]
+    fn test_search_tree_path() {
+        assert_eq!(
+            search_tree_path(&args(&["--solve-collection", "a.json"])),
+            None
+        );
+        assert_eq!(
+            search_tree_path(&args(&["--search-tree-stats", "tree.pzst"])),
+            Some(Ok(PathBuf::from("tree.pzst")))
+        );
+        assert!(matches!(
+            search_tree_path(&args(&["--search-tree-stats"])),
+            Some(Err(_))
+        ));
+    }
+
+    #[test]
+    fn test_board_json() {
+        let layout = shape_square(&[[true, true], [false, true], [true, true]]);
+
+        let json: serde_json::Value = serde_json::from_str(&board_json(&layout)).unwrap();
+
+        assert_eq!(json, json!({ "layout": [[0, 1, 0], [0, 0, 0]] }));
+    }
+}
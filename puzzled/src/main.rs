@@ -23,11 +23,15 @@ extern crate core;
 mod adw_ext;
 mod app;
 mod application;
+mod audio;
 mod config;
 mod global;
+mod headless;
 mod model;
 mod offset;
 mod solver;
+mod sync;
+mod ui_bridge;
 mod window;
 
 use self::application::PuzzledApplication;
@@ -49,6 +53,40 @@ fn main() -> glib::ExitCode {
         .expect("Could not load resources");
     gio::resources_register(&resources);
 
+    // Solve a collection without the window, e.g. to check it in CI
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match headless::collection_path(&args) {
+        Some(Ok(path)) => return headless::solve_collection(&path),
+        Some(Err(usage)) => {
+            eprintln!("{}", usage);
+            return glib::ExitCode::FAILURE;
+        }
+        None => {}
+    }
+
+    // Print statistics about a search tree written by the solver
+    match headless::search_tree_path(&args) {
+        Some(Ok(path)) => return headless::print_search_tree_stats(&path),
+        Some(Err(usage)) => {
+            eprintln!("{}", usage);
+            return glib::ExitCode::FAILURE;
+        }
+        None => {}
+    }
+
+    // Print a draft board detected in a photo of a physical puzzle
+    match headless::board_photo_path(&args) {
+        Some(Ok(path)) => return headless::print_digitized_board(&path),
+        Some(Err(usage)) => {
+            eprintln!("{}", usage);
+            return glib::ExitCode::FAILURE;
+        }
+        None => {}
+    }
+
+    // Use the files and settings of the profile selected in the preferences
+    global::storage::set_profile(model::profiles::load().storage_profile());
+
     // Create a new GtkApplication. The application manages our main loop,
     // application windows, integration with the window manager/compositor, and
     // desktop features such as file opening and single-instance applications.
@@ -1,11 +1,13 @@
 use crate::model::puzzle::PuzzleModel;
 use crate::model::puzzle_meta::PuzzleMeta;
 use crate::model::stars;
+use crate::model::store;
 use crate::model::store::with_puzzle_collection_store;
 use adw::glib;
 use adw::prelude::ObjectExt;
 use adw::subclass::prelude::*;
-use puzzle_config::PuzzleConfigCollection;
+use log::debug;
+use puzzle_config::{CollectionSummary, PuzzleConfigCollection};
 
 const PROGRESS_CHANGED_SIGNAL_NAME: &str = "progress-changed";
 const DELETED_SIGNAL_NAME: &str = "deleted";
@@ -15,12 +17,16 @@ mod imp {
     use crate::model::puzzle::PuzzleModel;
     use adw::glib::subclass::Signal;
     use adw::glib::Properties;
-    use std::cell::OnceCell;
+    use std::cell::{Cell, OnceCell, RefCell};
     use std::sync::OnceLock;
 
     #[derive(Debug, Default, Properties)]
     #[properties(wrapper_type = super::CollectionModel)]
     pub struct PuzzledCollectionModel {
+        pub summary: OnceCell<CollectionSummary>,
+        /// The JSON to load the config from, until it is loaded.
+        pub json: RefCell<Option<String>>,
+        pub core: Cell<bool>,
         pub config: OnceCell<PuzzleConfigCollection>,
         pub puzzles: OnceCell<Vec<PuzzleModel>>,
     }
@@ -66,8 +72,39 @@ impl CollectionModel {
     ///
     /// returns: CollectionModel
     pub fn new(config: PuzzleConfigCollection, puzzle_meta: &PuzzleMeta) -> Self {
+        let obj: CollectionModel = glib::Object::builder().build();
+        obj.imp()
+            .summary
+            .set(CollectionSummary::from(&config))
+            .expect("Failed to set summary for CollectionModel");
+        obj.set_config(config, puzzle_meta);
+        obj
+    }
+
+    /// Creates a new CollectionModel, which only knows the summary of the collection.
+    /// The config and the puzzle models are loaded from the JSON, when they are first accessed
+    /// or [Self::load()] is called.
+    ///
+    /// # Arguments
+    ///
+    /// * `summary`: the summary loaded from the JSON
+    /// * `json`: the JSON describing the whole collection
+    /// * `core`: true, if the collection is a core collection. False otherwise.
+    ///
+    /// returns: CollectionModel
+    pub fn from_summary(summary: CollectionSummary, json: String, core: bool) -> Self {
         let obj: CollectionModel = glib::Object::builder().build();
         let imp = obj.imp();
+        imp.summary
+            .set(summary)
+            .expect("Failed to set summary for CollectionModel");
+        imp.json.replace(Some(json));
+        imp.core.set(core);
+        obj
+    }
+
+    fn set_config(&self, config: PuzzleConfigCollection, puzzle_meta: &PuzzleMeta) {
+        let imp = self.imp();
 
         let puzzle_configs = config.puzzles().clone();
 
@@ -78,10 +115,16 @@ impl CollectionModel {
         let puzzles: Vec<PuzzleModel> = puzzle_configs
             .into_iter()
             .map(|puzzle_config| {
-                let puzzle = PuzzleModel::new(&obj, puzzle_config, puzzle_meta.clone());
+                let puzzle = PuzzleModel::new(self, puzzle_config, puzzle_meta.clone());
 
                 puzzle.connect_progress_improved({
-                    let collection = obj.clone();
+                    let collection = self.clone();
+                    move || {
+                        collection.emit_progress_changed();
+                    }
+                });
+                puzzle.connect_marked_unsolved({
+                    let collection = self.clone();
                     move || {
                         collection.emit_progress_changed();
                     }
@@ -93,17 +136,41 @@ impl CollectionModel {
         imp.puzzles
             .set(puzzles)
             .expect("Failed to set puzzles for CollectionModel");
+    }
 
-        obj
+    /// Loads the config and the puzzle models of the collection, if they are not loaded yet.
+    /// Emits the `progress_changed` signal after loading, as the progress is known from then on.
+    pub fn load(&self) {
+        let Some(json) = self.imp().json.take() else {
+            return;
+        };
+        debug!("Loading collection: {}", self.summary().id());
+        let config = store::load_collection(self.summary(), &json, self.imp().core.get());
+        self.set_config(config, &PuzzleMeta::new());
+        self.emit_progress_changed();
+    }
+
+    /// Returns true, if the config and the puzzle models of the collection are loaded.
+    pub fn is_loaded(&self) -> bool {
+        self.imp().puzzles.get().is_some()
+    }
+
+    /// Returns the metadata of the collection, which is available without loading it.
+    pub fn summary(&self) -> &CollectionSummary {
+        self.imp().summary.get().unwrap()
     }
 
     /// Returns the config defining the puzzles and metadata of the collection.
+    /// The collection is loaded, if it is not loaded yet.
     pub fn config(&self) -> &PuzzleConfigCollection {
+        self.load();
         self.imp().config.get().unwrap()
     }
 
     /// Returns the puzzle models of the collection.
+    /// The collection is loaded, if it is not loaded yet.
     pub fn puzzles(&self) -> &Vec<PuzzleModel> {
+        self.load();
         self.imp().puzzles.get().unwrap()
     }
 
@@ -127,18 +194,53 @@ impl CollectionModel {
         (stars_reached, stars_total)
     }
 
-    /// Marks all puzzles as unsolved and emits the `progress_changed` signal for UIs to
-    /// update.
+    /// Marks all puzzles as unsolved. Each puzzle emits its `marked_unsolved` signal, which
+    /// emits the `progress_changed` signal of this collection.
     pub fn mark_all_as_unsolved(&self) {
         for puzzle in self.puzzles() {
             puzzle.mark_as_unsolved();
         }
-        self.emit_progress_changed();
+    }
+
+    /// Marks all solvable puzzles of this collection as solved on request of the user.
+    /// See [PuzzleModel::mark_as_solved_manually].
+    pub fn mark_puzzles_as_solved_manually(&self) {
+        for puzzle in self
+            .puzzles()
+            .iter()
+            .filter(|p| !p.config().is_unsolvable())
+        {
+            puzzle.mark_as_solved_manually();
+        }
+    }
+
+    /// Removes the stored progress of all puzzles of this collection.
+    /// Other collections are not affected.
+    pub fn reset_progress(&self) {
+        for puzzle in self.puzzles() {
+            puzzle.reset_progress();
+        }
+    }
+
+    /// Loads the progress of all puzzles again.
+    /// Each puzzle emits its `progress_improved` signal, which emits the `progress_changed`
+    /// signal of this collection.
+    ///
+    /// Nothing happens, if the collection is not loaded yet, as it reads the progress when it is
+    /// loaded.
+    pub fn reload_progress(&self) {
+        if !self.is_loaded() {
+            return;
+        }
+        let puzzle_meta = PuzzleMeta::new();
+        for puzzle in self.puzzles() {
+            puzzle.reload_progress(&puzzle_meta);
+        }
     }
 
     /// Connects to the `progress_changes` signal.
     /// This is emitted, when the solved status of a puzzle changes or
-    /// all puzzles are marked as unsolved.
+    /// a puzzle is marked as unsolved.
     ///
     /// # Arguments
     ///
@@ -159,7 +261,7 @@ impl CollectionModel {
     /// Deletes the collection from the collection store and emits the `deleted` signal.
     pub fn delete(&self) {
         with_puzzle_collection_store(|store| {
-            store.remove_community_collection(self.config().id());
+            store.remove_community_collection(self.summary().id());
         });
         self.emit_deleted();
     }
@@ -0,0 +1,121 @@
+use crate::model::collection::CollectionModel;
+use crate::model::extension::PuzzleTypeExtension;
+use crate::model::puzzle::PuzzleModel;
+use crate::model::puzzle_meta::PuzzleMeta;
+use crate::model::store::with_puzzle_collection_store;
+use adw::glib;
+
+/// The puzzle featured as the puzzle of the week.
+#[derive(Debug, Clone)]
+pub struct FeaturedPuzzle {
+    /// The ISO week like `2026-W42`, in which the puzzle is featured.
+    pub week: String,
+    pub puzzle: PuzzleModel,
+}
+
+impl FeaturedPuzzle {
+    /// Returns true, if the puzzle was solved in the week it is featured in.
+    pub fn is_solved(&self) -> bool {
+        PuzzleMeta::new().is_featured_solved(&self.week)
+    }
+}
+
+/// Returns the ISO week of today like `2026-W42`.
+pub fn current_week() -> Option<String> {
+    glib::DateTime::now_local()
+        .and_then(|now| now.format("%G-W%V"))
+        .map(|week| week.to_string())
+        .ok()
+}
+
+/// Returns the puzzle of the current week.
+/// It is picked from the solvable puzzles of the core collections, so every player sees the same
+/// puzzle in the same week. Only the collection of the picked puzzle is loaded.
+pub fn featured_puzzle() -> Option<FeaturedPuzzle> {
+    let week = current_week()?;
+    let (collection, puzzle_index) = with_puzzle_collection_store(|store| {
+        let candidates: Vec<(&CollectionModel, usize)> = store
+            .core_puzzle_collections()
+            .iter()
+            .flat_map(|collection| {
+                collection
+                    .summary()
+                    .puzzles()
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, puzzle)| !puzzle.is_unsolvable())
+                    .map(move |(index, _)| (collection, index))
+            })
+            .collect();
+        featured_index(&week, candidates.len()).map(|index| {
+            let (collection, puzzle_index) = candidates[index];
+            (collection.clone(), puzzle_index)
+        })
+    })?;
+    let puzzle = collection.puzzles().get(puzzle_index)?.clone();
+    Some(FeaturedPuzzle { week, puzzle })
+}
+
+/// Remembers the solved puzzle of the week, if the solved puzzle is the one featured this week
+/// and it was solved for its default target.
+///
+/// # Arguments
+///
+/// * `puzzle`: the solved puzzle
+/// * `extension`: the extension the puzzle was solved with
+///
+/// returns: ()
+pub fn record_solved(puzzle: &PuzzleModel, extension: &Option<PuzzleTypeExtension>) {
+    let Some(featured) = featured_puzzle() else {
+        return;
+    };
+    if featured.puzzle == *puzzle
+        && *extension == Some(PuzzleTypeExtension::default_for_puzzle(puzzle.config()))
+    {
+        PuzzleMeta::new().set_featured_solved(&featured.week);
+    }
+}
+
+/// Returns the index of the puzzle featured in the given week out of the given number of
+/// puzzles.
+///
+/// The index is derived from the week with a fixed hash, so it does not change between versions
+/// or platforms.
+///
+/// returns: Option<usize> `None`, if there are no puzzles.
+fn featured_index(week: &str, count: usize) -> Option<usize> {
+    if count == 0 {
+        return None;
+    }
+    // FNV-1a
+    let hash = week.bytes().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x100000001b3)
+    });
+    Some((hash % count as u64) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_featured_index() {
+        assert_eq!(featured_index("2026-W42", 0), None);
+        assert_eq!(featured_index("2026-W42", 1), Some(0));
+        // The index must not change between versions, so all players see the same puzzle.
+        assert_eq!(featured_index("2026-W42", 100), Some(29));
+        assert_eq!(featured_index("2026-W43", 37), Some(30));
+        for week in 1..=53 {
+            let index = featured_index(&format!("2026-W{:02}", week), 37).unwrap();
+            assert!(index < 37);
+        }
+    }
+
+    #[test]
+    fn test_featured_index_rotates() {
+        let indices: Vec<usize> = (1..=10)
+            .map(|week| featured_index(&format!("2026-W{:02}", week), 37).unwrap())
+            .collect();
+        assert!(indices.windows(2).any(|pair| pair[0] != pair[1]));
+    }
+}
@@ -0,0 +1,77 @@
+use std::time::{Duration, Instant};
+
+/// Decides when the coach offers a hint, because the player made no progress for a while.
+///
+/// A hint is offered once, after no progress was made for the delay. The next hint is only
+/// offered after the player made progress again and then got stuck for another full delay, so
+/// the player is not nagged while thinking about a single move.
+#[derive(Debug, Clone, Copy)]
+pub struct InactivityTracker {
+    /// When the player made progress last.
+    last_progress: Instant,
+    /// Whether a hint was offered since the last progress.
+    offered: bool,
+}
+
+impl Default for InactivityTracker {
+    fn default() -> Self {
+        InactivityTracker {
+            last_progress: Instant::now(),
+            offered: false,
+        }
+    }
+}
+
+impl InactivityTracker {
+    /// Records, that the player made progress now, e.g. by moving a tile.
+    pub fn record_progress(&mut self) {
+        self.record_progress_at(Instant::now());
+    }
+
+    /// Returns true, if a hint should be offered now. A hint is only offered once per period of
+    /// inactivity.
+    ///
+    /// # Arguments
+    ///
+    /// * `delay`: how long the player has to be inactive, before a hint is offered
+    ///
+    /// returns: bool
+    pub fn should_offer(&mut self, delay: Duration) -> bool {
+        self.should_offer_at(Instant::now(), delay)
+    }
+
+    fn record_progress_at(&mut self, now: Instant) {
+        self.last_progress = now;
+        self.offered = false;
+    }
+
+    fn should_offer_at(&mut self, now: Instant, delay: Duration) -> bool {
+        if self.offered || now.saturating_duration_since(self.last_progress) < delay {
+            return false;
+        }
+        self.offered = true;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inactivity_tracker() {
+        let start = Instant::now();
+        let at = |seconds| start + Duration::from_secs(seconds);
+        let delay = Duration::from_secs(60);
+        let mut tracker = InactivityTracker::default();
+        tracker.record_progress_at(at(0));
+
+        assert!(!tracker.should_offer_at(at(59), delay));
+        assert!(tracker.should_offer_at(at(60), delay));
+        assert!(!tracker.should_offer_at(at(200), delay));
+
+        tracker.record_progress_at(at(210));
+        assert!(!tracker.should_offer_at(at(260), delay));
+        assert!(tracker.should_offer_at(at(270), delay));
+    }
+}
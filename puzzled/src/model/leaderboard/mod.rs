@@ -0,0 +1,129 @@
+pub mod token;
+
+use crate::global::settings::{PlayerName, Preferences};
+use crate::global::storage;
+use crate::model::extension::PuzzleTypeExtension;
+use crate::model::leaderboard::token::{ResultToken, TokenError};
+use crate::model::puzzle::PuzzleModel;
+use crate::model::puzzle_meta;
+use crate::model::store::file;
+use adw::glib;
+use log::{error, warn};
+use std::io;
+use std::time::Duration;
+
+/// The collection, whose puzzles are daily challenges, when they are solved for today's date.
+const DAILY_CHALLENGE_COLLECTION_ID: &str = "de.til7701.Puzzled.PuzzleADay";
+/// The file in the data directory, which contains the tokens of the own and imported results.
+/// It contains one token per line.
+const LEADERBOARD_FILE_NAME: &str = "leaderboard.txt";
+
+/// Returns true, if the puzzle is solved as a daily challenge with the given extension.
+/// This is the case for the puzzles of the Puzzle a Day collection with their default target,
+/// which is today's date.
+pub fn is_daily_challenge(puzzle: &PuzzleModel, extension: &Option<PuzzleTypeExtension>) -> bool {
+    puzzle.collection().config().id() == DAILY_CHALLENGE_COLLECTION_ID
+        && *extension == Some(PuzzleTypeExtension::default_for_puzzle(puzzle.config()))
+}
+
+/// Creates the result of a solved daily challenge for today.
+///
+/// # Arguments
+///
+/// * `puzzle`: the solved puzzle
+/// * `extension`: the extension the puzzle was solved with
+/// * `time`: how long the player needed to solve the puzzle
+/// * `hints`: the number of hints used
+///
+/// returns: Option<ResultToken> `None`, if the puzzle has no key for the extension.
+pub fn create_result(
+    puzzle: &PuzzleModel,
+    extension: &Option<PuzzleTypeExtension>,
+    time: Duration,
+    hints: u32,
+) -> Option<ResultToken> {
+    let collection = puzzle.collection().config();
+    let puzzle_key = puzzle_meta::puzzle_key(puzzle.config(), extension)?;
+    let date = glib::DateTime::now_local()
+        .and_then(|now| now.format("%Y-%m-%d"))
+        .ok()?;
+    Some(ResultToken {
+        date: date.to_string(),
+        puzzle: puzzle.config().name().to_string(),
+        puzzle_hash: token::puzzle_hash(collection.id(), &puzzle_key),
+        seconds: time.as_secs() as u32,
+        hints,
+        player: player_name(),
+    })
+}
+
+/// Returns the name of the player as set in the preferences or the real name of the user.
+pub fn player_name() -> String {
+    let name = Preferences::default().get(PlayerName);
+    if !name.trim().is_empty() {
+        return name.trim().to_string();
+    }
+    let real_name = glib::real_name().to_string_lossy().to_string();
+    if real_name.is_empty() || real_name == "Unknown" {
+        glib::user_name().to_string_lossy().to_string()
+    } else {
+        real_name
+    }
+}
+
+/// Returns the own and imported results.
+/// Lines of the leaderboard file, which are no valid tokens, are skipped.
+pub fn results() -> Vec<ResultToken> {
+    let path = storage::data_dir().join(LEADERBOARD_FILE_NAME);
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Vec::new(),
+        Err(e) => {
+            error!("Failed to read the leaderboard: {}", e);
+            return Vec::new();
+        }
+    };
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match ResultToken::decode(line) {
+            Ok(result) => Some(result),
+            Err(e) => {
+                warn!("Skipping invalid result token in the leaderboard: {:?}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Adds the result to the leaderboard file, if it is not in there already.
+pub fn add_result(result: &ResultToken) -> io::Result<()> {
+    let mut results = results();
+    if results.contains(result) {
+        return Ok(());
+    }
+    results.push(result.clone());
+
+    let path = storage::data_dir().join(LEADERBOARD_FILE_NAME);
+    std::fs::create_dir_all(storage::data_dir())?;
+    let content: Vec<String> = results.iter().map(ResultToken::encode).collect();
+    file::write_atomically(&path, &content.join("\n"))
+}
+
+/// Verifies the token of a friend's result and adds it to the leaderboard.
+///
+/// # Arguments
+///
+/// * `token`: the shared result token
+///
+/// returns: Result<ResultToken, TokenError>
+pub fn import_token(token: &str) -> Result<ResultToken, TokenError> {
+    let result = ResultToken::decode(token)?;
+    if let Err(e) = add_result(&result) {
+        error!(
+            "Failed to add the imported result to the leaderboard: {}",
+            e
+        );
+    }
+    Ok(result)
+}
@@ -0,0 +1,249 @@
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use time::{Date, Month};
+
+/// The prefix of result tokens in the current format.
+const TOKEN_PREFIX: &str = "PZD1";
+/// The number of hex digits of the SHA-256 hash used to identify a puzzle.
+const PUZZLE_HASH_LENGTH: usize = 16;
+
+/// An error while reading a result token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenError {
+    /// The text is not a result token or its content is not valid.
+    Malformed,
+    /// The token was created by a newer version of Puzzled.
+    UnsupportedVersion,
+    /// The checksum does not match the content, so the token has been changed.
+    InvalidChecksum,
+}
+
+/// The result of a daily challenge, which can be shared with friends as a token.
+///
+/// Tokens carry a checksum, which detects accidental changes like incomplete copies. It does not
+/// stop someone from forging a result, since anyone can compute the checksum.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultToken {
+    /// The day of the challenge in the format `YYYY-MM-DD`.
+    pub date: String,
+    /// The name of the puzzle for display.
+    pub puzzle: String,
+    /// Identifies the puzzle and target, see [puzzle_hash].
+    pub puzzle_hash: String,
+    /// The time the player needed to solve the puzzle in seconds.
+    pub seconds: u32,
+    /// The number of hints the player used.
+    pub hints: u32,
+    /// The name of the player.
+    pub player: String,
+}
+
+impl ResultToken {
+    /// Encodes the result as a token, which can be shared as text.
+    ///
+    /// The token consists of a prefix for the format, the result as Base64 encoded JSON and
+    /// its checksum separated by dots.
+    pub fn encode(&self) -> String {
+        let payload = serde_json::to_string(self).expect("Failed to serialize result token");
+        let content = format!("{}.{}", TOKEN_PREFIX, URL_SAFE_NO_PAD.encode(payload));
+        let checksum = URL_SAFE_NO_PAD.encode(Sha256::digest(&content));
+        format!("{}.{}", content, checksum)
+    }
+
+    /// Decodes and verifies a token created with [Self::encode].
+    ///
+    /// # Arguments
+    ///
+    /// * `token`: the token text, surrounding whitespace is ignored
+    ///
+    /// returns: Result<ResultToken, TokenError>
+    pub fn decode(token: &str) -> Result<ResultToken, TokenError> {
+        let token = token.trim();
+        let (content, checksum) = token.rsplit_once('.').ok_or(TokenError::Malformed)?;
+        let (prefix, payload) = content.split_once('.').ok_or(TokenError::Malformed)?;
+        if prefix != TOKEN_PREFIX {
+            return if prefix.starts_with("PZD") {
+                Err(TokenError::UnsupportedVersion)
+            } else {
+                Err(TokenError::Malformed)
+            };
+        }
+
+        let checksum = URL_SAFE_NO_PAD
+            .decode(checksum)
+            .map_err(|_| TokenError::Malformed)?;
+        if checksum[..] != Sha256::digest(content)[..] {
+            return Err(TokenError::InvalidChecksum);
+        }
+
+        let payload = URL_SAFE_NO_PAD
+            .decode(payload)
+            .map_err(|_| TokenError::Malformed)?;
+        let result: ResultToken =
+            serde_json::from_slice(&payload).map_err(|_| TokenError::Malformed)?;
+        if parse_date(&result.date).is_none() {
+            return Err(TokenError::Malformed);
+        }
+        Ok(result)
+    }
+}
+
+/// Returns the hash identifying a puzzle and target of a daily challenge.
+/// Results are only compared, if they have the same date and puzzle hash.
+///
+/// # Arguments
+///
+/// * `collection_id`: the id of the collection of the puzzle
+/// * `puzzle_key`: identifies the puzzle and the target inside the collection
+///
+/// returns: String
+pub fn puzzle_hash(collection_id: &str, puzzle_key: &str) -> String {
+    let hash = format!(
+        "{:x}",
+        Sha256::digest(format!("{}/{}", collection_id, puzzle_key))
+    );
+    hash[..PUZZLE_HASH_LENGTH].to_string()
+}
+
+/// The results of a single daily challenge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Challenge {
+    pub date: String,
+    pub puzzle: String,
+    /// The best result of each player, the best result first.
+    pub results: Vec<ResultToken>,
+}
+
+/// Groups the results by challenge, so they can be compared in a table.
+///
+/// Only the best result of each player is kept for each challenge. Results are ranked by the
+/// number of hints and then by the time needed. The newest challenges come first.
+///
+/// # Arguments
+///
+/// * `results`: the results of the player and their friends
+///
+/// returns: Vec<Challenge>
+pub fn challenges(results: &[ResultToken]) -> Vec<Challenge> {
+    let mut challenges: BTreeMap<(String, String), Challenge> = BTreeMap::new();
+    for result in results {
+        let challenge = challenges
+            .entry((result.date.clone(), result.puzzle_hash.clone()))
+            .or_insert_with(|| Challenge {
+                date: result.date.clone(),
+                puzzle: result.puzzle.clone(),
+                results: Vec::new(),
+            });
+        match challenge
+            .results
+            .iter_mut()
+            .find(|existing| existing.player == result.player)
+        {
+            Some(existing) if rank_key(result) < rank_key(existing) => *existing = result.clone(),
+            Some(_) => {}
+            None => challenge.results.push(result.clone()),
+        }
+    }
+    let mut challenges: Vec<Challenge> = challenges.into_values().collect();
+    for challenge in &mut challenges {
+        challenge.results.sort_by_key(rank_key);
+    }
+    challenges.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| a.puzzle.cmp(&b.puzzle)));
+    challenges
+}
+
+fn rank_key(result: &ResultToken) -> (u32, u32, String) {
+    (result.hints, result.seconds, result.player.clone())
+}
+
+/// Parses a date in the format `YYYY-MM-DD`.
+fn parse_date(date: &str) -> Option<Date> {
+    let mut parts = date.splitn(3, '-');
+    let year = parts.next().filter(|part| part.len() == 4)?.parse().ok()?;
+    let month = parts
+        .next()
+        .filter(|part| part.len() == 2)?
+        .parse::<u8>()
+        .ok()?;
+    let day = parts.next().filter(|part| part.len() == 2)?.parse().ok()?;
+    Date::from_calendar_date(year, Month::try_from(month).ok()?, day).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(player: &str, date: &str, seconds: u32, hints: u32) -> ResultToken {
+        ResultToken {
+            date: date.to_string(),
+            puzzle: "Month".to_string(),
+            puzzle_hash: puzzle_hash("de.til7701.Puzzled.PuzzleADay", "m/x2-3"),
+            seconds,
+            hints,
+            player: player.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_token_round_trip() {
+        let result = result("Alice", "2026-10-16", 95, 1);
+        let token = result.encode();
+        assert!(token.starts_with("PZD1."));
+        assert_eq!(ResultToken::decode(&format!(" {}\n", token)), Ok(result));
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        let token = result("Alice", "2026-10-16", 95, 1).encode();
+        let (content, _) = token.rsplit_once('.').unwrap();
+        let other = result("Alice", "2026-10-16", 5, 0).encode();
+        let (_, other_checksum) = other.rsplit_once('.').unwrap();
+
+        assert_eq!(
+            ResultToken::decode(&format!("{}.{}", content, other_checksum)),
+            Err(TokenError::InvalidChecksum)
+        );
+        assert_eq!(
+            ResultToken::decode(&token.replacen("PZD1", "PZD2", 1)),
+            Err(TokenError::UnsupportedVersion)
+        );
+        assert_eq!(ResultToken::decode("hello"), Err(TokenError::Malformed));
+        assert_eq!(
+            ResultToken::decode(&result("Bob", "yesterday", 1, 0).encode()),
+            Err(TokenError::Malformed)
+        );
+        assert_eq!(
+            ResultToken::decode(&result("Bob", "2026-02-30", 1, 0).encode()),
+            Err(TokenError::Malformed)
+        );
+    }
+
+    #[test]
+    fn test_challenges() {
+        let other_day = result("Alice", "2026-10-15", 50, 0);
+        let results = vec![
+            result("Alice", "2026-10-16", 95, 1),
+            result("Bob", "2026-10-16", 120, 0),
+            result("Carol", "2026-10-16", 60, 1),
+            result("Alice", "2026-10-16", 80, 1),
+            other_day.clone(),
+        ];
+
+        let challenges = challenges(&results);
+
+        assert_eq!(challenges.len(), 2);
+        assert_eq!(challenges[0].date, "2026-10-16");
+        assert_eq!(
+            challenges[0].results,
+            vec![
+                result("Bob", "2026-10-16", 120, 0),
+                result("Carol", "2026-10-16", 60, 1),
+                result("Alice", "2026-10-16", 80, 1),
+            ]
+        );
+        assert_eq!(challenges[1].results, vec![other_day]);
+    }
+}
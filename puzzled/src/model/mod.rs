@@ -1,7 +1,14 @@
 pub mod collection;
 pub mod extension;
+pub mod featured;
+pub mod inactivity;
+pub mod leaderboard;
 pub mod placement;
+pub mod play_history;
+pub mod profiles;
 pub mod puzzle;
 pub mod puzzle_meta;
+pub mod release_notes;
+pub mod solve_timer;
 pub mod stars;
 pub mod store;
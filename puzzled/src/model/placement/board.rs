@@ -34,6 +34,10 @@ impl PlacedBoard {
         self.position_pixel
     }
 
+    pub fn set_cell_size(&mut self, cell_size: CellOffset) {
+        self.cell_size = cell_size;
+    }
+
     pub fn set_position_cells(&mut self, position_cells: CellOffset) {
         self.position_cells = position_cells;
     }
@@ -0,0 +1,278 @@
+use crate::app::puzzle::puzzle_area::puzzle_state::PuzzleState;
+use crate::offset::{CellOffset, PixelOffset};
+
+/// Snaps a position in pixels to the nearest cell of the grid.
+///
+/// # Arguments
+///
+/// * `position`: the position in pixels
+/// * `cell_size_pixel`: the size of a cell in pixels
+///
+/// returns: CellOffset
+pub fn pixels_to_cells(position: PixelOffset, cell_size_pixel: u32) -> CellOffset {
+    position
+        .div_scalar(cell_size_pixel.max(1) as f64)
+        .round()
+        .into()
+}
+
+/// Converts a position in cells to the position of the top left corner of the cell in pixels.
+///
+/// # Arguments
+///
+/// * `position`: the position in cells
+/// * `cell_size_pixel`: the size of a cell in pixels
+///
+/// returns: PixelOffset
+pub fn cells_to_pixels(position: CellOffset, cell_size_pixel: u32) -> PixelOffset {
+    position.mul_scalar(cell_size_pixel as f64).into()
+}
+
+/// Converts a position in the grid to the corresponding position in the puzzle state.
+///
+/// # Arguments
+///
+/// * `position`: the position in the grid
+/// * `board_position`: the position of the board in the grid
+///
+/// returns: CellOffset
+pub fn grid_to_puzzle_state(position: CellOffset, board_position: CellOffset) -> CellOffset {
+    PuzzleState::board_to_state(position - board_position)
+}
+
+/// Whether the position lies within an area of the given dimensions starting at (0, 0).
+///
+/// # Arguments
+///
+/// * `position`: the position to check
+/// * `dim`: the width and height of the area
+///
+/// returns: bool
+pub fn is_within(position: CellOffset, dim: (usize, usize)) -> bool {
+    position.0 >= 0
+        && position.1 >= 0
+        && (position.0 as usize) < dim.0
+        && (position.1 as usize) < dim.1
+}
+
+/// Calculates the largest cell size in pixels, so the required number of cells fits into the
+/// available area.
+///
+/// The result is at least 1, so positions can always be converted.
+///
+/// # Arguments
+///
+/// * `available`: the size of the area in pixels
+/// * `required_cells`: the number of cells that need to fit horizontally and vertically
+///
+/// returns: u32
+pub fn cell_size_pixel(available: PixelOffset, required_cells: CellOffset) -> u32 {
+    let cell_width_pixel = (available.0 / required_cells.0.max(1) as f64).floor() as u32;
+    let cell_height_pixel = (available.1 / required_cells.1.max(1) as f64).floor() as u32;
+    cell_width_pixel.min(cell_height_pixel).max(1)
+}
+
+/// Calculates how many whole cells fit into the available area.
+///
+/// # Arguments
+///
+/// * `available`: the size of the area in pixels
+/// * `cell_size_pixel`: the size of a cell in pixels
+///
+/// returns: CellOffset
+pub fn grid_cells(available: PixelOffset, cell_size_pixel: u32) -> CellOffset {
+    let cell_size_pixel = cell_size_pixel.max(1) as f64;
+    CellOffset(
+        (available.0 / cell_size_pixel).floor() as i32,
+        (available.1 / cell_size_pixel).floor() as i32,
+    )
+}
+
+/// Calculates the horizontal offset of the board in cells, so it is centered in the grid.
+/// If the board cannot be centered exactly, it is moved one cell to the left.
+///
+/// # Arguments
+///
+/// * `grid_width_cells`: the width of the grid in cells
+/// * `board_width_cells`: the width of the board in cells
+///
+/// returns: i32
+pub fn centered_board_offset(grid_width_cells: i32, board_width_cells: i32) -> i32 {
+    (grid_width_cells - board_width_cells) / 2
+}
+
+/// Clamps the position of an element in pixels, so it stays completely inside the area.
+/// If the element is larger than the area, it is placed at the top left corner.
+///
+/// # Arguments
+///
+/// * `position`: the position of the top left corner of the element
+/// * `element_size`: the size of the element
+/// * `area_size`: the size of the area
+///
+/// returns: PixelOffset
+pub fn clamp_to_area(
+    position: PixelOffset,
+    element_size: PixelOffset,
+    area_size: PixelOffset,
+) -> PixelOffset {
+    let max_x = (area_size.0 - element_size.0).max(0.0);
+    let max_y = (area_size.1 - element_size.1).max(0.0);
+    PixelOffset(position.0.clamp(0.0, max_x), position.1.clamp(0.0, max_y))
+}
+
+/// Calculates the position of an element relative to the board, after the board is rotated
+/// clockwise by 90 degrees.
+///
+/// # Arguments
+///
+/// * `position_on_board`: the position of the element relative to the board
+/// * `element_size`: the size of the element in cells before the rotation
+/// * `board_height`: the height of the board in cells before the rotation
+///
+/// returns: CellOffset
+pub fn rotate_position_on_board_clockwise(
+    position_on_board: CellOffset,
+    element_size: CellOffset,
+    board_height: i32,
+) -> CellOffset {
+    CellOffset(
+        board_height - position_on_board.1 - element_size.1,
+        position_on_board.0,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pixels_to_cells_snaps_to_nearest_cell() {
+        assert_eq!(pixels_to_cells(PixelOffset(0.0, 0.0), 10), CellOffset(0, 0));
+        assert_eq!(pixels_to_cells(PixelOffset(4.9, 5.0), 10), CellOffset(0, 1));
+        assert_eq!(
+            pixels_to_cells(PixelOffset(14.9, 15.1), 10),
+            CellOffset(1, 2)
+        );
+        assert_eq!(
+            pixels_to_cells(PixelOffset(-4.9, -5.1), 10),
+            CellOffset(0, -1)
+        );
+        assert_eq!(pixels_to_cells(PixelOffset(3.0, 4.0), 0), CellOffset(3, 4));
+    }
+
+    #[test]
+    fn test_cells_and_pixels_round_trip() {
+        for cell_size in [1, 7, 10, 33] {
+            for x in -3..10 {
+                for y in -3..10 {
+                    let cells = CellOffset(x, y);
+                    let pixels = cells_to_pixels(cells, cell_size);
+                    assert_eq!(pixels_to_cells(pixels, cell_size), cells);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_grid_and_puzzle_state_positions() {
+        let board_position = CellOffset(6, 1);
+
+        assert_eq!(
+            grid_to_puzzle_state(board_position, board_position),
+            CellOffset(1, 1)
+        );
+        assert_eq!(
+            grid_to_puzzle_state(CellOffset(5, 0), board_position),
+            CellOffset(0, 0)
+        );
+        assert_eq!(
+            grid_to_puzzle_state(CellOffset(4, 3), board_position),
+            CellOffset(-1, 3)
+        );
+    }
+
+    #[test]
+    fn test_is_within_edges() {
+        let dim = (3, 2);
+        let mut inside = Vec::new();
+        for x in -1..=3 {
+            for y in -1..=2 {
+                if is_within(CellOffset(x, y), dim) {
+                    inside.push((x, y));
+                }
+            }
+        }
+
+        assert_eq!(inside, vec![(0, 0), (0, 1), (1, 0), (1, 1), (2, 0), (2, 1)]);
+        assert!(!is_within(CellOffset(0, 0), (0, 0)));
+    }
+
+    #[test]
+    fn test_cell_size_pixel() {
+        assert_eq!(
+            cell_size_pixel(PixelOffset(200.0, 100.0), CellOffset(20, 20)),
+            5
+        );
+        assert_eq!(
+            cell_size_pixel(PixelOffset(199.0, 300.0), CellOffset(20, 10)),
+            9
+        );
+        assert_eq!(
+            cell_size_pixel(PixelOffset(10.0, 10.0), CellOffset(20, 20)),
+            1
+        );
+        assert_eq!(
+            cell_size_pixel(PixelOffset(10.0, 10.0), CellOffset(0, 0)),
+            10
+        );
+    }
+
+    #[test]
+    fn test_grid_cells() {
+        assert_eq!(grid_cells(PixelOffset(105.0, 99.9), 10), CellOffset(10, 9));
+        assert_eq!(grid_cells(PixelOffset(5.0, 5.0), 0), CellOffset(5, 5));
+    }
+
+    #[test]
+    fn test_centered_board_offset() {
+        assert_eq!(centered_board_offset(20, 8), 6);
+        assert_eq!(centered_board_offset(21, 8), 6);
+        assert_eq!(centered_board_offset(8, 8), 0);
+        assert_eq!(centered_board_offset(7, 8), 0);
+    }
+
+    #[test]
+    fn test_clamp_to_area() {
+        let size = PixelOffset(20.0, 10.0);
+        let area = PixelOffset(100.0, 50.0);
+
+        let inside = clamp_to_area(PixelOffset(30.0, 20.0), size, area);
+        let before = clamp_to_area(PixelOffset(-5.0, -1.0), size, area);
+        let after = clamp_to_area(PixelOffset(90.0, 45.0), size, area);
+        let too_large = clamp_to_area(PixelOffset(5.0, 5.0), PixelOffset(200.0, 60.0), area);
+
+        assert_eq!((inside.0, inside.1), (30.0, 20.0));
+        assert_eq!((before.0, before.1), (0.0, 0.0));
+        assert_eq!((after.0, after.1), (80.0, 40.0));
+        assert_eq!((too_large.0, too_large.1), (0.0, 0.0));
+    }
+
+    #[test]
+    fn test_rotate_position_on_board_clockwise() {
+        // A 1x2 tile in the top left corner of a 4x3 board ends up in the top right corner.
+        let rotated = rotate_position_on_board_clockwise(CellOffset(0, 0), CellOffset(1, 2), 3);
+        assert_eq!(rotated, CellOffset(1, 0));
+
+        // Rotating four times with the rotated sizes returns to the original position.
+        let mut position = CellOffset(2, 1);
+        let mut size = CellOffset(2, 1);
+        let mut board = (4, 3);
+        for _ in 0..4 {
+            position = rotate_position_on_board_clockwise(position, size, board.1);
+            size = CellOffset(size.1, size.0);
+            board = (board.1, board.0);
+        }
+        assert_eq!(position, CellOffset(2, 1));
+    }
+}
@@ -1,11 +1,14 @@
-use crate::model::placement::PlacementModel;
-use crate::offset::CellOffset;
+use crate::model::placement::{geometry, PlacementModel};
+use crate::offset::{CellOffset, PixelOffset};
 use adw::subclass::prelude::ObjectSubclassIsExt;
 use puzzle_config::PuzzleConfig;
 
 pub(super) const MIN_CELLS_TO_THE_TOP_OF_BOARD: i32 = 1;
 pub(super) const MIN_CELLS_TO_THE_SIDES_OF_BOARD: i32 = 6;
 pub(super) const MIN_CELLS_TO_THE_BOTTOM_OF_BOARD: i32 = 6;
+/// Used instead of [MIN_CELLS_TO_THE_SIDES_OF_BOARD] and [MIN_CELLS_TO_THE_BOTTOM_OF_BOARD] on
+/// screens, which are too small for them. See [PlacementModel::set_compact_margins].
+const COMPACT_CELLS_AROUND_BOARD: i32 = 2;
 
 /// Configuration for the puzzle grid layout.
 #[derive(Debug, Eq, PartialEq)]
@@ -25,7 +28,71 @@ impl Default for GridConfig {
     }
 }
 
+/// The values the layout of the puzzle area was computed from, which are shown in developer mode
+/// to make layout bugs reproducible with a screenshot.
+#[derive(Debug, Clone, Copy)]
+pub struct LayoutDiagnostics {
+    /// The size available for the puzzle area.
+    pub area_pixel_size: PixelOffset,
+    /// The size the window needs at least to show the puzzle area.
+    pub min_area_pixel_size: PixelOffset,
+    pub cell_size_pixel: u32,
+    pub grid_cells: CellOffset,
+    pub min_grid_cells: CellOffset,
+    pub board_offset_cells: CellOffset,
+    pub board_size_cells: CellOffset,
+    /// The cells needed to show all tiles at their current positions.
+    pub tiles_required_cells: CellOffset,
+}
+
+impl LayoutDiagnostics {
+    /// Formats the values as lines of text.
+    pub fn lines(&self) -> Vec<String> {
+        vec![
+            format!(
+                "area: {:.0} x {:.0} px (min {:.0} x {:.0} px)",
+                self.area_pixel_size.0,
+                self.area_pixel_size.1,
+                self.min_area_pixel_size.0,
+                self.min_area_pixel_size.1
+            ),
+            format!("cell size: {} px", self.cell_size_pixel),
+            format!(
+                "grid: {} x {} cells (min {} x {} cells)",
+                self.grid_cells.0, self.grid_cells.1, self.min_grid_cells.0, self.min_grid_cells.1
+            ),
+            format!(
+                "board: {} x {} cells at ({}, {})",
+                self.board_size_cells.0,
+                self.board_size_cells.1,
+                self.board_offset_cells.0,
+                self.board_offset_cells.1
+            ),
+            format!(
+                "tiles require: {} x {} cells",
+                self.tiles_required_cells.0, self.tiles_required_cells.1
+            ),
+        ]
+    }
+}
+
 impl PlacementModel {
+    /// Returns the values the current layout was computed from.
+    pub fn layout_diagnostics(&self) -> LayoutDiagnostics {
+        let grid_config = self.imp().grid_config.borrow();
+        let board = self.imp().board.borrow();
+        LayoutDiagnostics {
+            area_pixel_size: self.imp().area_pixel_size.get(),
+            min_area_pixel_size: self.imp().min_area_pixel_size.get(),
+            cell_size_pixel: grid_config.cell_size_pixel,
+            grid_cells: grid_config.grid_cells,
+            min_grid_cells: grid_config.min_grid_cells,
+            board_offset_cells: board.position_cells(),
+            board_size_cells: board.cell_size(),
+            tiles_required_cells: self.tiles_required_cells(),
+        }
+    }
+
     pub fn initial_grid_config(puzzle_config: &PuzzleConfig) -> GridConfig {
         let board_cell_width = puzzle_config.board_config().layout().dim().0 as i32;
         let board_cell_height = puzzle_config.board_config().layout().dim().1 as i32;
@@ -48,34 +115,21 @@ impl PlacementModel {
     /// new calculations.
     pub(super) fn update_grid_layout(&self) {
         let area_pixel_size = self.imp().area_pixel_size.get();
-        let available_width_pixel = area_pixel_size.0;
-        let available_height_pixel = area_pixel_size.1;
 
         let board_size_cells = self.imp().board.borrow().cell_size();
-        let board_size_cells_with_margin = board_size_cells.add_tuple((
-            MIN_CELLS_TO_THE_SIDES_OF_BOARD * 2,
-            MIN_CELLS_TO_THE_TOP_OF_BOARD + MIN_CELLS_TO_THE_BOTTOM_OF_BOARD,
-        ));
-        let tiles_required_cells = self.tiles_required_cells();
-        let required_cells = board_size_cells_with_margin.max(tiles_required_cells);
-
-        let cell_width_pixel = (available_width_pixel / required_cells.0 as f64).floor() as u32;
-        let cell_height_pixel = (available_height_pixel / required_cells.1 as f64).floor() as u32;
-        let cell_size_pixel = cell_width_pixel.min(cell_height_pixel);
-
-        let grid_h_cell_count = (available_width_pixel / cell_size_pixel as f64).floor() as i32;
-        let min_grid_h_cell_count = required_cells.0;
-        let grid_v_cell_count = (available_height_pixel / cell_size_pixel as f64).floor() as i32;
-        let min_grid_v_cell_count = required_cells.1;
+        let required_cells = self.required_cells(self.imp().compact_margins.get());
+
+        let cell_size_pixel = geometry::cell_size_pixel(area_pixel_size, required_cells);
+        let grid_cells = geometry::grid_cells(area_pixel_size, cell_size_pixel);
 
         let board_offset_cells = CellOffset(
-            (grid_h_cell_count - board_size_cells.0) / 2,
+            geometry::centered_board_offset(grid_cells.0, board_size_cells.0),
             MIN_CELLS_TO_THE_TOP_OF_BOARD,
         );
 
         let grid_config = GridConfig {
-            grid_cells: CellOffset(grid_h_cell_count, grid_v_cell_count),
-            min_grid_cells: CellOffset(min_grid_h_cell_count, min_grid_v_cell_count),
+            grid_cells,
+            min_grid_cells: required_cells,
             cell_size_pixel,
         };
         let old_grid_config = self.imp().grid_config.borrow();
@@ -86,6 +140,47 @@ impl PlacementModel {
         }
     }
 
+    /// Sets whether fewer empty cells are kept around the board for the tiles, so the puzzle fits
+    /// on small screens. It takes effect with the next layout update.
+    pub fn set_compact_margins(&self, compact: bool) {
+        self.imp().compact_margins.set(compact);
+    }
+
+    /// Returns the size the view needs at least to show the board and all tiles with the given
+    /// margins around the board, like [Self::min_area_size].
+    ///
+    /// # Arguments
+    ///
+    /// * `compact_margins`: whether to use the margins for small screens
+    /// * `min_cell_size_pixel`: the min cell size
+    ///
+    /// returns: PixelOffset
+    pub fn required_area_size(
+        &self,
+        compact_margins: bool,
+        min_cell_size_pixel: u32,
+    ) -> PixelOffset {
+        self.required_cells(compact_margins)
+            .mul_scalar(min_cell_size_pixel as f64)
+            .into()
+    }
+
+    /// Returns the cells needed to show the board with its margins and all tiles.
+    fn required_cells(&self, compact_margins: bool) -> CellOffset {
+        let (side_cells, bottom_cells) = if compact_margins {
+            (COMPACT_CELLS_AROUND_BOARD, COMPACT_CELLS_AROUND_BOARD)
+        } else {
+            (
+                MIN_CELLS_TO_THE_SIDES_OF_BOARD,
+                MIN_CELLS_TO_THE_BOTTOM_OF_BOARD,
+            )
+        };
+        let board_size_cells = self.imp().board.borrow().cell_size();
+        let board_size_cells_with_margin = board_size_cells
+            .add_tuple((side_cells * 2, MIN_CELLS_TO_THE_TOP_OF_BOARD + bottom_cells));
+        board_size_cells_with_margin.max(self.tiles_required_cells())
+    }
+
     /// Calculates the dimensions required to fit all tiles in their current positions.
     fn tiles_required_cells(&self) -> CellOffset {
         let tiles = self.imp().tiles.borrow();
@@ -143,3 +238,33 @@ impl PlacementModel {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_diagnostics_lines() {
+        let diagnostics = LayoutDiagnostics {
+            area_pixel_size: PixelOffset(800.0, 600.0),
+            min_area_pixel_size: PixelOffset(340.0, 260.0),
+            cell_size_pixel: 40,
+            grid_cells: CellOffset(20, 15),
+            min_grid_cells: CellOffset(17, 13),
+            board_offset_cells: CellOffset(7, 1),
+            board_size_cells: CellOffset(5, 6),
+            tiles_required_cells: CellOffset(12, 9),
+        };
+
+        assert_eq!(
+            diagnostics.lines(),
+            vec![
+                "area: 800 x 600 px (min 340 x 260 px)",
+                "cell size: 40 px",
+                "grid: 20 x 15 cells (min 17 x 13 cells)",
+                "board: 5 x 6 cells at (7, 1)",
+                "tiles require: 12 x 9 cells",
+            ]
+        );
+    }
+}
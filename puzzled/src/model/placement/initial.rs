@@ -1,92 +1,159 @@
 use crate::offset::CellOffset;
-use puzzle_config::{PuzzleConfig, TileConfig};
+use puzzle_config::TileConfig;
 
+/// The empty cells kept between the tiles and the board or the edges of the grid.
+const GAP: i32 = 1;
+
+/// Calculates the positions of the tiles at the start of a puzzle.
+///
+/// The tiles are packed in their order into shelves, which are rows as high as their highest
+/// tile. Shelves beside the board are split by it, so no tile covers the board. Below the board,
+/// shelves use the whole width of the grid. A tile, which is wider than the grid, is placed on a
+/// shelf of its own below the other tiles, so the grid grows to show it.
+///
+/// # Arguments
+///
+/// * `tiles`: the tiles to place
+/// * `board_size_cells`: the width and height of the board
+/// * `board_offset_cells`: the position of the board in the grid
+///
+/// returns: Vec<CellOffset> the position of each tile in the grid
 pub fn calculate_tile_start_positions(
     tiles: &[TileConfig],
-    puzzle_config: &PuzzleConfig,
+    board_size_cells: CellOffset,
     board_offset_cells: CellOffset,
 ) -> Vec<CellOffset> {
-    let mut positions: Vec<CellOffset> = Vec::new();
-
-    // Left
-    place_in_column(
-        CellOffset(1, 1),
-        puzzle_config.board_config().layout().dim().1 as i32,
-        tiles,
-        &mut positions,
-    );
-
-    // Right
-    place_in_column(
-        board_offset_cells
-            + CellOffset(puzzle_config.board_config().layout().dim().0 as i32 + 1, 0),
-        puzzle_config.board_config().layout().dim().1 as i32,
-        tiles,
-        &mut positions,
-    );
-
-    // Bottom Rows
-    let mut start_y = 2 + puzzle_config.board_config().layout().dim().1 as i32;
-    while tiles.len() > positions.len() {
-        let end = place_in_row(
-            CellOffset(1, start_y),
-            board_offset_cells.0 * 2 + puzzle_config.board_config().layout().dim().0 as i32,
-            tiles,
-            &mut positions,
-        );
-        start_y = end;
-    }
-
-    if tiles.len() != positions.len() {
-        panic!("Not enough space to place all tiles around the board");
-    }
+    let tile_sizes: Vec<CellOffset> = tiles
+        .iter()
+        .map(|tile| CellOffset::from(tile.base().dim()))
+        .collect();
+    pack_shelves(&tile_sizes, board_size_cells, board_offset_cells)
+}
 
-    positions
+/// A row of tiles, which is filled from left to right.
+struct Shelf {
+    /// The top of the shelf.
+    y: i32,
+    /// The left of the next tile on the shelf.
+    x: i32,
+    /// The height of the highest tile on the shelf.
+    height: i32,
 }
 
-fn place_in_column(
-    start: CellOffset,
-    end: i32,
-    tiles: &[TileConfig],
-    positions: &mut Vec<CellOffset>,
-) {
-    if tiles.len() != positions.len() {
-        let mut next_pos = start;
-        let mut next_tile_index = positions.len();
-        while end > next_pos.1 {
-            positions.push(next_pos);
-            if tiles.len() == positions.len() {
+fn pack_shelves(
+    tile_sizes: &[CellOffset],
+    board_size_cells: CellOffset,
+    board_offset_cells: CellOffset,
+) -> Vec<CellOffset> {
+    let grid_width = board_offset_cells.0 * 2 + board_size_cells.0;
+    let free_end_x = grid_width - GAP;
+    let board_start = board_offset_cells - CellOffset(GAP, GAP);
+    let board_end = board_offset_cells + board_size_cells + CellOffset(GAP, GAP);
+
+    let mut shelf = Shelf {
+        y: GAP,
+        x: GAP,
+        height: 0,
+    };
+    let mut positions = Vec::with_capacity(tile_sizes.len());
+    for &size in tile_sizes {
+        loop {
+            let beside_board = shelf.y < board_end.1 && shelf.y + size.1 > board_start.1;
+            let mut x = shelf.x;
+            if beside_board && x < board_end.0 && x + size.0 > board_start.0 {
+                x = board_end.0;
+            }
+            if x + size.0 <= free_end_x {
+                positions.push(CellOffset(x, shelf.y));
+                shelf.x = x + size.0;
+                shelf.height = shelf.height.max(size.1);
                 break;
             }
-            let tile = &tiles[next_tile_index];
-            next_pos.1 += tile.base().dim().1 as i32;
-            next_tile_index += 1;
+            if shelf.x == GAP && !beside_board {
+                // The tile is wider than the grid, so it gets a shelf of its own.
+                positions.push(CellOffset(GAP, shelf.y));
+                shelf.y += size.1;
+                break;
+            }
+            shelf = Shelf {
+                y: shelf.y + shelf.height.max(1),
+                x: GAP,
+                height: 0,
+            };
         }
     }
+    positions
 }
 
-fn place_in_row(
-    start: CellOffset,
-    end: i32,
-    tiles: &[TileConfig],
-    positions: &mut Vec<CellOffset>,
-) -> i32 {
-    let mut highest_tile: i32 = 0;
-    if tiles.len() != positions.len() {
-        let mut next_pos = start;
-        let mut next_tile_index = positions.len();
-        while end > next_pos.0 + tiles[next_tile_index].base().dim().0 as i32 {
-            positions.push(next_pos);
-            if tiles.len() == positions.len() {
-                break;
-            }
-            let tile = &tiles[next_tile_index];
-            next_pos.0 += tile.base().dim().0 as i32;
-            next_tile_index += 1;
-            if tile.base().dim().1 as i32 > highest_tile {
-                highest_tile = tile.base().dim().1 as i32;
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOARD_SIZE: CellOffset = CellOffset(4, 3);
+    const BOARD_OFFSET: CellOffset = CellOffset(6, 1);
+
+    /// Checks, that no tile covers the board or another tile.
+    fn assert_no_overlap(sizes: &[CellOffset], positions: &[CellOffset]) {
+        let overlaps = |a: CellOffset, a_size: CellOffset, b: CellOffset, b_size: CellOffset| {
+            a.0 < b.0 + b_size.0
+                && b.0 < a.0 + a_size.0
+                && a.1 < b.1 + b_size.1
+                && b.1 < a.1 + a_size.1
+        };
+        for (i, (&position, &size)) in positions.iter().zip(sizes).enumerate() {
+            assert!(
+                !overlaps(position, size, BOARD_OFFSET, BOARD_SIZE),
+                "tile {} covers the board",
+                i
+            );
+            for (&other, &other_size) in positions[i + 1..].iter().zip(&sizes[i + 1..]) {
+                assert!(
+                    !overlaps(position, size, other, other_size),
+                    "tile {} overlaps another tile",
+                    i
+                );
             }
         }
     }
-    start.1 + highest_tile
+
+    #[test]
+    fn test_pack_shelves_beside_and_below_board() {
+        let sizes = vec![
+            CellOffset(2, 2),
+            CellOffset(2, 1),
+            CellOffset(3, 2),
+            CellOffset(1, 1),
+            CellOffset(5, 1),
+        ];
+        let positions = pack_shelves(&sizes, BOARD_SIZE, BOARD_OFFSET);
+
+        assert_eq!(
+            positions,
+            vec![
+                CellOffset(1, 1),
+                CellOffset(3, 1),
+                CellOffset(11, 1),
+                CellOffset(14, 1),
+                CellOffset(1, 5),
+            ]
+        );
+        assert_no_overlap(&sizes, &positions);
+    }
+
+    #[test]
+    fn test_pack_shelves_oversized_tiles() {
+        let sizes = vec![
+            CellOffset(20, 1),
+            CellOffset(1, 1),
+            CellOffset(30, 2),
+            CellOffset(2, 2),
+        ];
+        let positions = pack_shelves(&sizes, BOARD_SIZE, BOARD_OFFSET);
+
+        assert_eq!(positions.len(), sizes.len());
+        assert_eq!(positions[0], CellOffset(1, 5));
+        assert_eq!(positions[1], CellOffset(1, 6));
+        assert_eq!(positions[2], CellOffset(1, 7));
+        assert_no_overlap(&sizes, &positions);
+    }
 }
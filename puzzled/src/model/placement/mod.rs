@@ -3,6 +3,7 @@ use crate::app::puzzle::puzzle_area::puzzle_state::{
 };
 use crate::model::extension::PuzzleTypeExtension;
 use crate::model::placement::board::PlacedBoard;
+use crate::model::placement::geometry;
 use crate::model::placement::grid::{
     MIN_CELLS_TO_THE_SIDES_OF_BOARD, MIN_CELLS_TO_THE_TOP_OF_BOARD,
 };
@@ -13,13 +14,16 @@ use adw::glib;
 use adw::prelude::ObjectExt;
 use adw::subclass::prelude::*;
 use log::debug;
+use puzzle_config::{BoardConfig, TileConstraint};
 use puzzled_common::Shape;
-use std::cell::Ref;
+use std::cell::{Ref, RefCell};
 use std::mem::take;
 
 mod board;
-mod grid;
+pub mod geometry;
+pub mod grid;
 mod initial;
+mod push;
 mod tile;
 
 const TILE_MOVED_SIGNAL_NAME: &str = "tile-moved";
@@ -43,8 +47,14 @@ mod imp {
         pub(super) min_area_pixel_size: Cell<PixelOffset>,
         pub(super) grid_config: RefCell<GridConfig>,
         pub(super) board: RefCell<PlacedBoard>,
+        /// The board as it is shown, which differs from the config, if it was rotated.
+        pub(super) board_config: RefCell<Option<BoardConfig>>,
+        /// How often the board was rotated clockwise by 90 degrees.
+        pub(super) board_rotations: Cell<u8>,
         pub(super) tiles: RefCell<Vec<PlacedTile>>,
         pub(super) hint_tile: RefCell<Option<PlacedTile>>,
+        /// Whether fewer empty cells are kept around the board, since the screen is too small.
+        pub(super) compact_margins: Cell<bool>,
     }
 
     #[glib::object_subclass]
@@ -104,10 +114,11 @@ impl PlacementModel {
             ),
         );
         obj.imp().board.replace(board);
+        obj.imp().board_config.replace(Some(board_config.clone()));
 
         let start_positions = initial::calculate_tile_start_positions(
             puzzle_config.tiles(),
-            puzzle_config,
+            board_config.layout().dim().into(),
             CellOffset(
                 MIN_CELLS_TO_THE_SIDES_OF_BOARD,
                 MIN_CELLS_TO_THE_TOP_OF_BOARD,
@@ -195,6 +206,115 @@ impl PlacementModel {
         board.position_pixel()
     }
 
+    /// The board as it is currently shown.
+    pub fn board_config(&self) -> Ref<'_, BoardConfig> {
+        Ref::map(self.imp().board_config.borrow(), |board_config| {
+            board_config.as_ref().expect("Board config not set")
+        })
+    }
+
+    /// Rotates the board clockwise by 90 degrees.
+    ///
+    /// Tiles with cells on the board are rotated with it, so they keep covering the same cells
+    /// of the board. The hint tile is removed, since it would no longer match the board.
+    ///
+    /// returns: the indices of the tiles, which were rotated with the board
+    pub fn rotate_board_clockwise(&self) -> Vec<usize> {
+        let rotated_board_config = self.board_config().rotated_clockwise();
+        let (width, height) = self.board_config().layout().dim();
+        let board_position = self.board_cell_position();
+
+        let mut rotated_tiles = Vec::new();
+        {
+            let mut tiles = self.imp().tiles.borrow_mut();
+            for (i, tile) in tiles.iter_mut().enumerate() {
+                let position_on_board = tile.position_cells() - board_position;
+                let covers_board = tile
+                    .current_rotation()
+                    .indexed_iter()
+                    .any(|((x, y), cell)| {
+                        let position = position_on_board + CellOffset(x as i32, y as i32);
+                        *cell && geometry::is_within(position, (width, height))
+                    });
+                if !covers_board {
+                    continue;
+                }
+
+                let mut rotation = tile.current_rotation().clone();
+                // The shape is drawn transposed, so counterclockwise is clockwise on screen.
+                rotation.rotate_counterclockwise();
+                let size = tile.cell_size();
+                let new_position_on_board = geometry::rotate_position_on_board_clockwise(
+                    position_on_board,
+                    size,
+                    height as i32,
+                );
+                tile.set_cell_size(rotation.dim().into());
+                tile.set_current_rotation(rotation);
+                tile.set_position_cells(board_position + new_position_on_board);
+                rotated_tiles.push(i);
+            }
+        }
+
+        self.imp()
+            .board
+            .borrow_mut()
+            .set_cell_size(rotated_board_config.layout().dim().into());
+        self.imp().board_config.replace(Some(rotated_board_config));
+        let board_rotations = self.imp().board_rotations.get();
+        self.imp().board_rotations.set((board_rotations + 1) % 4);
+        self.remove_hint_tile();
+        rotated_tiles
+    }
+
+    /// Returns the given extension with its target indices referring to the board as it is
+    /// currently shown instead of the board in the puzzle config.
+    pub fn extension_for_shown_board(
+        &self,
+        puzzle_type_extension: &Option<PuzzleTypeExtension>,
+    ) -> Option<PuzzleTypeExtension> {
+        let Some(PuzzleTypeExtension::Area {
+            target: Some(target),
+        }) = puzzle_type_extension
+        else {
+            return puzzle_type_extension.clone();
+        };
+        let puzzle = self.imp().puzzle.borrow();
+        let Some(puzzle) = puzzle.as_ref() else {
+            return puzzle_type_extension.clone();
+        };
+
+        let mut board_config = puzzle.config().board_config().clone();
+        let mut target = target.clone();
+        for _ in 0..self.imp().board_rotations.get() {
+            target = board_config.rotate_target_clockwise(&target);
+            board_config = board_config.rotated_clockwise();
+        }
+        Some(PuzzleTypeExtension::Area {
+            target: Some(target),
+        })
+    }
+
+    /// Returns the tile constraints of the puzzle with their cells referring to the board as it
+    /// is currently shown instead of the board in the puzzle config.
+    pub fn tile_constraints_for_shown_board(&self) -> Vec<TileConstraint> {
+        let puzzle = self.imp().puzzle.borrow();
+        let Some(puzzle) = puzzle.as_ref() else {
+            return Vec::new();
+        };
+
+        let mut board_config = puzzle.config().board_config().clone();
+        let mut tile_constraints = puzzle.config().tile_constraints().to_vec();
+        for _ in 0..self.imp().board_rotations.get() {
+            tile_constraints = tile_constraints
+                .iter()
+                .map(|tile_constraint| tile_constraint.rotated_clockwise(&board_config))
+                .collect();
+            board_config = board_config.rotated_clockwise();
+        }
+        tile_constraints
+    }
+
     /// The board position in cells.
     fn board_cell_position(&self) -> CellOffset {
         let board = self.imp().board.borrow();
@@ -366,7 +486,7 @@ impl PlacementModel {
     /// returns: CellOffset
     fn translate_pixels_to_cells(&self, position: PixelOffset) -> CellOffset {
         let cell_size = self.imp().grid_config.borrow().cell_size_pixel;
-        position.div_scalar(cell_size as f64).round().into()
+        geometry::pixels_to_cells(position, cell_size)
     }
 
     /// Converts a CellOffset to a PixelOffset respecting the current grid layout.
@@ -378,7 +498,7 @@ impl PlacementModel {
     /// returns: PixelOffset
     fn translate_cells_to_pixels(&self, position: CellOffset) -> PixelOffset {
         let cell_size = self.imp().grid_config.borrow().cell_size_pixel;
-        position.mul_scalar(cell_size as f64).into()
+        geometry::cells_to_pixels(position, cell_size)
     }
 
     /// Finds the index of a tile that has the same base shape.
@@ -403,14 +523,13 @@ impl PlacementModel {
         &self,
         puzzle_type_extension: Ref<Option<PuzzleTypeExtension>>,
     ) -> Result<PuzzleState, String> {
-        let puzzle = self.imp().puzzle.borrow();
-        if puzzle.is_none() {
+        if self.imp().puzzle.borrow().is_none() {
             return Err("No puzzle set".to_string());
         }
-        let puzzle = puzzle.as_ref().unwrap();
-        let puzzle_config = puzzle.config();
 
-        let mut state = PuzzleState::new(puzzle_config, puzzle_type_extension);
+        let extension = RefCell::new(self.extension_for_shown_board(&puzzle_type_extension));
+        let mut state = PuzzleState::new(&self.board_config(), extension.borrow());
+        state.tile_constraints = self.tile_constraints_for_shown_board();
 
         let tiles = self.imp().tiles.borrow();
         let board_position = self.board_cell_position();
@@ -419,19 +538,16 @@ impl PlacementModel {
             let tile_position = self
                 .tile_cell_position(i)
                 .ok_or_else(|| "Tile position not set".to_string())?;
-            let tile_position = tile_position - board_position + CellOffset(1, 1);
+            let tile_position = geometry::grid_to_puzzle_state(tile_position, board_position);
             let mut any_cell_on_board = false;
-            for ((x, y), cell) in tile.current_rotation().indexed_iter() {
+            let rotation = tile.current_rotation();
+            for ((x, y), cell) in rotation.indexed_iter() {
                 if !*cell {
                     continue;
                 }
 
                 let cell_position = tile_position + CellOffset(x as i32, y as i32);
-                if cell_position.0 >= 0
-                    && cell_position.1 >= 0
-                    && (cell_position.0 as usize) < state.grid.dim().0
-                    && (cell_position.1 as usize) < state.grid.dim().1
-                {
+                if geometry::is_within(cell_position, state.grid.dim()) {
                     let idx: (usize, usize) = cell_position.into();
                     let new = match state.grid.get_mut(idx) {
                         None => return Err("Index out of bounds".to_string()),
@@ -440,6 +556,7 @@ impl PlacementModel {
                             let tile_cell_placement = TileCellPlacement {
                                 tile_id: i,
                                 cell_position: CellOffset(x as i32, y as i32),
+                                quarters: rotation.quarters((x, y)),
                             };
                             match old {
                                 Cell::Empty(data) => {
@@ -462,13 +579,15 @@ impl PlacementModel {
                     state.grid[idx] = new;
                 }
             }
-            if !any_cell_on_board {
-                let unused_tile = UnusedTile {
-                    id: i,
-                    base: tile.base().clone(),
-                    name: tile.name().clone(),
-                };
-                state.unused_tiles.insert(unused_tile);
+            let tile_state = UnusedTile {
+                id: i,
+                base: tile.base().clone(),
+                name: tile.name().clone(),
+            };
+            if any_cell_on_board {
+                state.placed_tiles.insert(tile_state);
+            } else {
+                state.unused_tiles.insert(tile_state);
             }
         }
         Ok(state)
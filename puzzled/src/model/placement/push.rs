@@ -0,0 +1,148 @@
+use crate::model::placement::PlacementModel;
+use crate::offset::CellOffset;
+use adw::subclass::prelude::ObjectSubclassIsExt;
+use puzzled_common::Shape;
+use std::collections::HashSet;
+
+impl PlacementModel {
+    /// Moves tiles next to the board out of the way of the tile with the given index.
+    ///
+    /// Each tile sharing a cell with the given tile is moved to the nearest position, where it
+    /// does not overlap with any other tile or the board. Tiles covering the board are never
+    /// moved, neither are tiles being dragged.
+    ///
+    /// # Arguments
+    ///
+    /// * `idx`: the index of the tile that was dropped
+    ///
+    /// returns: ()
+    pub fn push_aside_tiles_overlapping(&self, idx: usize) {
+        let board_cells = self.board_cells();
+        let grid_cells = self.imp().grid_config.borrow().grid_cells;
+        let max_distance = grid_cells.0.max(grid_cells.1);
+
+        let mut moved_any = false;
+        {
+            let mut tiles = self.imp().tiles.borrow_mut();
+            let Some(dropped) = tiles.get(idx) else {
+                return;
+            };
+            let dropped_cells = filled_cells(dropped.current_rotation(), dropped.position_cells());
+
+            for i in 0..tiles.len() {
+                let tile = &tiles[i];
+                if i == idx || tile.dragged() {
+                    continue;
+                }
+                let cells = filled_cells(tile.current_rotation(), tile.position_cells());
+                if !cells.is_disjoint(&board_cells) || cells.is_disjoint(&dropped_cells) {
+                    continue;
+                }
+
+                let occupied: HashSet<CellOffset> = tiles
+                    .iter()
+                    .enumerate()
+                    .filter(|(j, other)| *j != i && !other.dragged())
+                    .flat_map(|(_, other)| {
+                        filled_cells(other.current_rotation(), other.position_cells())
+                    })
+                    .chain(board_cells.iter().copied())
+                    .collect();
+                if let Some(position) = nearest_free_position(
+                    tile.current_rotation(),
+                    tile.position_cells(),
+                    &occupied,
+                    max_distance,
+                ) {
+                    tiles[i].set_position_cells(position);
+                    moved_any = true;
+                }
+            }
+        }
+
+        if moved_any {
+            self.update_pixel_from_cell_data();
+            self.emit_tile_moved();
+        }
+    }
+
+    /// Returns all cells covered by the bounding box of the board.
+    fn board_cells(&self) -> HashSet<CellOffset> {
+        let board = self.imp().board.borrow();
+        let position = board.position_cells();
+        let size = board.cell_size();
+        (0..size.0)
+            .flat_map(|x| (0..size.1).map(move |y| position + CellOffset(x, y)))
+            .collect()
+    }
+}
+
+/// Returns the cells covered by the given shape at the given position.
+fn filled_cells(shape: &Shape, position: CellOffset) -> HashSet<CellOffset> {
+    shape
+        .indexed_iter()
+        .filter(|(_, filled)| **filled)
+        .map(|(index, _)| position + CellOffset::from(index))
+        .collect()
+}
+
+/// Finds the position closest to the given one, at which the shape does not cover any of the
+/// occupied cells and stays inside the positive quadrant.
+///
+/// # Arguments
+///
+/// * `shape`: the shape to place
+/// * `position`: the current position of the shape
+/// * `occupied`: the cells the shape must not cover
+/// * `max_distance`: how far the shape may be moved in each direction
+///
+/// returns: Option<CellOffset>
+fn nearest_free_position(
+    shape: &Shape,
+    position: CellOffset,
+    occupied: &HashSet<CellOffset>,
+    max_distance: i32,
+) -> Option<CellOffset> {
+    (1..=max_distance).find_map(|distance| {
+        let mut candidates: Vec<CellOffset> = (-distance..=distance)
+            .flat_map(|dx| (-distance..=distance).map(move |dy| CellOffset(dx, dy)))
+            .filter(|offset| offset.0.abs().max(offset.1.abs()) == distance)
+            .map(|offset| position + offset)
+            .filter(|candidate| candidate.0 >= 0 && candidate.1 >= 0)
+            .collect();
+        candidates.sort_by_key(|candidate| {
+            let offset = *candidate - position;
+            offset.0 * offset.0 + offset.1 * offset.1
+        });
+        candidates
+            .into_iter()
+            .find(|candidate| filled_cells(shape, *candidate).is_disjoint(occupied))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use puzzled_common::shape::shape_square;
+
+    #[test]
+    fn test_nearest_free_position() {
+        let shape = shape_square(&[[true, true]]);
+        let occupied: HashSet<CellOffset> =
+            [CellOffset(2, 2), CellOffset(2, 3), CellOffset(3, 2)].into();
+
+        let position = nearest_free_position(&shape, CellOffset(2, 2), &occupied, 5);
+
+        assert_eq!(position, Some(CellOffset(1, 2)));
+    }
+
+    #[test]
+    fn test_nearest_free_position_stays_positive() {
+        let shape = shape_square(&[[true]]);
+        let occupied: HashSet<CellOffset> = [CellOffset(0, 0), CellOffset(1, 0)].into();
+
+        let position = nearest_free_position(&shape, CellOffset(0, 0), &occupied, 1);
+
+        assert_eq!(position, Some(CellOffset(0, 1)));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::global::storage;
+use crate::model::puzzle::PuzzleModel;
+use adw::glib;
+use log::{error, warn};
+use serde::{Deserialize, Serialize};
+use std::io;
+use std::io::Write;
+use std::time::Duration;
+
+/// The file in the data directory, which contains the solved puzzles.
+/// It contains one record as JSON per line, so new records can be appended.
+const PLAY_HISTORY_FILE_NAME: &str = "play-history.jsonl";
+
+/// A puzzle solved by the player.
+/// The history is only kept on this device to show the insights and is never sent anywhere.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct PlayRecord {
+    /// The ISO week like `2026-W42`, in which the puzzle was solved.
+    pub week: String,
+    /// The day of the week from 1 for Monday to 7 for Sunday.
+    pub weekday: u8,
+    pub collection: String,
+    pub seconds: u32,
+    pub hints: u32,
+}
+
+/// Creates the record of a puzzle solved now.
+///
+/// # Arguments
+///
+/// * `puzzle`: the solved puzzle
+/// * `time`: how long the player needed to solve the puzzle
+/// * `hints`: the number of hints used
+///
+/// returns: Option<PlayRecord> `None`, if the local time is not available.
+pub fn create_record(puzzle: &PuzzleModel, time: Duration, hints: u32) -> Option<PlayRecord> {
+    let now = glib::DateTime::now_local().ok()?;
+    Some(PlayRecord {
+        week: now.format("%G-W%V").ok()?.to_string(),
+        weekday: now.day_of_week() as u8,
+        collection: puzzle.collection().config().name().to_string(),
+        seconds: time.as_secs() as u32,
+        hints,
+    })
+}
+
+/// Returns all recorded puzzles.
+/// Lines of the history file, which are no valid records, are skipped.
+pub fn records() -> Vec<PlayRecord> {
+    let path = storage::data_dir().join(PLAY_HISTORY_FILE_NAME);
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Vec::new(),
+        Err(e) => {
+            error!("Failed to read the play history: {}", e);
+            return Vec::new();
+        }
+    };
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str(line) {
+            Ok(record) => Some(record),
+            Err(e) => {
+                warn!("Skipping invalid record in the play history: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+/// Appends the record to the history file.
+pub fn add_record(record: &PlayRecord) -> io::Result<()> {
+    let line = serde_json::to_string(record).map_err(io::Error::other)?;
+    std::fs::create_dir_all(storage::data_dir())?;
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(storage::data_dir().join(PLAY_HISTORY_FILE_NAME))?;
+    writeln!(file, "{}", line)
+}
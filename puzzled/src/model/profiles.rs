@@ -0,0 +1,166 @@
+use crate::global::storage;
+use crate::model::store::file;
+use log::error;
+use serde::{Deserialize, Serialize};
+use std::io;
+
+/// The file in the shared data directory, which lists the profiles.
+const PROFILES_FILE_NAME: &str = "profiles.json";
+/// The id of the profile, which uses the files and settings of versions without profiles.
+pub const DEFAULT_PROFILE_ID: &str = "default";
+/// The name of the default profile, until the player renames it.
+const DEFAULT_PROFILE_NAME: &str = "Default";
+
+/// A player on this device, who keeps their own progress, statistics and preferences.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Profile {
+    /// Identifies the directory of the profile. It never changes.
+    pub id: String,
+    pub name: String,
+}
+
+/// The profiles on this device and the one used after the next start.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Profiles {
+    profiles: Vec<Profile>,
+    active: String,
+}
+
+impl Default for Profiles {
+    /// Only the default profile, which owns all data stored before profiles existed.
+    fn default() -> Self {
+        Profiles {
+            profiles: vec![Profile {
+                id: DEFAULT_PROFILE_ID.to_string(),
+                name: DEFAULT_PROFILE_NAME.to_string(),
+            }],
+            active: DEFAULT_PROFILE_ID.to_string(),
+        }
+    }
+}
+
+impl Profiles {
+    pub fn profiles(&self) -> &[Profile] {
+        &self.profiles
+    }
+
+    /// Returns the profile used after the next start.
+    /// If the active profile is unknown, the first one is returned.
+    pub fn active(&self) -> &Profile {
+        self.profiles
+            .iter()
+            .find(|profile| profile.id == self.active)
+            .unwrap_or(&self.profiles[0])
+    }
+
+    /// Selects the profile used after the next start.
+    ///
+    /// returns: bool false, if there is no profile with the given id
+    pub fn set_active(&mut self, profile_id: &str) -> bool {
+        if !self.profiles.iter().any(|profile| profile.id == profile_id) {
+            return false;
+        }
+        self.active = profile_id.to_string();
+        true
+    }
+
+    /// Adds a profile with the given name.
+    ///
+    /// returns: Option<&Profile> `None`, if the name is empty or another profile has it already.
+    pub fn add(&mut self, name: &str) -> Option<&Profile> {
+        let name = name.trim();
+        if name.is_empty() || self.profiles.iter().any(|profile| profile.name == name) {
+            return None;
+        }
+        let id = (1..)
+            .map(|number| format!("profile-{}", number))
+            .find(|id| self.profiles.iter().all(|profile| profile.id != *id))?;
+        self.profiles.push(Profile {
+            id,
+            name: name.to_string(),
+        });
+        self.profiles.last()
+    }
+
+    /// Returns the profile to pass to [storage::set_profile] to use the active profile.
+    pub fn storage_profile(&self) -> Option<String> {
+        let active = self.active();
+        (active.id != DEFAULT_PROFILE_ID).then(|| active.id.clone())
+    }
+}
+
+/// Loads the profiles from the shared data directory.
+///
+/// If there is no profiles file yet, only the default profile exists. It uses the files and
+/// settings of versions without profiles, so they do not need to be moved.
+pub fn load() -> Profiles {
+    let path = storage::shared_data_dir().join(PROFILES_FILE_NAME);
+    let json = match std::fs::read_to_string(&path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Profiles::default(),
+        Err(e) => {
+            error!("Failed to read the profiles: {}", e);
+            return Profiles::default();
+        }
+    };
+    match serde_json::from_str::<Profiles>(&json) {
+        Ok(profiles) if !profiles.profiles.is_empty() => profiles,
+        Ok(_) => Profiles::default(),
+        Err(e) => {
+            error!("Failed to parse the profiles: {}", e);
+            Profiles::default()
+        }
+    }
+}
+
+/// Writes the profiles to the shared data directory.
+pub fn save(profiles: &Profiles) -> io::Result<()> {
+    let json = serde_json::to_string_pretty(profiles).map_err(io::Error::other)?;
+    std::fs::create_dir_all(storage::shared_data_dir())?;
+    file::write_atomically(&storage::shared_data_dir().join(PROFILES_FILE_NAME), &json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::global::storage::DirectoryStorageBackend;
+
+    #[test]
+    fn test_default_profiles() {
+        let profiles = Profiles::default();
+
+        assert_eq!(profiles.active().id, DEFAULT_PROFILE_ID);
+        assert_eq!(profiles.storage_profile(), None);
+    }
+
+    #[test]
+    fn test_add_and_set_active() {
+        let mut profiles = Profiles::default();
+
+        assert_eq!(profiles.add("Alice").unwrap().id, "profile-1");
+        assert_eq!(profiles.add(" Bob ").unwrap().name, "Bob");
+        assert!(profiles.add("Alice").is_none());
+        assert!(profiles.add("  ").is_none());
+
+        assert!(profiles.set_active("profile-2"));
+        assert_eq!(profiles.active().name, "Bob");
+        assert_eq!(profiles.storage_profile(), Some("profile-2".to_string()));
+        assert!(!profiles.set_active("profile-3"));
+        assert_eq!(profiles.active().name, "Bob");
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let root = std::env::temp_dir().join(format!("puzzled-profiles-{}", std::process::id()));
+        storage::set_backend(DirectoryStorageBackend::new(root.clone()));
+        assert_eq!(load(), Profiles::default());
+
+        let mut profiles = Profiles::default();
+        profiles.add("Alice");
+        profiles.set_active("profile-1");
+        save(&profiles).unwrap();
+
+        assert_eq!(load(), profiles);
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
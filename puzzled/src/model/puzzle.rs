@@ -6,10 +6,11 @@ use crate::model::stars::Stars;
 use adw::glib;
 use adw::prelude::ObjectExt;
 use adw::subclass::prelude::*;
-use puzzle_config::PuzzleConfig;
+use puzzle_config::{ProgressionConfig, PuzzleConfig};
 
 const PROGRESS_IMPROVED_SIGNAL_NAME: &str = "progress-improved";
 const MARKED_UNSOLVED_SIGNAL_NAME: &str = "marked-unsolved";
+const NOTES_CHANGED_SIGNAL_NAME: &str = "notes-changed";
 
 mod imp {
     use super::*;
@@ -28,6 +29,7 @@ mod imp {
         pub(super) solved: RefCell<HashMap<Option<PuzzleTypeExtension>, bool>>,
         pub(super) hints_used: RefCell<HashMap<Option<PuzzleTypeExtension>, Option<u32>>>,
         pub(super) stars: RefCell<HashMap<Option<PuzzleTypeExtension>, Stars>>,
+        pub(super) notes: RefCell<Option<String>>,
     }
 
     #[glib::object_subclass]
@@ -45,6 +47,7 @@ mod imp {
                 vec![
                     Signal::builder(PROGRESS_IMPROVED_SIGNAL_NAME).build(),
                     Signal::builder(MARKED_UNSOLVED_SIGNAL_NAME).build(),
+                    Signal::builder(NOTES_CHANGED_SIGNAL_NAME).build(),
                 ]
             })
         }
@@ -85,26 +88,44 @@ impl PuzzleModel {
         imp.collection
             .set(collection.clone())
             .expect("Failed to set collection for PuzzleModel");
+        obj.load_default_progress(&puzzle_meta);
+        imp.notes
+            .replace(puzzle_meta.notes(obj.collection().config(), obj.config()));
 
-        let solved = puzzle_meta.is_solved(
-            collection.config(),
-            obj.config().index(),
-            &Some(default_extension.clone()),
-        );
+        obj
+    }
+
+    /// Loads the solved state and hints for the default extension from the puzzle meta into the
+    /// caches.
+    fn load_default_progress(&self, puzzle_meta: &PuzzleMeta) {
+        let imp = self.imp();
+        let collection = self.collection().config();
+        let default_extension = Some(imp.default_extension.get().unwrap().clone());
+
+        let solved = puzzle_meta.is_solved(collection, self.config(), &default_extension);
         imp.solved
             .borrow_mut()
-            .insert(Some(default_extension.clone()), solved);
+            .insert(default_extension.clone(), solved);
 
-        let hints = puzzle_meta.hints(
-            collection.config(),
-            obj.config().index(),
-            &Some(default_extension.clone()),
-        );
-        imp.hints_used
-            .borrow_mut()
-            .insert(Some(default_extension), hints);
+        let hints = puzzle_meta.hints(collection, self.config(), &default_extension);
+        imp.hints_used.borrow_mut().insert(default_extension, hints);
+    }
 
-        obj
+    /// Clears the caches and loads the solved state and hints again, e.g. after they were
+    /// synced from another device. The `progress_improved` signal is emitted for UIs to update.
+    ///
+    /// # Arguments
+    ///
+    /// * `puzzle_meta`: the puzzle meta to load the progress from
+    ///
+    /// returns: ()
+    pub fn reload_progress(&self, puzzle_meta: &PuzzleMeta) {
+        let imp = self.imp();
+        imp.solved.borrow_mut().clear();
+        imp.hints_used.borrow_mut().clear();
+        imp.stars.borrow_mut().clear();
+        self.load_default_progress(puzzle_meta);
+        self.emit_by_name::<()>(PROGRESS_IMPROVED_SIGNAL_NAME, &[]);
     }
 
     /// The config defining the puzzle tiles and board.
@@ -156,6 +177,17 @@ impl PuzzleModel {
         Some(collection.puzzles()[this_index - 1].is_solved_default())
     }
 
+    /// Returns true, if the puzzle is locked by the progression of its collection.
+    /// A puzzle, which is already solved, is never locked.
+    pub fn is_locked_default(&self) -> bool {
+        match self.collection().config().progression() {
+            ProgressionConfig::Any => false,
+            ProgressionConfig::Sequential => {
+                !self.is_solved_default() && !self.is_previous_solved_default().unwrap_or(true)
+            }
+        }
+    }
+
     /// Sets the puzzle as solved for the given extension.
     /// The hints are the amount of hints used to solve the puzzle.
     ///
@@ -176,13 +208,13 @@ impl PuzzleModel {
         puzzle_meta.set_solved(
             true,
             self.imp().collection.get().unwrap().config(),
-            self.config().index(),
+            self.config(),
             extension,
         );
         puzzle_meta.set_hints(
             hints,
             self.imp().collection.get().unwrap().config(),
-            self.config().index(),
+            self.config(),
             extension,
         );
         self.emit_progress_improved();
@@ -247,6 +279,29 @@ impl PuzzleModel {
         self.emit_marked_unsolved();
     }
 
+    /// Marks the puzzle as solved for the default extension on request of the user.
+    /// No hints are stored, so no stars are reached until the puzzle is actually solved.
+    /// A puzzle, which is already solved, keeps its progress.
+    pub fn mark_as_solved_manually(&self) {
+        if self.is_solved_default() {
+            return;
+        }
+        let extension = Some(self.imp().default_extension.get().unwrap().clone());
+        self.imp()
+            .solved
+            .borrow_mut()
+            .insert(extension.clone(), true);
+        PuzzleMeta::new().set_solved(true, self.collection().config(), self.config(), &extension);
+        self.emit_progress_improved();
+    }
+
+    /// Removes the stored solved state and hints of all extensions of the puzzle and emits the
+    /// `marked_unsolved` signal.
+    pub fn reset_progress(&self) {
+        PuzzleMeta::new().remove_progress(self.collection().config(), self.config());
+        self.mark_as_unsolved();
+    }
+
     /// Connects to the `progress_improved` signal.
     /// This signal is emitted, if the solved status changed to true or the hints used improved.
     /// This can be used to update the UI.
@@ -290,6 +345,42 @@ impl PuzzleModel {
         self.emit_by_name::<()>(MARKED_UNSOLVED_SIGNAL_NAME, &[]);
     }
 
+    /// Returns the notes the user wrote for this puzzle or none, if there are none.
+    pub fn notes(&self) -> Option<String> {
+        self.imp().notes.borrow().clone()
+    }
+
+    /// Stores the notes for this puzzle and emits the `notes_changed` signal.
+    /// Empty notes remove the notes of the puzzle.
+    ///
+    /// # Arguments
+    ///
+    /// * `notes`: the text of the notes
+    ///
+    /// returns: ()
+    pub fn set_notes(&self, notes: &str) {
+        PuzzleMeta::new().set_notes(notes, self.collection().config(), self.config());
+        self.imp()
+            .notes
+            .replace((!notes.is_empty()).then(|| notes.to_string()));
+        self.emit_by_name::<()>(NOTES_CHANGED_SIGNAL_NAME, &[]);
+    }
+
+    /// Connects to the `notes_changed` signal.
+    /// This signal is emitted, if the notes of the puzzle were changed.
+    ///
+    /// # Arguments
+    ///
+    /// * `callback`: the callback to call when the signal is emitted
+    ///
+    /// returns: ()
+    pub fn connect_notes_changed<F: Fn() + 'static>(&self, callback: F) {
+        self.connect_local(NOTES_CHANGED_SIGNAL_NAME, false, move |_| {
+            callback();
+            None
+        });
+    }
+
     /// Returns true, if there is a next puzzle in the collection. False, if not.
     pub fn has_next_puzzle(&self) -> bool {
         let imp = self.imp();
@@ -307,4 +398,18 @@ impl PuzzleModel {
         let collection = imp.collection.get().unwrap();
         collection.puzzles().get(self_index + 1)
     }
+
+    /// Returns the first puzzle after this one, which is neither solved nor locked for the
+    /// default target. The search continues at the start of the collection after its last
+    /// puzzle.
+    /// None, if all other puzzles of the collection are solved or locked.
+    pub fn next_unsolved_puzzle(&self) -> Option<&PuzzleModel> {
+        let imp = self.imp();
+        let self_index = imp.config.get().unwrap().index();
+        let puzzles = imp.collection.get().unwrap().puzzles();
+        puzzles[self_index + 1..]
+            .iter()
+            .chain(&puzzles[..self_index])
+            .find(|puzzle| !puzzle.is_solved_default() && !puzzle.is_locked_default())
+    }
 }
@@ -1,12 +1,26 @@
+use crate::global::storage;
 use crate::model::extension::PuzzleTypeExtension;
+use crate::model::store::file;
+use crate::sync::progress::Progress;
 use adw::gio::Settings;
 use adw::glib::{Variant, VariantDict, VariantTy};
-use adw::prelude::{SettingsExt, SettingsExtManual};
-use log::{debug, error};
-use puzzle_config::{BoardConfig, PuzzleConfigCollection, Target};
+use adw::prelude::{SettingsExt, SettingsExtManual, ToVariant};
+use log::{debug, error, warn};
+use puzzle_config::{AreaTarget, BoardConfig, PuzzleConfig, PuzzleConfigCollection, Target};
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 const SOLVED_KEY: &str = "solved";
 const HINTS_KEY: &str = "hints";
+const FEATURED_SOLVED_KEY: &str = "featured-solved";
+const NOTES_KEY: &str = "notes";
+const VERSION_KEY: &str = "version";
+const MODIFIED_KEY: &str = "modified";
+/// The version of the format of the solved and hints keys.
+/// Increase it, when the format changes, and migrate older values.
+const PUZZLE_META_VERSION: u32 = 1;
+/// The file, to which damaged entries are written before they are removed.
+const BACKUP_FILE_NAME: &str = "puzzle-meta.bak";
 
 /// A struct to access the solved state and hints used for a given puzzle.
 /// Instances should be reused if possible.
@@ -18,22 +32,178 @@ pub struct PuzzleMeta {
 impl PuzzleMeta {
     pub fn new() -> Self {
         PuzzleMeta {
-            settings: Settings::new("de.til7701.Puzzled.puzzle-meta"),
+            settings: storage::settings("de.til7701.Puzzled.puzzle-meta"),
         }
     }
 
     pub fn reset(&self) {
         self.settings.reset(SOLVED_KEY);
         self.settings.reset(HINTS_KEY);
+        self.settings.reset(FEATURED_SOLVED_KEY);
+        self.touch();
+    }
+
+    /// Returns the solved puzzles and their hints of all collections, so they can be synced.
+    pub fn progress(&self) -> Progress {
+        let mut progress = Progress {
+            modified: self.settings.int64(MODIFIED_KEY),
+            ..Progress::default()
+        };
+        for (collection_id, puzzle_key, value) in self.entries(SOLVED_KEY) {
+            if value.get::<bool>() == Some(true) {
+                progress
+                    .solved
+                    .entry(collection_id)
+                    .or_default()
+                    .insert(puzzle_key);
+            }
+        }
+        for (collection_id, puzzle_key, value) in self.entries(HINTS_KEY) {
+            if let Some(hints) = value.get::<u32>() {
+                progress
+                    .hints
+                    .entry(collection_id)
+                    .or_default()
+                    .insert(puzzle_key, hints);
+            }
+        }
+        progress
+    }
+
+    /// Replaces the solved puzzles and their hints of all collections with the given progress.
+    /// Models, which were created before, still have the old state cached.
+    ///
+    /// # Arguments
+    ///
+    /// * `progress`: the progress to store
+    ///
+    /// returns: ()
+    pub fn set_progress(&self, progress: &Progress) {
+        let solved = VariantDict::new(None);
+        for (collection_id, puzzle_keys) in &progress.solved {
+            let puzzle_dict = VariantDict::new(None);
+            for puzzle_key in puzzle_keys {
+                puzzle_dict.insert(puzzle_key, true);
+            }
+            solved.insert(collection_id, Variant::from(puzzle_dict));
+        }
+        let hints = VariantDict::new(None);
+        for (collection_id, puzzle_hints) in &progress.hints {
+            let puzzle_dict = VariantDict::new(None);
+            for (puzzle_key, count) in puzzle_hints {
+                puzzle_dict.insert(puzzle_key, *count);
+            }
+            hints.insert(collection_id, Variant::from(puzzle_dict));
+        }
+
+        let result = self
+            .settings
+            .set(SOLVED_KEY, Variant::from(solved))
+            .and_then(|_| self.settings.set(HINTS_KEY, Variant::from(hints)))
+            .and_then(|_| self.settings.set(MODIFIED_KEY, progress.modified));
+        if result.is_err() {
+            error!("Failed to store the synced progress");
+        }
+    }
+
+    /// Returns the collection id, puzzle key and value of each puzzle stored for the key.
+    fn entries(&self, key: &str) -> Vec<(String, String, Variant)> {
+        let mut entries = Vec::new();
+        for collection_entry in self.settings.value(key).iter() {
+            let collection_id = collection_entry.child_value(0);
+            let Some(puzzles) = collection_entry
+                .child_value(1)
+                .as_variant()
+                .filter(|v| v.is_type(VariantTy::VARDICT))
+            else {
+                continue;
+            };
+            for puzzle_entry in puzzles.iter() {
+                let puzzle_key = puzzle_entry.child_value(0);
+                if let Some(value) = puzzle_entry.child_value(1).as_variant() {
+                    entries.push((
+                        collection_id.str().unwrap_or_default().to_string(),
+                        puzzle_key.str().unwrap_or_default().to_string(),
+                        value,
+                    ));
+                }
+            }
+        }
+        entries
+    }
+
+    /// Marks the stored progress as changed now, so the sync knows which progress is newer.
+    fn touch(&self) {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or_default();
+        if self.settings.set(MODIFIED_KEY, now).is_err() {
+            error!("Failed to set the modification time of the puzzle meta");
+        }
+    }
+
+    /// Checks the stored data for damage and removes damaged entries.
+    ///
+    /// Each collection must map to a dictionary of puzzles. Entries which do not, are written to a
+    /// backup file and removed, so the solved state and hints of the collection start fresh.
+    ///
+    /// returns: the path of the backup or none, if nothing was damaged
+    pub fn recover_damaged_entries(&self) -> Option<PathBuf> {
+        let version = self.settings.uint(VERSION_KEY);
+        if version > PUZZLE_META_VERSION {
+            warn!(
+                "Puzzle meta was stored by a newer version of Puzzled (version {}).",
+                version
+            );
+        }
+
+        let mut damaged_entries = Vec::new();
+        for key in [SOLVED_KEY, HINTS_KEY] {
+            let collection_dict = self.settings.get::<VariantDict>(key);
+            let mut damaged_collections = Vec::new();
+            for entry in self.settings.value(key).iter() {
+                let collection_id = entry.child_value(0);
+                let value = entry.child_value(1).as_variant();
+                if !value.is_some_and(|v| v.is_type(VariantTy::VARDICT)) {
+                    damaged_entries.push(format!("{}: {}", key, entry.print(true)));
+                    damaged_collections.push(collection_id.str().unwrap_or_default().to_string());
+                }
+            }
+            if damaged_collections.is_empty() {
+                continue;
+            }
+            for collection_id in damaged_collections {
+                collection_dict.remove(&collection_id);
+            }
+            if self.settings.set(key, collection_dict.end()).is_err() {
+                error!("Failed to remove damaged entries of key '{}'", key);
+            }
+        }
+        if damaged_entries.is_empty() {
+            return None;
+        }
+
+        let backup_path = storage::data_dir().join(BACKUP_FILE_NAME);
+        match file::write_atomically(&backup_path, &damaged_entries.join("\n")) {
+            Ok(()) => {
+                warn!("Moved damaged puzzle meta entries to {:?}", backup_path);
+                Some(backup_path)
+            }
+            Err(e) => {
+                error!("Failed to back up damaged puzzle meta entries: {}", e);
+                None
+            }
+        }
     }
 
     pub fn is_solved(
         &self,
         collection: &PuzzleConfigCollection,
-        puzzle_index: usize,
+        puzzle: &PuzzleConfig,
         extension: &Option<PuzzleTypeExtension>,
     ) -> bool {
-        let variant = self.get_value(SOLVED_KEY, collection, puzzle_index, extension);
+        let variant = self.get_value(SOLVED_KEY, collection, puzzle, extension);
         variant.and_then(|v| v.get::<bool>()).unwrap_or(false)
     }
 
@@ -41,25 +211,68 @@ impl PuzzleMeta {
         &self,
         solved: bool,
         collection: &PuzzleConfigCollection,
-        puzzle_index: usize,
+        puzzle: &PuzzleConfig,
         extension: &Option<PuzzleTypeExtension>,
     ) {
         self.set_value(
             SOLVED_KEY,
             &Variant::from(solved),
             collection,
-            puzzle_index,
+            puzzle,
             extension,
         );
     }
 
+    /// Removes the solved state and hints of the puzzle for all of its targets.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection`: the collection of the puzzle
+    /// * `puzzle`: the puzzle
+    ///
+    /// returns: ()
+    pub fn remove_progress(&self, collection: &PuzzleConfigCollection, puzzle: &PuzzleConfig) {
+        let prefix = format!("{}/", puzzle.id());
+        for key in [SOLVED_KEY, HINTS_KEY] {
+            let (collection_dict, puzzle_dict) = self.get_dicts(key, collection);
+            let remaining = VariantDict::new(None);
+            let mut removed = false;
+            for entry in puzzle_dict.end().iter() {
+                let puzzle_key = entry.child_value(0);
+                let puzzle_key = puzzle_key.str().unwrap_or_default();
+                if puzzle_key.starts_with(&prefix) {
+                    removed = true;
+                } else if let Some(value) = entry.child_value(1).as_variant() {
+                    remaining.insert_value(puzzle_key, &value);
+                }
+            }
+            if !removed {
+                continue;
+            }
+            collection_dict.insert(collection.id(), Variant::from(remaining));
+            if self
+                .settings
+                .set(key, Variant::from(collection_dict))
+                .is_err()
+            {
+                error!(
+                    "Failed to remove progress for key='{}', collection='{}', puzzle='{}'",
+                    key,
+                    collection.id(),
+                    puzzle.id()
+                );
+            }
+        }
+        self.touch();
+    }
+
     pub fn hints(
         &self,
         collection: &PuzzleConfigCollection,
-        puzzle_index: usize,
+        puzzle: &PuzzleConfig,
         extension: &Option<PuzzleTypeExtension>,
     ) -> Option<u32> {
-        let variant = self.get_value(HINTS_KEY, collection, puzzle_index, extension);
+        let variant = self.get_value(HINTS_KEY, collection, puzzle, extension);
         variant.and_then(|v| v.get::<u32>())
     }
 
@@ -67,37 +280,115 @@ impl PuzzleMeta {
         &self,
         hints: u32,
         collection: &PuzzleConfigCollection,
-        puzzle_index: usize,
+        puzzle: &PuzzleConfig,
         extension: &Option<PuzzleTypeExtension>,
     ) {
         self.set_value(
             HINTS_KEY,
             &Variant::from(hints),
             collection,
-            puzzle_index,
+            puzzle,
             extension,
         );
     }
 
+    /// Returns true, if the puzzle of the given week was solved while it was featured.
+    ///
+    /// # Arguments
+    ///
+    /// * `week`: the ISO week like `2026-W42`
+    ///
+    /// returns: bool
+    pub fn is_featured_solved(&self, week: &str) -> bool {
+        self.settings
+            .get::<Vec<String>>(FEATURED_SOLVED_KEY)
+            .iter()
+            .any(|solved_week| solved_week == week)
+    }
+
+    /// Remembers that the puzzle of the given week was solved while it was featured.
+    /// This is tracked separately from the solved state of the puzzle, which may have been solved
+    /// before.
+    pub fn set_featured_solved(&self, week: &str) {
+        if self.is_featured_solved(week) {
+            return;
+        }
+        let mut weeks = self.settings.get::<Vec<String>>(FEATURED_SOLVED_KEY);
+        weeks.push(week.to_string());
+        if self.settings.set(FEATURED_SOLVED_KEY, weeks).is_err() {
+            error!("Failed to store the solved puzzle of the week {}", week);
+        }
+    }
+
+    /// Returns the notes the user wrote for the puzzle or none, if there are none.
+    /// Notes belong to the puzzle and are shared between all its targets.
+    pub fn notes(
+        &self,
+        collection: &PuzzleConfigCollection,
+        puzzle: &PuzzleConfig,
+    ) -> Option<String> {
+        let (_, puzzle_dict) = self.get_dicts(NOTES_KEY, collection);
+        puzzle_dict
+            .lookup::<String>(puzzle.id())
+            .ok()
+            .flatten()
+            .filter(|notes| !notes.is_empty())
+    }
+
+    /// Stores the notes for the puzzle. Empty notes are removed.
+    /// Notes are not part of the progress and are therefore neither synced nor reset.
+    ///
+    /// # Arguments
+    ///
+    /// * `notes`: the text of the notes
+    /// * `collection`: the collection of the puzzle
+    /// * `puzzle`: the puzzle
+    ///
+    /// returns: ()
+    pub fn set_notes(
+        &self,
+        notes: &str,
+        collection: &PuzzleConfigCollection,
+        puzzle: &PuzzleConfig,
+    ) {
+        let (collection_dict, puzzle_dict) = self.get_dicts(NOTES_KEY, collection);
+        if notes.is_empty() {
+            puzzle_dict.remove(puzzle.id());
+        } else {
+            puzzle_dict.insert(puzzle.id(), notes.to_variant());
+        }
+        collection_dict.insert(collection.id(), Variant::from(puzzle_dict));
+        if self
+            .settings
+            .set(NOTES_KEY, Variant::from(collection_dict))
+            .is_err()
+        {
+            error!(
+                "Failed to set notes for collection='{}', puzzle='{}'",
+                collection.id(),
+                puzzle.id()
+            );
+        }
+    }
+
     fn get_value(
         &self,
         key: &str,
         collection: &PuzzleConfigCollection,
-        puzzle_index: usize,
+        puzzle: &PuzzleConfig,
         extension: &Option<PuzzleTypeExtension>,
     ) -> Option<Variant> {
         let (_, puzzle_dict) = self.get_dicts(key, collection);
-        let puzzle_key = puzzle_key(collection, puzzle_index, extension);
+        let puzzle_key = puzzle_key(puzzle, extension);
         let value = if let Some(puzzle_key) = &puzzle_key {
             puzzle_dict.lookup_value(puzzle_key, None)
         } else {
             None
         };
         debug!(
-            "Get value for key='{}', collection='{}', puzzle_index={}, puzzle_key={}: {:?}",
+            "Get value for key='{}', collection='{}', puzzle_key={}: {:?}",
             key,
             collection.id(),
-            puzzle_index,
             puzzle_key.unwrap_or_else(|| "none".to_string()),
             value
         );
@@ -109,12 +400,12 @@ impl PuzzleMeta {
         key: &str,
         value: &Variant,
         collection: &PuzzleConfigCollection,
-        puzzle_index: usize,
+        puzzle: &PuzzleConfig,
         extension: &Option<PuzzleTypeExtension>,
     ) {
         let (collection_dict, puzzle_dict) = self.get_dicts(key, collection);
 
-        let puzzle_key = puzzle_key(collection, puzzle_index, extension);
+        let puzzle_key = puzzle_key(puzzle, extension);
         if let Some(puzzle_key) = &puzzle_key {
             puzzle_dict.insert(puzzle_key, value);
         }
@@ -123,20 +414,19 @@ impl PuzzleMeta {
         let result = self.settings.set(key, Variant::from(collection_dict));
         match result {
             Ok(_) => {
+                self.touch();
                 debug!(
-                    "Set value for key='{}', collection='{}', puzzle_index={}, puzzle_key={}",
+                    "Set value for key='{}', collection='{}', puzzle_key={}",
                     key,
                     collection.id(),
-                    puzzle_index,
                     puzzle_key.unwrap_or_else(|| "none".to_string())
                 );
             }
             Err(_) => {
                 error!(
-                    "Failed to set value for key='{}', collection='{}', puzzle_index={}, puzzle_key={}",
+                    "Failed to set value for key='{}', collection='{}', puzzle_key={}",
                     key,
                     collection.id(),
-                    puzzle_index,
                     puzzle_key.unwrap_or_else(|| "none".to_string())
                 )
             }
@@ -157,22 +447,17 @@ impl PuzzleMeta {
     }
 }
 
-fn puzzle_key(
-    collection: &PuzzleConfigCollection,
-    puzzle_index: usize,
+/// Returns the key identifying the puzzle and the target inside the collection.
+pub(crate) fn puzzle_key(
+    puzzle: &PuzzleConfig,
     extension: &Option<PuzzleTypeExtension>,
 ) -> Option<String> {
-    let puzzle = collection.puzzles().get(puzzle_index)?;
-    let extension_key = extension_key(collection, puzzle_index, extension);
+    let extension_key = extension_key(puzzle, extension);
     Some(format!("{}/{}", puzzle.id(), extension_key?))
 }
 
-fn extension_key(
-    collection: &PuzzleConfigCollection,
-    puzzle_index: usize,
-    extension: &Option<PuzzleTypeExtension>,
-) -> Option<String> {
-    match collection.puzzles()[puzzle_index].board_config() {
+fn extension_key(puzzle: &PuzzleConfig, extension: &Option<PuzzleTypeExtension>) -> Option<String> {
+    match puzzle.board_config() {
         BoardConfig::Simple { .. } => Some("simple".to_string()),
         BoardConfig::Area { .. } => {
             let target = match extension {
@@ -182,10 +467,7 @@ fn extension_key(
             if let Some(target) = target {
                 let key = target_key(target);
                 Some(key)
-            } else if let Some(target) = collection.puzzles()[puzzle_index]
-                .board_config()
-                .default_target()
-            {
+            } else if let Some(target) = puzzle.board_config().default_target() {
                 let key = target_key(&target);
                 Some(key)
             } else {
@@ -197,8 +479,11 @@ fn extension_key(
 
 fn target_key(target: &Target) -> String {
     let mut key = "".to_string();
-    for index in &target.indices {
-        key = format!("{}x{}-{}", key, index.0, index.1);
+    for area_target in &target.indices {
+        key = match area_target {
+            AreaTarget::Index(index) => format!("{}x{}-{}", key, index.0, index.1),
+            AreaTarget::Any => format!("{}xany", key),
+        };
     }
     key
 }
@@ -0,0 +1,162 @@
+use crate::model::store::read_resource;
+use log::error;
+use serde::Deserialize;
+
+/// The resource containing the release notes, newest release first.
+const RELEASE_NOTES_RESOURCE: &str = "/de/til7701/Puzzled/release-notes.json";
+
+/// The notes of all releases shown in the "What's New" dialog.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ReleaseNotes {
+    releases: Vec<Release>,
+}
+
+/// The changes of one release.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Release {
+    /// The version like `1.2.0`.
+    pub version: String,
+    /// The date of the release like `2026-04-26`.
+    pub date: String,
+    pub changes: Vec<String>,
+    /// The collections with new or more puzzles in this release.
+    #[serde(default)]
+    pub collections: Vec<CollectionHighlight>,
+}
+
+/// A collection, which got new puzzles in a release.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct CollectionHighlight {
+    /// The id of the collection like `de.til7701.Puzzled.Pentominoes`.
+    pub id: String,
+    /// What is new in the collection.
+    pub highlight: String,
+}
+
+impl ReleaseNotes {
+    /// Loads the release notes bundled with the application.
+    /// If they cannot be parsed, no releases are returned.
+    pub fn load() -> Self {
+        Self::parse(&read_resource(RELEASE_NOTES_RESOURCE)).unwrap_or_else(|e| {
+            error!("Failed to parse the release notes: {}", e);
+            ReleaseNotes {
+                releases: Vec::new(),
+            }
+        })
+    }
+
+    pub fn parse(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    /// Returns the releases newer than the last seen version up to and including the current
+    /// version, newest first.
+    ///
+    /// # Arguments
+    ///
+    /// * `last_seen`: the version, for which the release notes were shown last. If empty, only the
+    ///   notes of the current version are returned.
+    /// * `current`: the version of the running application
+    ///
+    /// returns: Vec<&Release> empty, if the current version was already seen or has no notes
+    pub fn unseen(&self, last_seen: &str, current: &str) -> Vec<&Release> {
+        if last_seen == current || self.release(current).is_none() {
+            return Vec::new();
+        }
+        let current_order = version_order(current);
+        let last_seen_order = if last_seen.is_empty() {
+            None
+        } else {
+            Some(version_order(last_seen))
+        };
+        self.releases
+            .iter()
+            .filter(|release| {
+                let order = version_order(&release.version);
+                match &last_seen_order {
+                    Some(last_seen_order) => order > *last_seen_order && order <= current_order,
+                    None => order == current_order,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the notes of the given version.
+    pub fn release(&self, version: &str) -> Option<&Release> {
+        self.releases
+            .iter()
+            .find(|release| release.version == version)
+    }
+
+    /// Returns all releases, newest first.
+    pub fn releases(&self) -> &[Release] {
+        &self.releases
+    }
+}
+
+/// Splits a version like `1.2.0` into its numbers, so versions can be compared.
+/// Parts, which are no numbers, are compared as 0.
+fn version_order(version: &str) -> Vec<u32> {
+    version
+        .split('.')
+        .map(|part| part.parse().unwrap_or(0))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config;
+    use std::fs;
+
+    fn bundled_release_notes() -> ReleaseNotes {
+        let json = fs::read_to_string("resources/release-notes.json").unwrap();
+        ReleaseNotes::parse(&json).unwrap()
+    }
+
+    fn versions(releases: Vec<&Release>) -> Vec<&str> {
+        releases
+            .iter()
+            .map(|release| release.version.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn test_bundled_release_notes() {
+        let notes = bundled_release_notes();
+
+        assert_eq!(
+            notes
+                .releases()
+                .first()
+                .map(|release| release.version.as_str()),
+            Some(config::VERSION),
+            "the newest release notes must be for the current version"
+        );
+        for pair in notes.releases().windows(2) {
+            assert!(
+                version_order(&pair[0].version) > version_order(&pair[1].version),
+                "release {} must be listed before {}",
+                pair[0].version,
+                pair[1].version
+            );
+        }
+        assert!(notes
+            .releases()
+            .iter()
+            .all(|release| !release.changes.is_empty()));
+    }
+
+    #[test]
+    fn test_unseen() {
+        let notes = bundled_release_notes();
+
+        assert_eq!(
+            versions(notes.unseen("1.0.1", "1.2.0")),
+            vec!["1.2.0", "1.1.0"]
+        );
+        assert_eq!(versions(notes.unseen("", "1.2.0")), vec!["1.2.0"]);
+        assert!(notes.unseen("1.2.0", "1.2.0").is_empty());
+        assert!(notes.unseen("1.0.0", "9.0.0").is_empty());
+    }
+}
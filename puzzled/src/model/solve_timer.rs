@@ -0,0 +1,96 @@
+use std::time::{Duration, Instant};
+
+/// Measures the time needed to solve a puzzle.
+///
+/// The timer can be paused, e.g. while the session is idle, so the time away from the puzzle is
+/// not counted.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SolveTimer {
+    /// Whether the timer was started since it was created.
+    started: bool,
+    /// The time measured before the last pause.
+    accumulated: Duration,
+    /// When the timer was started or resumed last, while it is running.
+    resumed: Option<Instant>,
+}
+
+impl SolveTimer {
+    /// Starts measuring from zero.
+    pub fn start(&mut self) {
+        self.start_at(Instant::now());
+    }
+
+    /// Stops measuring until [Self::resume] is called.
+    /// Nothing happens, if the timer is not running.
+    pub fn pause(&mut self) {
+        self.pause_at(Instant::now());
+    }
+
+    /// Continues measuring after [Self::pause].
+    /// Nothing happens, if the timer was never started or is running already.
+    pub fn resume(&mut self) {
+        self.resume_at(Instant::now());
+    }
+
+    /// Returns the time measured without the pauses or none, if the timer was never started.
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.elapsed_at(Instant::now())
+    }
+
+    fn start_at(&mut self, now: Instant) {
+        self.started = true;
+        self.accumulated = Duration::ZERO;
+        self.resumed = Some(now);
+    }
+
+    fn pause_at(&mut self, now: Instant) {
+        if let Some(resumed) = self.resumed.take() {
+            self.accumulated += now.saturating_duration_since(resumed);
+        }
+    }
+
+    fn resume_at(&mut self, now: Instant) {
+        if self.started && self.resumed.is_none() {
+            self.resumed = Some(now);
+        }
+    }
+
+    fn elapsed_at(&self, now: Instant) -> Option<Duration> {
+        if !self.started {
+            return None;
+        }
+        let running = self
+            .resumed
+            .map(|resumed| now.saturating_duration_since(resumed))
+            .unwrap_or_default();
+        Some(self.accumulated + running)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_solve_timer() {
+        let start = Instant::now();
+        let at = |seconds| start + Duration::from_secs(seconds);
+        let mut timer = SolveTimer::default();
+        assert_eq!(timer.elapsed_at(at(0)), None);
+
+        timer.resume_at(at(0));
+        assert_eq!(timer.elapsed_at(at(1)), None);
+
+        timer.start_at(at(0));
+        timer.pause_at(at(10));
+        timer.pause_at(at(20));
+        assert_eq!(timer.elapsed_at(at(30)), Some(Duration::from_secs(10)));
+
+        timer.resume_at(at(40));
+        timer.resume_at(at(45));
+        assert_eq!(timer.elapsed_at(at(50)), Some(Duration::from_secs(20)));
+
+        timer.start_at(at(60));
+        assert_eq!(timer.elapsed_at(at(65)), Some(Duration::from_secs(5)));
+    }
+}
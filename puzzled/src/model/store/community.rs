@@ -1,18 +1,38 @@
+use crate::global::storage;
+use crate::model::store::file;
 use adw::glib;
-use log::{error, info};
-use std::path::PathBuf;
+use log::{error, info, warn};
+use std::path::{Path, PathBuf};
+
+/// The directory in the data directory, which contains the community collections.
+const COMMUNITY_DIR_NAME: &str = "community_puzzles";
+/// The version of the layout of the community collections directory.
+/// Increase it, when the way collections are stored changes, and migrate older directories.
+const STORAGE_VERSION: u32 = 1;
+/// The file in the community collections directory containing the [STORAGE_VERSION].
+const VERSION_FILE_NAME: &str = "version";
+
+/// The community collections read from the data directory.
+#[derive(Debug, Default)]
+pub struct CommunityCollectionFiles {
+    /// The path and content of each collection file.
+    pub collections: Vec<(PathBuf, String)>,
+    /// The backups of files, which could not be read and were reset.
+    pub recovered: Vec<PathBuf>,
+}
 
 pub fn save_community_collection(collection_id: &str, json_str: &str) {
-    let puzzles_dir = get_xdg_data_dir();
+    let puzzles_dir = get_community_dir();
     let file_path = puzzles_dir.join(format!("{}.json", collection_id));
-    if let Err(e) = std::fs::write(&file_path, json_str) {
+    if let Err(e) = file::write_atomically(&file_path, json_str) {
         error!("Failed to save community collection to file: {}", e);
     }
 }
 
-pub fn load_community_collections() -> Vec<String> {
-    let puzzles_dir = get_xdg_data_dir();
-    let mut collections = Vec::new();
+pub fn load_community_collections() -> CommunityCollectionFiles {
+    let puzzles_dir = get_community_dir();
+    check_storage_version(&puzzles_dir);
+    let mut files = CommunityCollectionFiles::default();
 
     if let Ok(entries) = std::fs::read_dir(&puzzles_dir) {
         for entry in entries.flatten() {
@@ -21,13 +41,22 @@ pub fn load_community_collections() -> Vec<String> {
                 && let Some(ext) = entry.path().extension()
                 && ext == "json"
             {
-                if let Ok(json_str) = std::fs::read_to_string(entry.path()) {
-                    collections.push(json_str);
-                } else {
-                    error!(
-                        "Failed to read community collection file: {:?}",
-                        entry.path()
-                    );
+                match std::fs::read(entry.path()).map(String::from_utf8) {
+                    Ok(Ok(json_str)) => files.collections.push((entry.path(), json_str)),
+                    Ok(Err(_)) => {
+                        error!(
+                            "Community collection file is not valid UTF-8: {:?}",
+                            entry.path()
+                        );
+                        if let Some(backup_path) = recover_damaged_file(&entry.path()) {
+                            files.recovered.push(backup_path);
+                        }
+                    }
+                    Err(e) => error!(
+                        "Failed to read community collection file {:?}: {}",
+                        entry.path(),
+                        e
+                    ),
                 }
             }
         }
@@ -35,11 +64,33 @@ pub fn load_community_collections() -> Vec<String> {
         error!("Failed to read puzzles directory: {:?}", puzzles_dir);
     }
 
-    collections
+    files
+}
+
+/// Backs up the damaged collection file and removes it from the community collections.
+///
+/// returns: the path of the backup or none, if the file could not be backed up
+pub fn recover_damaged_file(file_path: &Path) -> Option<PathBuf> {
+    match file::back_up(file_path) {
+        Ok(backup_path) => {
+            warn!(
+                "Moved damaged community collection file {:?} to {:?}",
+                file_path, backup_path
+            );
+            Some(backup_path)
+        }
+        Err(e) => {
+            error!(
+                "Failed to back up damaged community collection file {:?}: {}",
+                file_path, e
+            );
+            None
+        }
+    }
 }
 
 pub fn delete_community_collection(collection_id: &str) {
-    let puzzles_dir = get_xdg_data_dir();
+    let puzzles_dir = get_community_dir();
     let file_path = puzzles_dir.join(format!("{}.json", collection_id));
     if !file_path.exists() {
         info!(
@@ -53,11 +104,89 @@ pub fn delete_community_collection(collection_id: &str) {
     }
 }
 
-fn get_xdg_data_dir() -> PathBuf {
-    let xdg_data_dir = glib::user_data_dir();
-    let puzzles_dir = xdg_data_dir.join("../../..").join("community_puzzles");
+/// Moves the community collections from the directory used by older versions of Puzzled to the
+/// given directory, unless it exists already.
+fn migrate_legacy_dir(puzzles_dir: &Path) {
+    if storage::is_portable() || puzzles_dir.exists() {
+        return;
+    }
+    let legacy_dir = glib::user_data_dir()
+        .join("../../..")
+        .join(COMMUNITY_DIR_NAME);
+    if !legacy_dir.is_dir() {
+        return;
+    }
+    if let Some(parent) = puzzles_dir.parent()
+        && let Err(e) = std::fs::create_dir_all(parent)
+    {
+        error!("Failed to create data directory: {}", e);
+        return;
+    }
+    match std::fs::rename(&legacy_dir, puzzles_dir) {
+        Ok(()) => info!(
+            "Moved community collections from {:?} to {:?}",
+            legacy_dir, puzzles_dir
+        ),
+        Err(e) => error!(
+            "Failed to move community collections from {:?}: {}",
+            legacy_dir, e
+        ),
+    }
+}
+
+/// Checks the version of the community collections directory and writes the current version, if
+/// there is none yet or it is unreadable.
+fn check_storage_version(puzzles_dir: &Path) {
+    let version_file = puzzles_dir.join(VERSION_FILE_NAME);
+    let version = std::fs::read_to_string(&version_file)
+        .ok()
+        .and_then(|version| version.trim().parse::<u32>().ok());
+    match version {
+        Some(version) if version > STORAGE_VERSION => warn!(
+            "Community collections were stored by a newer version of Puzzled (storage version {}).",
+            version
+        ),
+        Some(_) => {}
+        None => {
+            if let Err(e) = file::write_atomically(&version_file, &STORAGE_VERSION.to_string()) {
+                error!("Failed to write community collections version: {}", e);
+            }
+        }
+    }
+}
+
+fn get_community_dir() -> PathBuf {
+    let puzzles_dir = storage::shared_data_dir().join(COMMUNITY_DIR_NAME);
+    migrate_legacy_dir(&puzzles_dir);
     if let Err(e) = std::fs::create_dir_all(&puzzles_dir) {
         error!("Failed to create puzzles directory: {}", e);
     }
     puzzles_dir
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::global::storage::DirectoryStorageBackend;
+
+    #[test]
+    fn test_save_and_load_community_collections() {
+        let root = std::env::temp_dir().join(format!("puzzled-community-{}", std::process::id()));
+        storage::set_backend(DirectoryStorageBackend::new(root.clone()));
+
+        save_community_collection("de.til7701.test", "{}");
+        let files = load_community_collections();
+
+        let puzzles_dir = root.join("data").join(COMMUNITY_DIR_NAME);
+        assert_eq!(
+            files.collections,
+            vec![(puzzles_dir.join("de.til7701.test.json"), "{}".to_string())]
+        );
+        assert!(files.recovered.is_empty());
+        assert_eq!(
+            std::fs::read_to_string(puzzles_dir.join(VERSION_FILE_NAME)).unwrap(),
+            STORAGE_VERSION.to_string()
+        );
+        std::fs::remove_dir_all(root).unwrap();
+    }
+}
@@ -0,0 +1,81 @@
+use std::fs::File;
+use std::io;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Extension appended to the name of damaged files, when they are backed up.
+const BACKUP_EXTENSION: &str = "bak";
+
+/// Writes the contents to the file at the given path, so that the file either contains the old
+/// or the new contents, even if the app crashes while writing.
+///
+/// The contents are written to a temporary file next to the target first, which then replaces
+/// the target.
+pub fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    let temp_path = with_appended_extension(path, "tmp");
+    let result = (|| {
+        let mut file = File::create(&temp_path)?;
+        file.write_all(contents.as_bytes())?;
+        file.sync_all()?;
+        std::fs::rename(&temp_path, path)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&temp_path);
+    }
+    result
+}
+
+/// Moves the damaged file at the given path out of the way, so it is not read again.
+/// An older backup of the same file is replaced.
+///
+/// returns: the path of the backup
+pub fn back_up(path: &Path) -> io::Result<PathBuf> {
+    let backup_path = with_appended_extension(path, BACKUP_EXTENSION);
+    std::fs::rename(path, &backup_path)?;
+    Ok(backup_path)
+}
+
+fn with_appended_extension(path: &Path, extension: &str) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(".");
+    file_name.push(extension);
+    path.with_file_name(file_name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("puzzled-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_write_atomically() {
+        let dir = temp_dir("write-atomically");
+        let path = dir.join("collection.json");
+
+        write_atomically(&path, "old").unwrap();
+        write_atomically(&path, "new").unwrap();
+
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "new");
+        assert!(!dir.join("collection.json.tmp").exists());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_back_up() {
+        let dir = temp_dir("back-up");
+        let path = dir.join("collection.json");
+        std::fs::write(&path, "damaged").unwrap();
+
+        let backup_path = back_up(&path).unwrap();
+
+        assert_eq!(backup_path, dir.join("collection.json.bak"));
+        assert!(!path.exists());
+        assert_eq!(std::fs::read_to_string(backup_path).unwrap(), "damaged");
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
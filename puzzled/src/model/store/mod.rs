@@ -1,13 +1,25 @@
 mod community;
+pub mod file;
 
 use crate::config;
+use crate::global::storage;
 use crate::model::collection::CollectionModel;
 use crate::model::puzzle_meta::PuzzleMeta;
 use crate::model::store::community::save_community_collection;
 use adw::gio::{resources_lookup_data, ResourceLookupFlags};
+use adw::glib;
 use log::error;
-use puzzle_config::{JsonLoader, PuzzleConfigCollection, ReadError};
+use puzzle_config::{
+    CollectionSummary, Ed25519Verifier, JsonLoader, PreviewConfig, ProgressionConfig,
+    PuzzleConfigCollection, ReadError,
+};
 use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io;
+use std::path::PathBuf;
+
+/// The file with the public keys of trusted collection authors.
+const TRUSTED_KEYS_FILE_NAME: &str = "trusted-keys.json";
 
 const CORE_COLLECTIONS: [&str; 9] = [
     "puzzle_a_day",
@@ -24,6 +36,9 @@ const CORE_COLLECTIONS: [&str; 9] = [
 thread_local! {
     static PUZZLE_COLLECTION_STORE: RefCell<PuzzleCollectionStore> =
         RefCell::new(PuzzleCollectionStore::default());
+    /// The loader is kept, because collections are loaded one by one, when they are first needed.
+    /// It knows the namespaces of all community collections.
+    static JSON_LOADER: RefCell<JsonLoader> = RefCell::new(create_json_loader());
 }
 
 /// Provides access to the singleton PuzzleCollectionStore instance, which holds the core and
@@ -42,10 +57,18 @@ pub fn with_puzzle_collection_store<R>(f: impl FnOnce(&mut PuzzleCollectionStore
 ///
 /// Get the singleton by calling [get_puzzle_collection_store]().
 /// The store is initialized by calling [init]() once at application startup.
+///
+/// Only the summaries of the collections are loaded at startup. The puzzles of a collection are
+/// loaded, when they are first accessed through its [CollectionModel].
 #[derive(Debug, Default)]
 pub struct PuzzleCollectionStore {
     core_puzzle_collections: Vec<CollectionModel>,
     community_puzzle_collections: Vec<CollectionModel>,
+    /// The JSON of each core collection mapped by collection id.
+    core_collection_json: HashMap<String, String>,
+    /// The JSON of each community collection mapped by collection id.
+    community_collection_json: HashMap<String, String>,
+    recovered_files: Vec<PathBuf>,
 }
 
 impl PuzzleCollectionStore {
@@ -57,10 +80,25 @@ impl PuzzleCollectionStore {
         &self.community_puzzle_collections
     }
 
+    /// Returns the JSON, from which the given collection was loaded.
+    pub fn collection_json(&self, collection: &CollectionModel) -> Option<&str> {
+        let id = collection.summary().id();
+        if self.core_puzzle_collections.contains(collection) {
+            self.core_collection_json.get(id).map(String::as_str)
+        } else {
+            self.community_collection_json.get(id).map(String::as_str)
+        }
+    }
+
+    /// The backups of damaged files, which were reset while loading the store.
+    pub fn recovered_files(&self) -> &[PathBuf] {
+        &self.recovered_files
+    }
+
     pub fn find_community_collection_by_id(&self, id: &str) -> Option<&CollectionModel> {
         self.community_puzzle_collections
             .iter()
-            .find(|collection| collection.config().id() == id)
+            .find(|collection| collection.summary().id() == id)
     }
 
     /// Adds a community collection from the provided JSON string.
@@ -76,7 +114,10 @@ impl PuzzleCollectionStore {
         collection: PuzzleConfigCollection,
         json_str: &str,
     ) -> Result<(), ReadError> {
+        JSON_LOADER.with_borrow_mut(|json_loader| json_loader.register_namespace(json_str))?;
         save_community_collection(collection.id(), json_str);
+        self.community_collection_json
+            .insert(collection.id().to_string(), json_str.to_string());
         self.community_puzzle_collections
             .push(CollectionModel::new(collection, &PuzzleMeta::new()));
         Ok(())
@@ -96,71 +137,180 @@ impl PuzzleCollectionStore {
     /// returns: ()
     pub fn remove_community_collection(&mut self, collection_id: &str) {
         self.community_puzzle_collections
-            .retain(|collection| collection.config().id() != collection_id);
+            .retain(|collection| collection.summary().id() != collection_id);
+        self.community_collection_json.remove(collection_id);
         community::delete_community_collection(collection_id);
     }
 
     /// Triggers all collections to mark themselves as unsolved.
     /// This updates the backend using [PuzzleMeta] and informs the collection models.
+    /// Collections, which are not loaded yet, read the reset progress once they are loaded.
     pub fn mark_all_as_unsolved(&self) {
         PuzzleMeta::new().reset();
-        for collection in &self.core_puzzle_collections {
-            collection.mark_all_as_unsolved();
-        }
-        for collection in &self.community_puzzle_collections {
+        for collection in self
+            .core_puzzle_collections
+            .iter()
+            .chain(&self.community_puzzle_collections)
+            .filter(|collection| collection.is_loaded())
+        {
             collection.mark_all_as_unsolved();
         }
     }
 }
 
-/// Must be called once at application startup to load the core and community puzzle collections into the store.
+/// Must be called once at application startup to load the summaries of the core and community
+/// puzzle collections into the store.
 ///
 /// A second call has undefined behavior.
 pub fn init() {
     PUZZLE_COLLECTION_STORE.with_borrow_mut(|store| {
-        let json_loader = create_json_loader();
-        let puzzle_meta = PuzzleMeta::new();
-
-        for &collection_name in CORE_COLLECTIONS.iter() {
-            let path = format!("/de/til7701/Puzzled/puzzles/{}.json", collection_name);
-            let collection = load_core_from_resource(&path, &json_loader);
-            store
-                .core_puzzle_collections
-                .push(CollectionModel::new(collection, &puzzle_meta));
-        }
+        JSON_LOADER.with_borrow_mut(|json_loader| init_store(store, json_loader));
+    });
+}
 
-        let community_collections = community::load_community_collections();
-        for json_str in community_collections {
-            let collection = match json_loader.load_puzzle_collection(&json_str) {
-                Ok(collection) => collection,
-                Err(e) => {
-                    error!(
-                        "Failed to load community puzzle collection from JSON string: {:?}",
-                        e
-                    );
-                    continue;
+fn init_store(store: &mut PuzzleCollectionStore, json_loader: &mut JsonLoader) {
+    let puzzle_meta = PuzzleMeta::new();
+    if let Some(backup_path) = puzzle_meta.recover_damaged_entries() {
+        store.recovered_files.push(backup_path);
+    }
+
+    for &collection_name in CORE_COLLECTIONS.iter() {
+        let path = format!("/de/til7701/Puzzled/puzzles/{}.json", collection_name);
+        let json_str = read_resource(&path);
+        let summary = match json_loader.load_collection_summary(&json_str) {
+            Ok(summary) => summary,
+            Err(e) => panic!(
+                "Failed to load core puzzle collection from '{}': {:?}",
+                path, e
+            ),
+        };
+        store
+            .core_collection_json
+            .insert(summary.id().to_string(), json_str.clone());
+        store
+            .core_puzzle_collections
+            .push(CollectionModel::from_summary(summary, json_str, true));
+    }
+
+    let community_collections = community::load_community_collections();
+    store
+        .recovered_files
+        .extend(community_collections.recovered);
+    for (path, json_str) in community_collections.collections {
+        let summary = match json_loader.load_collection_summary(&json_str) {
+            Ok(summary) => summary,
+            Err(e) => {
+                error!(
+                    "Failed to load community puzzle collection from JSON string: {:?}",
+                    e
+                );
+                if is_damaged(&e)
+                    && let Some(backup_path) = community::recover_damaged_file(&path)
+                {
+                    store.recovered_files.push(backup_path);
                 }
-            };
-            store
-                .community_puzzle_collections
-                .push(CollectionModel::new(collection, &puzzle_meta));
+                continue;
+            }
+        };
+        store
+            .community_collection_json
+            .insert(summary.id().to_string(), json_str.clone());
+        store
+            .community_puzzle_collections
+            .push(CollectionModel::from_summary(summary, json_str, false));
+    }
+    // All namespaces are registered before any community collection is loaded, so collections
+    // can reference each other regardless of their order.
+    for json_str in store.community_collection_json.values() {
+        if let Err(e) = json_loader.register_namespace(json_str) {
+            error!("Failed to register the namespace of a collection: {:?}", e);
         }
-    });
+    }
+}
+
+/// Loads a collection, which is not stored yet, e.g. to import it. The tiles and boards of the
+/// namespaces of the community collections can be referenced.
+pub fn load_community_collection(json_str: &str) -> Result<PuzzleConfigCollection, ReadError> {
+    JSON_LOADER.with_borrow(|json_loader| json_loader.load_puzzle_collection(json_str))
 }
 
-/// Loads a core puzzle collection from a resource file using the provided JsonLoader.
+/// Loads the whole collection, of which only the summary was loaded at startup.
 ///
-/// Panics if the resource cannot be found or if loading the collection fails.
+/// Panics if loading a core collection fails. If a community collection cannot be loaded, the
+/// error is logged and an empty collection is returned, so the rest of the app keeps working.
 ///
 /// # Arguments
 ///
-/// * `filename`: The path to the resource file containing the puzzle collection JSON. This must be a valid resource path, e.g., "/de/til7701/Puzzled/puzzles/puzzle_a_day.json".
+/// * `summary`: The summary loaded from the same JSON at startup.
+/// * `json_str`: The JSON of the collection.
+/// * `core`: true, if the collection is a core collection. False otherwise.
+///
+/// returns: PuzzleConfigCollection
+pub(crate) fn load_collection(
+    summary: &CollectionSummary,
+    json_str: &str,
+    core: bool,
+) -> PuzzleConfigCollection {
+    JSON_LOADER.with_borrow(|json_loader| {
+        if core {
+            return load_core_from_string(summary.id(), json_str, json_loader);
+        }
+        match json_loader.load_puzzle_collection(json_str) {
+            Ok(collection) => collection,
+            Err(e) => {
+                error!(
+                    "Failed to load community puzzle collection '{}': {:?}",
+                    summary.id(),
+                    e
+                );
+                PuzzleConfigCollection::new(
+                    summary.name().to_string(),
+                    summary.description().clone(),
+                    summary.author().to_string(),
+                    summary.id().to_string(),
+                    summary.version().clone(),
+                    ProgressionConfig::Any,
+                    PreviewConfig::default(),
+                    Vec::new(),
+                    true,
+                    None,
+                    Vec::new(),
+                )
+            }
+        }
+    })
+}
+
+/// Returns true, if the error indicates that a stored collection file is damaged.
+/// Other errors, like collections requiring a newer version of the app, leave the file untouched.
+fn is_damaged(error: &ReadError) -> bool {
+    matches!(
+        error,
+        ReadError::JsonError(_)
+            | ReadError::InvalidJson { .. }
+            | ReadError::MissingVersion
+            | ReadError::MalformedVersion
+            | ReadError::ChecksumMismatch
+    )
+}
+
+/// Loads a core puzzle collection read from a resource file using the provided JsonLoader.
+///
+/// Panics if loading the collection fails.
+///
+/// # Arguments
+///
+/// * `filename`: The name of the puzzle collection, e.g., its id. It is used in the panic message.
+/// * `json_str`: The content of the resource file.
 /// * `json_loader`: The JsonLoader instance to use.
 ///
 /// returns: PuzzleConfigCollection
-fn load_core_from_resource(filename: &str, json_loader: &JsonLoader) -> PuzzleConfigCollection {
-    let json_str = read_resource(filename);
-    match json_loader.load_puzzle_collection(&json_str) {
+fn load_core_from_string(
+    filename: &str,
+    json_str: &str,
+    json_loader: &JsonLoader,
+) -> PuzzleConfigCollection {
+    match json_loader.load_puzzle_collection(json_str) {
         Ok(collection) => collection,
         Err(e) => panic!(
             "Failed to load core puzzle collection from '{}': {:?}",
@@ -170,15 +320,64 @@ fn load_core_from_resource(filename: &str, json_loader: &JsonLoader) -> PuzzleCo
 }
 
 /// Creates a JsonLoader and adds predefined tiles from the predefined JSON resource.
+/// Signatures of collections are verified with the trusted keys, if there are any.
+/// Months are named in the locale of the app.
 pub fn create_json_loader() -> JsonLoader {
     let predefined_json_str = read_resource("/de/til7701/Puzzled/predefined.json");
-    puzzle_config::create_json_loader(&predefined_json_str, config::VERSION).unwrap()
+    let mut json_loader =
+        puzzle_config::create_json_loader(&predefined_json_str, config::VERSION).unwrap();
+    if let Some(verifier) = trusted_keys() {
+        json_loader.set_signature_verifier(Box::new(verifier));
+    }
+    if let Some(month_names) = locale_month_names() {
+        json_loader.set_month_names(month_names);
+    }
+    json_loader
+}
+
+/// Returns the full names of the months in the locale of the app starting with January.
+///
+/// returns: Option<[String; 12]> None, if a month could not be formatted
+fn locale_month_names() -> Option<[String; 12]> {
+    let month_names = (1..=12)
+        .map(|month| {
+            glib::DateTime::from_utc(2000, month, 1, 0, 0, 0.0)
+                .and_then(|date| date.format("%B"))
+                .map(|name| name.to_string())
+                .ok()
+        })
+        .collect::<Option<Vec<String>>>()?;
+    month_names.try_into().ok()
+}
+
+/// Reads the keys of authors, whose signed collections are shown as verified.
+/// They are stored in a file shared by all profiles, which does not exist by default.
+///
+/// returns: Option<Ed25519Verifier> None, if no keys are trusted or the file is invalid
+fn trusted_keys() -> Option<Ed25519Verifier> {
+    let path = storage::shared_data_dir().join(TRUSTED_KEYS_FILE_NAME);
+    let json = match std::fs::read_to_string(&path) {
+        Ok(json) => json,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return None,
+        Err(e) => {
+            error!("Failed to read the trusted keys: {}", e);
+            return None;
+        }
+    };
+    match Ed25519Verifier::from_json(&json) {
+        Ok(verifier) if !verifier.is_empty() => Some(verifier),
+        Ok(_) => None,
+        Err(e) => {
+            error!("Failed to parse the trusted keys: {:?}", e);
+            None
+        }
+    }
 }
 
 /// Convenience function to read a resource file as a string.
 ///
 /// Panics if the resource cannot be found or read.
-fn read_resource(filename: &str) -> String {
+pub fn read_resource(filename: &str) -> String {
     let data = resources_lookup_data(filename, ResourceLookupFlags::NONE).unwrap();
     std::str::from_utf8(&data).unwrap().to_string()
 }
@@ -209,6 +408,23 @@ mod tests {
         }
     }
 
+    /// Ensures the summaries loaded at startup match the collections loaded later
+    #[test]
+    fn test_core_collection_summaries() {
+        let predefined_json_str =
+            fs::read_to_string(&"resources/predefined.json".to_string()).unwrap();
+        let json_loader =
+            puzzle_config::create_json_loader(&predefined_json_str, config::VERSION).unwrap();
+
+        for collection_name in CORE_COLLECTIONS.iter() {
+            let json =
+                fs::read_to_string(&format!("resources/puzzles/{}.json", collection_name)).unwrap();
+            let summary = json_loader.load_collection_summary(&json).unwrap();
+            let collection = json_loader.load_puzzle_collection(&json).unwrap();
+            assert_eq!(summary, CollectionSummary::from(&collection));
+        }
+    }
+
     /// Ensures solvability of puzzles in core collections that are not known to be unsolvable or take too long to solve.
     #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
     async fn test_solve_core_collections() {
@@ -289,6 +505,14 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_is_damaged() {
+        assert!(is_damaged(&ReadError::JsonError("EOF".to_string())));
+        assert!(is_damaged(&ReadError::ChecksumMismatch));
+        assert!(!is_damaged(&ReadError::UnsupportedVersion));
+        assert!(!is_damaged(&ReadError::UnsupportedFormat(2)));
+    }
+
     /// Ensures unique collection ids
     #[test]
     fn test_core_collections_ids() {
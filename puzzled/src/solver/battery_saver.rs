@@ -0,0 +1,81 @@
+use crate::global::settings::{BatterySaver, BatterySaverMode, Preferences};
+use adw::gio::prelude::PowerProfileMonitorExt;
+use adw::gio::PowerProfileMonitor;
+use log::debug;
+use std::cell::OnceCell;
+use std::sync::LazyLock;
+use tokio::sync::{watch, Semaphore, SemaphorePermit};
+use tokio_util::sync::CancellationToken;
+
+/// The restriction for background solver calls currently in effect.
+/// This is [BatterySaverMode::Off], unless the system is in power saver mode.
+static ACTIVE_MODE: LazyLock<watch::Sender<BatterySaverMode>> =
+    LazyLock::new(|| watch::Sender::new(BatterySaverMode::Off));
+
+/// Limits the background solver calls running at the same time in [BatterySaverMode::Reduced].
+static REDUCED_PERMITS: Semaphore = Semaphore::const_new(1);
+
+thread_local! {
+    /// Keeps the monitor and the settings alive, so changes are still reported.
+    static MONITOR: OnceCell<(PowerProfileMonitor, Preferences)> = const { OnceCell::new() };
+}
+
+/// Starts following the power saver mode of the system and the battery saver setting.
+/// Calling this again has no effect.
+///
+/// This must be called on the main thread.
+pub fn monitor() {
+    MONITOR.with(|cell| {
+        cell.get_or_init(|| {
+            let monitor = PowerProfileMonitor::get_default();
+            let preferences = Preferences::default();
+            monitor.connect_power_saver_enabled_notify(|_| update_active_mode());
+            preferences.connect_changed(BatterySaver, update_active_mode);
+            (monitor, preferences)
+        });
+    });
+    update_active_mode();
+}
+
+fn update_active_mode() {
+    let mode = MONITOR.with(|cell| {
+        let (monitor, preferences) = cell.get()?;
+        Some(if monitor.is_power_saver_enabled() {
+            preferences.get(BatterySaver)
+        } else {
+            BatterySaverMode::Off
+        })
+    });
+    let mode = mode.unwrap_or(BatterySaverMode::Off);
+    debug!("Battery saver mode for background solver calls: {:?}", mode);
+    ACTIVE_MODE.send_replace(mode);
+}
+
+/// Waits until a background solver call may run according to the battery saver.
+///
+/// The returned permit must be kept until the solver call finished. `None` is returned, if the
+/// call is not restricted or was cancelled while waiting.
+pub(super) async fn background_permit(
+    cancel_token: &CancellationToken,
+) -> Option<SemaphorePermit<'static>> {
+    tokio::select! {
+        permit = wait_for_permit() => permit,
+        _ = cancel_token.cancelled() => None,
+    }
+}
+
+async fn wait_for_permit() -> Option<SemaphorePermit<'static>> {
+    let mut receiver = ACTIVE_MODE.subscribe();
+    loop {
+        let mode = *receiver.borrow_and_update();
+        match mode {
+            BatterySaverMode::Off => return None,
+            BatterySaverMode::Reduced => return REDUCED_PERMITS.acquire().await.ok(),
+            BatterySaverMode::Deferred => {
+                if receiver.changed().await.is_err() {
+                    return None;
+                }
+            }
+        }
+    }
+}
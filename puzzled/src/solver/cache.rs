@@ -0,0 +1,408 @@
+use crate::global::storage;
+use crate::model::store::file;
+use crate::solver::target_boards::TargetBoards;
+use log::{error, warn};
+use puzzle_solver::board::Board;
+use puzzle_solver::result::{Solution, TilePlacement, UnsolvableReason};
+use puzzle_solver::tile::Tile;
+use puzzled_common::{Shape, ShapeType};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// The file in the cache directory, which contains the cached results.
+const CACHE_FILE_NAME: &str = "solver-cache.json";
+/// The maximum number of results kept in the cache.
+/// The least recently used result is dropped, when a new one is added to a full cache.
+const MAX_ENTRIES: usize = 256;
+
+/// The cached results, which are read from the file on first use.
+/// Shared by all solver calls, so calls on different threads do not overwrite each other's
+/// changes.
+static ENTRIES: Mutex<Option<LoadedEntries>> = Mutex::new(None);
+
+/// A result stored in the cache.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(tag = "type")]
+enum CachedResult {
+    Solved {
+        placements: Vec<CachedPlacement>,
+        trim_offset: (usize, usize),
+    },
+    /// No tile combination fills the board.
+    /// Other reasons are found quickly without a search, so they are not cached.
+    Unsolvable,
+}
+
+/// A placement, which references the tile and rotation by their index, so no shapes have to be
+/// stored.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+struct CachedPlacement {
+    /// The index of the tile in all tiles of the solver call.
+    tile: usize,
+    /// The index of the rotation in [Shape::unique_rotations_flips] of the tile.
+    rotation: usize,
+    position: (usize, usize),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+struct CacheEntry {
+    key: String,
+    result: CachedResult,
+}
+
+/// The entries of the cache file with the most recently used entry first.
+#[derive(Debug)]
+struct LoadedEntries {
+    entries: Vec<CacheEntry>,
+    /// Whether the order of the entries changed since they were written to the file.
+    unsaved: bool,
+}
+
+/// Caches the results of solver calls on disk, so searching for the same board does not have to
+/// be repeated after the app was restarted.
+///
+/// The directory is resolved on creation, so the cache can be used on the threads of the solver.
+#[derive(Debug, Clone)]
+pub struct SolverCache {
+    path: PathBuf,
+}
+
+impl Default for SolverCache {
+    fn default() -> Self {
+        SolverCache {
+            path: storage::cache_dir().join(CACHE_FILE_NAME),
+        }
+    }
+}
+
+impl SolverCache {
+    /// Returns the cached result for the given key.
+    /// The result is moved to the front, so it is the last one to be dropped. The new order is
+    /// written to the file with the next stored result or by [Self::save].
+    ///
+    /// # Arguments
+    ///
+    /// * `key`: the key created by [cache_key]
+    /// * `tiles`: all tiles of the solver call, see [all_tiles]
+    ///
+    /// returns: Option<Result<Solution, UnsolvableReason>>
+    pub fn get(&self, key: &str, tiles: &[Shape]) -> Option<Result<Solution, UnsolvableReason>> {
+        let mut loaded = ENTRIES.lock().unwrap();
+        let loaded = self.loaded(&mut loaded);
+        let position = loaded.entries.iter().position(|entry| entry.key == key)?;
+        let result = decode_result(&loaded.entries[position].result, tiles)?;
+        if position > 0 {
+            let entry = loaded.entries.remove(position);
+            loaded.entries.insert(0, entry);
+            loaded.unsaved = true;
+        }
+        Some(result)
+    }
+
+    /// Stores the result for the given key.
+    /// Partial solutions and results of cancelled calls are not stored.
+    ///
+    /// # Arguments
+    ///
+    /// * `key`: the key created by [cache_key]
+    /// * `tiles`: all tiles of the solver call, see [all_tiles]
+    /// * `result`: the result of the solver call
+    pub fn put(&self, key: &str, tiles: &[Shape], result: &Result<Solution, UnsolvableReason>) {
+        let Some(result) = encode_result(result, tiles) else {
+            return;
+        };
+        let mut loaded = ENTRIES.lock().unwrap();
+        let loaded = self.loaded(&mut loaded);
+        insert_entry(
+            &mut loaded.entries,
+            CacheEntry {
+                key: key.to_string(),
+                result,
+            },
+        );
+        self.write_entries(&loaded.entries);
+        loaded.unsaved = false;
+    }
+
+    /// Writes the order of the results to the file, if it changed since the last write.
+    /// Called when the app shuts down.
+    pub fn save(&self) {
+        let mut loaded = ENTRIES.lock().unwrap();
+        if let Some(loaded) = loaded.as_mut().filter(|loaded| loaded.unsaved) {
+            self.write_entries(&loaded.entries);
+            loaded.unsaved = false;
+        }
+    }
+
+    /// Removes all cached results.
+    pub fn clear(&self) -> io::Result<()> {
+        let mut loaded = ENTRIES.lock().unwrap();
+        *loaded = Some(LoadedEntries {
+            entries: Vec::new(),
+            unsaved: false,
+        });
+        match std::fs::remove_file(&self.path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the entries, which are read from the file, if they were not used before.
+    fn loaded<'a>(&self, loaded: &'a mut Option<LoadedEntries>) -> &'a mut LoadedEntries {
+        loaded.get_or_insert_with(|| LoadedEntries {
+            entries: self.read_entries(),
+            unsaved: false,
+        })
+    }
+
+    fn read_entries(&self) -> Vec<CacheEntry> {
+        let content = match std::fs::read_to_string(&self.path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Vec::new(),
+            Err(e) => {
+                error!("Failed to read the solver cache: {}", e);
+                return Vec::new();
+            }
+        };
+        serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!("Ignoring the damaged solver cache: {}", e);
+            Vec::new()
+        })
+    }
+
+    fn write_entries(&self, entries: &[CacheEntry]) {
+        let result = serde_json::to_string(entries)
+            .map_err(io::Error::other)
+            .and_then(|json| {
+                if let Some(dir) = self.path.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                file::write_atomically(&self.path, &json)
+            });
+        if let Err(e) = result {
+            error!("Failed to write the solver cache: {}", e);
+        }
+    }
+}
+
+/// Creates the key of a solver call.
+/// The boards already contain the target, the placed tiles and the reserved cells, so together
+/// with the cells, which may stay uncovered, and the tiles they describe the puzzle completely.
+///
+/// The key is the SHA-256 hash of the puzzle as JSON, so it stays the same between versions of
+/// the app.
+pub fn cache_key(attempts: &[(TargetBoards, Vec<Tile>)]) -> String {
+    let description: Vec<AttemptDescription> = attempts
+        .iter()
+        .map(|(boards, tiles)| AttemptDescription {
+            board: describe_shape(boards.board().get_shape()),
+            reserved_cells: reserved_cells(boards.board()),
+            uncovered_cells: boards.uncovered_cells(),
+            tiles: tiles
+                .iter()
+                .map(|tile| describe_shape(tile.base()))
+                .collect(),
+        })
+        .collect();
+    let json = serde_json::to_vec(&description).expect("Failed to serialize solver cache key");
+    format!("{:x}", Sha256::digest(json))
+}
+
+/// A single attempt of a solver call as it is hashed for the cache key.
+#[derive(Serialize)]
+struct AttemptDescription<'a> {
+    board: ShapeDescription,
+    /// The cells reserved for a tile with the tile they are reserved for, in a stable order.
+    reserved_cells: Vec<((usize, usize), ShapeDescription)>,
+    uncovered_cells: &'a [Vec<(usize, usize)>],
+    tiles: Vec<ShapeDescription>,
+}
+
+/// A shape with its cells as rows like in the collection format.
+/// The quarters of the cells are only included, if the shape has half cells.
+#[derive(Serialize)]
+struct ShapeDescription {
+    #[serde(rename = "type")]
+    shape_type: &'static str,
+    cells: Vec<Vec<bool>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quarters: Option<Vec<Vec<u8>>>,
+}
+
+fn describe_shape(shape: &Shape) -> ShapeDescription {
+    let (width, height) = shape.dim();
+    ShapeDescription {
+        shape_type: match shape.shape_type() {
+            ShapeType::Square => "square",
+            ShapeType::Triangle => "triangle",
+            ShapeType::Hexagon => "hexagon",
+        },
+        cells: (0..height)
+            .map(|y| (0..width).map(|x| shape[(x, y)]).collect())
+            .collect(),
+        quarters: shape.has_half_cells().then(|| {
+            (0..height)
+                .map(|y| (0..width).map(|x| shape.quarters((x, y))).collect())
+                .collect()
+        }),
+    }
+}
+
+fn reserved_cells(board: &Board) -> Vec<((usize, usize), ShapeDescription)> {
+    board
+        .get_shape()
+        .indexed_iter()
+        .filter_map(|(position, _)| {
+            board
+                .reserved_tile([position.0, position.1])
+                .map(|tile| (position, describe_shape(tile.base())))
+        })
+        .collect()
+}
+
+/// Returns the bases of all tiles of all attempts of a solver call.
+/// The cached placements reference the tiles by their index in this list.
+pub fn all_tiles(attempts: &[(TargetBoards, Vec<Tile>)]) -> Vec<Shape> {
+    attempts
+        .iter()
+        .flat_map(|(_, tiles)| tiles.iter().map(|tile| tile.base().clone()))
+        .collect()
+}
+
+/// Adds the entry to the front and drops the least recently used entries, if there are too many.
+fn insert_entry(entries: &mut Vec<CacheEntry>, entry: CacheEntry) {
+    entries.retain(|existing| existing.key != entry.key);
+    entries.insert(0, entry);
+    entries.truncate(MAX_ENTRIES);
+}
+
+fn encode_result(
+    result: &Result<Solution, UnsolvableReason>,
+    tiles: &[Shape],
+) -> Option<CachedResult> {
+    match result {
+        Ok(solution) if solution.is_complete() => {
+            let placements = solution
+                .placements()
+                .iter()
+                .map(|placement| {
+                    let tile = tiles.iter().position(|base| base == placement.base())?;
+                    let rotation = placement
+                        .base()
+                        .unique_rotations_flips()
+                        .iter()
+                        .position(|rotation| rotation == placement.rotation())?;
+                    Some(CachedPlacement {
+                        tile,
+                        rotation,
+                        position: placement.position(),
+                    })
+                })
+                .collect::<Option<Vec<CachedPlacement>>>()?;
+            Some(CachedResult::Solved {
+                placements,
+                trim_offset: solution.trim_offset(),
+            })
+        }
+        Err(UnsolvableReason::NoFit) => Some(CachedResult::Unsolvable),
+        _ => None,
+    }
+}
+
+fn decode_result(
+    result: &CachedResult,
+    tiles: &[Shape],
+) -> Option<Result<Solution, UnsolvableReason>> {
+    match result {
+        CachedResult::Solved {
+            placements,
+            trim_offset,
+        } => {
+            let placements = placements
+                .iter()
+                .map(|placement| {
+                    let base = tiles.get(placement.tile)?;
+                    let rotation = base
+                        .unique_rotations_flips()
+                        .get(placement.rotation)?
+                        .clone();
+                    Some(TilePlacement::new(
+                        base.clone(),
+                        rotation,
+                        placement.position,
+                    ))
+                })
+                .collect::<Option<Vec<TilePlacement>>>()?;
+            Some(Ok(Solution::restored(placements, *trim_offset)))
+        }
+        CachedResult::Unsolvable => Some(Err(UnsolvableReason::NoFit)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use puzzled_common::shape::shape_square;
+
+    fn entry(key: &str) -> CacheEntry {
+        CacheEntry {
+            key: key.to_string(),
+            result: CachedResult::Unsolvable,
+        }
+    }
+
+    #[test]
+    fn test_insert_entry() {
+        let mut entries = vec![entry("a"), entry("b")];
+
+        insert_entry(&mut entries, entry("b"));
+        assert_eq!(entries, vec![entry("b"), entry("a")]);
+
+        for i in 0..MAX_ENTRIES {
+            insert_entry(&mut entries, entry(&i.to_string()));
+        }
+        assert_eq!(entries.len(), MAX_ENTRIES);
+        assert_eq!(entries[0], entry(&(MAX_ENTRIES - 1).to_string()));
+    }
+
+    #[test]
+    fn test_encode_decode_result() {
+        let l_tile = shape_square(&[[true, false], [true, true]]);
+        let i_tile = shape_square(&[[true, true]]);
+        let tiles = vec![i_tile.clone(), l_tile.clone()];
+        let rotation = l_tile.unique_rotations_flips()[2].clone();
+        let solution = Solution::restored(
+            vec![
+                TilePlacement::new(l_tile.clone(), rotation.clone(), (1, 0)),
+                TilePlacement::new(i_tile.clone(), i_tile.clone(), (0, 2)),
+            ],
+            (1, 0),
+        );
+
+        let encoded = encode_result(&Ok(solution), &tiles).unwrap();
+        let Some(Ok(decoded)) = decode_result(&encoded, &tiles) else {
+            panic!("Expected a solution");
+        };
+        assert!(decoded.is_complete());
+        assert_eq!(decoded.trim_offset(), (1, 0));
+        assert_eq!(
+            decoded.placements(),
+            &[
+                TilePlacement::new(l_tile, rotation, (1, 0)),
+                TilePlacement::new(i_tile.clone(), i_tile, (0, 2)),
+            ]
+        );
+
+        assert_eq!(
+            encode_result(&Err(UnsolvableReason::NoFit), &tiles),
+            Some(CachedResult::Unsolvable)
+        );
+        assert_eq!(
+            encode_result(&Err(UnsolvableReason::Cancelled), &tiles),
+            None
+        );
+    }
+}
@@ -1,6 +1,6 @@
 use crate::app::puzzle::puzzle_area::puzzle_state::{PuzzleState, UnusedTile};
-use crate::global::runtime::get_runtime;
-use crate::solver::Solver;
+use crate::solver::queue::SolverPriority;
+use crate::solver::{queue, Solver};
 use log::{debug, info};
 use std::collections::HashSet;
 use std::sync::{Arc, RwLock};
@@ -26,7 +26,7 @@ impl CombinationsSolver {
             .unwrap()
             .replace(cancellation_token.clone());
 
-        get_runtime().spawn({
+        queue::submit(SolverPriority::Batch, {
             let self_clone = self.clone();
             async move {
                 self_clone
@@ -42,6 +42,9 @@ impl CombinationsSolver {
         cancellation_token: CancellationToken,
     ) {
         let tiles = puzzle_state.unused_tiles;
+        let wildcard_areas = puzzle_state.wildcard_areas;
+        let placed_tiles = puzzle_state.placed_tiles;
+        let tile_constraints = puzzle_state.tile_constraints;
         let mut grid = puzzle_state.grid;
         let mut iter = TileCombinationsIter::new(&tiles);
         while let Some(tiles) = iter.next()
@@ -50,9 +53,13 @@ impl CombinationsSolver {
             let new_puzzle_state = PuzzleState {
                 grid,
                 unused_tiles: tiles.clone(),
+                placed_tiles: placed_tiles.clone(),
+                wildcard_areas: wildcard_areas.clone(),
+                tile_constraints: tile_constraints.clone(),
             };
-            self.solver.solver_for_target_maybe_callback(
+            self.solver.solve_in_background(
                 &new_puzzle_state,
+                SolverPriority::Batch,
                 Box::new({
                     move |result| {
                         debug!("Solver call completed");
@@ -74,7 +81,6 @@ impl CombinationsSolver {
                         }
                     }
                 }),
-                true,
                 cancellation_token.clone(),
             );
             grid = new_puzzle_state.grid;
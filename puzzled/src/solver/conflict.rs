@@ -0,0 +1,164 @@
+use crate::app::puzzle::puzzle_area::puzzle_state::PuzzleState;
+use crate::solver::queue::SolverPriority;
+use crate::solver::{queue, Solver};
+use crate::ui_bridge;
+use adw::glib;
+use log::debug;
+use puzzle_solver::result::UnsolvableReason;
+use puzzle_solver::session::SolverSession;
+use puzzle_solver::tile::Tile;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+
+/// The maximum time spent on searching conflicting tiles.
+/// After it passed, the smallest conflicting set found so far is used.
+const TIME_BUDGET: Duration = Duration::from_secs(3);
+
+/// Searches a minimal set of placed tiles, which cannot stay where they are together in any
+/// solution.
+///
+/// Starting with all placed tiles, each tile is taken off the board once. If the remaining tiles of
+/// the set still conflict, the tile is dropped from the set. Otherwise, it is part of the conflict
+/// and kept. Every set along the way conflicts, so the search can be stopped at any time.
+#[derive(Debug)]
+struct ConflictSearch {
+    conflict: Vec<usize>,
+    /// The index of the tile in the conflict, which is checked next.
+    next: usize,
+}
+
+impl ConflictSearch {
+    fn new(tile_ids: Vec<usize>) -> Self {
+        Self {
+            conflict: tile_ids,
+            next: 0,
+        }
+    }
+
+    /// Returns the tiles, which must stay on the board in the next check, or `None`, if the search
+    /// is finished.
+    fn candidate(&self) -> Option<Vec<usize>> {
+        if self.next >= self.conflict.len() {
+            return None;
+        }
+        let mut candidate = self.conflict.clone();
+        candidate.remove(self.next);
+        Some(candidate)
+    }
+
+    /// Records the result of the check of the last candidate.
+    ///
+    /// # Arguments
+    ///
+    /// * `conflicting`: Whether the puzzle was unsolvable with the tiles of the candidate placed.
+    ///
+    /// returns: ()
+    fn record(&mut self, conflicting: bool) {
+        if conflicting {
+            self.conflict.remove(self.next);
+        } else {
+            self.next += 1;
+        }
+    }
+}
+
+/// Searches the placed tiles, which likely prevent the given unsolvable state from being solved.
+///
+/// The state is solved again with only some of the placed tiles on the board, until a minimal set
+/// of tiles is found, which cannot be completed to a solution together. The search stops after
+/// [TIME_BUDGET] with the smallest set found so far.
+///
+/// This must be called on the main thread.
+///
+/// # Arguments
+///
+/// * `puzzle_state`: The state of the puzzle, which was found to be unsolvable.
+/// * `on_complete`: Called on the main thread with the ids of the conflicting tiles. They are empty,
+///   if the puzzle cannot be solved even without the placed tiles.
+///
+/// returns: ()
+pub fn find_conflicting_tiles<F: FnOnce(Vec<usize>) + 'static>(
+    puzzle_state: &PuzzleState,
+    on_complete: F,
+) {
+    let mut placed_tile_ids: Vec<usize> = puzzle_state
+        .placed_tiles
+        .iter()
+        .map(|tile| tile.id)
+        .collect();
+    placed_tile_ids.sort();
+
+    let sender = ui_bridge::oneshot(move |conflict| {
+        if let Some(conflict) = conflict {
+            on_complete(conflict);
+        }
+    });
+    let cancel_token = CancellationToken::new();
+    glib::timeout_add_local_once(TIME_BUDGET, {
+        let cancel_token = cancel_token.clone();
+        move || cancel_token.cancel()
+    });
+
+    let solver = Solver::default();
+    let puzzle_state = puzzle_state.clone();
+    queue::submit(SolverPriority::Requested, async move {
+        let mut search = ConflictSearch::new(placed_tile_ids.clone());
+        while let Some(candidate) = search.candidate() {
+            let removed: Vec<usize> = placed_tile_ids
+                .iter()
+                .filter(|id| !candidate.contains(id))
+                .cloned()
+                .collect();
+            let state = puzzle_state.without_tiles(&removed);
+            let tiles: Vec<Tile> = state
+                .unused_tiles
+                .iter()
+                .map(|tile_state| Tile::new(tile_state.base.clone()))
+                .collect();
+            let result = Solver::solve_first_solvable(
+                solver.create_boards(&state),
+                &tiles,
+                &mut SolverSession::default(),
+                false,
+                None,
+                None,
+                cancel_token.clone(),
+            )
+            .await;
+            match result {
+                Ok(_) => search.record(false),
+                Err(UnsolvableReason::Cancelled) => {
+                    debug!("Time budget for searching conflicting tiles exceeded");
+                    break;
+                }
+                Err(_) => search.record(true),
+            }
+        }
+        sender.send(search.conflict);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conflict_search() {
+        // Tiles 2 and 5 conflict, tile 7 does not matter.
+        let mut search = ConflictSearch::new(vec![2, 5, 7]);
+        let mut checked = Vec::new();
+        while let Some(candidate) = search.candidate() {
+            let conflicting = candidate.contains(&2) && candidate.contains(&5);
+            checked.push(candidate);
+            search.record(conflicting);
+        }
+        assert_eq!(search.conflict, vec![2, 5]);
+        assert_eq!(checked, vec![vec![5, 7], vec![2, 7], vec![2, 5]]);
+    }
+
+    #[test]
+    fn test_conflict_search_without_tiles() {
+        let search = ConflictSearch::new(Vec::new());
+        assert_eq!(search.candidate(), None);
+    }
+}
@@ -1,16 +1,39 @@
+pub mod battery_saver;
+pub mod cache;
 pub mod combination_solutions;
+pub mod conflict;
+pub mod queue;
+pub mod target_boards;
 
 use crate::app::puzzle::puzzle_area::puzzle_state::{Cell, PuzzleState};
-use crate::global::runtime::get_runtime;
-use log::debug;
+use crate::global::events;
+use crate::global::events::AppEvent;
+use crate::global::settings::{DeveloperMode, Preferences};
+use crate::offset::CellOffset;
+use crate::solver::cache::SolverCache;
+use crate::solver::queue::SolverPriority;
+use crate::solver::target_boards::TargetBoards;
+use crate::ui_bridge;
+use adw::glib;
+use log::{debug, error, info};
 use puzzle_solver::board::Board;
-use puzzle_solver::result::{Solution, UnsolvableReason};
+use puzzle_solver::result::{Solution, TilePlacement, UnsolvableReason};
+use puzzle_solver::search_tree::{SearchTree, SearchTreeRecorder};
+use puzzle_solver::session::SolverSession;
 use puzzle_solver::tile::Tile;
+use puzzle_solver::trace::SearchTrace;
+use puzzled_common::shape::cell::QUARTERS_ALL;
+use puzzled_common::Shape;
 use std::cmp::PartialEq;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::BufWriter;
 use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::slice;
 use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::{Arc, LazyLock, RwLock};
-use std::time::Instant;
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
+use std::time::{Duration, Instant};
 use tokio_util::sync::CancellationToken;
 
 /// Represents the current state of the puzzle solver.
@@ -27,6 +50,10 @@ enum SolverState {
     },
 }
 
+/// The maximum number of placed tiles, which are taken off the board to find a hint, if the placed
+/// tiles cannot be part of a solution.
+const MAX_REMOVED_TILES_FOR_HINT: usize = 2;
+
 /// Unique identifier for a solver call.
 /// It can be used to track and manage individual solver tasks.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd)]
@@ -36,15 +63,29 @@ struct SolverCallId(u64);
 /// It receives the final `SolverState` as an argument.
 pub type OnCompleteCallback = Box<dyn Fn(Result<Solution, UnsolvableReason>) + Send>;
 
+/// The minimum time between two snapshots of the search shown in developer mode.
+const SEARCH_TRACE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// If set, the search tree of each solver call requested by the player is written to the file at
+/// this path. The file is overwritten by every call, so it holds the last one.
+pub const SEARCH_TREE_FILE_ENV_VAR: &str = "PUZZLED_SEARCH_TREE_FILE";
+/// The maximum number of placements recorded in a search tree.
+/// Each of them takes 16 bytes of memory while solving.
+const MAX_SEARCH_TREE_NODES: usize = 10_000_000;
+
 static SOLVER_CALL_ID_ATOMIC_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 static SOLVER: LazyLock<Solver> = LazyLock::new(|| Solver {
     state: Arc::new(RwLock::new(SolverState::default())),
+    session: Arc::new(Mutex::new(SolverSession::default())),
 });
 
 #[derive(Debug, Clone)]
 pub struct Solver {
     state: Arc<RwLock<SolverState>>,
+    /// Remembers the last solution, so the next call after a tile was moved can try its
+    /// placements first.
+    session: Arc<Mutex<SolverSession>>,
 }
 
 impl Default for Solver {
@@ -68,35 +109,166 @@ impl Solver {
         self.solver_for_target_maybe_callback(puzzle_state, on_complete, false, cancel_token);
     }
 
+    /// Works like [Self::solve_for_target], but if the cancel token is cancelled before a solution
+    /// is found, the best partial solution found so far is passed to `on_complete`.
+    /// See [puzzle_solver::solve_all_filling_anytime].
+    ///
+    /// If the placed tiles cannot be part of a solution, the solution keeping the most placed
+    /// tiles is preferred. See [hint_states].
+    pub fn solve_for_hint(
+        &self,
+        puzzle_state: &PuzzleState,
+        on_complete: OnCompleteCallback,
+        cancel_token: CancellationToken,
+    ) {
+        self.start_solver_call(
+            &hint_states(puzzle_state),
+            on_complete,
+            false,
+            true,
+            SolverPriority::Requested,
+            cancel_token,
+        );
+    }
+
+    /// Works like [Self::solver_for_target_maybe_callback] with `always_run_callback`, but the
+    /// call is not needed right away by the player and is queued with the given priority.
+    /// While the system is in power saver mode, it may be delayed by the battery saver.
+    /// It is not stopped by [Self::interrupt_solver_call], so it is only cancelled by its token.
+    pub fn solve_in_background(
+        &self,
+        puzzle_state: &PuzzleState,
+        priority: SolverPriority,
+        on_complete: OnCompleteCallback,
+        cancel_token: CancellationToken,
+    ) {
+        self.start_solver_call(
+            slice::from_ref(puzzle_state),
+            on_complete,
+            true,
+            false,
+            priority,
+            cancel_token,
+        );
+    }
+
     pub fn solver_for_target_maybe_callback(
         &self,
         puzzle_state: &PuzzleState,
         on_complete: OnCompleteCallback,
         always_run_callback: bool,
         cancel_token: CancellationToken,
+    ) {
+        self.start_solver_call(
+            slice::from_ref(puzzle_state),
+            on_complete,
+            always_run_callback,
+            false,
+            SolverPriority::Requested,
+            cancel_token,
+        );
+    }
+
+    /// Solves the given states one after another in a single solver call and passes the result of
+    /// the first solvable state to `on_complete`.
+    /// If no state is solvable, the reason of the first state is passed.
+    fn start_solver_call(
+        &self,
+        puzzle_states: &[PuzzleState],
+        on_complete: OnCompleteCallback,
+        always_run_callback: bool,
+        anytime: bool,
+        priority: SolverPriority,
+        cancel_token: CancellationToken,
     ) {
         let solver_call_id = self.create_solver_call_id();
-        let mut state = self.state.write().unwrap();
-        *state = SolverState::Running {
-            call_id: solver_call_id,
-            cancel_token: cancel_token.clone(),
-        };
+        // Background calls run beside the call the player is waiting for, so they must neither
+        // replace it nor be interrupted with it.
+        if !priority.is_background() {
+            let mut state = self.state.write().unwrap();
+            *state = SolverState::Running {
+                call_id: solver_call_id,
+                cancel_token: cancel_token.clone(),
+            };
+            drop(state);
+            Self::publish_state_changed(true);
+        }
 
-        let board = self.create_board(puzzle_state);
-        let tiles: Vec<Tile> = puzzle_state
-            .unused_tiles
+        let attempts: Vec<(TargetBoards, Vec<Tile>)> = puzzle_states
             .iter()
-            .map(|tile_state| Tile::new(tile_state.base.clone()))
+            .map(|puzzle_state| {
+                let tiles = puzzle_state
+                    .unused_tiles
+                    .iter()
+                    .map(|tile_state| Tile::new(tile_state.base.clone()))
+                    .collect();
+                (self.create_boards(puzzle_state), tiles)
+            })
             .collect();
+        let trace = (!priority.is_background() && Preferences::default().get(DeveloperMode))
+            .then(Self::create_search_trace);
+        let search_tree = (!priority.is_background())
+            .then(Self::search_tree_file)
+            .flatten()
+            .map(|path| (path, SearchTreeRecorder::new(MAX_SEARCH_TREE_NODES)));
+        let cache = SolverCache::default();
+        let cache_key = cache::cache_key(&attempts);
+        let all_tiles = cache::all_tiles(&attempts);
 
-        let runtime = get_runtime();
         let now = Instant::now();
-        runtime.spawn({
+        queue::submit(priority, {
             let self_clone = self.clone();
             let cancel_token = cancel_token.clone();
             async move {
+                if search_tree.is_none()
+                    && let Some(result) = cache.get(&cache_key, &all_tiles)
+                {
+                    debug!("Solver call id {:?} found in cache.", solver_call_id);
+                    drop(trace);
+                    self_clone.complete_solver_call(
+                        solver_call_id,
+                        result,
+                        always_run_callback,
+                        on_complete,
+                    );
+                    return;
+                }
+                let _permit = if priority.is_background() {
+                    battery_saver::background_permit(&cancel_token).await
+                } else {
+                    None
+                };
                 debug!("Starting Solver task. Solver call id: {:?}", solver_call_id);
-                let result = puzzle_solver::solve_all_filling(board, &tiles, cancel_token).await;
+                let mut session = self_clone.session.lock().unwrap().clone();
+                let mut result = Err(UnsolvableReason::NoFit);
+                for (i, (boards, tiles)) in attempts.into_iter().enumerate() {
+                    let attempt_result = Self::solve_first_solvable(
+                        boards,
+                        &tiles,
+                        &mut session,
+                        anytime,
+                        trace.as_ref(),
+                        search_tree.as_ref().map(|(_, recorder)| recorder),
+                        cancel_token.clone(),
+                    )
+                    .await;
+                    let finished =
+                        matches!(attempt_result, Ok(_) | Err(UnsolvableReason::Cancelled));
+                    if i == 0 || finished {
+                        result = attempt_result;
+                    }
+                    if finished {
+                        break;
+                    }
+                }
+                drop(trace);
+                if let Some((path, recorder)) = search_tree {
+                    Self::write_search_tree(&path, recorder.take_tree());
+                }
+                if result.as_ref().is_ok_and(Solution::is_complete) {
+                    *self_clone.session.lock().unwrap() = session;
+                }
+                cache.put(&cache_key, &all_tiles, &result);
                 let end = Instant::now();
                 let duration = end.duration_since(now);
                 debug!(
@@ -104,15 +276,151 @@ impl Solver {
                     solver_call_id,
                     duration.as_millis()
                 );
-                if always_run_callback {
-                    on_complete(result);
-                } else {
-                    self_clone.handle_on_complete(solver_call_id, result, on_complete);
-                }
+                self_clone.complete_solver_call(
+                    solver_call_id,
+                    result,
+                    always_run_callback,
+                    on_complete,
+                );
             }
         });
     }
 
+    /// Passes the result to `on_complete` and marks the call as finished.
+    fn complete_solver_call(
+        &self,
+        solver_call_id: SolverCallId,
+        result: Result<Solution, UnsolvableReason>,
+        always_run_callback: bool,
+        on_complete: OnCompleteCallback,
+    ) {
+        if always_run_callback {
+            on_complete(result);
+        } else {
+            self.handle_on_complete(solver_call_id, result, on_complete);
+        }
+        self.finish_solver_call(solver_call_id);
+    }
+
+    /// Resets the state to idle, if the given call is still the latest one.
+    /// Otherwise, another call is running, which will do this when it finishes.
+    fn finish_solver_call(&self, solver_call_id: SolverCallId) {
+        let mut state = self.state.write().unwrap();
+        if let SolverState::Running { call_id, .. } = state.deref()
+            && *call_id == solver_call_id
+        {
+            *state = SolverState::Idle;
+            drop(state);
+            Self::publish_state_changed(false);
+        }
+    }
+
+    /// Publishes the new state of the solver on the main thread, since solver calls may be
+    /// started and finish on other threads.
+    fn publish_state_changed(running: bool) {
+        glib::MainContext::default()
+            .invoke(move || events::publish(AppEvent::SolverStateChanged { running }));
+    }
+
+    /// Creates a trace, which publishes the snapshots of the search on the main thread.
+    /// After the solver call finished and the trace was dropped, `None` is published.
+    ///
+    /// This must be called on the main thread.
+    fn create_search_trace() -> SearchTrace {
+        let sender = ui_bridge::stream(
+            |snapshot| events::publish(AppEvent::SolverSearchSnapshot(Some(snapshot))),
+            || events::publish(AppEvent::SolverSearchSnapshot(None)),
+        );
+        SearchTrace::new(SEARCH_TRACE_INTERVAL, move |snapshot| sender.send(snapshot))
+    }
+
+    /// Returns the file to write search trees to, if [SEARCH_TREE_FILE_ENV_VAR] is set.
+    fn search_tree_file() -> Option<PathBuf> {
+        match std::env::var_os(SEARCH_TREE_FILE_ENV_VAR) {
+            Some(path) if !path.is_empty() => Some(PathBuf::from(path)),
+            _ => None,
+        }
+    }
+
+    /// Writes the search tree to the file, so it can be analyzed with
+    /// [crate::headless::SEARCH_TREE_STATS_OPTION].
+    fn write_search_tree(path: &Path, tree: SearchTree) {
+        let result = File::create(path).and_then(|file| tree.write_to(BufWriter::new(file)));
+        match result {
+            Ok(()) => info!(
+                "Wrote search tree with {} nodes to '{}'.",
+                tree.nodes().len(),
+                path.display()
+            ),
+            Err(e) => error!("Failed to write search tree to '{}': {}", path.display(), e),
+        }
+    }
+
+    /// Solves the given boards one after another and returns the first solution found.
+    /// The remaining boards are not created, once a board was solved.
+    ///
+    /// If no board can be solved, the reason of the last board is returned.
+    /// In anytime mode, a partial solution is returned, if the search is cancelled.
+    /// If a trace is given, snapshots of the search are sent to it.
+    /// If a search tree recorder is given, it holds the search tree of the last board solved
+    /// afterward.
+    async fn solve_first_solvable(
+        boards: TargetBoards,
+        tiles: &[Tile],
+        session: &mut SolverSession,
+        anytime: bool,
+        trace: Option<&SearchTrace>,
+        search_tree: Option<&SearchTreeRecorder>,
+        cancel_token: CancellationToken,
+    ) -> Result<Solution, UnsolvableReason> {
+        let mut result = Err(UnsolvableReason::NoFit);
+        for board in boards.iter() {
+            result = if let Some(search_tree) = search_tree {
+                search_tree.take_tree();
+                puzzle_solver::solve_all_filling_recorded(
+                    board,
+                    tiles,
+                    session,
+                    anytime,
+                    trace,
+                    search_tree,
+                    cancel_token.clone(),
+                )
+                .await
+            } else if let Some(trace) = trace {
+                puzzle_solver::solve_all_filling_traced(
+                    board,
+                    tiles,
+                    session,
+                    anytime,
+                    trace,
+                    cancel_token.clone(),
+                )
+                .await
+            } else if anytime {
+                puzzle_solver::solve_all_filling_anytime(
+                    board,
+                    tiles,
+                    session,
+                    cancel_token.clone(),
+                )
+                .await
+            } else {
+                puzzle_solver::solve_all_filling_in_session(
+                    board,
+                    tiles,
+                    session,
+                    cancel_token.clone(),
+                )
+                .await
+            };
+            if matches!(result, Ok(_) | Err(UnsolvableReason::Cancelled)) {
+                return result;
+            }
+        }
+        result
+    }
+
     fn handle_on_complete(
         &self,
         solver_call_id: SolverCallId,
@@ -127,6 +435,31 @@ impl Solver {
         }
     }
 
+    /// Returns all placements of the tile, which fit into the empty cells of the board.
+    /// The positions of the placements are positions in the grid of the given state.
+    ///
+    /// This does not call the solver, so the placements may not be part of a solution.
+    ///
+    /// # Arguments
+    ///
+    /// * `puzzle_state`: The state of the puzzle, in which the tile is not placed.
+    /// * `base`: The base of the tile.
+    ///
+    /// returns: Vec<TilePlacement>
+    pub fn placements_of_tile(
+        &self,
+        puzzle_state: &PuzzleState,
+        base: &Shape,
+    ) -> Vec<TilePlacement> {
+        self.create_board(puzzle_state)
+            .placements(&Tile::new(base.clone()))
+    }
+
+    /// Forgets the last solution, so it is not tried first for a different puzzle.
+    pub fn clear_session(&self) {
+        self.session.lock().unwrap().clear();
+    }
+
     /// Interrupts an ongoing solver stored in the given `state`.
     ///
     /// # Arguments
@@ -157,10 +490,51 @@ impl Solver {
     /// returns: bool
     pub fn is_solved(&self, puzzle_state: &PuzzleState) -> bool {
         let board = self.create_board(puzzle_state);
-        board.get_shape().iter().all(|cell| *cell)
+        let wildcard_cells: HashSet<&(usize, usize)> =
+            puzzle_state.wildcard_areas.iter().flatten().collect();
+        let all_other_cells_filled = board.get_shape().indexed_iter().all(|(index, cell)| {
+            (*cell && board.get_shape().quarters(index) == QUARTERS_ALL)
+                || wildcard_cells.contains(&index)
+        });
+        let one_cell_per_wildcard_area_uncovered = puzzle_state
+            .wildcard_areas
+            .iter()
+            .all(|area| area.iter().filter(|(x, y)| !board[[*x, *y]]).count() == 1);
+        all_other_cells_filled && one_cell_per_wildcard_area_uncovered
+    }
+
+    /// Creates the boards to give to the solver.
+    ///
+    /// Without wildcard areas, this is just the board created by [Self::create_board].
+    /// Otherwise, there is one board for each combination of uncovered cells, in which exactly
+    /// one cell of each wildcard area is left uncovered. See [TargetBoards].
+    ///
+    /// # Arguments
+    ///
+    /// * `puzzle_state`: A reference to the current puzzle state.
+    ///
+    /// returns: TargetBoards
+    pub(crate) fn create_boards(&self, puzzle_state: &PuzzleState) -> TargetBoards {
+        let uncovered_cells = puzzle_state
+            .wildcard_areas
+            .iter()
+            .map(|area| {
+                area.iter()
+                    .filter(|index| {
+                        matches!(
+                            puzzle_state.grid.get(**index),
+                            Some(Cell::Empty(cell_data)) if cell_data.is_on_board
+                        )
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .collect();
+        TargetBoards::new(self.create_board(puzzle_state), uncovered_cells)
     }
 
     /// Creates a board representation from the given puzzle state and target to give to the solver.
+    /// The cells of the tile constraints are reserved for their tiles.
     ///
     /// # Arguments
     ///
@@ -173,22 +547,83 @@ impl Solver {
         let mut board = Board::new(dims);
 
         puzzle_state.grid.indexed_iter().for_each(|((x, y), cell)| {
-            let is_filled = match cell {
-                Cell::Empty(cell_data) => !cell_data.is_on_board,
-                Cell::One(_, _) => true,
-                Cell::Many(_, _) => true,
+            let filled_quarters = match cell {
+                Cell::Empty(cell_data) => !cell_data.board_quarters & QUARTERS_ALL,
+                Cell::One(cell_data, tile_cell_placement) => {
+                    !cell_data.board_quarters & QUARTERS_ALL | tile_cell_placement.quarters
+                }
+                Cell::Many(cell_data, tile_cell_placements) => tile_cell_placements.iter().fold(
+                    !cell_data.board_quarters & QUARTERS_ALL,
+                    |quarters, placement| quarters | placement.quarters,
+                ),
             };
 
-            board[[x, y]] = is_filled;
+            board.set_quarters([x, y], filled_quarters);
         });
 
+        for tile_constraint in &puzzle_state.tile_constraints {
+            let (x, y) =
+                PuzzleState::board_to_state(CellOffset::from(tile_constraint.cell())).into();
+            board.reserve_cell([x, y], &Tile::new(tile_constraint.base().clone()));
+        }
+
         board
     }
 }
 
+/// Returns the states to search a hint in, ordered by how many placed tiles they keep.
+///
+/// The first state is the given one, so a hint is consistent with all placed tiles, if possible.
+/// The following states have up to [MAX_REMOVED_TILES_FOR_HINT] placed tiles taken off the board.
+/// This way, the hint prefers a solution, which keeps as much of the progress of the player as
+/// possible, instead of any solution of the empty board.
+///
+/// # Arguments
+///
+/// * `puzzle_state`: The current state of the puzzle.
+///
+/// returns: Vec<PuzzleState>
+fn hint_states(puzzle_state: &PuzzleState) -> Vec<PuzzleState> {
+    let mut placed_tile_ids: Vec<usize> = puzzle_state
+        .placed_tiles
+        .iter()
+        .map(|tile| tile.id)
+        .collect();
+    placed_tile_ids.sort();
+    removal_combinations(&placed_tile_ids, MAX_REMOVED_TILES_FOR_HINT)
+        .iter()
+        .map(|removed| puzzle_state.without_tiles(removed))
+        .collect()
+}
+
+/// Returns all combinations of the given ids with at most `max_removed` elements, ordered by
+/// their size, starting with the empty combination.
+fn removal_combinations(ids: &[usize], max_removed: usize) -> Vec<Vec<usize>> {
+    let mut combinations = vec![Vec::new()];
+    let mut last_size: Vec<Vec<usize>> = vec![Vec::new()];
+    for _ in 0..max_removed.min(ids.len()) {
+        last_size = last_size
+            .iter()
+            .flat_map(|combination| {
+                let start = combination
+                    .last()
+                    .map(|last| ids.iter().position(|id| id == last).unwrap() + 1)
+                    .unwrap_or(0);
+                ids[start..].iter().map(move |id| {
+                    let mut combination = combination.clone();
+                    combination.push(*id);
+                    combination
+                })
+            })
+            .collect();
+        combinations.extend(last_size.iter().cloned());
+    }
+    combinations
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::solver::Solver;
+    use crate::solver::{removal_combinations, Solver};
 
     #[test]
     fn test_create_solver_call_id() {
@@ -198,4 +633,25 @@ mod tests {
         assert_ne!(id1, id2);
         assert!(id1 < id2);
     }
+
+    #[test]
+    fn test_removal_combinations() {
+        assert_eq!(removal_combinations(&[], 2), vec![Vec::<usize>::new()]);
+        assert_eq!(
+            removal_combinations(&[3, 5, 7], 2),
+            vec![
+                vec![],
+                vec![3],
+                vec![5],
+                vec![7],
+                vec![3, 5],
+                vec![3, 7],
+                vec![5, 7]
+            ]
+        );
+        assert_eq!(
+            removal_combinations(&[3, 5], 1),
+            vec![vec![], vec![3], vec![5]]
+        );
+    }
 }
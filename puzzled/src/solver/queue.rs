@@ -0,0 +1,167 @@
+use crate::global::runtime::get_runtime;
+use log::debug;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::LazyLock;
+use std::thread;
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+
+/// How urgent a solver job is. Jobs with a higher priority are started first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum SolverPriority {
+    /// Analysis of many puzzle states, like finding the tile combinations to solve.
+    Batch,
+    /// A check running in the background, like while the player moves tiles.
+    SolvabilityCheck,
+    /// A call the player is waiting for, like a hint.
+    /// These are started right away, even if all slots are taken.
+    Requested,
+}
+
+impl SolverPriority {
+    /// Whether the player is not waiting for the job.
+    /// Such jobs are restricted by the battery saver.
+    pub fn is_background(&self) -> bool {
+        *self != SolverPriority::Requested
+    }
+}
+
+type JobFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+struct SolverJob {
+    priority: SolverPriority,
+    /// Jobs with the same priority are started in the order they were submitted.
+    sequence: u64,
+    future: JobFuture,
+}
+
+impl PartialEq for SolverJob {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for SolverJob {}
+
+impl PartialOrd for SolverJob {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SolverJob {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.priority
+            .cmp(&other.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+/// The number of jobs running at the same time, not counting [SolverPriority::Requested] jobs.
+fn max_running_jobs() -> usize {
+    thread::available_parallelism()
+        .map(|parallelism| parallelism.get())
+        .unwrap_or(1)
+}
+
+static QUEUE: LazyLock<mpsc::UnboundedSender<(SolverPriority, JobFuture)>> = LazyLock::new(|| {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    get_runtime().spawn(run_worker(receiver, max_running_jobs()));
+    sender
+});
+
+/// Queues a solver job on the solver runtime.
+///
+/// All solver work is started through this queue, so jobs requested by different features are
+/// scheduled by their priority instead of competing for the runtime.
+pub fn submit<F: Future<Output = ()> + Send + 'static>(priority: SolverPriority, job: F) {
+    if QUEUE.send((priority, Box::pin(job))).is_err() {
+        debug!("Solver queue is shut down, dropping job");
+    }
+}
+
+/// Starts the queued jobs by priority, as long as fewer than `max_running` jobs are running.
+async fn run_worker(
+    mut receiver: mpsc::UnboundedReceiver<(SolverPriority, JobFuture)>,
+    max_running: usize,
+) {
+    let mut queue = BinaryHeap::new();
+    let mut running = JoinSet::new();
+    let mut sequence = 0;
+    loop {
+        tokio::select! {
+            job = receiver.recv() => {
+                let Some((priority, future)) = job else {
+                    break;
+                };
+                queue.push(SolverJob {
+                    priority,
+                    sequence,
+                    future,
+                });
+                sequence += 1;
+            }
+            Some(_) = running.join_next(), if !running.is_empty() => {}
+        }
+        while let Some(job) = next_job(&mut queue, running.len(), max_running) {
+            running.spawn(job.future);
+        }
+    }
+}
+
+fn next_job(
+    queue: &mut BinaryHeap<SolverJob>,
+    running: usize,
+    max_running: usize,
+) -> Option<SolverJob> {
+    let job = queue.peek()?;
+    if job.priority == SolverPriority::Requested || running < max_running {
+        queue.pop()
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(priority: SolverPriority, sequence: u64) -> SolverJob {
+        SolverJob {
+            priority,
+            sequence,
+            future: Box::pin(async {}),
+        }
+    }
+
+    #[test]
+    fn test_next_job_order() {
+        let mut queue = BinaryHeap::from([
+            job(SolverPriority::Batch, 0),
+            job(SolverPriority::SolvabilityCheck, 1),
+            job(SolverPriority::Batch, 2),
+            job(SolverPriority::Requested, 3),
+        ]);
+
+        let order: Vec<u64> = std::iter::from_fn(|| next_job(&mut queue, 0, 1))
+            .map(|job| job.sequence)
+            .collect();
+
+        assert_eq!(order, vec![3, 1, 0, 2]);
+    }
+
+    #[test]
+    fn test_next_job_respects_running_limit() {
+        let mut queue = BinaryHeap::from([
+            job(SolverPriority::Batch, 0),
+            job(SolverPriority::Requested, 1),
+        ]);
+
+        assert_eq!(next_job(&mut queue, 2, 2).map(|job| job.sequence), Some(1));
+        assert!(next_job(&mut queue, 2, 2).is_none());
+        assert_eq!(next_job(&mut queue, 1, 2).map(|job| job.sequence), Some(0));
+    }
+}
@@ -0,0 +1,139 @@
+use puzzle_solver::board::{Board, CellRequirement};
+
+/// The boards to give to the solver for a puzzle state with wildcard areas.
+///
+/// Exactly one cell of each wildcard area stays uncovered, so there is one board for each
+/// combination of uncovered cells. The boards are created one after another while iterating, so
+/// a search stopping at the first solvable target does not create the remaining ones.
+#[derive(Debug, Clone)]
+pub struct TargetBoards {
+    /// The board, on which no cell of a wildcard area has to stay empty.
+    board: Board,
+    /// The cells of each wildcard area, which may stay uncovered.
+    uncovered_cells: Vec<Vec<(usize, usize)>>,
+}
+
+impl TargetBoards {
+    pub fn new(board: Board, uncovered_cells: Vec<Vec<(usize, usize)>>) -> Self {
+        TargetBoards {
+            board,
+            uncovered_cells,
+        }
+    }
+
+    /// Returns the board, on which no cell of a wildcard area has to stay empty.
+    pub fn board(&self) -> &Board {
+        &self.board
+    }
+
+    /// Returns the cells of each wildcard area, which may stay uncovered.
+    pub fn uncovered_cells(&self) -> &[Vec<(usize, usize)>] {
+        &self.uncovered_cells
+    }
+
+    /// Returns the boards in the order of the combinations of uncovered cells, in which the cell
+    /// of the last wildcard area changes first.
+    /// Without wildcard areas, this is just the board.
+    pub fn iter(&self) -> TargetBoardsIter<'_> {
+        let indices = self
+            .uncovered_cells
+            .iter()
+            .all(|cells| !cells.is_empty())
+            .then(|| vec![0; self.uncovered_cells.len()]);
+        TargetBoardsIter {
+            boards: self,
+            indices,
+        }
+    }
+}
+
+/// Iterates the boards of [TargetBoards].
+pub struct TargetBoardsIter<'a> {
+    boards: &'a TargetBoards,
+    /// The index of the uncovered cell of each wildcard area on the next board.
+    /// `None`, if all boards were returned.
+    indices: Option<Vec<usize>>,
+}
+
+impl Iterator for TargetBoardsIter<'_> {
+    type Item = Board;
+
+    fn next(&mut self) -> Option<Board> {
+        let indices = self.indices.as_mut()?;
+        let mut board = self.boards.board.clone();
+        for (cells, &index) in self.boards.uncovered_cells.iter().zip(indices.iter()) {
+            let (x, y) = cells[index];
+            board.set_requirement([x, y], CellRequirement::MustStayEmpty);
+        }
+
+        let mut advanced = false;
+        for (cells, index) in self
+            .boards
+            .uncovered_cells
+            .iter()
+            .zip(indices.iter_mut())
+            .rev()
+        {
+            *index += 1;
+            if *index < cells.len() {
+                advanced = true;
+                break;
+            }
+            *index = 0;
+        }
+        if !advanced {
+            self.indices = None;
+        }
+        Some(board)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn empty_cells(board: &Board) -> Vec<(usize, usize)> {
+        board
+            .get_shape()
+            .indexed_iter()
+            .filter(|(position, _)| {
+                board.requirement([position.0, position.1]) == CellRequirement::MustStayEmpty
+            })
+            .map(|(position, _)| position)
+            .collect()
+    }
+
+    #[test]
+    fn test_target_boards() {
+        let boards = TargetBoards::new(
+            Board::new((3, 2)),
+            vec![vec![(0, 0), (1, 0)], vec![(0, 1), (1, 1), (2, 1)]],
+        );
+
+        let combinations: Vec<Vec<(usize, usize)>> =
+            boards.iter().map(|b| empty_cells(&b)).collect();
+        assert_eq!(
+            combinations,
+            vec![
+                vec![(0, 0), (0, 1)],
+                vec![(0, 0), (1, 1)],
+                vec![(0, 0), (2, 1)],
+                vec![(0, 1), (1, 0)],
+                vec![(1, 0), (1, 1)],
+                vec![(1, 0), (2, 1)],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_target_boards_without_wildcard_areas() {
+        let boards = TargetBoards::new(Board::new((2, 2)), Vec::new());
+        assert_eq!(boards.iter().count(), 1);
+    }
+
+    #[test]
+    fn test_target_boards_with_covered_wildcard_area() {
+        let boards = TargetBoards::new(Board::new((2, 2)), vec![vec![(0, 0)], Vec::new()]);
+        assert_eq!(boards.iter().count(), 0);
+    }
+}
@@ -0,0 +1,20 @@
+/// An error, which occurred while syncing the progress.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyncError {
+    /// No URL is set for the sync.
+    NotConfigured,
+    /// The URL does not use `https` or `davs`.
+    UnsupportedUrl(String),
+    /// Another sync is running already.
+    AlreadyRunning,
+    /// The server could not be reached or the login failed.
+    Connection(String),
+    /// The progress file could not be read from or written to the server.
+    Transfer(String),
+    /// The progress file was changed on the server, while it was synced.
+    Conflict,
+    /// The progress file on the server is not valid.
+    MalformedProgress(String),
+    /// The progress file on the server was written by a newer version of Puzzled.
+    UnsupportedVersion(u32),
+}
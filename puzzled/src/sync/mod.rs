@@ -0,0 +1,126 @@
+pub mod error;
+pub mod progress;
+mod webdav;
+
+use crate::global::events;
+use crate::global::events::AppEvent;
+use crate::global::settings::{Preferences, SyncEnabled, SyncUrl};
+use crate::model::collection::CollectionModel;
+use crate::model::puzzle_meta::PuzzleMeta;
+use crate::model::store::with_puzzle_collection_store;
+use crate::sync::error::SyncError;
+use crate::sync::progress::Progress;
+use adw::gio;
+use gtk::glib;
+use log::{info, warn};
+use std::cell::Cell;
+
+thread_local! {
+    static SYNC_RUNNING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Syncs the progress now and every time a puzzle is solved, if the sync is enabled.
+///
+/// This must be called once on the main thread after the collection store is initialized.
+pub fn init() {
+    events::subscribe(|event| {
+        if *event == AppEvent::PuzzleSolved {
+            sync_in_background();
+        }
+    });
+    sync_in_background();
+}
+
+/// Syncs the progress, if the sync is enabled, and logs errors instead of showing them.
+fn sync_in_background() {
+    if !Preferences::default().get(SyncEnabled) {
+        return;
+    }
+    sync(None, |result| {
+        if let Err(e) = result {
+            warn!("Failed to sync progress: {:?}", e);
+        }
+    });
+}
+
+/// Syncs the progress with the progress file at the configured URL.
+///
+/// The progress file is read, merged with the local progress and both are updated with the
+/// merged progress. See [Progress::merge] for how conflicts are resolved.
+///
+/// This must be called on the main thread.
+///
+/// # Arguments
+///
+/// * `window`: the window to show the login dialog on, if the server asks for it
+/// * `on_complete`: called on the main thread, when the sync is finished
+///
+/// returns: ()
+pub fn sync(
+    window: Option<gtk::Window>,
+    on_complete: impl FnOnce(Result<(), SyncError>) + 'static,
+) {
+    if SYNC_RUNNING.replace(true) {
+        on_complete(Err(SyncError::AlreadyRunning));
+        return;
+    }
+    glib::spawn_future_local(async move {
+        let result = sync_progress(window.as_ref()).await;
+        SYNC_RUNNING.set(false);
+        on_complete(result);
+    });
+}
+
+async fn sync_progress(window: Option<&gtk::Window>) -> Result<(), SyncError> {
+    let uri = webdav::webdav_uri(&Preferences::default().get(SyncUrl))?;
+    let file = gio::File::for_uri(&uri);
+    webdav::mount(&file, window).await?;
+    // The file was written by another device in the meantime, so it is merged once more.
+    match sync_file(&file).await {
+        Err(SyncError::Conflict) => sync_file(&file).await,
+        result => result,
+    }
+}
+
+async fn sync_file(file: &gio::File) -> Result<(), SyncError> {
+    let local = PuzzleMeta::new().progress();
+    let (remote, etag) = match webdav::read(file).await? {
+        Some((json, etag)) => (Some(Progress::from_json(&json)?), etag),
+        None => (None, None),
+    };
+    let merged = match &remote {
+        Some(remote) => local.merge(remote),
+        None => local.clone(),
+    };
+
+    if remote.as_ref() != Some(&merged) {
+        webdav::write(file, merged.to_json(), etag.as_deref()).await?;
+    }
+    if merged != local {
+        info!("Applying synced progress");
+        apply_progress(&merged);
+    }
+    Ok(())
+}
+
+/// Stores the progress and updates the progress shown for all collections.
+///
+/// # Arguments
+///
+/// * `progress`: the progress replacing the local one
+///
+/// returns: ()
+pub fn apply_progress(progress: &Progress) {
+    PuzzleMeta::new().set_progress(progress);
+    let collections: Vec<CollectionModel> = with_puzzle_collection_store(|store| {
+        store
+            .core_puzzle_collections()
+            .iter()
+            .chain(store.community_puzzle_collections())
+            .cloned()
+            .collect()
+    });
+    for collection in collections {
+        collection.reload_progress();
+    }
+}
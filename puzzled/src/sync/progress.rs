@@ -0,0 +1,258 @@
+use crate::sync::error::SyncError;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The version of the format of the progress file.
+/// Increase it, when the format changes in a way older versions cannot read.
+pub const PROGRESS_VERSION: u32 = 1;
+
+/// The progress of the player, which is synced between devices.
+///
+/// Puzzles are identified by the id of their collection and the key of the puzzle, which contains
+/// the puzzle id and the target.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Progress {
+    pub version: u32,
+    /// When the progress was changed last in seconds since the Unix epoch.
+    pub modified: i64,
+    /// The keys of the solved puzzles mapped by collection id.
+    pub solved: BTreeMap<String, BTreeSet<String>>,
+    /// The hints used to solve each puzzle mapped by collection id and puzzle key.
+    pub hints: BTreeMap<String, BTreeMap<String, u32>>,
+}
+
+/// What changes in a progress, when another progress is merged into it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ProgressChanges {
+    /// The number of puzzles, which become solved.
+    pub solved: usize,
+    /// The number of puzzles, whose hint count changes.
+    pub hints: usize,
+}
+
+impl ProgressChanges {
+    /// Returns true, if nothing changes.
+    pub fn is_empty(&self) -> bool {
+        self.solved == 0 && self.hints == 0
+    }
+}
+
+impl Default for Progress {
+    fn default() -> Self {
+        Progress {
+            version: PROGRESS_VERSION,
+            modified: 0,
+            solved: BTreeMap::new(),
+            hints: BTreeMap::new(),
+        }
+    }
+}
+
+impl Progress {
+    /// Reads the progress from the content of a progress file.
+    ///
+    /// # Arguments
+    ///
+    /// * `json`: the content of the progress file
+    ///
+    /// returns: Result<Progress, SyncError>
+    pub fn from_json(json: &str) -> Result<Progress, SyncError> {
+        let progress: Progress =
+            serde_json::from_str(json).map_err(|e| SyncError::MalformedProgress(e.to_string()))?;
+        if progress.version > PROGRESS_VERSION {
+            return Err(SyncError::UnsupportedVersion(progress.version));
+        }
+        Ok(progress)
+    }
+
+    /// Writes the progress in the format of the progress file.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Failed to serialize progress")
+    }
+
+    /// Merges this progress with the other one.
+    ///
+    /// The last writer wins: hints of the progress, which was modified later, replace the hints
+    /// of the other one. The solved puzzles of both are kept, so a puzzle solved on any device
+    /// stays solved.
+    ///
+    /// # Arguments
+    ///
+    /// * `other`: the progress to merge with
+    ///
+    /// returns: Progress
+    pub fn merge(&self, other: &Progress) -> Progress {
+        let (older, newer) = if self.modified > other.modified {
+            (other, self)
+        } else {
+            (self, other)
+        };
+        let mut merged = older.clone();
+        merged.version = PROGRESS_VERSION;
+        merged.modified = newer.modified;
+        for (collection_id, puzzle_keys) in &newer.solved {
+            merged
+                .solved
+                .entry(collection_id.clone())
+                .or_default()
+                .extend(puzzle_keys.iter().cloned());
+        }
+        for (collection_id, hints) in &newer.hints {
+            merged
+                .hints
+                .entry(collection_id.clone())
+                .or_default()
+                .extend(hints.iter().map(|(key, count)| (key.clone(), *count)));
+        }
+        merged
+    }
+
+    /// Counts what changes, when this progress is replaced by the other one.
+    ///
+    /// # Arguments
+    ///
+    /// * `other`: the progress replacing this one, usually the result of [Progress::merge]
+    ///
+    /// returns: ProgressChanges
+    pub fn changes(&self, other: &Progress) -> ProgressChanges {
+        let solved = other
+            .solved
+            .iter()
+            .flat_map(|(collection_id, puzzle_keys)| {
+                puzzle_keys.iter().map(move |key| (collection_id, key))
+            })
+            .filter(|(collection_id, key)| {
+                !self
+                    .solved
+                    .get(*collection_id)
+                    .is_some_and(|keys| keys.contains(*key))
+            })
+            .count();
+        let hints = other
+            .hints
+            .iter()
+            .flat_map(|(collection_id, hints)| {
+                hints
+                    .iter()
+                    .map(move |(key, count)| (collection_id, key, count))
+            })
+            .filter(|(collection_id, key, count)| {
+                self.hints
+                    .get(*collection_id)
+                    .and_then(|hints| hints.get(*key))
+                    != Some(*count)
+            })
+            .count();
+        ProgressChanges { solved, hints }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn progress(modified: i64, solved: &[(&str, &str)], hints: &[(&str, &str, u32)]) -> Progress {
+        let mut progress = Progress {
+            modified,
+            ..Progress::default()
+        };
+        for (collection_id, puzzle_key) in solved {
+            progress
+                .solved
+                .entry(collection_id.to_string())
+                .or_default()
+                .insert(puzzle_key.to_string());
+        }
+        for (collection_id, puzzle_key, count) in hints {
+            progress
+                .hints
+                .entry(collection_id.to_string())
+                .or_default()
+                .insert(puzzle_key.to_string(), *count);
+        }
+        progress
+    }
+
+    #[test]
+    fn test_merge_keeps_solved_of_both() {
+        let local = progress(10, &[("a", "1/simple")], &[]);
+        let remote = progress(5, &[("a", "2/simple"), ("b", "1/simple")], &[]);
+
+        let merged = local.merge(&remote);
+
+        assert_eq!(merged.modified, 10);
+        assert_eq!(
+            merged,
+            progress(
+                10,
+                &[("a", "1/simple"), ("a", "2/simple"), ("b", "1/simple")],
+                &[]
+            )
+        );
+        assert_eq!(remote.merge(&local), merged);
+    }
+
+    #[test]
+    fn test_merge_newer_hints_win() {
+        let local = progress(10, &[], &[("a", "1/simple", 3), ("a", "2/simple", 1)]);
+        let remote = progress(20, &[], &[("a", "1/simple", 5), ("b", "1/simple", 0)]);
+
+        let merged = local.merge(&remote);
+
+        assert_eq!(
+            merged.hints,
+            progress(
+                0,
+                &[],
+                &[
+                    ("a", "1/simple", 5),
+                    ("a", "2/simple", 1),
+                    ("b", "1/simple", 0)
+                ]
+            )
+            .hints
+        );
+        assert_eq!(merged.modified, 20);
+    }
+
+    #[test]
+    fn test_changes() {
+        let local = progress(10, &[("a", "1/simple")], &[("a", "1/simple", 2)]);
+        let imported = progress(
+            20,
+            &[("a", "2/simple"), ("b", "1/simple")],
+            &[("a", "1/simple", 4), ("a", "2/simple", 0)],
+        );
+
+        let changes = local.changes(&local.merge(&imported));
+
+        assert_eq!(
+            changes,
+            ProgressChanges {
+                solved: 2,
+                hints: 2
+            }
+        );
+        assert!(local.changes(&local).is_empty());
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let progress = progress(42, &[("a", "1/simple")], &[("a", "1/simple", 2)]);
+        assert_eq!(Progress::from_json(&progress.to_json()), Ok(progress));
+    }
+
+    #[test]
+    fn test_from_json_errors() {
+        assert!(matches!(
+            Progress::from_json("{"),
+            Err(SyncError::MalformedProgress(_))
+        ));
+        assert_eq!(
+            Progress::from_json(r#"{"version": 2}"#),
+            Err(SyncError::UnsupportedVersion(2))
+        );
+        assert_eq!(Progress::from_json("{}"), Ok(Progress::default()));
+    }
+}
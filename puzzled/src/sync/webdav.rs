@@ -0,0 +1,103 @@
+use crate::sync::error::SyncError;
+use adw::gio;
+use adw::prelude::FileExt;
+use gtk::glib::GString;
+
+/// Converts the URL entered by the player to a URI, which GVfs can access with WebDAV.
+///
+/// Only encrypted connections are supported, since the login is sent to the server.
+///
+/// # Arguments
+///
+/// * `url`: an `https` or `davs` URL of the progress file
+///
+/// returns: Result<String, SyncError>
+pub fn webdav_uri(url: &str) -> Result<String, SyncError> {
+    let url = url.trim();
+    if url.is_empty() {
+        Err(SyncError::NotConfigured)
+    } else if let Some(rest) = url.strip_prefix("https://") {
+        Ok(format!("davs://{}", rest))
+    } else if url.starts_with("davs://") {
+        Ok(url.to_string())
+    } else {
+        Err(SyncError::UnsupportedUrl(url.to_string()))
+    }
+}
+
+/// Mounts the WebDAV share containing the file.
+/// If the server requires a login, it is asked for with a dialog on the given window.
+pub async fn mount(file: &gio::File, window: Option<&gtk::Window>) -> Result<(), SyncError> {
+    let operation = gtk::MountOperation::new(window);
+    match file
+        .mount_enclosing_volume_future(gio::MountMountFlags::NONE, Some(&operation))
+        .await
+    {
+        Ok(()) => Ok(()),
+        Err(e) if e.matches(gio::IOErrorEnum::AlreadyMounted) => Ok(()),
+        Err(e) => Err(SyncError::Connection(e.to_string())),
+    }
+}
+
+/// Reads the content and the entity tag of the file.
+///
+/// returns: Result<Option<(String, Option<GString>)>, SyncError> `None`, if the file does not
+/// exist yet.
+pub async fn read(file: &gio::File) -> Result<Option<(String, Option<GString>)>, SyncError> {
+    match file.load_contents_future().await {
+        Ok((bytes, etag)) => String::from_utf8(bytes.to_vec())
+            .map(|content| Some((content, etag)))
+            .map_err(|e| SyncError::MalformedProgress(e.to_string())),
+        Err(e) if e.matches(gio::IOErrorEnum::NotFound) => Ok(None),
+        Err(e) => Err(SyncError::Transfer(e.to_string())),
+    }
+}
+
+/// Writes the content to the file.
+///
+/// # Arguments
+///
+/// * `file`: the file to write
+/// * `content`: the new content of the file
+/// * `etag`: the entity tag of the file, when it was read, or `None`, if it did not exist
+///
+/// returns: Result<(), SyncError> [SyncError::Conflict], if the file was changed since it was read.
+pub async fn write(file: &gio::File, content: String, etag: Option<&str>) -> Result<(), SyncError> {
+    match file
+        .replace_contents_future(
+            content.into_bytes(),
+            etag,
+            false,
+            gio::FileCreateFlags::REPLACE_DESTINATION,
+        )
+        .await
+    {
+        Ok(_) => Ok(()),
+        Err((_, e)) if e.matches(gio::IOErrorEnum::WrongEtag) => Err(SyncError::Conflict),
+        Err((_, e)) => Err(SyncError::Transfer(e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webdav_uri() {
+        assert_eq!(
+            webdav_uri(" https://cloud.example.com/remote.php/dav/files/me/progress.json "),
+            Ok("davs://cloud.example.com/remote.php/dav/files/me/progress.json".to_string())
+        );
+        assert_eq!(
+            webdav_uri("davs://example.com/progress.json"),
+            Ok("davs://example.com/progress.json".to_string())
+        );
+        assert_eq!(webdav_uri(""), Err(SyncError::NotConfigured));
+        assert_eq!(
+            webdav_uri("http://example.com/progress.json"),
+            Err(SyncError::UnsupportedUrl(
+                "http://example.com/progress.json".to_string()
+            ))
+        );
+    }
+}
@@ -0,0 +1,57 @@
+use adw::glib;
+use tokio::sync::mpsc;
+
+/// Sends a value from any thread to the main thread.
+///
+/// Created by [oneshot] or [stream]. For [oneshot], only the first value sent is passed on, later
+/// ones are ignored.
+#[derive(Debug, Clone)]
+pub struct UiSender<T> {
+    sender: mpsc::UnboundedSender<T>,
+}
+
+impl<T> UiSender<T> {
+    /// Sends the value to the main thread.
+    /// Nothing happens, if the receiving side does not accept further values.
+    pub fn send(&self, value: T) {
+        let _ = self.sender.send(value);
+    }
+}
+
+/// Creates a sender, whose first value is passed to `on_result` on the main thread.
+///
+/// `on_result` is called with `None`, if all clones of the sender are dropped without sending a
+/// value, e.g. because the solver call was superseded by another one. The receiving side waits
+/// asynchronously on the main context and is removed after calling `on_result`, so no idle
+/// handlers are left behind.
+///
+/// This must be called on the main thread.
+pub fn oneshot<T: Send + 'static>(on_result: impl FnOnce(Option<T>) + 'static) -> UiSender<T> {
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    glib::spawn_future_local(async move {
+        let value = receiver.recv().await;
+        on_result(value);
+    });
+    UiSender { sender }
+}
+
+/// Creates a sender, whose values are passed to `on_value` on the main thread in the order they
+/// were sent.
+///
+/// `on_closed` is called after all clones of the sender are dropped and all values were passed
+/// on. The receiving side is removed afterward.
+///
+/// This must be called on the main thread.
+pub fn stream<T: Send + 'static>(
+    mut on_value: impl FnMut(T) + 'static,
+    on_closed: impl FnOnce() + 'static,
+) -> UiSender<T> {
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    glib::spawn_future_local(async move {
+        while let Some(value) = receiver.recv().await {
+            on_value(value);
+        }
+        on_closed();
+    });
+    UiSender { sender }
+}
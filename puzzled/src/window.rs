@@ -87,6 +87,7 @@ impl PuzzledWindow {
         obj.imp().puzzle_area_nav_page.set_window(&obj);
         obj.imp().collection_selection_nav_page.set_window(&obj);
         obj.setup_nav_signals();
+        obj.setup_progress_drop();
         obj
     }
 
@@ -123,6 +124,25 @@ impl PuzzledWindow {
                         .upcast()]);
                 }
             });
+        self.imp()
+            .collection_selection_nav_page
+            .connect_featured_selected({
+                let self_clone = self.clone();
+                move |puzzle| {
+                    self_clone
+                        .imp()
+                        .puzzle_selection_nav_page
+                        .show_collection(puzzle.collection());
+                    self_clone.imp().inner_view.set_show_content(true);
+                    self_clone.imp().content_page.replace(&[self_clone
+                        .imp()
+                        .puzzle_selection_nav_page
+                        .clone()
+                        .upcast()]);
+                    self_clone.imp().puzzle_area_nav_page.show_puzzle(puzzle);
+                    self_clone.imp().outer_view.set_show_content(true);
+                }
+            });
         self.imp()
             .puzzle_selection_nav_page
             .connect_puzzle_selected({
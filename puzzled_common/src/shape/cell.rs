@@ -0,0 +1,227 @@
+/// Bit of the top quarter of a cell.
+pub const QUARTER_TOP: u8 = 0b0001;
+/// Bit of the right quarter of a cell.
+pub const QUARTER_RIGHT: u8 = 0b0010;
+/// Bit of the bottom quarter of a cell.
+pub const QUARTER_BOTTOM: u8 = 0b0100;
+/// Bit of the left quarter of a cell.
+pub const QUARTER_LEFT: u8 = 0b1000;
+/// All quarters of a cell.
+pub const QUARTERS_ALL: u8 = 0b1111;
+
+/// The shape of a single cell of a square shape.
+///
+/// A cell is either a full square or one half of it, which is cut along a diagonal.
+/// The halves are named after the corner of the cell, at which their right angle is.
+/// The coordinates follow the shapes, so the top of a cell is at the lower y index.
+///
+/// To check for overlaps between halves cut along different diagonals, a cell can be divided
+/// into four quarters (top, right, bottom and left), by cutting it along both diagonals.
+/// Each half is made up of two of those quarters.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CellShape {
+    #[default]
+    Full,
+    TopLeft,
+    TopRight,
+    BottomRight,
+    BottomLeft,
+}
+
+impl CellShape {
+    /// Returns the quarters of the cell covered by this shape as bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use puzzled_common::shape::cell::{CellShape, QUARTER_LEFT, QUARTER_TOP};
+    ///
+    /// assert_eq!(CellShape::TopLeft.quarters(), QUARTER_TOP | QUARTER_LEFT);
+    /// ```
+    pub const fn quarters(&self) -> u8 {
+        match self {
+            CellShape::Full => QUARTERS_ALL,
+            CellShape::TopLeft => QUARTER_TOP | QUARTER_LEFT,
+            CellShape::TopRight => QUARTER_TOP | QUARTER_RIGHT,
+            CellShape::BottomRight => QUARTER_BOTTOM | QUARTER_RIGHT,
+            CellShape::BottomLeft => QUARTER_BOTTOM | QUARTER_LEFT,
+        }
+    }
+
+    /// Returns the cell shape covering exactly the given quarters or none, if there is no such
+    /// shape.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use puzzled_common::shape::cell::{CellShape, QUARTER_BOTTOM, QUARTER_RIGHT, QUARTER_TOP};
+    ///
+    /// assert_eq!(
+    ///     CellShape::from_quarters(QUARTER_BOTTOM | QUARTER_RIGHT),
+    ///     Some(CellShape::BottomRight)
+    /// );
+    /// assert_eq!(CellShape::from_quarters(QUARTER_BOTTOM | QUARTER_TOP), None);
+    /// ```
+    pub const fn from_quarters(quarters: u8) -> Option<CellShape> {
+        match quarters {
+            QUARTERS_ALL => Some(CellShape::Full),
+            0b1001 => Some(CellShape::TopLeft),
+            0b0011 => Some(CellShape::TopRight),
+            0b0110 => Some(CellShape::BottomRight),
+            0b1100 => Some(CellShape::BottomLeft),
+            _ => None,
+        }
+    }
+
+    /// Returns true, if this is only one half of a cell.
+    pub const fn is_half(&self) -> bool {
+        !matches!(self, CellShape::Full)
+    }
+
+    /// Returns the other half of the cell. A full cell stays full.
+    pub const fn complement(&self) -> CellShape {
+        match self {
+            CellShape::Full => CellShape::Full,
+            CellShape::TopLeft => CellShape::BottomRight,
+            CellShape::TopRight => CellShape::BottomLeft,
+            CellShape::BottomRight => CellShape::TopLeft,
+            CellShape::BottomLeft => CellShape::TopRight,
+        }
+    }
+
+    /// Returns true, if the given point lies in this shape.
+    /// The point is relative to the cell, where `(0.0, 0.0)` is the top left and `(1.0, 1.0)`
+    /// the bottom right corner of the cell.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use puzzled_common::shape::cell::CellShape;
+    ///
+    /// assert!(CellShape::TopLeft.contains(0.2, 0.3));
+    /// assert!(!CellShape::TopLeft.contains(0.8, 0.7));
+    /// ```
+    pub fn contains(&self, x: f64, y: f64) -> bool {
+        match self {
+            CellShape::Full => true,
+            CellShape::TopLeft => x + y <= 1.0,
+            CellShape::TopRight => y <= x,
+            CellShape::BottomRight => x + y >= 1.0,
+            CellShape::BottomLeft => y >= x,
+        }
+    }
+
+    /// Returns the corners of this shape in drawing order.
+    /// The corners are relative to the cell like in [CellShape::contains].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use puzzled_common::shape::cell::CellShape;
+    ///
+    /// assert_eq!(
+    ///     CellShape::BottomLeft.corners(),
+    ///     &[(0.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+    /// );
+    /// ```
+    pub const fn corners(&self) -> &'static [(f64, f64)] {
+        match self {
+            CellShape::Full => &[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)],
+            CellShape::TopLeft => &[(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)],
+            CellShape::TopRight => &[(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)],
+            CellShape::BottomRight => &[(1.0, 0.0), (1.0, 1.0), (0.0, 1.0)],
+            CellShape::BottomLeft => &[(0.0, 0.0), (1.0, 1.0), (0.0, 1.0)],
+        }
+    }
+
+    /// Returns the cell shape after rotating it in the same direction as
+    /// [Shape::rotate_counterclockwise](crate::Shape::rotate_counterclockwise).
+    pub const fn rotated(&self) -> CellShape {
+        match self {
+            CellShape::Full => CellShape::Full,
+            CellShape::TopLeft => CellShape::TopRight,
+            CellShape::TopRight => CellShape::BottomRight,
+            CellShape::BottomRight => CellShape::BottomLeft,
+            CellShape::BottomLeft => CellShape::TopLeft,
+        }
+    }
+
+    /// Returns the cell shape after flipping it in the same direction as
+    /// [Shape::flip_default](crate::Shape::flip_default).
+    pub const fn flipped(&self) -> CellShape {
+        match self {
+            CellShape::Full => CellShape::Full,
+            CellShape::TopLeft => CellShape::TopRight,
+            CellShape::TopRight => CellShape::TopLeft,
+            CellShape::BottomRight => CellShape::BottomLeft,
+            CellShape::BottomLeft => CellShape::BottomRight,
+        }
+    }
+
+    /// Returns the cell shape after transposing it like [Shape::transpose](crate::Shape::transpose).
+    pub const fn transposed(&self) -> CellShape {
+        match self {
+            CellShape::Full => CellShape::Full,
+            CellShape::TopLeft => CellShape::TopLeft,
+            CellShape::TopRight => CellShape::BottomLeft,
+            CellShape::BottomRight => CellShape::BottomRight,
+            CellShape::BottomLeft => CellShape::TopRight,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [CellShape; 5] = [
+        CellShape::Full,
+        CellShape::TopLeft,
+        CellShape::TopRight,
+        CellShape::BottomRight,
+        CellShape::BottomLeft,
+    ];
+
+    #[test]
+    fn test_quarters_round_trip() {
+        for cell_shape in ALL {
+            assert_eq!(
+                CellShape::from_quarters(cell_shape.quarters()),
+                Some(cell_shape)
+            );
+        }
+    }
+
+    #[test]
+    fn test_complement() {
+        for cell_shape in ALL.iter().filter(|c| c.is_half()) {
+            assert_eq!(
+                cell_shape.quarters() | cell_shape.complement().quarters(),
+                QUARTERS_ALL
+            );
+            assert_eq!(
+                cell_shape.quarters() & cell_shape.complement().quarters(),
+                0
+            );
+        }
+    }
+
+    #[test]
+    fn test_rotated_four_times() {
+        for cell_shape in ALL {
+            assert_eq!(
+                cell_shape.rotated().rotated().rotated().rotated(),
+                cell_shape
+            );
+            assert_ne!(cell_shape.is_half(), cell_shape.rotated() == cell_shape);
+        }
+    }
+
+    #[test]
+    fn test_flipped_and_transposed_twice() {
+        for cell_shape in ALL {
+            assert_eq!(cell_shape.flipped().flipped(), cell_shape);
+            assert_eq!(cell_shape.transposed().transposed(), cell_shape);
+        }
+    }
+}
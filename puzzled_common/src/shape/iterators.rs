@@ -18,6 +18,40 @@ impl Shape {
     pub fn rotations_flips_iter(&self) -> ShapeRotationIterator {
         ShapeRotationIterator::new(self.clone())
     }
+
+    /// Returns all distinct rotations and flips of the shape, starting with the shape itself.
+    pub fn unique_rotations_flips(&self) -> Vec<Shape> {
+        let mut unique: Vec<Shape> = Vec::with_capacity(8);
+        for rotation in self.rotations_flips_iter() {
+            if !unique.contains(&rotation) {
+                unique.push(rotation);
+            }
+        }
+        unique
+    }
+
+    /// Returns the canonical form of the shape.
+    ///
+    /// Two shapes have the same canonical form, if one can be rotated and flipped into the other.
+    /// Rows and columns without any `true` cells are ignored.
+    pub fn canonical(&self) -> Shape {
+        let mut trimmed = self.clone();
+        trimmed.trim_matching(false);
+        trimmed
+            .unique_rotations_flips()
+            .into_iter()
+            .min_by_key(Shape::canonical_order_key)
+            .expect("A shape has at least one rotation")
+    }
+
+    /// Key to order the rotations of a shape to choose the canonical form.
+    fn canonical_order_key(&self) -> ((usize, usize), Vec<u8>) {
+        let quarters = self
+            .indexed_iter()
+            .map(|(index, _)| self.quarters(index))
+            .collect();
+        (self.dim(), quarters)
+    }
 }
 
 pub struct ShapeRotationIterator {
@@ -95,4 +129,29 @@ mod test {
         );
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn test_unique_rotations_flips() {
+        let base = shape_square(&[[true, true], [true, false]]);
+
+        let rotations = base.unique_rotations_flips();
+
+        assert_eq!(rotations.len(), 4);
+        assert_eq!(rotations[0], base);
+    }
+
+    #[test]
+    fn test_canonical() {
+        let l = shape_square(&[[true, false], [true, false], [true, true]]);
+        let mirrored_l = shape_square(&[
+            [false, false, false],
+            [true, true, true],
+            [true, false, false],
+        ]);
+        let t = shape_square(&[[true, true, true], [false, true, false]]);
+
+        assert_eq!(l.canonical(), mirrored_l.canonical());
+        assert_ne!(l.canonical(), t.canonical());
+        assert_eq!(l.canonical().dim(), (2, 3));
+    }
 }
@@ -1,5 +1,7 @@
+pub mod cell;
 mod iterators;
 
+use crate::shape::cell::{CellShape, QUARTERS_ALL};
 use crate::ShapeType::*;
 use ndarray::{arr2, s, Array2, Axis};
 use std::fmt::{Display, Formatter};
@@ -9,6 +11,12 @@ use std::ops::{Index, IndexMut};
 pub struct Shape {
     shape_type: ShapeType,
     data: Array2<bool>,
+    /// The shapes of the cells, if any of them is not a full cell.
+    /// The cell shape describes the part of the cell, which has the value in `data`.
+    /// The rest of the cell has the opposite value.
+    /// Half cells are always stored with the value `true`, so that equal shapes are stored
+    /// equally.
+    cells: Option<Array2<CellShape>>,
 }
 
 impl Shape {
@@ -38,7 +46,11 @@ impl Shape {
     /// assert_eq!(shape.get((1, 1)), Some(&true));
     /// ```
     pub fn new(shape_type: ShapeType, data: Array2<bool>) -> Self {
-        Self { shape_type, data }
+        Self {
+            shape_type,
+            data,
+            cells: None,
+        }
     }
 
     /// Creates a new `Shape` instance with the specified dimensions, shape type, and initial value
@@ -69,6 +81,7 @@ impl Shape {
         Self {
             shape_type,
             data: Array2::from_elem((x, y), value),
+            cells: None,
         }
     }
 
@@ -89,6 +102,137 @@ impl Shape {
         self.shape_type
     }
 
+    /// Sets the shapes of the cells of this shape.
+    /// Only square shapes support cells, which are not full.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the dimensions of `cell_shapes` differ from the dimensions of the shape.
+    ///
+    /// # Arguments
+    ///
+    /// * `cell_shapes`: the shape of each cell
+    ///
+    /// returns: Shape
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ndarray::arr2;
+    /// use puzzled_common::shape::cell::CellShape;
+    /// use puzzled_common::shape::shape_square;
+    ///
+    /// let shape = shape_square(&[[true, true]])
+    ///     .with_cell_shapes(arr2(&[[CellShape::Full, CellShape::TopLeft]]));
+    ///
+    /// assert!(shape.has_half_cells());
+    /// assert_eq!(shape.cell_shape((0, 1)), CellShape::TopLeft);
+    /// ```
+    pub fn with_cell_shapes(mut self, cell_shapes: Array2<CellShape>) -> Self {
+        assert_eq!(
+            self.data.dim(),
+            cell_shapes.dim(),
+            "Cell shapes must have the same dimensions as the shape"
+        );
+        self.cells = Some(cell_shapes);
+        self.normalize_cells();
+        self
+    }
+
+    /// Returns the shape of the cell at the given position.
+    /// Cells are full, unless specified otherwise via [Shape::with_cell_shapes] or
+    /// [Shape::set_cell_shape].
+    pub fn cell_shape(&self, index: (usize, usize)) -> CellShape {
+        self.cells
+            .as_ref()
+            .and_then(|cells| cells.get(index))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Sets the shape of the cell at the given position.
+    pub fn set_cell_shape(&mut self, index: (usize, usize), cell_shape: CellShape) {
+        let cells = self
+            .cells
+            .get_or_insert_with(|| Array2::default(self.data.dim()));
+        cells[index] = cell_shape;
+        self.normalize_cells();
+    }
+
+    /// Returns true, if any cell of the shape is only a half cell.
+    pub fn has_half_cells(&self) -> bool {
+        self.cells.is_some()
+    }
+
+    /// Returns the quarters of the cell at the given position, which are `true`.
+    /// See [CellShape::quarters] for the meaning of the bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use puzzled_common::shape::cell::{CellShape, QUARTERS_ALL};
+    /// use puzzled_common::shape::shape_square;
+    ///
+    /// let mut shape = shape_square(&[[true, false]]);
+    /// shape.set_cell_shape((0, 1), CellShape::TopLeft);
+    ///
+    /// assert_eq!(shape.quarters((0, 0)), QUARTERS_ALL);
+    /// assert_eq!(shape.quarters((0, 1)), CellShape::BottomRight.quarters());
+    /// ```
+    pub fn quarters(&self, index: (usize, usize)) -> u8 {
+        let cell_quarters = self.cell_shape(index).quarters();
+        if self.data[index] {
+            cell_quarters
+        } else {
+            QUARTERS_ALL & !cell_quarters
+        }
+    }
+
+    /// Sets the value and shape of the cell at the given position, so that exactly the given
+    /// quarters are `true`.
+    /// Quarters, which cannot be represented by a cell shape, are extended to the full cell.
+    pub fn set_quarters(&mut self, index: (usize, usize), quarters: u8) {
+        let (value, cell_shape) = match quarters & QUARTERS_ALL {
+            0 => (false, CellShape::Full),
+            quarters => (
+                true,
+                CellShape::from_quarters(quarters).unwrap_or(CellShape::Full),
+            ),
+        };
+        self.data[index] = value;
+        if cell_shape.is_half() || self.cells.is_some() {
+            self.set_cell_shape(index, cell_shape);
+        }
+    }
+
+    /// Stores half cells with the value `true` and drops the cell shapes, if all cells are full.
+    /// This keeps shapes without half cells equal to shapes, which never had cell shapes.
+    fn normalize_cells(&mut self) {
+        if let Some(cells) = &mut self.cells {
+            for (value, cell) in self.data.iter_mut().zip(cells.iter_mut()) {
+                if !*value && cell.is_half() {
+                    *value = true;
+                    *cell = cell.complement();
+                }
+            }
+            if cells.iter().all(|cell| !cell.is_half()) {
+                self.cells = None;
+            }
+        }
+    }
+
+    /// Applies a transformation of the data to the cell shapes and maps each cell shape.
+    fn transform_cells<F, M>(&mut self, transform: F, map: M)
+    where
+        F: FnOnce(&mut Array2<CellShape>),
+        M: Fn(&CellShape) -> CellShape,
+    {
+        if let Some(cells) = &mut self.cells {
+            transform(cells);
+            cells.map_inplace(|cell| *cell = map(cell));
+        }
+    }
+
     /// Returns the dimensions of this shape
     ///
     /// # Examples
@@ -185,10 +329,13 @@ impl Shape {
     where
         F: FnMut(&bool) -> bool,
     {
-        Shape {
+        let mut shape = Shape {
             shape_type: self.shape_type,
             data: self.data.map(f),
-        }
+            cells: self.cells.clone(),
+        };
+        shape.normalize_cells();
+        shape
     }
 
     /// Fills the shape with the given value.
@@ -214,6 +361,7 @@ impl Shape {
     /// ```
     pub fn fill(&mut self, value: bool) {
         self.data.fill(value);
+        self.cells = None;
     }
 
     /// Rotates the shape counterclockwise.
@@ -238,6 +386,13 @@ impl Shape {
             Square => {
                 self.data.reverse_axes();
                 self.data.invert_axis(Axis(0));
+                self.transform_cells(
+                    |cells| {
+                        cells.reverse_axes();
+                        cells.invert_axis(Axis(0));
+                    },
+                    CellShape::rotated,
+                );
             }
             Triangle => {
                 todo!()
@@ -265,6 +420,7 @@ impl Shape {
         let dim = self.dim();
         if dim.0 < dim.1 {
             self.data.reverse_axes();
+            self.transform_cells(|cells| cells.reverse_axes(), CellShape::transposed);
         }
     }
 
@@ -286,6 +442,7 @@ impl Shape {
         match self.shape_type {
             Square => {
                 self.data.invert_axis(Axis(0));
+                self.transform_cells(|cells| cells.invert_axis(Axis(0)), CellShape::flipped);
             }
             Triangle => {
                 todo!()
@@ -314,6 +471,7 @@ impl Shape {
         match self.shape_type {
             Square => {
                 self.data.reverse_axes();
+                self.transform_cells(|cells| cells.reverse_axes(), CellShape::transposed);
             }
             Triangle => {
                 todo!()
@@ -325,10 +483,10 @@ impl Shape {
     }
 
     pub fn transposed(&self) -> Self {
-        Self {
-            shape_type: self.shape_type,
-            data: self.data.clone().reversed_axes(),
-        }
+        let mut transposed = self.clone();
+        transposed.data.reverse_axes();
+        transposed.transform_cells(|cells| cells.reverse_axes(), CellShape::transposed);
+        transposed
     }
 
     /// Removes rows and columns from the sides of a 2D boolean array where all cells are matching`
@@ -340,42 +498,55 @@ impl Shape {
     ///
     /// returns: ()
     pub fn trim_matching(&mut self, to_trim: bool) -> TrimSides {
+        let (original_rows, original_cols) = self.data.dim();
         let mut trim_sides = TrimSides::default();
         loop {
             if self.data.nrows() == 0 || self.data.ncols() == 0 {
                 break;
             }
-
-            let left_col_all_true = self.data.column(0).iter().all(|&cell| cell == to_trim);
+            let cells = self.cells.as_ref().map(|cells| {
+                cells.slice(s![
+                    trim_sides.lower_x..original_rows - trim_sides.upper_x,
+                    trim_sides.lower_y..original_cols - trim_sides.upper_y
+                ])
+            });
+            let line_matches = |axis: Axis, index: usize| {
+                let values_match = self
+                    .data
+                    .index_axis(axis, index)
+                    .iter()
+                    .all(|&cell| cell == to_trim);
+                let cells_full = cells.as_ref().is_none_or(|cells| {
+                    cells
+                        .index_axis(axis, index)
+                        .iter()
+                        .all(|cell| !cell.is_half())
+                });
+                values_match && cells_full
+            };
+
+            let left_col_all_true = line_matches(Axis(1), 0);
             if left_col_all_true {
                 self.data = self.data.slice(s![.., 1..]).to_owned();
                 trim_sides.lower_y += 1;
                 continue;
             }
 
-            let right_col_all_true = self
-                .data
-                .column(self.data.ncols() - 1)
-                .iter()
-                .all(|&cell| cell == to_trim);
+            let right_col_all_true = line_matches(Axis(1), self.data.ncols() - 1);
             if right_col_all_true {
                 self.data = self.data.slice(s![.., ..self.data.ncols() - 1]).to_owned();
                 trim_sides.upper_y += 1;
                 continue;
             }
 
-            let top_row_all_true = self.data.row(0).iter().all(|&cell| cell == to_trim);
+            let top_row_all_true = line_matches(Axis(0), 0);
             if top_row_all_true {
                 self.data = self.data.slice(s![1.., ..]).to_owned();
                 trim_sides.lower_x += 1;
                 continue;
             }
 
-            let bottom_row_all_true = self
-                .data
-                .row(self.data.nrows() - 1)
-                .iter()
-                .all(|&cell| cell == to_trim);
+            let bottom_row_all_true = line_matches(Axis(0), self.data.nrows() - 1);
             if bottom_row_all_true {
                 self.data = self.data.slice(s![..self.data.nrows() - 1, ..]).to_owned();
                 trim_sides.upper_x += 1;
@@ -384,6 +555,17 @@ impl Shape {
 
             break;
         }
+        if let Some(cells) = &self.cells {
+            self.cells = Some(
+                cells
+                    .slice(s![
+                        trim_sides.lower_x..original_rows - trim_sides.upper_x,
+                        trim_sides.lower_y..original_cols - trim_sides.upper_y
+                    ])
+                    .to_owned(),
+            );
+            self.normalize_cells();
+        }
         trim_sides
     }
 
@@ -432,6 +614,7 @@ impl Shape {
     /// operation.
     /// This means that if either the parent or child cell is `true`, the resulting cell will be
     /// `true`.
+    /// For half cells, the OR operation is done on the quarters of the cells.
     ///
     /// # Arguments
     ///
@@ -454,7 +637,8 @@ impl Shape {
                     && parent_y >= 0
                     && parent_y < self.data.ncols() as isize
                 {
-                    new_array[(parent_x as usize, parent_y as usize)] |= child[(x, y)];
+                    let index = (parent_x as usize, parent_y as usize);
+                    new_array.set_quarters(index, self.quarters(index) | child.quarters((x, y)));
                 }
             }
         }
@@ -464,6 +648,8 @@ impl Shape {
 
     /// Generates all possible placements of the `child` array onto self using a logical OR
     /// operation.
+    /// Placements, in which a `true` quarter of the child is placed on a `true` quarter of self,
+    /// are skipped.
     ///
     /// # Arguments
     ///
@@ -487,11 +673,16 @@ impl Shape {
                 let mut valid = true;
                 for r in 0..child_rows {
                     for c in 0..child_cols {
-                        if child[(r, c)] && self[(row_offset + r, col_offset + c)] {
+                        let index = (row_offset + r, col_offset + c);
+                        let child_quarters = child.quarters((r, c));
+                        let parent_quarters = self.quarters(index);
+                        if child_quarters & parent_quarters != 0 {
                             valid = false;
                             break;
                         }
-                        new_array[(row_offset + r, col_offset + c)] |= child[(r, c)];
+                        if child_quarters != 0 {
+                            new_array.set_quarters(index, parent_quarters | child_quarters);
+                        }
                     }
                     if !valid {
                         break;
@@ -507,6 +698,7 @@ impl Shape {
     }
 
     /// Removes the `true` values from `self` wherever parent has `true` values.
+    /// For half cells, only the `true` quarters of the parent are removed.
     ///
     /// # Arguments
     ///
@@ -516,8 +708,10 @@ impl Shape {
     pub fn remove_parent(&mut self, parent: &Shape) {
         for row in 0..parent.data.nrows() {
             for col in 0..parent.data.ncols() {
-                if parent[(row, col)] {
-                    self[(row, col)] = false;
+                let parent_quarters = parent.quarters((row, col));
+                if parent_quarters != 0 {
+                    let quarters = self.quarters((row, col));
+                    self.set_quarters((row, col), quarters & !parent_quarters);
                 }
             }
         }
@@ -1099,4 +1293,65 @@ mod test {
         assert_eq!(count_true, 4);
         assert_eq!(count_false, 1);
     }
+
+    #[test]
+    fn test_rotate_counterclockwise_half_cells() {
+        let mut shape = shape_square(&[[true, true]])
+            .with_cell_shapes(arr2(&[[CellShape::TopLeft, CellShape::Full]]));
+        shape.rotate_counterclockwise();
+        let expected = shape_square(&[[true], [true]])
+            .with_cell_shapes(arr2(&[[CellShape::Full], [CellShape::TopRight]]));
+        assert_eq!(expected, shape);
+    }
+
+    #[test]
+    fn test_rotations_flips_half_cell() {
+        let shape = shape_square(&[[true]]).with_cell_shapes(arr2(&[[CellShape::TopLeft]]));
+        let distinct: std::collections::HashSet<Shape> = shape.rotations_flips_iter().collect();
+        assert_eq!(distinct.len(), 4);
+    }
+
+    #[test]
+    fn test_with_cell_shapes_all_full() {
+        let shape = shape_square(&[[true, false]])
+            .with_cell_shapes(arr2(&[[CellShape::Full, CellShape::Full]]));
+        assert!(!shape.has_half_cells());
+        assert_eq!(shape, shape_square(&[[true, false]]));
+    }
+
+    #[test]
+    fn test_trim_keeps_half_cells() {
+        let mut shape = shape_square(&[[true, true, true], [true, true, false]])
+            .with_cell_shapes(arr2(&[
+                [CellShape::Full, CellShape::Full, CellShape::Full],
+                [CellShape::Full, CellShape::TopLeft, CellShape::Full],
+            ]));
+        shape.trim_matching(true);
+        let expected = shape_square(&[[true, false]])
+            .with_cell_shapes(arr2(&[[CellShape::TopLeft, CellShape::Full]]));
+        assert_eq!(expected, shape);
+    }
+
+    #[test]
+    fn test_place_on_all_positions_half_cells() {
+        let mut parent = shape_square(&[[false, false]]);
+        parent.set_cell_shape((0, 0), CellShape::TopLeft);
+        let child = shape_square(&[[true]]).with_cell_shapes(arr2(&[[CellShape::TopLeft]]));
+
+        let placements = parent.place_on_all_positions(&child);
+
+        assert_eq!(placements.len(), 2);
+        assert_eq!(placements[0].quarters((0, 0)), QUARTERS_ALL);
+        assert_eq!(
+            placements[1].quarters((0, 1)),
+            CellShape::TopLeft.quarters()
+        );
+
+        let mut placement = placements[0].clone();
+        placement.remove_parent(&parent);
+        assert_eq!(
+            placement.quarters((0, 0)),
+            CellShape::TopLeft.quarters()
+        );
+    }
 }